@@ -1,4 +1,5 @@
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use crate::net_utils::normalize_port;
@@ -21,6 +22,33 @@ pub struct InstanceConfig {
     pub shares_per_min: Option<u32>,
     pub var_diff_stats: Option<bool>,
     pub pow2_clamp: Option<bool>,
+    // Optional TLS listener and miner authorization policies
+    pub tls: Option<TlsConfig>,
+    pub auth: Option<AuthConfig>,
+}
+
+/// TLS settings of a stratum instance
+///
+/// The certificate and key files are watched for modification and reloaded without
+/// restarting the listener, so certificates can be renewed in place.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TlsConfig {
+    /// PEM file containing the certificate chain (leaf first)
+    pub cert_path: PathBuf,
+    /// PEM file containing the private key (PKCS#8, PKCS#1 or SEC1)
+    pub key_path: PathBuf,
+}
+
+/// Miner authorization policies of a stratum instance (all optional)
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    /// If non-empty, only these payout addresses may authorize
+    pub allowed_addresses: Vec<String>,
+    /// File with one `worker:password` entry per line; workers not listed are rejected
+    pub worker_passwords_file: Option<PathBuf>,
+    /// Maximum number of concurrent connections accepted from a single IP address
+    pub max_connections_per_ip: Option<u32>,
 }
 
 /// Global configuration (shared across all instances)
@@ -216,6 +244,8 @@ impl Default for InstanceConfig {
             shares_per_min: None,
             var_diff_stats: None,
             pow2_clamp: None,
+            tls: None,
+            auth: None,
         }
     }
 }
@@ -272,6 +302,23 @@ impl BridgeConfig {
             }
         }
 
+        // Validate: TLS and authorization settings
+        for instance in &instances {
+            if let Some(tls) = &instance.tls {
+                if tls.cert_path.as_os_str().is_empty() || tls.key_path.as_os_str().is_empty() {
+                    return Err(anyhow::anyhow!("Instance {} tls requires both 'cert_path' and 'key_path'", instance.stratum_port));
+                }
+            }
+            if let Some(auth) = &instance.auth {
+                if auth.max_connections_per_ip == Some(0) {
+                    return Err(anyhow::anyhow!(
+                        "Instance {} max_connections_per_ip must be greater than zero",
+                        instance.stratum_port
+                    ));
+                }
+            }
+        }
+
         Ok(BridgeConfig { global: raw.global, instances })
     }
 
@@ -21,6 +21,11 @@ pub(crate) fn get_bridge_logs_dir() -> PathBuf {
     get_bridge_app_dir().join("logs")
 }
 
+/// File holding the found block journal; testnet blocks are kept apart from mainnet ones
+pub(crate) fn get_found_blocks_journal_path(testnet: bool) -> PathBuf {
+    get_bridge_app_dir().join(if testnet { "found_blocks-testnet.json" } else { "found_blocks.json" })
+}
+
 pub(crate) fn default_inprocess_kaspad_appdir() -> PathBuf {
     get_bridge_app_dir().join("kaspad")
 }
@@ -0,0 +1,482 @@
+use crate::prom::WorkerContext;
+use kaspa_addresses::Address;
+use kaspa_consensus_core::tx::ScriptPublicKey;
+use kaspa_txscript::pay_to_address_script;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, mpsc};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, warn};
+
+/// Maximum number of found blocks kept in the journal; the oldest entries are dropped first
+pub const MAX_FOUND_BLOCK_RECORDS: usize = 1000;
+
+/// Changes made within this delay of a first change are saved by a single write
+const JOURNAL_WRITE_DELAY: Duration = Duration::from_millis(500);
+
+/// Lifecycle stage of a block found by one of the bridge's workers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FoundBlockStatus {
+    /// Journaled, the node's answer is not applied yet
+    Submitted,
+    /// Refused by the node
+    Rejected,
+    /// Accepted by the node but not yet merged by a selected chain block
+    Accepted,
+    /// Merged as a blue block; its reward is paid by the merging chain block
+    Blue,
+    /// Merged as a red block; it earns no reward
+    Red,
+    /// Blue and the merging chain block's coinbase has reached maturity
+    Matured,
+}
+
+impl FoundBlockStatus {
+    pub const ALL: [FoundBlockStatus; 6] = [Self::Submitted, Self::Rejected, Self::Accepted, Self::Blue, Self::Red, Self::Matured];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Submitted => "submitted",
+            Self::Rejected => "rejected",
+            Self::Accepted => "accepted",
+            Self::Blue => "blue",
+            Self::Red => "red",
+            Self::Matured => "matured",
+        }
+    }
+
+    /// Whether the block still waits to be merged by the selected chain
+    pub fn is_unmerged(&self) -> bool {
+        matches!(self, Self::Submitted | Self::Accepted)
+    }
+}
+
+/// One journal entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FoundBlockRecord {
+    pub hash: String,
+    pub instance: String,
+    pub worker: String,
+    pub wallet: String,
+    pub nonce: u64,
+    pub blue_score: u64,
+    /// DAA score of the block, from which an unmerged block expires
+    #[serde(default)]
+    pub daa_score: Option<u64>,
+    /// Unix seconds of the submission
+    pub submitted_at: u64,
+    /// Unix seconds of the last status change
+    pub updated_at: u64,
+    pub status: FoundBlockStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejection: Option<String>,
+    /// Whether the block itself is on the selected parent chain, once known
+    #[serde(default)]
+    pub is_chain_block: Option<bool>,
+    /// Selected chain block whose mergeset contains this block
+    #[serde(default)]
+    pub merged_by: Option<String>,
+    #[serde(default)]
+    pub merged_daa_score: Option<u64>,
+    /// Reward paid to the worker's wallet for this block, in sompi.
+    /// `None` while unmerged, or when the coinbase output could not be matched.
+    #[serde(default)]
+    pub reward_sompi: Option<u64>,
+}
+
+/// A selected chain block added to the virtual chain, with what it merged
+#[derive(Debug, Clone, Default)]
+pub struct ChainBlockMerge {
+    pub hash: String,
+    pub daa_score: u64,
+    pub merge_set_blues: Vec<String>,
+    pub merge_set_reds: Vec<String>,
+    /// Outputs of the chain block's coinbase transaction, in order
+    pub coinbase_outputs: Vec<(ScriptPublicKey, u64)>,
+}
+
+/// Found blocks and rewards of one worker
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct WorkerRewardSummary {
+    pub instance: String,
+    pub worker: String,
+    pub wallet: String,
+    pub blocks: HashMap<FoundBlockStatus, u64>,
+    /// Rewards of blue blocks whose coinbase is not mature yet, in sompi
+    pub pending_reward_sompi: u64,
+    pub matured_reward_sompi: u64,
+}
+
+/// Response of `GET /api/blocks`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FoundBlocksSnapshot {
+    /// Journal entries, newest first
+    pub blocks: Vec<FoundBlockRecord>,
+    pub workers: Vec<WorkerRewardSummary>,
+}
+
+/// Persistent record of the blocks found through the bridge
+///
+/// Each submission is followed from the node's answer through its blue/red merge by the
+/// selected chain up to the maturity of the reward. Changes are saved to `path` by a
+/// background writer, off the submission and notification paths, so the journal survives
+/// restarts.
+pub struct FoundBlockJournal {
+    path: Option<PathBuf>,
+    records: Arc<Mutex<VecDeque<FoundBlockRecord>>>,
+    /// Wakes the background writer; `None` for an in-memory journal
+    writer: Option<mpsc::Sender<()>>,
+    /// Serializes the background writes with [`Self::flush`]
+    file_lock: Arc<Mutex<()>>,
+}
+
+impl FoundBlockJournal {
+    /// A journal that is not persisted
+    pub fn in_memory() -> Self {
+        Self { path: None, records: Arc::new(Mutex::new(VecDeque::new())), writer: None, file_lock: Arc::new(Mutex::new(())) }
+    }
+
+    /// Open the journal stored at `path`, starting empty if it is missing or unreadable
+    pub fn open(path: PathBuf) -> Self {
+        let records = match std::fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str::<VecDeque<FoundBlockRecord>>(&content) {
+                Ok(records) => records,
+                Err(e) => {
+                    warn!("ignoring corrupt found block journal {}: {}", path.display(), e);
+                    VecDeque::new()
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => VecDeque::new(),
+            Err(e) => {
+                warn!("failed to read found block journal {}: {}", path.display(), e);
+                VecDeque::new()
+            }
+        };
+        let records = Arc::new(Mutex::new(records));
+        let file_lock = Arc::new(Mutex::new(()));
+        let (writer, wake) = mpsc::channel();
+        let (writer_path, writer_records, writer_file_lock) = (path.clone(), Arc::clone(&records), Arc::clone(&file_lock));
+        if let Err(e) = std::thread::Builder::new()
+            .name("found-block-journal".to_string())
+            .spawn(move || run_journal_writer(&writer_path, &writer_records, &writer_file_lock, wake))
+        {
+            warn!("failed to start the found block journal writer, changes are saved on flush only: {}", e);
+        }
+        Self { path: Some(path), records, writer: Some(writer), file_lock }
+    }
+
+    /// Save the journal right away, e.g. on shutdown
+    pub fn flush(&self) {
+        if let Some(path) = &self.path {
+            save_records(path, &self.records, &self.file_lock);
+        }
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.lock().is_empty()
+    }
+
+    pub fn get(&self, hash: &str) -> Option<FoundBlockRecord> {
+        self.records.lock().iter().find(|record| record.hash == hash).cloned()
+    }
+
+    /// Record a block once the node answered its submission
+    pub fn record_submitted(&self, hash: &str, worker: &WorkerContext, nonce: u64, blue_score: u64, daa_score: u64) {
+        let now = unix_now();
+        let mut records = self.records.lock();
+        if records.iter().any(|record| record.hash == hash) {
+            return;
+        }
+        records.push_back(FoundBlockRecord {
+            hash: hash.to_string(),
+            instance: worker.instance_id.clone(),
+            worker: worker.worker_name.clone(),
+            wallet: worker.wallet.clone(),
+            nonce,
+            blue_score,
+            daa_score: Some(daa_score),
+            submitted_at: now,
+            updated_at: now,
+            status: FoundBlockStatus::Submitted,
+            rejection: None,
+            is_chain_block: None,
+            merged_by: None,
+            merged_daa_score: None,
+            reward_sompi: None,
+        });
+        while records.len() > MAX_FOUND_BLOCK_RECORDS {
+            records.pop_front();
+        }
+        self.commit(&records);
+    }
+
+    pub fn mark_accepted(&self, hash: &str) {
+        self.update(hash, |record| {
+            // A virtual chain notification may already have merged the block
+            if record.status != FoundBlockStatus::Submitted {
+                return false;
+            }
+            record.status = FoundBlockStatus::Accepted;
+            true
+        });
+    }
+
+    pub fn mark_rejected(&self, hash: &str, reason: impl Into<String>) {
+        let reason = reason.into();
+        self.update(hash, |record| {
+            record.status = FoundBlockStatus::Rejected;
+            record.rejection = Some(reason);
+            true
+        });
+    }
+
+    /// Whether any block still waits for a merging chain block
+    pub fn has_unmerged(&self) -> bool {
+        self.records.lock().iter().any(|record| record.status.is_unmerged())
+    }
+
+    /// Whether any blue block waits for its reward to mature
+    pub fn has_immature(&self) -> bool {
+        self.records.lock().iter().any(|record| record.status == FoundBlockStatus::Blue)
+    }
+
+    /// Apply a chain block added to the virtual selected chain
+    ///
+    /// Blues are matched to coinbase outputs by position, which holds as long as no blue of the
+    /// mergeset was skipped for having no reward. The output is only credited when it pays the
+    /// worker's wallet, otherwise the reward is left unknown.
+    pub fn apply_chain_block_added(&self, chain_block: &ChainBlockMerge) -> bool {
+        let mut records = self.records.lock();
+        let now = unix_now();
+        let mut changed = false;
+        for record in records.iter_mut() {
+            if record.hash == chain_block.hash && record.is_chain_block != Some(true) {
+                record.is_chain_block = Some(true);
+                record.updated_at = now;
+                changed = true;
+            }
+            if !record.status.is_unmerged() {
+                continue;
+            }
+            if let Some(index) = chain_block.merge_set_blues.iter().position(|hash| *hash == record.hash) {
+                record.status = FoundBlockStatus::Blue;
+                record.reward_sompi = blue_reward(record, chain_block, index);
+            } else if chain_block.merge_set_reds.contains(&record.hash) {
+                record.status = FoundBlockStatus::Red;
+                record.reward_sompi = Some(0);
+            } else {
+                continue;
+            }
+            record.merged_by = Some(chain_block.hash.clone());
+            record.merged_daa_score = Some(chain_block.daa_score);
+            // A chain block is announced before the chain block merging it
+            if record.is_chain_block.is_none() {
+                record.is_chain_block = Some(false);
+            }
+            record.updated_at = now;
+            changed = true;
+        }
+        if changed {
+            self.commit(&records);
+        }
+        changed
+    }
+
+    /// Undo the effects of a chain block removed from the virtual selected chain by a reorg
+    pub fn apply_chain_block_removed(&self, chain_hash: &str) -> bool {
+        let mut records = self.records.lock();
+        let now = unix_now();
+        let mut changed = false;
+        for record in records.iter_mut() {
+            if record.hash == chain_hash && record.is_chain_block == Some(true) {
+                record.is_chain_block = Some(false);
+                record.updated_at = now;
+                changed = true;
+            }
+            if record.merged_by.as_deref() == Some(chain_hash) {
+                record.status = FoundBlockStatus::Accepted;
+                record.merged_by = None;
+                record.merged_daa_score = None;
+                record.reward_sompi = None;
+                record.updated_at = now;
+                changed = true;
+            }
+        }
+        if changed {
+            self.commit(&records);
+        }
+        changed
+    }
+
+    /// Promote blue blocks whose merging coinbase reached `coinbase_maturity` DAA scores
+    pub fn update_maturity(&self, virtual_daa_score: u64, coinbase_maturity: u64) -> bool {
+        let mut records = self.records.lock();
+        let now = unix_now();
+        let mut changed = false;
+        for record in records.iter_mut().filter(|record| record.status == FoundBlockStatus::Blue) {
+            if record.merged_daa_score.is_some_and(|score| virtual_daa_score >= score.saturating_add(coinbase_maturity)) {
+                record.status = FoundBlockStatus::Matured;
+                record.updated_at = now;
+                changed = true;
+            }
+        }
+        if changed {
+            self.commit(&records);
+        }
+        changed
+    }
+
+    /// Reject blocks still unmerged `merge_depth` DAA scores past their own, which no chain block can merge anymore
+    ///
+    /// This also ends the chain block fetches of blocks whose answer was lost, or that the node accepted but never merged.
+    pub fn expire_unmerged(&self, virtual_daa_score: u64, merge_depth: u64) -> bool {
+        let mut records = self.records.lock();
+        let now = unix_now();
+        let mut changed = false;
+        for record in records.iter_mut().filter(|record| record.status.is_unmerged()) {
+            // Records journaled without a DAA score start their window now
+            let daa_score = *record.daa_score.get_or_insert(virtual_daa_score);
+            if virtual_daa_score >= daa_score.saturating_add(merge_depth) {
+                record.status = FoundBlockStatus::Rejected;
+                record.rejection =
+                    Some(format!("not merged by the selected chain within the merge depth of {} DAA scores", merge_depth));
+                record.updated_at = now;
+                changed = true;
+            }
+        }
+        if changed {
+            self.commit(&records);
+        }
+        changed
+    }
+
+    /// Journal entries and per-worker totals, optionally restricted to one instance
+    pub fn snapshot(&self, instance: Option<&str>) -> FoundBlocksSnapshot {
+        let records = self.records.lock();
+        let blocks = records.iter().rev().filter(|record| instance.is_none_or(|id| record.instance == id)).cloned().collect();
+        let workers = summarize(records.iter().filter(|record| instance.is_none_or(|id| record.instance == id)));
+        FoundBlocksSnapshot { blocks, workers }
+    }
+
+    pub fn worker_summaries(&self) -> Vec<WorkerRewardSummary> {
+        summarize(self.records.lock().iter())
+    }
+
+    /// Export the current per-worker totals to prometheus
+    pub fn publish_metrics(&self) {
+        crate::prom::record_found_block_summaries(&self.worker_summaries());
+    }
+
+    fn update(&self, hash: &str, f: impl FnOnce(&mut FoundBlockRecord) -> bool) {
+        let mut records = self.records.lock();
+        let Some(record) = records.iter_mut().find(|record| record.hash == hash) else {
+            return;
+        };
+        if f(record) {
+            record.updated_at = unix_now();
+            self.commit(&records);
+        }
+    }
+
+    fn commit(&self, records: &VecDeque<FoundBlockRecord>) {
+        crate::prom::record_found_block_summaries(&summarize(records.iter()));
+        if let Some(writer) = &self.writer {
+            // The writer only stops once the journal is dropped
+            let _ = writer.send(());
+        }
+    }
+}
+
+fn blue_reward(record: &FoundBlockRecord, chain_block: &ChainBlockMerge, index: usize) -> Option<u64> {
+    let (script, value) = chain_block.coinbase_outputs.get(index)?;
+    let expected = Address::try_from(record.wallet.as_str()).ok().map(|address| pay_to_address_script(&address))?;
+    if *script == expected {
+        Some(*value)
+    } else {
+        debug!("coinbase output {} of {} does not pay the wallet of block {}", index, chain_block.hash, record.hash);
+        None
+    }
+}
+
+fn summarize<'a>(records: impl Iterator<Item = &'a FoundBlockRecord>) -> Vec<WorkerRewardSummary> {
+    let mut summaries: Vec<WorkerRewardSummary> = Vec::new();
+    for record in records {
+        let position = summaries
+            .iter()
+            .position(|s| s.instance == record.instance && s.worker == record.worker && s.wallet == record.wallet)
+            .unwrap_or_else(|| {
+                summaries.push(WorkerRewardSummary {
+                    instance: record.instance.clone(),
+                    worker: record.worker.clone(),
+                    wallet: record.wallet.clone(),
+                    ..Default::default()
+                });
+                summaries.len() - 1
+            });
+        let summary = &mut summaries[position];
+        *summary.blocks.entry(record.status).or_default() += 1;
+        let reward = record.reward_sompi.unwrap_or_default();
+        match record.status {
+            FoundBlockStatus::Blue => summary.pending_reward_sompi += reward,
+            FoundBlockStatus::Matured => summary.matured_reward_sompi += reward,
+            _ => {}
+        }
+    }
+    summaries
+}
+
+/// Save the journal after each burst of changes, until the journal is dropped
+fn run_journal_writer(path: &Path, records: &Mutex<VecDeque<FoundBlockRecord>>, file_lock: &Mutex<()>, wake: mpsc::Receiver<()>) {
+    while wake.recv().is_ok() {
+        std::thread::sleep(JOURNAL_WRITE_DELAY);
+        while wake.try_recv().is_ok() {}
+        save_records(path, records, file_lock);
+    }
+}
+
+/// Write a copy of the records, leaving the records lock to the submission and notification paths
+fn save_records(path: &Path, records: &Mutex<VecDeque<FoundBlockRecord>>, file_lock: &Mutex<()>) {
+    let _file = file_lock.lock();
+    let records = records.lock().clone();
+    if let Err(e) = write_records(path, &records) {
+        warn!("failed to save found block journal {}: {}", path.display(), e);
+    }
+}
+
+fn write_records(path: &Path, records: &VecDeque<FoundBlockRecord>) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_vec(records).map_err(std::io::Error::other)?;
+    // Write to a sibling file first so a crash never leaves a truncated journal behind
+    let tmp_path = path.with_extension("json.tmp");
+    std::fs::write(&tmp_path, json)?;
+    std::fs::rename(tmp_path, path)
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+static FOUND_BLOCK_JOURNAL: OnceLock<FoundBlockJournal> = OnceLock::new();
+
+/// Open the process-wide journal at `path`. Only the first call has an effect.
+pub fn init_found_block_journal(path: PathBuf) -> &'static FoundBlockJournal {
+    FOUND_BLOCK_JOURNAL.get_or_init(|| FoundBlockJournal::open(path))
+}
+
+/// The process-wide journal, if [`init_found_block_journal`] was called
+pub fn found_block_journal() -> Option<&'static FoundBlockJournal> {
+    FOUND_BLOCK_JOURNAL.get()
+}
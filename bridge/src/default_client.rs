@@ -1,4 +1,5 @@
 use crate::jsonrpc_event::{JsonRpcEvent, JsonRpcResponse};
+use crate::stratum_auth::{AuthPolicy, AuthRequest};
 use crate::stratum_context::StratumContext;
 use kaspa_addresses::Address;
 use regex::Regex;
@@ -181,6 +182,17 @@ pub async fn handle_authorize(
     event: JsonRpcEvent,
     client_handler: Option<Arc<crate::client_handler::ClientHandler>>,
     kaspa_api: Option<Arc<dyn crate::share_handler::KaspaApiTrait + Send + Sync>>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    handle_authorize_with_policy(ctx, event, client_handler, kaspa_api, None).await
+}
+
+/// Handle authorize request, rejecting and disconnecting miners refused by `auth_policy`
+pub async fn handle_authorize_with_policy(
+    ctx: Arc<StratumContext>,
+    event: JsonRpcEvent,
+    client_handler: Option<Arc<crate::client_handler::ClientHandler>>,
+    kaspa_api: Option<Arc<dyn crate::share_handler::KaspaApiTrait + Send + Sync>>,
+    auth_policy: Option<Arc<AuthPolicy>>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     tracing::debug!("[AUTHORIZE] ===== AUTHORIZE REQUEST FROM {} =====", ctx.remote_addr);
     tracing::debug!("[AUTHORIZE] Event ID: {:?}", event.id);
@@ -223,6 +235,17 @@ pub async fn handle_authorize(
 
     tracing::debug!("[AUTHORIZE] Final parsed - address: '{}', worker: '{}', canxium: '{}'", address, worker_name, canxium_address);
 
+    if let Some(policy) = auth_policy.as_deref() {
+        let password = event.params.get(1).and_then(Value::as_str);
+        let request = AuthRequest { remote_addr: &ctx.remote_addr, wallet_addr: &address, worker_name: &worker_name, password };
+        if let Err(rejection) = policy.authorize(&request) {
+            tracing::warn!("[AUTHORIZE] rejected {}:{} worker='{}': {}", ctx.remote_addr, ctx.remote_port, worker_name, rejection);
+            let _ = ctx.reply_unauthorized_worker(event.id.clone()).await;
+            ctx.disconnect();
+            return Err(format!("unauthorized worker: {}", rejection).into());
+        }
+    }
+
     *ctx.wallet_addr.lock() = address.clone();
     *ctx.worker_name.lock() = worker_name;
     ctx.ensure_default_worker_name();
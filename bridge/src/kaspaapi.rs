@@ -1,15 +1,22 @@
+use crate::block_journal::{ChainBlockMerge, found_block_journal};
 use crate::log_colors::LogColors;
 use crate::share_handler::KaspaApiTrait;
 use anyhow::{Context, Result};
+use futures_util::FutureExt;
 use kaspa_addresses::Address;
 use kaspa_consensus_core::block::Block;
+use kaspa_consensus_core::config::params::Params;
 use kaspa_grpc_client::GrpcClient;
-use kaspa_notify::{listener::ListenerId, scope::NewBlockTemplateScope};
+use kaspa_notify::scope::{NewBlockTemplateScope, VirtualChainChangedScope};
+use kaspa_rpc_core::notify::connection::{ChannelConnection, ChannelType};
 use kaspa_rpc_core::notify::mode::NotificationMode;
 use kaspa_rpc_core::{
-    GetBlockDagInfoRequest, GetBlockTemplateRequest, GetConnectedPeerInfoRequest, GetCurrentBlockColorRequest, GetInfoRequest,
-    GetServerInfoRequest, Notification, RpcHash, RpcRawBlock, SubmitBlockRequest, SubmitBlockResponse, api::rpc::RpcApi,
+    GetBlockDagInfoRequest, GetBlockRequest, GetBlockTemplateRequest, GetConnectedPeerInfoRequest, GetCurrentBlockColorRequest,
+    GetInfoRequest, GetServerInfoRequest, Notification, RpcHash, RpcRawBlock, SubmitBlockRequest, SubmitBlockResponse,
+    VirtualChainChangedNotification,
+    api::rpc::{DynRpcService, RpcApi},
 };
+use kaspa_rpc_failover::{FailoverNode, FailoverOptions, FailoverRpcClient, NodeConnector};
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 use std::collections::{HashMap, VecDeque};
@@ -21,6 +28,7 @@ use tokio::sync::watch;
 use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
+const KASPA_API_LISTENER: &str = "stratum-bridge";
 const STRATUM_COINBASE_TAG_BYTES: &[u8] = b"RK-Stratum";
 const MAX_COINBASE_TAG_SUFFIX_LEN: usize = 64;
 
@@ -128,10 +136,18 @@ pub struct NodeStatusSnapshot {
 
 pub static NODE_STATUS: Lazy<Mutex<NodeStatusSnapshot>> = Lazy::new(|| Mutex::new(NodeStatusSnapshot::default()));
 
+/// Split a `kaspad_address` setting into the addresses of its nodes
+///
+/// Several nodes can be listed separated by commas, in which case RPC calls and notification
+/// subscriptions fail over to the most up-to-date synced one.
+pub fn parse_node_addresses(address: &str) -> Vec<String> {
+    address.split(',').map(str::trim).filter(|address| !address.is_empty()).map(str::to_string).collect()
+}
+
 /// Kaspa API client wrapper using RPC client
 /// Both use gRPC under the hood, but through an RPC client wrapper abstraction
 pub struct KaspaApi {
-    client: Arc<GrpcClient>,
+    client: FailoverRpcClient,
     notification_rx: Arc<Mutex<Option<mpsc::UnboundedReceiver<Notification>>>>,
     connected: Arc<Mutex<bool>>,
     coinbase_tag: Vec<u8>,
@@ -144,71 +160,41 @@ impl KaspaApi {
         coinbase_tag_suffix: Option<String>,
         mut shutdown_rx: watch::Receiver<bool>,
     ) -> Result<Arc<Self>> {
-        info!("Connecting to Kaspa node at {}", address);
-
-        // GrpcClient requires explicit "grpc://" prefix for connection
-        // Always add it if not present (avoids unnecessary connection failure)
-        let grpc_address = if address.starts_with("grpc://") { address.clone() } else { format!("grpc://{}", address) };
+        let addresses = parse_node_addresses(&address);
+        if addresses.is_empty() {
+            return Err(anyhow::anyhow!("no kaspa node address configured"));
+        }
+        info!("Connecting to Kaspa node at {}", addresses.join(", "));
 
         // Log connection attempt (detailed logs moved to debug)
         debug!("{} {}", LogColors::api("[API]"), LogColors::label("Establishing RPC connection to Kaspa node:"));
-        debug!("{} {} {}", LogColors::api("[API]"), LogColors::label("  - Address:"), &grpc_address);
+        debug!("{} {} {}", LogColors::api("[API]"), LogColors::label("  - Address:"), addresses.join(", "));
         debug!("{} {} {}", LogColors::api("[API]"), LogColors::label("  - Protocol:"), "gRPC (via RPC client wrapper)");
 
-        let mut attempt: u64 = 0;
-        let mut backoff_ms: u64 = 250;
-
-        let client = loop {
-            attempt += 1;
-            let connect_fut = GrpcClient::connect_with_args(
-                NotificationMode::Direct,
-                grpc_address.clone(),
-                None,
-                true,
-                None,
-                false,
-                Some(500_000),
-                Default::default(),
-            );
-
-            let res = tokio::select! {
-                _ = shutdown_rx.wait_for(|v| *v) => {
-                    return Err(anyhow::anyhow!("shutdown requested"));
-                }
-                res = connect_fut => res,
-            };
-
-            match res {
-                Ok(client) => break Arc::new(client),
-                Err(e) => {
-                    let backoff = Duration::from_millis(backoff_ms);
-                    warn!(
-                        "failed to connect to kaspa node at {} (attempt {}): {}, retrying in {:.2}s",
-                        grpc_address,
-                        attempt,
-                        e,
-                        backoff.as_secs_f64()
-                    );
-
-                    tokio::select! {
-                        _ = shutdown_rx.wait_for(|v| *v) => {
-                            return Err(anyhow::anyhow!("shutdown requested"));
-                        }
-                        _ = sleep(backoff) => {}
-                    }
-
-                    backoff_ms = (backoff_ms.saturating_mul(2)).min(5_000);
-                }
-            }
-        };
+        // Calls are routed to the healthiest node, and subscriptions follow it when it changes.
+        // Nodes unreachable for now are connected by the health checks once they come up.
+        let nodes = addresses.iter().map(|address| Self::failover_node(address)).collect();
+        let client = FailoverRpcClient::new(nodes, FailoverOptions::default(), None);
+        client.start().await;
+        Self::wait_for_node(&client, &mut shutdown_rx).await?;
 
         // Log successful connection (detailed logs moved to debug)
         debug!("{} {}", LogColors::api("[API]"), LogColors::block("RPC Connection Established Successfully"));
-        debug!("{} {} {}", LogColors::api("[API]"), LogColors::label("  - Connected to:"), &grpc_address);
+        for (name, health) in client.nodes_health() {
+            if health.is_reachable {
+                debug!("{} {} {}", LogColors::api("[API]"), LogColors::label("  - Connected to:"), name);
+            } else {
+                warn!("kaspa node at {} is unreachable for now: {}", name, health.last_error.unwrap_or_default());
+            }
+        }
         debug!("{} {} {}", LogColors::api("[API]"), LogColors::label("  - Connection Type:"), "gRPC (via RPC client wrapper)");
 
-        // Start the client (no notify needed for Direct mode)
-        client.start(None).await;
+        let notification_channel = kaspa_utils::channel::Channel::unbounded();
+        let listener_id = client.register_new_listener(ChannelConnection::new(
+            KASPA_API_LISTENER,
+            notification_channel.sender(),
+            ChannelType::Persistent,
+        ));
 
         // Subscribe to block template notifications
         // Some nodes may take time to accept notification subscriptions; retry until it succeeds.
@@ -218,7 +204,7 @@ impl KaspaApi {
         let mut backoff_ms: u64 = 250;
         loop {
             attempt += 1;
-            let notify_fut = client.start_notify(ListenerId::default(), NewBlockTemplateScope {}.into());
+            let notify_fut = client.start_notify(listener_id, NewBlockTemplateScope {}.into());
 
             let res = tokio::select! {
                 _ = shutdown_rx.wait_for(|v| *v) => {
@@ -249,15 +235,30 @@ impl KaspaApi {
             }
         }
 
+        // Virtual chain changes drive the found block journal. Without them the journal still
+        // records submissions, so a failed subscription is not fatal.
+        let track_found_blocks = found_block_journal().is_some();
+        if track_found_blocks
+            && let Err(e) = client.start_notify(listener_id, VirtualChainChangedScope::new(false).into()).await
+        {
+            warn!("failed to subscribe to virtual chain changes, found blocks will not be followed past acceptance: {}", e);
+        }
+
         // Start receiving notifications
+        let (chain_tx, chain_rx) = tokio::sync::mpsc::unbounded_channel();
         let notification_rx = {
-            let receiver = client.notification_channel_receiver();
+            let receiver = notification_channel.receiver();
             // Convert async_channel::Receiver to tokio::sync::mpsc::UnboundedReceiver
             let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
             let receiver_clone = receiver.clone();
             tokio::spawn(async move {
                 while let Ok(notification) = receiver_clone.recv().await {
-                    let _ = tx.send(notification);
+                    // Keep chain changes away from the template listener, which drains its queue
+                    if let Notification::VirtualChainChanged(chain_changed) = notification {
+                        let _ = chain_tx.send(chain_changed);
+                    } else {
+                        let _ = tx.send(notification);
+                    }
                 }
             });
             Arc::new(Mutex::new(Some(rx)))
@@ -278,9 +279,138 @@ impl KaspaApi {
             api_clone.start_node_status_thread().await;
         });
 
+        if track_found_blocks {
+            let api_clone = Arc::clone(&api);
+            tokio::spawn(async move {
+                api_clone.start_found_block_tracker(chain_rx).await;
+            });
+        }
+
         Ok(api)
     }
 
+    /// Failover node for `address`, connected on the first health check reaching it
+    ///
+    /// Once connected, the gRPC client reconnects by itself whenever its connection drops.
+    fn failover_node(address: &str) -> FailoverNode {
+        // GrpcClient requires explicit "grpc://" prefix for connection
+        // Always add it if not present (avoids unnecessary connection failure)
+        let grpc_address = if address.starts_with("grpc://") { address.to_string() } else { format!("grpc://{}", address) };
+        let connector: NodeConnector = Arc::new(move || {
+            let grpc_address = grpc_address.clone();
+            async move {
+                // Multi-listeners mode lets the failover client move its subscriptions between nodes
+                let client = GrpcClient::connect_with_args(
+                    NotificationMode::MultiListeners,
+                    grpc_address,
+                    None,
+                    true,
+                    None,
+                    false,
+                    Some(500_000),
+                    Default::default(),
+                )
+                .await?;
+                client.start(None).await;
+                Ok(Arc::new(client) as DynRpcService)
+            }
+            .boxed()
+        });
+        FailoverNode::with_connector(address, connector)
+    }
+
+    /// Wait until at least one of the nodes is reachable
+    async fn wait_for_node(client: &FailoverRpcClient, shutdown_rx: &mut watch::Receiver<bool>) -> Result<()> {
+        let mut attempt: u64 = 0;
+        let mut backoff_ms: u64 = 250;
+
+        while client.active_node().is_none() {
+            attempt += 1;
+            let backoff = Duration::from_millis(backoff_ms);
+            for (name, health) in client.nodes_health() {
+                warn!(
+                    "failed to connect to kaspa node at {} (attempt {}): {}, retrying in {:.2}s",
+                    name,
+                    attempt,
+                    health.last_error.unwrap_or_default(),
+                    backoff.as_secs_f64()
+                );
+            }
+
+            tokio::select! {
+                _ = shutdown_rx.wait_for(|v| *v) => {
+                    return Err(anyhow::anyhow!("shutdown requested"));
+                }
+                _ = sleep(backoff) => {}
+            }
+
+            client.check_health().await;
+            backoff_ms = (backoff_ms.saturating_mul(2)).min(5_000);
+        }
+        Ok(())
+    }
+
+    /// Follow journaled found blocks along the virtual selected chain and until their reward matures
+    async fn start_found_block_tracker(self: Arc<Self>, mut chain_rx: mpsc::UnboundedReceiver<VirtualChainChangedNotification>) {
+        let Some(journal) = found_block_journal() else { return };
+        journal.publish_metrics();
+
+        let mut maturity_interval = tokio::time::interval(Duration::from_secs(30));
+        maturity_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tokio::select! {
+                notification = chain_rx.recv() => {
+                    let Some(notification) = notification else { break };
+                    for removed in notification.removed_chain_block_hashes.iter() {
+                        journal.apply_chain_block_removed(&removed.to_string());
+                    }
+                    for added in notification.added_chain_block_hashes.iter() {
+                        // Fetching every chain block is only worth it while a block waits to be merged
+                        if !journal.has_unmerged() {
+                            break;
+                        }
+                        match self.get_chain_block_merge(*added).await {
+                            Ok(chain_block) => {
+                                journal.apply_chain_block_added(&chain_block);
+                            }
+                            Err(e) => warn!("failed to fetch chain block {} for the found block journal: {}", added, e),
+                        }
+                    }
+                }
+                _ = maturity_interval.tick() => {
+                    if !journal.has_immature() && !journal.has_unmerged() {
+                        continue;
+                    }
+                    match self.client.get_block_dag_info_call(None, GetBlockDagInfoRequest {}).await {
+                        Ok(dag_info) => {
+                            let params = Params::from(dag_info.network);
+                            journal.update_maturity(dag_info.virtual_daa_score, params.coinbase_maturity());
+                            journal.expire_unmerged(dag_info.virtual_daa_score, params.merge_depth());
+                        }
+                        Err(e) => debug!("failed to get DAG info for found block maturity: {}", e),
+                    }
+                }
+            }
+        }
+    }
+
+    async fn get_chain_block_merge(&self, hash: RpcHash) -> Result<ChainBlockMerge> {
+        let block = self.client.get_block_call(None, GetBlockRequest::new(hash, true)).await.context("Failed to get block")?.block;
+        let verbose_data = block.verbose_data.ok_or_else(|| anyhow::anyhow!("block {} has no verbose data", hash))?;
+        let coinbase_outputs = block
+            .transactions
+            .first()
+            .map(|coinbase| coinbase.outputs.iter().map(|output| (output.script_public_key.clone(), output.value)).collect())
+            .unwrap_or_default();
+        Ok(ChainBlockMerge {
+            hash: hash.to_string(),
+            daa_score: block.header.daa_score,
+            merge_set_blues: verbose_data.merge_set_blues_hashes.iter().map(|hash| hash.to_string()).collect(),
+            merge_set_reds: verbose_data.merge_set_reds_hashes.iter().map(|hash| hash.to_string()).collect(),
+            coinbase_outputs,
+        })
+    }
+
     /// Start network stats thread
     /// Fetches network stats every 30 seconds and records them in Prometheus
     async fn start_stats_thread(self: Arc<Self>) {
@@ -336,7 +466,7 @@ impl KaspaApi {
         loop {
             interval.tick().await;
 
-            let connected = self.client.is_connected();
+            let connected = self.client.active_node().is_some();
 
             let server_info_fut = self.client.get_server_info_call(None, GetServerInfoRequest {});
             let dag_info_fut = self.client.get_block_dag_info_call(None, GetBlockDagInfoRequest {});
@@ -486,7 +616,7 @@ impl KaspaApi {
 
                 // Optional: Check if block appears in tip hashes (verifies propagation)
                 // This is informational only - block may still propagate even if not immediately in tips
-                let client_clone = Arc::clone(&self.client);
+                let client_clone = self.client.clone();
                 let block_hash_clone = block_hash.clone();
                 let block_hash_for_check = header::hash(&block.header); // Use the actual Hash type
                 tokio::spawn(async move {
@@ -802,7 +932,7 @@ impl KaspaApi {
                     // In Go, RegisterForNewBlockTemplateNotifications is called here when restartChannel is true
                     // In Rust, we already subscribed in new(), and the notification channel persists
                     // If the connection is lost, the gRPC client handles reconnection automatically
-                    // The notification subscription should be maintained by the gRPC client
+                    // and the failover client renews the subscription on whichever node it switches to
                     // If notifications stop working, we'll fall back to ticker polling
                     restart_channel = false;
                 }
@@ -1,4 +1,5 @@
 pub mod app_config;
+pub mod block_journal;
 pub mod client_handler;
 pub mod default_client;
 pub mod errors;
@@ -13,11 +14,14 @@ pub mod prom;
 #[cfg(feature = "rkstratum_cpu_miner")]
 pub mod rkstratum_cpu_miner;
 pub mod share_handler;
+pub mod stratum_auth;
 pub mod stratum_context;
 pub mod stratum_listener;
 pub mod stratum_server;
+pub mod stratum_tls;
 
-pub use app_config::{BridgeConfig, InstanceConfig};
+pub use app_config::{AuthConfig, BridgeConfig, InstanceConfig, TlsConfig};
+pub use block_journal::*;
 pub use client_handler::*;
 pub use default_client::*;
 pub use errors::*;
@@ -29,7 +33,9 @@ pub use prom::{WorkerContext, *};
 #[cfg(feature = "rkstratum_cpu_miner")]
 pub use rkstratum_cpu_miner::*;
 pub use share_handler::*;
+pub use stratum_auth::*;
 pub use stratum_context::*;
 pub use stratum_listener::*;
 pub use stratum_server::BridgeConfig as StratumServerBridgeConfig;
 pub use stratum_server::*;
+pub use stratum_tls::*;
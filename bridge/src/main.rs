@@ -2,7 +2,9 @@ use clap::Parser;
 use futures_util::future::try_join_all;
 use kaspa_alloc::init_allocator_with_default_settings;
 use kaspa_stratum_bridge::log_colors::LogColors;
-use kaspa_stratum_bridge::{KaspaApi, StratumServerBridgeConfig as StratumBridgeConfig, listen_and_serve_with_shutdown, prom};
+use kaspa_stratum_bridge::{
+    KaspaApi, StratumServerBridgeConfig as StratumBridgeConfig, block_journal, listen_and_serve_with_shutdown, prom,
+};
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 #[cfg(windows)]
@@ -171,6 +173,20 @@ fn log_bridge_configuration(config: &BridgeConfig) {
         if let Some(log_to_file) = instance.log_to_file {
             tracing::info!("\t  log to file:   {}", log_to_file);
         }
+        if let Some(ref tls) = instance.tls {
+            tracing::info!("\t  tls cert:      {}", tls.cert_path.display());
+        }
+        if let Some(ref auth) = instance.auth {
+            if !auth.allowed_addresses.is_empty() {
+                tracing::info!("\t  allowed addrs: {}", auth.allowed_addresses.len());
+            }
+            if let Some(ref path) = auth.worker_passwords_file {
+                tracing::info!("\t  worker auth:   {}", path.display());
+            }
+            if let Some(max) = auth.max_connections_per_ip {
+                tracing::info!("\t  max conns/ip:  {}", max);
+            }
+        }
     }
     tracing::info!("----------------------------------");
 }
@@ -279,6 +295,12 @@ async fn main() -> Result<(), anyhow::Error> {
         health_check::spawn_health_check_server(health_port);
     }
 
+    // The journal must exist before the API client, which subscribes to chain changes only when it does
+    let found_blocks = block_journal::init_found_block_journal(app_dirs::get_found_blocks_journal_path(cli.testnet));
+    if let Some(path) = found_blocks.path() {
+        tracing::info!("Found block journal: {} ({} blocks)", path.display(), found_blocks.len());
+    }
+
     // Create shared kaspa API client (all instances use the same node)
     let kaspa_api =
         KaspaApi::new(config.global.kaspad_address.clone(), config.global.coinbase_tag_suffix.clone(), shutdown_rx.clone())
@@ -428,6 +450,8 @@ async fn main() -> Result<(), anyhow::Error> {
                 extranonce_size: instance.extranonce_size.unwrap_or(global.extranonce_size),
                 pow2_clamp: instance.pow2_clamp.unwrap_or(global.pow2_clamp),
                 coinbase_tag_suffix: global.coinbase_tag_suffix.clone(),
+                tls: instance.tls.clone(),
+                auth: instance.auth.clone().unwrap_or_default(),
             };
 
             listen_and_serve_with_shutdown(
@@ -477,6 +501,7 @@ async fn main() -> Result<(), anyhow::Error> {
 
     tokio::select! {
         res = &mut bridge_fut => {
+            found_blocks.flush();
             if let Some(node) = inprocess_node {
                 shutdown_inprocess_with_timeout(node).await;
             }
@@ -500,6 +525,7 @@ async fn main() -> Result<(), anyhow::Error> {
                     }
                 };
 
+                found_blocks.flush();
                 if let Some(node) = inprocess_node {
                     shutdown_inprocess_with_timeout(node).await;
                 }
@@ -520,6 +546,7 @@ async fn main() -> Result<(), anyhow::Error> {
                     }
                 };
 
+                found_blocks.flush();
                 if let Some(node) = inprocess_node {
                     shutdown_inprocess_with_timeout(node).await;
                 }
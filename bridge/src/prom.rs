@@ -28,6 +28,18 @@ const ERROR_LABELS: &[&str] = &["instance", "wallet", "error"];
 /// Balance labels
 const BALANCE_LABELS: &[&str] = &["instance", "wallet"];
 
+/// Found block journal labels
+const FOUND_BLOCK_LABELS: &[&str] = &["instance", "worker", "wallet", "status"];
+
+/// Found block reward labels
+const FOUND_BLOCK_REWARD_LABELS: &[&str] = &["instance", "worker", "wallet", "state"];
+
+/// Found blocks per lifecycle status, from the found block journal
+static FOUND_BLOCKS_GAUGE: OnceLock<GaugeVec> = OnceLock::new();
+
+/// Effective block rewards per worker, pending or matured
+static FOUND_BLOCK_REWARD_GAUGE: OnceLock<GaugeVec> = OnceLock::new();
+
 /// Share counter - number of valid shares found by worker
 static SHARE_COUNTER: OnceLock<CounterVec> = OnceLock::new();
 
@@ -157,6 +169,19 @@ pub fn init_metrics() {
         .unwrap()
     });
 
+    FOUND_BLOCKS_GAUGE.get_or_init(|| {
+        register_gauge_vec!("ks_found_blocks", "Number of journaled found blocks by lifecycle status", FOUND_BLOCK_LABELS).unwrap()
+    });
+
+    FOUND_BLOCK_REWARD_GAUGE.get_or_init(|| {
+        register_gauge_vec!(
+            "ks_found_block_reward_kas",
+            "Rewards of journaled blue blocks paid to the worker's wallet, pending or matured",
+            FOUND_BLOCK_REWARD_LABELS
+        )
+        .unwrap()
+    });
+
     ERROR_BY_WALLET
         .get_or_init(|| register_counter_vec!("ks_worker_errors", "Gauge representing errors by worker", ERROR_LABELS).unwrap());
 
@@ -411,6 +436,22 @@ async fn handle_http_request(
         return Ok(());
     }
 
+    if request.starts_with("GET /api/blocks") {
+        let instance = match mode {
+            HttpMode::Aggregated { .. } => None,
+            HttpMode::Instance { instance_id, .. } => Some(instance_id.as_str()),
+        };
+        let snapshot = crate::block_journal::found_block_journal().map(|journal| journal.snapshot(instance)).unwrap_or_default();
+        let json = serde_json::to_string(&snapshot).unwrap_or_else(|_| "{}".to_string());
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nAccess-Control-Allow-Origin: *\r\nContent-Length: {}\r\n\r\n{}",
+            json.len(),
+            json
+        );
+        stream.write_all(response.as_bytes()).await?;
+        return Ok(());
+    }
+
     if matches!(mode, HttpMode::Instance { .. }) && request.starts_with("GET /api/config") {
         let config_json = get_config_json().await;
         let response = format!(
@@ -744,6 +785,31 @@ pub fn record_balances(instance_id: &str, balances: &[(String, u64)]) {
     }
 }
 
+/// Replace the found block series with the journal's current per-worker totals
+pub fn record_found_block_summaries(summaries: &[crate::block_journal::WorkerRewardSummary]) {
+    if let Some(gauge) = FOUND_BLOCKS_GAUGE.get() {
+        gauge.reset();
+        for summary in summaries {
+            for status in crate::block_journal::FoundBlockStatus::ALL {
+                let count = summary.blocks.get(&status).copied().unwrap_or_default();
+                gauge
+                    .with_label_values(&[summary.instance.as_str(), &summary.worker, &summary.wallet, status.as_str()])
+                    .set(count as f64);
+            }
+        }
+    }
+    if let Some(gauge) = FOUND_BLOCK_REWARD_GAUGE.get() {
+        gauge.reset();
+        for summary in summaries {
+            for (state, sompi) in [("pending", summary.pending_reward_sompi), ("matured", summary.matured_reward_sompi)] {
+                // Convert from sompi to KAS, like the balance gauge
+                let kas = sompi as f64 / 100_000_000.0;
+                gauge.with_label_values(&[summary.instance.as_str(), &summary.worker, &summary.wallet, state]).set(kas);
+            }
+        }
+    }
+}
+
 fn metric_matches_instance(metric: &prometheus::proto::Metric, instance_id: &str) -> bool {
     metric.get_label().iter().any(|label| label.name() == "instance" && label.value() == instance_id)
 }
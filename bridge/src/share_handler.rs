@@ -792,6 +792,20 @@ impl ShareHandler {
                 // Submit block to node
                 let block_submit_result = kaspa_api.submit_block(block.clone()).await;
 
+                // Journaled once the node answered, keeping the journal off the submission path. A duplicate is
+                // refused before reaching the node and says nothing about the original submission.
+                let journal = crate::block_journal::found_block_journal()
+                    .filter(|_| !matches!(&block_submit_result, Err(e) if e.to_string().contains("ErrDuplicateBlock")));
+                if let Some(journal) = journal {
+                    journal.record_submitted(
+                        &block_hash,
+                        &self.worker_prom_context(&ctx, ""),
+                        nonce_val,
+                        blue_score,
+                        block.header.daa_score,
+                    );
+                }
+
                 match block_submit_result {
                     Ok(response) => {
                         if !response.report.is_success() {
@@ -804,10 +818,17 @@ impl ShareHandler {
                                 LogColors::label("REJECTION REASON:"),
                                 format!("{:?}", response.report)
                             );
+                            if let Some(journal) = journal {
+                                journal.mark_rejected(&block_hash, format!("{:?}", response.report));
+                            }
                             invalid_share = true;
                             break;
                         }
 
+                        if let Some(journal) = journal {
+                            journal.mark_accepted(&block_hash);
+                        }
+
                         let prefix = self.log_prefix();
                         // Block accepted - log after submit to get it submitted faster
                         info!(
@@ -896,6 +917,10 @@ impl ShareHandler {
                         error!("{} {} {} {}", prefix, LogColors::block("[BLOCK]"), LogColors::label("Blockhash:"), block_hash);
                         error!("{} {} {} {}", prefix, LogColors::block("[BLOCK]"), LogColors::error("Error:"), error_str);
 
+                        if let Some(journal) = journal {
+                            journal.mark_rejected(&block_hash, error_str.clone());
+                        }
+
                         if error_str.contains("ErrDuplicateBlock") {
                             // Block rejected, stale
                             warn!("{} {} {}", prefix, LogColors::block("[BLOCK]"), LogColors::error("block rejected, stale"));
@@ -0,0 +1,212 @@
+use crate::app_config::AuthConfig;
+use anyhow::Context;
+use kaspa_addresses::Address;
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Credentials presented by a miner in `mining.authorize`
+#[derive(Debug, Clone, Copy)]
+pub struct AuthRequest<'a> {
+    pub remote_addr: &'a str,
+    /// Payout address, already normalized to a valid Kaspa address
+    pub wallet_addr: &'a str,
+    pub worker_name: &'a str,
+    /// Second `mining.authorize` param, if the miner sent one
+    pub password: Option<&'a str>,
+}
+
+/// Reason a `mining.authorize` request was refused
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AuthRejection {
+    #[error("payout address {0} is not allowed")]
+    AddressNotAllowed(String),
+    #[error("unknown worker '{0}'")]
+    UnknownWorker(String),
+    #[error("invalid password for worker '{0}'")]
+    InvalidPassword(String),
+    #[error("{0}")]
+    Other(String),
+}
+
+/// A pluggable check run on every `mining.authorize` request
+pub trait WorkerAuthorizer: Send + Sync {
+    fn authorize(&self, request: &AuthRequest<'_>) -> Result<(), AuthRejection>;
+}
+
+/// Set of authorizers a miner must all pass; an empty policy accepts everyone
+#[derive(Clone, Default)]
+pub struct AuthPolicy {
+    authorizers: Vec<Arc<dyn WorkerAuthorizer>>,
+}
+
+impl AuthPolicy {
+    /// Build the policy described by an instance `auth` config section
+    pub fn from_config(config: &AuthConfig) -> Result<Self, anyhow::Error> {
+        let mut policy = Self::default();
+        if !config.allowed_addresses.is_empty() {
+            policy = policy.with_authorizer(Arc::new(AddressAllowlist::new(&config.allowed_addresses)?));
+        }
+        if let Some(path) = &config.worker_passwords_file {
+            policy = policy.with_authorizer(Arc::new(WorkerPasswords::load(path.clone())?));
+        }
+        Ok(policy)
+    }
+
+    pub fn with_authorizer(mut self, authorizer: Arc<dyn WorkerAuthorizer>) -> Self {
+        self.authorizers.push(authorizer);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.authorizers.is_empty()
+    }
+
+    pub fn authorize(&self, request: &AuthRequest<'_>) -> Result<(), AuthRejection> {
+        self.authorizers.iter().try_for_each(|authorizer| authorizer.authorize(request))
+    }
+}
+
+/// Accepts only the configured payout addresses
+pub struct AddressAllowlist {
+    addresses: HashSet<Address>,
+}
+
+impl AddressAllowlist {
+    pub fn new(addresses: &[String]) -> Result<Self, anyhow::Error> {
+        let addresses = addresses
+            .iter()
+            .map(|address| Address::try_from(address.trim()).map_err(|e| anyhow::anyhow!("invalid allowed address '{address}': {e}")))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { addresses })
+    }
+}
+
+impl WorkerAuthorizer for AddressAllowlist {
+    fn authorize(&self, request: &AuthRequest<'_>) -> Result<(), AuthRejection> {
+        match Address::try_from(request.wallet_addr) {
+            Ok(address) if self.addresses.contains(&address) => Ok(()),
+            _ => Err(AuthRejection::AddressNotAllowed(request.wallet_addr.to_string())),
+        }
+    }
+}
+
+/// Per-worker passwords read from a `worker:password` file
+///
+/// Blank lines and lines starting with `#` are ignored. The file is re-read when its
+/// modification time changes, so workers can be added without restarting the bridge.
+pub struct WorkerPasswords {
+    path: PathBuf,
+    state: Mutex<(Option<SystemTime>, HashMap<String, String>)>,
+}
+
+impl WorkerPasswords {
+    pub fn load(path: PathBuf) -> Result<Self, anyhow::Error> {
+        let modified = modified_time(&path);
+        let passwords = read_password_file(&path)?;
+        Ok(Self { path, state: Mutex::new((modified, passwords)) })
+    }
+
+    fn reload_if_changed(&self, state: &mut (Option<SystemTime>, HashMap<String, String>)) {
+        let modified = modified_time(&self.path);
+        if modified == state.0 {
+            return;
+        }
+        match read_password_file(&self.path) {
+            Ok(passwords) => {
+                tracing::info!("reloaded {} worker passwords from {}", passwords.len(), self.path.display());
+                *state = (modified, passwords);
+            }
+            Err(e) => tracing::warn!("failed to reload worker passwords, keeping the previous ones: {:#}", e),
+        }
+    }
+}
+
+impl WorkerAuthorizer for WorkerPasswords {
+    fn authorize(&self, request: &AuthRequest<'_>) -> Result<(), AuthRejection> {
+        let mut state = self.state.lock();
+        self.reload_if_changed(&mut state);
+        match state.1.get(request.worker_name) {
+            None => Err(AuthRejection::UnknownWorker(request.worker_name.to_string())),
+            Some(expected) if request.password == Some(expected.as_str()) => Ok(()),
+            Some(_) => Err(AuthRejection::InvalidPassword(request.worker_name.to_string())),
+        }
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn read_password_file(path: &Path) -> Result<HashMap<String, String>, anyhow::Error> {
+    let content = std::fs::read_to_string(path).with_context(|| format!("failed reading worker passwords file {}", path.display()))?;
+    parse_worker_passwords(&content).with_context(|| format!("invalid worker passwords file {}", path.display()))
+}
+
+/// Parse the content of a worker passwords file
+pub fn parse_worker_passwords(content: &str) -> Result<HashMap<String, String>, anyhow::Error> {
+    let mut passwords = HashMap::new();
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (worker, password) =
+            line.split_once(':').ok_or_else(|| anyhow::anyhow!("line {}: expected 'worker:password'", idx + 1))?;
+        let worker = worker.trim();
+        if worker.is_empty() {
+            return Err(anyhow::anyhow!("line {}: empty worker name", idx + 1));
+        }
+        passwords.insert(worker.to_string(), password.trim().to_string());
+    }
+    Ok(passwords)
+}
+
+/// Tracks open connections per IP and refuses new ones above a limit
+pub struct ConnectionLimiter {
+    max_per_ip: u32,
+    active: Mutex<HashMap<IpAddr, u32>>,
+}
+
+impl ConnectionLimiter {
+    pub fn new(max_per_ip: u32) -> Arc<Self> {
+        Arc::new(Self { max_per_ip, active: Mutex::new(HashMap::new()) })
+    }
+
+    /// Reserve a connection slot for `ip`, or `None` if the IP is at its limit.
+    /// The slot is released when the returned guard is dropped.
+    pub fn try_acquire(self: &Arc<Self>, ip: IpAddr) -> Option<ConnectionSlot> {
+        let mut active = self.active.lock();
+        let count = active.entry(ip).or_insert(0);
+        if *count >= self.max_per_ip {
+            return None;
+        }
+        *count += 1;
+        Some(ConnectionSlot { limiter: Arc::clone(self), ip })
+    }
+
+    pub fn active_connections(&self, ip: IpAddr) -> u32 {
+        self.active.lock().get(&ip).copied().unwrap_or(0)
+    }
+}
+
+/// A connection counted against its IP limit until dropped
+pub struct ConnectionSlot {
+    limiter: Arc<ConnectionLimiter>,
+    ip: IpAddr,
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        let mut active = self.limiter.active.lock();
+        if let Some(count) = active.get_mut(&self.ip) {
+            *count -= 1;
+            if *count == 0 {
+                active.remove(&self.ip);
+            }
+        }
+    }
+}
@@ -5,10 +5,17 @@ use parking_lot::Mutex;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
-use tokio::io::AsyncWriteExt;
-use tokio::net::TcpStream;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio::sync::mpsc;
 
+/// A bidirectional client connection: plain TCP or TLS
+pub trait StratumIo: AsyncRead + AsyncWrite + Unpin + Send + 'static {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send + 'static> StratumIo for T {}
+
+/// Type-erased client connection held by a [`StratumContext`]
+pub type StratumStream = Box<dyn StratumIo>;
+
 /// Error for disconnected clients
 #[derive(Debug, thiserror::Error)]
 #[error("disconnecting")]
@@ -37,8 +44,8 @@ pub struct StratumContext {
     pub state: Arc<crate::mining_state::MiningState>,
     disconnecting: Arc<AtomicBool>,
     write_lock: Arc<AtomicBool>,
-    read_half: Arc<Mutex<Option<tokio::io::ReadHalf<TcpStream>>>>,
-    write_half: Arc<Mutex<Option<tokio::io::WriteHalf<TcpStream>>>>,
+    read_half: Arc<Mutex<Option<tokio::io::ReadHalf<StratumStream>>>>,
+    write_half: Arc<Mutex<Option<tokio::io::WriteHalf<StratumStream>>>>,
     on_disconnect: mpsc::UnboundedSender<Arc<StratumContext>>,
 }
 
@@ -46,11 +53,11 @@ impl StratumContext {
     pub fn new(
         remote_addr: String,
         remote_port: u16,
-        stream: TcpStream,
+        stream: impl StratumIo,
         state: Arc<crate::mining_state::MiningState>,
         on_disconnect: mpsc::UnboundedSender<Arc<StratumContext>>,
     ) -> Arc<Self> {
-        let (read_half, write_half) = tokio::io::split(stream);
+        let (read_half, write_half) = tokio::io::split(Box::new(stream) as StratumStream);
         Arc::new(Self {
             remote_addr,
             remote_port,
@@ -566,6 +573,12 @@ impl StratumContext {
         self.reply(JsonRpcResponse::error(id, 20, "Unknown problem", None)).await
     }
 
+    /// Reply with unauthorized worker error
+    pub async fn reply_unauthorized_worker(&self, id: Option<Value>) -> Result<(), ErrorDisconnected> {
+        tracing::debug!("[BRIDGE->ASIC] Preparing UNAUTHORIZED WORKER response (Error Code: 24, Unauthorized worker)");
+        self.reply(JsonRpcResponse::error(id, 24, "Unauthorized worker", None)).await
+    }
+
     /// Reply with low difficulty share error
     pub async fn reply_low_diff_share(&self, id: &serde_json::Value) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         tracing::debug!("[BRIDGE->ASIC] Preparing LOW DIFFICULTY SHARE response (Error Code: 23, Invalid difficulty)");
@@ -639,7 +652,7 @@ impl StratumContext {
     }
 
     /// Get a reference to the read half (for reading)
-    pub fn get_read_half(&self) -> parking_lot::MutexGuard<'_, Option<tokio::io::ReadHalf<TcpStream>>> {
+    pub fn get_read_half(&self) -> parking_lot::MutexGuard<'_, Option<tokio::io::ReadHalf<StratumStream>>> {
         self.read_half.lock()
     }
 }
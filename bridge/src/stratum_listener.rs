@@ -1,12 +1,15 @@
 use crate::jsonrpc_event::JsonRpcEvent;
 use crate::log_colors::LogColors;
 use crate::net_utils::bind_addr_from_port;
-use crate::stratum_context::StratumContext;
+use crate::stratum_auth::{ConnectionLimiter, ConnectionSlot};
+use crate::stratum_context::{StratumContext, StratumIo};
+use crate::stratum_tls::{StratumTls, TLS_HANDSHAKE_TIMEOUT};
 use hex;
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::io::AsyncReadExt;
-use tokio::net::TcpListener;
+use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::mpsc;
 use tokio::sync::watch;
 use tracing::{debug, error, info, warn};
@@ -58,6 +61,10 @@ pub struct StratumListenerConfig {
     pub on_connect: Arc<dyn Fn(Arc<StratumContext>) + Send + Sync>,
     pub on_disconnect: Arc<dyn Fn(Arc<StratumContext>) + Send + Sync>,
     pub port: String,
+    /// When set, clients must connect over TLS
+    pub tls: Option<Arc<StratumTls>>,
+    /// When set, limits the number of concurrent connections per IP
+    pub connection_limiter: Option<Arc<ConnectionLimiter>>,
 }
 
 /// Stratum TCP listener
@@ -152,48 +159,7 @@ impl StratumListener {
                     result = listener.accept() => {
                         match result {
                             Ok((stream, addr)) => {
-                            let remote_addr = addr.ip().to_string();
-                            let remote_port = addr.port();
-
-                            debug!("[CONNECTION] new client connecting - {}:{}", remote_addr, remote_port);
-                            debug!("[CONNECTION] ===== TCP CONNECTION ESTABLISHED =====");
-                            debug!("[CONNECTION] Remote address: {}:{}", remote_addr, remote_port);
-                            debug!("[CONNECTION] Local address: {:?}", stream.local_addr());
-                            debug!("[CONNECTION] Connection accepted successfully");
-
-                            // Create new MiningState for each client
-                            // Each client gets its own isolated state, just like in Go
-                            use crate::mining_state::MiningState;
-                            let state = Arc::new(MiningState::new());
-
-                            // Clone for logging after move
-                            let remote_addr_for_log = remote_addr.clone();
-                            let remote_port_for_log = remote_port;
-
-                            debug!("[CONNECTION] Creating StratumContext for {}:{}", remote_addr_for_log, remote_port_for_log);
-                            let ctx = StratumContext::new(
-                                remote_addr,
-                                remote_port,
-                                stream,
-                                state,
-                                disconnect_tx_clone.clone(),
-                            );
-                            debug!("[CONNECTION] StratumContext created successfully");
-
-                            debug!("[CONNECTION] Calling on_connect handler");
-                            (self.config.on_connect)(ctx.clone());
-                            debug!("[CONNECTION] on_connect handler completed");
-
-                            // Spawn client handler
-                            debug!("[CONNECTION] Spawning client listener task for {}:{}", remote_addr_for_log, remote_port_for_log);
-                            let ctx_clone = ctx.clone();
-                            let handler_map = self.config.handler_map.clone();
-                            tokio::spawn(async move {
-                                debug!("[CONNECTION] Client listener task started for {}:{}", ctx_clone.remote_addr, ctx_clone.remote_port);
-                                Self::spawn_client_listener(ctx_clone, &handler_map).await;
-                                debug!("[CONNECTION] Client listener task ended");
-                            });
-                            debug!("[CONNECTION] ===== CONNECTION SETUP COMPLETE FOR {}:{} =====", remote_addr_for_log, remote_port_for_log);
+                                self.accept_connection(stream, addr, &disconnect_tx_clone);
                         }
                             Err(e) => {
                             if self.shutting_down.load(std::sync::atomic::Ordering::Acquire) {
@@ -211,44 +177,7 @@ impl StratumListener {
             } else {
                 match listener.accept().await {
                     Ok((stream, addr)) => {
-                        let remote_addr = addr.ip().to_string();
-                        let remote_port = addr.port();
-
-                        debug!("[CONNECTION] new client connecting - {}:{}", remote_addr, remote_port);
-                        debug!("[CONNECTION] ===== TCP CONNECTION ESTABLISHED =====");
-                        debug!("[CONNECTION] Remote address: {}:{}", remote_addr, remote_port);
-                        debug!("[CONNECTION] Local address: {:?}", stream.local_addr());
-                        debug!("[CONNECTION] Connection accepted successfully");
-
-                        use crate::mining_state::MiningState;
-                        let state = Arc::new(MiningState::new());
-
-                        let remote_addr_for_log = remote_addr.clone();
-                        let remote_port_for_log = remote_port;
-
-                        debug!("[CONNECTION] Creating StratumContext for {}:{}", remote_addr_for_log, remote_port_for_log);
-                        let ctx = StratumContext::new(remote_addr, remote_port, stream, state, disconnect_tx_clone.clone());
-                        debug!("[CONNECTION] StratumContext created successfully");
-
-                        debug!("[CONNECTION] Calling on_connect handler");
-                        (self.config.on_connect)(ctx.clone());
-                        debug!("[CONNECTION] on_connect handler completed");
-
-                        debug!("[CONNECTION] Spawning client listener task for {}:{}", remote_addr_for_log, remote_port_for_log);
-                        let ctx_clone = ctx.clone();
-                        let handler_map = self.config.handler_map.clone();
-                        tokio::spawn(async move {
-                            debug!(
-                                "[CONNECTION] Client listener task started for {}:{}",
-                                ctx_clone.remote_addr, ctx_clone.remote_port
-                            );
-                            Self::spawn_client_listener(ctx_clone, &handler_map).await;
-                            debug!("[CONNECTION] Client listener task ended");
-                        });
-                        debug!(
-                            "[CONNECTION] ===== CONNECTION SETUP COMPLETE FOR {}:{} =====",
-                            remote_addr_for_log, remote_port_for_log
-                        );
+                        self.accept_connection(stream, addr, &disconnect_tx_clone);
                     }
                     Err(e) => {
                         if self.shutting_down.load(std::sync::atomic::Ordering::Acquire) {
@@ -267,6 +196,82 @@ impl StratumListener {
         Ok(())
     }
 
+    /// Set up a freshly accepted TCP connection, performing the TLS handshake first if enabled
+    fn accept_connection(&self, stream: TcpStream, addr: SocketAddr, disconnect_tx: &mpsc::UnboundedSender<Arc<StratumContext>>) {
+        debug!("[CONNECTION] new client connecting - {}:{}", addr.ip(), addr.port());
+        debug!("[CONNECTION] ===== TCP CONNECTION ESTABLISHED =====");
+        debug!("[CONNECTION] Local address: {:?}", stream.local_addr());
+
+        let slot = match &self.config.connection_limiter {
+            Some(limiter) => match limiter.try_acquire(addr.ip()) {
+                Some(slot) => Some(slot),
+                None => {
+                    warn!("[CONNECTION] rejecting {}:{} - too many connections from this IP", addr.ip(), addr.port());
+                    return;
+                }
+            },
+            None => None,
+        };
+
+        let on_connect = Arc::clone(&self.config.on_connect);
+        let handler_map = Arc::clone(&self.config.handler_map);
+        let disconnect_tx = disconnect_tx.clone();
+        match &self.config.tls {
+            Some(tls) => {
+                let acceptor = tls.acceptor();
+                tokio::spawn(async move {
+                    match tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                        Ok(Ok(tls_stream)) => {
+                            debug!("[CONNECTION] TLS handshake completed with {}:{}", addr.ip(), addr.port());
+                            Self::start_session(tls_stream, addr, slot, on_connect, handler_map, disconnect_tx);
+                        }
+                        Ok(Err(e)) => debug!("[CONNECTION] TLS handshake with {}:{} failed: {}", addr.ip(), addr.port(), e),
+                        Err(_) => debug!("[CONNECTION] TLS handshake with {}:{} timed out", addr.ip(), addr.port()),
+                    }
+                });
+            }
+            None => Self::start_session(stream, addr, slot, on_connect, handler_map, disconnect_tx),
+        }
+    }
+
+    /// Create the client context and spawn its listener task
+    fn start_session(
+        stream: impl StratumIo,
+        addr: SocketAddr,
+        slot: Option<ConnectionSlot>,
+        on_connect: Arc<dyn Fn(Arc<StratumContext>) + Send + Sync>,
+        handler_map: Arc<HashMap<String, EventHandler>>,
+        disconnect_tx: mpsc::UnboundedSender<Arc<StratumContext>>,
+    ) {
+        let remote_addr = addr.ip().to_string();
+        let remote_port = addr.port();
+
+        // Create new MiningState for each client
+        // Each client gets its own isolated state, just like in Go
+        use crate::mining_state::MiningState;
+        let state = Arc::new(MiningState::new());
+
+        debug!("[CONNECTION] Creating StratumContext for {}:{}", remote_addr, remote_port);
+        let ctx = StratumContext::new(remote_addr, remote_port, stream, state, disconnect_tx);
+        debug!("[CONNECTION] StratumContext created successfully");
+
+        debug!("[CONNECTION] Calling on_connect handler");
+        on_connect(ctx.clone());
+        debug!("[CONNECTION] on_connect handler completed");
+
+        // Spawn client handler
+        debug!("[CONNECTION] Spawning client listener task for {}:{}", ctx.remote_addr, ctx.remote_port);
+        let ctx_clone = ctx.clone();
+        tokio::spawn(async move {
+            debug!("[CONNECTION] Client listener task started for {}:{}", ctx_clone.remote_addr, ctx_clone.remote_port);
+            Self::spawn_client_listener(ctx_clone, &handler_map).await;
+            // Release the per-IP connection slot only once the client is gone
+            drop(slot);
+            debug!("[CONNECTION] Client listener task ended");
+        });
+        debug!("[CONNECTION] ===== CONNECTION SETUP COMPLETE FOR {}:{} =====", ctx.remote_addr, ctx.remote_port);
+    }
+
     /// Spawn a client listener task
     async fn spawn_client_listener(ctx: Arc<StratumContext>, handler_map: &Arc<HashMap<String, EventHandler>>) {
         debug!("[CLIENT_LISTENER] Starting client listener for {}:{}", ctx.remote_addr, ctx.remote_port);
@@ -1,11 +1,14 @@
 use crate::{
+    app_config::{AuthConfig, TlsConfig},
     client_handler::ClientHandler,
-    default_client::{default_handlers, handle_authorize, handle_subscribe},
+    default_client::{default_handlers, handle_authorize_with_policy, handle_subscribe},
     jsonrpc_event::JsonRpcEvent,
     kaspaapi::KaspaApi,
     share_handler::{KaspaApiTrait, ShareHandler},
+    stratum_auth::{AuthPolicy, ConnectionLimiter},
     stratum_context::StratumContext,
     stratum_listener::{StratumListener, StratumListenerConfig},
+    stratum_tls::StratumTls,
 };
 use std::sync::Arc;
 use std::time::Duration;
@@ -28,6 +31,8 @@ pub struct BridgeConfig {
     pub extranonce_size: u8,
     pub pow2_clamp: bool,
     pub coinbase_tag_suffix: Option<String>,
+    pub tls: Option<TlsConfig>,
+    pub auth: AuthConfig,
 }
 
 /// Start block template listener with concrete KaspaApi
@@ -78,6 +83,20 @@ async fn listen_and_serve_impl<T: KaspaApiTrait + Send + Sync + 'static>(
     concrete_kaspa_api: Option<Arc<KaspaApi>>,
     shutdown_rx: Option<watch::Receiver<bool>>,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    // Load TLS and authorization settings first so that a bad certificate or password
+    // file fails the instance at startup rather than on the first miner connection
+    let tls = match config.tls.clone() {
+        Some(tls_config) => {
+            let tls = StratumTls::load(tls_config).map_err(|e| format!("{} failed to set up TLS: {:#}", config.instance_id, e))?;
+            Some(Arc::new(tls))
+        }
+        None => None,
+    };
+    let auth_policy = AuthPolicy::from_config(&config.auth)
+        .map_err(|e| format!("{} failed to set up authorization: {:#}", config.instance_id, e))?;
+    let auth_policy = if auth_policy.is_empty() { None } else { Some(Arc::new(auth_policy)) };
+    let connection_limiter = config.auth.max_connections_per_ip.map(ConnectionLimiter::new);
+
     // Calculate min diff with pow2 clamp if needed
     let mut min_diff = config.min_share_diff as f64;
     if config.pow2_clamp && min_diff > 0.0 {
@@ -132,13 +151,15 @@ async fn listen_and_serve_impl<T: KaspaApiTrait + Send + Sync + 'static>(
     let authorize_handler = {
         let client_handler = Arc::clone(&client_handler);
         let kaspa_api = Arc::clone(&kaspa_api);
+        let auth_policy = auth_policy.clone();
         Arc::new(move |ctx: Arc<StratumContext>, event: JsonRpcEvent| {
             let client_handler = Arc::clone(&client_handler);
             let kaspa_api = Arc::clone(&kaspa_api);
+            let auth_policy = auth_policy.clone();
             let ctx_clone = Arc::clone(&ctx);
             let event_clone = event.clone();
             Box::pin(async move {
-                handle_authorize(ctx_clone, event_clone, Some(client_handler), Some(kaspa_api))
+                handle_authorize_with_policy(ctx_clone, event_clone, Some(client_handler), Some(kaspa_api), auth_policy)
                     .await
                     .map_err(|e| Box::new(std::io::Error::other(e.to_string())) as Box<dyn std::error::Error + Send + Sync>)
             })
@@ -171,6 +192,8 @@ async fn listen_and_serve_impl<T: KaspaApiTrait + Send + Sync + 'static>(
     // Each client gets its own isolated state
     let listener_config = StratumListenerConfig {
         port: config.stratum_port.clone(),
+        tls,
+        connection_limiter,
         handler_map: Arc::new(handlers),
         on_connect: Arc::new({
             let client_handler = Arc::clone(&client_handler);
@@ -279,7 +302,8 @@ async fn listen_and_serve_impl<T: KaspaApiTrait + Send + Sync + 'static>(
 
     // Start listener
     let listener = StratumListener::new(listener_config);
-    info!("{} Starting stratum listener on {}", instance_id, config.stratum_port);
+    let transport = if config.tls.is_some() { "TLS" } else { "TCP" };
+    info!("{} Starting stratum listener on {} ({})", instance_id, config.stratum_port, transport);
 
     let listen_result =
         if let Some(shutdown_rx) = shutdown_rx { listener.listen_with_shutdown(shutdown_rx).await } else { listener.listen().await };
@@ -0,0 +1,93 @@
+use crate::app_config::TlsConfig;
+use anyhow::Context;
+use parking_lot::Mutex;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::rustls::{
+    self,
+    pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+};
+use tracing::{info, warn};
+
+/// Minimum time between two checks of the certificate files for modification
+pub const TLS_RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Maximum time a client may take to complete the TLS handshake
+pub const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct TlsState {
+    acceptor: TlsAcceptor,
+    cert_modified: Option<SystemTime>,
+    key_modified: Option<SystemTime>,
+    last_check: Instant,
+}
+
+/// TLS acceptor of a stratum listener, reloaded when the certificate or key file changes
+pub struct StratumTls {
+    config: TlsConfig,
+    state: Mutex<TlsState>,
+}
+
+impl StratumTls {
+    /// Load the certificate and key, failing if either is missing or invalid
+    pub fn load(config: TlsConfig) -> Result<Self, anyhow::Error> {
+        let acceptor = build_acceptor(&config)?;
+        let state = TlsState {
+            acceptor,
+            cert_modified: modified_time(&config.cert_path),
+            key_modified: modified_time(&config.key_path),
+            last_check: Instant::now(),
+        };
+        Ok(Self { config, state: Mutex::new(state) })
+    }
+
+    /// Returns the acceptor to use for a new connection, reloading the certificate first if
+    /// its files changed since the last check
+    ///
+    /// A failed reload (e.g. a half-written file during renewal) keeps the previous acceptor
+    /// and is retried on the next check.
+    pub fn acceptor(&self) -> TlsAcceptor {
+        let mut state = self.state.lock();
+        if state.last_check.elapsed() >= TLS_RELOAD_CHECK_INTERVAL {
+            state.last_check = Instant::now();
+            let cert_modified = modified_time(&self.config.cert_path);
+            let key_modified = modified_time(&self.config.key_path);
+            if cert_modified != state.cert_modified || key_modified != state.key_modified {
+                match build_acceptor(&self.config) {
+                    Ok(acceptor) => {
+                        info!("reloaded TLS certificate from {}", self.config.cert_path.display());
+                        state.acceptor = acceptor;
+                        state.cert_modified = cert_modified;
+                        state.key_modified = key_modified;
+                    }
+                    Err(e) => warn!("failed to reload TLS certificate, keeping the previous one: {:#}", e),
+                }
+            }
+        }
+        state.acceptor.clone()
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn build_acceptor(config: &TlsConfig) -> Result<TlsAcceptor, anyhow::Error> {
+    let certs = CertificateDer::pem_file_iter(&config.cert_path)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .with_context(|| format!("failed reading TLS certificate {}", config.cert_path.display()))?;
+    if certs.is_empty() {
+        return Err(anyhow::anyhow!("no certificate found in {}", config.cert_path.display()));
+    }
+    let key = PrivateKeyDer::from_pem_file(&config.key_path)
+        .with_context(|| format!("failed reading TLS private key {}", config.key_path.display()))?;
+
+    let server_config = rustls::ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .context("invalid TLS certificate or private key")?;
+    Ok(TlsAcceptor::from(Arc::new(server_config)))
+}
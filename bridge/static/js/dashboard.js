@@ -765,6 +765,69 @@ function updateBlocksChartFromBlocks(blocks, totalAllBlocks, walletFilter) {
   renderDonutChart('blocksPie', 'blocksPieLegend', top, emptyMessage);
 }
 
+function formatSompi(sompi) {
+  if (sompi == null) return '-';
+  return (Number(sompi) / 1e8).toFixed(8).replace(/\.?0+$/, '');
+}
+
+function formatChainFlag(flag) {
+  if (flag == null) return '-';
+  return flag ? 'yes' : 'no';
+}
+
+// The found block journal is optional; a failure here must not mark the dashboard offline.
+async function refreshFoundBlocks(filter) {
+  let journal;
+  try {
+    const res = await fetch('api/blocks', { cache: 'no-store' });
+    if (!res.ok) return;
+    journal = await res.json();
+  } catch {
+    return;
+  }
+
+  const workersBody = document.getElementById('foundBlockWorkersBody');
+  const blocksBody = document.getElementById('foundBlocksBody');
+  if (!workersBody || !blocksBody) return;
+
+  const matchesFilter = (item) => !filter || (item.wallet || '').includes(filter);
+  workersBody.innerHTML = '';
+  (journal.workers || []).filter(matchesFilter).forEach((w) => {
+    const counts = w.blocks || {};
+    const accepted = (counts.accepted || 0) + (counts.blue || 0) + (counts.red || 0) + (counts.matured || 0);
+    const tr = document.createElement('tr');
+    tr.className = 'border-b border-card/50';
+    tr.innerHTML = `
+      <td class="py-1.5 pr-3">${escapeHtmlAttr(w.instance || '-')}</td>
+      <td class="py-1.5 pr-3">${escapeHtmlAttr(displayWorkerName(w.worker))}</td>
+      <td class="py-1.5 pr-3 truncate" title="${escapeHtmlAttr(w.wallet || '')}">${escapeHtmlAttr(w.wallet || '-')}</td>
+      <td class="py-1.5 pr-3">${accepted}</td>
+      <td class="py-1.5 pr-3">${counts.blue || 0}</td>
+      <td class="py-1.5 pr-3">${counts.red || 0}</td>
+      <td class="py-1.5 pr-3">${counts.matured || 0}</td>
+      <td class="py-1.5 pr-3">${formatSompi(w.pending_reward_sompi)}</td>
+      <td class="py-1.5 pr-3">${formatSompi(w.matured_reward_sompi)}</td>
+    `;
+    workersBody.appendChild(tr);
+  });
+
+  blocksBody.innerHTML = '';
+  (journal.blocks || []).filter(matchesFilter).slice(0, 100).forEach((b) => {
+    const tr = document.createElement('tr');
+    tr.className = 'border-b border-card/50';
+    tr.innerHTML = `
+      <td class="py-1.5 pr-3" title="${b.submitted_at || ''}">${formatUnixSeconds(b.submitted_at)}</td>
+      <td class="py-1.5 pr-3">${escapeHtmlAttr(displayWorkerName(b.worker))}</td>
+      <td class="py-1.5 pr-3" title="${escapeHtmlAttr(b.rejection || '')}">${escapeHtmlAttr(b.status || '-')}</td>
+      <td class="py-1.5 pr-3">${formatChainFlag(b.is_chain_block)}</td>
+      <td class="py-1.5 pr-3 font-mono" title="${escapeHtmlAttr(b.merged_by || '')}">${shortHash(b.merged_by || '')}</td>
+      <td class="py-1.5 pr-3">${formatSompi(b.reward_sompi)}</td>
+      <td class="py-1.5 pr-3 font-mono" title="${escapeHtmlAttr(b.hash || '')}">${shortHash(b.hash || '')}</td>
+    `;
+    blocksBody.appendChild(tr);
+  });
+}
+
 async function refresh() {
   const loader = document.getElementById('status-loader');
   const statusText = document.getElementById('status-text');
@@ -840,6 +903,7 @@ async function refresh() {
     const dayFilter = getBlocksDayFilter();
 
     renderWalletSummary(mergedStats, filter);
+    refreshFoundBlocks(filter);
 
     let blocks = (mergedStats.blocks || []).filter(b => !filter || (b.wallet || '').includes(filter));
     blocks = filterBlocksByDays(blocks, dayFilter);
@@ -29,6 +29,7 @@ pub mod ping;
 pub mod pskb;
 pub mod reload;
 pub mod rpc;
+pub mod script;
 pub mod select;
 pub mod send;
 pub mod server;
@@ -41,6 +42,7 @@ pub mod sweep;
 pub mod theme;
 pub mod track;
 pub mod transfer;
+pub mod utxo;
 pub mod wallet;
 
 // this module is registered manually within
@@ -58,8 +60,8 @@ pub fn register_handlers(cli: &Arc<KaspaCli>) -> Result<()> {
         cli.handlers(),
         [
             account, address, close, connect, details, disconnect, estimate, exit, export, guide, help, history, rpc, list, miner,
-            message, monitor, mute, network, node, open, ping, pskb, reload, select, send, server, settings, sweep, track, transfer,
-            wallet,
+            message, monitor, mute, network, node, open, ping, pskb, reload, script, select, send, server, settings, sweep, track,
+            transfer, utxo, wallet,
             // halt,
             // theme,  start, stop
         ]
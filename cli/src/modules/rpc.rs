@@ -57,6 +57,10 @@ impl Rpc {
                 let result = rpc.get_sync_status_call(None, GetSyncStatusRequest {}).await?;
                 self.println(&ctx, result);
             }
+            RpcApiOps::GetSyncProgress => {
+                let result = rpc.get_sync_progress_call(None, GetSyncProgressRequest {}).await?;
+                self.println(&ctx, result);
+            }
             RpcApiOps::GetCurrentNetwork => {
                 let result = rpc.get_current_network_call(None, GetCurrentNetworkRequest {}).await?;
                 self.println(&ctx, result);
@@ -263,7 +267,17 @@ impl Rpc {
             }
             RpcApiOps::GetFeeEstimateExperimental => {
                 let verbose = if argv.is_empty() { false } else { argv.remove(0).parse().unwrap_or(false) };
-                let result = rpc.get_fee_estimate_experimental_call(None, GetFeeEstimateExperimentalRequest { verbose }).await?;
+                // Remaining arguments are inclusion time targets in the `<seconds>:<confidence>` format
+                let targets = argv
+                    .iter()
+                    .map(|target| {
+                        let (seconds, confidence) = target.split_once(':')?;
+                        Some(RpcFeerateTarget { target_seconds: seconds.parse().ok()?, confidence: confidence.parse().ok()? })
+                    })
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| Error::custom("Feerate targets must be specified as <seconds>:<confidence>, e.g. 60:0.95"))?;
+                let result =
+                    rpc.get_fee_estimate_experimental_call(None, GetFeeEstimateExperimentalRequest { verbose, targets }).await?;
                 self.println(&ctx, result);
             }
             RpcApiOps::GetCurrentBlockColor => {
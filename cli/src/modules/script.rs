@@ -0,0 +1,56 @@
+use crate::imports::*;
+use kaspa_txscript::script_asm;
+
+#[derive(Default, Handler)]
+#[help("Assemble or disassemble transaction scripts")]
+pub struct Script;
+
+impl Script {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        let action = argv.remove(0);
+        match action.as_str() {
+            "disasm" => {
+                if argv.len() != 1 {
+                    tprintln!(ctx, "usage: script disasm <hex script>");
+                    return Ok(());
+                }
+                let script = Vec::<u8>::from_hex(argv[0].trim_start_matches("0x"))
+                    .map_err(|_| Error::custom(format!("Supplied script is not valid hex: '{}'", argv[0])))?;
+                let text = script_asm::disassemble_annotated(&script).map_err(|err| Error::custom(err.to_string()))?;
+                tprintln!(ctx, "\n{text}\n");
+            }
+            "asm" => {
+                if argv.is_empty() {
+                    tprintln!(ctx, "usage: script asm <opcodes and data ...>");
+                    return Ok(());
+                }
+                let script = script_asm::assemble(&argv.join(" ")).map_err(|err| Error::custom(err.to_string()))?;
+                tprintln!(ctx, "\n{}\n", script.to_hex());
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("disasm <hex>", "Disassemble a script into opcodes (push data is decoded as comments)"),
+                ("asm <text>", "Assemble opcodes (e.g. 'OpDup OpBlake2b 0x<hash> OpEqualVerify OpCheckSig') into a hex script"),
+            ],
+            None,
+        )?;
+
+        Ok(())
+    }
+}
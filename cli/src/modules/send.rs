@@ -12,7 +12,7 @@ impl Send {
         let account = ctx.wallet().account()?;
 
         if argv.len() < 2 {
-            tprintln!(ctx, "usage: send <address> <amount> <priority fee>");
+            tprintln!(ctx, "usage: send <address> <amount> <priority fee> [<txid:index> ...]");
             return Ok(());
         }
 
@@ -21,6 +21,8 @@ impl Send {
         // TODO fee_rate
         let fee_rate = None;
         let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(argv.get(2))?.unwrap_or(0);
+        // optional explicit UTXO selection (coin control)
+        let utxos = (argv.len() > 3).then(|| try_parse_utxo_outpoints(&argv[3..])).transpose()?;
         let outputs = PaymentOutputs::from((address.clone(), amount_sompi));
         let abortable = Abortable::default();
         let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
@@ -32,6 +34,7 @@ impl Send {
                 fee_rate,
                 priority_fee_sompi.into(),
                 None,
+                utxos,
                 wallet_secret,
                 payment_secret,
                 &abortable,
@@ -11,7 +11,7 @@ impl Transfer {
         let account = ctx.wallet().account()?;
 
         if argv.len() < 2 {
-            tprintln!(ctx, "usage: transfer <account> <amount> <priority fee>");
+            tprintln!(ctx, "usage: transfer <account> <amount> <priority fee> [<txid:index> ...]");
             return Ok(());
         }
 
@@ -24,6 +24,8 @@ impl Transfer {
         // TODO fee_rate
         let fee_rate = None;
         let priority_fee_sompi = try_parse_optional_kaspa_as_sompi_i64(argv.get(2))?.unwrap_or(0);
+        // optional explicit UTXO selection (coin control)
+        let utxos = (argv.len() > 3).then(|| try_parse_utxo_outpoints(&argv[3..])).transpose()?;
         let target_address = target_account.receive_address()?;
         let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
 
@@ -37,6 +39,7 @@ impl Transfer {
                 fee_rate,
                 priority_fee_sompi.into(),
                 None,
+                utxos,
                 wallet_secret,
                 payment_secret,
                 &abortable,
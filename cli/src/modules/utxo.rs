@@ -0,0 +1,111 @@
+use crate::imports::*;
+
+const CONSOLIDATION_FEE_RATE_POLLER_INTERVAL_SECONDS: u64 = 30;
+
+#[derive(Default, Handler)]
+#[help("UTXO coin control: lock, unlock and consolidate account UTXOs")]
+pub struct Utxo;
+
+impl Utxo {
+    async fn main(self: Arc<Self>, ctx: &Arc<dyn Context>, mut argv: Vec<String>, _cmd: &str) -> Result<()> {
+        let ctx = ctx.clone().downcast_arc::<KaspaCli>()?;
+        let wallet = ctx.wallet();
+
+        if !wallet.is_open() {
+            return Err(Error::WalletIsNotOpen);
+        }
+
+        if argv.is_empty() {
+            return self.display_help(ctx, argv).await;
+        }
+
+        let account = ctx.wallet().account()?;
+        let action = argv.remove(0);
+
+        match action.as_str() {
+            "lock" => {
+                if argv.is_empty() {
+                    tprintln!(ctx, "usage: utxo lock <txid:index> [<txid:index> ...]");
+                    return Ok(());
+                }
+                account.utxo_context().lock_utxos(&try_parse_utxo_outpoints(&argv)?)?;
+                self.list_locked(&ctx, &account);
+            }
+            "unlock" => {
+                let outpoints = (!argv.is_empty()).then(|| try_parse_utxo_outpoints(&argv)).transpose()?;
+                account.utxo_context().unlock_utxos(outpoints.as_deref());
+                self.list_locked(&ctx, &account);
+            }
+            "locked" => {
+                self.list_locked(&ctx, &account);
+            }
+            "consolidate" => {
+                if argv.first().map(|arg| arg.as_str()) == Some("off") {
+                    wallet.clear_consolidation_policy(account.id());
+                    tprintln!(ctx, "UTXO consolidation disabled for account {}", account.name_with_id());
+                    return Ok(());
+                }
+
+                if argv.len() != 2 {
+                    tprintln!(ctx, "usage: utxo consolidate <min utxo count> <max fee rate> | utxo consolidate off");
+                    return Ok(());
+                }
+
+                let min_utxo_count =
+                    argv[0].parse::<u64>().map_err(|_| Error::custom(format!("Invalid UTXO count: '{}'", argv[0])))?;
+                let max_fee_rate = argv[1].parse::<f64>().map_err(|_| Error::custom(format!("Invalid fee rate: '{}'", argv[1])))?;
+                let (wallet_secret, payment_secret) = ctx.ask_wallet_secret(Some(&account)).await?;
+                wallet
+                    .clone()
+                    .accounts_consolidation_policy_set(
+                        *account.id(),
+                        wallet_secret,
+                        payment_secret,
+                        Some(ConsolidationPolicy::new(min_utxo_count, max_fee_rate)),
+                    )
+                    .await?;
+                // the consolidation policy is evaluated on fee rate updates
+                wallet.clone().fee_rate_poller_enable(CONSOLIDATION_FEE_RATE_POLLER_INTERVAL_SECONDS).await?;
+                tprintln!(
+                    ctx,
+                    "UTXO consolidation enabled for account {}: more than {min_utxo_count} UTXOs at fee rate <= {max_fee_rate}",
+                    account.name_with_id()
+                );
+            }
+            v => {
+                tprintln!(ctx, "unknown command: '{v}'\r\n");
+                return self.display_help(ctx, argv).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn list_locked(&self, ctx: &Arc<KaspaCli>, account: &Arc<dyn Account>) {
+        let locked = account.utxo_context().locked_utxos();
+        if locked.is_empty() {
+            tprintln!(ctx, "No locked UTXOs");
+        } else {
+            tprintln!(ctx, "Locked UTXOs:");
+            locked.iter().for_each(|id| tprintln!(ctx, "{}:{}", id.transaction_id, id.index));
+        }
+    }
+
+    async fn display_help(self: Arc<Self>, ctx: Arc<KaspaCli>, _argv: Vec<String>) -> Result<()> {
+        ctx.term().help(
+            &[
+                ("lock <txid:index> ...", "Exclude UTXOs from automatic input selection"),
+                ("unlock [<txid:index> ...]", "Unlock the specified (or all) UTXOs"),
+                ("locked", "List locked UTXOs"),
+                (
+                    "consolidate <count> <fee rate>",
+                    "Compound UTXOs when their count exceeds <count> and the fee rate is <= <fee rate>",
+                ),
+                ("consolidate off", "Disable UTXO consolidation"),
+            ],
+            None,
+        )?;
+
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
 use crate::error::Error;
 use crate::result::Result;
 use kaspa_consensus_core::constants::SOMPI_PER_KASPA;
+use kaspa_consensus_core::tx::TransactionId;
+use kaspa_wallet_core::utxo::UtxoEntryId;
 use std::fmt::Display;
+use std::str::FromStr;
 
 pub fn try_parse_required_nonzero_kaspa_as_sompi_u64<S: ToString + Display>(kaspa_amount: Option<S>) -> Result<u64> {
     if let Some(kaspa_amount) = kaspa_amount {
@@ -58,3 +61,20 @@ pub fn try_parse_optional_kaspa_as_sompi_i64<S: ToString + Display>(kaspa_amount
         Ok(None)
     }
 }
+
+/// Parse a list of UTXO outpoints supplied as `<transaction id>:<index>`.
+pub fn try_parse_utxo_outpoints<S: AsRef<str>>(outpoints: &[S]) -> Result<Vec<UtxoEntryId>> {
+    outpoints
+        .iter()
+        .map(|outpoint| {
+            let outpoint = outpoint.as_ref();
+            let (transaction_id, index) = outpoint
+                .split_once(':')
+                .ok_or_else(|| Error::custom(format!("Supplied outpoint is not valid (expecting <txid>:<index>): '{outpoint}'")))?;
+            let transaction_id = TransactionId::from_str(transaction_id)
+                .map_err(|_| Error::custom(format!("Supplied transaction id is not valid: '{transaction_id}'")))?;
+            let index = index.parse().map_err(|_| Error::custom(format!("Supplied outpoint index is not valid: '{index}'")))?;
+            Ok(UtxoEntryId { transaction_id, index })
+        })
+        .collect()
+}
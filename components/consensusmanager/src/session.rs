@@ -327,6 +327,15 @@ impl ConsensusSessionOwned {
         self.clone().spawn_blocking(move |c| c.get_transactions_by_accepting_daa_score(accepting_daa_score, tx_ids, tx_type)).await
     }
 
+    pub async fn async_get_chain_block_hashes_by_daa_score_range(
+        &self,
+        start_daa_score: u64,
+        end_daa_score: u64,
+        limit: usize,
+    ) -> ConsensusResult<Vec<Hash>> {
+        self.clone().spawn_blocking(move |c| c.get_chain_block_hashes_by_daa_score_range(start_daa_score, end_daa_score, limit)).await
+    }
+
     pub async fn async_get_transactions_by_block_acceptance_data(
         &self,
         accepting_block: Hash,
@@ -248,6 +248,17 @@ pub trait ConsensusApi: Send + Sync {
         unimplemented!()
     }
 
+    /// Returns up to `limit` selected chain block hashes, in chain order, whose DAA scores fall within the inclusive
+    /// range `[start_daa_score, end_daa_score]`. Only chain blocks above the retention period root are considered.
+    fn get_chain_block_hashes_by_daa_score_range(
+        &self,
+        start_daa_score: u64,
+        end_daa_score: u64,
+        limit: usize,
+    ) -> ConsensusResult<Vec<Hash>> {
+        unimplemented!()
+    }
+
     fn get_transactions_by_block_acceptance_data(
         &self,
         accepting_block: Hash,
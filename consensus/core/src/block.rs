@@ -1,6 +1,7 @@
 use crate::{
     BlueWorkType,
     coinbase::MinerData,
+    errors::tx::TxRuleError,
     header::Header,
     tx::{Transaction, TransactionId},
 };
@@ -97,6 +98,10 @@ pub trait TemplateTransactionSelector {
     /// track the selection state and discard the rejected tx from internal occupation calculations
     fn reject_selection(&mut self, tx_id: TransactionId);
 
+    /// Reports the validation error of a transaction right before it is passed to `reject_selection`.
+    /// Selectors may record it for diagnostic purposes, the default implementation ignores it
+    fn report_invalid_transaction(&mut self, _tx_id: TransactionId, _error: &TxRuleError) {}
+
     /// Determine whether this was an overall successful selection episode
     fn is_successful(&self) -> bool;
 }
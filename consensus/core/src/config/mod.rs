@@ -42,6 +42,9 @@ pub struct Config {
     /// Enable the UTXO index
     pub utxoindex: bool,
 
+    /// Enable the address history index (requires the UTXO index)
+    pub address_history_index: bool,
+
     /// Enable RPC commands which affect the state of the node
     pub unsafe_rpc: bool,
 
@@ -89,6 +92,7 @@ impl Config {
             is_archival: false,
             enable_sanity_checks: false,
             utxoindex: false,
+            address_history_index: false,
             unsafe_rpc: false,
             enable_unsynced_mining: false,
             enable_mainnet_mining: false,
@@ -11,6 +11,7 @@ use kaspa_notify::{
         single::{OverallSubscription, UtxosChangedSubscription, VirtualChainChangedSubscription},
     },
 };
+use kaspa_utils::networking::{NetAddress, PeerId};
 use std::sync::Arc;
 
 full_featured! {
@@ -42,6 +43,9 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "SyncProgressChanged notification: {:?}", "_0.progress.as_ref().map(|p| p.phase)")]
+    SyncProgressChanged(SyncProgressChangedNotification),
 }
 }
 
@@ -182,3 +186,52 @@ pub struct PruningPointUtxoSetOverrideNotification {}
 
 #[derive(Debug, Clone)]
 pub struct NewBlockTemplateNotification {}
+
+/// Phase of an initial block download (IBD)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncPhase {
+    /// Downloading and validating the pruning point proof
+    PruningProof,
+    /// Downloading block headers
+    Headers,
+    /// Downloading the pruning point UTXO set
+    UtxoSet,
+    /// Downloading the pruning point SMT state
+    SmtState,
+    /// Downloading block bodies
+    Bodies,
+    /// Resolving the virtual state after all block data was received
+    VirtualResolution,
+}
+
+/// Progress of a running IBD phase
+#[derive(Debug, Clone)]
+pub struct SyncProgress {
+    pub phase: SyncPhase,
+    pub syncer_id: PeerId,
+    pub syncer_address: NetAddress,
+    /// Number of items (headers, UTXOs, SMT lanes, blocks) processed so far in this phase
+    pub processed: u64,
+    /// Total number of items of this phase, if known in advance
+    pub total: Option<u64>,
+    /// DAA score range covered by this phase, if it processes blocks
+    pub low_daa_score: u64,
+    pub high_daa_score: u64,
+    /// DAA score of the last processed block
+    pub current_daa_score: u64,
+    /// Estimated remaining time of this phase in seconds, once enough progress was made for a meaningful estimation.
+    /// Unavailable for phases with neither a known total nor a DAA score range
+    pub eta_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SyncProgressChangedNotification {
+    /// Current IBD progress, `None` once IBD completed
+    pub progress: Option<SyncProgress>,
+}
+
+impl SyncProgressChangedNotification {
+    pub fn new(progress: Option<SyncProgress>) -> Self {
+        Self { progress }
+    }
+}
@@ -74,6 +74,27 @@ impl DbBranchVersionStore {
         Ok(count)
     }
 
+    #[cfg(feature = "test-smt-pruning-diagnostics")]
+    pub(crate) fn delete_entries_at_or_below(
+        &self,
+        cutoff_blue_score: u64,
+        batch_size: usize,
+        progress: impl FnMut(u64),
+    ) -> StoreResult<u64> {
+        crate::compaction::delete_prefix_entries_at_or_below(
+            &self.db,
+            self.prefix,
+            cutoff_blue_score,
+            batch_size,
+            |key_bytes| {
+                let key = BranchVersionKey::ref_from_bytes(key_bytes)
+                    .map_err(|e| StoreError::DataInconsistency(format!("branch version key: {e}")))?;
+                Ok(key.rev_blue_score.blue_score())
+            },
+            progress,
+        )
+    }
+
     /// Find the latest canonical version in `[min_blue_score, target_blue_score]`.
     ///
     /// Iterates via `get_at` from `target_blue_score` downward, stopping at
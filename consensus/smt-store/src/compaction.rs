@@ -0,0 +1,55 @@
+//! Offline removal of versioned SMT entries at or below a pruning cutoff.
+//!
+//! [`SmtStores::prune`](crate::processor::SmtStores::prune) discovers stale versions through the
+//! score index, so versions whose score-index records are already gone (an interrupted prune, or a
+//! cutoff that moved across an upgrade) are never revisited. Compaction scans the versioned stores by
+//! key instead and deletes every entry at or below the cutoff, regardless of how it got there.
+//!
+//! The scan reads and deletes from the same DB, so it must only run while no node holds it open.
+
+use kaspa_database::prelude::{DB, StoreError, StoreResult};
+use rocksdb::WriteBatch;
+
+/// Deletes every entry under `prefix` whose blue score (as extracted by `blue_score_of`) is at or
+/// below `cutoff_blue_score`. Deletes are committed in batches of `batch_size` keys and `progress`
+/// is invoked with the running total after each committed batch. Returns the number of deleted entries.
+pub(crate) fn delete_prefix_entries_at_or_below(
+    db: &DB,
+    prefix: u8,
+    cutoff_blue_score: u64,
+    batch_size: usize,
+    blue_score_of: impl Fn(&[u8]) -> StoreResult<u64>,
+    mut progress: impl FnMut(u64),
+) -> StoreResult<u64> {
+    let prefix_bytes = [prefix];
+    let batch_size = batch_size.max(1);
+    // The iterator reads from an implicit snapshot taken at creation, so committing deletes
+    // while it is open does not affect the scan
+    let mut iter = db.raw_iterator();
+    iter.seek(prefix_bytes);
+
+    let mut batch = WriteBatch::default();
+    let mut deleted = 0u64;
+    while iter.valid() {
+        let Some(key_bytes) = iter.key() else { break };
+        if !key_bytes.starts_with(&prefix_bytes) {
+            break;
+        }
+        if blue_score_of(key_bytes)? <= cutoff_blue_score {
+            batch.delete(key_bytes);
+            deleted += 1;
+            if batch.len() >= batch_size {
+                db.write(std::mem::take(&mut batch)).map_err(StoreError::DbError)?;
+                progress(deleted);
+            }
+        }
+        iter.next();
+    }
+    iter.status().map_err(StoreError::DbError)?;
+
+    if !batch.is_empty() {
+        db.write(batch).map_err(StoreError::DbError)?;
+        progress(deleted);
+    }
+    Ok(deleted)
+}
@@ -2,6 +2,8 @@ use kaspa_hashes::Hash;
 
 pub mod branch_version_store;
 pub mod cache;
+#[cfg(feature = "test-smt-pruning-diagnostics")]
+mod compaction;
 pub mod keys;
 pub mod lane_version_store;
 pub mod maybe_fork;
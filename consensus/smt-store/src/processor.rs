@@ -397,6 +397,36 @@ impl SmtStores {
             score_index: self.score_index.count_entries_at_or_below(cutoff_blue_score)?,
         })
     }
+
+    #[cfg(feature = "test-smt-pruning-diagnostics")]
+    #[doc(hidden)]
+    /// Offline repair helper: delete every versioned SMT entry at or below
+    /// `cutoff_blue_score` by scanning the stores directly rather than going
+    /// through the score index like [`Self::prune`]. No node may hold the DB.
+    ///
+    /// Lane and branch versions are deleted before the score index, so an
+    /// interrupted run still leaves the score-index records that regular
+    /// pruning needs to find the remainder. `progress` receives the store
+    /// name and its running delete count after every committed batch.
+    pub fn compact_entries_at_or_below(
+        &self,
+        cutoff_blue_score: u64,
+        batch_size: usize,
+        mut progress: impl FnMut(&'static str, u64),
+    ) -> StoreResult<StaleSmtEntriesCount> {
+        let lane_versions =
+            self.lane_version.delete_entries_at_or_below(cutoff_blue_score, batch_size, |n| progress("lane_versions", n))?;
+        let branch_versions =
+            self.branch_version.delete_entries_at_or_below(cutoff_blue_score, batch_size, |n| progress("branch_versions", n))?;
+        let score_index =
+            self.score_index.delete_entries_at_or_below(cutoff_blue_score, batch_size, |n| progress("score_index", n))?;
+        self.evict_caches_below_score(cutoff_blue_score);
+        Ok(StaleSmtEntriesCount {
+            branch_versions: branch_versions as usize,
+            lane_versions: lane_versions as usize,
+            score_index: score_index as usize,
+        })
+    }
 }
 
 /// Abstraction over lane change collections.
@@ -723,4 +753,43 @@ mod tests {
         assert_eq!(got.block_hash(), pre_restart_bh);
         assert_eq!(*got.data(), pre_restart_tip);
     }
+
+    /// Compaction must also remove versions that are no longer reachable
+    /// through the score index, which regular pruning would leave behind.
+    #[cfg(feature = "test-smt-pruning-diagnostics")]
+    #[test]
+    fn compact_removes_unindexed_stale_versions() {
+        use crate::keys::ScoreIndexKind;
+
+        let (_lt, db, stores) = make_stores();
+        let e = entity();
+        for score in 1..=6u64 {
+            let bh = hash(score as u8);
+            stores.lane_version.put(DirectDbWriter::new(&db), hash(0xEE), score, bh, &hash(0xCC)).unwrap();
+            stores.branch_version.put(DirectDbWriter::new(&db), e.depth, e.node_key, score, bh, internal(hash(0xDD))).unwrap();
+            // Only the upper half is indexed, as if an earlier prune dropped the lower records
+            if score > 3 {
+                stores.score_index.put(DirectDbWriter::new(&db), score, ScoreIndexKind::LeafUpdate, bh, &[hash(0xEE)], 7).unwrap();
+            }
+        }
+
+        let stale = stores.count_entries_at_or_below(4).unwrap();
+        assert_eq!(stale, StaleSmtEntriesCount { branch_versions: 4, lane_versions: 4, score_index: 1 });
+
+        let mut reported = Vec::new();
+        let compacted = stores.compact_entries_at_or_below(4, 3, |store, n| reported.push((store, n))).unwrap();
+        assert_eq!(compacted, stale);
+        assert_eq!(
+            reported,
+            vec![("lane_versions", 3), ("lane_versions", 4), ("branch_versions", 3), ("branch_versions", 4), ("score_index", 1)]
+        );
+        assert_eq!(stores.count_entries_at_or_below(4).unwrap().total(), 0);
+        assert_eq!(
+            stores.count_entries_at_or_below(6).unwrap(),
+            StaleSmtEntriesCount { branch_versions: 2, lane_versions: 2, score_index: 2 }
+        );
+
+        let got = stores.get_lane(hash(0xEE), bounds(10, 0), |_| true).unwrap();
+        assert_eq!(got.blue_score(), 6);
+    }
 }
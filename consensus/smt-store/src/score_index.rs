@@ -105,6 +105,27 @@ impl DbScoreIndex {
         Ok(count)
     }
 
+    #[cfg(feature = "test-smt-pruning-diagnostics")]
+    pub(crate) fn delete_entries_at_or_below(
+        &self,
+        cutoff_blue_score: u64,
+        batch_size: usize,
+        progress: impl FnMut(u64),
+    ) -> StoreResult<u64> {
+        crate::compaction::delete_prefix_entries_at_or_below(
+            &self.db,
+            self.prefix,
+            cutoff_blue_score,
+            batch_size,
+            |key_bytes| {
+                let key = ScoreIndexKey::try_ref_from_key_bytes(key_bytes)
+                    .map_err(|e| StoreError::DataInconsistency(format!("score index key: {e}")))?;
+                Ok(key.rev_blue_score.blue_score())
+            },
+            progress,
+        )
+    }
+
     /// Iterate `LeafUpdate` entries across the inclusive score band.
     ///
     /// Skips `Structural` entries. Used by expiration logic to find lanes whose
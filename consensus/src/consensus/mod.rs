@@ -945,6 +945,22 @@ impl ConsensusApi for Consensus {
         self.get_transactions_by_accepting_block(accepting_block, tx_ids, tx_type)
     }
 
+    fn get_chain_block_hashes_by_daa_score_range(
+        &self,
+        start_daa_score: u64,
+        end_daa_score: u64,
+        limit: usize,
+    ) -> ConsensusResult<Vec<Hash>> {
+        // Hold the pruning lock so that the retention period root and the selected chain store are consistent
+        let _guard = self.pruning_lock.blocking_read();
+        Ok(self.virtual_processor.find_chain_block_hashes_in_daa_score_range(
+            start_daa_score,
+            end_daa_score,
+            limit,
+            self.get_retention_period_root(),
+        )?)
+    }
+
     fn get_transactions_by_block_acceptance_data(
         &self,
         accepting_block: Hash,
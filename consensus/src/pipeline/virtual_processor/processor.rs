@@ -1372,6 +1372,7 @@ impl VirtualStateProcessor {
         for (tx, res) in txs.iter().zip(results) {
             match res {
                 Err(e) => {
+                    tx_selector.report_invalid_transaction(tx.id(), &e);
                     invalid_transactions.insert(tx.id(), e);
                     tx_selector.reject_selection(tx.id());
                 }
@@ -1394,6 +1395,7 @@ impl VirtualStateProcessor {
             for (tx, res) in next_batch.into_iter().zip(next_batch_results) {
                 match res {
                     Err(e) => {
+                        tx_selector.report_invalid_transaction(tx.id(), &e);
                         invalid_transactions.insert(tx.id(), e);
                         tx_selector.reject_selection(tx.id());
                         has_rejections = true;
@@ -352,6 +352,55 @@ impl VirtualStateProcessor {
         Ok(matching_chain_block_hash)
     }
 
+    /// Returns up to `limit` selected chain block hashes, in chain order, whose DAA scores fall within the inclusive
+    /// range `[start_daa_score, end_daa_score]`. The search is restricted to the chain above `retention_period_root_hash`.
+    pub fn find_chain_block_hashes_in_daa_score_range(
+        &self,
+        start_daa_score: u64,
+        end_daa_score: u64,
+        limit: usize,
+        retention_period_root_hash: Hash,
+    ) -> UtxoInquirerResult<Vec<Hash>> {
+        let sc_read = self.selected_chain_store.read();
+
+        let retention_period_root_index = sc_read
+            .get_by_hash(retention_period_root_hash)
+            .map_err(|_| UtxoInquirerError::MissingIndexForHash(retention_period_root_hash))?;
+        let (tip_index, _) = sc_read.get_tip().map_err(|_| UtxoInquirerError::MissingTipData)?;
+
+        let daa_score_at = |index: u64| {
+            let hash = sc_read.get_by_index(index).map_err(|_| UtxoInquirerError::MissingHashAtIndex(index))?;
+            let daa_score =
+                self.headers_store.get_daa_score(hash).map_err(|_| UtxoInquirerError::MissingCompactHeaderForBlockHash(hash))?;
+            Ok::<_, UtxoInquirerError>((hash, daa_score))
+        };
+
+        // Binary search for the lowest chain index whose DAA score is at least `start_daa_score`
+        let (mut low_index, mut high_index) = (retention_period_root_index, tip_index + 1);
+        while low_index < high_index {
+            let mid = low_index + (high_index - low_index) / 2;
+            if daa_score_at(mid)?.1 < start_daa_score {
+                low_index = mid + 1;
+            } else {
+                high_index = mid;
+            }
+        }
+
+        let mut hashes = Vec::new();
+        for index in low_index..=tip_index {
+            if hashes.len() >= limit {
+                break;
+            }
+            let (hash, daa_score) = daa_score_at(index)?;
+            if daa_score > end_daa_score {
+                break;
+            }
+            hashes.push(hash);
+        }
+
+        Ok(hashes)
+    }
+
     /// Finds a transaction's containing block hash and index within block through
     /// the accepting block acceptance data
     fn _find_containing_blocks_and_indices_from_acceptance_data(
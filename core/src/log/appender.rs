@@ -1,6 +1,7 @@
 use super::consts::{
     LOG_ARCHIVE_SUFFIX, LOG_FILE_BASE_ROLLS, LOG_FILE_MAX_ROLLS, LOG_FILE_MAX_SIZE, LOG_LINE_PATTERN, LOG_LINE_PATTERN_COLORED,
 };
+use super::{LogFormat, json::JsonEncoder};
 use log::LevelFilter;
 use log4rs::{
     append::{
@@ -24,15 +25,15 @@ pub(super) struct AppenderSpec {
 }
 
 impl AppenderSpec {
-    pub fn console(name: &'static str, level: Option<LevelFilter>) -> Self {
-        Self::new(
-            name,
-            level,
-            Box::new(ConsoleAppender::builder().encoder(Box::new(ForkEncoder::new(LOG_LINE_PATTERN_COLORED))).build()),
-        )
+    pub fn console(name: &'static str, level: Option<LevelFilter>, format: LogFormat) -> Self {
+        let encoder: Box<dyn Encode> = match format {
+            LogFormat::Text => Box::new(ForkEncoder::new(LOG_LINE_PATTERN_COLORED)),
+            LogFormat::Json => Box::new(JsonEncoder),
+        };
+        Self::new(name, level, Box::new(ConsoleAppender::builder().encoder(encoder).build()))
     }
 
-    pub fn roller(name: &'static str, level: Option<LevelFilter>, log_dir: &str, file_name: &str) -> Self {
+    pub fn roller(name: &'static str, level: Option<LevelFilter>, log_dir: &str, file_name: &str, format: LogFormat) -> Self {
         let appender = {
             let trigger = Box::new(SizeTrigger::new(LOG_FILE_MAX_SIZE));
 
@@ -46,10 +47,11 @@ impl AppenderSpec {
             );
 
             let compound_policy = Box::new(CompoundPolicy::new(trigger, roller));
-            let file_appender = RollingFileAppender::builder()
-                .encoder(Box::new(PatternEncoder::new(LOG_LINE_PATTERN)))
-                .build(file_path, compound_policy)
-                .unwrap();
+            let encoder: Box<dyn Encode> = match format {
+                LogFormat::Text => Box::new(PatternEncoder::new(LOG_LINE_PATTERN)),
+                LogFormat::Json => Box::new(JsonEncoder),
+            };
+            let file_appender = RollingFileAppender::builder().encoder(encoder).build(file_path, compound_policy).unwrap();
 
             Box::new(file_appender) as Box<dyn Append>
         };
@@ -0,0 +1,90 @@
+use log::{
+    Record,
+    kv::{self, Key, Value, VisitSource},
+};
+use log4rs::encode::{Encode, Write};
+use serde_json::{Map, Number, Value as JsonValue};
+
+/// Encodes each record as a single line JSON object of the form
+/// `{"timestamp":..,"level":..,"target":..,"message":..,"fields":{..}}`,
+/// where `fields` holds the key-values attached to the record (e.g. `peer`, `block_hash` or `tx_id`)
+#[derive(Debug, Default)]
+pub(super) struct JsonEncoder;
+
+impl Encode for JsonEncoder {
+    fn encode(&self, w: &mut dyn Write, record: &Record) -> anyhow::Result<()> {
+        let mut line = serde_json::to_vec(&json_record(record))?;
+        line.push(b'\n');
+        w.write_all(&line)?;
+        Ok(())
+    }
+}
+
+fn json_record(record: &Record) -> JsonValue {
+    let mut object = Map::new();
+    object.insert("timestamp".into(), chrono::Local::now().format("%Y-%m-%dT%H:%M:%S%.3f%:z").to_string().into());
+    object.insert("level".into(), record.level().as_str().into());
+    object.insert("target".into(), record.target().into());
+    object.insert("message".into(), record.args().to_string().into());
+    let mut fields = FieldCollector(Map::new());
+    // The collector never fails
+    let _ = record.key_values().visit(&mut fields);
+    if !fields.0.is_empty() {
+        object.insert("fields".into(), fields.0.into());
+    }
+    object.into()
+}
+
+struct FieldCollector(Map<String, JsonValue>);
+
+impl<'kvs> VisitSource<'kvs> for FieldCollector {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        self.0.insert(key.as_str().to_string(), json_value(&value));
+        Ok(())
+    }
+}
+
+/// Keeps numbers and booleans typed, anything else is rendered with its `Display` implementation
+fn json_value(value: &Value) -> JsonValue {
+    if let Some(v) = value.to_bool() {
+        v.into()
+    } else if let Some(v) = value.to_u64() {
+        v.into()
+    } else if let Some(v) = value.to_i64() {
+        v.into()
+    } else if let Some(v) = value.to_f64().and_then(Number::from_f64) {
+        v.into()
+    } else {
+        value.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::Level;
+
+    #[test]
+    fn test_json_record() {
+        let fields: &[(&str, Value)] =
+            &[("peer", Value::from_display(&"10.0.0.1:16111")), ("count", Value::from(3u64)), ("synced", Value::from(true))];
+        let json = json_record(
+            &Record::builder()
+                .level(Level::Warn)
+                .target("kaspa_p2p_lib::core::hub")
+                .args(format_args!("Peer {} misbehaved", "10.0.0.1:16111"))
+                .key_values(&fields)
+                .build(),
+        );
+        assert_eq!(json["level"], "WARN");
+        assert_eq!(json["target"], "kaspa_p2p_lib::core::hub");
+        assert_eq!(json["message"], "Peer 10.0.0.1:16111 misbehaved");
+        assert_eq!(json["fields"], serde_json::json!({ "peer": "10.0.0.1:16111", "count": 3, "synced": true }));
+        assert!(json["timestamp"].as_str().is_some_and(|t| t.contains('T')));
+
+        let json = json_record(&Record::builder().level(Level::Info).target("kaspad").args(format_args!("line\n\"quoted\"")).build());
+        assert_eq!(json["message"], "line\n\"quoted\"");
+        assert!(json.get("fields").is_none());
+        assert!(!serde_json::to_string(&json).unwrap().contains('\n'));
+    }
+}
@@ -2,6 +2,10 @@
 //!
 //! For the macros to properly compile, the calling crate must add a dependency to
 //! crate log (ie. `log.workspace = true`) when target architecture is not wasm32.
+//!
+//! With [`LogFormat::Json`], key-values attached to a record are emitted as structured fields.
+//! Call sites use the `peer`, `block_hash` and `tx_id` keys where relevant, for instance
+//! `info!(block_hash:% = hash; "Accepted block {}", hash)`. These are ignored by the text format.
 
 #[allow(unused_imports)]
 pub use log::{Level, LevelFilter};
@@ -13,6 +17,7 @@ cfg_if::cfg_if! {
 
         mod appender;
         mod consts;
+        mod json;
         mod logger;
 
         pub use appender::FORK_KEYWORD;
@@ -23,8 +28,44 @@ pub fn set_log_level(level: LevelFilter) {
     workflow_log::set_log_level(level);
 }
 
+/// Output format of the console and file log appenders
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human readable lines
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(format!("unknown log format `{s}`, expected one of: text, json")),
+        }
+    }
+}
+
+impl std::fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogFormat::Text => write!(f, "text"),
+            LogFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub fn init_logger(log_dir: Option<&str>, filters: &str) {
+    init_logger_with_format(log_dir, filters, LogFormat::Text)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn init_logger_with_format(log_dir: Option<&str>, filters: &str, format: LogFormat) {
     use crate::log::appender::AppenderSpec;
     use log4rs::{Config, config::Root};
     use std::iter::once;
@@ -36,10 +77,10 @@ pub fn init_logger(log_dir: Option<&str>, filters: &str) {
     let level = LevelFilter::Info;
     let loggers = logger::Builder::new().root_level(level).parse_env(DEFAULT_LOGGER_ENV).parse_expression(filters).build();
 
-    let mut stdout_appender = AppenderSpec::console(CONSOLE_APPENDER, None);
-    let mut file_appender = log_dir.map(|x| AppenderSpec::roller(LOG_FILE_APPENDER, None, x, LOG_FILE_NAME));
+    let mut stdout_appender = AppenderSpec::console(CONSOLE_APPENDER, None, format);
+    let mut file_appender = log_dir.map(|x| AppenderSpec::roller(LOG_FILE_APPENDER, None, x, LOG_FILE_NAME, format));
     let mut err_file_appender =
-        log_dir.map(|x| AppenderSpec::roller(ERR_LOG_FILE_APPENDER, Some(LevelFilter::Warn), x, ERR_LOG_FILE_NAME));
+        log_dir.map(|x| AppenderSpec::roller(ERR_LOG_FILE_APPENDER, Some(LevelFilter::Warn), x, ERR_LOG_FILE_NAME, format));
     let appenders = once(&mut stdout_appender).chain(&mut file_appender).chain(&mut err_file_appender).map(|x| x.appender());
 
     let config = Config::builder()
@@ -67,7 +108,7 @@ pub fn try_init_logger(filters: &str) {
     const CONSOLE_APPENDER: &str = "stdout";
 
     let loggers = logger::Builder::new().root_level(LevelFilter::Info).parse_env(DEFAULT_LOGGER_ENV).parse_expression(filters).build();
-    let mut stdout_appender = AppenderSpec::console(CONSOLE_APPENDER, None);
+    let mut stdout_appender = AppenderSpec::console(CONSOLE_APPENDER, None, LogFormat::Text);
     let config = Config::builder()
         .appender(stdout_appender.appender())
         .loggers(loggers.items())
@@ -30,6 +30,7 @@ blake2b_hasher! {
     struct MuHashFinalizeHash => b"MuHashFinalize",
     struct PersonalMessageSigningHash => b"PersonalMessageSigningHash",
     struct CovenantID => b"CovenantID",
+    struct PruningPointSnapshotHash => b"PruningPointSnapshot",
 }
 
 sha256_hasher! {
@@ -1,4 +1,4 @@
-use crate::script_builder;
+use crate::{script_asm, script_builder};
 use thiserror::Error;
 use wasm_bindgen::{JsError, JsValue};
 use workflow_wasm::jserror::JsErrorData;
@@ -17,6 +17,9 @@ pub enum Error {
     #[error(transparent)]
     ScriptBuilder(#[from] script_builder::ScriptBuilderError),
 
+    #[error(transparent)]
+    ScriptAsm(#[from] script_asm::ScriptAsmError),
+
     #[error("{0}")]
     ParseInt(#[from] std::num::ParseIntError),
 
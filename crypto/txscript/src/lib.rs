@@ -7,6 +7,7 @@ pub mod error;
 pub mod hex;
 pub mod opcodes;
 pub mod result;
+pub mod script_asm;
 pub mod script_builder;
 pub mod script_class;
 pub mod standard;
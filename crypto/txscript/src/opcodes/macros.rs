@@ -133,6 +133,14 @@ macro_rules! opcode_list {
             }
         }
 
+        /// Opcode names as declared in the opcode table, followed by their aliases.
+        pub const OPCODE_NAMES: &[(&str, u8)] = &[
+            $(
+                (stringify!($name), $num),
+                $( (stringify!($alias), $num), )?
+            )*
+        ];
+
         use crate::script_builder::{ScriptBuilder, ScriptBuilderResult};
 
         #[allow(unused_comparisons)]
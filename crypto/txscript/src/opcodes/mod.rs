@@ -1718,6 +1718,24 @@ opcode_list! {
     opcode OpInvalidOpCode<0xff, 1>(self, vm) Err(TxScriptError::InvalidOpcode(format!("{self:?}")))
 }
 
+/// Returns the name of the opcode as declared in the opcode table.
+pub fn opcode_name(opcode: u8) -> &'static str {
+    // every byte value is declared in the opcode table and primary names precede aliases
+    OPCODE_NAMES.iter().find(|(_, code)| *code == opcode).map(|(name, _)| *name).unwrap_or("OpInvalidOpCode")
+}
+
+/// Resolves an opcode by its name or alias. Matching ignores case and underscores
+/// and the `Op` prefix may be omitted for non-numeric opcodes (i.e. `CheckSig`, `OP_CHECKSIG`).
+pub fn opcode_from_name(name: &str) -> Option<u8> {
+    let name = name.replace('_', "").to_uppercase();
+    OPCODE_NAMES.iter().find_map(|(candidate, code)| {
+        let suffix = &candidate[2..];
+        let matches =
+            candidate.to_uppercase() == name || (!suffix.starts_with(|c: char| c.is_ascii_digit()) && suffix.to_uppercase() == name);
+        matches.then_some(*code)
+    })
+}
+
 // converts an opcode from the list of Op0 to Op16 to its associated value
 #[allow(clippy::borrowed_box)]
 pub fn to_small_int<T: VerifiableTransaction, Reused: SigHashReusedValues>(opcode: &Box<dyn OpCodeImplementation<T, Reused>>) -> u8 {
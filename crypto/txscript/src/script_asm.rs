@@ -0,0 +1,279 @@
+//!
+//! Script assembler and disassembler.
+//!
+//! The textual script representation is a whitespace-separated list of tokens:
+//!
+//! - opcode names as declared in the opcode table (`OpCheckSig`, `CheckSig` and `OP_CHECKSIG` are equivalent)
+//! - `0x`-prefixed hex data, pushed using the canonical push encoding
+//! - an explicit push opcode followed by hex data (`OpData1 0x05`, `OpPushData1 0x...`), preserving non-canonical pushes
+//! - decimal integers, pushed as script numbers
+//! - `'text'` quoted strings (without whitespace), pushed as UTF-8 bytes
+//! - `#` comments extending to the end of the line
+//!
+//! [`disassemble`] produces this representation, so any parseable script survives
+//! an [`assemble`] round trip byte-for-byte.
+//!
+
+use crate::{
+    EngineFlags, TxScriptError, deserialize_i64,
+    opcodes::{
+        OP_1_NEGATE_VAL, OP_DATA_MAX_VAL, OP_DATA_MIN_VAL, OP_SMALL_INT_MAX_VAL, OP_SMALL_INT_MIN_VAL, codes::*, opcode_from_name,
+        opcode_name,
+    },
+    parse_script,
+    script_builder::{ScriptBuilder, ScriptBuilderError},
+    zk_precompiles::tags::ZkTag,
+};
+use kaspa_consensus_core::{hashing::sighash::SigHashReusedValuesUnsync, tx::PopulatedTransaction};
+use kaspa_utils::hex::{FromHex, ToHex};
+use std::fmt::{Display, Formatter};
+use thiserror::Error;
+
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
+pub enum ScriptAsmError {
+    #[error("unknown token '{0}'")]
+    UnknownToken(String),
+
+    #[error("invalid hex data '{0}'")]
+    InvalidHex(String),
+
+    #[error("push opcode {0} must be followed by hex data")]
+    MissingPushData(&'static str),
+
+    #[error("push opcode {0} can not push {1} bytes of data")]
+    InvalidPushLength(&'static str, usize),
+
+    #[error(transparent)]
+    ScriptBuilder(#[from] ScriptBuilderError),
+}
+
+pub type ScriptAsmResult<T> = std::result::Result<T, ScriptAsmError>;
+
+/// A single disassembled script instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptInstruction {
+    /// Offset of the instruction within the script
+    pub offset: usize,
+    pub opcode: u8,
+    /// Data pushed by the instruction (empty for non-push opcodes)
+    pub data: Vec<u8>,
+    /// Human-readable interpretation of the pushed data, if any
+    pub annotation: Option<String>,
+}
+
+impl ScriptInstruction {
+    pub fn name(&self) -> &'static str {
+        opcode_name(self.opcode)
+    }
+
+    /// Returns `true` if the opcode carries push data (`OpData#` and `OpPushData#` opcodes)
+    pub fn is_data_push(&self) -> bool {
+        is_data_push_opcode(self.opcode)
+    }
+
+    /// Returns `true` if the data is pushed using the encoding chosen by [`ScriptBuilder::add_data`]
+    pub fn is_canonical_push(&self) -> bool {
+        self.is_data_push() && canonical_push_opcode(&self.data) == self.opcode
+    }
+}
+
+impl Display for ScriptInstruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.is_data_push() {
+            write!(f, "{}", self.name())
+        } else if self.is_canonical_push() {
+            write!(f, "0x{}", self.data.to_hex())
+        } else {
+            write!(f, "{} 0x{}", self.name(), self.data.to_hex())
+        }
+    }
+}
+
+fn is_data_push_opcode(opcode: u8) -> bool {
+    (OP_DATA_MIN_VAL..=OpPushData4).contains(&opcode)
+}
+
+/// Returns the opcode used by the canonical encoding of a data push
+fn canonical_push_opcode(data: &[u8]) -> u8 {
+    match data {
+        [] => Op0,
+        [OP_1_NEGATE_VAL] => Op1Negate,
+        [value @ OP_SMALL_INT_MIN_VAL..=OP_SMALL_INT_MAX_VAL] => (Op1 - 1) + value,
+        _ if data.len() <= OP_DATA_MAX_VAL as usize => data.len() as u8,
+        _ if data.len() <= u8::MAX as usize => OpPushData1,
+        _ if data.len() <= u16::MAX as usize => OpPushData2,
+        _ => OpPushData4,
+    }
+}
+
+/// Disassembles a script into a list of instructions.
+pub fn disassemble(script: &[u8]) -> Result<Vec<ScriptInstruction>, TxScriptError> {
+    let opcodes = parse_script::<PopulatedTransaction, SigHashReusedValuesUnsync>(script).collect::<Result<Vec<_>, _>>()?;
+
+    let mut offset = 0;
+    let mut instructions = Vec::with_capacity(opcodes.len());
+    for (index, opcode) in opcodes.iter().enumerate() {
+        let next = opcodes.get(index + 1).map(|next| next.value());
+        let data = opcode.get_data().to_vec();
+        let annotation = annotate(opcode.value(), &data, next);
+        instructions.push(ScriptInstruction { offset, opcode: opcode.value(), data, annotation });
+        offset += opcode.serialize().len();
+    }
+
+    Ok(instructions)
+}
+
+fn annotate(opcode: u8, data: &[u8], next: Option<u8>) -> Option<String> {
+    if !is_data_push_opcode(opcode) {
+        return None;
+    }
+
+    if next == Some(OpZkPrecompile)
+        && let [tag] = data
+    {
+        return ZkTag::try_from(*tag).ok().map(|tag| format!("zk tag {tag:?}"));
+    }
+
+    deserialize_i64(data, true).ok().map(|value| value.to_string())
+}
+
+/// Disassembles a script into a single line of text.
+pub fn disassemble_to_string(script: &[u8]) -> Result<String, TxScriptError> {
+    Ok(disassemble(script)?.iter().map(|instruction| instruction.to_string()).collect::<Vec<_>>().join(" "))
+}
+
+/// Disassembles a script listing one instruction per line, with decoded push data as comments.
+pub fn disassemble_annotated(script: &[u8]) -> Result<String, TxScriptError> {
+    let lines = disassemble(script)?
+        .iter()
+        .map(|instruction| match &instruction.annotation {
+            Some(annotation) => format!("{instruction} # {annotation}"),
+            None => instruction.to_string(),
+        })
+        .collect::<Vec<_>>();
+    Ok(lines.join("\n"))
+}
+
+/// Assembles a script from its textual representation using default engine flags.
+pub fn assemble(source: &str) -> ScriptAsmResult<Vec<u8>> {
+    assemble_with_flags(source, EngineFlags::default())
+}
+
+/// Assembles a script from its textual representation. The flags determine the script
+/// size limits enforced by the underlying [`ScriptBuilder`].
+pub fn assemble_with_flags(source: &str, flags: EngineFlags) -> ScriptAsmResult<Vec<u8>> {
+    let mut builder = ScriptBuilder::with_flags(flags);
+    let mut tokens = source.lines().flat_map(|line| line.split('#').next().unwrap_or_default().split_whitespace());
+
+    while let Some(token) = tokens.next() {
+        if let Some(hex) = token.strip_prefix("0x") {
+            builder.add_data(&parse_hex(hex)?)?;
+        } else if let Ok(value) = token.parse::<i64>() {
+            builder.add_i64(value)?;
+        } else if token.len() >= 2 && token.starts_with('\'') && token.ends_with('\'') {
+            builder.add_data(token[1..token.len() - 1].as_bytes())?;
+        } else if let Some(opcode) = opcode_from_name(token) {
+            if is_data_push_opcode(opcode) {
+                let name = opcode_name(opcode);
+                let data = tokens.next().and_then(|token| token.strip_prefix("0x")).ok_or(ScriptAsmError::MissingPushData(name))?;
+                builder.add_ops(&encode_push(opcode, &parse_hex(data)?)?)?;
+            } else {
+                builder.add_op(opcode)?;
+            }
+        } else {
+            return Err(ScriptAsmError::UnknownToken(token.to_string()));
+        }
+    }
+
+    Ok(builder.drain())
+}
+
+fn parse_hex(hex: &str) -> ScriptAsmResult<Vec<u8>> {
+    Vec::from_hex(hex).map_err(|_| ScriptAsmError::InvalidHex(hex.to_string()))
+}
+
+/// Encodes a data push using the supplied push opcode
+fn encode_push(opcode: u8, data: &[u8]) -> ScriptAsmResult<Vec<u8>> {
+    let len = data.len();
+    let invalid_length = || ScriptAsmError::InvalidPushLength(opcode_name(opcode), len);
+    let mut bytes = vec![opcode];
+    match opcode {
+        OpPushData1 => bytes.push(u8::try_from(len).map_err(|_| invalid_length())?),
+        OpPushData2 => bytes.extend(u16::try_from(len).map_err(|_| invalid_length())?.to_le_bytes()),
+        OpPushData4 => bytes.extend(u32::try_from(len).map_err(|_| invalid_length())?.to_le_bytes()),
+        _ if len != opcode as usize => return Err(invalid_length()),
+        _ => {}
+    }
+    bytes.extend_from_slice(data);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disassemble_assemble_round_trip() {
+        let mut builder = ScriptBuilder::with_flags(EngineFlags { covenants_enabled: true, ..Default::default() });
+        builder
+            .add_op(OpDup)
+            .unwrap()
+            .add_data(&[0xab; 32])
+            .unwrap()
+            .add_op(OpCheckSig)
+            .unwrap()
+            .add_i64(1000)
+            .unwrap()
+            .add_op(OpTxInputAmount)
+            .unwrap()
+            .add_op(OpInputCovenantId)
+            .unwrap()
+            .add_data(&[0x20])
+            .unwrap()
+            .add_op(OpZkPrecompile)
+            .unwrap()
+            .add_data(&[0x01; 300])
+            .unwrap()
+            .add_data(&[])
+            .unwrap()
+            .add_data(&[5])
+            .unwrap();
+        let mut script = builder.drain();
+        // non-canonical push of a small integer
+        script.extend([OpData1, 0x05]);
+
+        let text = disassemble_to_string(&script).unwrap();
+        assert!(text.starts_with(&format!("OpDup 0x{} OpCheckSig 0xe803 OpTxInputAmount OpInputCovenantId", "ab".repeat(32))));
+        assert!(text.ends_with("OpFalse Op5 OpData1 0x05"));
+        assert_eq!(assemble(&text).unwrap(), script);
+
+        let annotated = disassemble_annotated(&script).unwrap();
+        assert!(annotated.contains("0xe803 # 1000"));
+        assert!(annotated.contains("0x20 # zk tag Groth16\nOpZkPrecompile"));
+        assert_eq!(assemble(&annotated).unwrap(), script);
+
+        let instructions = disassemble(&script).unwrap();
+        assert_eq!(instructions[1].offset, 1);
+        assert_eq!(instructions[2].offset, 34);
+    }
+
+    #[test]
+    fn test_assemble() {
+        assert_eq!(assemble("OP_DUP checksig op_check_sig_verify 2dup").unwrap_err(), ScriptAsmError::UnknownToken("2dup".into()));
+        assert_eq!(assemble("OP_DUP checksig op_check_sig_verify Op2Dup").unwrap(), vec![OpDup, OpCheckSig, OpCheckSigVerify, Op2Dup]);
+        assert_eq!(assemble("-1 0 16 17 'ab'").unwrap(), vec![Op1Negate, Op0, Op16, OpData1, 17, OpData2, b'a', b'b']);
+        assert_eq!(assemble("OpPushData1 0x0102 # comment\n OpTrue").unwrap(), vec![OpPushData1, 2, 1, 2, OpTrue]);
+        assert_eq!(assemble("OpData2 0x01").unwrap_err(), ScriptAsmError::InvalidPushLength("OpData2", 1));
+        assert_eq!(assemble("OpData2").unwrap_err(), ScriptAsmError::MissingPushData("OpData2"));
+        assert_eq!(assemble("0xzz").unwrap_err(), ScriptAsmError::InvalidHex("zz".into()));
+    }
+
+    #[test]
+    fn test_opcode_names() {
+        for opcode in 0..=u8::MAX {
+            assert_eq!(opcode_from_name(opcode_name(opcode)), Some(opcode));
+        }
+        assert_eq!(opcode_from_name("OP_0"), Some(Op0));
+        assert_eq!(opcode_from_name("1"), None);
+    }
+}
@@ -11,9 +11,15 @@ use kaspa_txscript_errors::TxScriptError;
 use smallvec::SmallVec;
 use std::iter::once;
 
+mod htlc;
 mod multisig;
+mod template;
+mod vault;
 
+pub use htlc::{Error as HtlcCreateError, HTLC_PREIMAGE_SIZE, HtlcScript, htlc_claim_signature_script, htlc_refund_signature_script};
 pub use multisig::{Error as MultisigCreateError, multisig_redeem_script, multisig_redeem_script_ecdsa};
+pub use template::ScriptTemplate;
+pub use vault::{Error as VaultCreateError, VaultScript, vault_recover_signature_script, vault_withdraw_signature_script};
 
 /// Creates a new script to pay a transaction output to a 32-byte pubkey.
 fn pay_to_pub_key(address_payload: &[u8]) -> ScriptVec {
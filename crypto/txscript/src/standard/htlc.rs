@@ -0,0 +1,180 @@
+use super::{pay_to_script_hash_signature_script, template::TemplateReader};
+use crate::opcodes::codes::{
+    OpCheckLockTimeVerify, OpCheckSig, OpElse, OpEndIf, OpEqualVerify, OpFalse, OpIf, OpSHA256, OpSize, OpTrue,
+};
+use crate::script_builder::{ScriptBuilder, ScriptBuilderError};
+use thiserror::Error;
+
+/// Size of the secret preimage revealed by the claim path
+pub const HTLC_PREIMAGE_SIZE: usize = 32;
+
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
+pub enum Error {
+    #[error("preimage must be {size} bytes long, got {0}", size = HTLC_PREIMAGE_SIZE)]
+    InvalidPreimageLength(usize),
+    #[error(transparent)]
+    ScriptBuilderError(#[from] ScriptBuilderError),
+}
+
+/// Parameters of a hash-time-locked contract.
+///
+/// The redeem script lets the receiver claim the funds by revealing the SHA256 preimage
+/// of `secret_hash`, or lets the refund key take the funds back once the transaction lock
+/// time reaches `lock_time` (a DAA score or a millisecond timestamp, see [`OpCheckLockTimeVerify`]):
+///
+/// ```text
+/// OpIf
+///     OpSize 32 OpEqualVerify OpSHA256 <secret_hash> OpEqualVerify <receiver> OpCheckSig
+/// OpElse
+///     <lock_time> OpCheckLockTimeVerify <refund> OpCheckSig
+/// OpEndIf
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtlcScript {
+    pub secret_hash: [u8; 32],
+    pub receiver: [u8; 32],
+    pub refund: [u8; 32],
+    pub lock_time: u64,
+}
+
+impl HtlcScript {
+    pub fn new(secret_hash: [u8; 32], receiver: [u8; 32], refund: [u8; 32], lock_time: u64) -> Self {
+        Self { secret_hash, receiver, refund, lock_time }
+    }
+
+    pub fn redeem_script(&self) -> Result<Vec<u8>, Error> {
+        let mut builder = ScriptBuilder::new();
+        builder
+            .add_op(OpIf)?
+            .add_op(OpSize)?
+            .add_i64(HTLC_PREIMAGE_SIZE as i64)?
+            .add_op(OpEqualVerify)?
+            .add_op(OpSHA256)?
+            .add_data(&self.secret_hash)?
+            .add_op(OpEqualVerify)?
+            .add_data(&self.receiver)?
+            .add_op(OpCheckSig)?
+            .add_op(OpElse)?
+            .add_lock_time(self.lock_time)?
+            .add_op(OpCheckLockTimeVerify)?
+            .add_data(&self.refund)?
+            .add_op(OpCheckSig)?
+            .add_op(OpEndIf)?;
+        Ok(builder.drain())
+    }
+
+    /// Parses an HTLC redeem script, returning `None` if the script does not match the template.
+    pub fn from_redeem_script(script: &[u8]) -> Option<Self> {
+        let mut reader = TemplateReader::new(script)?;
+        reader.expect_op(OpIf)?;
+        reader.expect_op(OpSize)?;
+        (reader.read_number()? == HTLC_PREIMAGE_SIZE as u64).then_some(())?;
+        reader.expect_op(OpEqualVerify)?;
+        reader.expect_op(OpSHA256)?;
+        let secret_hash = reader.read_key()?;
+        reader.expect_op(OpEqualVerify)?;
+        let receiver = reader.read_key()?;
+        reader.expect_op(OpCheckSig)?;
+        reader.expect_op(OpElse)?;
+        let lock_time = reader.read_number()?;
+        reader.expect_op(OpCheckLockTimeVerify)?;
+        let refund = reader.read_key()?;
+        reader.expect_op(OpCheckSig)?;
+        reader.expect_op(OpEndIf)?;
+        reader.finish()?;
+        Some(Self { secret_hash, receiver, refund, lock_time })
+    }
+
+    /// Number of signature operations of the redeem script
+    pub fn sig_op_count(&self) -> u8 {
+        2
+    }
+}
+
+/// Generates the signature script claiming an HTLC output with the receiver signature
+/// (including the sighash type byte) and the secret preimage.
+pub fn htlc_claim_signature_script(redeem_script: Vec<u8>, signature: &[u8], preimage: &[u8]) -> Result<Vec<u8>, Error> {
+    if preimage.len() != HTLC_PREIMAGE_SIZE {
+        return Err(Error::InvalidPreimageLength(preimage.len()));
+    }
+    let unlock = ScriptBuilder::new().add_data(signature)?.add_data(preimage)?.add_op(OpTrue)?.drain();
+    Ok(pay_to_script_hash_signature_script(redeem_script, unlock)?)
+}
+
+/// Generates the signature script refunding an HTLC output with the refund key signature
+/// (including the sighash type byte). The spending transaction must have a lock time
+/// of at least the HTLC lock time and a non-final input sequence.
+pub fn htlc_refund_signature_script(redeem_script: Vec<u8>, signature: &[u8]) -> Result<Vec<u8>, Error> {
+    let unlock = ScriptBuilder::new().add_data(signature)?.add_op(OpFalse)?.drain();
+    Ok(pay_to_script_hash_signature_script(redeem_script, unlock)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pay_to_script_hash_script,
+        script_class::ScriptClass,
+        standard::template::{
+            ScriptTemplate,
+            test_helpers::{sign, spend_p2sh},
+        },
+    };
+    use kaspa_consensus_core::tx::ScriptPublicKey;
+    use kaspa_txscript_errors::TxScriptError;
+    use secp256k1::Keypair;
+    use sha2::{Digest, Sha256};
+
+    #[test]
+    fn test_htlc_template() {
+        let receiver = Keypair::new(secp256k1::SECP256K1, &mut rand::thread_rng());
+        let refund = Keypair::new(secp256k1::SECP256K1, &mut rand::thread_rng());
+        let preimage = [7u8; HTLC_PREIMAGE_SIZE];
+        let htlc = HtlcScript::new(
+            Sha256::digest(preimage).into(),
+            receiver.x_only_public_key().0.serialize(),
+            refund.x_only_public_key().0.serialize(),
+            1_000,
+        );
+        let redeem_script = htlc.redeem_script().unwrap();
+        assert_eq!(HtlcScript::from_redeem_script(&redeem_script), Some(htlc.clone()));
+        assert_eq!(HtlcScript::from_redeem_script(&redeem_script[..redeem_script.len() - 1]), None);
+        assert_eq!(ScriptTemplate::from_redeem_script(&redeem_script), Some(ScriptTemplate::HashTimeLock));
+        // templates are only recognized behind a script hash
+        let spk = ScriptPublicKey::new(0, redeem_script.clone().into());
+        assert_eq!(ScriptClass::from_script(&spk), ScriptClass::NonStandard);
+        assert_eq!(ScriptClass::from_script(&pay_to_script_hash_script(&redeem_script)), ScriptClass::ScriptHash);
+
+        let claim = |kp: Keypair, preimage: [u8; HTLC_PREIMAGE_SIZE]| {
+            let redeem_script = redeem_script.clone();
+            move |msg| htlc_claim_signature_script(redeem_script, &sign(&kp, msg), &preimage).unwrap()
+        };
+        let refund_with = |kp: Keypair| {
+            let redeem_script = redeem_script.clone();
+            move |msg| htlc_refund_signature_script(redeem_script, &sign(&kp, msg)).unwrap()
+        };
+        let sig_ops = htlc.sig_op_count();
+
+        // the receiver claims with the preimage at any time
+        assert_eq!(spend_p2sh(&redeem_script, 0, u64::MAX, sig_ops, claim(receiver, preimage)), Ok(()));
+        assert_eq!(
+            spend_p2sh(&redeem_script, 0, u64::MAX, sig_ops, claim(receiver, [8; HTLC_PREIMAGE_SIZE])),
+            Err(TxScriptError::VerifyError)
+        );
+        assert!(spend_p2sh(&redeem_script, 0, u64::MAX, sig_ops, claim(refund, preimage)).is_err());
+
+        // the refund key spends once the lock time is reached
+        assert_eq!(spend_p2sh(&redeem_script, 1_000, 0, sig_ops, refund_with(refund)), Ok(()));
+        assert!(matches!(
+            spend_p2sh(&redeem_script, 999, 0, sig_ops, refund_with(refund)),
+            Err(TxScriptError::UnsatisfiedLockTime(_))
+        ));
+        assert!(matches!(
+            spend_p2sh(&redeem_script, 1_000, u64::MAX, sig_ops, refund_with(refund)),
+            Err(TxScriptError::UnsatisfiedLockTime(_))
+        ));
+        assert!(spend_p2sh(&redeem_script, 1_000, 0, sig_ops, refund_with(receiver)).is_err());
+
+        assert_eq!(htlc_claim_signature_script(redeem_script, &[], &[0; 20]), Err(Error::InvalidPreimageLength(20)));
+    }
+}
@@ -0,0 +1,141 @@
+use super::{HtlcScript, VaultScript};
+use crate::{
+    opcodes::codes::{Op0, Op1, Op1Negate, Op16, OpData32},
+    script_asm::{ScriptInstruction, disassemble},
+};
+use std::iter::Peekable;
+
+/// Script templates recognized as pay-to-script-hash redeem scripts.
+///
+/// Templates are only ever spent through P2SH, so they are not a [`ScriptClass`](crate::script_class::ScriptClass)
+/// of their own and their spends remain subject to the standard P2SH signature operation limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptTemplate {
+    /// Hash-time-locked contract, see [`HtlcScript`]
+    HashTimeLock,
+    /// Timelocked vault with recovery keys, see [`VaultScript`]
+    TimeLockVault,
+}
+
+impl ScriptTemplate {
+    /// Classifies a redeem script, returning `None` if it matches none of the templates
+    pub fn from_redeem_script(redeem_script: &[u8]) -> Option<Self> {
+        if HtlcScript::from_redeem_script(redeem_script).is_some() {
+            Some(Self::HashTimeLock)
+        } else if VaultScript::from_redeem_script(redeem_script).is_some() {
+            Some(Self::TimeLockVault)
+        } else {
+            None
+        }
+    }
+}
+
+/// Sequential reader matching a script against a fixed template
+pub(crate) struct TemplateReader {
+    instructions: Peekable<std::vec::IntoIter<ScriptInstruction>>,
+}
+
+impl TemplateReader {
+    pub fn new(script: &[u8]) -> Option<Self> {
+        Some(Self { instructions: disassemble(script).ok()?.into_iter().peekable() })
+    }
+
+    pub fn expect_op(&mut self, opcode: u8) -> Option<()> {
+        (self.instructions.next()?.opcode == opcode).then_some(())
+    }
+
+    /// Reads a 32-byte push (a schnorr public key or a hash)
+    pub fn read_key(&mut self) -> Option<[u8; 32]> {
+        let instruction = self.instructions.next()?;
+        (instruction.opcode == OpData32).then(|| instruction.data.try_into().ok()).flatten()
+    }
+
+    /// Reads all consecutive 32-byte pushes
+    pub fn read_keys(&mut self) -> Vec<[u8; 32]> {
+        let mut keys = Vec::new();
+        while self.instructions.peek().is_some_and(|instruction| instruction.opcode == OpData32) {
+            keys.extend(self.read_key());
+        }
+        keys
+    }
+
+    /// Reads an unsigned little-endian number of up to 8 bytes, as pushed by
+    /// [`ScriptBuilder::add_lock_time`](crate::script_builder::ScriptBuilder::add_lock_time)
+    pub fn read_number(&mut self) -> Option<u64> {
+        let instruction = self.instructions.next()?;
+        match instruction.opcode {
+            Op0 => Some(0),
+            Op1Negate => Some(0x81),
+            Op1..=Op16 => Some((instruction.opcode - Op1 + 1) as u64),
+            _ if instruction.is_data_push() && instruction.data.len() <= 8 => {
+                let mut bytes = instruction.data;
+                bytes.resize(8, 0);
+                Some(u64::from_le_bytes(bytes.try_into().ok()?))
+            }
+            _ => None,
+        }
+    }
+
+    /// Succeeds if the whole script has been consumed
+    pub fn finish(mut self) -> Option<()> {
+        self.instructions.next().is_none().then_some(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_helpers {
+    use crate::{EngineContext, TxScriptEngine, caches::Cache, pay_to_script_hash_script};
+    use kaspa_consensus_core::{
+        hashing::{
+            sighash::{SigHashReusedValuesUnsync, calc_schnorr_signature_hash},
+            sighash_type::SIG_HASH_ALL,
+        },
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::*,
+    };
+    use kaspa_txscript_errors::TxScriptError;
+    use secp256k1::Keypair;
+
+    /// Signs the input sighash with the key pair, appending the sighash type byte
+    pub fn sign(kp: &Keypair, msg: secp256k1::Message) -> Vec<u8> {
+        kp.sign_schnorr(msg).serialize().into_iter().chain([SIG_HASH_ALL.to_u8()]).collect()
+    }
+
+    /// Executes a transaction spending a P2SH output of `redeem_script`, with the
+    /// signature script produced by `signature_script` from the input sighash.
+    pub fn spend_p2sh(
+        redeem_script: &[u8],
+        lock_time: u64,
+        sequence: u64,
+        sig_op_count: u8,
+        signature_script: impl FnOnce(secp256k1::Message) -> Vec<u8>,
+    ) -> Result<(), TxScriptError> {
+        let tx = Transaction::new(
+            0,
+            vec![TransactionInput {
+                previous_outpoint: TransactionOutpoint { transaction_id: TransactionId::from_bytes([1; 32]), index: 0 },
+                signature_script: vec![],
+                sequence,
+                compute_commit: ComputeCommit::SigopCount(sig_op_count.into()),
+            }],
+            vec![],
+            lock_time,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        let entry = UtxoEntry::new(1_000_000, pay_to_script_hash_script(redeem_script), 0, false, None);
+        let mut tx = MutableTransaction::with_entries(tx, vec![entry]);
+
+        let reused_values = SigHashReusedValuesUnsync::new();
+        let sig_hash = calc_schnorr_signature_hash(&tx.as_verifiable(), 0, SIG_HASH_ALL, &reused_values);
+        let msg = secp256k1::Message::from_digest_slice(sig_hash.as_bytes().as_slice()).unwrap();
+        tx.tx.inputs[0].signature_script = signature_script(msg);
+
+        let tx = tx.as_verifiable();
+        let (input, entry) = tx.populated_inputs().next().unwrap();
+        let cache = Cache::new(10_000);
+        let ctx = EngineContext::new(&cache).with_reused(&reused_values);
+        TxScriptEngine::from_transaction_input(&tx, input, 0, entry, ctx, Default::default()).execute()
+    }
+}
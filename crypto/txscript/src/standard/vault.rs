@@ -0,0 +1,185 @@
+use super::{pay_to_script_hash_signature_script, template::TemplateReader};
+use crate::MAX_PUB_KEYS_PER_MUTLTISIG;
+use crate::opcodes::codes::{OpCheckMultiSig, OpCheckSequenceVerify, OpCheckSig, OpElse, OpEndIf, OpFalse, OpIf, OpTrue};
+use crate::script_builder::{ScriptBuilder, ScriptBuilderError};
+use thiserror::Error;
+
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
+pub enum Error {
+    #[error("recovery keys should not be empty")]
+    EmptyRecoveryKeys,
+    #[error("too many recovery keys: {0}, the maximum is {max}", max = MAX_PUB_KEYS_PER_MUTLTISIG)]
+    TooManyRecoveryKeys(usize),
+    #[error("required recovery signatures must be between 1 and the number of recovery keys")]
+    InvalidRequiredSigs,
+    #[error("expected {0} recovery signatures, got {1}")]
+    RecoverySignatureCount(usize, usize),
+    #[error(transparent)]
+    ScriptBuilderError(#[from] ScriptBuilderError),
+}
+
+/// Parameters of a timelocked vault.
+///
+/// The owner key can withdraw the funds only after the output has aged by `delay`
+/// (a relative lock enforced by [`OpCheckSequenceVerify`] against the input sequence),
+/// while `required` of the recovery keys can move the funds at any time, e.g. to claw back
+/// an unauthorized withdrawal before the delay expires:
+///
+/// ```text
+/// OpIf
+///     <delay> OpCheckSequenceVerify <owner> OpCheckSig
+/// OpElse
+///     <required> <recovery keys...> <count> OpCheckMultiSig
+/// OpEndIf
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VaultScript {
+    pub owner: [u8; 32],
+    pub delay: u64,
+    pub recovery_keys: Vec<[u8; 32]>,
+    pub required: usize,
+}
+
+impl VaultScript {
+    pub fn new(owner: [u8; 32], delay: u64, recovery_keys: Vec<[u8; 32]>, required: usize) -> Self {
+        Self { owner, delay, recovery_keys, required }
+    }
+
+    pub fn redeem_script(&self) -> Result<Vec<u8>, Error> {
+        if self.recovery_keys.is_empty() {
+            return Err(Error::EmptyRecoveryKeys);
+        }
+        if self.recovery_keys.len() > MAX_PUB_KEYS_PER_MUTLTISIG as usize {
+            return Err(Error::TooManyRecoveryKeys(self.recovery_keys.len()));
+        }
+        if self.required == 0 || self.required > self.recovery_keys.len() {
+            return Err(Error::InvalidRequiredSigs);
+        }
+
+        let mut builder = ScriptBuilder::new();
+        builder
+            .add_op(OpIf)?
+            .add_sequence(self.delay)?
+            .add_op(OpCheckSequenceVerify)?
+            .add_data(&self.owner)?
+            .add_op(OpCheckSig)?
+            .add_op(OpElse)?
+            .add_i64(self.required as i64)?;
+        for key in self.recovery_keys.iter() {
+            builder.add_data(key)?;
+        }
+        builder.add_i64(self.recovery_keys.len() as i64)?.add_op(OpCheckMultiSig)?.add_op(OpEndIf)?;
+        Ok(builder.drain())
+    }
+
+    /// Parses a vault redeem script, returning `None` if the script does not match the template.
+    pub fn from_redeem_script(script: &[u8]) -> Option<Self> {
+        let mut reader = TemplateReader::new(script)?;
+        reader.expect_op(OpIf)?;
+        let delay = reader.read_number()?;
+        reader.expect_op(OpCheckSequenceVerify)?;
+        let owner = reader.read_key()?;
+        reader.expect_op(OpCheckSig)?;
+        reader.expect_op(OpElse)?;
+        let required = reader.read_number()? as usize;
+        let recovery_keys = reader.read_keys();
+        (reader.read_number()? == recovery_keys.len() as u64).then_some(())?;
+        reader.expect_op(OpCheckMultiSig)?;
+        reader.expect_op(OpEndIf)?;
+        reader.finish()?;
+        let vault = Self { owner, delay, recovery_keys, required };
+        vault.is_valid().then_some(vault)
+    }
+
+    fn is_valid(&self) -> bool {
+        !self.recovery_keys.is_empty()
+            && self.recovery_keys.len() <= MAX_PUB_KEYS_PER_MUTLTISIG as usize
+            && (1..=self.recovery_keys.len()).contains(&self.required)
+    }
+
+    /// Number of signature operations of the redeem script
+    pub fn sig_op_count(&self) -> u8 {
+        1 + self.recovery_keys.len() as u8
+    }
+}
+
+/// Generates the signature script withdrawing a vault output with the owner signature
+/// (including the sighash type byte). The input sequence must be at least the vault delay.
+pub fn vault_withdraw_signature_script(redeem_script: Vec<u8>, signature: &[u8]) -> Result<Vec<u8>, Error> {
+    let unlock = ScriptBuilder::new().add_data(signature)?.add_op(OpTrue)?.drain();
+    Ok(pay_to_script_hash_signature_script(redeem_script, unlock)?)
+}
+
+/// Generates the signature script moving a vault output with the recovery keys. The
+/// signatures (each including the sighash type byte) must follow the order of the
+/// recovery keys in the redeem script.
+pub fn vault_recover_signature_script(redeem_script: Vec<u8>, signatures: &[Vec<u8>]) -> Result<Vec<u8>, Error> {
+    if let Some(vault) = VaultScript::from_redeem_script(&redeem_script)
+        && vault.required != signatures.len()
+    {
+        return Err(Error::RecoverySignatureCount(vault.required, signatures.len()));
+    }
+    let mut builder = ScriptBuilder::new();
+    for signature in signatures {
+        builder.add_data(signature)?;
+    }
+    let unlock = builder.add_op(OpFalse)?.drain();
+    Ok(pay_to_script_hash_signature_script(redeem_script, unlock)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        pay_to_script_hash_script,
+        script_class::ScriptClass,
+        standard::template::{
+            ScriptTemplate,
+            test_helpers::{sign, spend_p2sh},
+        },
+    };
+    use kaspa_consensus_core::tx::ScriptPublicKey;
+    use kaspa_txscript_errors::TxScriptError;
+    use secp256k1::Keypair;
+
+    #[test]
+    fn test_vault_template() {
+        let [owner, recovery1, recovery2, recovery3] =
+            std::array::from_fn(|_| Keypair::new(secp256k1::SECP256K1, &mut rand::thread_rng()));
+        let recovery_keys = [recovery1, recovery2, recovery3].iter().map(|kp| kp.x_only_public_key().0.serialize()).collect();
+        let vault = VaultScript::new(owner.x_only_public_key().0.serialize(), 100, recovery_keys, 2);
+        let redeem_script = vault.redeem_script().unwrap();
+        assert_eq!(VaultScript::from_redeem_script(&redeem_script), Some(vault.clone()));
+        assert_eq!(ScriptTemplate::from_redeem_script(&redeem_script), Some(ScriptTemplate::TimeLockVault));
+        // templates are only recognized behind a script hash
+        let spk = ScriptPublicKey::new(0, redeem_script.clone().into());
+        assert_eq!(ScriptClass::from_script(&spk), ScriptClass::NonStandard);
+        assert_eq!(ScriptClass::from_script(&pay_to_script_hash_script(&redeem_script)), ScriptClass::ScriptHash);
+
+        let sig_ops = vault.sig_op_count();
+        let withdraw = |kp: Keypair| {
+            let redeem_script = redeem_script.clone();
+            move |msg| vault_withdraw_signature_script(redeem_script, &sign(&kp, msg)).unwrap()
+        };
+        let recover = |kps: Vec<Keypair>| {
+            let redeem_script = redeem_script.clone();
+            move |msg| {
+                let signatures = kps.iter().map(|kp| sign(kp, msg)).collect::<Vec<_>>();
+                vault_recover_signature_script(redeem_script, &signatures).unwrap()
+            }
+        };
+
+        // the owner withdraws once the output has aged by the delay
+        assert_eq!(spend_p2sh(&redeem_script, 0, 100, sig_ops, withdraw(owner)), Ok(()));
+        assert!(matches!(spend_p2sh(&redeem_script, 0, 99, sig_ops, withdraw(owner)), Err(TxScriptError::UnsatisfiedLockTime(_))));
+        assert!(spend_p2sh(&redeem_script, 0, 100, sig_ops, withdraw(recovery1)).is_err());
+
+        // the recovery keys spend at any time
+        assert_eq!(spend_p2sh(&redeem_script, 0, u64::MAX, sig_ops, recover(vec![recovery1, recovery3])), Ok(()));
+        assert!(spend_p2sh(&redeem_script, 0, u64::MAX, sig_ops, recover(vec![recovery1, owner])).is_err());
+
+        assert_eq!(vault_recover_signature_script(redeem_script, &[vec![]]), Err(Error::RecoverySignatureCount(2, 1)));
+        assert_eq!(VaultScript::new([0; 32], 100, vec![], 1).redeem_script(), Err(Error::EmptyRecoveryKeys));
+        assert_eq!(VaultScript::new([0; 32], 100, vec![[1; 32]], 2).redeem_script(), Err(Error::InvalidRequiredSigs));
+    }
+}
@@ -8,8 +8,10 @@ cfg_if! {
     if #[cfg(any(feature = "wasm32-sdk", feature = "wasm32-core"))] {
         pub mod opcodes;
         pub mod builder;
+        pub mod script_asm;
 
         pub use self::opcodes::*;
         pub use self::builder::*;
+        pub use self::script_asm::*;
     }
 }
@@ -0,0 +1,37 @@
+#![allow(non_snake_case)]
+
+use crate::wasm::builder::ScriptBuilderOptions;
+use crate::{EngineFlags, error::Error, result::Result, script_asm};
+use kaspa_wasm_core::types::{BinaryT, HexString};
+use wasm_bindgen::prelude::wasm_bindgen;
+use workflow_wasm::prelude::*;
+
+/// Disassembles a script into its textual (opcode) representation.
+/// If `annotate` is `true`, each instruction is listed on a separate line
+/// followed by a `#` comment containing the decoded push data (where applicable).
+/// The result can be converted back into the original script using {@link assembleScript}.
+/// @param script - The script ({@link HexString} or Uint8Array).
+/// @category Consensus
+#[wasm_bindgen(js_name = disassembleScript)]
+pub fn disassemble_script(script: BinaryT, annotate: Option<bool>) -> Result<String> {
+    let script = script.try_as_vec_u8()?;
+    let text = if annotate.unwrap_or(false) {
+        script_asm::disassemble_annotated(&script)
+    } else {
+        script_asm::disassemble_to_string(&script)
+    };
+    text.map_err(|err| Error::custom(err.to_string()))
+}
+
+/// Assembles a script from its textual (opcode) representation.
+/// Data supplied as `0x`-prefixed hex is pushed using canonical encoding,
+/// decimal values are pushed as script numbers.
+/// @param source - The script text.
+/// @param options - Optional {@link ScriptBuilderOptions} determining script limits.
+/// @category Consensus
+#[wasm_bindgen(js_name = assembleScript)]
+pub fn assemble_script(source: String, options: Option<ScriptBuilderOptions>) -> Result<HexString> {
+    let flags = options.map(EngineFlags::try_from).transpose()?.unwrap_or_default();
+    let script = script_asm::assemble_with_flags(&source, flags)?;
+    Ok(HexString::from(script.as_slice()))
+}
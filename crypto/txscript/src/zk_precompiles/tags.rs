@@ -2,7 +2,7 @@ use kaspa_consensus_core::mass::{Gram, ScriptUnits};
 
 use crate::zk_precompiles::error::ZkIntegrityError;
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 #[repr(u8)]
 /// The supported ZK proof tags
 pub enum ZkTag {
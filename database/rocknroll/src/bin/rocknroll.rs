@@ -0,0 +1,308 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    process::ExitCode,
+    str::FromStr,
+};
+
+use clap::{Parser, Subcommand};
+use kaspa_consensus::{
+    model::stores::{
+        headers::{DbHeadersStore, HeaderStoreReader},
+        virtual_state::{DbVirtualStateStore, LkgVirtualState, VirtualStateStoreReader},
+    },
+    params::Params,
+};
+use kaspa_database::prelude::CachePolicy;
+use kaspa_hashes::Hash;
+use kaspa_rocknroll::{
+    Error, Result,
+    args::DbSourceArgs,
+    dag::DagChecker,
+    db::{print_db_header, resolve_consensus_db},
+    pruning::SmtPruningState,
+    stores::{StoreStats, dump_store, parse_store, scan_store_stats, store_name},
+    verify::{SmtVerification, UtxoSetVerification, verify_pruning_utxo_set, verify_smt_state, verify_virtual_utxo_set},
+};
+use kaspa_smt_store::processor::{SmtStores, StaleSmtEntriesCount};
+
+/// Exit code reported when a check completed but found an inconsistency
+const INCONSISTENT: u8 = 2;
+
+#[derive(Parser, Debug)]
+#[command(about = "Offline inspection and integrity checks for a kaspad consensus DB")]
+struct Args {
+    #[command(flatten)]
+    db: DbSourceArgs,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Count entries and logical key/value sizes per store prefix
+    Stats {
+        #[arg(long, help = "Sort stores by total size (largest first) instead of by prefix")]
+        by_size: bool,
+    },
+    /// Print raw entries of a single store as hex
+    Dump {
+        #[arg(value_name = "STORE", help = "Store name from the database registry (e.g. VirtualUtxoset) or its numeric prefix")]
+        store: String,
+
+        #[arg(long, default_value_t = 20, help = "Maximum number of entries to print")]
+        limit: usize,
+    },
+    /// Verify the virtual UTXO set against the MuHash stored in the virtual state
+    VerifyUtxo {
+        #[arg(long, help = "Also verify the pruning point UTXO set against the UTXO commitment of its header")]
+        pruning_point: bool,
+    },
+    /// Verify the SMT lanes root against the sequencing commitment of a chain block header
+    VerifySmt {
+        #[arg(long, value_name = "BLOCK_HASH", help = "Chain block to check from (default: the virtual selected parent)")]
+        block: Option<String>,
+    },
+    /// Check reachability, GHOSTDAG and relations consistency along a range of the selected chain
+    CheckDag {
+        #[arg(long, value_name = "INDEX", help = "First selected chain index to check (default: tip index - depth)")]
+        from_index: Option<u64>,
+
+        #[arg(long, value_name = "INDEX", help = "Last selected chain index to check (default: tip index)")]
+        to_index: Option<u64>,
+
+        #[arg(long, default_value_t = 1000, help = "Number of chain blocks below the tip to check when --from-index is omitted")]
+        depth: u64,
+    },
+    /// Delete stale SMT lane/branch versions and score-index records at or below the pruning cutoff
+    CompactSmt {
+        #[arg(long, value_name = "BLUE_SCORE", help = "Cutoff blue score (default and maximum: the cutoff consensus prunes with)")]
+        cutoff_blue_score: Option<u64>,
+
+        #[arg(long, help = "Only report what would be deleted; opens the DB read-only")]
+        dry_run: bool,
+
+        #[arg(long, default_value_t = 10_000, help = "Number of deletes per committed write batch")]
+        batch_size: usize,
+    },
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(1)
+        }
+    }
+}
+
+fn run() -> Result<ExitCode> {
+    let args = Args::parse();
+    let resolved = resolve_consensus_db(&args.db)?;
+    print_db_header(&resolved);
+    let db = match args.command {
+        Command::CompactSmt { dry_run: false, .. } => resolved.open_consensus_writable(args.db.files_limit)?,
+        _ => resolved.open_consensus_readonly(args.db.files_limit)?,
+    };
+    let params: Params = resolved.network.into();
+
+    match args.command {
+        Command::Stats { by_size } => {
+            println!("scanning...");
+            let _ = io::stdout().flush();
+            let stats = scan_store_stats(&db, 10_000_000, |scanned| eprintln!("scanned {scanned} entries"))?;
+            let mut rows: Vec<(u8, StoreStats)> = stats.into_iter().collect();
+            if by_size {
+                rows.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.total_bytes()));
+            }
+            let total = rows.iter().fold(StoreStats::default(), |acc, (_, stats)| StoreStats {
+                entries: acc.entries + stats.entries,
+                key_bytes: acc.key_bytes + stats.key_bytes,
+                value_bytes: acc.value_bytes + stats.value_bytes,
+            });
+            print_stats_row("prefix", "store", "entries", "key_bytes", "value_bytes", "total_bytes");
+            for (prefix, stats) in rows.iter() {
+                print_stats_row(prefix, store_name(*prefix), stats.entries, stats.key_bytes, stats.value_bytes, stats.total_bytes());
+            }
+            print_stats_row("", "total", total.entries, total.key_bytes, total.value_bytes, total.total_bytes());
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Dump { store, limit } => {
+            let prefix = parse_store(&store)?;
+            println!("store: {} ({prefix})", store_name(prefix));
+            let dumped = dump_store(&db, prefix, limit, |key, value| {
+                println!("{} => {}", faster_hex::hex_string(key), faster_hex::hex_string(value));
+            })?;
+            println!("entries: {dumped}");
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::VerifyUtxo { pruning_point } => {
+            println!("scanning virtual UTXO set...");
+            let _ = io::stdout().flush();
+            let mut valid = print_utxo_verification("virtual", &verify_virtual_utxo_set(db.clone(), print_utxo_progress)?);
+            if pruning_point {
+                println!("scanning pruning point UTXO set...");
+                let _ = io::stdout().flush();
+                valid &= print_utxo_verification("pruning_point", &verify_pruning_utxo_set(db, print_utxo_progress)?);
+            }
+            Ok(if valid { ExitCode::SUCCESS } else { ExitCode::from(INCONSISTENT) })
+        }
+        Command::VerifySmt { block } => {
+            let block = match block {
+                Some(block) => Hash::from_str(&block).map_err(|_| Error::InvalidArgs(format!("invalid block hash `{block}`")))?,
+                None => DbVirtualStateStore::new(db.clone(), LkgVirtualState::default()).get()?.ghostdag_data.selected_parent,
+            };
+            println!("block: {block}");
+            let _ = io::stdout().flush();
+            let verification = verify_smt_state(db, block, params.finality_depth(), params.toccata_activation)?;
+            println!("blue_score: {}", verification.blue_score);
+            println!("stored_lanes_root: {}", verification.stored_lanes_root);
+            println!("recomputed_lanes_root: {}", verification.recomputed_lanes_root);
+            println!("active_lanes_count: {}", verification.active_lanes_count);
+            println!("streamed_lanes_count: {}", verification.streamed_lanes_count);
+            match &verification.seq_commit {
+                Ok(()) => println!("seq_commit: ok"),
+                Err(err) => println!("seq_commit: {err}"),
+            }
+            println!("valid: {}", verification.is_valid());
+            Ok(if verification.is_valid() { ExitCode::SUCCESS } else { ExitCode::from(INCONSISTENT) })
+        }
+        Command::CheckDag { from_index, to_index, depth } => {
+            let checker = DagChecker::new(db);
+            let (tip_index, tip) = checker.chain_tip()?;
+            let to_index = to_index.unwrap_or(tip_index).min(tip_index);
+            let from_index = from_index.unwrap_or(to_index.saturating_sub(depth));
+            if from_index > to_index {
+                return Err(Error::InvalidArgs(format!("--from-index {from_index} is above --to-index {to_index}")));
+            }
+            println!("chain_tip: {tip} (index {tip_index})");
+            println!("range: [{from_index}, {to_index}]");
+            let _ = io::stdout().flush();
+            let report = checker.check_chain_range(from_index, to_index, |checked| eprintln!("checked {checked} chain blocks"))?;
+            for issue in report.issues.iter() {
+                println!("issue: index {} block {}: {}", issue.index, issue.block, issue.description);
+            }
+            println!("checked: {}", report.checked);
+            println!("issues: {}", report.issues.len());
+            Ok(if report.issues.is_empty() { ExitCode::SUCCESS } else { ExitCode::from(INCONSISTENT) })
+        }
+        Command::CompactSmt { cutoff_blue_score, dry_run, batch_size } => {
+            if resolved.archival == Some(true) {
+                println!("compaction: skipped");
+                println!("reason: archival nodes do not prune SMT history");
+                return Ok(ExitCode::SUCCESS);
+            }
+            let state = SmtPruningState::load(db.clone(), params.finality_depth())?;
+            state.print();
+            if !state.is_stable() {
+                println!("stable: false");
+                println!("compaction: skipped");
+                println!("reason: retention checkpoint differs from retention period root, so pruning has not completed cleanly");
+                return Ok(ExitCode::from(INCONSISTENT));
+            }
+            println!("stable: true");
+
+            let max_cutoff = state.inclusive_prune_cutoff();
+            let cutoff_blue_score = cutoff_blue_score.unwrap_or(max_cutoff);
+            if cutoff_blue_score > max_cutoff {
+                return Err(Error::InvalidArgs(format!(
+                    "--cutoff-blue-score {cutoff_blue_score} is above {max_cutoff}, entries above it are still readable from the pruning point"
+                )));
+            }
+            println!("cutoff_blue_score: {cutoff_blue_score}");
+            println!("scanning...");
+            let _ = io::stdout().flush();
+
+            let smt_stores = SmtStores::new(db.clone(), 1, 1);
+            let stale = smt_stores.count_entries_at_or_below(cutoff_blue_score)?;
+            print_stale_counts("stale", stale);
+            if dry_run {
+                println!("dry_run: true");
+                return Ok(ExitCode::SUCCESS);
+            }
+            if stale.total() == 0 {
+                println!("compaction: nothing to do");
+                return Ok(ExitCode::SUCCESS);
+            }
+
+            // The lanes root must be reproducible before and identical after, otherwise compaction touched live state
+            let sink = DbVirtualStateStore::new(db.clone(), LkgVirtualState::default()).get()?.ghostdag_data.selected_parent;
+            let sink_daa_score = DbHeadersStore::new(db.clone(), CachePolicy::Empty, CachePolicy::Empty).get_daa_score(sink)?;
+            let verify = |db| -> Result<Option<SmtVerification>> {
+                if !params.toccata_activation.is_active(sink_daa_score) {
+                    return Ok(None);
+                }
+                Ok(Some(verify_smt_state(db, sink, params.finality_depth(), params.toccata_activation)?))
+            };
+            println!("verify_block: {sink}");
+            let before = verify(db.clone())?;
+            match before.as_ref() {
+                Some(before) if !before.is_valid() => {
+                    println!("pre_verify: failed");
+                    println!("compaction: skipped");
+                    println!("reason: the SMT state is already inconsistent, see `rocknroll verify-smt`");
+                    return Ok(ExitCode::from(INCONSISTENT));
+                }
+                Some(before) => println!("pre_lanes_root: {}", before.stored_lanes_root),
+                None => println!("pre_verify: skipped (sequencing commitment is not active at the sink)"),
+            }
+
+            println!("compacting...");
+            let _ = io::stdout().flush();
+            let compacted = smt_stores.compact_entries_at_or_below(cutoff_blue_score, batch_size, |store, deleted| {
+                eprintln!("{store}: deleted {deleted} entries");
+            })?;
+            print_stale_counts("deleted", compacted);
+
+            let remaining = smt_stores.count_entries_at_or_below(cutoff_blue_score)?;
+            print_stale_counts("remaining", remaining);
+            let mut valid = remaining.total() == 0;
+            if let Some(before) = before {
+                let after = verify(db)?.expect("activation is fixed for the sink");
+                println!("post_lanes_root: {}", after.stored_lanes_root);
+                println!("post_recomputed_lanes_root: {}", after.recomputed_lanes_root);
+                let unchanged = after.stored_lanes_root == before.stored_lanes_root;
+                println!("lanes_root_unchanged: {unchanged}");
+                println!("post_verify: {}", if after.is_valid() { "ok" } else { "failed" });
+                valid &= unchanged && after.is_valid();
+            }
+            println!("valid: {valid}");
+            Ok(if valid { ExitCode::SUCCESS } else { ExitCode::from(INCONSISTENT) })
+        }
+    }
+}
+
+fn print_stale_counts(label: &str, counts: StaleSmtEntriesCount) {
+    println!("{label}.branch_versions: {}", counts.branch_versions);
+    println!("{label}.lane_versions: {}", counts.lane_versions);
+    println!("{label}.score_index: {}", counts.score_index);
+    println!("{label}.total: {}", counts.total());
+}
+
+fn print_stats_row(
+    prefix: impl Display,
+    store: impl Display,
+    entries: impl Display,
+    key_bytes: impl Display,
+    value_bytes: impl Display,
+    total_bytes: impl Display,
+) {
+    println!("{prefix:>6}  {store:<32} {entries:>14} {key_bytes:>16} {value_bytes:>16} {total_bytes:>16}");
+}
+
+fn print_utxo_progress(entries: u64) {
+    eprintln!("hashed {entries} UTXO entries");
+}
+
+fn print_utxo_verification(label: &str, verification: &UtxoSetVerification) -> bool {
+    if let Some(anchor) = verification.anchor {
+        println!("{label}.anchor: {anchor}");
+    }
+    println!("{label}.entries: {}", verification.entries);
+    println!("{label}.expected_muhash: {}", verification.expected);
+    println!("{label}.computed_muhash: {}", verification.computed);
+    println!("{label}.valid: {}", verification.is_valid());
+    verification.is_valid()
+}
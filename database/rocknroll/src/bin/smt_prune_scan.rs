@@ -4,16 +4,12 @@ use std::{
 };
 
 use clap::Parser;
-use kaspa_consensus::model::stores::{
-    headers::{DbHeadersStore, HeaderStoreReader},
-    pruning::{DbPruningStore, PruningStoreReader},
-};
 use kaspa_consensus::{config::ConfigBuilder, params::Params};
-use kaspa_database::prelude::CachePolicy;
 use kaspa_rocknroll::{
     Result,
     args::DbSourceArgs,
-    db::{ResolvedConsensusDb, resolve_consensus_db},
+    db::{print_db_header, resolve_consensus_db},
+    pruning::SmtPruningState,
 };
 use kaspa_smt_store::processor::{SmtStores, StaleSmtEntriesCount};
 
@@ -49,28 +45,20 @@ fn run() -> Result<ExitCode> {
     }
 
     let db = resolved.open_consensus_readonly(args.db.files_limit)?;
-    let pruning_store = DbPruningStore::new(db.clone());
-    let pruning_point = pruning_store.pruning_point()?;
-    let retention_period_root = pruning_store.retention_period_root()?;
-    let retention_checkpoint = pruning_store.retention_checkpoint()?;
-    println!("pruning_point: {pruning_point}");
-    println!("retention_period_root: {retention_period_root}");
-    println!("retention_checkpoint: {retention_checkpoint}");
+    let params: Params = resolved.network.into();
+    let config = ConfigBuilder::new(params).adjust_perf_params_to_consensus_params().build();
+    let state = SmtPruningState::load(db.clone(), config.params.finality_depth())?;
+    state.print();
 
-    if retention_checkpoint != retention_period_root {
+    if !state.is_stable() {
         print_unstable_pruning();
         return Ok(ExitCode::from(2));
     }
     println!("stable: true");
 
-    let headers_store = DbHeadersStore::new(db.clone(), CachePolicy::Empty, CachePolicy::Empty);
-    let pruning_point_blue_score = headers_store.get_blue_score(pruning_point)?;
-    let params: Params = resolved.network.into();
-    let config = ConfigBuilder::new(params).adjust_perf_params_to_consensus_params().build();
-    let finality_depth = config.params.finality_depth();
-    let cutoff_blue_score = args.cutoff_blue_score.unwrap_or_else(|| inclusive_prune_cutoff(pruning_point_blue_score, finality_depth));
-    println!("pruning_point_blue_score: {pruning_point_blue_score}");
-    println!("finality_depth: {finality_depth}");
+    let cutoff_blue_score = args.cutoff_blue_score.unwrap_or_else(|| state.inclusive_prune_cutoff());
+    println!("pruning_point_blue_score: {}", state.pruning_point_blue_score);
+    println!("finality_depth: {}", state.finality_depth);
     println!("cutoff_blue_score: {cutoff_blue_score}");
     println!("scanning...");
     let _ = io::stdout().flush();
@@ -82,10 +70,6 @@ fn run() -> Result<ExitCode> {
     Ok(ExitCode::SUCCESS)
 }
 
-fn inclusive_prune_cutoff(blue_score: u64, finality_depth: u64) -> u64 {
-    blue_score.saturating_sub(finality_depth).saturating_sub(1)
-}
-
 fn print_archival_skip() {
     println!("scan: skipped");
     println!("reason: archival nodes do not prune SMT history");
@@ -103,17 +87,3 @@ fn print_counts(stale: StaleSmtEntriesCount) {
     println!("score_index: {}", stale.score_index);
     println!("total: {}", stale.total());
 }
-
-fn print_db_header(resolved: &ResolvedConsensusDb) {
-    println!("network: {}", resolved.network);
-    if let Some(app_dir) = resolved.app_dir.as_ref() {
-        println!("app_dir: {}", app_dir.display());
-    }
-    if let Some(meta_db_path) = resolved.meta_db_path.as_ref() {
-        println!("meta_db: {}", meta_db_path.display());
-    }
-    if let Some(active_consensus_dir) = resolved.active_consensus_dir.as_ref() {
-        println!("active_consensus_dir: {active_consensus_dir}");
-    }
-    println!("consensus_db: {}", resolved.consensus_db_path.display());
-}
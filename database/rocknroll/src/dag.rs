@@ -0,0 +1,152 @@
+//! Offline consistency checks between the reachability, GHOSTDAG, relations and headers stores
+//! along a range of the selected chain.
+
+use std::sync::Arc;
+
+use kaspa_consensus::{
+    model::stores::{
+        ghostdag::{DbGhostdagStore, GhostdagStoreReader},
+        headers::{DbHeadersStore, HeaderStoreReader},
+        reachability::{DbReachabilityStore, ReachabilityStoreReader},
+        relations::{DbRelationsStore, RelationsStoreReader},
+        selected_chain::{DbSelectedChainStore, SelectedChainStoreReader},
+    },
+    processes::reachability::inquirer,
+};
+use kaspa_database::prelude::{CachePolicy, DB, StoreResultExt};
+use kaspa_hashes::Hash;
+
+use crate::Result;
+
+/// A single inconsistency found for a chain block
+#[derive(Debug, Clone)]
+pub struct DagIssue {
+    pub index: u64,
+    pub block: Hash,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DagCheckReport {
+    pub checked: u64,
+    pub issues: Vec<DagIssue>,
+}
+
+pub struct DagChecker {
+    selected_chain: DbSelectedChainStore,
+    ghostdag: DbGhostdagStore,
+    reachability: DbReachabilityStore,
+    relations: DbRelationsStore,
+    headers: DbHeadersStore,
+}
+
+impl DagChecker {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            selected_chain: DbSelectedChainStore::new(db.clone(), CachePolicy::Empty),
+            ghostdag: DbGhostdagStore::new(db.clone(), 0, CachePolicy::Empty, CachePolicy::Empty),
+            reachability: DbReachabilityStore::new(db.clone(), CachePolicy::Empty, CachePolicy::Empty),
+            relations: DbRelationsStore::new(db.clone(), 0, CachePolicy::Empty, CachePolicy::Empty),
+            headers: DbHeadersStore::new(db, CachePolicy::Empty, CachePolicy::Empty),
+        }
+    }
+
+    /// Returns the `(index, hash)` of the selected chain tip
+    pub fn chain_tip(&self) -> Result<(u64, Hash)> {
+        Ok(self.selected_chain.get_tip()?)
+    }
+
+    /// Checks every selected chain block with index in `[from_index, to_index]`
+    pub fn check_chain_range(&self, from_index: u64, to_index: u64, mut progress: impl FnMut(u64)) -> Result<DagCheckReport> {
+        let mut report = DagCheckReport::default();
+        let mut prev_chain_block = None;
+        for index in from_index..=to_index {
+            let block = self.selected_chain.get_by_index(index)?;
+            let mut issues = Vec::new();
+            self.check_chain_block(block, prev_chain_block, &mut issues)?;
+            report.issues.extend(issues.into_iter().map(|description| DagIssue { index, block, description }));
+            report.checked += 1;
+            prev_chain_block = Some(block);
+            if report.checked.is_multiple_of(10_000) {
+                progress(report.checked);
+            }
+        }
+        Ok(report)
+    }
+
+    fn check_chain_block(&self, block: Hash, prev_chain_block: Option<Hash>, issues: &mut Vec<String>) -> Result<()> {
+        if !self.reachability.has(block)? {
+            issues.push("missing reachability data".to_string());
+            return Ok(());
+        }
+        let Some(ghostdag_data) = self.ghostdag.get_data(block).optional()? else {
+            issues.push("missing GHOSTDAG data".to_string());
+            return Ok(());
+        };
+        let selected_parent = ghostdag_data.selected_parent;
+
+        if let Some(prev_chain_block) = prev_chain_block
+            && selected_parent != prev_chain_block
+        {
+            issues.push(format!("selected parent {selected_parent} differs from the previous chain block {prev_chain_block}"));
+        }
+
+        if let Some(header) = self.headers.get_header(block).optional()?
+            && header.blue_score != ghostdag_data.blue_score
+        {
+            issues.push(format!(
+                "header blue score {} differs from GHOSTDAG blue score {}",
+                header.blue_score, ghostdag_data.blue_score
+            ));
+        }
+
+        // Selected parent data may be missing below the pruning point, in which case the relative checks are skipped
+        if let Some(selected_parent_data) = self.ghostdag.get_data(selected_parent).optional()? {
+            let expected_blue_score = selected_parent_data.blue_score + ghostdag_data.mergeset_blues.len() as u64;
+            if ghostdag_data.blue_score != expected_blue_score {
+                issues.push(format!("blue score {} differs from the expected {}", ghostdag_data.blue_score, expected_blue_score));
+            }
+            if ghostdag_data.blue_work <= selected_parent_data.blue_work {
+                issues.push("blue work does not exceed the blue work of the selected parent".to_string());
+            }
+        }
+
+        if self.reachability.has(selected_parent)?
+            && !inquirer::is_chain_ancestor_of(&self.reachability, selected_parent, block).unwrap_or(false)
+        {
+            issues.push(format!("selected parent {selected_parent} is not a reachability chain ancestor"));
+        }
+
+        for merged in ghostdag_data.unordered_mergeset_without_selected_parent() {
+            if !self.reachability.has(merged)? {
+                continue;
+            }
+            if !inquirer::is_dag_ancestor_of(&self.reachability, merged, block).unwrap_or(false) {
+                issues.push(format!("mergeset block {merged} is not in the past of the block"));
+            }
+            if self.reachability.has(selected_parent)?
+                && inquirer::is_dag_ancestor_of(&self.reachability, merged, selected_parent).unwrap_or(false)
+            {
+                issues.push(format!("mergeset block {merged} is in the past of the selected parent"));
+            }
+        }
+
+        match self.relations.get_parents(block).optional()? {
+            Some(parents) => {
+                if !parents.contains(&selected_parent) {
+                    issues.push(format!("selected parent {selected_parent} is not among the relations parents"));
+                }
+                for &parent in parents.iter() {
+                    if self.reachability.has(parent)?
+                        && !inquirer::is_dag_ancestor_of(&self.reachability, parent, block).unwrap_or(false)
+                    {
+                        issues.push(format!("parent {parent} is not in the past of the block"));
+                    }
+                }
+            }
+            None => issues.push("missing relations parents".to_string()),
+        }
+
+        Ok(())
+    }
+}
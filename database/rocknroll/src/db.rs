@@ -26,6 +26,10 @@ impl ResolvedConsensusDb {
     pub fn open_consensus_readonly(&self, files_limit: i32) -> Result<Arc<DB>> {
         open_readonly_db(self.consensus_db_path.clone(), files_limit)
     }
+
+    pub fn open_consensus_writable(&self, files_limit: i32) -> Result<Arc<DB>> {
+        open_writable_db(self.consensus_db_path.clone(), files_limit)
+    }
 }
 
 pub fn resolve_consensus_db(args: &DbSourceArgs) -> Result<ResolvedConsensusDb> {
@@ -79,3 +83,25 @@ pub fn resolve_consensus_db(args: &DbSourceArgs) -> Result<ResolvedConsensusDb>
 pub fn open_readonly_db(db_path: PathBuf, files_limit: i32) -> Result<Arc<DB>> {
     Ok(ConnBuilder::default().with_db_path(db_path).with_files_limit(files_limit).build_readonly()?)
 }
+
+/// Opens a RocksDB instance in read-write mode for offline repairs.
+///
+/// RocksDB holds an exclusive lock on the DB directory, so opening cannot succeed while kaspad
+/// is running against the same DB.
+pub fn open_writable_db(db_path: PathBuf, files_limit: i32) -> Result<Arc<DB>> {
+    ConnBuilder::default().with_db_path(db_path).with_files_limit(files_limit).build().map_err(|err| Error::RocksDb(Box::new(err)))
+}
+
+pub fn print_db_header(resolved: &ResolvedConsensusDb) {
+    println!("network: {}", resolved.network);
+    if let Some(app_dir) = resolved.app_dir.as_ref() {
+        println!("app_dir: {}", app_dir.display());
+    }
+    if let Some(meta_db_path) = resolved.meta_db_path.as_ref() {
+        println!("meta_db: {}", meta_db_path.display());
+    }
+    if let Some(active_consensus_dir) = resolved.active_consensus_dir.as_ref() {
+        println!("active_consensus_dir: {active_consensus_dir}");
+    }
+    println!("consensus_db: {}", resolved.consensus_db_path.display());
+}
@@ -1,5 +1,9 @@
 pub mod args;
+pub mod dag;
 pub mod db;
+pub mod pruning;
+pub mod stores;
+pub mod verify;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
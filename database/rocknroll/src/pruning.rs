@@ -0,0 +1,53 @@
+//! Pruning state of a consensus DB as it relates to SMT history retention.
+
+use std::sync::Arc;
+
+use kaspa_consensus::model::stores::{
+    headers::{DbHeadersStore, HeaderStoreReader},
+    pruning::{DbPruningStore, PruningStoreReader},
+};
+use kaspa_database::prelude::{CachePolicy, DB};
+use kaspa_hashes::Hash;
+
+use crate::Result;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SmtPruningState {
+    pub pruning_point: Hash,
+    pub retention_period_root: Hash,
+    pub retention_checkpoint: Hash,
+    pub pruning_point_blue_score: u64,
+    pub finality_depth: u64,
+}
+
+impl SmtPruningState {
+    pub fn load(db: Arc<DB>, finality_depth: u64) -> Result<Self> {
+        let pruning_store = DbPruningStore::new(db.clone());
+        let pruning_point = pruning_store.pruning_point()?;
+        let headers_store = DbHeadersStore::new(db, CachePolicy::Empty, CachePolicy::Empty);
+        Ok(Self {
+            pruning_point,
+            retention_period_root: pruning_store.retention_period_root()?,
+            retention_checkpoint: pruning_store.retention_checkpoint()?,
+            pruning_point_blue_score: headers_store.get_blue_score(pruning_point)?,
+            finality_depth,
+        })
+    }
+
+    /// Pruning has completed cleanly only once the retention checkpoint caught up with the retention period root
+    pub fn is_stable(&self) -> bool {
+        self.retention_checkpoint == self.retention_period_root
+    }
+
+    /// The highest blue score whose SMT versions are no longer readable from any point of view at or above the
+    /// pruning point, i.e. the inclusive cutoff consensus prunes with
+    pub fn inclusive_prune_cutoff(&self) -> u64 {
+        self.pruning_point_blue_score.saturating_sub(self.finality_depth).saturating_sub(1)
+    }
+
+    pub fn print(&self) {
+        println!("pruning_point: {}", self.pruning_point);
+        println!("retention_period_root: {}", self.retention_period_root);
+        println!("retention_checkpoint: {}", self.retention_checkpoint);
+    }
+}
@@ -0,0 +1,97 @@
+//! Raw per-store statistics and entry dumps, keyed by the first key byte as assigned in
+//! [`DatabaseStorePrefixes`].
+
+use std::collections::BTreeMap;
+
+use kaspa_database::{prelude::DB, registry::DatabaseStorePrefixes};
+use num_traits::FromPrimitive;
+use rocksdb::{Direction, IteratorMode};
+
+use crate::{Error, Result};
+
+/// Entry count and logical (uncompressed) key/value sizes of a single store prefix
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StoreStats {
+    pub entries: u64,
+    pub key_bytes: u64,
+    pub value_bytes: u64,
+}
+
+impl StoreStats {
+    pub fn total_bytes(&self) -> u64 {
+        self.key_bytes + self.value_bytes
+    }
+
+    fn add(&mut self, key: &[u8], value: &[u8]) {
+        self.entries += 1;
+        self.key_bytes += key.len() as u64;
+        self.value_bytes += value.len() as u64;
+    }
+}
+
+/// Returns the registry name of a store prefix, or `Unknown(<byte>)` for unregistered prefixes
+pub fn store_name(prefix: u8) -> String {
+    DatabaseStorePrefixes::from_u8(prefix).map_or_else(|| format!("Unknown({prefix})"), |store| format!("{store:?}"))
+}
+
+/// Resolves a store given either by its registry name (case-insensitive) or by its numeric prefix
+pub fn parse_store(store: &str) -> Result<u8> {
+    if let Ok(prefix) = store.parse::<u8>() {
+        return Ok(prefix);
+    }
+    (0..=u8::MAX)
+        .find(|&prefix| DatabaseStorePrefixes::from_u8(prefix).is_some_and(|known| format!("{known:?}").eq_ignore_ascii_case(store)))
+        .ok_or_else(|| {
+            Error::InvalidArgs(format!("unknown store `{store}`; use a name from the database registry or a numeric prefix"))
+        })
+}
+
+/// Scans the whole DB once and aggregates entry counts and sizes per store prefix.
+/// `progress` is invoked with the running entry count every `progress_interval` entries.
+pub fn scan_store_stats(db: &DB, progress_interval: u64, mut progress: impl FnMut(u64)) -> Result<BTreeMap<u8, StoreStats>> {
+    let mut stats: BTreeMap<u8, StoreStats> = BTreeMap::new();
+    let mut scanned = 0u64;
+    for item in db.iterator(IteratorMode::Start) {
+        let (key, value) = item.map_err(|err| Error::RocksDb(err.into()))?;
+        let Some(&prefix) = key.first() else { continue };
+        stats.entry(prefix).or_default().add(&key, &value);
+        scanned += 1;
+        if progress_interval > 0 && scanned.is_multiple_of(progress_interval) {
+            progress(scanned);
+        }
+    }
+    Ok(stats)
+}
+
+/// Visits up to `limit` raw entries of the store with the given prefix, in key order.
+/// Returns the number of visited entries.
+pub fn dump_store(db: &DB, prefix: u8, limit: usize, mut visit: impl FnMut(&[u8], &[u8])) -> Result<usize> {
+    let mut visited = 0;
+    for item in db.iterator(IteratorMode::From(&[prefix], Direction::Forward)) {
+        if visited == limit {
+            break;
+        }
+        let (key, value) = item.map_err(|err| Error::RocksDb(err.into()))?;
+        if key.first() != Some(&prefix) {
+            break;
+        }
+        visit(&key, &value);
+        visited += 1;
+    }
+    Ok(visited)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_store_name_roundtrip() {
+        let prefix = DatabaseStorePrefixes::VirtualUtxoset as u8;
+        assert_eq!(store_name(prefix), "VirtualUtxoset");
+        assert_eq!(parse_store("virtualutxoset").unwrap(), prefix);
+        assert_eq!(parse_store(&prefix.to_string()).unwrap(), prefix);
+        assert_eq!(store_name(250), "Unknown(250)");
+        assert!(parse_store("NoSuchStore").is_err());
+    }
+}
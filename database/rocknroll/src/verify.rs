@@ -0,0 +1,149 @@
+//! Offline commitment checks: UTXO sets against their MuHash commitments and the SMT lanes root
+//! against the sequencing commitment of a chain block header.
+
+use std::sync::Arc;
+
+use kaspa_consensus::{
+    model::stores::{
+        headers::{DbHeadersStore, HeaderStoreReader},
+        pruning_meta::PruningMetaStores,
+        reachability::DbReachabilityStore,
+        smt_metadata::DbSmtMetadataStore,
+        utxo_set::DbUtxoSetStore,
+        virtual_state::{DbVirtualStateStore, LkgVirtualState, VirtualStateStoreReader},
+    },
+    processes::reachability::inquirer,
+};
+use kaspa_consensus_core::{config::params::ForkActivation, muhash::MuHashExtensions};
+use kaspa_database::{
+    prelude::{CachePolicy, DB},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::{Hash, ZERO_HASH};
+use kaspa_muhash::MuHash;
+use kaspa_seq_commit::verify::{SmtMetadata, SmtVerifyError, verify_smt_metadata};
+use kaspa_smt_store::processor::{SmtReadBounds, SmtStores};
+
+use crate::Result;
+
+/// Outcome of recomputing the MuHash of a stored UTXO set
+#[derive(Debug, Clone)]
+pub struct UtxoSetVerification {
+    /// The block whose commitment the set is checked against, if any (virtual has no header)
+    pub anchor: Option<Hash>,
+    pub entries: u64,
+    pub expected: Hash,
+    pub computed: Hash,
+}
+
+impl UtxoSetVerification {
+    pub fn is_valid(&self) -> bool {
+        self.expected == self.computed
+    }
+}
+
+/// Outcome of checking the SMT state at the point of view of a chain block
+#[derive(Debug, Clone)]
+pub struct SmtVerification {
+    pub block: Hash,
+    pub blue_score: u64,
+    /// Lanes root as read from the stored branch nodes
+    pub stored_lanes_root: Hash,
+    /// Lanes root rebuilt from the active lane versions
+    pub recomputed_lanes_root: Hash,
+    pub active_lanes_count: u64,
+    pub streamed_lanes_count: u64,
+    pub seq_commit: std::result::Result<(), SmtVerifyError>,
+}
+
+impl SmtVerification {
+    pub fn is_valid(&self) -> bool {
+        self.stored_lanes_root == self.recomputed_lanes_root
+            && self.active_lanes_count == self.streamed_lanes_count
+            && self.seq_commit.is_ok()
+    }
+}
+
+fn utxo_set_multiset(store: &DbUtxoSetStore, mut progress: impl FnMut(u64)) -> Result<(MuHash, u64)> {
+    let mut multiset = MuHash::new();
+    let mut entries = 0u64;
+    for item in store.iterator() {
+        let (outpoint, entry) = item?;
+        multiset.add_utxo(&outpoint, &entry);
+        entries += 1;
+        if entries.is_multiple_of(1_000_000) {
+            progress(entries);
+        }
+    }
+    Ok((multiset, entries))
+}
+
+/// Recomputes the MuHash of the virtual UTXO set and compares it with the multiset stored in the virtual state
+pub fn verify_virtual_utxo_set(db: Arc<DB>, progress: impl FnMut(u64)) -> Result<UtxoSetVerification> {
+    let virtual_state = DbVirtualStateStore::new(db.clone(), LkgVirtualState::default()).get()?;
+    let store = DbUtxoSetStore::new(db, CachePolicy::Empty, DatabaseStorePrefixes::VirtualUtxoset.into());
+    let (mut multiset, entries) = utxo_set_multiset(&store, progress)?;
+    Ok(UtxoSetVerification {
+        anchor: None,
+        entries,
+        expected: virtual_state.multiset.clone().finalize(),
+        computed: multiset.finalize(),
+    })
+}
+
+/// Recomputes the MuHash of the pruning point UTXO set and compares it with the UTXO commitment of the
+/// header at the stored UTXO set position
+pub fn verify_pruning_utxo_set(db: Arc<DB>, progress: impl FnMut(u64)) -> Result<UtxoSetVerification> {
+    let pruning_meta = PruningMetaStores::new(db.clone(), CachePolicy::Empty);
+    let position = pruning_meta.utxoset_position()?;
+    let header = DbHeadersStore::new(db, CachePolicy::Empty, CachePolicy::Empty).get_header(position)?;
+    let (mut multiset, entries) = utxo_set_multiset(&pruning_meta.utxo_set, progress)?;
+    Ok(UtxoSetVerification { anchor: Some(position), entries, expected: header.utxo_commitment, computed: multiset.finalize() })
+}
+
+/// Checks the SMT state from the point of view of `block`: the stored lanes root must match the root rebuilt
+/// from the active lanes, and together with the block SMT metadata it must reproduce the sequencing commitment
+/// (`accepted_id_merkle_root`) of the block header.
+pub fn verify_smt_state(db: Arc<DB>, block: Hash, finality_depth: u64, toccata_activation: ForkActivation) -> Result<SmtVerification> {
+    let headers = DbHeadersStore::new(db.clone(), CachePolicy::Empty, CachePolicy::Empty);
+    let header = headers.get_header(block)?;
+    let parent_header = headers.get_header(header.direct_parents()[0])?;
+    let metadata = DbSmtMetadataStore::new(db.clone(), CachePolicy::Empty).get(block)?;
+
+    // Same canonicity rule as consensus: IBD-imported entries (ZERO_HASH) are always canonical,
+    // otherwise the writing block must be a chain ancestor of the point of view
+    let reachability = DbReachabilityStore::new(db.clone(), CachePolicy::Empty, CachePolicy::Empty);
+    let is_canonical = |block_hash: Hash| {
+        block_hash == ZERO_HASH || matches!(inquirer::is_chain_ancestor_of(&reachability, block_hash, block), Ok(true))
+    };
+
+    let smt_stores = SmtStores::new(db, 1, 1);
+    let bounds = SmtReadBounds::for_pov(header.blue_score, finality_depth);
+    let stored_lanes_root = smt_stores.get_lanes_root(bounds, is_canonical);
+    let (recomputed_lanes_root, streamed_lanes_count) =
+        smt_stores.recompute_lanes_root_from_leaf_stream(bounds, metadata.active_lanes_count(), is_canonical)?;
+
+    let shortcut_header = headers.get_header(metadata.inactivity_shortcut_block())?;
+    let inactivity_shortcut =
+        if toccata_activation.is_active(shortcut_header.daa_score) { shortcut_header.accepted_id_merkle_root } else { ZERO_HASH };
+    let seq_commit = verify_smt_metadata(
+        &SmtMetadata {
+            lanes_root: &stored_lanes_root,
+            payload_and_ctx_digest: &metadata.payload_and_ctx_digest(),
+            parent_seq_commit: &parent_header.accepted_id_merkle_root,
+        },
+        inactivity_shortcut,
+        header.accepted_id_merkle_root,
+        parent_header.accepted_id_merkle_root,
+    );
+
+    Ok(SmtVerification {
+        block,
+        blue_score: header.blue_score,
+        stored_lanes_root,
+        recomputed_lanes_root,
+        active_lanes_count: metadata.active_lanes_count(),
+        streamed_lanes_count,
+        seq_commit,
+    })
+}
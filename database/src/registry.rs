@@ -74,6 +74,11 @@ pub enum DatabaseStorePrefixes {
     UtxoIndex = 192,
     UtxoIndexTips = 193,
     CirculatingSupply = 194,
+    AddressHistory = 195,
+    AddressHistoryOutpoints = 196,
+    AddressHistorySyncFlag = 197,
+    AddressHistoryCheckpoints = 198,
+    AddressHistorySink = 199,
 
     // ---- SMT Versioned Store ----
     SmtBranchVersions = 71,
@@ -1,6 +1,6 @@
 use kaspa_consensus_core::{
     BlockHashSet,
-    tx::{ScriptPublicKeys, TransactionOutpoint},
+    tx::{ScriptPublicKey, ScriptPublicKeys, TransactionOutpoint},
     utxo::utxo_diff::UtxoDiff,
 };
 use kaspa_consensusmanager::spawn_blocking;
@@ -12,7 +12,7 @@ use std::{collections::HashSet, fmt::Debug, sync::Arc};
 
 use crate::{
     errors::UtxoIndexResult,
-    model::{UtxoChanges, UtxoSetByScriptPublicKey},
+    model::{AddressHistoryCursor, AddressHistoryPage, UtxoChanges, UtxoSetByScriptPublicKey},
 };
 
 ///Utxoindex API targeted at retrieval calls.
@@ -29,6 +29,21 @@ pub trait UtxoIndexApi: Send + Sync + Debug {
 
     fn get_balance_by_script_public_keys(&self, script_public_keys: ScriptPublicKeys) -> StoreResult<BalanceByScriptPublicKey>;
 
+    /// Retrieve up to `limit` address history entries of a script public key, starting at `from`.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_address_history(
+        &self,
+        script_public_key: ScriptPublicKey,
+        from: AddressHistoryCursor,
+        limit: usize,
+    ) -> UtxoIndexResult<AddressHistoryPage>;
+
+    /// Retrieve the balances of script public keys as they were once all entries up to `daa_score` were applied.
+    ///
+    /// Note: Use a read lock when accessing this method
+    fn get_balances_at(&self, script_public_keys: ScriptPublicKeys, daa_score: u64) -> UtxoIndexResult<BalanceByScriptPublicKey>;
+
     // This can have a big memory footprint, so it should be used only for tests.
     fn get_all_outpoints(&self) -> StoreResult<HashSet<TransactionOutpoint>>;
 
@@ -81,6 +96,23 @@ impl UtxoIndexProxy {
         spawn_blocking(move || self.inner.read().get_balance_by_script_public_keys(script_public_keys)).await.unwrap()
     }
 
+    pub async fn get_address_history(
+        self,
+        script_public_key: ScriptPublicKey,
+        from: AddressHistoryCursor,
+        limit: usize,
+    ) -> UtxoIndexResult<AddressHistoryPage> {
+        spawn_blocking(move || self.inner.read().get_address_history(script_public_key, from, limit)).await.unwrap()
+    }
+
+    pub async fn get_balances_at(
+        self,
+        script_public_keys: ScriptPublicKeys,
+        daa_score: u64,
+    ) -> UtxoIndexResult<BalanceByScriptPublicKey> {
+        spawn_blocking(move || self.inner.read().get_balances_at(script_public_keys, daa_score)).await.unwrap()
+    }
+
     pub async fn update(self, utxo_diff: Arc<UtxoDiff>, tips: Arc<Vec<Hash>>) -> UtxoIndexResult<UtxoChanges> {
         spawn_blocking(move || self.inner.write().update(utxo_diff, tips)).await.unwrap()
     }
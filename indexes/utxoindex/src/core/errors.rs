@@ -2,6 +2,7 @@ use std::io;
 use thiserror::Error;
 
 use crate::IDENT;
+use kaspa_consensus_core::errors::consensus::ConsensusError;
 use kaspa_database::prelude::StoreError;
 
 /// Errors originating from the [`UtxoIndex`](crate::UtxoIndex).
@@ -12,6 +13,12 @@ pub enum UtxoIndexError {
 
     #[error("[{IDENT}]: {0}")]
     DBResetError(#[from] io::Error),
+
+    #[error("[{IDENT}]: {0}")]
+    ConsensusError(#[from] ConsensusError),
+
+    #[error("[{IDENT}]: the address history index is disabled")]
+    AddressHistoryDisabled,
 }
 
 /// Results originating from the [`UtxoIndex`](crate::UtxoIndex).
@@ -0,0 +1,117 @@
+use kaspa_consensus_core::tx::{TransactionId, TransactionIndexType, TransactionOutpoint};
+use kaspa_hashes::{HASH_SIZE, Hash};
+use serde::{Deserialize, Serialize};
+
+/// Direction of an address history entry
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum AddressHistoryEntryKind {
+    /// An output paying to the address was created by a transaction accepted on the selected chain
+    Credit = 0,
+    /// An output paying to the address was spent by a transaction accepted on the selected chain
+    Debit = 1,
+}
+
+/// A credit or debit of an address, as accepted by the selected chain
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AddressHistoryEntry {
+    /// The credited or debited outpoint
+    pub outpoint: TransactionOutpoint,
+    /// The transaction that created the credited output, or that spent the debited one
+    pub transaction_id: TransactionId,
+    pub kind: AddressHistoryEntryKind,
+    pub amount: u64,
+    /// The DAA score of the chain block accepting the creating or spending transaction. Outputs predating the history
+    /// are credited at the DAA score of their UTXO entry.
+    pub daa_score: u64,
+    pub is_coinbase: bool,
+}
+
+impl AddressHistoryEntry {
+    pub fn cursor(&self) -> AddressHistoryCursor {
+        AddressHistoryCursor { daa_score: self.daa_score, outpoint: self.outpoint, kind: self.kind }
+    }
+}
+
+/// Size of a serialized [`AddressHistoryCursor`]
+pub const ADDRESS_HISTORY_CURSOR_SIZE: usize = size_of::<u64>() + HASH_SIZE + size_of::<TransactionIndexType>() + 1;
+
+/// A position in the history of an address. Entries are ordered by DAA score, then outpoint and kind.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AddressHistoryCursor {
+    pub daa_score: u64,
+    pub outpoint: TransactionOutpoint,
+    pub kind: AddressHistoryEntryKind,
+}
+
+impl AddressHistoryCursor {
+    /// The position of the first entry having a DAA score of at least `daa_score`
+    pub fn from_daa_score(daa_score: u64) -> Self {
+        Self {
+            daa_score,
+            outpoint: TransactionOutpoint::new(Hash::from_bytes([0; HASH_SIZE]), 0),
+            kind: AddressHistoryEntryKind::Credit,
+        }
+    }
+
+    /// Serializes the cursor so that the byte order matches the entry order
+    pub fn to_bytes(&self) -> [u8; ADDRESS_HISTORY_CURSOR_SIZE] {
+        let mut bytes = [0; ADDRESS_HISTORY_CURSOR_SIZE];
+        bytes[..8].copy_from_slice(&self.daa_score.to_be_bytes());
+        bytes[8..8 + HASH_SIZE].copy_from_slice(&self.outpoint.transaction_id.as_bytes());
+        bytes[8 + HASH_SIZE..ADDRESS_HISTORY_CURSOR_SIZE - 1].copy_from_slice(&self.outpoint.index.to_be_bytes());
+        bytes[ADDRESS_HISTORY_CURSOR_SIZE - 1] = self.kind as u8;
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != ADDRESS_HISTORY_CURSOR_SIZE {
+            return None;
+        }
+        let daa_score = u64::from_be_bytes(bytes[..8].try_into().unwrap());
+        let transaction_id = Hash::from_slice(&bytes[8..8 + HASH_SIZE]);
+        let index = TransactionIndexType::from_be_bytes(bytes[8 + HASH_SIZE..ADDRESS_HISTORY_CURSOR_SIZE - 1].try_into().unwrap());
+        let kind = match bytes[ADDRESS_HISTORY_CURSOR_SIZE - 1] {
+            0 => AddressHistoryEntryKind::Credit,
+            1 => AddressHistoryEntryKind::Debit,
+            _ => return None,
+        };
+        Some(Self { daa_score, outpoint: TransactionOutpoint::new(transaction_id, index), kind })
+    }
+}
+
+/// A page of address history entries
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AddressHistoryPage {
+    pub entries: Vec<AddressHistoryEntry>,
+    /// The position of the next entry, if any
+    pub next: Option<AddressHistoryCursor>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_order() {
+        let cursor = |daa_score, index, kind| AddressHistoryCursor {
+            daa_score,
+            outpoint: TransactionOutpoint::new(Hash::from_u64_word(7), index),
+            kind,
+        };
+        let ordered = [
+            AddressHistoryCursor::from_daa_score(1),
+            cursor(1, 0, AddressHistoryEntryKind::Credit),
+            cursor(1, 0, AddressHistoryEntryKind::Debit),
+            cursor(1, 256, AddressHistoryEntryKind::Credit),
+            cursor(2, 0, AddressHistoryEntryKind::Credit),
+            cursor(256, 0, AddressHistoryEntryKind::Credit),
+        ];
+        for pair in ordered.windows(2) {
+            assert!(pair[0].to_bytes() < pair[1].to_bytes());
+        }
+        for cursor in ordered {
+            assert_eq!(AddressHistoryCursor::from_bytes(&cursor.to_bytes()), Some(cursor));
+        }
+        assert_eq!(AddressHistoryCursor::from_bytes(&[0; 3]), None);
+    }
+}
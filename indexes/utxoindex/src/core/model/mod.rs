@@ -1,3 +1,4 @@
+mod history;
 mod supply;
 
-pub use {kaspa_index_core::indexed_utxos::*, supply::*};
+pub use {history::*, kaspa_index_core::indexed_utxos::*, supply::*};
@@ -2,11 +2,15 @@ use crate::{
     IDENT,
     api::UtxoIndexApi,
     errors::{UtxoIndexError, UtxoIndexResult},
-    model::{CirculatingSupply, UtxoChanges, UtxoSetByScriptPublicKey},
+    model::{AddressHistoryCursor, AddressHistoryPage, CirculatingSupply, UtxoChanges, UtxoSetByScriptPublicKey},
     stores::store_manager::Store,
     update_container::UtxoIndexChanges,
 };
-use kaspa_consensus_core::{BlockHashSet, tx::ScriptPublicKeys, utxo::utxo_diff::UtxoDiff};
+use kaspa_consensus_core::{
+    BlockHashSet,
+    tx::{ScriptPublicKey, ScriptPublicKeys, SignableTransaction, TransactionQueryResult, TransactionType},
+    utxo::utxo_diff::UtxoDiff,
+};
 use kaspa_consensusmanager::{ConsensusManager, ConsensusResetHandler};
 use kaspa_core::{info, trace};
 use kaspa_database::prelude::{DB, StoreError, StoreResult};
@@ -36,9 +40,28 @@ pub struct UtxoIndex {
 impl UtxoIndex {
     /// Creates a new [`UtxoIndex`] within a [`RwLock`]
     pub fn new(consensus_manager: Arc<ConsensusManager>, db: Arc<DB>) -> UtxoIndexResult<Arc<RwLock<Self>>> {
-        let mut utxoindex =
-            Self { consensus_manager: consensus_manager.clone(), store: Store::new(db), monotonic_circulating_supply: 0 };
-        if !utxoindex.is_synced()? {
+        Self::with_address_history(consensus_manager, db, false)
+    }
+
+    /// Creates a new [`UtxoIndex`] within a [`RwLock`], optionally maintaining the address history index.
+    ///
+    /// The address history follows the transactions accepted by the selected chain, and reverts those of chain blocks
+    /// removed by a reorg. Enabling it on an existing index resyncs it, the history then starts with the UTXO set of that time.
+    /// When disabled, any previously stored history is removed since it would have gaps once re-enabled.
+    pub fn with_address_history(
+        consensus_manager: Arc<ConsensusManager>,
+        db: Arc<DB>,
+        address_history: bool,
+    ) -> UtxoIndexResult<Arc<RwLock<Self>>> {
+        let mut utxoindex = Self {
+            consensus_manager: consensus_manager.clone(),
+            store: Store::new(db, address_history),
+            monotonic_circulating_supply: 0,
+        };
+        if !address_history {
+            utxoindex.store.delete_address_history()?;
+        }
+        if !utxoindex.is_synced()? || (address_history && !utxoindex.store.is_address_history_synced()?) {
             utxoindex.resync()?;
         } else {
             utxoindex.monotonic_circulating_supply = utxoindex.store.get_circulating_supply()?;
@@ -47,6 +70,37 @@ impl UtxoIndex {
         consensus_manager.register_consensus_reset_handler(Arc::new(UtxoIndexConsensusResetHandler::new(Arc::downgrade(&utxoindex))));
         Ok(utxoindex)
     }
+
+    /// Brings the address history to the current sink, reverting the chain blocks it left and applying the ones it added.
+    fn update_address_history(&mut self) -> UtxoIndexResult<()> {
+        let Some(history_sink) = self.store.get_address_history_sink()? else {
+            return Ok(());
+        };
+        let consensus = self.consensus_manager.consensus();
+        let session = futures::executor::block_on(consensus.session_blocking());
+
+        let chain_path = session.get_virtual_chain_from_block(history_sink, None)?;
+        let accepted_transactions = |hash| -> UtxoIndexResult<(u64, Vec<SignableTransaction>)> {
+            let daa_score = session.get_header(hash)?.daa_score;
+            match session.get_transactions_by_accepting_block(hash, None, TransactionType::SignableTransaction)? {
+                TransactionQueryResult::SignableTransaction(transactions) => Ok((daa_score, transactions.unwrap_or_clone())),
+                TransactionQueryResult::Transaction(_) => unreachable!("signable transactions were requested"),
+            }
+        };
+
+        if let Some(lowest_removed) = chain_path.removed.last() {
+            trace!("[{0}] reverting {1} chain blocks from the address history", IDENT, chain_path.removed.len());
+            let common_ancestor = session.get_ghostdag_data(*lowest_removed)?.selected_parent;
+            let removed = chain_path.removed.iter().map(|hash| accepted_transactions(*hash)).collect::<UtxoIndexResult<Vec<_>>>()?;
+            self.store.revert_address_history_chain_blocks(common_ancestor, &removed)?;
+        }
+        trace!("[{0}] applying {1} chain blocks to the address history", IDENT, chain_path.added.len());
+        for hash in chain_path.added.iter().copied() {
+            let (daa_score, transactions) = accepted_transactions(hash)?;
+            self.store.apply_address_history_chain_block(hash, daa_score, &transactions)?;
+        }
+        Ok(())
+    }
 }
 
 impl UtxoIndexApi for UtxoIndex {
@@ -71,6 +125,31 @@ impl UtxoIndexApi for UtxoIndex {
         self.store.get_balance_by_script_public_key(script_public_keys)
     }
 
+    /// Retrieve a page of the address history of a script public key.
+    fn get_address_history(
+        &self,
+        script_public_key: ScriptPublicKey,
+        from: AddressHistoryCursor,
+        limit: usize,
+    ) -> UtxoIndexResult<AddressHistoryPage> {
+        trace!("[{0}] retrieving up to {1} address history entries", IDENT, limit);
+
+        if !self.store.is_address_history_enabled() {
+            return Err(UtxoIndexError::AddressHistoryDisabled);
+        }
+        Ok(self.store.get_address_history(&script_public_key, from, limit)?)
+    }
+
+    /// Retrieve historical balances of script public keys from the address history.
+    fn get_balances_at(&self, script_public_keys: ScriptPublicKeys, daa_score: u64) -> UtxoIndexResult<BalanceByScriptPublicKey> {
+        trace!("[{0}] retrieving balances at DAA score {1} of {2} script public keys", IDENT, daa_score, script_public_keys.len());
+
+        if !self.store.is_address_history_enabled() {
+            return Err(UtxoIndexError::AddressHistoryDisabled);
+        }
+        Ok(self.store.get_balances_at(script_public_keys, daa_score)?)
+    }
+
     /// Retrieve the stored tips of the utxoindex.
     fn get_utxo_index_tips(&self) -> StoreResult<Arc<BlockHashSet>> {
         trace!("[{0}] retrieving tips", IDENT);
@@ -105,6 +184,10 @@ impl UtxoIndexApi for UtxoIndex {
         // Commit new consensus virtual tips.
         self.store.set_tips(utxoindex_changes.tips, false)?; //we expect new tips with every virtual!
 
+        if self.store.is_address_history_enabled() {
+            self.update_address_history()?;
+        }
+
         // Return the resulting changes in utxoindex.
         Ok(utxoindex_changes.utxo_changes)
     }
@@ -168,6 +251,9 @@ impl UtxoIndexApi for UtxoIndex {
             circulating_supply += utxoindex_changes.supply_change as CirculatingSupply;
 
             self.store.update_utxo_state(&utxoindex_changes.utxo_changes.added, &utxoindex_changes.utxo_changes.removed, true)?;
+            if self.store.is_address_history_enabled() {
+                self.store.seed_address_history(&utxoindex_changes.utxo_changes.added, true)?;
+            }
 
             if current_chunk_size < RESYNC_CHUNK_SIZE {
                 break;
@@ -187,6 +273,12 @@ impl UtxoIndexApi for UtxoIndex {
         trace!("[{0}] committing consensus tips {consensus_tips:?} from consensus db", IDENT);
         self.store.set_tips(consensus_tips, true)?;
 
+        if self.store.is_address_history_enabled() {
+            let sink = session.get_sink();
+            trace!("[{0}] marking the address history as synced at sink {1}", IDENT, sink);
+            self.store.set_address_history_synced(sink, true)?;
+        }
+
         Ok(())
     }
 
@@ -0,0 +1,578 @@
+use crate::{
+    model::{
+        ADDRESS_HISTORY_CURSOR_SIZE, AddressHistoryCursor, AddressHistoryEntry, AddressHistoryEntryKind, AddressHistoryPage,
+        UtxoSetByScriptPublicKey,
+    },
+    stores::indexed_utxos::{ScriptPublicKeyBucket, TransactionOutpointKey, UtxoEntryFullAccessKey},
+};
+use kaspa_consensus_core::tx::{ScriptPublicKey, ScriptPublicKeys, SignableTransaction, TransactionId, TransactionOutpoint};
+use kaspa_database::{
+    prelude::{BatchDbWriter, CachePolicy, CachedDbAccess, CachedDbItem, DB, DirectDbWriter, StoreError, StoreResult, StoreResultExt},
+    registry::DatabaseStorePrefixes,
+};
+use kaspa_hashes::Hash;
+use kaspa_index_core::indexed_utxos::BalanceByScriptPublicKey;
+use kaspa_utils::mem_size::MemSizeEstimator;
+use rocksdb::WriteBatch;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt::Display, sync::Arc};
+
+/// DAA score span of a balance checkpoint. A historical balance sums the checkpoints preceding the requested DAA score,
+/// so it only scans the entries of a single span.
+pub const BALANCE_CHECKPOINT_SPAN: u64 = 100_000;
+
+/// Address history entry access key.
+/// Consists of variable amount of bytes of [ScriptPublicKeyBucket], followed by the bytes of an [AddressHistoryCursor]
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+struct AddressHistoryKey(Arc<Vec<u8>>);
+
+impl AddressHistoryKey {
+    fn new(script_public_key_bucket: &ScriptPublicKeyBucket, cursor: &AddressHistoryCursor) -> Self {
+        let mut bytes = Vec::with_capacity(script_public_key_bucket.as_ref().len() + ADDRESS_HISTORY_CURSOR_SIZE);
+        bytes.extend_from_slice(script_public_key_bucket.as_ref());
+        bytes.extend_from_slice(&cursor.to_bytes());
+        Self(Arc::new(bytes))
+    }
+}
+
+impl Display for AddressHistoryKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl AsRef<[u8]> for AddressHistoryKey {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+/// Balance checkpoint access key.
+/// Consists of variable amount of bytes of [ScriptPublicKeyBucket], followed by the big endian index of the checkpoint span
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+struct BalanceCheckpointKey(Arc<Vec<u8>>);
+
+impl BalanceCheckpointKey {
+    fn new(script_public_key_bucket: &ScriptPublicKeyBucket, span: u64) -> Self {
+        let mut bytes = Vec::with_capacity(script_public_key_bucket.as_ref().len() + size_of::<u64>());
+        bytes.extend_from_slice(script_public_key_bucket.as_ref());
+        bytes.extend_from_slice(&span.to_be_bytes());
+        Self(Arc::new(bytes))
+    }
+}
+
+impl Display for BalanceCheckpointKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl AsRef<[u8]> for BalanceCheckpointKey {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_slice()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct AddressHistoryRecord {
+    amount: u64,
+    is_coinbase: bool,
+    /// The transaction creating the credited output or spending the debited one
+    transaction_id: TransactionId,
+}
+
+impl MemSizeEstimator for AddressHistoryRecord {}
+
+/// The net balance change of the entries of an address within a checkpoint span
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+struct BalanceCheckpoint {
+    delta: i128,
+}
+
+impl MemSizeEstimator for BalanceCheckpoint {}
+
+/// The credit of an outpoint, allowing to find it once the outpoint is spent or its creation moves
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct OutpointCredit {
+    daa_score: u64,
+    amount: u64,
+    is_coinbase: bool,
+}
+
+impl MemSizeEstimator for OutpointCredit {}
+
+/// Changes accumulated for a single write batch, overlaying the stored data
+#[derive(Default)]
+struct HistoryChanges {
+    entries: HashMap<AddressHistoryKey, Option<AddressHistoryRecord>>,
+    outpoints: HashMap<UtxoEntryFullAccessKey, Option<OutpointCredit>>,
+    checkpoints: HashMap<BalanceCheckpointKey, i128>,
+}
+
+// Traits:
+
+pub trait AddressHistoryStoreReader {
+    /// Get up to `limit` history entries of `script_public_key` starting at `from`
+    fn get_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        from: AddressHistoryCursor,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage>;
+
+    /// Get the balance of each script public key once all entries up to `daa_score` (included) are applied
+    fn get_balances_at(&self, script_public_keys: ScriptPublicKeys, daa_score: u64) -> StoreResult<BalanceByScriptPublicKey>;
+
+    /// Get the last chain block whose accepted transactions are recorded
+    fn get_sink(&self) -> StoreResult<Option<Hash>>;
+
+    fn is_synced(&self) -> StoreResult<bool>;
+}
+
+pub trait AddressHistoryStore: AddressHistoryStoreReader {
+    /// Records the credits of UTXOs predating the history
+    fn seed_utxos(&mut self, utxos: &UtxoSetByScriptPublicKey) -> StoreResult<()>;
+
+    /// Records the credits and debits of the transactions accepted by a chain block with DAA score `daa_score`, which becomes the sink
+    fn apply_chain_block(&mut self, sink: Hash, daa_score: u64, transactions: &[SignableTransaction]) -> StoreResult<()>;
+
+    /// Reverts the entries of chain blocks removed from the selected chain, given as DAA scores along with the accepted
+    /// transactions. `sink` is the chain block the removed chain blocks were built on.
+    fn revert_chain_blocks(&mut self, sink: Hash, blocks: &[(u64, Vec<SignableTransaction>)]) -> StoreResult<()>;
+
+    /// Marks the history as following the selected chain from `sink`
+    fn set_synced(&mut self, sink: Hash) -> StoreResult<()>;
+
+    /// removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()>;
+}
+
+// Implementations:
+
+#[derive(Clone)]
+pub struct DbAddressHistoryStore {
+    db: Arc<DB>,
+    access: CachedDbAccess<AddressHistoryKey, AddressHistoryRecord>,
+    outpoints_access: CachedDbAccess<UtxoEntryFullAccessKey, OutpointCredit>,
+    checkpoints_access: CachedDbAccess<BalanceCheckpointKey, BalanceCheckpoint>,
+    sink_access: CachedDbItem<Hash>,
+    sync_flag_access: CachedDbItem<bool>,
+}
+
+impl DbAddressHistoryStore {
+    pub fn new(db: Arc<DB>) -> Self {
+        Self {
+            db: Arc::clone(&db),
+            access: CachedDbAccess::new(db.clone(), CachePolicy::Empty, DatabaseStorePrefixes::AddressHistory.into()),
+            outpoints_access: CachedDbAccess::new(
+                db.clone(),
+                CachePolicy::Empty,
+                DatabaseStorePrefixes::AddressHistoryOutpoints.into(),
+            ),
+            checkpoints_access: CachedDbAccess::new(
+                db.clone(),
+                CachePolicy::Empty,
+                DatabaseStorePrefixes::AddressHistoryCheckpoints.into(),
+            ),
+            sink_access: CachedDbItem::new(db.clone(), DatabaseStorePrefixes::AddressHistorySink.into()),
+            sync_flag_access: CachedDbItem::new(db, DatabaseStorePrefixes::AddressHistorySyncFlag.into()),
+        }
+    }
+
+    fn outpoint_credit(&self, changes: &HistoryChanges, key: &UtxoEntryFullAccessKey) -> StoreResult<Option<OutpointCredit>> {
+        match changes.outpoints.get(key) {
+            Some(credit) => Ok(*credit),
+            None => self.outpoints_access.read(key.clone()).optional(),
+        }
+    }
+
+    fn entry_exists(&self, changes: &HistoryChanges, key: &AddressHistoryKey) -> StoreResult<bool> {
+        match changes.entries.get(key) {
+            Some(record) => Ok(record.is_some()),
+            None => self.access.has(key.clone()),
+        }
+    }
+
+    fn checkpoint_delta(kind: AddressHistoryEntryKind, amount: u64) -> i128 {
+        match kind {
+            AddressHistoryEntryKind::Credit => amount as i128,
+            AddressHistoryEntryKind::Debit => -(amount as i128),
+        }
+    }
+
+    /// Adds an entry unless it already exists, so that replaying changes leaves the checkpoints untouched
+    fn insert_entry(
+        &self,
+        changes: &mut HistoryChanges,
+        bucket: &ScriptPublicKeyBucket,
+        cursor: AddressHistoryCursor,
+        record: AddressHistoryRecord,
+    ) -> StoreResult<()> {
+        let key = AddressHistoryKey::new(bucket, &cursor);
+        if !self.entry_exists(changes, &key)? {
+            changes.entries.insert(key, Some(record));
+            *changes.checkpoints.entry(BalanceCheckpointKey::new(bucket, cursor.daa_score / BALANCE_CHECKPOINT_SPAN)).or_default() +=
+                Self::checkpoint_delta(cursor.kind, record.amount);
+        }
+        Ok(())
+    }
+
+    fn remove_entry(
+        &self,
+        changes: &mut HistoryChanges,
+        bucket: &ScriptPublicKeyBucket,
+        cursor: AddressHistoryCursor,
+        amount: u64,
+    ) -> StoreResult<()> {
+        let key = AddressHistoryKey::new(bucket, &cursor);
+        if self.entry_exists(changes, &key)? {
+            changes.entries.insert(key, None);
+            *changes.checkpoints.entry(BalanceCheckpointKey::new(bucket, cursor.daa_score / BALANCE_CHECKPOINT_SPAN)).or_default() -=
+                Self::checkpoint_delta(cursor.kind, amount);
+        }
+        Ok(())
+    }
+
+    /// Records the credit of an output accepted at `daa_score`, moving a credit recorded with another DAA score
+    fn credit(
+        &self,
+        changes: &mut HistoryChanges,
+        bucket: &ScriptPublicKeyBucket,
+        outpoint: TransactionOutpoint,
+        credit: OutpointCredit,
+    ) -> StoreResult<()> {
+        let outpoint_key = UtxoEntryFullAccessKey::new(bucket.clone(), TransactionOutpointKey::from(&outpoint));
+        match self.outpoint_credit(changes, &outpoint_key)? {
+            Some(current) if current.daa_score == credit.daa_score => return Ok(()),
+            Some(current) => {
+                // The output was seeded while only accepted by the virtual, or its creating transaction is accepted anew
+                let cursor = AddressHistoryCursor { daa_score: current.daa_score, outpoint, kind: AddressHistoryEntryKind::Credit };
+                self.remove_entry(changes, bucket, cursor, current.amount)?;
+            }
+            None => {}
+        }
+        let cursor = AddressHistoryCursor { daa_score: credit.daa_score, outpoint, kind: AddressHistoryEntryKind::Credit };
+        let record =
+            AddressHistoryRecord { amount: credit.amount, is_coinbase: credit.is_coinbase, transaction_id: outpoint.transaction_id };
+        self.insert_entry(changes, bucket, cursor, record)?;
+        changes.outpoints.insert(outpoint_key, Some(credit));
+        Ok(())
+    }
+
+    fn commit(&mut self, changes: HistoryChanges, sink: Option<Hash>) -> StoreResult<()> {
+        let mut batch = WriteBatch::default();
+        let mut writer = BatchDbWriter::new(&mut batch);
+        for (key, record) in changes.entries {
+            match record {
+                Some(record) => self.access.write(&mut writer, key, record)?,
+                None => self.access.delete(&mut writer, key)?,
+            }
+        }
+        for (key, credit) in changes.outpoints {
+            match credit {
+                Some(credit) => self.outpoints_access.write(&mut writer, key, credit)?,
+                None => self.outpoints_access.delete(&mut writer, key)?,
+            }
+        }
+        for (key, delta) in changes.checkpoints.into_iter().filter(|(_, delta)| *delta != 0) {
+            let mut checkpoint = self.checkpoints_access.read(key.clone()).optional()?.unwrap_or_default();
+            checkpoint.delta += delta;
+            self.checkpoints_access.write(&mut writer, key, checkpoint)?;
+        }
+        if let Some(sink) = sink {
+            self.sink_access.write(&mut writer, &sink)?;
+        }
+        self.db.write(batch)?;
+        Ok(())
+    }
+}
+
+impl AddressHistoryStoreReader for DbAddressHistoryStore {
+    fn get_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        from: AddressHistoryCursor,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        let script_public_key_bucket = ScriptPublicKeyBucket::from(script_public_key);
+        let seek_key = AddressHistoryKey::new(&script_public_key_bucket, &from);
+        let mut page = AddressHistoryPage::default();
+        // One extra entry is read in order to know where the next page starts
+        for res in self.access.seek_iterator(Some(script_public_key_bucket.as_ref()), Some(seek_key), limit.saturating_add(1), false) {
+            let (key, record) = res.map_err(|err| StoreError::DataInconsistency(err.to_string()))?;
+            let cursor = AddressHistoryCursor::from_bytes(&key).expect("keys are built from cursors");
+            if page.entries.len() == limit {
+                page.next = Some(cursor);
+                break;
+            }
+            page.entries.push(AddressHistoryEntry {
+                outpoint: cursor.outpoint,
+                transaction_id: record.transaction_id,
+                kind: cursor.kind,
+                amount: record.amount,
+                daa_score: cursor.daa_score,
+                is_coinbase: record.is_coinbase,
+            });
+        }
+        Ok(page)
+    }
+
+    fn get_balances_at(&self, script_public_keys: ScriptPublicKeys, daa_score: u64) -> StoreResult<BalanceByScriptPublicKey> {
+        let span = daa_score / BALANCE_CHECKPOINT_SPAN;
+        let mut balances = BalanceByScriptPublicKey::new();
+        for script_public_key in script_public_keys.into_iter() {
+            let script_public_key_bucket = ScriptPublicKeyBucket::from(&script_public_key);
+            let mut balance: i128 = 0;
+            // The checkpoints of all spans preceding the one of `daa_score`
+            for res in self.checkpoints_access.seek_iterator(Some(script_public_key_bucket.as_ref()), None, usize::MAX, false) {
+                let (key, checkpoint) = res.map_err(|err| StoreError::DataInconsistency(err.to_string()))?;
+                let checkpoint_span = u64::from_be_bytes(key[..].try_into().expect("keys end with the span"));
+                if checkpoint_span >= span {
+                    break;
+                }
+                balance += checkpoint.delta;
+            }
+            // Followed by the entries of the span up to `daa_score`
+            let seek_key = AddressHistoryKey::new(
+                &script_public_key_bucket,
+                &AddressHistoryCursor::from_daa_score(span * BALANCE_CHECKPOINT_SPAN),
+            );
+            for res in self.access.seek_iterator(Some(script_public_key_bucket.as_ref()), Some(seek_key), usize::MAX, false) {
+                let (key, record) = res.map_err(|err| StoreError::DataInconsistency(err.to_string()))?;
+                let cursor = AddressHistoryCursor::from_bytes(&key).expect("keys are built from cursors");
+                if cursor.daa_score > daa_score {
+                    break;
+                }
+                balance += Self::checkpoint_delta(cursor.kind, record.amount);
+            }
+            let balance = u64::try_from(balance).map_err(|_| {
+                StoreError::DataInconsistency(format!(
+                    "balance {balance} of {script_public_key:?} at DAA score {daa_score} is out of range"
+                ))
+            })?;
+            balances.insert(script_public_key, balance);
+        }
+        Ok(balances)
+    }
+
+    fn get_sink(&self) -> StoreResult<Option<Hash>> {
+        self.sink_access.read().optional()
+    }
+
+    fn is_synced(&self) -> StoreResult<bool> {
+        Ok(self.sync_flag_access.read().optional()?.unwrap_or(false) && self.get_sink()?.is_some())
+    }
+}
+
+impl AddressHistoryStore for DbAddressHistoryStore {
+    fn seed_utxos(&mut self, utxos: &UtxoSetByScriptPublicKey) -> StoreResult<()> {
+        let mut changes = HistoryChanges::default();
+        for (script_public_key, collection) in utxos.iter() {
+            let bucket = ScriptPublicKeyBucket::from(script_public_key);
+            for (outpoint, entry) in collection.iter() {
+                let credit = OutpointCredit { daa_score: entry.block_daa_score, amount: entry.amount, is_coinbase: entry.is_coinbase };
+                self.credit(&mut changes, &bucket, *outpoint, credit)?;
+            }
+        }
+        self.commit(changes, None)
+    }
+
+    fn apply_chain_block(&mut self, sink: Hash, daa_score: u64, transactions: &[SignableTransaction]) -> StoreResult<()> {
+        let mut changes = HistoryChanges::default();
+        for transaction in transactions {
+            let transaction_id = transaction.id();
+            for (input, entry) in transaction.tx.inputs.iter().zip(transaction.entries.iter()) {
+                let entry = entry.as_ref().ok_or_else(|| {
+                    StoreError::DataInconsistency(format!(
+                        "accepted transaction {} has no entry for outpoint {}",
+                        transaction_id, input.previous_outpoint
+                    ))
+                })?;
+                let bucket = ScriptPublicKeyBucket::from(&entry.script_public_key);
+                let outpoint = input.previous_outpoint;
+                // The credit of an output predating the history is recorded along with its debit
+                let credit = OutpointCredit { daa_score: entry.block_daa_score, amount: entry.amount, is_coinbase: entry.is_coinbase };
+                let outpoint_key = UtxoEntryFullAccessKey::new(bucket.clone(), TransactionOutpointKey::from(&outpoint));
+                if self.outpoint_credit(&changes, &outpoint_key)?.is_none() {
+                    self.credit(&mut changes, &bucket, outpoint, credit)?;
+                }
+                let cursor = AddressHistoryCursor { daa_score, outpoint, kind: AddressHistoryEntryKind::Debit };
+                let record = AddressHistoryRecord { amount: entry.amount, is_coinbase: entry.is_coinbase, transaction_id };
+                self.insert_entry(&mut changes, &bucket, cursor, record)?;
+            }
+            let is_coinbase = transaction.tx.is_coinbase();
+            for (index, output) in transaction.tx.outputs.iter().enumerate() {
+                let bucket = ScriptPublicKeyBucket::from(&output.script_public_key);
+                let outpoint = TransactionOutpoint::new(transaction_id, index as u32);
+                self.credit(&mut changes, &bucket, outpoint, OutpointCredit { daa_score, amount: output.value, is_coinbase })?;
+            }
+        }
+        self.commit(changes, Some(sink))
+    }
+
+    fn revert_chain_blocks(&mut self, sink: Hash, blocks: &[(u64, Vec<SignableTransaction>)]) -> StoreResult<()> {
+        let mut changes = HistoryChanges::default();
+        for (daa_score, transactions) in blocks.iter() {
+            for transaction in transactions.iter().rev() {
+                let transaction_id = transaction.id();
+                // The outputs were never created on the selected chain, so their credits are removed rather than debited
+                for (index, output) in transaction.tx.outputs.iter().enumerate() {
+                    let bucket = ScriptPublicKeyBucket::from(&output.script_public_key);
+                    let outpoint = TransactionOutpoint::new(transaction_id, index as u32);
+                    let outpoint_key = UtxoEntryFullAccessKey::new(bucket.clone(), TransactionOutpointKey::from(&outpoint));
+                    if self.outpoint_credit(&changes, &outpoint_key)?.is_some_and(|credit| credit.daa_score == *daa_score) {
+                        changes.outpoints.insert(outpoint_key, None);
+                    }
+                    let cursor = AddressHistoryCursor { daa_score: *daa_score, outpoint, kind: AddressHistoryEntryKind::Credit };
+                    self.remove_entry(&mut changes, &bucket, cursor, output.value)?;
+                }
+                // The spent outputs return to the UTXO set along with their credits
+                for (input, entry) in transaction.tx.inputs.iter().zip(transaction.entries.iter()) {
+                    let Some(entry) = entry else { continue };
+                    let bucket = ScriptPublicKeyBucket::from(&entry.script_public_key);
+                    let cursor = AddressHistoryCursor {
+                        daa_score: *daa_score,
+                        outpoint: input.previous_outpoint,
+                        kind: AddressHistoryEntryKind::Debit,
+                    };
+                    self.remove_entry(&mut changes, &bucket, cursor, entry.amount)?;
+                }
+            }
+        }
+        self.commit(changes, Some(sink))
+    }
+
+    fn set_synced(&mut self, sink: Hash) -> StoreResult<()> {
+        self.sink_access.write(DirectDbWriter::new(&self.db), &sink)?;
+        self.sync_flag_access.write(DirectDbWriter::new(&self.db), &true)
+    }
+
+    /// Removes all entries in the cache and db, besides prefixes themselves.
+    fn delete_all(&mut self) -> StoreResult<()> {
+        self.sync_flag_access.remove(DirectDbWriter::new(&self.db)).optional()?;
+        self.sink_access.remove(DirectDbWriter::new(&self.db)).optional()?;
+        self.checkpoints_access.delete_all(DirectDbWriter::new(&self.db))?;
+        self.outpoints_access.delete_all(DirectDbWriter::new(&self.db))?;
+        self.access.delete_all(DirectDbWriter::new(&self.db))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::CompactUtxoEntry;
+    use kaspa_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{Transaction, TransactionInput, TransactionOutput, UtxoEntry},
+    };
+    use kaspa_database::{create_temp_db, prelude::ConnBuilder};
+
+    fn transaction(inputs: &[(TransactionOutpoint, UtxoEntry)], outputs: &[(u64, &ScriptPublicKey)]) -> SignableTransaction {
+        let tx = Transaction::new(
+            0,
+            inputs.iter().map(|(outpoint, _)| TransactionInput::new(*outpoint, vec![], 0, 1)).collect(),
+            outputs.iter().map(|&(value, script_public_key)| TransactionOutput::new(value, script_public_key.clone())).collect(),
+            0,
+            SUBNETWORK_ID_NATIVE,
+            0,
+            vec![],
+        );
+        SignableTransaction::with_entries(tx, inputs.iter().map(|(_, entry)| entry.clone()).collect())
+    }
+
+    #[test]
+    fn test_address_history_reorgs() {
+        let (_lifetime, db) = create_temp_db!(ConnBuilder::default().with_files_limit(10));
+        let mut store = DbAddressHistoryStore::new(db);
+        let script_public_key = ScriptPublicKey::from_vec(0, vec![1; 34]);
+        let other_script_public_key = ScriptPublicKey::from_vec(0, vec![2; 34]);
+        let balance_at = |store: &DbAddressHistoryStore, daa_score| {
+            store.get_balances_at(ScriptPublicKeys::from_iter([script_public_key.clone()]), daa_score).unwrap()[&script_public_key]
+        };
+        let history = |store: &DbAddressHistoryStore| {
+            store.get_history(&script_public_key, AddressHistoryCursor::from_daa_score(0), 100).unwrap().entries
+        };
+
+        // An output predating the history
+        let seeded = TransactionOutpoint::new(Hash::from_u64_word(1), 0);
+        let seeded_entry = CompactUtxoEntry { amount: 100, block_daa_score: 10, is_coinbase: false, covenant_id: None };
+        store
+            .seed_utxos(&UtxoSetByScriptPublicKey::from_iter([(script_public_key.clone(), [(seeded, seeded_entry)].into())]))
+            .unwrap();
+        store.set_synced(Hash::from_u64_word(100)).unwrap();
+        assert!(store.is_synced().unwrap());
+
+        // A chain block spends it, paying back part of it
+        let first = transaction(
+            &[(seeded, UtxoEntry::new(100, script_public_key.clone(), 10, false, None))],
+            &[(60, &script_public_key), (40, &other_script_public_key)],
+        );
+        let change = TransactionOutpoint::new(first.id(), 0);
+        store.apply_chain_block(Hash::from_u64_word(101), 20, std::slice::from_ref(&first)).unwrap();
+        assert_eq!(store.get_sink().unwrap(), Some(Hash::from_u64_word(101)));
+        let entries = history(&store);
+        assert_eq!(entries.len(), 3);
+        let debit = entries.iter().find(|entry| entry.kind == AddressHistoryEntryKind::Debit).unwrap();
+        assert_eq!((debit.outpoint, debit.transaction_id, debit.daa_score), (seeded, first.id(), 20));
+        assert_eq!(balance_at(&store, 9), 0);
+        assert_eq!(balance_at(&store, 19), 100);
+        assert_eq!(balance_at(&store, 20), 60);
+
+        // A chain block of a later checkpoint span spends the change
+        let second =
+            transaction(&[(change, UtxoEntry::new(60, script_public_key.clone(), 20, false, None))], &[(50, &script_public_key)]);
+        store.apply_chain_block(Hash::from_u64_word(102), 150_000, std::slice::from_ref(&second)).unwrap();
+        // Replaying a chain block leaves the history untouched
+        store.apply_chain_block(Hash::from_u64_word(102), 150_000, std::slice::from_ref(&second)).unwrap();
+        assert_eq!(history(&store).len(), 5);
+        assert_eq!(balance_at(&store, 149_999), 60);
+        assert_eq!(balance_at(&store, 150_000), 50);
+        assert_eq!(balance_at(&store, 1_000_000), 50);
+
+        // A reorg removes the chain block, its creation is undone rather than debited
+        store.revert_chain_blocks(Hash::from_u64_word(101), &[(150_000, vec![second.clone()])]).unwrap();
+        assert_eq!(store.get_sink().unwrap(), Some(Hash::from_u64_word(101)));
+        assert_eq!(history(&store).len(), 3);
+        assert!(history(&store).iter().all(|entry| entry.transaction_id != second.id()));
+        assert_eq!(balance_at(&store, 1_000_000), 60);
+
+        // The transaction is accepted again by another chain block
+        store.apply_chain_block(Hash::from_u64_word(103), 260_000, std::slice::from_ref(&second)).unwrap();
+        assert_eq!(balance_at(&store, 150_000), 60);
+        assert_eq!(balance_at(&store, 260_000), 50);
+
+        // An output seeded while only accepted by the virtual moves to the accepting chain block
+        let third = transaction(
+            &[(TransactionOutpoint::new(second.id(), 0), UtxoEntry::new(50, script_public_key.clone(), 260_000, false, None))],
+            &[(45, &script_public_key)],
+        );
+        let third_entry = CompactUtxoEntry { amount: 45, block_daa_score: 260_010, is_coinbase: false, covenant_id: None };
+        store
+            .seed_utxos(&UtxoSetByScriptPublicKey::from_iter([(
+                script_public_key.clone(),
+                [(TransactionOutpoint::new(third.id(), 0), third_entry)].into(),
+            )]))
+            .unwrap();
+        store.apply_chain_block(Hash::from_u64_word(104), 260_005, std::slice::from_ref(&third)).unwrap();
+        let credits = history(&store).into_iter().filter(|entry| entry.outpoint.transaction_id == third.id()).collect::<Vec<_>>();
+        assert_eq!(credits.len(), 1);
+        assert_eq!(credits[0].daa_score, 260_005);
+        assert_eq!(balance_at(&store, 260_010), 45);
+
+        // Pagination follows the DAA score order
+        let page = store.get_history(&script_public_key, AddressHistoryCursor::from_daa_score(0), 1).unwrap();
+        assert_eq!(page.entries.len(), 1);
+        assert_eq!(
+            (page.entries[0].outpoint, page.entries[0].kind, page.entries[0].daa_score),
+            (seeded, AddressHistoryEntryKind::Credit, 10)
+        );
+        let page = store.get_history(&script_public_key, page.next.unwrap(), 100).unwrap();
+        assert!(page.entries.windows(2).all(|pair| pair[0].daa_score <= pair[1].daa_score));
+        assert!(page.next.is_none());
+
+        store.delete_all().unwrap();
+        assert!(history(&store).is_empty());
+        assert!(!store.is_synced().unwrap());
+        assert_eq!(balance_at(&store, 1_000_000), 0);
+    }
+}
@@ -1,3 +1,4 @@
+mod address_history;
 mod indexed_utxos;
 pub mod store_manager;
 mod supply;
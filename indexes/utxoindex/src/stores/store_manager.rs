@@ -2,16 +2,18 @@ use std::{collections::HashSet, sync::Arc};
 
 use kaspa_consensus_core::{
     BlockHashSet,
-    tx::{ScriptPublicKeys, TransactionOutpoint},
+    tx::{ScriptPublicKey, ScriptPublicKeys, SignableTransaction, TransactionOutpoint},
 };
 use kaspa_core::trace;
 use kaspa_database::prelude::{CachePolicy, DB, StoreResult};
+use kaspa_hashes::Hash;
 use kaspa_index_core::indexed_utxos::BalanceByScriptPublicKey;
 
 use crate::{
     IDENT,
-    model::UtxoSetByScriptPublicKey,
+    model::{AddressHistoryCursor, AddressHistoryPage, UtxoSetByScriptPublicKey},
     stores::{
+        address_history::{AddressHistoryStore, AddressHistoryStoreReader, DbAddressHistoryStore},
         indexed_utxos::{DbUtxoSetByScriptPublicKeyStore, UtxoSetByScriptPublicKeyStore, UtxoSetByScriptPublicKeyStoreReader},
         supply::{CirculatingSupplyStore, CirculatingSupplyStoreReader, DbCirculatingSupplyStore},
         tips::{DbUtxoIndexTipsStore, UtxoIndexTipsStore, UtxoIndexTipsStoreReader},
@@ -23,17 +25,25 @@ pub struct Store {
     utxoindex_tips_store: DbUtxoIndexTipsStore,
     circulating_supply_store: DbCirculatingSupplyStore,
     utxos_by_script_public_key_store: DbUtxoSetByScriptPublicKeyStore,
+    address_history_store: DbAddressHistoryStore,
+    address_history_enabled: bool,
 }
 
 impl Store {
-    pub fn new(db: Arc<DB>) -> Self {
+    pub fn new(db: Arc<DB>, address_history_enabled: bool) -> Self {
         Self {
             utxoindex_tips_store: DbUtxoIndexTipsStore::new(db.clone()),
             circulating_supply_store: DbCirculatingSupplyStore::new(db.clone()),
-            utxos_by_script_public_key_store: DbUtxoSetByScriptPublicKeyStore::new(db, CachePolicy::Empty),
+            utxos_by_script_public_key_store: DbUtxoSetByScriptPublicKeyStore::new(db.clone(), CachePolicy::Empty),
+            address_history_store: DbAddressHistoryStore::new(db),
+            address_history_enabled,
         }
     }
 
+    pub fn is_address_history_enabled(&self) -> bool {
+        self.address_history_enabled
+    }
+
     pub fn get_utxos_by_script_public_key(&self, script_public_keys: ScriptPublicKeys) -> StoreResult<UtxoSetByScriptPublicKey> {
         self.utxos_by_script_public_key_store.get_utxos_from_script_public_keys(script_public_keys)
     }
@@ -106,6 +116,61 @@ impl Store {
         res
     }
 
+    pub fn get_address_history(
+        &self,
+        script_public_key: &ScriptPublicKey,
+        from: AddressHistoryCursor,
+        limit: usize,
+    ) -> StoreResult<AddressHistoryPage> {
+        self.address_history_store.get_history(script_public_key, from, limit)
+    }
+
+    pub fn get_balances_at(&self, script_public_keys: ScriptPublicKeys, daa_score: u64) -> StoreResult<BalanceByScriptPublicKey> {
+        self.address_history_store.get_balances_at(script_public_keys, daa_score)
+    }
+
+    pub fn is_address_history_synced(&self) -> StoreResult<bool> {
+        self.address_history_store.is_synced()
+    }
+
+    pub fn get_address_history_sink(&self) -> StoreResult<Option<Hash>> {
+        self.address_history_store.get_sink()
+    }
+
+    pub fn seed_address_history(&mut self, utxos: &UtxoSetByScriptPublicKey, try_reset_on_err: bool) -> StoreResult<()> {
+        let res = self.address_history_store.seed_utxos(utxos);
+        if try_reset_on_err && res.is_err() {
+            self.delete_all()?;
+        }
+        res
+    }
+
+    pub fn apply_address_history_chain_block(
+        &mut self,
+        sink: Hash,
+        daa_score: u64,
+        transactions: &[SignableTransaction],
+    ) -> StoreResult<()> {
+        self.address_history_store.apply_chain_block(sink, daa_score, transactions)
+    }
+
+    pub fn revert_address_history_chain_blocks(&mut self, sink: Hash, blocks: &[(u64, Vec<SignableTransaction>)]) -> StoreResult<()> {
+        self.address_history_store.revert_chain_blocks(sink, blocks)
+    }
+
+    pub fn set_address_history_synced(&mut self, sink: Hash, try_reset_on_err: bool) -> StoreResult<()> {
+        let res = self.address_history_store.set_synced(sink);
+        if try_reset_on_err && res.is_err() {
+            self.delete_all()?;
+        }
+        res
+    }
+
+    /// Removes the address history, which must not be kept once it stopped following the selected chain
+    pub fn delete_address_history(&mut self) -> StoreResult<()> {
+        self.address_history_store.delete_all()
+    }
+
     /// Resets the utxoindex database:
     pub fn delete_all(&mut self) -> StoreResult<()> {
         // TODO: explore possibility of deleting and replacing whole db, currently there is an issue because of file lock and db being in an arc.
@@ -115,6 +180,7 @@ impl Store {
         self.utxoindex_tips_store.remove()?;
         self.circulating_supply_store.remove()?;
         self.utxos_by_script_public_key_store.delete_all()?;
+        self.address_history_store.delete_all()?;
 
         trace!("[{0}] clearing utxoindex database - success!", IDENT);
 
@@ -3,7 +3,7 @@ use kaspa_consensus_core::{
     config::Config,
     network::{NetworkId, NetworkType},
 };
-use kaspa_core::kaspad_env::version;
+use kaspa_core::{kaspad_env::version, log::LogFormat};
 use kaspa_notify::address::tracker::Tracker;
 use kaspa_p2p_flows::user_agent_rule::UserAgentRule;
 use kaspa_utils::networking::ContextualNetAddress;
@@ -42,6 +42,8 @@ pub struct Args {
     pub wrpc_verbose: bool,
     #[serde(rename = "loglevel")]
     pub log_level: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub log_format: LogFormat,
     pub async_threads: usize,
     #[serde(rename = "connect")]
     #[serde_as(as = "Vec<DisplayFromStr>")]
@@ -55,6 +57,8 @@ pub struct Args {
     pub user_agent_comments: Vec<String>,
     pub ua_rule: Vec<String>,
     pub utxoindex: bool,
+    #[serde(rename = "addresshistoryindex")]
+    pub address_history_index: bool,
     pub reset_db: bool,
     #[serde(rename = "outpeers")]
     pub outbound_target: usize,
@@ -91,6 +95,9 @@ pub struct Args {
     pub disable_dns_seeding: bool,
     #[serde(rename = "nogrpc")]
     pub disable_grpc: bool,
+    #[serde(rename = "nomempoolpersist")]
+    pub disable_mempool_persistence: bool,
+    pub mempool_policy_file: Option<String>,
     pub ram_scale: f64,
     pub retention_period_days: Option<f64>,
 
@@ -99,6 +106,9 @@ pub struct Args {
     pub rocksdb_preset: Option<String>,
     pub rocksdb_wal_dir: Option<String>,
     pub rocksdb_cache_size: Option<usize>,
+
+    pub export_snapshot: Option<String>,
+    pub import_snapshot: Option<String>,
 }
 
 impl Default for Args {
@@ -111,6 +121,7 @@ impl Default for Args {
             unsafe_rpc: false,
             async_threads: num_cpus::get(),
             utxoindex: false,
+            address_history_index: false,
             reset_db: false,
             outbound_target: 8,
             inbound_limit: 128,
@@ -128,6 +139,7 @@ impl Default for Args {
             rpclisten: None,
             wrpc_verbose: false,
             log_level: "INFO".into(),
+            log_format: LogFormat::Text,
             connect_peers: vec![],
             add_peers: vec![],
             listen: None,
@@ -149,12 +161,16 @@ impl Default for Args {
             disable_upnp: false,
             disable_dns_seeding: false,
             disable_grpc: false,
+            disable_mempool_persistence: false,
+            mempool_policy_file: None,
             ram_scale: 1.0,
             retention_period_days: None,
             override_params_file: None,
             rocksdb_preset: None,
             rocksdb_wal_dir: None,
             rocksdb_cache_size: None,
+            export_snapshot: None,
+            import_snapshot: None,
         }
     }
 }
@@ -162,6 +178,7 @@ impl Default for Args {
 impl Args {
     pub fn apply_to_config(&self, config: &mut Config) {
         config.utxoindex = self.utxoindex;
+        config.address_history_index = self.address_history_index;
         config.disable_upnp = self.disable_upnp;
         config.unsafe_rpc = self.unsafe_rpc;
         config.enable_unsynced_mining = self.enable_unsynced_mining;
@@ -245,6 +262,15 @@ pub fn cli() -> Command {
                 .require_equals(true)
                 .help("Logging level for all subsystems {off, error, warn, info, debug, trace}\n-- You may also specify <subsystem>=<level>,<subsystem2>=<level>,... to set the log level for individual subsystems.".to_string()),
         )
+        .arg(
+            Arg::new("log-format")
+                .long("log-format")
+                .env("KASPAD_LOG_FORMAT")
+                .value_name("FORMAT")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(LogFormat))
+                .help("Format of console and file log output {text, json} (default: text). The json format writes one object per line with timestamp, level, target, message and structured fields."),
+        )
         .arg(
             Arg::new("rpclisten")
                 .long("rpclisten")
@@ -346,6 +372,11 @@ pub fn cli() -> Command {
                 .help("Allow mainnet mining (currently enabled by default while the flag is kept for backwards compatibility)"),
         )
         .arg(arg!(--utxoindex "Enable the UTXO index").env("KASPAD_UTXOINDEX"))
+        .arg(
+            arg!(--addresshistoryindex "Enable the address history index of credits and debits (requires --utxoindex)")
+                .env("KASPAD_ADDRESSHISTORYINDEX")
+                .requires("utxoindex"),
+        )
         .arg(
             Arg::new("max-tracked-addresses")
                 .long("max-tracked-addresses")
@@ -410,6 +441,7 @@ Setting to 0 prevents the preallocation and sets the maximum to {}, leading to 0
         .arg(arg!(--"disable-upnp" "Disable upnp").env("KASPAD_DISABLE_UPNP"))
         .arg(arg!(--"nodnsseed" "Disable DNS seeding for peers").env("KASPAD_NODNSSEED"))
         .arg(arg!(--"nogrpc" "Disable gRPC server").env("KASPAD_NOGRPC"))
+        .arg(arg!(--"nomempoolpersist" "Do not save the mempool to disk nor restore it on startup").env("KASPAD_NOMEMPOOLPERSIST"))
         .arg(
             Arg::new("ram-scale")
                 .long("ram-scale")
@@ -426,6 +458,14 @@ a large RAM (~64GB) can set this value to ~3.0-4.0 and gain superior performance
                 .value_parser(clap::value_parser!(f64))
                 .help("The number of total days of data to keep.")
         )
+        .arg(
+            Arg::new("mempool-policy-file")
+                .long("mempool-policy-file")
+                .env("KASPAD_MEMPOOL_POLICY_FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Path to a TOML file configuring mempool admission policies (script classes, payload size, covenants, ZK precompiles and address lists)."),
+        )
         .arg(
             Arg::new("override-params-file")
                 .long("override-params-file")
@@ -460,6 +500,26 @@ a large RAM (~64GB) can set this value to ~3.0-4.0 and gain superior performance
                 .help("RocksDB block cache size in MB. Default: 256MB for HDD preset (scales with --ram-scale). \
                        Increase for public RPC nodes with heavy query loads. Example: --rocksdb-cache-size=2048 for 2GB cache.")
         )
+        .arg(
+            Arg::new("export-snapshot")
+                .long("export-snapshot")
+                .env("KASPAD_EXPORT_SNAPSHOT")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Export the pruning point state (proof, headers, SMT lanes and UTXO set) to a snapshot file on startup, \
+                       before connecting to peers.")
+        )
+        .arg(
+            Arg::new("import-snapshot")
+                .long("import-snapshot")
+                .env("KASPAD_IMPORT_SNAPSHOT")
+                .value_name("FILE")
+                .require_equals(true)
+                .value_parser(clap::value_parser!(String))
+                .help("Bootstrap the node from a pruning point snapshot file on startup, before connecting to peers. \
+                       The snapshot is fully validated against the pruning proof and its commitments.")
+        )
         ;
 
     #[cfg(feature = "devnet-prealloc")]
@@ -510,6 +570,7 @@ impl Args {
             unsafe_rpc: arg_match_unwrap_or::<bool>(&m, "unsaferpc", defaults.unsafe_rpc),
             wrpc_verbose: false,
             log_level: arg_match_unwrap_or::<String>(&m, "log_level", defaults.log_level),
+            log_format: arg_match_unwrap_or::<LogFormat>(&m, "log-format", defaults.log_format),
             async_threads: arg_match_unwrap_or::<usize>(&m, "async_threads", defaults.async_threads),
             connect_peers: arg_match_many_unwrap_or::<ContextualNetAddress>(&m, "connect-peers", defaults.connect_peers),
             add_peers: arg_match_many_unwrap_or::<ContextualNetAddress>(&m, "add-peers", defaults.add_peers),
@@ -522,6 +583,7 @@ impl Args {
             enable_unsynced_mining: arg_match_unwrap_or::<bool>(&m, "enable-unsynced-mining", defaults.enable_unsynced_mining),
             enable_mainnet_mining: arg_match_unwrap_or::<bool>(&m, "enable-mainnet-mining", defaults.enable_mainnet_mining),
             utxoindex: arg_match_unwrap_or::<bool>(&m, "utxoindex", defaults.utxoindex),
+            address_history_index: arg_match_unwrap_or::<bool>(&m, "addresshistoryindex", defaults.address_history_index),
             testnet: arg_match_unwrap_or::<bool>(&m, "testnet", defaults.testnet),
             testnet_suffix: arg_match_unwrap_or::<u32>(&m, "netsuffix", defaults.testnet_suffix),
             devnet: arg_match_unwrap_or::<bool>(&m, "devnet", defaults.devnet),
@@ -539,6 +601,8 @@ impl Args {
             disable_upnp: arg_match_unwrap_or::<bool>(&m, "disable-upnp", defaults.disable_upnp),
             disable_dns_seeding: arg_match_unwrap_or::<bool>(&m, "nodnsseed", defaults.disable_dns_seeding),
             disable_grpc: arg_match_unwrap_or::<bool>(&m, "nogrpc", defaults.disable_grpc),
+            disable_mempool_persistence: arg_match_unwrap_or::<bool>(&m, "nomempoolpersist", defaults.disable_mempool_persistence),
+            mempool_policy_file: m.get_one::<String>("mempool-policy-file").cloned().or(defaults.mempool_policy_file),
             ram_scale: arg_match_unwrap_or::<f64>(&m, "ram-scale", defaults.ram_scale),
             retention_period_days: m.get_one::<f64>("retention-period-days").cloned().or(defaults.retention_period_days),
 
@@ -552,6 +616,8 @@ impl Args {
             rocksdb_preset: m.get_one::<String>("rocksdb-preset").cloned().or(defaults.rocksdb_preset),
             rocksdb_wal_dir: m.get_one::<String>("rocksdb-wal-dir").cloned().or(defaults.rocksdb_wal_dir),
             rocksdb_cache_size: m.get_one::<usize>("rocksdb-cache-size").cloned().or(defaults.rocksdb_cache_size),
+            export_snapshot: m.get_one::<String>("export-snapshot").cloned().or(defaults.export_snapshot),
+            import_snapshot: m.get_one::<String>("import-snapshot").cloned().or(defaults.import_snapshot),
         };
 
         if arg_match_unwrap_or::<bool>(&m, "enable-mainnet-mining", false) {
@@ -589,6 +655,7 @@ fn validate_ua_rules(rules: &[String]) -> Result<(), clap::Error> {
 #[cfg(test)]
 mod tests {
     use super::Args;
+    use kaspa_core::log::LogFormat;
 
     #[test]
     fn parses_ua_rules() {
@@ -603,6 +670,13 @@ mod tests {
 
         assert!(err.to_string().contains("invalid --ua-rule"));
     }
+
+    #[test]
+    fn parses_log_format() {
+        assert_eq!(Args::parse(["kaspad"]).unwrap().log_format, LogFormat::Text);
+        assert_eq!(Args::parse(["kaspad", "--log-format=json"]).unwrap().log_format, LogFormat::Json);
+        assert!(Args::parse(["kaspad", "--log-format=xml"]).is_err());
+    }
 }
 
 /*
@@ -676,6 +750,8 @@ mod tests {
       --maxutxocachesize=                   Max size of loaded UTXO into ram from the disk in bytes (default:
                                             5000000000)
       --utxoindex                           Enable the UTXO index
+      --addresshistoryindex                 Enable the address history index of credits and debits (requires
+                                            --utxoindex)
       --archival                            Run as an archival node: don't delete old block data when moving the
                                             pruning point (Warning: heavy disk usage)'
       --protocol-version=                   Use non default p2p protocol version (default: 5)
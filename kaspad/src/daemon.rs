@@ -40,9 +40,14 @@ use kaspa_index_processor::service::IndexService;
 use kaspa_mining::{
     MiningCounters,
     manager::{MiningManager, MiningManagerProxy},
+    mempool::policy::{AdmissionPolicies, AdmissionPolicyConfig},
     monitor::MiningMonitor,
 };
-use kaspa_p2p_flows::{flow_context::FlowContext, service::P2pService};
+use kaspa_p2p_flows::{
+    flow_context::FlowContext,
+    mempool_persistence::{DEFAULT_MEMPOOL_SAVE_INTERVAL, MempoolPersistenceService},
+    service::{P2pService, SnapshotTask},
+};
 
 use kaspa_perf_monitor::{builder::Builder as PerfMonitorBuilder, counters::CountersSnapshot};
 use kaspa_utxoindex::{UtxoIndex, api::UtxoIndexProxy};
@@ -69,6 +74,7 @@ const CONSENSUS_DB: &str = "consensus";
 const UTXOINDEX_DB: &str = "utxoindex";
 const META_DB: &str = "meta";
 const META_DB_FILE_LIMIT: i32 = 5;
+const MEMPOOL_SNAPSHOT_FILE: &str = "mempool.dat";
 const DEFAULT_LOG_DIR: &str = "logs";
 
 fn get_home_dir() -> PathBuf {
@@ -113,6 +119,9 @@ pub fn validate_args(args: &Args) -> ConfigResult<()> {
     if args.max_tracked_addresses > Tracker::MAX_ADDRESS_UPPER_BOUND {
         return Err(ConfigError::MaxTrackedAddressesTooHigh(Tracker::MAX_ADDRESS_UPPER_BOUND));
     }
+    if args.export_snapshot.is_some() && args.import_snapshot.is_some() {
+        return Err(ConfigError::MixedExportAndImportSnapshot);
+    }
     Ok(())
 }
 
@@ -188,9 +197,9 @@ impl Runtime {
         // Initialize the logger
         cfg_if::cfg_if! {
             if #[cfg(feature = "semaphore-trace")] {
-                kaspa_core::log::init_logger(log_dir.as_deref(), &format!("{},{}=debug", args.log_level, kaspa_utils::sync::semaphore_module_path()));
+                kaspa_core::log::init_logger_with_format(log_dir.as_deref(), &format!("{},{}=debug", args.log_level, kaspa_utils::sync::semaphore_module_path()), args.log_format);
             } else {
-                kaspa_core::log::init_logger(log_dir.as_deref(), &args.log_level);
+                kaspa_core::log::init_logger_with_format(log_dir.as_deref(), &args.log_level, args.log_format);
             }
         };
 
@@ -348,6 +357,7 @@ pub fn create_core_with_runtime(runtime: &Runtime, args: &Args, fd_total_budget:
     let consensus_db_dir = db_dir.join(CONSENSUS_DB);
     let utxoindex_db_dir = db_dir.join(UTXOINDEX_DB);
     let meta_db_dir = db_dir.join(META_DB);
+    let mempool_snapshot_path = db_dir.join(MEMPOOL_SNAPSHOT_FILE);
 
     let mut is_db_reset_needed = args.reset_db;
 
@@ -631,7 +641,9 @@ Do you confirm? (y/n)";
             .with_cache_budget(cache_budget)
             .build()
             .unwrap();
-        let utxoindex = UtxoIndexProxy::new(UtxoIndex::new(consensus_manager.clone(), utxoindex_db).unwrap());
+        let utxoindex = UtxoIndexProxy::new(
+            UtxoIndex::with_address_history(consensus_manager.clone(), utxoindex_db, args.address_history_index).unwrap(),
+        );
         let index_service = Arc::new(IndexService::new(&notify_service.notifier(), subscription_context.clone(), Some(utxoindex)));
         Some(index_service)
     } else {
@@ -640,6 +652,25 @@ Do you confirm? (y/n)";
 
     let (address_manager, port_mapping_extender_svc) = AddressManager::new(config.clone(), meta_db, tick_service.clone());
 
+    let admission_policies = match &args.mempool_policy_file {
+        Some(path) => {
+            let file_content = fs::read_to_string(path).unwrap_or_else(|err| {
+                println!("Failed to read mempool policy file '{}': {}", path, err);
+                exit(1);
+            });
+            let policy_config: AdmissionPolicyConfig = toml::from_str(&file_content).unwrap_or_else(|err| {
+                println!("Failed to parse mempool policy file '{}': {}", path, err);
+                exit(1);
+            });
+            let admission_policies = policy_config.build(config.prefix()).unwrap_or_else(|err| {
+                println!("Invalid mempool policy file '{}': {}", path, err);
+                exit(1);
+            });
+            info!("Mempool admission policies: {:?}", admission_policies);
+            admission_policies
+        }
+        None => AdmissionPolicies::default(),
+    };
     let mining_manager = MiningManagerProxy::new(Arc::new(MiningManager::new_with_extended_config(
         config.target_time_per_block(),
         false,
@@ -647,6 +678,7 @@ Do you confirm? (y/n)";
         config.toccata_activation,
         config.block_lane_limits,
         config.ram_scale,
+        admission_policies,
         config.block_template_cache_lifetime,
         mining_counters.clone(),
     )));
@@ -672,6 +704,19 @@ Do you confirm? (y/n)";
         hub.clone(),
         mining_rule_engine.clone(),
     ));
+    let mempool_persistence_service = (!args.disable_mempool_persistence).then(|| {
+        Arc::new(MempoolPersistenceService::new(
+            flow_context.clone(),
+            mempool_snapshot_path,
+            DEFAULT_MEMPOOL_SAVE_INTERVAL,
+            tick_service.clone(),
+        ))
+    });
+    let snapshot_task = match (&args.export_snapshot, &args.import_snapshot) {
+        (Some(path), _) => Some(SnapshotTask::Export(PathBuf::from(path))),
+        (None, Some(path)) => Some(SnapshotTask::Import(PathBuf::from(path))),
+        (None, None) => None,
+    };
     let p2p_service = Arc::new(P2pService::new(
         flow_context.clone(),
         connect_peers,
@@ -682,6 +727,7 @@ Do you confirm? (y/n)";
         dns_seeders,
         config.default_p2p_port(),
         p2p_tower_counters.clone(),
+        snapshot_task,
     ));
 
     let rpc_core_service = Arc::new(RpcCoreService::new(
@@ -736,6 +782,9 @@ Do you confirm? (y/n)";
     async_runtime.register(mining_monitor);
     async_runtime.register(perf_monitor);
     async_runtime.register(mining_rule_engine);
+    if let Some(mempool_persistence_service) = mempool_persistence_service {
+        async_runtime.register(mempool_persistence_service)
+    }
 
     let wrpc_service_tasks: usize = 2; // num_cpus::get() / 2;
     // Register wRPC servers based on command line arguments
@@ -12,6 +12,7 @@ pub enum MetricGroup {
     Storage,
     Bandwidth,
     Connections,
+    Peers,
     Network,
 }
 
@@ -22,6 +23,7 @@ impl std::fmt::Display for MetricGroup {
             MetricGroup::Storage => write!(f, "storage"),
             MetricGroup::Bandwidth => write!(f, "bandwidth"),
             MetricGroup::Connections => write!(f, "connections"),
+            MetricGroup::Peers => write!(f, "peers"),
             MetricGroup::Network => write!(f, "network"),
         }
     }
@@ -34,6 +36,7 @@ impl MetricGroup {
             MetricGroup::Storage => "Storage",
             MetricGroup::Bandwidth => "Bandwidth",
             MetricGroup::Connections => "Connections",
+            MetricGroup::Peers => "Peers",
             MetricGroup::Network => "Network",
         }
     }
@@ -94,6 +97,19 @@ impl MetricGroup {
             ]
             .as_slice()
             .iter(),
+            MetricGroup::Peers => [
+                Metric::NodeP2pMessagesTx,
+                Metric::NodeP2pMessagesTxPerSecond,
+                Metric::NodeP2pMessagesRx,
+                Metric::NodeP2pMessagesRxPerSecond,
+                Metric::NodeP2pInvalidBlocksCount,
+                Metric::NodeP2pInvalidTransactionsCount,
+                Metric::NodeP2pDroppedMessagesCount,
+                Metric::NodeP2pRelayLatency,
+                Metric::NodeP2pOutgoingQueueFullness,
+            ]
+            .as_slice()
+            .iter(),
             MetricGroup::Network => [
                 Metric::NodeBlocksSubmittedCount,
                 Metric::NodeHeadersProcessedCount,
@@ -138,6 +154,16 @@ impl From<Metric> for MetricGroup {
             | Metric::NodeJsonHandshakeFailures
             | Metric::NodeActivePeers => MetricGroup::Connections,
             // --
+            Metric::NodeP2pMessagesTx
+            | Metric::NodeP2pMessagesRx
+            | Metric::NodeP2pMessagesTxPerSecond
+            | Metric::NodeP2pMessagesRxPerSecond
+            | Metric::NodeP2pInvalidBlocksCount
+            | Metric::NodeP2pInvalidTransactionsCount
+            | Metric::NodeP2pDroppedMessagesCount
+            | Metric::NodeP2pRelayLatency
+            | Metric::NodeP2pOutgoingQueueFullness => MetricGroup::Peers,
+            // --
             Metric::NodeBorshBytesRx
             | Metric::NodeBorshBytesTx
             | Metric::NodeJsonBytesTx
@@ -205,6 +231,16 @@ pub enum Metric {
     NodeJsonConnectionAttempts,
     NodeJsonHandshakeFailures,
     // ---
+    NodeP2pMessagesTx,
+    NodeP2pMessagesRx,
+    NodeP2pMessagesTxPerSecond,
+    NodeP2pMessagesRxPerSecond,
+    NodeP2pInvalidBlocksCount,
+    NodeP2pInvalidTransactionsCount,
+    NodeP2pDroppedMessagesCount,
+    NodeP2pRelayLatency,
+    NodeP2pOutgoingQueueFullness,
+    // ---
     NodeTotalBytesTx,
     NodeTotalBytesRx,
     NodeTotalBytesTxPerSecond,
@@ -279,6 +315,8 @@ impl Metric {
                 | Metric::NodeTotalBytesTxPerSecond
                 | Metric::NodeTotalBytesRxPerSecond
                 | Metric::NodeActivePeers
+                | Metric::NodeP2pMessagesTxPerSecond
+                | Metric::NodeP2pMessagesRxPerSecond
                 | Metric::NetworkMempoolSize
                 | Metric::NetworkTipHashesCount
                 | Metric::NetworkTransactionsPerSecond
@@ -315,6 +353,16 @@ impl Metric {
             Metric::NodeJsonHandshakeFailures => f.trunc().separated_string(),
             Metric::NodeActivePeers => f.trunc().separated_string(),
             // --
+            Metric::NodeP2pMessagesTx => format_as_float(f, short),
+            Metric::NodeP2pMessagesRx => format_as_float(f, short),
+            Metric::NodeP2pMessagesTxPerSecond => format!("{}/s", format_as_float(f, short)),
+            Metric::NodeP2pMessagesRxPerSecond => format!("{}/s", format_as_float(f, short)),
+            Metric::NodeP2pInvalidBlocksCount => f.trunc().separated_string(),
+            Metric::NodeP2pInvalidTransactionsCount => f.trunc().separated_string(),
+            Metric::NodeP2pDroppedMessagesCount => f.trunc().separated_string(),
+            Metric::NodeP2pRelayLatency => format!("{} ms", f.trunc().separated_string()),
+            Metric::NodeP2pOutgoingQueueFullness => format!("{:1.2}%", f),
+            // --
             Metric::NodeBorshBytesTx => as_data_size(f, si),
             Metric::NodeBorshBytesRx => as_data_size(f, si),
             Metric::NodeJsonBytesTx => as_data_size(f, si),
@@ -379,6 +427,16 @@ impl Metric {
             Metric::NodeJsonConnectionAttempts => ("Json Connection Attempts", "Json Conn Att"),
             Metric::NodeJsonHandshakeFailures => ("Json Handshake Failures", "Json Failures"),
             // --
+            Metric::NodeP2pMessagesTx => ("p2p Messages Tx", "p2p Msg Tx"),
+            Metric::NodeP2pMessagesRx => ("p2p Messages Rx", "p2p Msg Rx"),
+            Metric::NodeP2pMessagesTxPerSecond => ("p2p Messages Tx/s", "p2p Msg Tx/s"),
+            Metric::NodeP2pMessagesRxPerSecond => ("p2p Messages Rx/s", "p2p Msg Rx/s"),
+            Metric::NodeP2pInvalidBlocksCount => ("Invalid Blocks Received", "Inv Blocks"),
+            Metric::NodeP2pInvalidTransactionsCount => ("Invalid Transactions Received", "Inv Txs"),
+            Metric::NodeP2pDroppedMessagesCount => ("Dropped p2p Messages", "Dropped Msg"),
+            Metric::NodeP2pRelayLatency => ("Relay Block Latency", "Relay Lat"),
+            Metric::NodeP2pOutgoingQueueFullness => ("Max Outgoing Queue Fullness", "Queue Full"),
+            // --
             Metric::NodeBorshBytesTx => ("wRPC Borsh Tx", "Borsh Tx"),
             Metric::NodeBorshBytesRx => ("wRPC Borsh Rx", "Borsh Rx"),
             Metric::NodeJsonBytesTx => ("wRPC JSON Tx", "Json Tx"),
@@ -448,6 +506,14 @@ pub struct MetricsData {
     pub node_json_handshake_failures: u64,
     pub node_active_peers: u32,
     // ---
+    pub node_p2p_messages_tx: u64,
+    pub node_p2p_messages_rx: u64,
+    pub node_p2p_invalid_blocks_count: u64,
+    pub node_p2p_invalid_transactions_count: u64,
+    pub node_p2p_dropped_messages_count: u64,
+    pub node_p2p_relay_latency_millis: u64,
+    pub node_p2p_outgoing_queue_fullness: f32,
+    // ---
     pub node_borsh_bytes_tx: u64,
     pub node_borsh_bytes_rx: u64,
     pub node_json_bytes_tx: u64,
@@ -531,6 +597,14 @@ impl TryFrom<GetMetricsResponse> for MetricsData {
             node_json_handshake_failures: connection_metrics.json_handshake_failures,
             node_active_peers: connection_metrics.active_peers,
 
+            node_p2p_messages_tx: connection_metrics.p2p_messages_tx,
+            node_p2p_messages_rx: connection_metrics.p2p_messages_rx,
+            node_p2p_invalid_blocks_count: connection_metrics.p2p_invalid_blocks,
+            node_p2p_invalid_transactions_count: connection_metrics.p2p_invalid_transactions,
+            node_p2p_dropped_messages_count: connection_metrics.p2p_dropped_messages,
+            node_p2p_relay_latency_millis: connection_metrics.p2p_relay_latency_millis,
+            node_p2p_outgoing_queue_fullness: connection_metrics.p2p_max_outgoing_queue_fullness,
+
             node_borsh_bytes_tx: bandwidth_metrics.borsh_bytes_tx,
             node_borsh_bytes_rx: bandwidth_metrics.borsh_bytes_rx,
             node_json_bytes_tx: bandwidth_metrics.json_bytes_tx,
@@ -591,6 +665,16 @@ pub struct MetricsSnapshot {
     pub node_json_handshake_failures: f64,
     pub node_active_peers: f64,
     // ---
+    pub node_p2p_messages_tx: f64,
+    pub node_p2p_messages_rx: f64,
+    pub node_p2p_messages_tx_per_second: f64,
+    pub node_p2p_messages_rx_per_second: f64,
+    pub node_p2p_invalid_blocks_count: f64,
+    pub node_p2p_invalid_transactions_count: f64,
+    pub node_p2p_dropped_messages_count: f64,
+    pub node_p2p_relay_latency: f64,
+    pub node_p2p_outgoing_queue_fullness: f64,
+    // ---
     pub node_borsh_bytes_tx: f64,
     pub node_borsh_bytes_rx: f64,
     pub node_json_bytes_tx: f64,
@@ -657,6 +741,16 @@ impl MetricsSnapshot {
             Metric::NodeJsonConnectionAttempts => self.node_json_connection_attempts,
             Metric::NodeJsonHandshakeFailures => self.node_json_handshake_failures,
             // ---
+            Metric::NodeP2pMessagesTx => self.node_p2p_messages_tx,
+            Metric::NodeP2pMessagesRx => self.node_p2p_messages_rx,
+            Metric::NodeP2pMessagesTxPerSecond => self.node_p2p_messages_tx_per_second,
+            Metric::NodeP2pMessagesRxPerSecond => self.node_p2p_messages_rx_per_second,
+            Metric::NodeP2pInvalidBlocksCount => self.node_p2p_invalid_blocks_count,
+            Metric::NodeP2pInvalidTransactionsCount => self.node_p2p_invalid_transactions_count,
+            Metric::NodeP2pDroppedMessagesCount => self.node_p2p_dropped_messages_count,
+            Metric::NodeP2pRelayLatency => self.node_p2p_relay_latency,
+            Metric::NodeP2pOutgoingQueueFullness => self.node_p2p_outgoing_queue_fullness,
+            // ---
             Metric::NodeBorshBytesTx => self.node_borsh_bytes_tx,
             Metric::NodeBorshBytesRx => self.node_borsh_bytes_rx,
             Metric::NodeJsonBytesTx => self.node_json_bytes_tx,
@@ -730,6 +824,8 @@ impl From<(&MetricsData, &MetricsData)> for MetricsSnapshot {
         let node_grpc_user_bytes_rx_per_second = per_sec(a.node_grpc_user_bytes_rx, b.node_grpc_user_bytes_rx, duration_millis);
         let node_total_bytes_tx_per_second = per_sec(a.node_total_bytes_tx, b.node_total_bytes_tx, duration_millis);
         let node_total_bytes_rx_per_second = per_sec(a.node_total_bytes_rx, b.node_total_bytes_rx, duration_millis);
+        let node_p2p_messages_tx_per_second = per_sec(a.node_p2p_messages_tx, b.node_p2p_messages_tx, duration_millis);
+        let node_p2p_messages_rx_per_second = per_sec(a.node_p2p_messages_rx, b.node_p2p_messages_rx, duration_millis);
 
         Self {
             unixtime_millis: b.unixtime_millis,
@@ -754,6 +850,16 @@ impl From<(&MetricsData, &MetricsData)> for MetricsSnapshot {
             node_json_handshake_failures: b.node_json_handshake_failures as f64,
             node_active_peers: b.node_active_peers as f64,
             // ---
+            node_p2p_messages_tx: b.node_p2p_messages_tx as f64,
+            node_p2p_messages_rx: b.node_p2p_messages_rx as f64,
+            node_p2p_messages_tx_per_second,
+            node_p2p_messages_rx_per_second,
+            node_p2p_invalid_blocks_count: b.node_p2p_invalid_blocks_count as f64,
+            node_p2p_invalid_transactions_count: b.node_p2p_invalid_transactions_count as f64,
+            node_p2p_dropped_messages_count: b.node_p2p_dropped_messages_count as f64,
+            node_p2p_relay_latency: b.node_p2p_relay_latency_millis as f64,
+            node_p2p_outgoing_queue_fullness: b.node_p2p_outgoing_queue_fullness as f64,
+            // ---
             node_borsh_bytes_tx: b.node_borsh_bytes_tx as f64,
             node_borsh_bytes_rx: b.node_borsh_bytes_rx as f64,
             node_json_bytes_tx: b.node_json_bytes_tx as f64,
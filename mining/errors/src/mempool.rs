@@ -93,6 +93,10 @@ pub enum RuleError {
 
     #[error("Rejected tx {0} from mempool due to incomputable storage mass")]
     RejectStorageMassIncomputable(TransactionId),
+
+    /// A transaction rejected by an admission policy set by the node operator
+    #[error("transaction {0} was rejected by the {1} policy: {2}")]
+    RejectPolicy(TransactionId, String, String),
 }
 
 impl From<NonStandardError> for RuleError {
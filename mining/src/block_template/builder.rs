@@ -1,11 +1,186 @@
-use super::errors::BuilderResult;
+use super::{diagnostics::SelectionOutcome, errors::BuilderResult, policy::Policy};
+use crate::mempool::model::frontier::selectors::{LaneId, LaneSelectionState};
 use kaspa_consensus_core::{
     api::ConsensusApi,
     block::{BlockTemplate, TemplateBuildMode, TemplateTransactionSelector},
     coinbase::MinerData,
-    tx::COINBASE_TRANSACTION_INDEX,
+    errors::tx::TxRuleError,
+    tx::{COINBASE_TRANSACTION_INDEX, Transaction, TransactionId},
 };
 use kaspa_core::time::{Stopwatch, unix_now};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+/// Requester specific adjustments of the transaction selection of a block template
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BlockTemplateCustomization {
+    /// Mempool transactions to include ahead of the feerate based selection, in order of preference.
+    /// Their mempool ancestors are included along with them. Transactions found invalid by consensus
+    /// are dropped like any other template transaction.
+    pub priority_transaction_ids: Vec<TransactionId>,
+
+    /// Mempool transactions which must not be included in the template
+    pub excluded_transaction_ids: Vec<TransactionId>,
+
+    /// Block mass reserved for the priority transactions, where 0 stands for the whole block mass.
+    /// Any unused part of the reservation remains available to the feerate based selection.
+    pub reserved_mass: u64,
+}
+
+impl BlockTemplateCustomization {
+    pub fn new(
+        priority_transaction_ids: Vec<TransactionId>,
+        excluded_transaction_ids: Vec<TransactionId>,
+        reserved_mass: u64,
+    ) -> Self {
+        Self { priority_transaction_ids, excluded_transaction_ids, reserved_mass }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.priority_transaction_ids.is_empty() && self.excluded_transaction_ids.is_empty()
+    }
+
+    /// Returns the mass budget of the priority transactions under `policy`
+    pub(crate) fn priority_mass_budget(&self, policy: &Policy) -> u64 {
+        match self.reserved_mass {
+            0 => policy.max_block_mass,
+            reserved_mass => reserved_mass.min(policy.max_block_mass),
+        }
+    }
+}
+
+/// A priority transaction along with its mass, see [`CustomizedTransactionSelector::new`]
+pub(crate) type PriorityCandidate = (Arc<Transaction>, u64);
+
+/// A selector applying a [`BlockTemplateCustomization`] on top of the regular feerate based selector.
+///
+/// Priority transactions are returned first, then the inner selector fills the remaining block mass.
+/// Lane limits are enforced over the union of both selections.
+pub(crate) struct CustomizedTransactionSelector {
+    /// Priority transactions not yet handed to consensus
+    priority: Vec<Arc<Transaction>>,
+    priority_ids: HashSet<TransactionId>,
+    /// Transactions the inner selector must not contribute, i.e. excluded and priority ones
+    skipped: HashSet<TransactionId>,
+    inner: Box<dyn TemplateTransactionSelector>,
+    /// Lane usage of all currently selected transactions
+    selected: HashMap<TransactionId, (LaneId, u64)>,
+    lanes: LaneSelectionState,
+    policy: Policy,
+    consensus_rejections: usize,
+}
+
+impl CustomizedTransactionSelector {
+    /// Builds the selector from the priority candidate groups, each group being a priority transaction
+    /// preceded by its mempool ancestors in topological order. Groups are selected atomically until the
+    /// reserved mass budget is reached. `build_inner` receives the policy left for the regular selection.
+    pub(crate) fn new(
+        policy: Policy,
+        customization: &BlockTemplateCustomization,
+        priority_groups: Vec<Vec<PriorityCandidate>>,
+        build_inner: impl FnOnce(&Policy) -> Box<dyn TemplateTransactionSelector>,
+    ) -> Self {
+        let budget = customization.priority_mass_budget(&policy);
+        let mut lanes = LaneSelectionState::default();
+        let mut priority = Vec::new();
+        let mut priority_mass = 0u64;
+        let mut selected = HashMap::new();
+        for group in priority_groups {
+            let group = group.into_iter().filter(|(tx, _)| !selected.contains_key(&tx.id())).collect::<Vec<_>>();
+            let group_mass = group.iter().map(|(_, mass)| *mass).sum::<u64>();
+            if priority_mass.saturating_add(group_mass) > budget {
+                group.iter().for_each(|(tx, _)| policy.trace(tx.id(), SelectionOutcome::BlockMassLimit));
+                continue;
+            }
+            let lane_limit = group
+                .iter()
+                .enumerate()
+                .find_map(|(i, (tx, _))| lanes.try_select(&policy, tx.subnetwork_id, tx.gas).err().map(|outcome| (i, outcome)));
+            if let Some((fitting, outcome)) = lane_limit {
+                group.iter().take(fitting).for_each(|(tx, _)| lanes.reject(tx.subnetwork_id, tx.gas));
+                // The whole group is dropped along with the transaction hitting the lane limit
+                group.iter().for_each(|(tx, _)| policy.trace(tx.id(), outcome.clone()));
+                continue;
+            }
+            priority_mass += group_mass;
+            for (tx, _) in group {
+                selected.insert(tx.id(), (tx.subnetwork_id, tx.gas));
+                priority.push(tx);
+            }
+        }
+
+        let priority_ids = selected.keys().copied().collect::<HashSet<_>>();
+        let skipped = customization.excluded_transaction_ids.iter().chain(priority_ids.iter()).copied().collect();
+        let inner_policy = Policy { max_block_mass: policy.max_block_mass - priority_mass, ..policy.clone() };
+        let inner = build_inner(&inner_policy);
+        Self { priority, priority_ids, skipped, inner, selected, lanes, policy, consensus_rejections: 0 }
+    }
+}
+
+impl TemplateTransactionSelector for CustomizedTransactionSelector {
+    fn select_transactions(&mut self) -> Vec<Transaction> {
+        let mut transactions = self
+            .priority
+            .drain(..)
+            .map(|tx| {
+                self.policy.trace(tx.id(), SelectionOutcome::Selected);
+                tx.as_ref().clone()
+            })
+            .collect::<Vec<_>>();
+        loop {
+            let batch = self.inner.select_transactions();
+            if batch.is_empty() {
+                break;
+            }
+            let mut has_skipped = false;
+            for tx in batch {
+                let id = tx.id();
+                let selection = if self.skipped.contains(&id) {
+                    Err(SelectionOutcome::Excluded)
+                } else {
+                    self.lanes.try_select(&self.policy, tx.subnetwork_id, tx.gas)
+                };
+                if let Err(outcome) = selection {
+                    // Skipped priority transactions were already selected ahead of the inner selection
+                    if !self.priority_ids.contains(&id) {
+                        self.policy.trace(id, outcome);
+                    }
+                    // Give the mass back to the inner selector so it can be used by its next batch
+                    self.inner.reject_selection(id);
+                    has_skipped = true;
+                    continue;
+                }
+                self.selected.insert(id, (tx.subnetwork_id, tx.gas));
+                transactions.push(tx);
+            }
+            if !has_skipped {
+                break;
+            }
+        }
+        transactions
+    }
+
+    fn reject_selection(&mut self, tx_id: TransactionId) {
+        let (lane, gas) = self.selected.remove(&tx_id).expect("only previously selected txs can be rejected (and only once)");
+        self.lanes.reject(lane, gas);
+        self.consensus_rejections += 1;
+        if !self.priority_ids.remove(&tx_id) {
+            self.inner.reject_selection(tx_id);
+        }
+    }
+
+    fn report_invalid_transaction(&mut self, tx_id: TransactionId, error: &TxRuleError) {
+        self.policy.trace(tx_id, SelectionOutcome::Invalid(error.to_string()));
+    }
+
+    fn is_successful(&self) -> bool {
+        // Skips of excluded transactions are reported to the inner selector as rejections, so it
+        // is only consulted when consensus actually rejected some of the selected transactions
+        self.consensus_rejections == 0 || self.inner.is_successful()
+    }
+}
 
 pub(crate) struct BlockTemplateBuilder {}
 
@@ -0,0 +1,253 @@
+use crate::mempool::model::{frontier::feerate_key::FeerateTransactionKey, tx::MempoolTransaction};
+use kaspa_consensus_core::{
+    block::BlockTemplate,
+    mass::{BlockLaneLimits, BlockMassLimits, MassCofactors},
+    subnets::SubnetworkId,
+    tx::TransactionId,
+};
+use parking_lot::Mutex;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+/// The final outcome of a transaction considered by a block template selection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectionOutcome {
+    /// The transaction is included in the block template
+    Selected,
+    /// The block already uses the maximum number of lanes allowed by [`BlockLaneLimits::lanes_per_block`]
+    LanesPerBlockLimit,
+    /// Including the transaction would exceed the [`BlockLaneLimits::gas_per_lane`] limit of its lane
+    GasPerLaneLimit,
+    /// The transaction did not fit into the block mass left when it was considered
+    BlockMassLimit,
+    /// The transaction is excluded by the template customization
+    Excluded,
+    /// Consensus found the transaction invalid, along with the validation error
+    Invalid(String),
+}
+
+/// Records the outcome of every candidate a selector considers. Outcomes of later selection rounds
+/// override earlier ones, except for validation failures which are final.
+#[derive(Default)]
+pub(crate) struct SelectionTrace {
+    order: Vec<TransactionId>,
+    outcomes: HashMap<TransactionId, SelectionOutcome>,
+}
+
+impl SelectionTrace {
+    pub(crate) fn record(&mut self, tx_id: TransactionId, outcome: SelectionOutcome) {
+        match self.outcomes.get_mut(&tx_id) {
+            Some(SelectionOutcome::Invalid(_)) => {}
+            Some(current) => *current = outcome,
+            None => {
+                self.order.push(tx_id);
+                self.outcomes.insert(tx_id, outcome);
+            }
+        }
+    }
+
+    /// Marks the transactions of the built template as selected. A selector may trace a transaction it did not
+    /// contribute, e.g. the inner selector of a customized selection finding a priority transaction over its budget.
+    pub(crate) fn reconcile(&mut self, selected: impl IntoIterator<Item = TransactionId>) {
+        for tx_id in selected {
+            if self.outcomes.insert(tx_id, SelectionOutcome::Selected).is_none() {
+                self.order.push(tx_id);
+            }
+        }
+    }
+
+    /// Returns the traced candidates in the order they were first considered
+    pub(crate) fn into_outcomes(mut self) -> Vec<(TransactionId, SelectionOutcome)> {
+        self.order.into_iter().map(|tx_id| (tx_id, self.outcomes.remove(&tx_id).unwrap())).collect()
+    }
+}
+
+/// A trace shared by all the selectors built for a single template, see [`crate::Policy::trace`]
+pub(crate) type SelectionTracer = Arc<Mutex<SelectionTrace>>;
+
+/// The frontier selector strategy used for building a template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrontierSelectorKind {
+    /// The whole frontier fits into a block
+    TakeAll,
+    /// The frontier is much larger than a block and was sampled in advance
+    Sequence,
+    /// The frontier is a few blocks large and is sampled during selection
+    MutatingTree,
+}
+
+/// A mass dimension of [`BlockMassLimits`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MassDimension {
+    Compute,
+    Transient,
+    Storage,
+}
+
+/// Masses per [`BlockMassLimits`] dimension
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DimensionMasses {
+    pub compute: u64,
+    pub transient: u64,
+    pub storage: u64,
+}
+
+impl DimensionMasses {
+    pub(crate) fn add(&mut self, other: &DimensionMasses) {
+        self.compute += other.compute;
+        self.transient += other.transient;
+        self.storage += other.storage;
+    }
+}
+
+/// A transaction considered by the selection along with the data the selectors based their decision on
+#[derive(Debug, Clone)]
+pub struct CandidateTransactionDiagnostics {
+    pub transaction_id: TransactionId,
+    pub outcome: SelectionOutcome,
+    pub fee: u64,
+    /// The normalized mass used by the selectors, i.e. the maximum over all dimensions once scaled to compute mass
+    pub mass: u64,
+    pub masses: DimensionMasses,
+    /// The dimension whose normalized mass equals `mass`
+    pub binding_dimension: MassDimension,
+    pub subnetwork_id: SubnetworkId,
+    pub gas: u64,
+}
+
+impl CandidateTransactionDiagnostics {
+    pub(crate) fn new(tx: &MempoolTransaction, cofactors: &MassCofactors, outcome: SelectionOutcome) -> Self {
+        let key = FeerateTransactionKey::from_tx(tx, cofactors);
+        let non_contextual = tx.mtx.calculated_non_contextual_masses.expect("masses are expected to be calculated");
+        let masses = DimensionMasses {
+            compute: non_contextual.compute_mass,
+            transient: non_contextual.transient_mass,
+            storage: tx.mtx.tx.storage_mass(),
+        };
+        let binding_dimension = if masses.compute == key.mass {
+            MassDimension::Compute
+        } else if non_contextual.normalized_transient(cofactors) == key.mass {
+            MassDimension::Transient
+        } else {
+            MassDimension::Storage
+        };
+        Self {
+            transaction_id: tx.id(),
+            outcome,
+            fee: key.fee,
+            mass: key.mass,
+            masses,
+            binding_dimension,
+            subnetwork_id: tx.mtx.tx.subnetwork_id,
+            gas: tx.mtx.tx.gas,
+        }
+    }
+}
+
+/// Usage of a lane by the selected transactions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaneUsageDiagnostics {
+    pub subnetwork_id: SubnetworkId,
+    pub transaction_count: usize,
+    pub gas: u64,
+}
+
+/// The transaction selection trace of a block template dry-run
+#[derive(Debug, Clone)]
+pub struct BlockTemplateDiagnostics {
+    pub selector_kind: FrontierSelectorKind,
+    /// Number of ready transactions the selection was based on
+    pub ready_transaction_count: usize,
+    /// Traced candidates in the order they were first considered. Sampling selectors may leave
+    /// part of the frontier unconsidered.
+    pub candidates: Vec<CandidateTransactionDiagnostics>,
+    /// Number of non-coinbase transactions in the template
+    pub selected_transaction_count: usize,
+    pub total_fees: u64,
+    pub mass_used: DimensionMasses,
+    pub mass_limits: BlockMassLimits,
+    /// Sum of the normalized masses of the selected transactions, see [`CandidateTransactionDiagnostics::mass`]
+    pub normalized_mass_used: u64,
+    pub normalized_mass_limit: u64,
+    pub lanes: Vec<LaneUsageDiagnostics>,
+    pub lane_limits: BlockLaneLimits,
+}
+
+impl BlockTemplateDiagnostics {
+    /// Summarizes the traced `candidates` of `block_template`. Totals of mass and lane usage only account
+    /// for the selected candidates, while the total fees are those calculated by consensus for the template.
+    pub(crate) fn new(
+        selector_kind: FrontierSelectorKind,
+        ready_transaction_count: usize,
+        candidates: Vec<CandidateTransactionDiagnostics>,
+        block_template: &BlockTemplate,
+        mass_limits: BlockMassLimits,
+        normalized_mass_limit: u64,
+        lane_limits: BlockLaneLimits,
+    ) -> Self {
+        let mut mass_used = DimensionMasses::default();
+        let mut normalized_mass_used = 0;
+        let mut lanes = BTreeMap::<SubnetworkId, (usize, u64)>::new();
+        for candidate in candidates.iter().filter(|candidate| candidate.outcome == SelectionOutcome::Selected) {
+            mass_used.add(&candidate.masses);
+            normalized_mass_used += candidate.mass;
+            let usage = lanes.entry(candidate.subnetwork_id).or_default();
+            usage.0 += 1;
+            usage.1 += candidate.gas;
+        }
+        Self {
+            selector_kind,
+            ready_transaction_count,
+            candidates,
+            selected_transaction_count: block_template.block.transactions.len().saturating_sub(1),
+            total_fees: block_template.calculated_fees.iter().sum(),
+            mass_used,
+            mass_limits,
+            normalized_mass_used,
+            normalized_mass_limit,
+            lanes: lanes
+                .into_iter()
+                .map(|(subnetwork_id, (transaction_count, gas))| LaneUsageDiagnostics { subnetwork_id, transaction_count, gas })
+                .collect(),
+            lane_limits,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_hashes::Hash;
+
+    #[test]
+    fn test_selection_trace_outcomes() {
+        let (a, b, c) = (Hash::from_u64_word(1), Hash::from_u64_word(2), Hash::from_u64_word(3));
+        let mut trace = SelectionTrace::default();
+        trace.record(a, SelectionOutcome::BlockMassLimit);
+        trace.record(b, SelectionOutcome::Selected);
+        trace.record(c, SelectionOutcome::LanesPerBlockLimit);
+        // A later round selects the mass deferred candidate
+        trace.record(a, SelectionOutcome::Selected);
+        // Validation failures are final
+        trace.record(b, SelectionOutcome::Invalid("missing outpoint".to_string()));
+        trace.record(b, SelectionOutcome::Excluded);
+        assert_eq!(
+            trace.into_outcomes(),
+            vec![
+                (a, SelectionOutcome::Selected),
+                (b, SelectionOutcome::Invalid("missing outpoint".to_string())),
+                (c, SelectionOutcome::LanesPerBlockLimit)
+            ]
+        );
+
+        // The transactions of the template are selected whatever was traced for them
+        let mut trace = SelectionTrace::default();
+        trace.record(a, SelectionOutcome::Selected);
+        trace.record(a, SelectionOutcome::BlockMassLimit);
+        trace.record(b, SelectionOutcome::BlockMassLimit);
+        trace.reconcile([a]);
+        assert_eq!(trace.into_outcomes(), vec![(a, SelectionOutcome::Selected), (b, SelectionOutcome::BlockMassLimit)]);
+    }
+}
@@ -1,3 +1,4 @@
 pub(crate) mod builder;
+pub(crate) mod diagnostics;
 pub(crate) mod errors;
 pub(crate) mod policy;
@@ -1,4 +1,5 @@
-use kaspa_consensus_core::mass::BlockLaneLimits;
+use super::diagnostics::{SelectionOutcome, SelectionTracer};
+use kaspa_consensus_core::{mass::BlockLaneLimits, tx::TransactionId};
 
 /// Policy houses the policy (configuration parameters) which is used to control
 /// the generation of block templates. See the documentation for
@@ -11,6 +12,8 @@ pub struct Policy {
     pub(crate) lanes_per_block_limit: usize,
     /// gas_per_lane_limit is the maximum total gas per lane in a block template.
     pub(crate) gas_per_lane_limit: u64,
+    /// tracer collects the selection outcomes of the candidate transactions when diagnosing a template.
+    pub(crate) tracer: Option<SelectionTracer>,
 }
 
 impl Policy {
@@ -19,6 +22,19 @@ impl Policy {
             max_block_mass,
             lanes_per_block_limit: block_lane_limits.lanes_per_block,
             gas_per_lane_limit: block_lane_limits.gas_per_lane,
+            tracer: None,
+        }
+    }
+
+    pub(crate) fn with_tracer(self, tracer: SelectionTracer) -> Self {
+        Self { tracer: Some(tracer), ..self }
+    }
+
+    /// Records the selection outcome of a candidate transaction if the policy is traced
+    #[inline]
+    pub(crate) fn trace(&self, tx_id: TransactionId, outcome: SelectionOutcome) {
+        if let Some(tracer) = self.tracer.as_ref() {
+            tracer.lock().record(tx_id, outcome);
         }
     }
 }
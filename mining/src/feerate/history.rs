@@ -0,0 +1,218 @@
+//! Rolling history of observed mempool waiting times, used to estimate the feerate required
+//! for inclusion within a target time at a given confidence level.
+//!
+//! Observations are sorted by descending feerate and grouped into exponentially spaced buckets.
+//! Starting from the highest feerates, consecutive buckets are merged until a group holds enough
+//! samples, and the group passes if the fraction of its transactions which got accepted within the
+//! target time reaches the requested confidence. The estimate is the lowest feerate of the last group
+//! passing without interruption, so a feerate is only recommended if every higher feerate also met the target.
+
+use super::{Feerate, FeerateTarget};
+use std::collections::VecDeque;
+
+/// Ratio between the lower bounds of two consecutive feerate buckets
+const BUCKET_SPACING: f64 = 1.1;
+
+/// Minimum number of observations a group of buckets must hold for its success ratio to be considered
+pub(crate) const MIN_GROUP_SAMPLES: usize = 20;
+
+#[derive(Clone, Copy, Debug)]
+struct ConfirmationRecord {
+    feerate: Feerate,
+    /// Time spent in the mempool before acceptance, in milliseconds
+    waiting_time: u64,
+    /// Unix time of the acceptance, in milliseconds
+    accepted_at: u64,
+}
+
+/// A transaction which is not yet accepted, along with the time it waited so far
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct PendingObservation {
+    pub feerate: Feerate,
+    /// In milliseconds
+    pub waiting_time: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HistoryEstimate {
+    pub feerate: Feerate,
+    /// Number of observations backing the estimate
+    pub sample_count: u64,
+}
+
+pub(crate) struct ConfirmationHistory {
+    records: VecDeque<ConfirmationRecord>,
+    /// Records older than this many milliseconds are dropped
+    window: u64,
+    max_records: usize,
+}
+
+impl ConfirmationHistory {
+    pub(crate) fn new(window_milliseconds: u64, max_records: usize) -> Self {
+        Self { records: VecDeque::new(), window: window_milliseconds, max_records }
+    }
+
+    /// Records a transaction added to the mempool at `added_at` and accepted at `accepted_at` (unix milliseconds)
+    pub(crate) fn record(&mut self, feerate: Feerate, added_at: u64, accepted_at: u64) {
+        if self.max_records == 0 {
+            return;
+        }
+        if self.records.len() == self.max_records {
+            self.records.pop_front();
+        }
+        self.records.push_back(ConfirmationRecord { feerate, waiting_time: accepted_at.saturating_sub(added_at), accepted_at });
+        self.prune(accepted_at);
+    }
+
+    /// Drops records which fell out of the history window
+    pub(crate) fn prune(&mut self, now: u64) {
+        while self.records.front().is_some_and(|record| record.accepted_at + self.window < now) {
+            self.records.pop_front();
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Copies the recorded observations along with `pending`, so that estimations can run without holding the mempool
+    pub(crate) fn samples(&self, pending: impl IntoIterator<Item = PendingObservation>) -> ConfirmationSamples {
+        let observations = self
+            .records
+            .iter()
+            .map(|record| Observation { feerate: record.feerate, waiting_time: record.waiting_time, pending: false })
+            .chain(pending.into_iter().map(|tx| Observation { feerate: tx.feerate, waiting_time: tx.waiting_time, pending: true }))
+            .filter(|observation| observation.feerate > 0.0)
+            .collect();
+        ConfirmationSamples { observations }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Observation {
+    feerate: Feerate,
+    /// In milliseconds
+    waiting_time: u64,
+    /// Whether the transaction is still waiting in the mempool
+    pending: bool,
+}
+
+/// Observations copied out of a [`ConfirmationHistory`]
+pub(crate) struct ConfirmationSamples {
+    observations: Vec<Observation>,
+}
+
+impl ConfirmationSamples {
+    /// Returns for each target the lowest feerate for which at least `confidence` of the observed transactions
+    /// were accepted within the target time, or `None` if the history holds too few observations to tell.
+    ///
+    /// Pending transactions which already waited longer than the target count as failures, others are
+    /// ignored since their outcome is still unknown.
+    pub(crate) fn estimate(mut self, targets: &[FeerateTarget]) -> Vec<Option<HistoryEstimate>> {
+        self.observations.sort_unstable_by(|a, b| b.feerate.total_cmp(&a.feerate));
+        targets.iter().map(|target| self.estimate_target(target.seconds * 1000, target.confidence)).collect()
+    }
+
+    fn estimate_target(&self, target: u64, confidence: f64) -> Option<HistoryEstimate> {
+        let mut observations = self
+            .observations
+            .iter()
+            .filter(|observation| !observation.pending || observation.waiting_time > target)
+            .map(|observation| (observation.feerate, !observation.pending && observation.waiting_time <= target))
+            .peekable();
+
+        let mut estimate = None;
+        let (mut passed, mut group_samples, mut group_successes) = (0u64, 0usize, 0usize);
+        while let Some((feerate, success)) = observations.next() {
+            group_samples += 1;
+            group_successes += success as usize;
+            // Groups are only closed on bucket boundaries so that equal feerates are never split
+            let closes_bucket = observations.peek().is_none_or(|(next, _)| bucket(*next) != bucket(feerate));
+            if !closes_bucket || group_samples < MIN_GROUP_SAMPLES {
+                continue;
+            }
+            if (group_successes as f64) < confidence * group_samples as f64 {
+                break;
+            }
+            passed += group_samples as u64;
+            estimate = Some(HistoryEstimate { feerate, sample_count: passed });
+            (group_samples, group_successes) = (0, 0);
+        }
+        estimate
+    }
+}
+
+fn bucket(feerate: Feerate) -> i64 {
+    (feerate.ln() / BUCKET_SPACING.ln()).floor() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: u64 = 60_000;
+
+    fn history_estimate(
+        history: &ConfirmationHistory,
+        seconds: u64,
+        confidence: f64,
+        pending: impl IntoIterator<Item = PendingObservation>,
+    ) -> Option<HistoryEstimate> {
+        history.samples(pending).estimate(&[FeerateTarget { seconds, confidence }])[0]
+    }
+
+    #[test]
+    fn test_confirmation_history_estimate() {
+        let mut history = ConfirmationHistory::new(60 * MINUTE, 10_000);
+        // High feerates are accepted within a second, medium ones within 30 seconds, low ones take 10 minutes
+        for i in 0..100 {
+            history.record(50.0 + i as f64 * 0.01, 0, 800);
+            history.record(10.0 + i as f64 * 0.01, 0, 30_000);
+            history.record(1.0 + i as f64 * 0.01, 0, 10 * MINUTE);
+        }
+
+        let estimate = history_estimate(&history, 1, 0.95, []).unwrap();
+        assert!((50.0..51.0).contains(&estimate.feerate), "{estimate:?}");
+        assert_eq!(estimate.sample_count, 100);
+
+        let estimate = history_estimate(&history, 60, 0.95, []).unwrap();
+        assert!((10.0..11.0).contains(&estimate.feerate), "{estimate:?}");
+        assert_eq!(estimate.sample_count, 200);
+
+        let estimate = history_estimate(&history, 3600, 0.95, []).unwrap();
+        assert!((1.0..2.0).contains(&estimate.feerate), "{estimate:?}");
+
+        // Nothing was ever accepted instantly
+        assert_eq!(history_estimate(&history, 0, 0.5, []), None);
+
+        // Low feerate transactions stuck in the mempool for over an hour fail the low buckets
+        let stuck = (0..500).map(|_| PendingObservation { feerate: 1.99, waiting_time: 2 * 60 * MINUTE });
+        let estimate = history_estimate(&history, 3600, 0.95, stuck).unwrap();
+        assert!((10.0..11.0).contains(&estimate.feerate), "{estimate:?}");
+
+        // Answering several targets at once matches answering them one by one
+        let targets = [1, 60, 3600].map(|seconds| FeerateTarget { seconds, confidence: 0.95 });
+        let estimates = history.samples([]).estimate(&targets);
+        assert_eq!(estimates, targets.map(|target| history_estimate(&history, target.seconds, target.confidence, [])));
+    }
+
+    #[test]
+    fn test_confirmation_history_bounds() {
+        let mut history = ConfirmationHistory::new(10 * MINUTE, 50);
+        for i in 0..100 {
+            history.record(1.0, i * 1000, i * 1000 + 500);
+        }
+        assert_eq!(history.len(), 50);
+
+        history.prune(99_500 + 10 * MINUTE + 1);
+        assert_eq!(history.len(), 0);
+        assert_eq!(history_estimate(&history, 1, 0.5, []), None);
+
+        // Too few samples to be trusted
+        for _ in 0..MIN_GROUP_SAMPLES - 1 {
+            history.record(1.0, 0, 500);
+        }
+        assert_eq!(history_estimate(&history, 1, 0.5, []), None);
+    }
+}
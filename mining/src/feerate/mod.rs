@@ -5,6 +5,8 @@ use crate::mempool::model::frontier::selectors::ALPHA;
 use itertools::Itertools;
 use std::fmt::Display;
 
+pub(crate) mod history;
+
 /// A type representing fee/mass of a transaction in `sompi/gram` units.
 /// Given a feerate value recommendation, calculate the required fee by
 /// taking the transaction mass and multiplying it by feerate: `fee = feerate * mass(tx)`
@@ -136,6 +138,12 @@ impl FeerateEstimator {
         ((c1 * c2) / (-2f64 * z)).powf(1f64 / (ALPHA - 1) as f64)
     }
 
+    /// Returns the feerate expected to provide inclusion within `seconds`, never above the feerate required
+    /// for inclusion in the next block nor below `minimum_standard_feerate`
+    pub fn feerate_for_time(&self, seconds: f64, minimum_standard_feerate: f64) -> f64 {
+        self.time_to_feerate(seconds.max(self.target_time_per_block_seconds)).max(minimum_standard_feerate)
+    }
+
     pub fn calc_estimations(&self, minimum_standard_feerate: f64) -> FeerateEstimations {
         let min = minimum_standard_feerate;
         // Choose `high` such that the transaction is expected to be included in the next block.
@@ -175,6 +183,25 @@ pub struct FeeEstimateVerbose {
     pub next_block_template_feerate_max: f64,
 }
 
+/// A target inclusion time along with the required probability of meeting it
+#[derive(Clone, Copy, Debug)]
+pub struct FeerateTarget {
+    pub seconds: u64,
+    /// In `(0, 1]`
+    pub confidence: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct FeerateTargetEstimation {
+    pub target: FeerateTarget,
+    pub feerate: Feerate,
+    /// Whether the feerate is backed by observed mempool waiting times. Otherwise it is derived
+    /// from the current mempool state alone and the target confidence is not accounted for.
+    pub from_history: bool,
+    /// Number of observed transactions backing a history based estimation
+    pub sample_count: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
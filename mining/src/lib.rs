@@ -17,8 +17,12 @@ pub mod monitor;
 #[cfg(test)]
 mod toccata_transient_mass_activation_tests;
 
+pub use block_template::diagnostics::{
+    BlockTemplateDiagnostics, CandidateTransactionDiagnostics, DimensionMasses, FrontierSelectorKind, LaneUsageDiagnostics,
+    MassDimension, SelectionOutcome,
+};
 // Exposed for benchmarks
-pub use block_template::policy::Policy;
+pub use block_template::{builder::BlockTemplateCustomization, policy::Policy};
 pub use mempool::model::frontier::{Frontier, feerate_key::FeerateTransactionKey, search_tree::SearchTree};
 
 #[cfg(test)]
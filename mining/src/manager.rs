@@ -1,17 +1,23 @@
 use crate::{
-    MempoolCountersSnapshot, MiningCounters, P2pTxCountSample,
-    block_template::{builder::BlockTemplateBuilder, errors::BuilderError},
+    BlockTemplateCustomization, MempoolCountersSnapshot, MiningCounters, P2pTxCountSample,
+    block_template::{
+        builder::BlockTemplateBuilder,
+        diagnostics::{BlockTemplateDiagnostics, SelectionTracer},
+        errors::BuilderError,
+    },
     cache::BlockTemplateCache,
     errors::MiningManagerResult,
-    feerate::{FeeEstimateVerbose, FeerateEstimations, FeerateEstimatorArgs},
+    feerate::{FeeEstimateVerbose, FeerateEstimations, FeerateEstimatorArgs, FeerateTarget, FeerateTargetEstimation},
     mempool::{
         Mempool,
         config::Config,
         model::tx::{MempoolTransaction, TransactionPostValidation, TransactionPreValidation, TxRemovalReason},
+        policy::AdmissionPolicies,
         populate_entries_and_try_validate::{
             PopulateError, populate_mempool_transactions_in_parallel, validate_mempool_transaction,
             validate_mempool_transactions_in_parallel,
         },
+        snapshot::MempoolSnapshot,
         tx::{Orphan, Priority, RbfPolicy},
     },
     model::{
@@ -38,7 +44,7 @@ use kaspa_consensusmanager::{ConsensusProxy, spawn_blocking};
 use kaspa_core::{debug, error, info, time::Stopwatch, warn};
 use kaspa_mining_errors::{manager::MiningManagerError, mempool::RuleError};
 use parking_lot::RwLock;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use tokio::sync::mpsc::UnboundedSender;
 
 pub struct MiningManager {
@@ -70,12 +76,14 @@ impl MiningManager {
         toccata_activation: ForkActivation,
         block_lane_limits: BlockLaneLimits,
         ram_scale: f64,
+        admission_policies: AdmissionPolicies,
         cache_lifetime: Option<u64>,
         counters: Arc<MiningCounters>,
     ) -> Self {
         let config =
             Config::build_default(target_time_per_block, relay_non_std_transactions, mempool_block_mass_limits, block_lane_limits)
-                .apply_ram_scale(ram_scale);
+                .apply_ram_scale(ram_scale)
+                .with_admission_policies(admission_policies);
         Self::with_config(config, toccata_activation, cache_lifetime, counters)
     }
 
@@ -92,9 +100,22 @@ impl MiningManager {
     }
 
     pub fn get_block_template(&self, consensus: &dyn ConsensusApi, miner_data: &MinerData) -> MiningManagerResult<BlockTemplate> {
+        self.get_customized_block_template(consensus, miner_data, &BlockTemplateCustomization::default())
+    }
+
+    /// Returns a block template whose transaction selection follows `customization`.
+    ///
+    /// Customized templates are specific to their requester, so they neither use nor update the template cache.
+    pub fn get_customized_block_template(
+        &self,
+        consensus: &dyn ConsensusApi,
+        miner_data: &MinerData,
+        customization: &BlockTemplateCustomization,
+    ) -> MiningManagerResult<BlockTemplate> {
+        let is_customized = !customization.is_empty();
         let virtual_state_approx_id = consensus.get_virtual_state_approx_id();
         let mut cache_lock = self.block_template_cache.lock(virtual_state_approx_id);
-        let immutable_template = cache_lock.get_immutable_cached_template();
+        let immutable_template = if is_customized { None } else { cache_lock.get_immutable_cached_template() };
 
         // We first try and use a cached template if not expired
         if let Some(immutable_template) = immutable_template {
@@ -121,7 +142,7 @@ impl MiningManager {
         loop {
             attempts += 1;
 
-            let selector = self.build_selector();
+            let selector = if is_customized { self.build_customized_selector(customization) } else { self.build_selector() };
             let block_template_builder = BlockTemplateBuilder::new();
             let build_mode = if attempts < self.config.maximum_build_block_template_attempts {
                 TemplateBuildMode::Standard
@@ -130,7 +151,11 @@ impl MiningManager {
             };
             match block_template_builder.build_block_template(consensus, miner_data, selector, build_mode) {
                 Ok(block_template) => {
-                    let block_template = cache_lock.set_immutable_cached_template(block_template);
+                    let block_template = if is_customized {
+                        Arc::new(block_template)
+                    } else {
+                        cache_lock.set_immutable_cached_template(block_template)
+                    };
                     match attempts {
                         1 => {
                             debug!(
@@ -211,11 +236,60 @@ impl MiningManager {
         }
     }
 
+    /// Builds a block template dry-run following `customization` and returns the trace of its transaction selection.
+    ///
+    /// Unlike [`Self::get_customized_block_template`], the template is built in a single attempt, it is not cached
+    /// and transactions found invalid by consensus are only reported, not removed from the mempool.
+    pub fn get_block_template_diagnostics(
+        &self,
+        consensus: &dyn ConsensusApi,
+        miner_data: &MinerData,
+        customization: &BlockTemplateCustomization,
+    ) -> MiningManagerResult<BlockTemplateDiagnostics> {
+        let tracer = SelectionTracer::default();
+        let (selector, selector_kind, ready_transaction_count) = {
+            let mempool = self.mempool.read();
+            let (selector, selector_kind) = mempool.build_traced_selector(tracer.clone(), customization);
+            (selector, selector_kind, mempool.ready_transaction_count())
+        };
+        let block_template =
+            BlockTemplateBuilder::new().build_block_template(consensus, miner_data, selector, TemplateBuildMode::Infallible)?;
+
+        // Candidates which left the mempool since the selection are omitted
+        let mut trace = std::mem::take(&mut *tracer.lock());
+        trace.reconcile(block_template.block.transactions.iter().skip(1).map(|tx| tx.id()));
+        let candidates = {
+            let mempool = self.mempool.read();
+            trace
+                .into_outcomes()
+                .into_iter()
+                .filter_map(|(transaction_id, outcome)| mempool.get_candidate_diagnostics(&transaction_id, outcome))
+                .collect()
+        };
+        Ok(BlockTemplateDiagnostics::new(
+            selector_kind,
+            ready_transaction_count,
+            candidates,
+            &block_template,
+            self.config.mempool_block_mass_limits.get(consensus.get_virtual_daa_score()),
+            self.config.mempool_mass_cofactors.after().reference,
+            self.config.block_lane_limits,
+        ))
+    }
+
     /// Dynamically builds a transaction selector based on the specific state of the ready transactions frontier
     pub(crate) fn build_selector(&self) -> Box<dyn TemplateTransactionSelector> {
         self.mempool.read().build_selector()
     }
 
+    /// Builds a transaction selector applying `customization` over the regular frontier based selection
+    pub(crate) fn build_customized_selector(
+        &self,
+        customization: &BlockTemplateCustomization,
+    ) -> Box<dyn TemplateTransactionSelector> {
+        self.mempool.read().build_customized_selector(customization)
+    }
+
     /// Returns realtime feerate estimations based on internal mempool state
     pub(crate) fn get_realtime_feerate_estimations(&self) -> FeerateEstimations {
         let args =
@@ -224,6 +298,37 @@ impl MiningManager {
         estimator.calc_estimations(self.config.minimum_feerate())
     }
 
+    /// Returns a feerate estimation for meeting each target, based on observed mempool waiting times when
+    /// enough of them were recorded and falling back to estimations based on internal mempool state otherwise
+    pub(crate) fn get_feerate_estimations_for_targets(&self, targets: &[FeerateTarget]) -> Vec<FeerateTargetEstimation> {
+        let args =
+            FeerateEstimatorArgs::new(self.config.network_blocks_per_second, self.config.mempool_mass_cofactors.after().reference);
+        let mempool_read = self.mempool.read();
+        let estimator = mempool_read.build_feerate_estimator(args);
+        let history_samples = mempool_read.feerate_history_samples(targets);
+        drop(mempool_read);
+        let history_estimates = history_samples.estimate(targets);
+        let minimum_feerate = self.config.minimum_feerate();
+        targets
+            .iter()
+            .zip(history_estimates)
+            .map(|(&target, estimate)| match estimate {
+                Some(estimate) => FeerateTargetEstimation {
+                    target,
+                    feerate: estimate.feerate.max(minimum_feerate),
+                    from_history: true,
+                    sample_count: estimate.sample_count,
+                },
+                None => FeerateTargetEstimation {
+                    target,
+                    feerate: estimator.feerate_for_time(target.seconds as f64, minimum_feerate),
+                    from_history: false,
+                    sample_count: 0,
+                },
+            })
+            .collect()
+    }
+
     /// Returns realtime feerate estimations based on internal mempool state with additional verbose data
     pub(crate) fn get_realtime_feerate_estimations_verbose(
         &self,
@@ -399,7 +504,7 @@ impl MiningManager {
                         }
                         Ok(TransactionPostValidation { removed: _, accepted: None }) => vec![],
                         Err(err) => {
-                            debug!("Failed to unorphan transaction {0} due to rule error: {1}", orphan_id, err);
+                            debug!(tx_id:% = orphan_id; "Failed to unorphan transaction {0} due to rule error: {1}", orphan_id, err);
                             vec![]
                         }
                     }
@@ -463,7 +568,7 @@ impl MiningManager {
                         None
                     }
                     Err(err) => {
-                        debug!("Failed to pre validate transaction {0} due to rule error: {1}", transaction_id, err);
+                        debug!(tx_id:% = transaction_id; "Failed to pre validate transaction {0} due to rule error: {1}", transaction_id, err);
                         insert_results.push(Err(MiningManagerError::MempoolError(err)));
                         None
                     }
@@ -512,7 +617,7 @@ impl MiningManager {
                         vec![]
                     }
                     Err(err) => {
-                        debug!("Failed to post validate transaction {0} due to rule error: {1}", transaction_id, err);
+                        debug!(tx_id:% = transaction_id; "Failed to post validate transaction {0} due to rule error: {1}", transaction_id, err);
                         insert_results.push(Err(MiningManagerError::MempoolError(err)));
                         vec![]
                     }
@@ -639,7 +744,7 @@ impl MiningManager {
             let mut mempool = self.mempool.write();
             chunk.into_iter().for_each(|tx| {
                 if let Err(err) = mempool.remove_transaction(tx, true, TxRemovalReason::Muted, "") {
-                    warn!("Failed to remove transaction {} from mempool: {}", tx, err);
+                    warn!(tx_id:% = tx; "Failed to remove transaction {} from mempool: {}", tx, err);
                 }
             });
         }
@@ -842,6 +947,42 @@ impl MiningManager {
         }
     }
 
+    /// Returns a snapshot of all transactions held by the mempool, orphans included
+    pub fn export_snapshot(&self) -> MempoolSnapshot {
+        MempoolSnapshot::new(self.mempool.read().export_entries())
+    }
+
+    /// Revalidates the transactions of a snapshot against the current consensus state and inserts the valid ones
+    /// with their original priority. Transactions re-entering the mempool keep the time at which they initially
+    /// entered it.
+    ///
+    /// Returns the transactions accepted into the transaction pool.
+    pub fn import_snapshot(&self, consensus: &dyn ConsensusApi, snapshot: MempoolSnapshot) -> Vec<Arc<Transaction>> {
+        let added_times: HashMap<TransactionId, u64> =
+            snapshot.entries.iter().map(|entry| (entry.transaction.id(), entry.added_at)).collect();
+        let (high_priority, low_priority): (Vec<_>, Vec<_>) =
+            snapshot.entries.into_iter().partition(|entry| entry.priority == Priority::High);
+
+        let mut accepted_transactions = Vec::with_capacity(added_times.len());
+        let mut rejected_count = 0;
+        for (priority, entries) in [(Priority::High, high_priority), (Priority::Low, low_priority)] {
+            let transactions = entries.into_iter().map(|entry| entry.transaction).collect();
+            let results =
+                self.validate_and_insert_transaction_batch(consensus, transactions, priority, Orphan::Allowed, RbfPolicy::Forbidden);
+            for result in results {
+                match result {
+                    Ok(transaction) => accepted_transactions.push(transaction),
+                    Err(_) => rejected_count += 1,
+                }
+            }
+        }
+
+        // write lock on mempool
+        self.mempool.write().restore_added_times(&added_times);
+        debug!("Mempool snapshot import: {} transactions accepted, {} rejected", accepted_transactions.len(), rejected_count);
+        accepted_transactions
+    }
+
     pub fn has_accepted_transaction(&self, transaction_id: &TransactionId) -> bool {
         self.mempool.read().has_accepted_transaction(transaction_id)
     }
@@ -875,11 +1016,35 @@ impl MiningManagerProxy {
         consensus.clone().spawn_blocking(move |c| self.inner.get_block_template(c, &miner_data)).await
     }
 
+    pub async fn get_customized_block_template(
+        self,
+        consensus: &ConsensusProxy,
+        miner_data: MinerData,
+        customization: BlockTemplateCustomization,
+    ) -> MiningManagerResult<BlockTemplate> {
+        consensus.clone().spawn_blocking(move |c| self.inner.get_customized_block_template(c, &miner_data, &customization)).await
+    }
+
+    /// Builds a block template dry-run and returns the trace of its transaction selection
+    pub async fn get_block_template_diagnostics(
+        self,
+        consensus: &ConsensusProxy,
+        miner_data: MinerData,
+        customization: BlockTemplateCustomization,
+    ) -> MiningManagerResult<BlockTemplateDiagnostics> {
+        consensus.clone().spawn_blocking(move |c| self.inner.get_block_template_diagnostics(c, &miner_data, &customization)).await
+    }
+
     /// Returns realtime feerate estimations based on internal mempool state
     pub async fn get_realtime_feerate_estimations(self) -> FeerateEstimations {
         spawn_blocking(move || self.inner.get_realtime_feerate_estimations()).await.unwrap()
     }
 
+    /// Returns a feerate estimation for meeting each target, preferably based on observed mempool waiting times
+    pub async fn get_feerate_estimations_for_targets(self, targets: Vec<FeerateTarget>) -> Vec<FeerateTargetEstimation> {
+        spawn_blocking(move || self.inner.get_feerate_estimations_for_targets(&targets)).await.unwrap()
+    }
+
     /// Returns realtime feerate estimations based on internal mempool state with additional verbose data
     pub async fn get_realtime_feerate_estimations_verbose(
         self,
@@ -956,6 +1121,19 @@ impl MiningManagerProxy {
         consensus.clone().spawn_blocking(move |c| self.inner.revalidate_high_priority_transactions(c, transaction_ids_sender)).await;
     }
 
+    /// Returns a snapshot of all transactions held by the mempool, orphans included
+    pub async fn export_snapshot(self) -> MempoolSnapshot {
+        spawn_blocking(move || self.inner.export_snapshot()).await.unwrap()
+    }
+
+    /// Revalidates the transactions of a snapshot against the current consensus state and inserts the valid ones
+    /// with their original priority and entry time.
+    ///
+    /// Returns the transactions accepted into the transaction pool.
+    pub async fn import_snapshot(self, consensus: &ConsensusProxy, snapshot: MempoolSnapshot) -> Vec<Arc<Transaction>> {
+        consensus.clone().spawn_blocking(move |c| self.inner.import_snapshot(c, snapshot)).await
+    }
+
     /// Try to return a mempool transaction by its id.
     ///
     /// Note: the transaction is an orphan if tx.is_fully_populated() returns false.
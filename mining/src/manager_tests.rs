@@ -1,9 +1,14 @@
 #[cfg(test)]
 mod tests {
     use crate::{
-        MiningCounters,
-        block_template::{builder::BlockTemplateBuilder, policy::Policy},
+        BlockTemplateCustomization, MiningCounters,
+        block_template::{
+            builder::BlockTemplateBuilder,
+            diagnostics::{DimensionMasses, FrontierSelectorKind, SelectionOutcome},
+            policy::Policy,
+        },
         errors::{MiningManagerError, MiningManagerResult},
+        feerate::FeerateTarget,
         manager::MiningManager,
         mempool::{
             config::{Config, DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE},
@@ -1146,6 +1151,184 @@ mod tests {
         // TODO: extend the test according to the golang scenario
     }
 
+    /// test_customized_selector verifies that block template customizations prioritize transactions along with
+    /// their mempool ancestors, skip excluded transactions and respect the reserved mass.
+    #[test]
+    fn test_customized_selector() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let mining_manager = default_mining_manager();
+
+        const TX_PAIRS_COUNT: usize = 6;
+        let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, TX_PAIRS_COUNT);
+        for tx in parent_txs.iter().chain(child_txs.iter()) {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "the mempool should accept the valid transaction {}", tx.id());
+        }
+
+        // The prioritized child comes along with its parent while the child of an excluded parent is dropped
+        let customization = BlockTemplateCustomization::new(
+            vec![child_txs[0].id(), child_txs[1].id()],
+            vec![parent_txs[1].id(), parent_txs[2].id()],
+            0,
+        );
+        let transactions = mining_manager.build_customized_selector(&customization).select_transactions();
+        let position = |id: TransactionId| transactions.iter().position(|tx| tx.id() == id);
+        assert_eq!(position(parent_txs[0].id()), Some(0), "the ancestor of a priority transaction should be selected first");
+        assert_eq!(position(child_txs[0].id()), Some(1), "the priority transaction should follow its ancestor");
+        assert!(position(child_txs[1].id()).is_none(), "a priority transaction with an excluded ancestor should not be selected");
+        assert!(position(parent_txs[1].id()).is_none(), "an excluded transaction should not be selected");
+        assert!(position(parent_txs[2].id()).is_none(), "an excluded transaction should not be selected");
+        assert_eq!(TX_PAIRS_COUNT - 1, transactions.len(), "all the other ready transactions should be selected once");
+
+        // A priority group exceeding the reserved mass falls back to the regular selection
+        let customization = BlockTemplateCustomization::new(vec![child_txs[0].id()], vec![], 1);
+        let transactions = mining_manager.build_customized_selector(&customization).select_transactions();
+        assert!(!contained_by(child_txs[0].id(), &transactions), "the priority group should not fit in the reserved mass");
+        assert_eq!(TX_PAIRS_COUNT, transactions.len(), "all ready transactions should be selected");
+    }
+
+    /// test_block_template_diagnostics verifies that a template dry-run traces the frontier candidates and
+    /// sums up the fees and masses of the selected transactions without altering the mempool.
+    #[test]
+    fn test_block_template_diagnostics() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let mining_manager = default_mining_manager();
+
+        const TX_PAIRS_COUNT: usize = 5;
+        let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, TX_PAIRS_COUNT);
+        for tx in parent_txs.iter().chain(child_txs.iter()) {
+            let result = mining_manager.validate_and_insert_transaction(
+                consensus.as_ref(),
+                tx.clone(),
+                Priority::Low,
+                Orphan::Allowed,
+                RbfPolicy::Forbidden,
+            );
+            assert!(result.is_ok(), "the mempool should accept the valid transaction {}", tx.id());
+        }
+
+        let diagnostics = mining_manager
+            .get_block_template_diagnostics(consensus.as_ref(), &get_miner_data(Prefix::Testnet), &Default::default())
+            .expect("the dry-run should build a template");
+        assert_eq!(FrontierSelectorKind::TakeAll, diagnostics.selector_kind, "the whole frontier fits into a block");
+        assert_eq!(TX_PAIRS_COUNT, diagnostics.ready_transaction_count);
+        assert_eq!(TX_PAIRS_COUNT, diagnostics.candidates.len(), "only ready transactions should be candidates");
+        assert_eq!(TX_PAIRS_COUNT, diagnostics.selected_transaction_count);
+        for parent_tx in parent_txs.iter() {
+            let candidate = diagnostics.candidates.iter().find(|candidate| candidate.transaction_id == parent_tx.id()).unwrap();
+            assert_eq!(SelectionOutcome::Selected, candidate.outcome, "the parent transaction {} should be selected", parent_tx.id());
+            assert_eq!(DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE, candidate.fee);
+        }
+        let mass_used = diagnostics.candidates.iter().fold(DimensionMasses::default(), |mut total, candidate| {
+            total.add(&candidate.masses);
+            total
+        });
+        assert_eq!(mass_used, diagnostics.mass_used);
+        assert_eq!(diagnostics.candidates.iter().map(|candidate| candidate.mass).sum::<u64>(), diagnostics.normalized_mass_used);
+        assert_eq!(BlockMassLimits::with_shared_limit(MAX_BLOCK_MASS), diagnostics.mass_limits);
+        assert_eq!(MAX_BLOCK_MASS, diagnostics.normalized_mass_limit);
+        assert_eq!(1, diagnostics.lanes.len(), "all transactions should use the native lane");
+        assert_eq!(TX_PAIRS_COUNT, diagnostics.lanes[0].transaction_count);
+        assert_eq!(
+            2 * TX_PAIRS_COUNT,
+            mining_manager.transaction_count(TransactionQuery::TransactionsOnly),
+            "the dry-run should not remove transactions from the mempool"
+        );
+
+        // Excluded transactions are traced as such by customized dry-runs
+        let customization = BlockTemplateCustomization::new(vec![], vec![parent_txs[0].id()], 0);
+        let diagnostics = mining_manager
+            .get_block_template_diagnostics(consensus.as_ref(), &get_miner_data(Prefix::Testnet), &customization)
+            .expect("the dry-run should build a template");
+        let outcome = |id: TransactionId| {
+            diagnostics.candidates.iter().find(|candidate| candidate.transaction_id == id).unwrap().outcome.clone()
+        };
+        assert_eq!(SelectionOutcome::Excluded, outcome(parent_txs[0].id()));
+        assert_eq!(SelectionOutcome::Selected, outcome(parent_txs[1].id()));
+        assert_eq!(TX_PAIRS_COUNT - 1, diagnostics.selected_transaction_count);
+    }
+
+    /// test_block_template_diagnostics_mass_limits verifies the traces of the sampling selectors used once the frontier
+    /// exceeds the block mass, and that a priority transaction is traced as selected whatever the inner selection traced.
+    #[test]
+    fn test_block_template_diagnostics_mass_limits() {
+        const TX_COUNT: usize = 10;
+        let consensus = Arc::new(ConsensusMock::new());
+        let (parent_txs, _) = create_arrays_of_parent_and_children_transactions(&consensus, TX_COUNT);
+        let mining_manager_with_block_mass = |block_mass: u64| {
+            let mining_manager = MiningManager::new(
+                TARGET_TIME_PER_BLOCK,
+                false,
+                BlockMassLimits::with_shared_limit(block_mass),
+                ForkActivation::never(),
+                BLOCK_LANE_LIMITS,
+                None,
+                Arc::new(MiningCounters::default()),
+            );
+            for tx in parent_txs.iter() {
+                let result = mining_manager.validate_and_insert_transaction(
+                    consensus.as_ref(),
+                    tx.clone(),
+                    Priority::Low,
+                    Orphan::Forbidden,
+                    RbfPolicy::Forbidden,
+                );
+                assert!(result.is_ok(), "the mempool should accept the valid transaction {}", tx.id());
+            }
+            mining_manager
+        };
+
+        // All the transactions share the same structure, hence the same normalized mass
+        let diagnostics = mining_manager_with_block_mass(MAX_BLOCK_MASS)
+            .get_block_template_diagnostics(consensus.as_ref(), &get_miner_data(Prefix::Testnet), &Default::default())
+            .expect("the dry-run should build a template");
+        let tx_mass = diagnostics.candidates[0].mass;
+        assert!(diagnostics.candidates.iter().all(|candidate| candidate.mass == tx_mass));
+
+        for (block_txs, expected_kind) in [(3, FrontierSelectorKind::MutatingTree), (2, FrontierSelectorKind::Sequence)] {
+            let block_mass = block_txs * tx_mass;
+            let mining_manager = mining_manager_with_block_mass(block_mass);
+            let diagnostics = mining_manager
+                .get_block_template_diagnostics(consensus.as_ref(), &get_miner_data(Prefix::Testnet), &Default::default())
+                .expect("the dry-run should build a template");
+            assert_eq!(expected_kind, diagnostics.selector_kind);
+            assert_eq!(TX_COUNT, diagnostics.ready_transaction_count);
+            assert!(diagnostics.selected_transaction_count > 0, "a {:?} selection should fill the block", expected_kind);
+            assert!(diagnostics.normalized_mass_used <= block_mass, "a {:?} selection should respect the block mass", expected_kind);
+            let selected = diagnostics.candidates.iter().filter(|candidate| candidate.outcome == SelectionOutcome::Selected).count();
+            assert_eq!(diagnostics.selected_transaction_count, selected);
+            assert!(
+                diagnostics
+                    .candidates
+                    .iter()
+                    .all(|candidate| matches!(candidate.outcome, SelectionOutcome::Selected | SelectionOutcome::BlockMassLimit)),
+                "candidates left out of a {:?} selection should only be traced as over the block mass",
+                expected_kind
+            );
+
+            // The priority transaction takes the reserved mass, leaving the rest to the inner selection which may
+            // consider it again and find it over its own budget
+            let customization = BlockTemplateCustomization::new(vec![parent_txs[0].id()], vec![], tx_mass);
+            for _ in 0..10 {
+                let diagnostics = mining_manager
+                    .get_block_template_diagnostics(consensus.as_ref(), &get_miner_data(Prefix::Testnet), &customization)
+                    .expect("the dry-run should build a template");
+                let priority = diagnostics.candidates.iter().find(|candidate| candidate.transaction_id == parent_txs[0].id()).unwrap();
+                assert_eq!(SelectionOutcome::Selected, priority.outcome, "the priority transaction should be traced as selected");
+                let selected =
+                    diagnostics.candidates.iter().filter(|candidate| candidate.outcome == SelectionOutcome::Selected).count();
+                assert_eq!(diagnostics.selected_transaction_count, selected);
+                assert!(diagnostics.selected_transaction_count <= block_txs as usize);
+            }
+        }
+    }
+
     // This is a sanity test for the mempool eviction policy. We check that if the mempool reached to its maximum
     // (in bytes) a high paying transaction will evict as much transactions as needed so it can enter the
     // mempool.
@@ -1230,6 +1413,100 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_feerate_estimations_for_targets() {
+        let minimum_feerate = DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE as f64 / 1000.0;
+        let consensus = Arc::new(ConsensusMock::new());
+        let mining_manager = default_mining_manager();
+        let targets = [FeerateTarget { seconds: 60, confidence: 0.95 }, FeerateTarget { seconds: 3600, confidence: 0.5 }];
+
+        const TX_COUNT: u32 = 50;
+        let transactions = (0..TX_COUNT).map(|i| create_transaction_with_utxo_entry(i, 0)).collect::<Vec<_>>();
+        for tx in transactions.iter().cloned() {
+            validate_and_insert_mutable_transaction(&mining_manager, consensus.as_ref(), tx).unwrap();
+        }
+
+        // Without any observed waiting time, the estimations fall back to the mempool state
+        for estimation in mining_manager.get_feerate_estimations_for_targets(&targets) {
+            assert!(!estimation.from_history);
+            assert!(estimation.feerate >= minimum_feerate);
+        }
+
+        let block_transactions = build_block_transactions(transactions.iter().map(|mtx| mtx.tx.as_ref()));
+        mining_manager.handle_new_block_transactions(consensus.as_ref(), 2, &block_transactions).unwrap();
+
+        // All transactions got accepted well within the targets
+        for estimation in mining_manager.get_feerate_estimations_for_targets(&targets) {
+            assert!(estimation.from_history, "{estimation:?}");
+            assert!(estimation.sample_count > 0 && estimation.sample_count <= TX_COUNT as u64);
+            assert!(estimation.feerate >= minimum_feerate);
+        }
+    }
+
+    #[test]
+    fn test_mempool_snapshot_export_import() {
+        let consensus = Arc::new(ConsensusMock::new());
+        let mining_manager = default_mining_manager();
+
+        // All parents but the first one are submitted with high priority, so the first child remains an orphan
+        const TX_PAIRS_COUNT: usize = 5;
+        let (parent_txs, child_txs) = create_arrays_of_parent_and_children_transactions(&consensus, TX_PAIRS_COUNT);
+        for parent in parent_txs.iter().skip(1) {
+            mining_manager
+                .validate_and_insert_transaction(
+                    consensus.as_ref(),
+                    parent.clone(),
+                    Priority::High,
+                    Orphan::Forbidden,
+                    RbfPolicy::Forbidden,
+                )
+                .unwrap();
+        }
+        for child in child_txs.iter() {
+            mining_manager
+                .validate_and_insert_transaction(
+                    consensus.as_ref(),
+                    child.clone(),
+                    Priority::Low,
+                    Orphan::Allowed,
+                    RbfPolicy::Forbidden,
+                )
+                .unwrap();
+        }
+        assert_eq!(mining_manager.transaction_count(TransactionQuery::TransactionsOnly), 2 * TX_PAIRS_COUNT - 2);
+        assert_eq!(mining_manager.transaction_count(TransactionQuery::OrphansOnly), 1);
+
+        let snapshot = mining_manager.export_snapshot();
+        assert_eq!(snapshot.len(), 2 * TX_PAIRS_COUNT - 1);
+        let added_times = snapshot.entries.iter().map(|entry| (entry.transaction.id(), entry.added_at)).collect::<Vec<_>>();
+
+        // Import into a fresh mempool sharing the same consensus state
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        let restored_manager = default_mining_manager();
+        let accepted = restored_manager.import_snapshot(consensus.as_ref(), snapshot);
+        assert_eq!(accepted.len(), 2 * TX_PAIRS_COUNT - 2);
+        assert_eq!(restored_manager.transaction_count(TransactionQuery::TransactionsOnly), 2 * TX_PAIRS_COUNT - 2);
+        assert_eq!(restored_manager.transaction_count(TransactionQuery::OrphansOnly), 1);
+        assert!(restored_manager.has_transaction(&child_txs[0].id(), TransactionQuery::OrphansOnly));
+
+        let restored = restored_manager.export_snapshot();
+        for (transaction_id, added_at) in added_times {
+            let entry = restored.entries.iter().find(|entry| entry.transaction.id() == transaction_id).unwrap();
+            assert_eq!(entry.added_at, added_at, "transaction {transaction_id} should keep its original entry time");
+            let expected_priority = if parent_txs.iter().any(|tx| tx.id() == transaction_id) { Priority::High } else { Priority::Low };
+            assert_eq!(entry.priority, expected_priority);
+        }
+
+        // Transactions which meanwhile got included in a block are not restored
+        let block_transactions = build_block_transactions(parent_txs.iter().skip(1));
+        let restored_manager = default_mining_manager();
+        restored_manager.handle_new_block_transactions(consensus.as_ref(), 2, &block_transactions).unwrap();
+        parent_txs.iter().skip(1).for_each(|tx| consensus.add_transaction(tx.clone(), 2));
+        restored_manager.import_snapshot(consensus.as_ref(), mining_manager.export_snapshot());
+        assert!(parent_txs.iter().all(|tx| !restored_manager.has_transaction(&tx.id(), TransactionQuery::All)));
+        assert_eq!(restored_manager.transaction_count(TransactionQuery::TransactionsOnly), TX_PAIRS_COUNT - 1);
+    }
+
     fn validate_and_insert_mutable_transaction(
         mining_manager: &MiningManager,
         consensus: &dyn ConsensusApi,
@@ -198,8 +198,11 @@ mod tests {
         tx::{ScriptPublicKey, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry},
     };
     use kaspa_txscript::{
+        VaultScript,
         opcodes::codes::{OpReturn, OpTrue},
+        pay_to_script_hash_script,
         script_builder::ScriptBuilder,
+        vault_recover_signature_script,
     };
     use std::sync::Arc;
 
@@ -590,6 +593,45 @@ mod tests {
         // end-TODO
     }
 
+    #[test]
+    fn test_script_template_sig_op_limit() {
+        let params: Params = NetworkType::Simnet.into();
+        let config =
+            Config::build_default(params.target_time_per_block(), false, params.mempool_block_mass_limits(), params.block_lane_limits);
+        let mempool = Mempool::new(Arc::new(config), params.toccata_activation, Arc::new(MiningCounters::default()));
+
+        let spend_vault = |recovery_key_count: u8| {
+            let recovery_keys = (0..recovery_key_count).map(|i| [i + 1; 32]).collect();
+            let redeem_script = VaultScript::new([0; 32], 100, recovery_keys, 1).redeem_script().unwrap();
+            let signature_script = vault_recover_signature_script(redeem_script.clone(), &[vec![0; 65]]).unwrap();
+            let prev_out = TransactionOutpoint::new(kaspa_hashes::Hash::from_u64_word(1), 1);
+            let input = TransactionInput::new(prev_out, signature_script, MAX_TX_IN_SEQUENCE_NUM, recovery_key_count + 1);
+            let tx = Transaction::new(
+                TX_VERSION,
+                vec![input],
+                vec![TransactionOutput::new(SOMPI_PER_KASPA, pay_to_script_hash_script(&redeem_script))],
+                0,
+                SUBNETWORK_ID_NATIVE,
+                0,
+                vec![],
+            );
+            let entry = UtxoEntry::new(2 * SOMPI_PER_KASPA, pay_to_script_hash_script(&redeem_script), 0, false, None);
+            let mut mtx = MutableTransaction::with_entries(tx.into(), vec![entry]);
+            mtx.calculated_non_contextual_masses = Some(NonContextualMasses::new(1_000, 1_000));
+            mtx.calculated_fee = Some(SOMPI_PER_KASPA);
+            mtx
+        };
+
+        // Vault spends are regular P2SH spends: the owner key plus the recovery keys count toward the limit
+        let mtx = spend_vault(3);
+        assert_eq!(mempool.check_transaction_standard_in_context(&mtx, Priority::High, 0), Ok(()));
+        let mtx = spend_vault(20);
+        assert_eq!(
+            mempool.check_transaction_standard_in_context(&mtx, Priority::High, 0),
+            Err(NonStandardError::RejectSignatureCount(mtx.id(), 0, 21, MAX_STANDARD_P2SH_SIG_OPS))
+        );
+    }
+
     #[test]
     fn test_standard_transaction_mass_cap() {
         // Toccata score far enough out that the relaxation window (30 min * bps) starts at a positive score.
@@ -1,3 +1,4 @@
+use super::policy::AdmissionPolicies;
 use kaspa_consensus_core::{
     config::params::ForkedParam,
     mass::{BlockLaneLimits, BlockMassLimits, MassCofactors},
@@ -14,6 +15,10 @@ pub(crate) const DEFAULT_ACCEPTED_TRANSACTION_EXPIRE_SCAN_INTERVAL_SECONDS: u64
 pub(crate) const DEFAULT_ORPHAN_EXPIRE_INTERVAL_SECONDS: u64 = 60;
 pub(crate) const DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS: u64 = 10;
 
+/// Observed mempool waiting times are kept for this long for estimating feerates
+pub(crate) const DEFAULT_FEE_HISTORY_WINDOW_SECONDS: u64 = 6 * 60 * 60;
+pub(crate) const DEFAULT_FEE_HISTORY_MAX_RECORDS: usize = 200_000;
+
 pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_NORMALIZED_MASS: u64 = 500_000;
 pub(crate) const DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT: u64 = 500;
 
@@ -40,6 +45,8 @@ pub struct Config {
     pub accepted_transaction_expire_scan_interval_milliseconds: u64,
     pub orphan_expire_interval_daa_score: u64,
     pub orphan_expire_scan_interval_daa_score: u64,
+    pub fee_history_window_milliseconds: u64,
+    pub fee_history_max_records: usize,
     pub maximum_orphan_transaction_normalized_mass: u64,
     pub maximum_orphan_transaction_count: u64,
     pub accept_non_standard: bool,
@@ -48,6 +55,7 @@ pub struct Config {
     pub block_lane_limits: BlockLaneLimits,
     pub minimum_relay_transaction_fee: u64,
     pub network_blocks_per_second: u64,
+    pub admission_policies: AdmissionPolicies,
 }
 
 impl Config {
@@ -64,6 +72,8 @@ impl Config {
         accepted_transaction_expire_scan_interval_milliseconds: u64,
         orphan_expire_interval_daa_score: u64,
         orphan_expire_scan_interval_daa_score: u64,
+        fee_history_window_milliseconds: u64,
+        fee_history_max_records: usize,
         maximum_orphan_transaction_normalized_mass: u64,
         maximum_orphan_transaction_count: u64,
         accept_non_standard: bool,
@@ -86,6 +96,8 @@ impl Config {
             accepted_transaction_expire_scan_interval_milliseconds,
             orphan_expire_interval_daa_score,
             orphan_expire_scan_interval_daa_score,
+            fee_history_window_milliseconds,
+            fee_history_max_records,
             maximum_orphan_transaction_normalized_mass,
             maximum_orphan_transaction_count,
             accept_non_standard,
@@ -94,6 +106,7 @@ impl Config {
             block_lane_limits,
             minimum_relay_transaction_fee,
             network_blocks_per_second,
+            admission_policies: AdmissionPolicies::default(),
         }
     }
 
@@ -122,6 +135,8 @@ impl Config {
             accepted_transaction_expire_scan_interval_milliseconds: DEFAULT_ACCEPTED_TRANSACTION_EXPIRE_SCAN_INTERVAL_SECONDS * 1000,
             orphan_expire_interval_daa_score: DEFAULT_ORPHAN_EXPIRE_INTERVAL_SECONDS * 1000 / target_milliseconds_per_block,
             orphan_expire_scan_interval_daa_score: DEFAULT_ORPHAN_EXPIRE_SCAN_INTERVAL_SECONDS * 1000 / target_milliseconds_per_block,
+            fee_history_window_milliseconds: DEFAULT_FEE_HISTORY_WINDOW_SECONDS * 1000,
+            fee_history_max_records: DEFAULT_FEE_HISTORY_MAX_RECORDS,
             maximum_orphan_transaction_normalized_mass: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_NORMALIZED_MASS,
             maximum_orphan_transaction_count: DEFAULT_MAXIMUM_ORPHAN_TRANSACTION_COUNT,
             accept_non_standard: relay_non_std_transactions,
@@ -130,6 +145,7 @@ impl Config {
             block_lane_limits,
             minimum_relay_transaction_fee: DEFAULT_MINIMUM_RELAY_TRANSACTION_FEE,
             network_blocks_per_second: 1000 / target_milliseconds_per_block,
+            admission_policies: AdmissionPolicies::default(),
         }
     }
 
@@ -137,6 +153,12 @@ impl Config {
         // Allow only scaling down
         self.maximum_transaction_count = (self.maximum_transaction_count as f64 * ram_scale.min(1.0)) as usize;
         self.mempool_size_limit = (self.mempool_size_limit as f64 * ram_scale.min(1.0)) as usize;
+        self.fee_history_max_records = (self.fee_history_max_records as f64 * ram_scale.min(1.0)) as usize;
+        self
+    }
+
+    pub fn with_admission_policies(mut self, admission_policies: AdmissionPolicies) -> Self {
+        self.admission_policies = admission_policies;
         self
     }
 
@@ -10,7 +10,7 @@ use kaspa_consensus_core::{
     api::ConsensusApi,
     tx::{Transaction, TransactionId},
 };
-use kaspa_core::time::Stopwatch;
+use kaspa_core::time::{Stopwatch, unix_now};
 use std::{collections::HashSet, sync::atomic::Ordering};
 
 impl Mempool {
@@ -24,8 +24,13 @@ impl Mempool {
         let mut tx_accepted_counts = 0;
         let mut input_counts = 0;
         let mut output_counts = 0;
+        let now = unix_now();
         for transaction in block_transactions[1..].iter() {
             let transaction_id = transaction.id();
+            if let Some(tx) = self.transaction_pool.get(&transaction_id) {
+                let feerate = tx.feerate(&self.config.mempool_mass_cofactors.get(tx.added_at_daa_score));
+                self.confirmation_history.record(feerate, tx.added_at, now);
+            }
             // Rust rewrite: This behavior does differ from golang implementation.
             // If the transaction got accepted via a peer but is still an orphan here, do not remove
             // its redeemers in the orphan pool. We give those a chance to be unorphaned and included
@@ -1,6 +1,13 @@
 use crate::{
     MiningCounters,
-    feerate::{FeerateEstimator, FeerateEstimatorArgs},
+    block_template::{
+        builder::BlockTemplateCustomization,
+        diagnostics::{CandidateTransactionDiagnostics, FrontierSelectorKind, SelectionOutcome, SelectionTracer},
+    },
+    feerate::{
+        FeerateEstimator, FeerateEstimatorArgs, FeerateTarget,
+        history::{ConfirmationHistory, ConfirmationSamples, PendingObservation},
+    },
     model::{
         owner_txs::{GroupedOwnerTransactions, ScriptPublicKeySet},
         tx_query::TransactionQuery,
@@ -10,6 +17,7 @@ use crate::{
 use self::{
     config::Config,
     model::{accepted_transactions::AcceptedTransactions, orphan_pool::OrphanPool, pool::Pool, transactions_pool::TransactionsPool},
+    snapshot::MempoolEntrySnapshot,
     tx::Priority,
 };
 use kaspa_consensus_core::{
@@ -17,8 +25,8 @@ use kaspa_consensus_core::{
     config::params::ForkActivation,
     tx::{MutableTransaction, TransactionId},
 };
-use kaspa_core::time::Stopwatch;
-use std::sync::Arc;
+use kaspa_core::time::{Stopwatch, unix_now};
+use std::{collections::HashMap, sync::Arc};
 
 pub(crate) mod check_transaction_limits;
 pub(crate) mod check_transaction_standard;
@@ -26,9 +34,11 @@ pub mod config;
 pub mod errors;
 pub(crate) mod handle_new_block_transactions;
 pub(crate) mod model;
+pub mod policy;
 pub(crate) mod populate_entries_and_try_validate;
 pub(crate) mod remove_transaction;
 pub(crate) mod replace_by_fee;
+pub mod snapshot;
 pub(crate) mod validate_and_insert_transaction;
 
 /// Mempool contains transactions intended to be inserted into a block and mined.
@@ -54,6 +64,7 @@ pub(crate) struct Mempool {
     transaction_pool: TransactionsPool,
     orphan_pool: OrphanPool,
     accepted_transactions: AcceptedTransactions,
+    confirmation_history: ConfirmationHistory,
     counters: Arc<MiningCounters>,
 }
 
@@ -62,7 +73,8 @@ impl Mempool {
         let transaction_pool = TransactionsPool::new(config.clone());
         let orphan_pool = OrphanPool::new(config.clone());
         let accepted_transactions = AcceptedTransactions::new(config.clone());
-        Self { config, toccata_activation, transaction_pool, orphan_pool, accepted_transactions, counters }
+        let confirmation_history = ConfirmationHistory::new(config.fee_history_window_milliseconds, config.fee_history_max_records);
+        Self { config, toccata_activation, transaction_pool, orphan_pool, accepted_transactions, confirmation_history, counters }
     }
 
     pub(crate) fn get_transaction(&self, transaction_id: &TransactionId, query: TransactionQuery) -> Option<MutableTransaction> {
@@ -133,16 +145,75 @@ impl Mempool {
         self.transaction_pool.build_selector()
     }
 
+    pub(crate) fn build_customized_selector(
+        &self,
+        customization: &BlockTemplateCustomization,
+    ) -> Box<dyn TemplateTransactionSelector> {
+        let _sw = Stopwatch::<10>::with_threshold("build_customized_selector op");
+        self.transaction_pool.build_customized_selector(customization)
+    }
+
+    pub(crate) fn build_traced_selector(
+        &self,
+        tracer: SelectionTracer,
+        customization: &BlockTemplateCustomization,
+    ) -> (Box<dyn TemplateTransactionSelector>, FrontierSelectorKind) {
+        self.transaction_pool.build_traced_selector(tracer, customization)
+    }
+
+    pub(crate) fn get_candidate_diagnostics(
+        &self,
+        transaction_id: &TransactionId,
+        outcome: SelectionOutcome,
+    ) -> Option<CandidateTransactionDiagnostics> {
+        self.transaction_pool.get_candidate_diagnostics(transaction_id, outcome)
+    }
+
     /// Builds a feerate estimator based on internal state of the ready transactions frontier
     pub(crate) fn build_feerate_estimator(&self, args: FeerateEstimatorArgs) -> FeerateEstimator {
         self.transaction_pool.build_feerate_estimator(args)
     }
 
+    /// Copies the observed mempool waiting times for estimating the feerate required for meeting `targets`.
+    /// Transactions currently in the pool which already missed a target are accounted as failures.
+    pub(crate) fn feerate_history_samples(&self, targets: &[FeerateTarget]) -> ConfirmationSamples {
+        let _sw = Stopwatch::<50>::with_threshold("feerate_history_samples op");
+        let shortest_target = targets.iter().map(|target| target.seconds).min().unwrap_or_default();
+        let now = unix_now();
+        let pending =
+            self.transaction_pool.all().values().filter(|tx| now.saturating_sub(tx.added_at) > shortest_target * 1000).map(|tx| {
+                PendingObservation {
+                    feerate: tx.feerate(&self.config.mempool_mass_cofactors.get(tx.added_at_daa_score)),
+                    waiting_time: now.saturating_sub(tx.added_at),
+                }
+            });
+        self.confirmation_history.samples(pending)
+    }
+
     pub(crate) fn all_transaction_ids_with_priority(&self, priority: Priority) -> Vec<TransactionId> {
         let _sw = Stopwatch::<15>::with_threshold("all_transaction_ids_with_priority op");
         self.transaction_pool.all_transaction_ids_with_priority(priority)
     }
 
+    /// Returns a snapshot entry for every transaction of both the transaction pool and the orphan pool
+    pub(crate) fn export_entries(&self) -> Vec<MempoolEntrySnapshot> {
+        let _sw = Stopwatch::<100>::with_threshold("export_entries op");
+        self.transaction_pool
+            .all()
+            .values()
+            .chain(self.orphan_pool.all().values())
+            .map(|tx| MempoolEntrySnapshot { transaction: (*tx.mtx.tx).clone(), priority: tx.priority, added_at: tx.added_at })
+            .collect()
+    }
+
+    /// Sets back the time at which each listed transaction originally entered the mempool
+    pub(crate) fn restore_added_times(&mut self, added_times: &HashMap<TransactionId, u64>) {
+        for (transaction_id, added_at) in added_times.iter() {
+            self.transaction_pool.set_added_at(transaction_id, *added_at);
+            self.orphan_pool.set_added_at(transaction_id, *added_at);
+        }
+    }
+
     pub(crate) fn update_revalidated_transaction(&mut self, transaction: MutableTransaction) -> bool {
         self.transaction_pool.update_revalidated_transaction(transaction)
     }
@@ -169,7 +240,9 @@ impl Mempool {
 }
 
 pub mod tx {
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    use borsh::{BorshDeserialize, BorshSerialize};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
     pub enum Priority {
         /// Provenance is P2P
         Low,
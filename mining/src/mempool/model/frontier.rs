@@ -1,5 +1,6 @@
 use crate::{
     Policy,
+    block_template::diagnostics::FrontierSelectorKind,
     feerate::{FeerateEstimator, FeerateEstimatorArgs},
 };
 
@@ -354,14 +355,27 @@ impl Frontier {
     /// full transaction selection in less than 150 µs even if the frontier has 1M entries (!!). See mining/benches
     /// for more details.
     pub fn build_selector(&self, policy: &Policy) -> Box<dyn TemplateTransactionSelector> {
-        if self.total_mass <= policy.max_block_mass {
+        match self.selector_kind(policy) {
             // TakeAll can still filter by LPB/gas, so feed it best-first.
-            Box::new(TakeAllSelector::new(self.search_tree.descending_iter().map(|k| k.tx.clone()).collect(), policy.clone()))
+            FrontierSelectorKind::TakeAll => {
+                Box::new(TakeAllSelector::new(self.search_tree.descending_iter().map(|k| k.tx.clone()).collect(), policy.clone()))
+            }
+            FrontierSelectorKind::Sequence => {
+                let mut rng = rand::thread_rng();
+                Box::new(SequenceSelector::new(self.sample_inplace(&mut rng, policy, &mut 0), policy.clone()))
+            }
+            FrontierSelectorKind::MutatingTree => Box::new(MutatingTreeSelector::new(policy.clone(), self.search_tree.clone())),
+        }
+    }
+
+    /// Returns the kind of selector [`Self::build_selector`] builds for the current frontier state
+    pub fn selector_kind(&self, policy: &Policy) -> FrontierSelectorKind {
+        if self.total_mass <= policy.max_block_mass {
+            FrontierSelectorKind::TakeAll
         } else if self.total_mass > policy.max_block_mass * COLLISION_FACTOR {
-            let mut rng = rand::thread_rng();
-            Box::new(SequenceSelector::new(self.sample_inplace(&mut rng, policy, &mut 0), policy.clone()))
+            FrontierSelectorKind::Sequence
         } else {
-            Box::new(MutatingTreeSelector::new(policy.clone(), self.search_tree.clone()))
+            FrontierSelectorKind::MutatingTree
         }
     }
 
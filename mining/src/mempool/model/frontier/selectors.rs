@@ -1,7 +1,8 @@
 use super::{MAX_NULL_ATTEMPTS, feerate_key::FeerateTransactionKey, search_tree::SearchTree};
-use crate::Policy;
+use crate::{Policy, block_template::diagnostics::SelectionOutcome};
 use kaspa_consensus_core::{
     block::TemplateTransactionSelector,
+    errors::tx::TxRuleError,
     subnets::SubnetworkId,
     tx::{Transaction, TransactionId},
 };
@@ -17,7 +18,7 @@ use std::{
 /// weighted feerate.
 pub(crate) const ALPHA: i32 = 3;
 
-type LaneId = SubnetworkId;
+pub(crate) type LaneId = SubnetworkId;
 
 #[derive(Default)]
 struct LaneUsage {
@@ -26,36 +27,40 @@ struct LaneUsage {
 }
 
 #[derive(Default)]
-struct LaneSelectionState {
+pub(crate) struct LaneSelectionState {
     occupied: HashMap<LaneId, LaneUsage>,
 }
 
 impl LaneSelectionState {
     // LPB and gas are enforced during selection, but gas is intentionally not part of the
     // global feerate weight since gas capacity is lane-local.
-    fn try_select(&mut self, policy: &Policy, lane: LaneId, gas: u64) -> bool {
+    // On failure, returns the outcome describing the limit which was hit.
+    pub(crate) fn try_select(&mut self, policy: &Policy, lane: LaneId, gas: u64) -> Result<(), SelectionOutcome> {
         let occupied_len = self.occupied.len();
         match self.occupied.entry(lane) {
             Entry::Occupied(mut entry) => {
                 let usage = entry.get_mut();
                 if usage.gas.saturating_add(gas) > policy.gas_per_lane_limit {
-                    return false;
+                    return Err(SelectionOutcome::GasPerLaneLimit);
                 }
                 usage.tx_count += 1;
                 usage.gas += gas;
-                true
+                Ok(())
             }
             Entry::Vacant(entry) => {
-                if occupied_len >= policy.lanes_per_block_limit || gas > policy.gas_per_lane_limit {
-                    return false;
+                if occupied_len >= policy.lanes_per_block_limit {
+                    return Err(SelectionOutcome::LanesPerBlockLimit);
+                }
+                if gas > policy.gas_per_lane_limit {
+                    return Err(SelectionOutcome::GasPerLaneLimit);
                 }
                 entry.insert(LaneUsage { tx_count: 1, gas });
-                true
+                Ok(())
             }
         }
     }
 
-    fn reject(&mut self, lane: LaneId, gas: u64) {
+    pub(crate) fn reject(&mut self, lane: LaneId, gas: u64) {
         let usage = self.occupied.get_mut(&lane).expect("previously selected txs occupy a lane");
         usage.tx_count -= 1;
         usage.gas -= gas;
@@ -166,11 +171,14 @@ impl TemplateTransactionSelector for SequenceSelector {
             if self.total_selected_mass.saturating_add(tx.mass) > self.policy.max_block_mass {
                 // We assume the sequence is relatively small, hence we keep on searching
                 // for transactions with lower mass which might fit into the remaining gap
+                self.policy.trace(tx.tx.id(), SelectionOutcome::BlockMassLimit);
                 continue;
             }
-            if !self.lanes.try_select(&self.policy, tx.tx.subnetwork_id, tx.tx.gas) {
+            if let Err(outcome) = self.lanes.try_select(&self.policy, tx.tx.subnetwork_id, tx.tx.gas) {
+                self.policy.trace(tx.tx.id(), outcome);
                 continue;
             }
+            self.policy.trace(tx.tx.id(), SelectionOutcome::Selected);
             self.total_selected_mass += tx.mass;
             self.selected_vec.push(SequenceSelectorSelection {
                 tx_id: tx.tx.id(),
@@ -196,6 +204,10 @@ impl TemplateTransactionSelector for SequenceSelector {
         self.overall_rejections += 1;
     }
 
+    fn report_invalid_transaction(&mut self, tx_id: TransactionId, error: &TxRuleError) {
+        self.policy.trace(tx_id, SelectionOutcome::Invalid(error.to_string()));
+    }
+
     fn is_successful(&self) -> bool {
         const SUFFICIENT_MASS_THRESHOLD: f64 = 0.8;
         const LOW_REJECTION_FRACTION: f64 = 0.2;
@@ -230,7 +242,16 @@ impl TemplateTransactionSelector for TakeAllSelector {
         let mut lanes = LaneSelectionState::default();
         self.txs
             .drain(..)
-            .filter_map(|tx| if lanes.try_select(&self.policy, tx.subnetwork_id, tx.gas) { Some(tx.as_ref().clone()) } else { None })
+            .filter_map(|tx| match lanes.try_select(&self.policy, tx.subnetwork_id, tx.gas) {
+                Ok(()) => {
+                    self.policy.trace(tx.id(), SelectionOutcome::Selected);
+                    Some(tx.as_ref().clone())
+                }
+                Err(outcome) => {
+                    self.policy.trace(tx.id(), outcome);
+                    None
+                }
+            })
             .collect()
     }
 
@@ -238,6 +259,10 @@ impl TemplateTransactionSelector for TakeAllSelector {
         // No need to track rejections (for reduced mass), since there's nothing else to select
     }
 
+    fn report_invalid_transaction(&mut self, tx_id: TransactionId, error: &TxRuleError) {
+        self.policy.trace(tx_id, SelectionOutcome::Invalid(error.to_string()));
+    }
+
     fn is_successful(&self) -> bool {
         // Considered successful because we provided all mempool transactions to this
         // selector, so there's no point in retries
@@ -325,18 +350,21 @@ impl TemplateTransactionSelector for MutatingTreeSelector {
             let lane = candidate.lane();
 
             if candidate.mass > self.gap {
+                self.policy.trace(tx.id(), SelectionOutcome::BlockMassLimit);
                 self.mass_deferred.push(candidate);
                 mass_null_attempts += 1;
                 continue;
             }
 
-            if !self.lanes.try_select(&self.policy, lane, tx.gas) {
+            if let Err(outcome) = self.lanes.try_select(&self.policy, lane, tx.gas) {
                 // For now we compromise on retry optimality in this less-congested path:
                 // LPB/gas-skipped candidates are not reconsidered after later rejections
                 // (tx rejections are expected to be rare here).
+                self.policy.trace(tx.id(), outcome);
                 continue;
             }
 
+            self.policy.trace(tx.id(), SelectionOutcome::Selected);
             self.gap -= candidate.mass;
             self.selected_vec.push(TreeSelectorSelection { tx_id: tx.id(), mass: candidate.mass, lane, gas: tx.gas });
             transactions.push(tx.clone());
@@ -357,6 +385,10 @@ impl TemplateTransactionSelector for MutatingTreeSelector {
         self.overall_rejections += 1;
     }
 
+    fn report_invalid_transaction(&mut self, tx_id: TransactionId, error: &TxRuleError) {
+        self.policy.trace(tx_id, SelectionOutcome::Invalid(error.to_string()));
+    }
+
     fn is_successful(&self) -> bool {
         const SUFFICIENT_MASS_THRESHOLD: f64 = 0.8;
         const LOW_REJECTION_FRACTION: f64 = 0.2;
@@ -371,6 +403,7 @@ impl TemplateTransactionSelector for MutatingTreeSelector {
 mod tests {
     use super::super::feerate_key::FeerateTransactionKey;
     use super::*;
+    use crate::block_template::diagnostics::SelectionTracer;
     use kaspa_consensus_core::{
         config::constants::consensus::{DEFAULT_GAS_PER_LANE_LIMIT, DEFAULT_LANES_PER_BLOCK_LIMIT},
         mass::BlockLaneLimits,
@@ -409,6 +442,29 @@ mod tests {
         assert_eq!(selected.len(), 1);
     }
 
+    #[test]
+    fn test_traced_selector_records_lane_limit_outcomes() {
+        let tracer = SelectionTracer::default();
+        let txs = vec![tx(1, lane(1), 6), tx(2, lane(1), 6), tx(3, lane(2), 1), tx(4, lane(3), 1), tx(5, lane(4), 11)];
+        let ids = txs.iter().map(|tx| tx.id()).collect::<Vec<_>>();
+        let mut selector = TakeAllSelector::new(txs, policy().with_tracer(tracer.clone()));
+        assert_eq!(selector.select_transactions().len(), 2);
+        selector.report_invalid_transaction(ids[2], &TxRuleError::MissingTxOutpoints);
+        selector.reject_selection(ids[2]);
+
+        let outcomes = std::mem::take(&mut *tracer.lock()).into_outcomes();
+        assert_eq!(
+            outcomes,
+            vec![
+                (ids[0], SelectionOutcome::Selected),
+                (ids[1], SelectionOutcome::GasPerLaneLimit),
+                (ids[2], SelectionOutcome::Invalid(TxRuleError::MissingTxOutpoints.to_string())),
+                (ids[3], SelectionOutcome::LanesPerBlockLimit),
+                (ids[4], SelectionOutcome::LanesPerBlockLimit),
+            ]
+        );
+    }
+
     #[test]
     fn test_sequence_selector_respects_gas_limit_and_releases_on_reject() {
         let lane = lane(1);
@@ -465,7 +521,7 @@ mod tests {
         assert_eq!(selector.mass_deferred.len(), 1);
 
         selector.gap = 500;
-        assert!(selector.lanes.try_select(&selector.policy, lane, 0));
+        assert!(selector.lanes.try_select(&selector.policy, lane, 0).is_ok());
         selector.selected_vec.push(TreeSelectorSelection { tx_id: rejected_tx.id(), mass: 500, lane, gas: 0 });
         selector.reject_selection(rejected_tx.id());
 
@@ -495,7 +551,7 @@ mod tests {
         assert_eq!(selector.mass_deferred.len(), 1);
 
         selector.gap = 500;
-        assert!(selector.lanes.try_select(&selector.policy, lane, 0));
+        assert!(selector.lanes.try_select(&selector.policy, lane, 0).is_ok());
         selector.selected_vec.push(TreeSelectorSelection { tx_id: rejected_tx.id(), mass: 300, lane, gas: 0 });
         selector.reject_selection(rejected_tx.id());
 
@@ -263,6 +263,13 @@ impl OrphanPool {
         &mut self.chained_orphans
     }
 
+    /// Overrides the time at which the transaction entered the pool, if present
+    pub(crate) fn set_added_at(&mut self, transaction_id: &TransactionId, added_at: u64) {
+        if let Some(transaction) = self.all_orphans.get_mut(transaction_id) {
+            transaction.added_at = added_at;
+        }
+    }
+
     pub(crate) fn expire_low_priority_transactions(&mut self, virtual_daa_score: u64) -> RuleResult<()> {
         if virtual_daa_score < self.last_expire_scan + self.config.orphan_expire_scan_interval_daa_score {
             return Ok(());
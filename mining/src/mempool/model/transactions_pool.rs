@@ -1,5 +1,9 @@
 use crate::{
     Policy,
+    block_template::{
+        builder::{BlockTemplateCustomization, CustomizedTransactionSelector},
+        diagnostics::{CandidateTransactionDiagnostics, FrontierSelectorKind, SelectionOutcome, SelectionTracer},
+    },
     feerate::{FeerateEstimator, FeerateEstimatorArgs},
     mempool::{
         config::Config,
@@ -20,7 +24,7 @@ use kaspa_consensus_core::{
 };
 use kaspa_core::{debug, time::unix_now, trace};
 use std::{
-    collections::{hash_map::Keys, hash_set::Iter},
+    collections::{HashSet, hash_map::Keys, hash_set::Iter},
     iter::once,
     sync::Arc,
 };
@@ -207,11 +211,106 @@ impl TransactionsPool {
         self.ready_transactions.total_mass()
     }
 
+    fn block_template_policy(&self) -> Policy {
+        // Params::mempool_block_mass_cofactors asserts that the reference mass is stable across activation.
+        Policy::new(self.config.mempool_mass_cofactors.after().reference, self.config.block_lane_limits)
+    }
+
     /// Dynamically builds a transaction selector based on the specific state of the ready transactions frontier
     pub(crate) fn build_selector(&self) -> Box<dyn TemplateTransactionSelector> {
-        self.ready_transactions
-            // Params::mempool_block_mass_cofactors asserts that the reference mass is stable across activation.
-            .build_selector(&Policy::new(self.config.mempool_mass_cofactors.after().reference, self.config.block_lane_limits))
+        self.ready_transactions.build_selector(&self.block_template_policy())
+    }
+
+    /// Builds a transaction selector applying `customization` which records the outcome of every candidate to `tracer`.
+    /// Also returns the kind of the frontier selector the selection relies on.
+    pub(crate) fn build_traced_selector(
+        &self,
+        tracer: SelectionTracer,
+        customization: &BlockTemplateCustomization,
+    ) -> (Box<dyn TemplateTransactionSelector>, FrontierSelectorKind) {
+        let policy = self.block_template_policy().with_tracer(tracer);
+        if customization.is_empty() {
+            (self.ready_transactions.build_selector(&policy), self.ready_transactions.selector_kind(&policy))
+        } else {
+            self.build_customized_selector_with_policy(policy, customization)
+        }
+    }
+
+    /// Returns the diagnostics of a traced candidate, or `None` if it is no longer in the pool
+    pub(crate) fn get_candidate_diagnostics(
+        &self,
+        transaction_id: &TransactionId,
+        outcome: SelectionOutcome,
+    ) -> Option<CandidateTransactionDiagnostics> {
+        let tx = self.get(transaction_id)?;
+        let cofactors = self.config.mempool_mass_cofactors.get(tx.added_at_daa_score);
+        Some(CandidateTransactionDiagnostics::new(tx, &cofactors, outcome))
+    }
+
+    /// Builds a transaction selector applying `customization` over the ready transactions frontier selection
+    pub(crate) fn build_customized_selector(
+        &self,
+        customization: &BlockTemplateCustomization,
+    ) -> Box<dyn TemplateTransactionSelector> {
+        self.build_customized_selector_with_policy(self.block_template_policy(), customization).0
+    }
+
+    fn build_customized_selector_with_policy(
+        &self,
+        policy: Policy,
+        customization: &BlockTemplateCustomization,
+    ) -> (Box<dyn TemplateTransactionSelector>, FrontierSelectorKind) {
+        let excluded = customization.excluded_transaction_ids.iter().copied().collect::<HashSet<_>>();
+        let priority_groups = customization
+            .priority_transaction_ids
+            .iter()
+            .filter_map(|transaction_id| self.get_transaction_with_ancestors(transaction_id, &excluded))
+            .map(|group| {
+                group
+                    .into_iter()
+                    .map(|tx| {
+                        let cofactors = self.config.mempool_mass_cofactors.get(tx.added_at_daa_score);
+                        (tx.mtx.tx.clone(), FeerateTransactionKey::from_tx(tx, &cofactors).mass)
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut selector_kind = None;
+        let selector = Box::new(CustomizedTransactionSelector::new(policy, customization, priority_groups, |policy| {
+            selector_kind = Some(self.ready_transactions.selector_kind(policy));
+            self.ready_transactions.build_selector(policy)
+        }));
+        (selector, selector_kind.expect("the inner selector is built along with the customized selector"))
+    }
+
+    /// Returns the transaction preceded by all its ancestors in the pool, in topological order.
+    /// Returns `None` if the transaction is missing or if any of these transactions is in `excluded`.
+    fn get_transaction_with_ancestors(
+        &self,
+        transaction_id: &TransactionId,
+        excluded: &HashSet<TransactionId>,
+    ) -> Option<Vec<&MempoolTransaction>> {
+        let mut ordered = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![(*transaction_id, false)];
+        while let Some((id, expanded)) = stack.pop() {
+            if expanded {
+                // All ancestors of `id` were pushed after it, so they are already ordered
+                ordered.push(self.all_transactions.get(&id)?);
+                continue;
+            }
+            if excluded.contains(&id) {
+                return None;
+            }
+            if !visited.insert(id) {
+                continue;
+            }
+            stack.push((id, true));
+            if let Some(parents) = self.parent_transactions.get(&id) {
+                stack.extend(parents.iter().filter(|parent| !visited.contains(*parent)).map(|parent| (*parent, false)));
+            }
+        }
+        Some(ordered)
     }
 
     /// Builds a feerate estimator based on internal state of the ready transactions frontier
@@ -295,6 +394,13 @@ impl TransactionsPool {
         self.all().values().filter_map(|x| if x.priority == priority { Some(x.id()) } else { None }).collect()
     }
 
+    /// Overrides the time at which the transaction entered the pool, if present
+    pub(crate) fn set_added_at(&mut self, transaction_id: &TransactionId, added_at: u64) {
+        if let Some(transaction) = self.all_transactions.get_mut(transaction_id) {
+            transaction.added_at = added_at;
+        }
+    }
+
     pub(crate) fn get_outpoint_owner_id(&self, outpoint: &TransactionOutpoint) -> Option<&TransactionId> {
         self.utxo_set.get_outpoint_owner_id(outpoint)
     }
@@ -3,6 +3,7 @@ use kaspa_consensus_core::{
     mass::MassCofactors,
     tx::{MutableTransaction, Transaction, TransactionId, TransactionOutpoint},
 };
+use kaspa_core::time::unix_now;
 use kaspa_mining_errors::mempool::RuleError;
 use std::{
     fmt::{Display, Formatter},
@@ -13,12 +14,14 @@ pub(crate) struct MempoolTransaction {
     pub(crate) mtx: MutableTransaction,
     pub(crate) priority: Priority,
     pub(crate) added_at_daa_score: u64,
+    /// Unix time in milliseconds at which the transaction entered the pool
+    pub(crate) added_at: u64,
 }
 
 impl MempoolTransaction {
     pub(crate) fn new(mtx: MutableTransaction, priority: Priority, added_at_daa_score: u64) -> Self {
         assert_eq!(mtx.tx.inputs.len(), mtx.entries.len());
-        Self { mtx, priority, added_at_daa_score }
+        Self { mtx, priority, added_at_daa_score, added_at: unix_now() }
     }
 
     pub(crate) fn id(&self) -> TransactionId {
@@ -0,0 +1,470 @@
+//! Operator defined admission policies.
+//!
+//! Policies run on fully populated transactions right after the standardness checks and may either
+//! reject a transaction or deprioritize it. A deprioritized transaction is admitted with low priority,
+//! so it expires like any P2P transaction and is never rebroadcast by the node.
+//!
+//! Policies are usually built from an [`AdmissionPolicyConfig`] deserialized from an operator file, e.g.
+//!
+//! ```toml
+//! [script_class]
+//! classes = ["pubkeyecdsa"]
+//! action = "deprioritize"
+//!
+//! [payload]
+//! max_size = 1024
+//!
+//! [zk_precompile]
+//! action = "reject"
+//!
+//! [[address_list]]
+//! file = "denied-addresses.txt"
+//! mode = "deny"
+//! ```
+
+use super::tx::Priority;
+use kaspa_addresses::{Address, Prefix};
+use kaspa_consensus_core::{
+    hashing::sighash::SigHashReusedValuesUnsync,
+    mass::ScriptUnits,
+    tx::{MutableTransaction, PopulatedTransaction, ScriptPublicKey},
+};
+use kaspa_hashes::Hash;
+use kaspa_txscript::{get_zk_script_units_upper_bound, pay_to_address_script, script_class::ScriptClass};
+use serde::Deserialize;
+use std::{
+    collections::HashSet,
+    fmt::{Debug, Formatter},
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+
+/// Outcome of an admission policy check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyVerdict {
+    Accept,
+    /// Admit the transaction with low priority
+    Deprioritize,
+    /// Reject the transaction for the given reason
+    Reject(String),
+}
+
+pub trait AdmissionPolicy: Send + Sync {
+    /// Short name identifying the policy in rejection reasons
+    fn name(&self) -> &'static str;
+
+    /// Judges a transaction whose UTXO entries are all populated
+    fn check(&self, transaction: &MutableTransaction, priority: Priority) -> PolicyVerdict;
+}
+
+/// The ordered set of admission policies applied by the mempool
+#[derive(Clone, Default)]
+pub struct AdmissionPolicies(Vec<Arc<dyn AdmissionPolicy>>);
+
+impl AdmissionPolicies {
+    pub fn new(policies: Vec<Arc<dyn AdmissionPolicy>>) -> Self {
+        Self(policies)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &Arc<dyn AdmissionPolicy>> {
+        self.0.iter()
+    }
+}
+
+impl Debug for AdmissionPolicies {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.0.iter().map(|policy| policy.name())).finish()
+    }
+}
+
+/// Action taken by a policy on a matching transaction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyAction {
+    #[default]
+    Reject,
+    Deprioritize,
+}
+
+impl PolicyAction {
+    fn verdict(self, reason: impl FnOnce() -> String) -> PolicyVerdict {
+        match self {
+            PolicyAction::Reject => PolicyVerdict::Reject(reason()),
+            PolicyAction::Deprioritize => PolicyVerdict::Deprioritize,
+        }
+    }
+}
+
+/// Returns the script public keys of all inputs and outputs of a populated transaction
+fn script_public_keys(transaction: &MutableTransaction) -> impl Iterator<Item = &ScriptPublicKey> {
+    transaction
+        .entries
+        .iter()
+        .flatten()
+        .map(|entry| &entry.script_public_key)
+        .chain(transaction.tx.outputs.iter().map(|output| &output.script_public_key))
+}
+
+/// Matches transactions spending or paying to scripts of the given classes
+pub struct ScriptClassPolicy {
+    classes: HashSet<ScriptClass>,
+    action: PolicyAction,
+}
+
+impl ScriptClassPolicy {
+    pub fn new(classes: HashSet<ScriptClass>, action: PolicyAction) -> Self {
+        Self { classes, action }
+    }
+}
+
+impl AdmissionPolicy for ScriptClassPolicy {
+    fn name(&self) -> &'static str {
+        "script-class"
+    }
+
+    fn check(&self, transaction: &MutableTransaction, _priority: Priority) -> PolicyVerdict {
+        match script_public_keys(transaction).map(ScriptClass::from_script).find(|class| self.classes.contains(class)) {
+            Some(class) => self.action.verdict(|| format!("script class {class} is not allowed")),
+            None => PolicyVerdict::Accept,
+        }
+    }
+}
+
+/// Matches transactions carrying a payload larger than a given size
+pub struct PayloadSizePolicy {
+    max_size: usize,
+    action: PolicyAction,
+}
+
+impl PayloadSizePolicy {
+    pub fn new(max_size: usize, action: PolicyAction) -> Self {
+        Self { max_size, action }
+    }
+}
+
+impl AdmissionPolicy for PayloadSizePolicy {
+    fn name(&self) -> &'static str {
+        "payload-size"
+    }
+
+    fn check(&self, transaction: &MutableTransaction, _priority: Priority) -> PolicyVerdict {
+        let size = transaction.tx.payload.len();
+        if size > self.max_size {
+            self.action.verdict(|| format!("payload of {size} bytes is larger than {} bytes", self.max_size))
+        } else {
+            PolicyVerdict::Accept
+        }
+    }
+}
+
+/// Matches transactions spending or creating outputs bound to the given covenants
+pub struct CovenantPolicy {
+    covenant_ids: HashSet<Hash>,
+    action: PolicyAction,
+}
+
+impl CovenantPolicy {
+    pub fn new(covenant_ids: HashSet<Hash>, action: PolicyAction) -> Self {
+        Self { covenant_ids, action }
+    }
+}
+
+impl AdmissionPolicy for CovenantPolicy {
+    fn name(&self) -> &'static str {
+        "covenant"
+    }
+
+    fn check(&self, transaction: &MutableTransaction, _priority: Priority) -> PolicyVerdict {
+        let mut covenant_ids = transaction
+            .entries
+            .iter()
+            .flatten()
+            .filter_map(|entry| entry.covenant_id)
+            .chain(transaction.tx.outputs.iter().filter_map(|output| output.covenant.map(|binding| binding.covenant_id)));
+        match covenant_ids.find(|id| self.covenant_ids.contains(id)) {
+            Some(id) => self.action.verdict(|| format!("covenant {id} is not allowed")),
+            None => PolicyVerdict::Accept,
+        }
+    }
+}
+
+/// Matches transactions with at least one input running a ZK precompile
+pub struct ZkPrecompilePolicy {
+    action: PolicyAction,
+}
+
+impl ZkPrecompilePolicy {
+    pub fn new(action: PolicyAction) -> Self {
+        Self { action }
+    }
+}
+
+impl AdmissionPolicy for ZkPrecompilePolicy {
+    fn name(&self) -> &'static str {
+        "zk-precompile"
+    }
+
+    fn check(&self, transaction: &MutableTransaction, _priority: Priority) -> PolicyVerdict {
+        let uses_zk = transaction.tx.inputs.iter().zip(transaction.entries.iter().flatten()).position(|(input, entry)| {
+            get_zk_script_units_upper_bound::<PopulatedTransaction, SigHashReusedValuesUnsync>(
+                &input.signature_script,
+                &entry.script_public_key,
+            ) > ScriptUnits(0)
+        });
+        match uses_zk {
+            Some(i) => self.action.verdict(|| format!("input #{i} uses a ZK precompile")),
+            None => PolicyVerdict::Accept,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AddressListMode {
+    /// Only transactions spending from or paying to at least one listed address pass
+    Allow,
+    /// Transactions spending from or paying to any listed address are matched
+    Deny,
+}
+
+/// Matches transactions by the addresses they spend from and pay to
+pub struct AddressListPolicy {
+    script_public_keys: HashSet<ScriptPublicKey>,
+    mode: AddressListMode,
+    action: PolicyAction,
+}
+
+impl AddressListPolicy {
+    pub fn new(addresses: &[Address], mode: AddressListMode, action: PolicyAction) -> Self {
+        Self { script_public_keys: addresses.iter().map(pay_to_address_script).collect(), mode, action }
+    }
+
+    /// Loads the addresses from a file holding one address per line. Empty lines and lines starting with `#` are ignored.
+    pub fn from_file(path: &Path, prefix: Prefix, mode: AddressListMode, action: PolicyAction) -> Result<Self, PolicyConfigError> {
+        let content = fs::read_to_string(path).map_err(|err| PolicyConfigError::Io(path.to_path_buf(), err.to_string()))?;
+        let addresses = content
+            .lines()
+            .map(str::trim)
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| match Address::try_from(line) {
+                Ok(address) if address.prefix == prefix => Ok(address),
+                Ok(_) => Err(PolicyConfigError::AddressNetwork(path.to_path_buf(), i + 1, line.to_string())),
+                Err(err) => Err(PolicyConfigError::Address(path.to_path_buf(), i + 1, err.to_string())),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(&addresses, mode, action))
+    }
+}
+
+impl AdmissionPolicy for AddressListPolicy {
+    fn name(&self) -> &'static str {
+        match self.mode {
+            AddressListMode::Allow => "address-allow-list",
+            AddressListMode::Deny => "address-deny-list",
+        }
+    }
+
+    fn check(&self, transaction: &MutableTransaction, _priority: Priority) -> PolicyVerdict {
+        let listed = script_public_keys(transaction).any(|spk| self.script_public_keys.contains(spk));
+        match (self.mode, listed) {
+            (AddressListMode::Allow, false) => self.action.verdict(|| "no listed address is involved".to_string()),
+            (AddressListMode::Deny, true) => self.action.verdict(|| "a denied address is involved".to_string()),
+            _ => PolicyVerdict::Accept,
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PolicyConfigError {
+    #[error("invalid script class {0}")]
+    ScriptClass(String),
+
+    #[error("invalid covenant id {0}")]
+    CovenantId(String),
+
+    #[error("failed reading {0}: {1}")]
+    Io(PathBuf, String),
+
+    #[error("{0}, line {1}: {2}")]
+    Address(PathBuf, usize, String),
+
+    #[error("{0}, line {1}: address {2} does not belong to the current network")]
+    AddressNetwork(PathBuf, usize, String),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScriptClassPolicyConfig {
+    /// Script class names, e.g. `pubkeyecdsa` or `scripthash`
+    pub classes: Vec<String>,
+    #[serde(default)]
+    pub action: PolicyAction,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PayloadPolicyConfig {
+    pub max_size: usize,
+    #[serde(default)]
+    pub action: PolicyAction,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CovenantPolicyConfig {
+    /// Hex encoded covenant ids
+    pub covenant_ids: Vec<String>,
+    #[serde(default)]
+    pub action: PolicyAction,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ZkPrecompilePolicyConfig {
+    #[serde(default)]
+    pub action: PolicyAction,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AddressListPolicyConfig {
+    pub file: PathBuf,
+    pub mode: AddressListMode,
+    #[serde(default)]
+    pub action: PolicyAction,
+}
+
+/// Operator configuration of the built-in admission policies
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AdmissionPolicyConfig {
+    pub script_class: Option<ScriptClassPolicyConfig>,
+    pub payload: Option<PayloadPolicyConfig>,
+    pub covenant: Option<CovenantPolicyConfig>,
+    pub zk_precompile: Option<ZkPrecompilePolicyConfig>,
+    pub address_list: Vec<AddressListPolicyConfig>,
+}
+
+impl AdmissionPolicyConfig {
+    /// Builds the configured policies, loading address lists from disk. Addresses must match `prefix`.
+    pub fn build(&self, prefix: Prefix) -> Result<AdmissionPolicies, PolicyConfigError> {
+        let mut policies: Vec<Arc<dyn AdmissionPolicy>> = vec![];
+        if let Some(config) = &self.script_class {
+            let classes = config
+                .classes
+                .iter()
+                .map(|class| class.parse().map_err(|_| PolicyConfigError::ScriptClass(class.clone())))
+                .collect::<Result<_, _>>()?;
+            policies.push(Arc::new(ScriptClassPolicy::new(classes, config.action)));
+        }
+        if let Some(config) = &self.payload {
+            policies.push(Arc::new(PayloadSizePolicy::new(config.max_size, config.action)));
+        }
+        if let Some(config) = &self.covenant {
+            let covenant_ids = config
+                .covenant_ids
+                .iter()
+                .map(|id| id.parse().map_err(|_| PolicyConfigError::CovenantId(id.clone())))
+                .collect::<Result<_, _>>()?;
+            policies.push(Arc::new(CovenantPolicy::new(covenant_ids, config.action)));
+        }
+        if let Some(config) = &self.zk_precompile {
+            policies.push(Arc::new(ZkPrecompilePolicy::new(config.action)));
+        }
+        for config in self.address_list.iter() {
+            policies.push(Arc::new(AddressListPolicy::from_file(&config.file, prefix, config.mode, config.action)?));
+        }
+        Ok(AdmissionPolicies::new(policies))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::Version;
+    use kaspa_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{CovenantBinding, Transaction, TransactionInput, TransactionOutpoint, TransactionOutput, UtxoEntry},
+    };
+
+    fn populated_transaction(input_address: &Address, output_address: &Address, payload: Vec<u8>) -> MutableTransaction {
+        let input = TransactionInput::new(TransactionOutpoint::new(Hash::from_u64_word(1), 0), vec![], 0, 1);
+        let output = TransactionOutput::new(1000, pay_to_address_script(output_address));
+        let tx = Transaction::new(0, vec![input], vec![output], 0, SUBNETWORK_ID_NATIVE, 0, payload);
+        let entry = UtxoEntry::new(2000, pay_to_address_script(input_address), 0, false, None);
+        MutableTransaction::with_entries(Arc::new(tx), vec![entry])
+    }
+
+    #[test]
+    fn test_builtin_policies() {
+        let alice = Address::new(Prefix::Mainnet, Version::PubKey, &[1u8; 32]);
+        let bob = Address::new(Prefix::Mainnet, Version::PubKeyECDSA, &[2u8; 33]);
+        let carol = Address::new(Prefix::Mainnet, Version::PubKey, &[3u8; 32]);
+        let transaction = populated_transaction(&alice, &bob, vec![0; 100]);
+
+        let policy = ScriptClassPolicy::new(HashSet::from([ScriptClass::PubKeyECDSA]), PolicyAction::Deprioritize);
+        assert_eq!(policy.check(&transaction, Priority::High), PolicyVerdict::Deprioritize);
+        let policy = ScriptClassPolicy::new(HashSet::from([ScriptClass::ScriptHash]), PolicyAction::Reject);
+        assert_eq!(policy.check(&transaction, Priority::High), PolicyVerdict::Accept);
+
+        assert!(matches!(
+            PayloadSizePolicy::new(99, PolicyAction::Reject).check(&transaction, Priority::Low),
+            PolicyVerdict::Reject(_)
+        ));
+        assert_eq!(PayloadSizePolicy::new(100, PolicyAction::Reject).check(&transaction, Priority::Low), PolicyVerdict::Accept);
+
+        // Input addresses are matched as well as output ones
+        let deny = AddressListPolicy::new(&[alice.clone()], AddressListMode::Deny, PolicyAction::Reject);
+        assert!(matches!(deny.check(&transaction, Priority::Low), PolicyVerdict::Reject(_)));
+        let allow = AddressListPolicy::new(&[carol.clone()], AddressListMode::Allow, PolicyAction::Reject);
+        assert!(matches!(allow.check(&transaction, Priority::Low), PolicyVerdict::Reject(_)));
+        let allow = AddressListPolicy::new(&[carol, bob], AddressListMode::Allow, PolicyAction::Reject);
+        assert_eq!(allow.check(&transaction, Priority::Low), PolicyVerdict::Accept);
+
+        let covenant_id = Hash::from_u64_word(42);
+        let policy = CovenantPolicy::new(HashSet::from([covenant_id]), PolicyAction::Reject);
+        assert_eq!(policy.check(&transaction, Priority::Low), PolicyVerdict::Accept);
+        let mut bound_transaction = transaction.clone();
+        let mut tx = (*bound_transaction.tx).clone();
+        tx.outputs[0].covenant = Some(CovenantBinding::new(0, covenant_id));
+        bound_transaction.tx = Arc::new(tx);
+        assert!(matches!(policy.check(&bound_transaction, Priority::Low), PolicyVerdict::Reject(_)));
+
+        assert_eq!(ZkPrecompilePolicy::new(PolicyAction::Reject).check(&transaction, Priority::Low), PolicyVerdict::Accept);
+    }
+
+    #[test]
+    fn test_policy_config_build() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("addresses.txt");
+        let address = Address::new(Prefix::Testnet, Version::PubKey, &[1u8; 32]);
+        fs::write(&file, format!("# denied\n\n{address}\n")).unwrap();
+
+        let config = AdmissionPolicyConfig {
+            script_class: Some(ScriptClassPolicyConfig { classes: vec!["pubkeyecdsa".into()], action: PolicyAction::Deprioritize }),
+            zk_precompile: Some(ZkPrecompilePolicyConfig { action: PolicyAction::Reject }),
+            address_list: vec![AddressListPolicyConfig {
+                file: file.clone(),
+                mode: AddressListMode::Deny,
+                action: PolicyAction::Reject,
+            }],
+            ..Default::default()
+        };
+        let policies = config.build(Prefix::Testnet).unwrap();
+        assert_eq!(format!("{policies:?}"), r#"["script-class", "zk-precompile", "address-deny-list"]"#);
+
+        assert!(matches!(config.build(Prefix::Mainnet), Err(PolicyConfigError::AddressNetwork(_, 3, _))));
+        let invalid = AdmissionPolicyConfig {
+            script_class: Some(ScriptClassPolicyConfig { classes: vec!["p2pkh".into()], action: PolicyAction::Reject }),
+            ..Default::default()
+        };
+        assert_eq!(invalid.build(Prefix::Testnet).unwrap_err(), PolicyConfigError::ScriptClass("p2pkh".into()));
+    }
+}
@@ -0,0 +1,121 @@
+//! On-disk snapshot of the mempool content, allowing a node to recover its pending transactions after a restart.
+//!
+//! The file starts with a magic tag and a format version followed by the borsh encoded entries.
+//! Entries are revalidated against the consensus state when imported, so a stale snapshot is harmless.
+
+use super::tx::Priority;
+use borsh::{BorshDeserialize, BorshSerialize};
+use kaspa_consensus_core::tx::Transaction;
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+const MAGIC: [u8; 4] = *b"KMPL";
+const VERSION: u16 = 1;
+
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct MempoolEntrySnapshot {
+    pub transaction: Transaction,
+    pub priority: Priority,
+    /// Unix time in milliseconds at which the transaction entered the mempool
+    pub added_at: u64,
+}
+
+#[derive(Debug, Clone, Default, BorshSerialize, BorshDeserialize)]
+pub struct MempoolSnapshot {
+    /// Transactions of both the transaction pool and the orphan pool
+    pub entries: Vec<MempoolEntrySnapshot>,
+}
+
+impl MempoolSnapshot {
+    pub fn new(entries: Vec<MempoolEntrySnapshot>) -> Self {
+        Self { entries }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Writes the snapshot to `path`, replacing any previous file atomically
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+            writer.write_all(&MAGIC)?;
+            VERSION.serialize(&mut writer)?;
+            self.serialize(&mut writer)?;
+            writer.into_inner().map_err(|err| err.into_error())?.sync_all()?;
+        }
+        fs::rename(tmp_path, path)
+    }
+
+    /// Reads a snapshot previously written by [`Self::save`]
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not a mempool snapshot file"));
+        }
+        let version = u16::deserialize_reader(&mut reader)?;
+        if version != VERSION {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, format!("unsupported mempool snapshot version {version}")));
+        }
+        let mut snapshot = Self::deserialize_reader(&mut reader)?;
+        // The cached transaction ids are not trusted
+        snapshot.entries.iter_mut().for_each(|entry| entry.transaction.finalize());
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::{ScriptPublicKey, TransactionInput, TransactionOutpoint, TransactionOutput},
+    };
+    use kaspa_hashes::Hash;
+
+    #[test]
+    fn test_snapshot_save_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mempool.dat");
+
+        let entries = (0..3u64)
+            .map(|i| MempoolEntrySnapshot {
+                transaction: Transaction::new(
+                    0,
+                    vec![TransactionInput::new(TransactionOutpoint::new(Hash::from_u64_word(i), 0), vec![1, 2, 3], 0, 1)],
+                    vec![TransactionOutput::new(1000 + i, ScriptPublicKey::from_vec(0, vec![0xac]))],
+                    0,
+                    SUBNETWORK_ID_NATIVE,
+                    0,
+                    vec![],
+                ),
+                priority: if i == 0 { Priority::High } else { Priority::Low },
+                added_at: 1_700_000_000_000 + i,
+            })
+            .collect::<Vec<_>>();
+        MempoolSnapshot::new(entries.clone()).save(&path).unwrap();
+        assert!(!path.with_extension("tmp").exists());
+
+        let loaded = MempoolSnapshot::load(&path).unwrap();
+        assert_eq!(loaded.len(), entries.len());
+        for (loaded, expected) in loaded.entries.iter().zip(entries.iter()) {
+            assert_eq!(loaded.transaction, expected.transaction);
+            assert_eq!(loaded.transaction.id(), expected.transaction.id());
+            assert_eq!(loaded.priority, expected.priority);
+            assert_eq!(loaded.added_at, expected.added_at);
+        }
+
+        fs::write(&path, b"garbage").unwrap();
+        assert_eq!(MempoolSnapshot::load(&path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -7,6 +7,7 @@ use crate::mempool::{
         pool::Pool,
         tx::{MempoolTransaction, TransactionPostValidation, TransactionPreValidation, TxRemovalReason},
     },
+    policy::PolicyVerdict,
     tx::{Orphan, Priority, RbfPolicy},
 };
 use kaspa_consensus_core::{
@@ -75,6 +76,7 @@ impl Mempool {
         // Perform mempool in-context validations prior to possible RBF replacements
         self.validate_transaction_limits_in_context(&transaction, virtual_daa_score)?;
         self.validate_transaction_std_in_context(&transaction, priority, virtual_daa_score)?;
+        let priority = self.validate_transaction_admission_policies(&transaction, priority)?;
 
         // Check double spends and try to remove them if the RBF policy requires it
         let removed_transaction = self.execute_replace_by_fee(&transaction, rbf_policy, virtual_daa_score)?;
@@ -165,6 +167,21 @@ impl Mempool {
         Ok(())
     }
 
+    /// Applies the operator admission policies in order, returning the priority the transaction should be inserted with
+    fn validate_transaction_admission_policies(&self, transaction: &MutableTransaction, priority: Priority) -> RuleResult<Priority> {
+        let mut priority = priority;
+        for policy in self.config.admission_policies.iter() {
+            match policy.check(transaction, priority) {
+                PolicyVerdict::Accept => {}
+                PolicyVerdict::Deprioritize => priority = Priority::Low,
+                PolicyVerdict::Reject(reason) => {
+                    return Err(RuleError::RejectPolicy(transaction.id(), policy.name().to_string(), reason));
+                }
+            }
+        }
+        Ok(priority)
+    }
+
     /// Returns a list with all successfully unorphaned transactions after some
     /// transaction has been accepted.
     pub(crate) fn get_unorphaned_transactions_after_accepted_transaction(
@@ -1,6 +1,6 @@
 use crate::error::Error;
 use crate::notification::Notification;
-use async_channel::Sender;
+use async_channel::{Sender, TrySendError};
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
 
@@ -32,6 +32,7 @@ where
     name: &'static str,
     sender: Sender<N>,
     channel_type: ChannelType,
+    close_on_full: bool,
 }
 
 impl<N> ChannelConnection<N>
@@ -39,7 +40,13 @@ where
     N: Notification,
 {
     pub fn new(name: &'static str, sender: Sender<N>, channel_type: ChannelType) -> Self {
-        Self { name, sender, channel_type }
+        Self { name, sender, channel_type, close_on_full: false }
+    }
+
+    /// Closes the connection instead of waiting when its bounded channel is full, so that a
+    /// consumer falling behind never stalls the notifier
+    pub fn with_close_on_full(self) -> Self {
+        Self { close_on_full: true, ..self }
     }
 
     /// Close the connection, ignoring the channel type
@@ -82,9 +89,19 @@ where
     }
 
     async fn send(&self, message: Self::Message) -> Result<(), Self::Error> {
-        match !self.is_closed() {
-            true => Ok(self.sender.send(message).await?),
-            false => Err(Error::ConnectionClosed),
+        if self.is_closed() {
+            return Err(Error::ConnectionClosed);
+        }
+        if !self.close_on_full {
+            return Ok(self.sender.send(message).await?);
+        }
+        match self.sender.try_send(message) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => {
+                self.force_close();
+                Err(Error::ConnectionClosed)
+            }
+            Err(TrySendError::Closed(_)) => Err(Error::ConnectionClosed),
         }
     }
 
@@ -99,3 +116,27 @@ where
         self.sender.is_closed()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::test_helpers::{BlockAddedNotification, TestNotification};
+
+    #[tokio::test]
+    async fn test_channel_connection_close_on_full() {
+        let notification = |data| TestNotification::BlockAdded(BlockAddedNotification { data });
+        let (sender, receiver) = async_channel::bounded(2);
+        let connection = ChannelConnection::new("test", sender, ChannelType::Closable).with_close_on_full();
+
+        assert!(connection.send(notification(1)).await.is_ok());
+        assert!(connection.send(notification(2)).await.is_ok());
+        assert!(!connection.is_closed());
+
+        // The consumer fell behind so the connection gets closed, yet already queued notifications are still delivered
+        assert!(matches!(connection.send(notification(3)).await, Err(Error::ConnectionClosed)));
+        assert!(connection.is_closed());
+        assert_eq!(receiver.recv().await.unwrap(), notification(1));
+        assert_eq!(receiver.recv().await.unwrap(), notification(2));
+        assert!(receiver.recv().await.is_err());
+    }
+}
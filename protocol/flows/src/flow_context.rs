@@ -1,6 +1,7 @@
 use crate::flowcontext::{
     orphans::{OrphanBlocksPool, OrphanOutput},
     process_queue::ProcessQueue,
+    sync_progress::SyncProgressTracker,
     transactions::TransactionsSpread,
 };
 use crate::user_agent_rule::{UserAgentRuleRejectReason, UserAgentRuleSet};
@@ -271,6 +272,7 @@ pub struct FlowContextInner {
     shared_transaction_requests: Arc<Mutex<HashMap<TransactionId, RequestScopeMetadata>>>,
     is_ibd_running: Arc<AtomicBool>,
     ibd_metadata: Arc<RwLock<Option<IbdMetadata>>>,
+    sync_progress: SyncProgressTracker,
     pub address_manager: Arc<Mutex<AddressManager>>,
     connection_manager: RwLock<Option<Arc<ConnectionManager>>>,
     mining_manager: MiningManagerProxy,
@@ -381,6 +383,7 @@ impl FlowContext {
                 shared_transaction_requests: Arc::new(Mutex::new(HashMap::new())),
                 is_ibd_running: Default::default(),
                 ibd_metadata: Default::default(),
+                sync_progress: SyncProgressTracker::new(notification_root.clone()),
                 hub,
                 address_manager,
                 connection_manager: Default::default(),
@@ -458,6 +461,11 @@ impl FlowContext {
         if self.is_ibd_running() { self.ibd_metadata.read().map(|md| md.peer) } else { None }
     }
 
+    /// Tracks the phase and progress of the running IBD
+    pub fn sync_progress(&self) -> &SyncProgressTracker {
+        &self.sync_progress
+    }
+
     /// If IBD is running, returns the DAA score of the relay block which triggered it
     pub fn ibd_relay_daa_score(&self) -> Option<u64> {
         if self.is_ibd_running() { self.ibd_metadata.read().map(|md| md.daa_score) } else { None }
@@ -514,7 +522,7 @@ impl FlowContext {
                 Ok(_) => {
                     unorphaned_blocks.push((block, virtual_state_task));
                 }
-                Err(e) => warn!("Validation failed for orphan block {}: {}", block.hash(), e),
+                Err(e) => warn!(block_hash:% = block.hash(); "Validation failed for orphan block {}: {}", block.hash(), e),
             }
         }
 
@@ -544,7 +552,7 @@ impl FlowContext {
         let hash = block.hash();
         let BlockValidationFutures { block_task, virtual_state_task } = consensus.validate_and_insert_block(block.clone());
         if let Err(err) = block_task.await {
-            warn!("Validation failed for block {}: {}", hash, err);
+            warn!(block_hash:% = hash; "Validation failed for block {}: {}", hash, err);
             return Err(err)?;
         }
         // Broadcast as soon as the block has been validated and inserted into the DAG
@@ -562,8 +570,8 @@ impl FlowContext {
             logger.log(event)
         } else {
             match event {
-                BlockLogEvent::Relay(hash, _) => info!("Accepted block {} via relay", hash),
-                BlockLogEvent::Submit(hash, _) => info!("Accepted block {} via submit block", hash),
+                BlockLogEvent::Relay(hash, _) => info!(block_hash:% = hash; "Accepted block {} via relay", hash),
+                BlockLogEvent::Submit(hash, _) => info!(block_hash:% = hash; "Accepted block {} via submit block", hash),
                 BlockLogEvent::Orphaned(orphan, roots_count) => {
                     info!("Received a block with {} missing ancestors, adding to orphan pool: {}", roots_count, orphan)
                 }
@@ -865,7 +873,7 @@ impl ConnectionInitializer for FlowContext {
         // Send and receive the ready signal
         handshake.exchange_ready_messages().await?;
 
-        info!("Registering p2p flows for peer {} for protocol version {}", router, applied_protocol_version);
+        info!(peer:% = router; "Registering p2p flows for peer {} for protocol version {}", router, applied_protocol_version);
 
         // Launch all flows. Note we launch only after the ready signal was exchanged
         for flow in flows {
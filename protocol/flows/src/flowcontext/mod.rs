@@ -1,3 +1,4 @@
 pub mod orphans;
 pub(crate) mod process_queue;
+pub mod sync_progress;
 pub mod transactions;
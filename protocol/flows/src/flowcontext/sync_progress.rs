@@ -0,0 +1,291 @@
+use kaspa_consensus_notify::{
+    notification::{Notification, SyncPhase, SyncProgress, SyncProgressChangedNotification},
+    root::ConsensusNotificationRoot,
+};
+use kaspa_notify::notifier::Notify;
+use kaspa_p2p_lib::Router;
+use kaspa_utils::networking::{NetAddress, PeerId};
+use parking_lot::Mutex;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// Minimum time between two notifications of progress within the same phase
+const NOTIFICATION_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Minimum time spent in a phase before estimating its remaining time
+const MIN_ETA_ELAPSED: Duration = Duration::from_secs(10);
+
+struct TrackerState {
+    progress: SyncProgress,
+    phase_started: Instant,
+    last_notified: Instant,
+}
+
+struct SyncerInfo {
+    syncer_id: PeerId,
+    syncer_address: NetAddress,
+}
+
+#[derive(Default)]
+struct Inner {
+    syncer: Option<SyncerInfo>,
+    state: Option<TrackerState>,
+}
+
+/// Tracks the phase and progress of the running IBD, exposing it to RPC clients through
+/// [`SyncProgressTracker::progress`] and `SyncProgressChanged` notifications
+#[derive(Clone)]
+pub struct SyncProgressTracker {
+    inner: Arc<Mutex<Inner>>,
+    notification_root: Arc<ConsensusNotificationRoot>,
+}
+
+impl SyncProgressTracker {
+    pub fn new(notification_root: Arc<ConsensusNotificationRoot>) -> Self {
+        Self { inner: Default::default(), notification_root }
+    }
+
+    /// Returns the progress of the running IBD phase, if any
+    pub fn progress(&self) -> Option<SyncProgress> {
+        self.inner.lock().state.as_ref().map(|state| state.progress.clone())
+    }
+
+    /// Registers the start of an IBD with `syncer`
+    pub(crate) fn start(&self, syncer: &Router) {
+        let mut inner = self.inner.lock();
+        inner.syncer = Some(SyncerInfo { syncer_id: syncer.identity(), syncer_address: syncer.net_address().into() });
+        inner.state = None;
+    }
+
+    /// Registers the end of the IBD, whether successful or not
+    pub(crate) fn finish(&self) {
+        let mut inner = self.inner.lock();
+        inner.syncer = None;
+        if inner.state.take().is_some() {
+            self.notify(None);
+        }
+    }
+
+    /// Enters `phase`, covering blocks in the DAA score range `[low_daa_score, high_daa_score]` and
+    /// consisting of `total` items when known in advance
+    pub(crate) fn start_phase(&self, phase: SyncPhase, low_daa_score: u64, high_daa_score: u64, total: Option<u64>) {
+        let mut inner = self.inner.lock();
+        let Some(syncer) = inner.syncer.as_ref() else {
+            return;
+        };
+        let progress = SyncProgress {
+            phase,
+            syncer_id: syncer.syncer_id,
+            syncer_address: syncer.syncer_address,
+            processed: 0,
+            total,
+            low_daa_score,
+            high_daa_score,
+            current_daa_score: low_daa_score,
+            eta_seconds: None,
+        };
+        let now = Instant::now();
+        inner.state = Some(TrackerState { progress: progress.clone(), phase_started: now, last_notified: now });
+        self.notify(Some(progress));
+    }
+
+    /// Reports the overall number of items processed in the current phase and the DAA score of the last processed block
+    pub(crate) fn update(&self, processed: u64, current_daa_score: u64) {
+        let mut inner = self.inner.lock();
+        let Some(state) = inner.state.as_mut() else {
+            return;
+        };
+        let progress = &mut state.progress;
+        progress.processed = processed;
+        progress.current_daa_score = current_daa_score.max(progress.current_daa_score);
+        if progress.current_daa_score > progress.high_daa_score {
+            progress.high_daa_score = progress.current_daa_score;
+        }
+        progress.eta_seconds = Self::estimate_eta(progress, state.phase_started.elapsed());
+
+        let now = Instant::now();
+        if now.duration_since(state.last_notified) >= NOTIFICATION_INTERVAL {
+            state.last_notified = now;
+            let progress = progress.clone();
+            drop(inner);
+            self.notify(Some(progress));
+        }
+    }
+
+    /// Extrapolates the remaining time of the phase from its processing rate so far
+    fn estimate_eta(progress: &SyncProgress, elapsed: Duration) -> Option<u64> {
+        if elapsed < MIN_ETA_ELAPSED {
+            return None;
+        }
+        let fraction = match progress.total {
+            Some(total) if total > 0 => progress.processed as f64 / total as f64,
+            _ if progress.high_daa_score > progress.low_daa_score => {
+                (progress.current_daa_score - progress.low_daa_score) as f64
+                    / (progress.high_daa_score - progress.low_daa_score) as f64
+            }
+            _ => return None,
+        };
+        if fraction <= 0.0 {
+            return None;
+        }
+        Some((elapsed.as_secs_f64() * (1.0 - fraction.min(1.0)) / fraction) as u64)
+    }
+
+    fn notify(&self, progress: Option<SyncProgress>) {
+        // Notifications might be ignored if the inner channel is already closing due to global shutdown
+        let _ = self.notification_root.notify(Notification::SyncProgressChanged(SyncProgressChangedNotification::new(progress)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_channel::Receiver;
+    use kaspa_notify::{
+        scope::{Scope, SyncProgressChangedScope},
+        subscriber::SubscriptionManager,
+    };
+
+    async fn tracker() -> (SyncProgressTracker, Receiver<Notification>) {
+        let (sender, receiver) = async_channel::unbounded();
+        let notification_root = Arc::new(ConsensusNotificationRoot::new(sender));
+        notification_root.start_notify(0, Scope::SyncProgressChanged(SyncProgressChangedScope {})).await.unwrap();
+        let tracker = SyncProgressTracker::new(notification_root);
+        tracker.inner.lock().syncer =
+            Some(SyncerInfo { syncer_id: PeerId::default(), syncer_address: "127.0.0.1:16111".parse().unwrap() });
+        (tracker, receiver)
+    }
+
+    fn notified(receiver: &Receiver<Notification>) -> Option<Option<SyncProgress>> {
+        match receiver.try_recv().ok()? {
+            Notification::SyncProgressChanged(notification) => Some(notification.progress),
+            other => panic!("unexpected notification {other:?}"),
+        }
+    }
+
+    /// Moves the start of the current phase and its last notification `elapsed` into the past
+    fn rewind(tracker: &SyncProgressTracker, elapsed: Duration) {
+        let mut inner = tracker.inner.lock();
+        let state = inner.state.as_mut().unwrap();
+        state.phase_started -= elapsed;
+        state.last_notified -= elapsed;
+    }
+
+    fn progress(phase: SyncPhase, processed: u64, total: Option<u64>, daa_scores: (u64, u64, u64)) -> SyncProgress {
+        let (low_daa_score, current_daa_score, high_daa_score) = daa_scores;
+        SyncProgress {
+            phase,
+            syncer_id: PeerId::default(),
+            syncer_address: "127.0.0.1:16111".parse().unwrap(),
+            processed,
+            total,
+            low_daa_score,
+            high_daa_score,
+            current_daa_score,
+            eta_seconds: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sync_progress_phase_transitions() {
+        let (tracker, receiver) = tracker().await;
+        assert!(tracker.progress().is_none());
+
+        tracker.start_phase(SyncPhase::Headers, 100, 200, None);
+        let started = notified(&receiver).unwrap().unwrap();
+        assert_eq!(started.phase, SyncPhase::Headers);
+        assert_eq!((started.processed, started.current_daa_score, started.eta_seconds), (0, 100, None));
+
+        tracker.update(10, 150);
+        let progress = tracker.progress().unwrap();
+        assert_eq!((progress.processed, progress.current_daa_score), (10, 150));
+
+        // The current DAA score never moves backwards
+        tracker.update(11, 120);
+        assert_eq!(tracker.progress().unwrap().current_daa_score, 150);
+
+        tracker.start_phase(SyncPhase::Bodies, 0, 0, Some(50));
+        let started = notified(&receiver).unwrap().unwrap();
+        assert_eq!((started.phase, started.processed, started.total), (SyncPhase::Bodies, 0, Some(50)));
+
+        tracker.finish();
+        assert!(tracker.progress().is_none());
+        assert!(notified(&receiver).unwrap().is_none());
+        assert!(notified(&receiver).is_none());
+
+        // Without a running IBD, phases are ignored
+        tracker.start_phase(SyncPhase::Headers, 0, 0, None);
+        tracker.update(1, 1);
+        assert!(tracker.progress().is_none());
+        assert!(notified(&receiver).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sync_progress_high_daa_score_bump() {
+        let (tracker, _receiver) = tracker().await;
+        tracker.start_phase(SyncPhase::Headers, 100, 200, None);
+        // Blocks beyond the initially expected range extend it
+        tracker.update(5, 250);
+        let progress = tracker.progress().unwrap();
+        assert_eq!((progress.current_daa_score, progress.high_daa_score), (250, 250));
+    }
+
+    #[tokio::test]
+    async fn test_sync_progress_notification_throttling() {
+        let (tracker, receiver) = tracker().await;
+        tracker.start_phase(SyncPhase::Bodies, 0, 0, Some(100));
+        assert!(notified(&receiver).is_some());
+
+        // Updates within the notification interval only refresh the polled progress
+        tracker.update(1, 0);
+        tracker.update(2, 0);
+        assert!(notified(&receiver).is_none());
+        assert_eq!(tracker.progress().unwrap().processed, 2);
+
+        rewind(&tracker, NOTIFICATION_INTERVAL);
+        tracker.update(3, 0);
+        assert_eq!(notified(&receiver).unwrap().unwrap().processed, 3);
+        tracker.update(4, 0);
+        assert!(notified(&receiver).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sync_progress_eta() {
+        let (tracker, _receiver) = tracker().await;
+        tracker.start_phase(SyncPhase::Bodies, 0, 0, Some(100));
+        // No estimation before enough time was spent in the phase
+        tracker.update(50, 0);
+        assert_eq!(tracker.progress().unwrap().eta_seconds, None);
+
+        rewind(&tracker, Duration::from_secs(30));
+        tracker.update(25, 0);
+        let eta = tracker.progress().unwrap().eta_seconds.unwrap();
+        assert!((89..=91).contains(&eta), "{eta}");
+    }
+
+    #[test]
+    fn test_sync_progress_estimate_eta() {
+        let elapsed = Duration::from_secs(60);
+        let eta = |progress: &SyncProgress| SyncProgressTracker::estimate_eta(progress, elapsed);
+
+        // A known total takes precedence over the DAA score range
+        assert_eq!(eta(&progress(SyncPhase::Bodies, 25, Some(100), (0, 90, 100))), Some(180));
+        assert_eq!(eta(&progress(SyncPhase::Bodies, 100, Some(100), (0, 0, 0))), Some(0));
+        assert_eq!(eta(&progress(SyncPhase::Bodies, 0, Some(100), (0, 0, 0))), None);
+
+        // Unknown totals fall back to the DAA score range
+        assert_eq!(eta(&progress(SyncPhase::Headers, 10, None, (1000, 1250, 2000))), Some(180));
+        assert_eq!(eta(&progress(SyncPhase::Headers, 10, Some(0), (1000, 1500, 2000))), Some(60));
+        assert_eq!(eta(&progress(SyncPhase::Headers, 10, None, (1000, 1000, 2000))), None);
+
+        // Neither a total nor a DAA score range
+        assert_eq!(eta(&progress(SyncPhase::UtxoSet, 1000, None, (0, 0, 0))), None);
+
+        assert_eq!(
+            SyncProgressTracker::estimate_eta(&progress(SyncPhase::Bodies, 25, Some(100), (0, 0, 0)), MIN_ETA_ELAPSED / 2),
+            None
+        );
+    }
+}
@@ -0,0 +1,469 @@
+use super::{IBD_BATCH_SIZE, IbdFlow, progress::ProgressReporter};
+use futures::{
+    future::{join_all, try_join_all},
+    stream::{FuturesOrdered, FuturesUnordered, StreamExt},
+};
+use itertools::Itertools;
+use kaspa_consensus_core::{
+    block::Block,
+    merkle::{calc_hash_merkle_root, calc_hash_merkle_root_pre_crescendo},
+    tx::Transaction,
+};
+use kaspa_consensusmanager::ConsensusProxy;
+use kaspa_core::{debug, info, warn};
+use kaspa_hashes::Hash;
+use kaspa_p2p_lib::{
+    IncomingRoute, Router,
+    common::ProtocolError,
+    dequeue_with_timeout, make_request,
+    pb::{RequestBlockBodiesMessage, RequestIbdChainBlockLocatorMessage, kaspad_message::Payload},
+};
+use rand::seq::SliceRandom;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    future::Future,
+    iter::once,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+type BlockBody = Vec<Transaction>;
+
+/// Minimal number of missing block bodies for which additional peers are recruited
+pub(super) const MIN_PARALLEL_BLOCK_BODIES: usize = 4 * IBD_BATCH_SIZE;
+/// Maximal number of peers (including the syncer) concurrently serving block bodies
+const MAX_BODY_PEERS: usize = 4;
+/// Maximal number of candidate peers probed for sharing the syncer chain
+const MAX_PROBED_CANDIDATES: usize = 16;
+/// The first protocol version supporting `RequestBlockBodies`
+const MIN_BODY_PROTOCOL_VERSION: u32 = 8;
+/// Number of chunks which may be requested ahead of the next chunk to be processed, per serving peer
+const CHUNKS_AHEAD_PER_PEER: usize = 2;
+/// Maximal number of chunks concurrently processed by consensus
+const MAX_PROCESSING_CHUNKS: usize = 2;
+/// Time without receiving a requested body after which a peer is considered stalled
+const BODY_STALL_TIMEOUT: Duration = Duration::from_secs(30);
+/// Minimal time the next chunk to be processed must be in flight before it is also requested from an idle peer
+const MIN_REASSIGN_DELAY: Duration = Duration::from_secs(5);
+/// Timeout for the chain block locator query used to probe candidate peers
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A peer serving block bodies, with a dedicated response route and throughput statistics
+struct BodyPeer {
+    router: Arc<Router>,
+    route: IncomingRoute,
+    is_syncer: bool,
+    blocks: usize,
+    busy_time: Duration,
+}
+
+impl BodyPeer {
+    fn new(router: Arc<Router>, is_syncer: bool) -> Self {
+        // The route is only addressed by request id, hence it subscribes to no message types
+        let route = router.subscribe(vec![]);
+        Self { router, route, is_syncer, blocks: 0, busy_time: Duration::ZERO }
+    }
+
+    /// Received blocks per second, once any block was received
+    fn throughput(&self) -> Option<f64> {
+        (self.blocks > 0).then(|| self.blocks as f64 / self.busy_time.as_secs_f64().max(f64::EPSILON))
+    }
+
+    fn expected_duration(&self, blocks: usize) -> Option<Duration> {
+        self.throughput().map(|throughput| Duration::from_secs_f64(blocks as f64 / throughput))
+    }
+
+    /// Checks that the peer has the same pruning point and that its sink is a known block in the chain future of `high`,
+    /// hence that it holds the bodies of all blocks in the past of `high` which are missing locally
+    async fn shares_chain(&mut self, consensus: &ConsensusProxy, pruning_point: Hash, high: Hash) -> Result<bool, ProtocolError> {
+        self.router
+            .enqueue(make_request!(
+                Payload::RequestIbdChainBlockLocator,
+                RequestIbdChainBlockLocatorMessage { low_hash: None, high_hash: None },
+                self.route.id()
+            ))
+            .await?;
+        let msg = dequeue_with_timeout!(self.route, Payload::IbdChainBlockLocator, PROBE_TIMEOUT)?;
+        let locator: Vec<Hash> = msg.try_into()?;
+        let (Some(&sink), Some(&peer_pruning_point)) = (locator.first(), locator.last()) else {
+            return Ok(false);
+        };
+        if peer_pruning_point != pruning_point || !consensus.async_get_block_status(sink).await.is_some_and(|s| s.has_block_header()) {
+            return Ok(false);
+        }
+        Ok(consensus.async_is_chain_ancestor_of(high, sink).await.unwrap_or(false))
+    }
+
+    async fn request_bodies(&mut self, consensus: &ConsensusProxy, chunk: &[Hash]) -> Result<Vec<Block>, ProtocolError> {
+        let start = Instant::now();
+        self.router
+            .enqueue(make_request!(
+                Payload::RequestBlockBodies,
+                RequestBlockBodiesMessage { hashes: chunk.iter().map(|h| h.into()).collect() },
+                self.route.id()
+            ))
+            .await?;
+        let mut blocks = Vec::with_capacity(chunk.len());
+        for &expected_hash in chunk {
+            let msg = dequeue_with_timeout!(self.route, Payload::BlockBody, BODY_STALL_TIMEOUT)?;
+            let header = consensus.async_get_header(expected_hash).await.map_err(|err| {
+                ProtocolError::OtherOwned(format!("syncee inconsistency: missing block header for {}, err: {}", expected_hash, err))
+            })?;
+            let body: BlockBody = msg.try_into()?;
+            if body.is_empty() {
+                return Err(ProtocolError::OtherOwned(format!("sent empty block body for block {}", expected_hash)));
+            }
+            // Catch bodies not matching the header before they reach consensus, so that the range can be re-assigned
+            if calc_hash_merkle_root(body.iter()) != header.hash_merkle_root
+                && calc_hash_merkle_root_pre_crescendo(body.iter()) != header.hash_merkle_root
+            {
+                return Err(ProtocolError::OtherOwned(format!(
+                    "sent block body not matching the merkle root of block {}",
+                    expected_hash
+                )));
+            }
+            blocks.push(Block { header, transactions: body.into() });
+        }
+        self.blocks += blocks.len();
+        self.busy_time += start.elapsed();
+        Ok(blocks)
+    }
+}
+
+impl Drop for BodyPeer {
+    fn drop(&mut self) {
+        // Responses still in flight are dropped by the router
+        self.router.unsubscribe(self.route.id());
+    }
+}
+
+/// Download state of a chunk which was requested from at least one peer
+struct InFlightChunk {
+    requested_at: Instant,
+    requests: usize,
+    expected_duration: Option<Duration>,
+}
+
+/// Scheduling state of the chunks of a block body download, independent of the peers serving them
+struct ChunkScheduler<T> {
+    chunk_count: usize,
+    /// Number of peers still serving chunks
+    active_peers: usize,
+    pending: BTreeSet<usize>,
+    in_flight: HashMap<usize, InFlightChunk>,
+    downloaded: BTreeMap<usize, T>,
+    next_chunk: usize,
+}
+
+impl<T> ChunkScheduler<T> {
+    fn new(chunk_count: usize, active_peers: usize) -> Self {
+        Self {
+            chunk_count,
+            active_peers,
+            pending: (0..chunk_count).collect(),
+            in_flight: HashMap::new(),
+            downloaded: BTreeMap::new(),
+            next_chunk: 0,
+        }
+    }
+
+    /// Whether all chunks were taken for processing
+    fn is_complete(&self) -> bool {
+        self.next_chunk == self.chunk_count
+    }
+
+    /// Takes the next chunk to be processed, if it was downloaded
+    fn take_next(&mut self) -> Option<T> {
+        let chunk = self.downloaded.remove(&self.next_chunk)?;
+        self.in_flight.remove(&self.next_chunk);
+        self.next_chunk += 1;
+        Some(chunk)
+    }
+
+    /// Picks the chunk an idle peer should request at `now`, given the expected duration of the peer for a chunk index.
+    ///
+    /// Pending chunks are picked in order within a window ahead of the next chunk to be processed. Once the window
+    /// is exhausted, the next chunk to be processed is picked again if it is stalled by the single peer it was
+    /// requested from.
+    fn assign(&mut self, now: Instant, expected_duration: impl FnOnce(usize) -> Option<Duration>) -> Option<usize> {
+        let window_end = self.next_chunk + CHUNKS_AHEAD_PER_PEER * self.active_peers;
+        let index = match self.pending.first() {
+            Some(&index) if index < window_end => {
+                self.pending.remove(&index);
+                index
+            }
+            _ => {
+                let chunk = self.in_flight.get(&self.next_chunk)?;
+                // A downloaded chunk is only waiting for a processing slot
+                let stalled = chunk.requests == 1
+                    && !self.downloaded.contains_key(&self.next_chunk)
+                    && now.saturating_duration_since(chunk.requested_at)
+                        > MIN_REASSIGN_DELAY.max(2 * chunk.expected_duration.unwrap_or(BODY_STALL_TIMEOUT));
+                if !stalled {
+                    return None;
+                }
+                debug!("IBD: chunk {} is stalled, requesting it from another peer as well", self.next_chunk);
+                self.next_chunk
+            }
+        };
+        let chunk = self.in_flight.entry(index).or_insert(InFlightChunk {
+            requested_at: now,
+            requests: 0,
+            expected_duration: expected_duration(index),
+        });
+        chunk.requests += 1;
+        Some(index)
+    }
+
+    /// Records a downloaded chunk, keeping the first copy of chunks requested from several peers
+    fn complete(&mut self, index: usize, chunk: T) {
+        if index >= self.next_chunk {
+            self.downloaded.entry(index).or_insert(chunk);
+        }
+    }
+
+    /// Records the failure of the peer downloading the chunk at `index`, which no longer serves chunks. The chunk
+    /// returns to pending unless another peer is still downloading it. Returns whether any peers are left.
+    fn fail(&mut self, index: usize) -> bool {
+        let done = index < self.next_chunk || self.downloaded.contains_key(&index);
+        if let Some(chunk) = self.in_flight.get_mut(&index) {
+            chunk.requests -= 1;
+            if chunk.requests == 0 && !done {
+                self.in_flight.remove(&index);
+                self.pending.insert(index);
+            }
+        }
+        self.active_peers -= 1;
+        self.active_peers > 0
+    }
+}
+
+type DownloadResult = (BodyPeer, usize, Result<Vec<Block>, ProtocolError>);
+
+fn download(mut peer: BodyPeer, consensus: &ConsensusProxy, index: usize, chunk: &[Hash]) -> impl Future<Output = DownloadResult> {
+    async move {
+        let result = peer.request_bodies(consensus, chunk).await;
+        (peer, index, result)
+    }
+}
+
+fn process(consensus: &ConsensusProxy, blocks: Vec<Block>) -> impl Future<Output = Result<(usize, u64, u64), ProtocolError>> {
+    let (len, last) = (blocks.len(), blocks.last().map(|block| (block.header.daa_score, block.header.timestamp)).unwrap_or_default());
+    let jobs = blocks.into_iter().map(|block| consensus.validate_and_insert_block(block).virtual_state_task).collect_vec();
+    async move {
+        try_join_all(jobs).await?;
+        Ok((len, last.0, last.1))
+    }
+}
+
+impl IbdFlow {
+    /// Looks for additional peers which can serve the bodies missing in the past of `high`
+    pub(super) async fn recruit_body_peers(&self, consensus: &ConsensusProxy, high: Hash) -> Vec<Arc<Router>> {
+        let mut candidates = self
+            .ctx
+            .hub()
+            .active_routers()
+            .into_iter()
+            .filter(|router| router.key() != self.router.key() && router.properties().protocol_version >= MIN_BODY_PROTOCOL_VERSION)
+            .collect_vec();
+        candidates.shuffle(&mut rand::thread_rng());
+        candidates.truncate(MAX_PROBED_CANDIDATES);
+
+        let pruning_point = consensus.async_pruning_point().await;
+        let probes = candidates.into_iter().map(|router| async move {
+            let mut peer = BodyPeer::new(router.clone(), false);
+            match peer.shares_chain(consensus, pruning_point, high).await {
+                Ok(shares_chain) => shares_chain.then_some(router),
+                Err(err) => {
+                    debug!("IBD: peer {} was not recruited for block body download: {}", router, err);
+                    None
+                }
+            }
+        });
+        join_all(probes).await.into_iter().flatten().take(MAX_BODY_PEERS - 1).collect()
+    }
+
+    /// Downloads the bodies of `hashes` concurrently from the syncer and `helpers`, and processes them in order.
+    ///
+    /// Chunks of [`IBD_BATCH_SIZE`] hashes are assigned to idle peers, fastest first, within a window ahead of
+    /// the next chunk to be processed. A peer failing or stalling on a chunk is dropped and the chunk is re-assigned,
+    /// and the next chunk to be processed is also requested from an idle peer once it takes much longer than
+    /// expected from the throughput of the peer it was assigned to.
+    pub(super) async fn download_block_bodies_from_peers(
+        &self,
+        consensus: &ConsensusProxy,
+        hashes: &[Hash],
+        helpers: Vec<Arc<Router>>,
+        mut progress_reporter: ProgressReporter,
+    ) -> Result<(), ProtocolError> {
+        info!("IBD: downloading {} block bodies from {} peers", hashes.len(), helpers.len() + 1);
+        let chunks = hashes.chunks(IBD_BATCH_SIZE).collect_vec();
+        let mut idle = once(BodyPeer::new(self.router.clone(), true))
+            .chain(helpers.into_iter().map(|router| BodyPeer::new(router, false)))
+            .collect_vec();
+        let mut scheduler = ChunkScheduler::new(chunks.len(), idle.len());
+        let mut downloads = FuturesUnordered::new();
+        let mut processing = FuturesOrdered::new();
+        let mut ticker = tokio::time::interval(Duration::from_secs(1));
+
+        while !scheduler.is_complete() || !processing.is_empty() {
+            // Submit downloaded chunks to consensus in order
+            while processing.len() < MAX_PROCESSING_CHUNKS
+                && let Some(blocks) = scheduler.take_next()
+            {
+                processing.push_back(process(consensus, blocks));
+            }
+
+            // Assign chunks to idle peers, fastest peers first
+            idle.sort_by(|a, b| a.throughput().unwrap_or(0.0).total_cmp(&b.throughput().unwrap_or(0.0)));
+            while let Some(peer) = idle.pop() {
+                let Some(index) = scheduler.assign(Instant::now(), |index| peer.expected_duration(chunks[index].len())) else {
+                    idle.push(peer);
+                    break;
+                };
+                downloads.push(download(peer, consensus, index, chunks[index]));
+            }
+
+            tokio::select! {
+                Some((peer, index, result)) = downloads.next(), if !downloads.is_empty() => {
+                    match result {
+                        Ok(blocks) => {
+                            scheduler.complete(index, blocks);
+                            idle.push(peer);
+                        }
+                        Err(err) => {
+                            if peer.is_syncer && !matches!(err, ProtocolError::Timeout(_)) {
+                                return Err(err);
+                            }
+                            warn!("IBD: stopped downloading block bodies from peer {}: {}", peer.router, err);
+                            if !scheduler.fail(index) {
+                                return Err(ProtocolError::Other("no peers left to download block bodies from"));
+                            }
+                        }
+                    }
+                }
+                Some(result) = processing.next(), if !processing.is_empty() => {
+                    let (len, daa_score, timestamp) = result?;
+                    progress_reporter.report(len, daa_score, timestamp);
+                }
+                _ = ticker.tick() => {}
+            }
+        }
+
+        for peer in idle.iter() {
+            info!(
+                "IBD: received {} block bodies from peer {} ({:.1} blocks/s)",
+                peer.blocks,
+                peer.router,
+                peer.throughput().unwrap_or_default()
+            );
+        }
+        progress_reporter.report_completion(0);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPECTED: Option<Duration> = Some(Duration::from_secs(1));
+
+    #[test]
+    fn test_chunk_scheduling_window_and_order() {
+        let now = Instant::now();
+        let mut scheduler = ChunkScheduler::new(10, 2);
+
+        // Two peers may request up to four chunks ahead of the next chunk to be processed
+        let assigned = (0..5).map(|_| scheduler.assign(now, |_| EXPECTED)).collect_vec();
+        assert_eq!(assigned, vec![Some(0), Some(1), Some(2), Some(3), None]);
+
+        // Chunks are taken for processing in order only
+        scheduler.complete(1, "1");
+        assert_eq!(scheduler.take_next(), None);
+        scheduler.complete(0, "0");
+        assert_eq!(scheduler.take_next(), Some("0"));
+        assert_eq!(scheduler.take_next(), Some("1"));
+        assert_eq!(scheduler.take_next(), None);
+
+        // Processing slides the window forward
+        assert_eq!(scheduler.assign(now, |_| EXPECTED), Some(4));
+        assert_eq!(scheduler.assign(now, |_| EXPECTED), Some(5));
+        assert_eq!(scheduler.assign(now, |_| EXPECTED), None);
+
+        for index in 2..10 {
+            if index >= 6 {
+                assert_eq!(scheduler.assign(now, |_| EXPECTED), Some(index));
+            }
+            scheduler.complete(index, "chunk");
+            assert_eq!(scheduler.take_next(), Some("chunk"));
+        }
+        assert!(scheduler.is_complete());
+    }
+
+    #[test]
+    fn test_chunk_scheduling_stall_reassignment() {
+        let now = Instant::now();
+        let mut scheduler = ChunkScheduler::new(2, 1);
+        assert_eq!(scheduler.assign(now, |_| EXPECTED), Some(0));
+        assert_eq!(scheduler.assign(now, |_| EXPECTED), Some(1));
+
+        // The next chunk is re-assigned once it takes much longer than expected, and to a single additional peer only
+        let stalled = now + MIN_REASSIGN_DELAY + Duration::from_secs(1);
+        assert_eq!(scheduler.assign(now + MIN_REASSIGN_DELAY, |_| EXPECTED), None);
+        assert_eq!(scheduler.assign(stalled, |_| EXPECTED), Some(0));
+        assert_eq!(scheduler.assign(stalled, |_| EXPECTED), None);
+
+        // Without any throughput estimate, the stall timeout applies
+        let mut scheduler = ChunkScheduler::new(1, 1);
+        assert_eq!(scheduler.assign(now, |_| None), Some(0));
+        assert_eq!(scheduler.assign(stalled, |_| EXPECTED), None);
+        assert_eq!(scheduler.assign(now + 2 * BODY_STALL_TIMEOUT + Duration::from_secs(1), |_| EXPECTED), Some(0));
+
+        // The first copy of a chunk downloaded twice is kept
+        scheduler.complete(0, "first");
+        scheduler.complete(0, "second");
+        assert_eq!(scheduler.take_next(), Some("first"));
+        scheduler.complete(0, "late");
+        assert!(scheduler.is_complete());
+        assert!(scheduler.downloaded.is_empty());
+    }
+
+    #[test]
+    fn test_chunk_scheduling_downloaded_chunk_not_reassigned() {
+        let now = Instant::now();
+        let mut scheduler = ChunkScheduler::new(1, 2);
+        assert_eq!(scheduler.assign(now, |_| EXPECTED), Some(0));
+
+        // A downloaded chunk waiting for a processing slot is not stalled
+        scheduler.complete(0, "0");
+        assert_eq!(scheduler.assign(now + BODY_STALL_TIMEOUT * 4, |_| EXPECTED), None);
+        assert_eq!(scheduler.take_next(), Some("0"));
+    }
+
+    #[test]
+    fn test_chunk_scheduling_peer_drop() {
+        let now = Instant::now();
+        let mut scheduler = ChunkScheduler::new(3, 3);
+        assert_eq!(scheduler.assign(now, |_| EXPECTED), Some(0));
+        assert_eq!(scheduler.assign(now, |_| EXPECTED), Some(1));
+        assert_eq!(scheduler.assign(now, |_| EXPECTED), Some(2));
+
+        // A chunk whose only peer failed returns to pending and is assigned again
+        assert!(scheduler.fail(1));
+        assert_eq!(scheduler.assign(now, |_| EXPECTED), Some(1));
+
+        // A re-assigned chunk stays in flight while another peer is still downloading it
+        let stalled = now + MIN_REASSIGN_DELAY + Duration::from_secs(1);
+        assert_eq!(scheduler.assign(stalled, |_| EXPECTED), Some(0));
+        assert!(scheduler.fail(0));
+        assert!(!scheduler.pending.contains(&0));
+        scheduler.complete(0, "0");
+        assert_eq!(scheduler.take_next(), Some("0"));
+
+        // Failing after another peer delivered the chunk does not request it again
+        scheduler.complete(1, "1");
+        assert!(!scheduler.fail(1));
+        assert!(scheduler.pending.is_empty());
+    }
+}
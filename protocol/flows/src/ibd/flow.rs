@@ -7,14 +7,18 @@ use futures::future::{Either, join_all, select, try_join_all};
 use itertools::Itertools;
 use kaspa_consensus_core::{
     BlockHashSet,
-    api::BlockValidationFuture,
+    api::{BlockValidationFuture, SmtExportMetadata},
     block::Block,
-    config::params::{ForkActivation, Params},
+    config::{
+        Config,
+        params::{ForkActivation, Params},
+    },
     header::Header,
     pruning::{PruningPointProof, PruningPointsList, PruningProofMetadata},
     trusted::TrustedBlock,
     tx::Transaction,
 };
+use kaspa_consensus_notify::notification::SyncPhase;
 use kaspa_consensusmanager::{ConsensusProxy, StagingConsensus, spawn_blocking};
 use kaspa_core::{debug, info, time::unix_now, warn};
 use kaspa_hashes::Hash;
@@ -33,6 +37,7 @@ use kaspa_p2p_lib::{
         kaspad_message::Payload,
     },
 };
+use kaspa_seq_commit::verify::{SmtMetadata, verify_smt_metadata};
 use kaspa_utils::channel::JobReceiver;
 use std::{
     sync::Arc,
@@ -40,7 +45,9 @@ use std::{
 };
 use tokio::time::sleep;
 
-use super::{HeadersChunk, IBD_BATCH_SIZE, PruningPointUtxosetChunkStream, progress::ProgressReporter};
+use super::{
+    HeadersChunk, IBD_BATCH_SIZE, PruningPointUtxosetChunkStream, bodies::MIN_PARALLEL_BLOCK_BODIES, progress::ProgressReporter,
+};
 type BlockBody = Vec<Transaction>;
 
 /// Flow for managing IBD - Initial Block Download
@@ -94,12 +101,15 @@ impl IbdFlow {
     async fn start_impl(&mut self) -> Result<(), ProtocolError> {
         while let Ok(relay_block) = self.relay_receiver.recv().await {
             if let Some(_guard) = self.ctx.try_set_ibd_running(self.router.key(), relay_block.header.daa_score) {
-                info!("IBD started with peer {}", self.router);
+                info!(peer:% = self.router; "IBD started with peer {}", self.router);
 
-                match self.ibd(relay_block).await {
-                    Ok(_) => info!("IBD with peer {} completed successfully", self.router),
+                self.ctx.sync_progress().start(&self.router);
+                let result = self.ibd(relay_block).await;
+                self.ctx.sync_progress().finish();
+                match result {
+                    Ok(_) => info!(peer:% = self.router; "IBD with peer {} completed successfully", self.router),
                     Err(e) => {
-                        info!("IBD with peer {} completed with error: {}", self.router, e);
+                        info!(peer:% = self.router; "IBD with peer {} completed with error: {}", self.router, e);
                         return Err(e);
                     }
                 }
@@ -226,6 +236,8 @@ impl IbdFlow {
         // check its past for missing bodies as well.
         self.sync_missing_block_bodies(&session, relay_block.hash()).await?;
 
+        self.ctx.sync_progress().start_phase(SyncPhase::VirtualResolution, 0, 0, None);
+
         // Following IBD we revalidate orphans since many of them might have been processed during the IBD
         // or are now processable
         let (queued_hashes, virtual_processing_tasks) = self.ctx.revalidate_orphans(&session).await;
@@ -397,6 +409,7 @@ impl IbdFlow {
     }
 
     async fn sync_and_validate_pruning_proof(&mut self, staging: &ConsensusProxy, relay_block: &Block) -> Result<Hash, ProtocolError> {
+        self.ctx.sync_progress().start_phase(SyncPhase::PruningProof, 0, 0, None);
         // [Toccata] Guard IBD from outdated nodes. P2P flow registration does not protect
         // fresh IBD peers, and the relay block is usually the syncer sink, so reject an unexpected
         // block version before requesting the pruning proof. The pruning point itself is
@@ -636,7 +649,9 @@ impl IbdFlow {
         relay_block: &Block,
     ) -> Result<(), ProtocolError> {
         let highest_shared_header_score = consensus.async_get_header(highest_known_syncer_chain_hash).await?.daa_score;
-        let mut progress_reporter = ProgressReporter::new(highest_shared_header_score, relay_block.header.daa_score, "block headers");
+        self.ctx.sync_progress().start_phase(SyncPhase::Headers, highest_shared_header_score, relay_block.header.daa_score, None);
+        let mut progress_reporter = ProgressReporter::new(highest_shared_header_score, relay_block.header.daa_score, "block headers")
+            .with_tracker(self.ctx.sync_progress().clone());
 
         self.router
             .enqueue(make_message!(
@@ -697,7 +712,6 @@ impl IbdFlow {
     async fn sync_new_smt_state(&mut self, consensus: &ConsensusProxy, pruning_point: Hash) -> Result<(), ProtocolError> {
         use super::streams::SmtStream;
         use kaspa_p2p_lib::pb::RequestPruningPointSmtStateMessage;
-        use kaspa_seq_commit::verify::{SmtMetadata, verify_smt_metadata};
 
         let pp_header = consensus.async_get_header(pruning_point).await.unwrap();
         if !self.ctx.config.toccata_activation.is_active(pp_header.daa_score) {
@@ -708,6 +722,8 @@ impl IbdFlow {
         consensus.async_clear_pruning_smt_stores().await;
 
         info!("downloading the pruning point SMT state from {}", self.router);
+        // The lane count is unknown until the stream ends and lanes cover no DAA score range, so this phase has no ETA
+        self.ctx.sync_progress().start_phase(SyncPhase::SmtState, 0, 0, None);
 
         self.router
             .enqueue(make_message!(
@@ -720,36 +736,7 @@ impl IbdFlow {
 
         // Phase 0: receive and verify metadata. Single 96-byte wire.
         let md = stream.recv_metadata().await?;
-        let parent_header = consensus.async_get_header(pp_header.direct_parents()[0]).await.unwrap();
-
-        // Derive the shortcut block via consensus (uses reachability + headers only; safe at the PP
-        // boundary before the SMT is imported). Then resolve to the seqcommit hash with the same
-        // fold-to-zero rule used by `inactivity_shortcut(block)`.
-        let shortcut_block = consensus
-            .async_inactivity_shortcut_block_for_pov(pruning_point)
-            .await
-            .map_err(|e| ProtocolError::OtherOwned(format!("inactivity_shortcut_block resolution failed: {e}")))?;
-        let shortcut_header = consensus
-            .async_get_header(shortcut_block)
-            .await
-            .map_err(|_| ProtocolError::Other("inactivity_shortcut_block header not found"))?;
-        let inactivity_shortcut = if !self.ctx.config.toccata_activation.is_active(shortcut_header.daa_score) {
-            kaspa_hashes::ZERO_HASH
-        } else {
-            shortcut_header.accepted_id_merkle_root
-        };
-
-        verify_smt_metadata(
-            &SmtMetadata {
-                lanes_root: &md.lanes_root,
-                payload_and_ctx_digest: &md.payload_and_ctx_digest,
-                parent_seq_commit: &md.parent_seq_commit,
-            },
-            inactivity_shortcut,
-            pp_header.accepted_id_merkle_root,
-            parent_header.accepted_id_merkle_root,
-        )
-        .map_err(|e| ProtocolError::OtherOwned(format!("SMT metadata verification failed: {e}")))?;
+        let shortcut_block = verify_pruning_point_smt_metadata(&self.ctx.config, consensus, &pp_header, &md).await?;
 
         // Small queue of already-chunked batches: one in flight + one being processed
         // by the importer is enough headroom; each chunk holds up to SMT_CHUNK_SIZE lanes.
@@ -761,6 +748,7 @@ impl IbdFlow {
 
         while let Some(chunk) = stream.next_chunk().await? {
             tx.send(chunk).await.map_err(|_| ProtocolError::Other("streaming SMT builder stopped unexpectedly"))?;
+            self.ctx.sync_progress().update(stream.lane_count(), 0);
         }
         drop(tx);
 
@@ -850,6 +838,8 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
 
     async fn sync_pruning_point_utxoset(&mut self, consensus: &ConsensusProxy, pruning_point: Hash) -> Result<(), ProtocolError> {
         info!("downloading the pruning point utxoset, this can take a little while.");
+        // The UTXO count is unknown until the stream ends and UTXOs cover no DAA score range, so this phase has no ETA
+        self.ctx.sync_progress().start_phase(SyncPhase::UtxoSet, 0, 0, None);
         self.router
             .enqueue(make_message!(
                 Payload::RequestPruningPointUtxoSet,
@@ -858,7 +848,10 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
             .await?;
         let mut chunk_stream = PruningPointUtxosetChunkStream::new(&self.router, &mut self.incoming_route);
         let mut multiset = MuHash::new();
+        let mut received_utxos = 0u64;
         while let Some(chunk) = chunk_stream.next().await? {
+            received_utxos += chunk.len() as u64;
+            self.ctx.sync_progress().update(received_utxos, 0);
             multiset = consensus
                 .clone()
                 .spawn_blocking(move |c| {
@@ -873,6 +866,7 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
     async fn sync_missing_trusted_bodies(&mut self, consensus: &ConsensusProxy) -> Result<(), ProtocolError> {
         info!("downloading pruning point anticone missing block data");
         let diesembodied_hashes = consensus.async_get_body_missing_anticone().await;
+        self.ctx.sync_progress().start_phase(SyncPhase::Bodies, 0, 0, Some(diesembodied_hashes.len() as u64));
         if self.body_only_ibd_permitted {
             self.sync_missing_trusted_bodies_no_headers(consensus, diesembodied_hashes).await?
         } else {
@@ -985,7 +979,21 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
 
         let low_header = consensus.async_get_header(*hashes.first().expect("hashes was non empty")).await?;
         let high_header = consensus.async_get_header(*hashes.last().expect("hashes was non empty")).await?;
-        let mut progress_reporter = ProgressReporter::new(low_header.daa_score, high_header.daa_score, "blocks");
+        self.ctx.sync_progress().start_phase(
+            SyncPhase::Bodies,
+            low_header.daa_score,
+            high_header.daa_score,
+            Some(hashes.len() as u64),
+        );
+        let mut progress_reporter = ProgressReporter::new(low_header.daa_score, high_header.daa_score, "blocks")
+            .with_tracker(self.ctx.sync_progress().clone());
+
+        if self.body_only_ibd_permitted && hashes.len() >= MIN_PARALLEL_BLOCK_BODIES {
+            let helpers = self.recruit_body_peers(consensus, high).await;
+            if !helpers.is_empty() {
+                return self.download_block_bodies_from_peers(consensus, &hashes, helpers, progress_reporter).await;
+            }
+        }
 
         let mut iter = hashes.chunks(IBD_BATCH_SIZE);
         let QueueChunkOutput { jobs: mut prev_jobs, daa_score: mut prev_daa_score, timestamp: mut prev_timestamp } =
@@ -1089,6 +1097,49 @@ staging selected tip ({}) is too small or negative. Aborting IBD...",
     }
 }
 
+/// Verifies SMT metadata claimed for the pruning point (`pp_header`) against the sequencing
+/// commitments of the pruning point and its selected parent. Returns the resolved
+/// `inactivity_shortcut_block` to be passed on to the SMT importer.
+pub(super) async fn verify_pruning_point_smt_metadata(
+    config: &Config,
+    consensus: &ConsensusProxy,
+    pp_header: &Header,
+    md: &SmtExportMetadata,
+) -> Result<Hash, ProtocolError> {
+    let parent_header = consensus.async_get_header(pp_header.direct_parents()[0]).await.unwrap();
+
+    // Derive the shortcut block via consensus (uses reachability + headers only; safe at the PP
+    // boundary before the SMT is imported). Then resolve to the seqcommit hash with the same
+    // fold-to-zero rule used by `inactivity_shortcut(block)`.
+    let shortcut_block = consensus
+        .async_inactivity_shortcut_block_for_pov(pp_header.hash)
+        .await
+        .map_err(|e| ProtocolError::OtherOwned(format!("inactivity_shortcut_block resolution failed: {e}")))?;
+    let shortcut_header = consensus
+        .async_get_header(shortcut_block)
+        .await
+        .map_err(|_| ProtocolError::Other("inactivity_shortcut_block header not found"))?;
+    let inactivity_shortcut = if !config.toccata_activation.is_active(shortcut_header.daa_score) {
+        kaspa_hashes::ZERO_HASH
+    } else {
+        shortcut_header.accepted_id_merkle_root
+    };
+
+    verify_smt_metadata(
+        &SmtMetadata {
+            lanes_root: &md.lanes_root,
+            payload_and_ctx_digest: &md.payload_and_ctx_digest,
+            parent_seq_commit: &md.parent_seq_commit,
+        },
+        inactivity_shortcut,
+        pp_header.accepted_id_merkle_root,
+        parent_header.accepted_id_merkle_root,
+    )
+    .map_err(|e| ProtocolError::OtherOwned(format!("SMT metadata verification failed: {e}")))?;
+
+    Ok(shortcut_block)
+}
+
 /// [Toccata] Fresh nodes cannot easily identify outdated peers after activation, so we guard
 /// against syncers advertising pruning points that are clearly stale.
 ///
@@ -1,7 +1,10 @@
+mod bodies;
 mod flow;
 mod negotiate;
 mod progress;
+mod snapshot;
 mod streams;
 
 pub use flow::*;
+pub use snapshot::*;
 pub use streams::*;
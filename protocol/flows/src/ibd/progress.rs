@@ -3,6 +3,8 @@ use std::time::{Duration, Instant};
 use chrono::{Local, LocalResult, TimeZone};
 use kaspa_core::info;
 
+use crate::flowcontext::sync_progress::SyncProgressTracker;
+
 /// Minimum number of items to report
 const REPORT_BATCH_GRANULARITY: usize = 500;
 /// Maximum time to go without report
@@ -16,6 +18,7 @@ pub struct ProgressReporter {
     last_log_time: Instant,
     current_batch: usize,
     processed: usize,
+    tracker: Option<SyncProgressTracker>,
 }
 
 impl ProgressReporter {
@@ -32,9 +35,16 @@ impl ProgressReporter {
             last_log_time: Instant::now(),
             current_batch: 0,
             processed: 0,
+            tracker: None,
         }
     }
 
+    /// Also reports the progress to `tracker`, whose current phase is expected to be the one reported here
+    pub fn with_tracker(mut self, tracker: SyncProgressTracker) -> Self {
+        self.tracker = Some(tracker);
+        self
+    }
+
     pub fn report(&mut self, processed_delta: usize, current_daa_score: u64, current_timestamp: u64) {
         self.current_batch += processed_delta;
         let now = Instant::now();
@@ -46,6 +56,9 @@ impl ProgressReporter {
         if current_daa_score > self.high_daa_score {
             self.high_daa_score = current_daa_score.saturating_add(1); // + 1 for keeping it at 99%
         }
+        if let Some(tracker) = self.tracker.as_ref() {
+            tracker.update(self.processed as u64, current_daa_score);
+        }
         let relative_daa_score = current_daa_score.saturating_sub(self.low_daa_score);
         let percent = ((relative_daa_score as f64 / (self.high_daa_score - self.low_daa_score) as f64) * 100.0) as i32;
         if percent > self.last_reported_percent {
@@ -61,6 +74,9 @@ impl ProgressReporter {
 
     pub fn report_completion(mut self, processed_delta: usize) {
         self.processed += self.current_batch + processed_delta;
+        if let Some(tracker) = self.tracker.as_ref() {
+            tracker.update(self.processed as u64, self.high_daa_score);
+        }
         info!("IBD: Processed {} {} (100%)", self.processed, self.object_name);
     }
 }
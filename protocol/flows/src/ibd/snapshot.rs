@@ -0,0 +1,854 @@
+//!
+//! Pruning point snapshots: a file-based alternative to the IBD headers-proof path.
+//!
+//! A snapshot carries the exact P2P messages a syncer would send during IBD (sink header, pruning proof,
+//! past pruning points, trusted data, headers up to the sink, SMT lane state and the pruning point UTXO set),
+//! framed as length-prefixed protobuf `KaspadMessage`s and sealed by a digest over the whole file. Importing
+//! goes through the same validation as a peer-driven IBD: the proof is validated against current consensus,
+//! the SMT metadata against the pruning point sequencing commitments and the UTXO set against the pruning
+//! point UTXO commitment (via MuHash). The file digest only protects against corruption; trust is anchored
+//! in consensus validation, exactly as when syncing from an untrusted peer.
+//!
+
+use crate::{
+    flow_context::FlowContext,
+    ibd::{
+        HeadersChunk, SMT_CHUNK_SIZE, UtxosetChunk, flow::verify_pruning_point_smt_metadata, import_lane_from_entry,
+        import_lane_to_entry, progress::ProgressReporter, smt_metadata_from_message, smt_metadata_to_message,
+    },
+};
+use futures::future::try_join_all;
+use itertools::Itertools;
+use kaspa_consensus_core::{
+    BlockHashSet,
+    api::{ConsensusApi, ImportLane, SMT_PROOF_INTERVAL},
+    block::Block,
+    config::Config,
+    errors::{block::RuleError, consensus::ConsensusError, pruning::PruningImportError},
+    header::Header,
+    pruning::{PruningPointProof, PruningPointsList, PruningProofMetadata},
+};
+use kaspa_consensusmanager::{ConsensusProxy, StagingConsensus, spawn_blocking};
+use kaspa_core::{debug, info};
+use kaspa_hashes::{Hash, PruningPointSnapshotHash};
+use kaspa_muhash::MuHash;
+use kaspa_p2p_lib::{
+    KaspadMessagePayloadType,
+    common::ProtocolError,
+    convert::{
+        error::ConversionError,
+        header::{HeaderFormat, Versioned},
+        model::trusted::{TrustedDataEntry, TrustedDataPackage},
+    },
+    pb::{
+        BlockHeadersMessage, BlockWithTrustedDataV4Message, DoneBlocksWithTrustedDataMessage, DoneHeadersMessage,
+        DonePruningPointUtxoSetChunksMessage, KaspadMessage, PruningPointProofMessage, PruningPointUtxoSetChunkMessage,
+        PruningPointsMessage, SmtLaneChunkMessage, TrustedDataMessage, kaspad_message::Payload,
+    },
+};
+use prost::Message;
+use std::{
+    cmp::max,
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+/// File magic identifying a pruning point snapshot
+const SNAPSHOT_MAGIC: &[u8; 8] = b"KASPASNP";
+/// Snapshot format version. Bump on any change to the frame sequence
+const SNAPSHOT_VERSION: u32 = 1;
+/// Headers are always written in the compressed format, regardless of the peer protocol versions in use
+const SNAPSHOT_HEADER_FORMAT: HeaderFormat = HeaderFormat::Compressed;
+/// Upper bound for a single frame, matching the maximum P2P message size
+const MAX_FRAME_SIZE: u32 = 1024 * 1024 * 1024;
+/// Maximum length of the network name stored in the preamble
+const MAX_NETWORK_NAME_LEN: u32 = 64;
+/// Number of UTXO entries per `PruningPointUtxoSetChunk` frame
+const UTXO_CHUNK_SIZE: usize = 1000;
+/// Number of decoded frames the reader thread may buffer ahead of the importer
+const READ_AHEAD_FRAMES: usize = 8;
+
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    #[error("snapshot I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("snapshot frame decoding error: {0}")]
+    Decode(#[from] prost::DecodeError),
+
+    #[error("invalid snapshot: {0}")]
+    InvalidFormat(&'static str),
+
+    #[error("invalid snapshot: {0}")]
+    InvalidFormatOwned(String),
+
+    #[error("snapshot was created for network {0} but this node runs on {1}")]
+    NetworkMismatch(String, String),
+
+    #[error("snapshot digest mismatch: expected {0}, computed {1}")]
+    DigestMismatch(Hash, Hash),
+
+    #[error("expected snapshot message {0} but got {1:?}")]
+    UnexpectedMessage(&'static str, KaspadMessagePayloadType),
+
+    #[error("{0}")]
+    Protocol(#[from] ProtocolError),
+
+    #[error("{0}")]
+    Conversion(#[from] ConversionError),
+
+    #[error("{0}")]
+    Consensus(#[from] ConsensusError),
+
+    #[error("{0}")]
+    Rule(#[from] RuleError),
+
+    #[error("{0}")]
+    PruningImport(#[from] PruningImportError),
+}
+
+pub type SnapshotResult<T> = std::result::Result<T, SnapshotError>;
+
+/// Summary of an exported or imported snapshot
+#[derive(Debug, Clone)]
+pub struct SnapshotSummary {
+    pub pruning_point: Hash,
+    pub sink: Hash,
+    pub header_count: usize,
+    pub smt_lane_count: u64,
+    pub utxo_count: usize,
+    pub digest: Hash,
+}
+
+/// Writes the snapshot preamble and frames while hashing every written byte
+struct SnapshotWriter {
+    writer: BufWriter<File>,
+    hasher: PruningPointSnapshotHash,
+}
+
+impl SnapshotWriter {
+    fn create(path: &Path, network: &str) -> SnapshotResult<Self> {
+        let mut writer = Self { writer: BufWriter::new(File::create(path)?), hasher: PruningPointSnapshotHash::new() };
+        writer.write_bytes(SNAPSHOT_MAGIC)?;
+        writer.write_bytes(&SNAPSHOT_VERSION.to_le_bytes())?;
+        writer.write_bytes(&(network.len() as u32).to_le_bytes())?;
+        writer.write_bytes(network.as_bytes())?;
+        Ok(writer)
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) -> SnapshotResult<()> {
+        self.hasher.write(bytes);
+        self.writer.write_all(bytes)?;
+        Ok(())
+    }
+
+    fn write(&mut self, payload: Payload) -> SnapshotResult<()> {
+        let bytes = KaspadMessage { payload: Some(payload), ..Default::default() }.encode_to_vec();
+        if bytes.is_empty() || bytes.len() > MAX_FRAME_SIZE as usize {
+            return Err(SnapshotError::InvalidFormatOwned(format!("frame size {} is out of bounds", bytes.len())));
+        }
+        self.write_bytes(&(bytes.len() as u32).to_le_bytes())?;
+        self.write_bytes(&bytes)
+    }
+
+    /// Writes the end marker followed by the digest of all preceding bytes and flushes the file
+    fn finish(mut self) -> SnapshotResult<Hash> {
+        self.write_bytes(&0u32.to_le_bytes())?;
+        let digest = self.hasher.finalize();
+        self.writer.write_all(&digest.as_bytes())?;
+        self.writer.into_inner().map_err(|err| err.into_error())?.sync_all()?;
+        Ok(digest)
+    }
+}
+
+/// Reads the snapshot preamble and frames while hashing every read byte. The digest is verified
+/// once the end marker is reached, so a fully consumed reader implies an intact file.
+struct SnapshotReader {
+    reader: BufReader<File>,
+    hasher: PruningPointSnapshotHash,
+    network: String,
+    digest: Option<Hash>,
+}
+
+impl SnapshotReader {
+    fn open(path: &Path) -> SnapshotResult<Self> {
+        let mut reader = Self {
+            reader: BufReader::new(File::open(path)?),
+            hasher: PruningPointSnapshotHash::new(),
+            network: String::new(),
+            digest: None,
+        };
+        let mut magic = [0u8; 8];
+        reader.read_bytes(&mut magic)?;
+        if &magic != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::InvalidFormat("not a pruning point snapshot file"));
+        }
+        let version = reader.read_u32()?;
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::InvalidFormatOwned(format!(
+                "unsupported snapshot version {} (expected {})",
+                version, SNAPSHOT_VERSION
+            )));
+        }
+        let network_len = reader.read_u32()?;
+        if network_len > MAX_NETWORK_NAME_LEN {
+            return Err(SnapshotError::InvalidFormat("network name is too long"));
+        }
+        let mut network = vec![0u8; network_len as usize];
+        reader.read_bytes(&mut network)?;
+        reader.network = String::from_utf8(network).map_err(|_| SnapshotError::InvalidFormat("network name is not valid UTF-8"))?;
+        Ok(reader)
+    }
+
+    fn read_bytes(&mut self, buf: &mut [u8]) -> SnapshotResult<()> {
+        self.reader.read_exact(buf)?;
+        self.hasher.write(&*buf);
+        Ok(())
+    }
+
+    fn read_u32(&mut self) -> SnapshotResult<u32> {
+        let mut buf = [0u8; 4];
+        self.read_bytes(&mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    /// Returns the next raw frame, or `None` once the end marker was reached and the digest verified
+    fn next_frame(&mut self) -> SnapshotResult<Option<Vec<u8>>> {
+        if self.digest.is_some() {
+            return Ok(None);
+        }
+        let len = self.read_u32()?;
+        if len == 0 {
+            self.verify_digest()?;
+            return Ok(None);
+        }
+        if len > MAX_FRAME_SIZE {
+            return Err(SnapshotError::InvalidFormatOwned(format!("frame size {} exceeds the maximum of {}", len, MAX_FRAME_SIZE)));
+        }
+        let mut frame = vec![0u8; len as usize];
+        self.read_bytes(&mut frame)?;
+        Ok(Some(frame))
+    }
+
+    fn next_payload(&mut self) -> SnapshotResult<Option<Payload>> {
+        match self.next_frame()? {
+            Some(frame) => {
+                KaspadMessage::decode(frame.as_slice())?.payload.map(Some).ok_or(SnapshotError::InvalidFormat("frame without payload"))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn verify_digest(&mut self) -> SnapshotResult<()> {
+        let computed = std::mem::take(&mut self.hasher).finalize();
+        let mut expected = [0u8; 32];
+        self.reader.read_exact(&mut expected)?;
+        let expected = Hash::from_bytes(expected);
+        if expected != computed {
+            return Err(SnapshotError::DigestMismatch(expected, computed));
+        }
+        if self.reader.read(&mut [0u8; 1])? != 0 {
+            return Err(SnapshotError::InvalidFormat("trailing bytes after the snapshot digest"));
+        }
+        self.digest = Some(computed);
+        Ok(())
+    }
+}
+
+/// Reads the whole snapshot file and verifies its digest without decoding any frame
+fn verify_snapshot_digest(path: &Path) -> SnapshotResult<Hash> {
+    let mut reader = SnapshotReader::open(path)?;
+    while reader.next_frame()?.is_some() {}
+    Ok(reader.digest.expect("set once frames are exhausted"))
+}
+
+/// Async facade over a [`SnapshotReader`] running on a blocking thread
+struct SnapshotStream {
+    receiver: mpsc::Receiver<SnapshotResult<Payload>>,
+}
+
+impl SnapshotStream {
+    fn new(mut reader: SnapshotReader) -> Self {
+        let (sender, receiver) = mpsc::channel(READ_AHEAD_FRAMES);
+        spawn_blocking(move || {
+            loop {
+                match reader.next_payload() {
+                    Ok(Some(payload)) => {
+                        if sender.blocking_send(Ok(payload)).is_err() {
+                            return;
+                        }
+                    }
+                    Ok(None) => return,
+                    Err(err) => {
+                        let _ = sender.blocking_send(Err(err));
+                        return;
+                    }
+                }
+            }
+        });
+        Self { receiver }
+    }
+
+    async fn next(&mut self) -> SnapshotResult<Payload> {
+        self.receiver.recv().await.unwrap_or(Err(SnapshotError::InvalidFormat("unexpected end of snapshot")))
+    }
+
+    /// Ensures the snapshot holds no further frames and its digest was verified
+    async fn finish(mut self) -> SnapshotResult<()> {
+        match self.receiver.recv().await {
+            None => Ok(()),
+            Some(Err(err)) => Err(err),
+            Some(Ok(_)) => Err(SnapshotError::InvalidFormat("unexpected frames after the pruning point UTXO set")),
+        }
+    }
+}
+
+/// Extracts the expected payload variant from the next snapshot frame
+macro_rules! expect_payload {
+    ($stream:expr, $pattern:path) => {{
+        match $stream.next().await? {
+            $pattern(payload) => payload,
+            other => return Err(SnapshotError::UnexpectedMessage(stringify!($pattern), (&other).into())),
+        }
+    }};
+}
+
+/// Exports a snapshot of the current pruning point state to `path`. The session is held for the whole export
+/// so that the consensus instance cannot be replaced midway.
+pub async fn export_pruning_point_snapshot(ctx: &FlowContext, path: PathBuf) -> SnapshotResult<SnapshotSummary> {
+    let config = ctx.config.clone();
+    let session = ctx.consensus().session().await;
+    info!("Exporting pruning point snapshot to {}", path.display());
+    let summary = session.spawn_blocking(move |c| export_snapshot(c, &config, &path)).await?;
+    info!(
+        "Exported pruning point snapshot of {} with {} headers, {} SMT lanes and {} UTXOs (digest {})",
+        summary.pruning_point, summary.header_count, summary.smt_lane_count, summary.utxo_count, summary.digest
+    );
+    Ok(summary)
+}
+
+fn export_snapshot(consensus: &dyn ConsensusApi, config: &Config, path: &Path) -> SnapshotResult<SnapshotSummary> {
+    if !consensus.is_pruning_utxoset_stable() || !consensus.is_pruning_smt_stable() {
+        return Err(SnapshotError::InvalidFormat("the pruning point UTXO set or SMT state is not stable yet"));
+    }
+    let pruning_point = consensus.pruning_point();
+    if pruning_point == config.genesis.hash {
+        return Err(SnapshotError::InvalidFormat("the pruning point is still genesis, there is nothing to export"));
+    }
+    let pp_header = consensus.get_header(pruning_point)?;
+    let sink = consensus.get_sink();
+
+    // Write to a temporary file first so that an interrupted export never leaves a truncated snapshot behind
+    let partial_path = path.with_extension("partial");
+    let mut writer = SnapshotWriter::create(&partial_path, &config.net.to_string())?;
+
+    // The sink header stands in for the relay block of a regular IBD
+    let sink_header = consensus.get_header(sink)?;
+    writer
+        .write(Payload::BlockHeaders(BlockHeadersMessage { block_headers: vec![(SNAPSHOT_HEADER_FORMAT, &*sink_header).into()] }))?;
+
+    let proof = consensus.get_pruning_point_proof();
+    writer.write(Payload::PruningPointProof(PruningPointProofMessage {
+        headers: proof.iter().map(|headers| (SNAPSHOT_HEADER_FORMAT, headers).into()).collect(),
+    }))?;
+
+    writer.write(Payload::PruningPoints(PruningPointsMessage {
+        headers: consensus.pruning_point_headers().into_iter().map(|header| (SNAPSHOT_HEADER_FORMAT, &*header).into()).collect(),
+    }))?;
+
+    let trusted_data = consensus.get_pruning_point_anticone_and_trusted_data()?;
+    writer.write(Payload::TrustedData(TrustedDataMessage {
+        daa_window: trusted_data.daa_window_blocks.iter().map(|daa_block| (SNAPSHOT_HEADER_FORMAT, daa_block).into()).collect_vec(),
+        ghostdag_data: trusted_data.ghostdag_blocks.iter().map(|gd| gd.into()).collect_vec(),
+    }))?;
+    for &hash in trusted_data.anticone.iter() {
+        let block = consensus.get_block(hash)?;
+        writer.write(Payload::BlockWithTrustedDataV4(BlockWithTrustedDataV4Message {
+            block: Some((SNAPSHOT_HEADER_FORMAT, &block).into()),
+            ..Default::default()
+        }))?;
+    }
+    for &hash in trusted_data.header_only_chain_segment.iter() {
+        let block = Block::from_header_arc(consensus.get_header(hash)?);
+        writer.write(Payload::BlockWithTrustedDataV4(BlockWithTrustedDataV4Message {
+            block: Some((SNAPSHOT_HEADER_FORMAT, &block).into()),
+            ..Default::default()
+        }))?;
+    }
+    writer.write(Payload::DoneBlocksWithTrustedData(DoneBlocksWithTrustedDataMessage {}))?;
+
+    // Internal consensus logic requires that `max_blocks > mergeset_size_limit`
+    let max_blocks = max(1 << 10, config.mergeset_size_limit() as usize + 1);
+    let mut header_count = 0;
+    let mut low = pruning_point;
+    while low != sink {
+        let hashes = consensus.get_hashes_between(low, sink, max_blocks)?.0;
+        low = *hashes.last().expect("low and sink are valid and different");
+        header_count += hashes.len();
+        let block_headers = hashes
+            .into_iter()
+            .map(|hash| consensus.get_header(hash).map(|header| (SNAPSHOT_HEADER_FORMAT, &*header).into()))
+            .collect::<Result<Vec<_>, _>>()?;
+        writer.write(Payload::BlockHeaders(BlockHeadersMessage { block_headers }))?;
+    }
+    writer.write(Payload::DoneHeaders(DoneHeadersMessage {}))?;
+
+    let mut smt_lane_count = 0u64;
+    if config.toccata_activation.is_active(pp_header.daa_score) {
+        let metadata = consensus.get_pruning_point_smt_metadata(pruning_point)?;
+        writer.write(Payload::SmtMetadata(smt_metadata_to_message(&metadata)))?;
+        let mut entries = Vec::with_capacity(SMT_CHUNK_SIZE);
+        for lane in consensus.open_pruning_point_smt_lane_stream(pruning_point)? {
+            entries.push(import_lane_to_entry(lane?));
+            smt_lane_count += 1;
+            if entries.len() == SMT_CHUNK_SIZE {
+                writer.write(Payload::SmtLaneChunk(SmtLaneChunkMessage { entries: std::mem::take(&mut entries) }))?;
+            }
+        }
+        if !entries.is_empty() {
+            writer.write(Payload::SmtLaneChunk(SmtLaneChunkMessage { entries }))?;
+        }
+        if smt_lane_count != metadata.active_lanes_count {
+            return Err(SnapshotError::InvalidFormatOwned(format!(
+                "SMT lane stream yielded {} lanes while metadata declares {}",
+                smt_lane_count, metadata.active_lanes_count
+            )));
+        }
+    }
+
+    let mut utxo_count = 0;
+    let mut from_outpoint = None;
+    loop {
+        let utxos = consensus.get_pruning_point_utxos(pruning_point, from_outpoint, UTXO_CHUNK_SIZE, from_outpoint.is_some())?;
+        utxo_count += utxos.len();
+        writer.write(Payload::PruningPointUtxoSetChunk(PruningPointUtxoSetChunkMessage {
+            outpoint_and_utxo_entry_pairs: utxos.iter().map(|(outpoint, entry)| (outpoint, entry).into()).collect_vec(),
+        }))?;
+        if utxos.len() < UTXO_CHUNK_SIZE {
+            break;
+        }
+        from_outpoint = Some(utxos.last().expect("chunk is full").0);
+    }
+    writer.write(Payload::DonePruningPointUtxoSetChunks(DonePruningPointUtxoSetChunksMessage {}))?;
+
+    // The UTXO and SMT readers already check the pruning point per call, this covers the remaining frames
+    if consensus.pruning_point() != pruning_point {
+        return Err(ConsensusError::UnexpectedPruningPoint.into());
+    }
+
+    let digest = writer.finish()?;
+    fs::rename(&partial_path, path)?;
+    Ok(SnapshotSummary { pruning_point, sink, header_count, smt_lane_count, utxo_count, digest })
+}
+
+/// Imports the snapshot at `path` into a staging consensus, committed once the pruning proof and headers were
+/// validated. The SMT state and UTXO set are then checked against the pruning point commitments; if either check
+/// fails, the node is left in the transitional IBD state, as after a failed IBD from a peer, and recovers through
+/// a regular IBD. Must run before any peer is able to trigger IBD.
+pub async fn import_pruning_point_snapshot(ctx: &FlowContext, path: PathBuf) -> SnapshotResult<SnapshotSummary> {
+    info!("Verifying pruning point snapshot {}", path.display());
+    let digest = {
+        let path = path.clone();
+        spawn_blocking(move || verify_snapshot_digest(&path)).await.unwrap()?
+    };
+    info!("Snapshot digest {} verified, importing", digest);
+
+    let reader = {
+        let path = path.clone();
+        spawn_blocking(move || SnapshotReader::open(&path)).await.unwrap()?
+    };
+    let network = ctx.config.net.to_string();
+    if reader.network != network {
+        return Err(SnapshotError::NetworkMismatch(reader.network, network));
+    }
+    let mut stream = SnapshotStream::new(reader);
+
+    let sink_header = {
+        let headers: HeadersChunk = Versioned(SNAPSHOT_HEADER_FORMAT, expect_payload!(stream, Payload::BlockHeaders)).try_into()?;
+        match headers.as_slice() {
+            [header] => header.clone(),
+            _ => return Err(SnapshotError::InvalidFormat("expected a single sink header")),
+        }
+    };
+
+    let staging = ctx.consensus_manager.new_staging_consensus();
+    let (pp_header, header_count) = match import_headers_into_staging(ctx, &staging, &mut stream, &sink_header).await {
+        Ok(res) => res,
+        Err(err) => {
+            staging.cancel();
+            return Err(err);
+        }
+    };
+    info!("Committing staging consensus at pruning point {}", pp_header.hash);
+    spawn_blocking(|| staging.commit()).await.unwrap();
+
+    let session = ctx.consensus().session().await;
+    let smt_lane_count = import_smt_state(ctx, &session, &mut stream, &pp_header).await?;
+    let utxo_count = import_utxo_set(ctx, &session, &mut stream, pp_header.hash).await?;
+    stream.finish().await?;
+
+    info!(
+        "Imported pruning point snapshot of {} with {} headers, {} SMT lanes and {} UTXOs",
+        pp_header.hash, header_count, smt_lane_count, utxo_count
+    );
+    Ok(SnapshotSummary { pruning_point: pp_header.hash, sink: sink_header.hash, header_count, smt_lane_count, utxo_count, digest })
+}
+
+/// Mirrors the headers-proof IBD path: validates the proof against current consensus, then applies it along
+/// with the past pruning points, the trusted anticone and the headers up to the sink onto the staging consensus
+async fn import_headers_into_staging(
+    ctx: &FlowContext,
+    staging: &StagingConsensus,
+    stream: &mut SnapshotStream,
+    sink_header: &Arc<Header>,
+) -> SnapshotResult<(Arc<Header>, usize)> {
+    let proof: PruningPointProof =
+        Versioned(SNAPSHOT_HEADER_FORMAT, expect_payload!(stream, Payload::PruningPointProof)).try_into()?;
+    let proof_metadata = PruningProofMetadata::new(sink_header.blue_work);
+
+    // The proof is validated in the context of current consensus
+    let consensus = ctx.consensus().session().await;
+    let proof = consensus.clone().spawn_blocking(move |c| c.validate_pruning_proof(&proof, &proof_metadata).map(|()| proof)).await?;
+    let pp_header = proof[0].last().expect("was just ensured by validation").clone();
+    let pruning_point = pp_header.hash;
+    if pruning_point == ctx.config.genesis.hash {
+        return Err(SnapshotError::InvalidFormat("the snapshot pruning point is the genesis block"));
+    }
+    if pruning_point == consensus.async_pruning_point().await {
+        return Err(SnapshotError::InvalidFormat("the snapshot pruning point is the same as the current pruning point"));
+    }
+
+    let pruning_points: PruningPointsList =
+        Versioned(SNAPSHOT_HEADER_FORMAT, expect_payload!(stream, Payload::PruningPoints)).try_into()?;
+    if pruning_points.is_empty() || pruning_points.last().unwrap().hash != pruning_point {
+        return Err(SnapshotError::InvalidFormat("the proof pruning point is not equal to the last pruning point in the list"));
+    }
+    if pruning_points.first().unwrap().hash != ctx.config.genesis.hash {
+        return Err(SnapshotError::InvalidFormat("the first pruning point in the list is expected to be genesis"));
+    }
+    if consensus.async_are_pruning_points_violating_finality(pruning_points.clone()).await {
+        return Err(SnapshotError::InvalidFormat("pruning points are violating finality"));
+    }
+    drop(consensus);
+
+    let pruning_points_set: BlockHashSet = pruning_points.iter().map(|h| h.hash).collect();
+    if proof
+        .iter()
+        .filter_map(|level| level.first())
+        .any(|root| root.hash != ctx.config.genesis.hash && !pruning_points_set.contains(&root.pruning_point))
+    {
+        return Err(SnapshotError::InvalidFormat("proof and past pruning points are inconsistent with each other"));
+    }
+
+    let pkg: TrustedDataPackage = Versioned(SNAPSHOT_HEADER_FORMAT, expect_payload!(stream, Payload::TrustedData)).try_into()?;
+    let mut entries = Vec::new();
+    let mut header_only_chain_segment = Vec::new();
+    loop {
+        match stream.next().await? {
+            Payload::BlockWithTrustedDataV4(payload) => {
+                let entry: TrustedDataEntry = Versioned(SNAPSHOT_HEADER_FORMAT, payload).try_into()?;
+                match entry.block.is_header_only() {
+                    true => header_only_chain_segment.push(entry.block.header.clone()),
+                    false if header_only_chain_segment.is_empty() => entries.push(entry),
+                    false => return Err(SnapshotError::InvalidFormat("trusted body entries follow header-only trusted entries")),
+                }
+            }
+            Payload::DoneBlocksWithTrustedData(_) => break,
+            other => {
+                return Err(SnapshotError::UnexpectedMessage(
+                    stringify!(Payload::BlockWithTrustedDataV4 | Payload::DoneBlocksWithTrustedData),
+                    (&other).into(),
+                ));
+            }
+        }
+    }
+    // The first entry of the trusted data is the pruning point itself
+    match entries.first() {
+        Some(entry) if entry.block.hash() == pruning_point => {}
+        _ => return Err(SnapshotError::InvalidFormat("the first trusted entry is expected to be the pruning point")),
+    }
+    info!("Read {} pruning point anticone blocks and {} chain segment headers", entries.len(), header_only_chain_segment.len());
+
+    let staging_session = staging.session().await;
+    let trusted_set = pkg.build_trusted_subdag(entries)?;
+    let trusted_set = staging_session
+        .clone()
+        .spawn_blocking(move |c| {
+            c.apply_pruning_proof(proof, &trusted_set, &header_only_chain_segment)?;
+            c.import_pruning_points(pruning_points)?;
+            SnapshotResult::Ok(trusted_set)
+        })
+        .await?;
+    info!("Processing {} trusted blocks", trusted_set.len());
+    for tb in trusted_set {
+        staging_session.validate_and_insert_trusted_block(tb).virtual_state_task.await?;
+    }
+    staging_session.async_clear_body_missing_anticone_set().await;
+
+    let mut progress_reporter = ProgressReporter::new(pp_header.daa_score, sink_header.daa_score, "block headers");
+    let mut header_count = 0;
+    loop {
+        match stream.next().await? {
+            Payload::BlockHeaders(payload) => {
+                let chunk: HeadersChunk = Versioned(SNAPSHOT_HEADER_FORMAT, payload).try_into()?;
+                let Some(last_header) = chunk.last().cloned() else {
+                    return Err(SnapshotError::InvalidFormat("empty headers frame"));
+                };
+                let chunk_len = chunk.len();
+                try_join_all(
+                    chunk.into_iter().map(|h| staging_session.validate_and_insert_block(Block::from_header_arc(h)).virtual_state_task),
+                )
+                .await?;
+                header_count += chunk_len;
+                progress_reporter.report(chunk_len, last_header.daa_score, last_header.timestamp);
+            }
+            Payload::DoneHeaders(_) => break,
+            other => {
+                return Err(SnapshotError::UnexpectedMessage(
+                    stringify!(Payload::BlockHeaders | Payload::DoneHeaders),
+                    (&other).into(),
+                ));
+            }
+        }
+    }
+    progress_reporter.report_completion(0);
+
+    if staging_session.async_get_block_status(sink_header.hash).await.is_none() {
+        return Err(SnapshotError::InvalidFormatOwned(format!(
+            "the snapshot does not contain the header of its sink {}",
+            sink_header.hash
+        )));
+    }
+    staging_session.async_validate_pruning_points(sink_header.hash).await?;
+    Ok((pp_header, header_count))
+}
+
+async fn import_smt_state(
+    ctx: &FlowContext,
+    consensus: &ConsensusProxy,
+    stream: &mut SnapshotStream,
+    pp_header: &Header,
+) -> SnapshotResult<u64> {
+    if !ctx.config.toccata_activation.is_active(pp_header.daa_score) {
+        consensus.async_set_pruning_smt_stable().await;
+        return Ok(0);
+    }
+    consensus.async_clear_pruning_smt_stores().await;
+
+    let md = smt_metadata_from_message(&expect_payload!(stream, Payload::SmtMetadata))?;
+    let shortcut_block = verify_pruning_point_smt_metadata(&ctx.config, consensus, pp_header, &md).await?;
+
+    let (tx, rx) = mpsc::channel::<Vec<ImportLane>>(2);
+    let consensus_for_import = consensus.clone();
+    let pruning_point = pp_header.hash;
+    let builder_handle = spawn_blocking(move || consensus_for_import.import_pruning_point_smt(pruning_point, md, shortcut_block, rx));
+
+    let mut lane_count = 0u64;
+    while lane_count < md.active_lanes_count {
+        let payload = expect_payload!(stream, Payload::SmtLaneChunk);
+        if payload.entries.is_empty() || payload.entries.len() as u64 > md.active_lanes_count - lane_count {
+            return Err(SnapshotError::InvalidFormatOwned(format!(
+                "SMT lane chunk of {} entries does not fit the {} remaining lanes",
+                payload.entries.len(),
+                md.active_lanes_count - lane_count
+            )));
+        }
+        let mut lanes = Vec::with_capacity(payload.entries.len());
+        for entry in payload.entries {
+            lanes.push(import_lane_from_entry(entry, (lane_count as usize).is_multiple_of(SMT_PROOF_INTERVAL))?);
+            lane_count += 1;
+        }
+        if tx.send(lanes).await.is_err() {
+            // The importer bailed out, its error is reported below
+            break;
+        }
+    }
+    drop(tx);
+
+    builder_handle.await.unwrap()?;
+    consensus.async_set_pruning_smt_stable().await;
+    debug!("Imported {} SMT lanes", lane_count);
+    Ok(lane_count)
+}
+
+async fn import_utxo_set(
+    ctx: &FlowContext,
+    consensus: &ConsensusProxy,
+    stream: &mut SnapshotStream,
+    pruning_point: Hash,
+) -> SnapshotResult<usize> {
+    let utxo_count = import_utxo_chunks(consensus, stream, pruning_point).await?;
+    consensus.async_set_pruning_utxoset_stable().await;
+
+    let consensus_manager = ctx.consensus_manager.clone();
+    spawn_blocking(move || consensus_manager.invoke_consensus_reset_handlers()).await.unwrap();
+    ctx.on_pruning_point_utxoset_override();
+    Ok(utxo_count)
+}
+
+/// Streams the UTXO set chunks into consensus and verifies their MuHash against the UTXO commitment of the pruning point
+async fn import_utxo_chunks(consensus: &ConsensusProxy, stream: &mut SnapshotStream, pruning_point: Hash) -> SnapshotResult<usize> {
+    consensus.async_clear_pruning_utxo_set().await;
+    let mut utxo_count = 0;
+    let mut multiset = MuHash::new();
+    loop {
+        match stream.next().await? {
+            Payload::PruningPointUtxoSetChunk(payload) => {
+                let chunk: UtxosetChunk = payload.try_into()?;
+                utxo_count += chunk.len();
+                multiset = consensus
+                    .clone()
+                    .spawn_blocking(move |c| {
+                        c.append_imported_pruning_point_utxos(&chunk, &mut multiset);
+                        multiset
+                    })
+                    .await;
+            }
+            Payload::DonePruningPointUtxoSetChunks(_) => break,
+            other => {
+                return Err(SnapshotError::UnexpectedMessage(
+                    stringify!(Payload::PruningPointUtxoSetChunk | Payload::DonePruningPointUtxoSetChunks),
+                    (&other).into(),
+                ));
+            }
+        }
+    }
+    consensus.clone().spawn_blocking(move |c| c.import_pruning_point_utxo_set(pruning_point, multiset)).await?;
+    Ok(utxo_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        errors::pruning::PruningImportResult,
+        muhash::MuHashExtensions,
+        tx::{ScriptPublicKey, TransactionOutpoint, UtxoEntry},
+    };
+    use kaspa_consensusmanager::{ConsensusInstance, SessionLock};
+
+    fn write_sample(path: &Path) -> Hash {
+        let mut writer = SnapshotWriter::create(path, "kaspa-simnet").unwrap();
+        writer.write(Payload::DoneHeaders(DoneHeadersMessage {})).unwrap();
+        writer.write(Payload::DoneBlocksWithTrustedData(DoneBlocksWithTrustedDataMessage {})).unwrap();
+        writer.finish().unwrap()
+    }
+
+    fn write_utxo_set(path: &Path, utxos: &[(TransactionOutpoint, UtxoEntry)]) {
+        let mut writer = SnapshotWriter::create(path, "kaspa-simnet").unwrap();
+        writer
+            .write(Payload::PruningPointUtxoSetChunk(PruningPointUtxoSetChunkMessage {
+                outpoint_and_utxo_entry_pairs: utxos.iter().map(|(outpoint, entry)| (outpoint, entry).into()).collect_vec(),
+            }))
+            .unwrap();
+        writer.write(Payload::DonePruningPointUtxoSetChunks(DonePruningPointUtxoSetChunksMessage {})).unwrap();
+        writer.finish().unwrap();
+    }
+
+    /// Consensus checking the imported UTXO set against the commitment of the pruning point, as the virtual processor does
+    struct UtxoCommitmentConsensus {
+        pruning_point: Hash,
+        utxo_commitment: Hash,
+    }
+
+    impl ConsensusApi for UtxoCommitmentConsensus {
+        fn clear_pruning_utxo_set(&self) {}
+
+        fn append_imported_pruning_point_utxos(
+            &self,
+            utxoset_chunk: &[(TransactionOutpoint, UtxoEntry)],
+            current_multiset: &mut MuHash,
+        ) {
+            utxoset_chunk.iter().for_each(|(outpoint, entry)| current_multiset.add_utxo(outpoint, entry));
+        }
+
+        fn import_pruning_point_utxo_set(
+            &self,
+            new_pruning_point: Hash,
+            mut imported_utxo_multiset: MuHash,
+        ) -> PruningImportResult<()> {
+            assert_eq!(new_pruning_point, self.pruning_point);
+            let imported = imported_utxo_multiset.finalize();
+            if imported != self.utxo_commitment {
+                return Err(PruningImportError::ImportedMultisetHashMismatch(self.utxo_commitment, imported));
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_snapshot_frames_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("roundtrip.snapshot");
+        let digest = write_sample(&path);
+
+        assert_eq!(verify_snapshot_digest(&path).unwrap(), digest);
+        let mut reader = SnapshotReader::open(&path).unwrap();
+        assert_eq!(reader.network, "kaspa-simnet");
+        assert!(matches!(reader.next_payload().unwrap(), Some(Payload::DoneHeaders(_))));
+        assert!(matches!(reader.next_payload().unwrap(), Some(Payload::DoneBlocksWithTrustedData(_))));
+        assert!(reader.next_payload().unwrap().is_none());
+        assert_eq!(reader.digest, Some(digest));
+    }
+
+    #[test]
+    fn test_snapshot_corruption_is_detected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("corrupted.snapshot");
+        write_sample(&path);
+
+        let mut bytes = fs::read(&path).unwrap();
+        let frame_offset = SNAPSHOT_MAGIC.len() + 4 + 4 + "kaspa-simnet".len() + 4;
+        bytes[frame_offset] ^= 0xff;
+        fs::write(&path, &bytes).unwrap();
+        assert!(matches!(verify_snapshot_digest(&path), Err(SnapshotError::DigestMismatch(_, _))));
+
+        bytes[frame_offset] ^= 0xff;
+        bytes.push(0);
+        fs::write(&path, &bytes).unwrap();
+        assert!(matches!(verify_snapshot_digest(&path), Err(SnapshotError::InvalidFormat(_))));
+
+        bytes[0] = b'X';
+        fs::write(&path, &bytes).unwrap();
+        assert!(matches!(SnapshotReader::open(&path), Err(SnapshotError::InvalidFormat(_))));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_utxo_set_commitment_is_verified() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("utxos.snapshot");
+        let utxos = (0..3u64)
+            .map(|i| {
+                let entry = UtxoEntry::new(1000 + i, ScriptPublicKey::from_vec(0, vec![0xac]), 10, i == 0, None);
+                (TransactionOutpoint::new(Hash::from_u64_word(i), 0), entry)
+            })
+            .collect_vec();
+        let mut multiset = MuHash::new();
+        utxos.iter().for_each(|(outpoint, entry)| multiset.add_utxo(outpoint, entry));
+        let pruning_point = Hash::from_u64_word(42);
+        let consensus = UtxoCommitmentConsensus { pruning_point, utxo_commitment: multiset.finalize() };
+        let session = ConsensusInstance::new(SessionLock::new(), Arc::new(consensus)).session().await;
+
+        let import = |utxos: &[(TransactionOutpoint, UtxoEntry)]| {
+            write_utxo_set(&path, utxos);
+            let mut stream = SnapshotStream::new(SnapshotReader::open(&path).unwrap());
+            let session = session.clone();
+            async move { import_utxo_chunks(&session, &mut stream, pruning_point).await }
+        };
+        assert_eq!(import(&utxos).await.unwrap(), utxos.len());
+
+        // Snapshots with an intact digest but a UTXO set not matching the pruning point commitment are rejected
+        let mut tampered = utxos.clone();
+        tampered[1].1.amount += 1;
+        for utxos in [&tampered[..], &utxos[..2]] {
+            assert!(matches!(
+                import(utxos).await,
+                Err(SnapshotError::PruningImport(PruningImportError::ImportedMultisetHashMismatch(_, _)))
+            ));
+        }
+    }
+}
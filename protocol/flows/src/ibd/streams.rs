@@ -3,6 +3,7 @@
 //!
 
 use kaspa_consensus_core::{
+    api::{ImportLane, SmtExportMetadata},
     errors::consensus::ConsensusError,
     header::Header,
     tx::{TransactionOutpoint, UtxoEntry},
@@ -16,7 +17,7 @@ use kaspa_p2p_lib::{
     make_message,
     pb::{
         RequestNextHeadersMessage, RequestNextPruningPointAndItsAnticoneBlocksMessage, RequestNextPruningPointSmtChunkMessage,
-        RequestNextPruningPointUtxoSetChunkMessage, kaspad_message::Payload,
+        RequestNextPruningPointUtxoSetChunkMessage, SmtLaneEntry, SmtMetadataMessage, kaspad_message::Payload,
     },
 };
 use std::sync::Arc;
@@ -215,6 +216,74 @@ impl<'a, 'b> PruningPointUtxosetChunkStream<'a, 'b> {
 
 const SMT_PROOF_INTERVAL: usize = kaspa_consensus_core::api::SMT_PROOF_INTERVAL;
 
+/// Encodes SMT metadata into its wire message.
+///
+/// Wire: 96 bytes = lanes_root || payload_and_ctx_digest || parent_seq_commit.
+pub(crate) fn smt_metadata_to_message(metadata: &SmtExportMetadata) -> SmtMetadataMessage {
+    let mut data = Vec::with_capacity(96);
+    data.extend_from_slice(&metadata.lanes_root.as_bytes());
+    data.extend_from_slice(&metadata.payload_and_ctx_digest.as_bytes());
+    data.extend_from_slice(&metadata.parent_seq_commit.as_bytes());
+    SmtMetadataMessage { data, active_lanes_count: metadata.active_lanes_count }
+}
+
+/// Decodes SMT metadata from its wire message. See [`smt_metadata_to_message`] for the layout.
+pub(crate) fn smt_metadata_from_message(payload: &SmtMetadataMessage) -> Result<SmtExportMetadata, ProtocolError> {
+    let (chunks, rem) = payload.data.as_chunks::<32>();
+    if !rem.is_empty() {
+        return Err(ProtocolError::Other("SmtMetadata data length not a multiple of 32 bytes"));
+    }
+    match *chunks {
+        [lanes_root, payload_and_ctx_digest, parent_seq_commit] => {
+            let [lanes_root, payload_and_ctx_digest, parent_seq_commit] =
+                [lanes_root, payload_and_ctx_digest, parent_seq_commit].map(Hash::from_bytes);
+            Ok(SmtExportMetadata {
+                lanes_root,
+                payload_and_ctx_digest,
+                parent_seq_commit,
+                active_lanes_count: payload.active_lanes_count,
+            })
+        }
+        _ => Err(ProtocolError::Other("SmtMetadata data must be 96 bytes")),
+    }
+}
+
+/// Encodes a single lane into its wire entry (`lane_key || lane_tip`, plus optional proof bytes).
+pub(crate) fn import_lane_to_entry(lane: ImportLane) -> SmtLaneEntry {
+    let mut data = Vec::with_capacity(64);
+    data.extend_from_slice(&lane.lane_key.as_bytes());
+    data.extend_from_slice(&lane.lane_tip.as_bytes());
+    let proof = lane.proof.as_ref().map(|p| p.to_bytes()).unwrap_or_default();
+    SmtLaneEntry { data, blue_score: lane.blue_score, proof }
+}
+
+/// Decodes a single lane from its wire entry. The proof is parsed only when `with_proof` is set,
+/// i.e., for the first and every [`SMT_PROOF_INTERVAL`]-th lane of the stream.
+pub(crate) fn import_lane_from_entry(entry: SmtLaneEntry, with_proof: bool) -> Result<ImportLane, ProtocolError> {
+    let Some((&key_bytes, rem)) = entry.data.split_first_chunk::<32>() else {
+        return Err(ProtocolError::Other("SmtLaneEntry data too short for lane_key"));
+    };
+    let Some(&tip_bytes) = rem.first_chunk::<32>() else {
+        return Err(ProtocolError::Other("SmtLaneEntry data too short for lane_tip"));
+    };
+    if rem.len() != 32 {
+        return Err(ProtocolError::Other("SmtLaneEntry data must be exactly 64 bytes"));
+    }
+    let lane_key = Hash::from_bytes(key_bytes);
+    let lane_tip = Hash::from_bytes(tip_bytes);
+
+    let proof = if with_proof {
+        Some(
+            kaspa_smt::proof::OwnedSmtProof::from_bytes(&entry.proof)
+                .map_err(|e| ProtocolError::OtherOwned(format!("invalid SMT proof: {e}")))?,
+        )
+    } else {
+        None
+    };
+
+    Ok(ImportLane { lane_key, lane_tip, blue_score: entry.blue_score, proof })
+}
+
 /// Stream of SMT lane chunks. Flow-controlled: after every [`SMT_FLOW_CONTROL_WINDOW`]
 /// chunks received the stream enqueues a [`RequestNextPruningPointSmtChunkMessage`]
 /// back to the peer. The total number of lanes is conveyed via the metadata header
@@ -235,30 +304,12 @@ impl<'a, 'b> SmtStream<'a, 'b> {
         Self { router, incoming_route, expected_count: 0, lane_count: 0, chunks_received: 0 }
     }
 
-    pub async fn recv_metadata(&mut self) -> Result<kaspa_consensus_core::api::SmtExportMetadata, ProtocolError> {
+    pub async fn recv_metadata(&mut self) -> Result<SmtExportMetadata, ProtocolError> {
         match timeout(DEFAULT_TIMEOUT, self.incoming_route.recv()).await {
             Ok(Some(msg)) => match msg.payload {
                 Some(Payload::SmtMetadata(payload)) => {
-                    use kaspa_consensus_core::api::SmtExportMetadata;
-                    // Wire: 96 bytes = lanes_root || payload_and_ctx_digest || parent_seq_commit.
-                    let (chunks, rem) = payload.data.as_chunks::<32>();
-                    if !rem.is_empty() {
-                        return Err(ProtocolError::Other("SmtMetadata data length not a multiple of 32 bytes"));
-                    }
-                    self.expected_count = payload.active_lanes_count;
-                    let md = match *chunks {
-                        [lanes_root, payload_and_ctx_digest, parent_seq_commit] => {
-                            let [lanes_root, payload_and_ctx_digest, parent_seq_commit] =
-                                [lanes_root, payload_and_ctx_digest, parent_seq_commit].map(Hash::from_bytes);
-                            SmtExportMetadata {
-                                lanes_root,
-                                payload_and_ctx_digest,
-                                parent_seq_commit,
-                                active_lanes_count: payload.active_lanes_count,
-                            }
-                        }
-                        _ => return Err(ProtocolError::Other("SmtMetadata data must be 96 bytes")),
-                    };
+                    let md = smt_metadata_from_message(&payload)?;
+                    self.expected_count = md.active_lanes_count;
                     Ok(md)
                 }
                 Some(Payload::UnexpectedPruningPoint(_)) => Err(ProtocolError::ConsensusError(ConsensusError::UnexpectedPruningPoint)),
@@ -271,7 +322,7 @@ impl<'a, 'b> SmtStream<'a, 'b> {
 
     /// Receives the next chunk of lanes from the peer. Returns `Ok(None)` once
     /// `active_lanes_count` lanes have been consumed.
-    pub async fn next_chunk(&mut self) -> Result<Option<Vec<kaspa_consensus_core::api::ImportLane>>, ProtocolError> {
+    pub async fn next_chunk(&mut self) -> Result<Option<Vec<ImportLane>>, ProtocolError> {
         if self.lane_count >= self.expected_count {
             return Ok(None);
         }
@@ -308,28 +359,8 @@ impl<'a, 'b> SmtStream<'a, 'b> {
 
         let mut lanes = Vec::with_capacity(payload.entries.len());
         for entry in payload.entries {
-            let Some((&key_bytes, rem)) = entry.data.split_first_chunk::<32>() else {
-                return Err(ProtocolError::Other("SmtLaneEntry data too short for lane_key"));
-            };
-            let Some(&tip_bytes) = rem.first_chunk::<32>() else {
-                return Err(ProtocolError::Other("SmtLaneEntry data too short for lane_tip"));
-            };
-            if rem.len() != 32 {
-                return Err(ProtocolError::Other("SmtLaneEntry data must be exactly 64 bytes"));
-            }
-            let lane_key = Hash::from_bytes(key_bytes);
-            let lane_tip = Hash::from_bytes(tip_bytes);
-
-            let proof = if (self.lane_count as usize).is_multiple_of(SMT_PROOF_INTERVAL) {
-                Some(
-                    kaspa_smt::proof::OwnedSmtProof::from_bytes(&entry.proof)
-                        .map_err(|e| ProtocolError::OtherOwned(format!("invalid SMT proof: {e}")))?,
-                )
-            } else {
-                None
-            };
-
-            lanes.push(kaspa_consensus_core::api::ImportLane { lane_key, lane_tip, blue_score: entry.blue_score, proof });
+            let with_proof = (self.lane_count as usize).is_multiple_of(SMT_PROOF_INTERVAL);
+            lanes.push(import_lane_from_entry(entry, with_proof)?);
             self.lane_count += 1;
         }
 
@@ -2,6 +2,7 @@ pub mod flow_context;
 pub mod flow_trait;
 pub mod flowcontext;
 pub mod ibd;
+pub mod mempool_persistence;
 pub mod service;
 pub mod user_agent_rule;
 pub mod v10;
@@ -0,0 +1,118 @@
+use crate::flow_context::FlowContext;
+use kaspa_core::{
+    info,
+    task::{
+        service::{AsyncService, AsyncServiceFuture},
+        tick::{TickReason, TickService},
+    },
+    trace, warn,
+};
+use kaspa_mining::mempool::snapshot::MempoolSnapshot;
+use std::{io, path::PathBuf, sync::Arc, time::Duration};
+
+const SERVICE_NAME: &str = "mempool-persistence";
+
+/// Default interval between two periodic saves of the mempool
+pub const DEFAULT_MEMPOOL_SAVE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Interval at which the sync state is polled before restoring a saved mempool
+const SYNC_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Saves the mempool content to disk periodically and on shutdown, and restores it on startup.
+///
+/// Restored transactions are revalidated once the node is nearly synced, since only then the
+/// UTXO set reflects the network state and both accepted and double spent transactions are
+/// correctly rejected.
+pub struct MempoolPersistenceService {
+    flow_context: Arc<FlowContext>,
+    path: PathBuf,
+    save_interval: Duration,
+    tick_service: Arc<TickService>,
+}
+
+impl MempoolPersistenceService {
+    pub fn new(flow_context: Arc<FlowContext>, path: PathBuf, save_interval: Duration, tick_service: Arc<TickService>) -> Self {
+        Self { flow_context, path, save_interval, tick_service }
+    }
+
+    async fn worker(&self) {
+        if !self.restore().await {
+            // The saved snapshot is left untouched for the next run
+            return;
+        }
+        loop {
+            let tick_reason = self.tick_service.tick(self.save_interval).await;
+            self.save().await;
+            if let TickReason::Shutdown = tick_reason {
+                break;
+            }
+        }
+        trace!("{} thread exiting", SERVICE_NAME);
+    }
+
+    /// Restores the saved mempool, if any. Returns false if the node is shutting down before the restore could complete.
+    async fn restore(&self) -> bool {
+        let snapshot = match MempoolSnapshot::load(&self.path) {
+            Ok(snapshot) => snapshot,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return true,
+            Err(err) => {
+                warn!("Failed to load the saved mempool from {}: {}", self.path.display(), err);
+                return true;
+            }
+        };
+        if snapshot.is_empty() {
+            return true;
+        }
+
+        info!("Loaded {} saved mempool transactions, they will be revalidated once the node is synced", snapshot.len());
+        let consensus = loop {
+            let session = self.flow_context.consensus().unguarded_session();
+            if self.flow_context.is_nearly_synced(&session).await {
+                break session;
+            }
+            if let TickReason::Shutdown = self.tick_service.tick(SYNC_POLL_INTERVAL).await {
+                return false;
+            }
+        };
+
+        let snapshot_len = snapshot.len();
+        let accepted = self.flow_context.mining_manager().clone().import_snapshot(&consensus, snapshot).await;
+        info!("Restored {} out of {} saved mempool transactions", accepted.len(), snapshot_len);
+        self.flow_context.broadcast_transactions(accepted.iter().map(|tx| tx.id()), true).await;
+        true
+    }
+
+    async fn save(&self) {
+        let snapshot = self.flow_context.mining_manager().clone().export_snapshot().await;
+        let path = self.path.clone();
+        let snapshot_len = snapshot.len();
+        match tokio::task::spawn_blocking(move || snapshot.save(&path)).await.unwrap() {
+            Ok(()) => trace!("Saved {} mempool transactions to {}", snapshot_len, self.path.display()),
+            Err(err) => warn!("Failed to save the mempool to {}: {}", self.path.display(), err),
+        }
+    }
+}
+
+impl AsyncService for MempoolPersistenceService {
+    fn ident(self: Arc<Self>) -> &'static str {
+        SERVICE_NAME
+    }
+
+    fn start(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            self.worker().await;
+            Ok(())
+        })
+    }
+
+    fn signal_exit(self: Arc<Self>) {
+        trace!("sending an exit signal to {}", SERVICE_NAME);
+    }
+
+    fn stop(self: Arc<Self>) -> AsyncServiceFuture {
+        Box::pin(async move {
+            trace!("{} stopped", SERVICE_NAME);
+            Ok(())
+        })
+    }
+}
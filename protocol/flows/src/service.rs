@@ -1,19 +1,29 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use kaspa_addressmanager::NetAddress;
 use kaspa_connectionmanager::ConnectionManager;
 use kaspa_core::{
-    task::service::{AsyncService, AsyncServiceFuture},
+    task::service::{AsyncService, AsyncServiceError, AsyncServiceFuture},
     trace,
 };
 use kaspa_p2p_lib::Adaptor;
 use kaspa_utils::triggers::SingleTrigger;
 use kaspa_utils_tower::counters::TowerConnectionCounters;
 
-use crate::flow_context::FlowContext;
+use crate::{
+    flow_context::FlowContext,
+    ibd::{export_pruning_point_snapshot, import_pruning_point_snapshot},
+};
 
 const P2P_CORE_SERVICE: &str = "p2p-service";
 
+/// A pruning point snapshot operation performed before any peer connection is established
+#[derive(Debug, Clone)]
+pub enum SnapshotTask {
+    Export(PathBuf),
+    Import(PathBuf),
+}
+
 pub struct P2pService {
     flow_context: Arc<FlowContext>,
     connect_peers: Vec<NetAddress>,
@@ -25,6 +35,7 @@ pub struct P2pService {
     default_port: u16,
     shutdown: SingleTrigger,
     counters: Arc<TowerConnectionCounters>,
+    snapshot_task: Option<SnapshotTask>,
 }
 
 impl P2pService {
@@ -38,6 +49,7 @@ impl P2pService {
         dns_seeders: &'static [&'static str],
         default_port: u16,
         counters: Arc<TowerConnectionCounters>,
+        snapshot_task: Option<SnapshotTask>,
     ) -> Self {
         Self {
             flow_context,
@@ -50,6 +62,7 @@ impl P2pService {
             dns_seeders,
             default_port,
             counters,
+            snapshot_task,
         }
     }
 }
@@ -65,26 +78,41 @@ impl AsyncService for P2pService {
         // Prepare a shutdown signal receiver
         let shutdown_signal = self.shutdown.listener.clone();
 
-        let p2p_adaptor = if self.inbound_limit == 0 {
-            Adaptor::client_only(self.flow_context.hub().clone(), self.flow_context.clone(), self.counters.clone())
-        } else {
-            Adaptor::bidirectional(self.listen, self.flow_context.hub().clone(), self.flow_context.clone(), self.counters.clone())
-                .unwrap()
-        };
-        let connection_manager = ConnectionManager::new(
-            p2p_adaptor.clone(),
-            self.outbound_target,
-            self.inbound_limit,
-            self.dns_seeders,
-            self.default_port,
-            self.flow_context.address_manager.clone(),
-        );
-
-        self.flow_context.set_connection_manager(connection_manager.clone());
-        self.flow_context.start_async_services();
-
         // Launch the service and wait for a shutdown signal
         Box::pin(async move {
+            // Snapshots are handled before the adaptor exists so that no peer can trigger IBD concurrently
+            match self.snapshot_task.clone() {
+                Some(SnapshotTask::Export(path)) => {
+                    export_pruning_point_snapshot(&self.flow_context, path)
+                        .await
+                        .map_err(|err| AsyncServiceError::Service(format!("pruning point snapshot export failed: {err}")))?;
+                }
+                Some(SnapshotTask::Import(path)) => {
+                    import_pruning_point_snapshot(&self.flow_context, path)
+                        .await
+                        .map_err(|err| AsyncServiceError::Service(format!("pruning point snapshot import failed: {err}")))?;
+                }
+                None => {}
+            }
+
+            let p2p_adaptor = if self.inbound_limit == 0 {
+                Adaptor::client_only(self.flow_context.hub().clone(), self.flow_context.clone(), self.counters.clone())
+            } else {
+                Adaptor::bidirectional(self.listen, self.flow_context.hub().clone(), self.flow_context.clone(), self.counters.clone())
+                    .unwrap()
+            };
+            let connection_manager = ConnectionManager::new(
+                p2p_adaptor.clone(),
+                self.outbound_target,
+                self.inbound_limit,
+                self.dns_seeders,
+                self.default_port,
+                self.flow_context.address_manager.clone(),
+            );
+
+            self.flow_context.set_connection_manager(connection_manager.clone());
+            self.flow_context.start_async_services();
+
             for peer_address in self.connect_peers.iter().cloned().chain(self.add_peers.iter().cloned()) {
                 connection_manager.add_connection_request(peer_address.into(), true).await;
             }
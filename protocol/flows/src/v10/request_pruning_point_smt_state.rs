@@ -1,19 +1,16 @@
 use crate::{
     flow_context::FlowContext,
     flow_trait::Flow,
-    ibd::{SMT_CHUNK_SIZE, SMT_FLOW_CONTROL_WINDOW},
-};
-use kaspa_consensus_core::{
-    api::{ImportLane, SmtExportMetadata},
-    errors::consensus::ConsensusError,
+    ibd::{SMT_CHUNK_SIZE, SMT_FLOW_CONTROL_WINDOW, import_lane_to_entry, smt_metadata_to_message},
 };
+use kaspa_consensus_core::{api::ImportLane, errors::consensus::ConsensusError};
 use kaspa_core::{debug, info};
 use kaspa_hashes::Hash;
 use kaspa_p2p_lib::{
     IncomingRoute, Router,
     common::ProtocolError,
     dequeue, make_message,
-    pb::{SmtLaneChunkMessage, SmtLaneEntry, SmtMetadataMessage, UnexpectedPruningPointMessage, kaspad_message::Payload},
+    pb::{SmtLaneChunkMessage, SmtLaneEntry, UnexpectedPruningPointMessage, kaspad_message::Payload},
 };
 use std::sync::Arc;
 
@@ -59,15 +56,7 @@ impl RequestPruningPointSmtStateFlow {
 
         let expected_count = metadata.active_lanes_count;
 
-        // Wire: 96 bytes = lanes_root || payload_and_ctx_digest || parent_seq_commit.
-        let SmtExportMetadata { lanes_root, payload_and_ctx_digest, parent_seq_commit, .. } = metadata;
-        let mut md_bytes = Vec::with_capacity(96);
-        md_bytes.extend_from_slice(&lanes_root.as_bytes());
-        md_bytes.extend_from_slice(&payload_and_ctx_digest.as_bytes());
-        md_bytes.extend_from_slice(&parent_seq_commit.as_bytes());
-        self.router
-            .enqueue(make_message!(Payload::SmtMetadata, SmtMetadataMessage { data: md_bytes, active_lanes_count: expected_count }))
-            .await?;
+        self.router.enqueue(make_message!(Payload::SmtMetadata, smt_metadata_to_message(&metadata))).await?;
 
         if expected_count == 0 {
             debug!("Finished sending SMT state for pruning point {}: 0 lanes", expected_pp);
@@ -108,16 +97,7 @@ impl RequestPruningPointSmtStateFlow {
         let mut chunks_sent: usize = 0;
 
         while let Some(batch) = rx.recv().await {
-            let entries: Vec<SmtLaneEntry> = batch
-                .into_iter()
-                .map(|lane| {
-                    let mut data = Vec::with_capacity(64);
-                    data.extend_from_slice(&lane.lane_key.as_bytes());
-                    data.extend_from_slice(&lane.lane_tip.as_bytes());
-                    let proof_bytes = lane.proof.as_ref().map(|p| p.to_bytes()).unwrap_or_default();
-                    SmtLaneEntry { data, blue_score: lane.blue_score, proof: proof_bytes }
-                })
-                .collect();
+            let entries: Vec<SmtLaneEntry> = batch.into_iter().map(import_lane_to_entry).collect();
 
             let chunk_len = entries.len() as u64;
             self.router.enqueue(make_message!(Payload::SmtLaneChunk, SmtLaneChunkMessage { entries })).await?;
@@ -15,7 +15,7 @@ use kaspa_p2p_lib::{
     pb::{InvRelayBlockMessage, RequestBlockLocatorMessage, RequestRelayBlocksMessage, kaspad_message::Payload},
 };
 use kaspa_utils::channel::{JobSender, JobTrySendError as TrySendError};
-use std::{collections::VecDeque, sync::Arc};
+use std::{collections::VecDeque, sync::Arc, time::Instant};
 
 pub struct RelayInvMessage {
     hash: Hash,
@@ -176,7 +176,7 @@ impl HandleRelayInvsFlow {
                                 Ok(_) => {}
                                 // We disconnect on invalidness even though this is not a direct relay from this peer, because
                                 // current relay is a descendant of this block (i.e. this peer claims all its ancestors are valid)
-                                Err(rule_error) => return Err(rule_error.into()),
+                                Err(rule_error) => return Err(self.invalid_block(rule_error)),
                             }
                         }
 
@@ -188,14 +188,14 @@ impl HandleRelayInvsFlow {
                                     debug!("Unorphaned {} ancestors and retried orphan block {} successfully", n, block.hash())
                                 }
                             },
-                            Err(rule_error) => return Err(rule_error.into()),
+                            Err(rule_error) => return Err(self.invalid_block(rule_error)),
                         }
                         ancestor_batch
                     } else {
                         continue;
                     }
                 }
-                Err(rule_error) => return Err(rule_error.into()),
+                Err(rule_error) => return Err(self.invalid_block(rule_error)),
             };
 
             // As a policy, we only relay blocks who stand a chance to enter past(virtual).
@@ -245,6 +245,7 @@ impl HandleRelayInvsFlow {
         let Some(request_scope) = self.ctx.try_adding_block_request(requested_hash) else {
             return Ok(None);
         };
+        let requested_at = Instant::now();
         self.router
             .enqueue(make_request!(
                 Payload::RequestRelayBlocks,
@@ -253,6 +254,7 @@ impl HandleRelayInvsFlow {
             ))
             .await?;
         let msg = dequeue_with_timeout!(self.msg_route, Payload::Block)?;
+        self.router.record_relay_latency(requested_at.elapsed());
         let block: Block = Versioned(header_format, msg).try_into()?;
         if block.hash() != requested_hash {
             Err(ProtocolError::OtherOwned(format!("requested block hash {} but got block {}", requested_hash, block.hash())))
@@ -377,4 +379,10 @@ impl HandleRelayInvsFlow {
             Err(TrySendError::Closed(_)) => Err(ProtocolError::ConnectionClosed), // This indicates that IBD flow has exited
         }
     }
+
+    /// Accounts a relayed block (or one of its ancestors) rejected by consensus to the peer
+    fn invalid_block(&self, rule_error: RuleError) -> ProtocolError {
+        self.router.record_invalid_block();
+        rule_error.into()
+    }
 }
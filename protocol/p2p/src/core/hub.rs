@@ -1,6 +1,6 @@
 use crate::{ConnectionInitializer, Peer, Router, common::ProtocolError, pb::KaspadMessage};
 use kaspa_core::{debug, info, warn};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use std::{
     collections::{HashMap, hash_map::Entry::Occupied},
     sync::Arc,
@@ -8,6 +8,7 @@ use std::{
 use tokio::sync::mpsc::Receiver as MpscReceiver;
 
 use super::peer::PeerKey;
+use super::stats::PeerStatsTotals;
 use rand::prelude::IteratorRandom;
 
 #[derive(Debug)]
@@ -23,11 +24,14 @@ pub struct Hub {
     ///
     /// Note: the map key holds the node id and IP to prevent node impersonating.
     pub(crate) peers: Arc<RwLock<HashMap<PeerKey, Arc<Router>>>>,
+
+    /// Statistics totals of the peers which were already removed from the map
+    retired_stats: Arc<Mutex<PeerStatsTotals>>,
 }
 
 impl Hub {
     pub fn new() -> Self {
-        Self { peers: Arc::new(RwLock::new(HashMap::new())) }
+        Self { peers: Arc::new(RwLock::new(HashMap::new())), retired_stats: Default::default() }
     }
 
     /// Starts a loop for receiving central hub events from all peer routers. This mechanism is used for
@@ -39,12 +43,12 @@ impl Hub {
                     HubEvent::NewPeer(new_router) => {
                         // If peer is outbound then connection initialization was already performed as part of the connect logic
                         if new_router.is_outbound() {
-                            info!("P2P Connected to outgoing peer {} (outbound: {})", new_router, self.peers_query(true) + 1);
+                            info!(peer:% = new_router; "P2P Connected to outgoing peer {} (outbound: {})", new_router, self.peers_query(true) + 1);
                             self.insert_new_router(new_router).await;
                         } else {
                             match initializer.initialize_connection(new_router.clone()).await {
                                 Ok(()) => {
-                                    info!("P2P Connected to incoming peer {} (inbound: {})", new_router, self.peers_query(false) + 1);
+                                    info!(peer:% = new_router; "P2P Connected to incoming peer {} (inbound: {})", new_router, self.peers_query(false) + 1);
                                     self.insert_new_router(new_router).await;
                                 }
                                 Err(err) => {
@@ -73,6 +77,7 @@ impl Hub {
                             // This is extremely important in cases of duplicate connection rejection etc.
                             if Arc::ptr_eq(entry.get(), &router) {
                                 entry.remove_entry();
+                                self.retired_stats.lock().add(&router.stats());
                                 debug!("P2P, Hub event loop, removing peer, router-id: {}", router.identity());
                             }
                         }
@@ -196,6 +201,22 @@ impl Hub {
         self.peers.read().values().map(|r| r.as_ref().into()).collect()
     }
 
+    /// Returns the routers of all currently active peers
+    pub fn active_routers(&self) -> Vec<Arc<Router>> {
+        self.peers.read().values().cloned().collect()
+    }
+
+    /// Returns the statistics totals of all peers since startup, including already disconnected ones
+    pub fn peer_stats_totals(&self) -> PeerStatsTotals {
+        // Lock the peers map first so that a peer being concurrently retired is counted exactly once
+        let peers = self.peers.read();
+        let mut totals = *self.retired_stats.lock();
+        for router in peers.values() {
+            totals.add(&router.stats());
+        }
+        totals
+    }
+
     /// Returns the number of currently active peers
     pub fn active_peers_len(&self) -> usize {
         self.peers.read().len()
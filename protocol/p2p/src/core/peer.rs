@@ -1,3 +1,4 @@
+use super::stats::PeerStats;
 use kaspa_consensus_core::subnets::SubnetworkId;
 use kaspa_utils::networking::{IpAddress, PeerId};
 use std::{fmt::Display, net::SocketAddr, sync::Arc, time::Instant};
@@ -21,6 +22,7 @@ pub struct Peer {
     connection_started: Instant,
     properties: Arc<PeerProperties>,
     last_ping_duration: u64,
+    stats: PeerStats,
 }
 
 impl Peer {
@@ -31,8 +33,9 @@ impl Peer {
         connection_started: Instant,
         properties: Arc<PeerProperties>,
         last_ping_duration: u64,
+        stats: PeerStats,
     ) -> Self {
-        Self { identity, net_address, is_outbound, connection_started, properties, last_ping_duration }
+        Self { identity, net_address, is_outbound, connection_started, properties, last_ping_duration, stats }
     }
 
     /// Internal identity of this peer
@@ -65,6 +68,11 @@ impl Peer {
     pub fn last_ping_duration(&self) -> u64 {
         self.last_ping_duration
     }
+
+    /// Traffic and misbehavior statistics of this peer at the time of the snapshot
+    pub fn stats(&self) -> &PeerStats {
+        &self.stats
+    }
 }
 
 #[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
@@ -6,13 +6,17 @@ use crate::{Peer, make_message};
 use kaspa_core::{debug, error, info, trace, warn};
 use kaspa_utils::networking::PeerId;
 use parking_lot::{Mutex, RwLock};
+use prost::Message;
 use seqlock::SeqLock;
 use std::fmt::{Debug, Display};
 use std::net::SocketAddr;
 use std::ops::{Deref, DerefMut};
 use std::sync::atomic::{AtomicU32, Ordering};
-use std::time::Instant;
-use std::{collections::HashMap, sync::Arc};
+use std::time::{Duration, Instant};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
 use tokio::select;
 use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::mpsc::{Receiver as MpscReceiver, Sender as MpscSender, channel as mpsc_channel};
@@ -20,6 +24,7 @@ use tokio::sync::oneshot::{Sender as OneshotSender, channel as oneshot_channel};
 use tonic::Streaming;
 
 use super::peer::{PeerKey, PeerProperties};
+use super::stats::{PeerStats, PeerStatsCounters};
 
 pub struct IncomingRoute {
     rx: MpscReceiver<KaspadMessage>,
@@ -32,6 +37,9 @@ pub struct IncomingRoute {
 pub const BLANK_ROUTE_ID: u32 = 0;
 static ROUTE_ID: AtomicU32 = AtomicU32::new(BLANK_ROUTE_ID + 1);
 
+/// Maximal number of recently unsubscribed route ids per router for which late responses are tolerated
+const MAX_UNSUBSCRIBED_ROUTE_IDS: usize = 32;
+
 impl IncomingRoute {
     pub fn new(rx: MpscReceiver<KaspadMessage>) -> Self {
         let id = ROUTE_ID.fetch_add(1, Ordering::SeqCst);
@@ -131,6 +139,9 @@ pub struct Router {
 
     routing_map_by_id: RwLock<HashMap<u32, MpscSender<KaspadMessage>>>,
 
+    /// Recently unsubscribed route ids, whose late responses are dropped rather than treated as unsolicited
+    unsubscribed_route_ids: Mutex<VecDeque<u32>>,
+
     /// The outgoing route for sending messages to this peer
     outgoing_route: MpscSender<KaspadMessage>,
 
@@ -139,6 +150,9 @@ pub struct Router {
 
     /// Used for managing router mutable state
     mutable_state: Mutex<RouterMutableState>,
+
+    /// Traffic and misbehavior counters of this peer
+    stats: PeerStatsCounters,
 }
 
 impl Display for Router {
@@ -162,6 +176,7 @@ impl From<&Router> for Peer {
             router.connection_started,
             router.properties(),
             router.last_ping_duration(),
+            router.stats(),
         )
     }
 }
@@ -190,9 +205,11 @@ impl Router {
             connection_started: Instant::now(),
             routing_map_by_type: RwLock::new(HashMap::new()),
             routing_map_by_id: RwLock::new(HashMap::new()),
+            unsubscribed_route_ids: Mutex::new(VecDeque::with_capacity(MAX_UNSUBSCRIBED_ROUTE_IDS)),
             outgoing_route,
             hub_sender,
             mutable_state: Mutex::new(RouterMutableState::new(Some(start_sender), Some(shutdown_sender))),
+            stats: Default::default(),
         });
 
         let router_clone = router.clone();
@@ -225,14 +242,14 @@ impl Router {
                             }
                         }
                         Ok(None) => {
-                            info!("P2P, incoming stream ended from peer {}", router);
+                            info!(peer:% = router; "P2P, incoming stream ended from peer {}", router);
                             break;
                         }
                         Err(status) => {
                             if let Some(err) = match_for_io_error(&status) {
-                                info!("P2P, network error: {} from peer {}", err, router);
+                                info!(peer:% = router; "P2P, network error: {} from peer {}", err, router);
                             } else {
-                                info!("P2P, network error: {} from peer {}", status, router);
+                                info!(peer:% = router; "P2P, network error: {} from peer {}", status, router);
                             }
                             break;
                         }
@@ -294,6 +311,27 @@ impl Router {
         self.mutable_state.lock().last_ping_duration
     }
 
+    /// Returns a snapshot of the traffic and misbehavior statistics of this peer
+    pub fn stats(&self) -> PeerStats {
+        let capacity = self.outgoing_route.max_capacity();
+        self.stats.snapshot(capacity - self.outgoing_route.capacity(), capacity)
+    }
+
+    /// Records that this peer sent an invalid block
+    pub fn record_invalid_block(&self) {
+        self.stats.record_invalid_block();
+    }
+
+    /// Records that this peer sent an invalid transaction
+    pub fn record_invalid_transaction(&self) {
+        self.stats.record_invalid_transaction();
+    }
+
+    /// Records the time it took this peer to deliver a requested relay block
+    pub fn record_relay_latency(&self, latency: Duration) {
+        self.stats.record_relay_latency(latency);
+    }
+
     pub fn incoming_flow_baseline_channel_size() -> usize {
         256
     }
@@ -361,6 +399,21 @@ impl Router {
         incoming_route
     }
 
+    /// Unregisters the route with the given id from response routing, for a route abandoned while requests
+    /// may still be in flight (e.g., a block body download re-assigned to another peer). Responses arriving
+    /// later are dropped instead of failing the connection, as long as the id is among the last
+    /// [`MAX_UNSUBSCRIBED_ROUTE_IDS`] unsubscribed ones. Message types the route was subscribed to remain routed to it.
+    pub fn unsubscribe(&self, route_id: u32) {
+        if self.routing_map_by_id.write().remove(&route_id).is_none() {
+            return;
+        }
+        let mut unsubscribed = self.unsubscribed_route_ids.lock();
+        if unsubscribed.len() == MAX_UNSUBSCRIBED_ROUTE_IDS {
+            unsubscribed.pop_front();
+        }
+        unsubscribed.push_back(route_id);
+    }
+
     /// Routes a message coming from the network to the corresponding registered flow
     pub fn route_to_flow(&self, msg: KaspadMessage) -> Result<(), ProtocolError> {
         if msg.payload.is_none() {
@@ -375,19 +428,32 @@ impl Router {
         }
 
         let op = if msg.response_id != BLANK_ROUTE_ID {
-            self.routing_map_by_id.read().get(&msg.response_id).cloned()
+            let op = self.routing_map_by_id.read().get(&msg.response_id).cloned();
+            if op.is_none() && self.unsubscribed_route_ids.lock().contains(&msg.response_id) {
+                debug!("P2P, dropping late {:?} response to unsubscribed route id {}, peer: {}", msg_type, msg.response_id, self);
+                return Ok(());
+            }
+            op
         } else {
             self.routing_map_by_type.read().get(&msg_type).cloned()
         };
 
         if let Some(sender) = op {
-            match sender.try_send(msg) {
-                Ok(_) => Ok(()),
+            match sender.try_reserve() {
+                Ok(permit) => {
+                    // Messages are only sized once routed, sparing the encoding pass for those about to be discarded
+                    self.stats.record_incoming(msg_type, msg.encoded_len());
+                    permit.send(msg);
+                    Ok(())
+                }
                 Err(TrySendError::Closed(_)) => Err(ProtocolError::ConnectionClosed),
                 Err(TrySendError::Full(_)) => {
                     let overflow_policy: IncomingRouteOverflowPolicy = msg_type.into();
                     match overflow_policy {
-                        IncomingRouteOverflowPolicy::Drop => Ok(()),
+                        IncomingRouteOverflowPolicy::Drop => {
+                            self.stats.record_dropped_message();
+                            Ok(())
+                        }
                         IncomingRouteOverflowPolicy::Disconnect => {
                             Err(ProtocolError::IncomingRouteCapacityReached(msg_type, self.to_string()))
                         }
@@ -402,8 +468,13 @@ impl Router {
     /// Enqueues a locally-originated message to be sent to the network peer
     pub async fn enqueue(&self, msg: KaspadMessage) -> Result<(), ProtocolError> {
         assert!(msg.payload.is_some(), "Kaspad P2P message should always have a value");
-        match self.outgoing_route.try_send(msg) {
-            Ok(_) => Ok(()),
+        let msg_type: KaspadMessagePayloadType = msg.payload.as_ref().expect("payload was just verified").into();
+        match self.outgoing_route.try_reserve() {
+            Ok(permit) => {
+                self.stats.record_outgoing(msg_type, msg.encoded_len());
+                permit.send(msg);
+                Ok(())
+            }
             Err(TrySendError::Closed(_)) => Err(ProtocolError::ConnectionClosed),
             Err(TrySendError::Full(_)) => Err(ProtocolError::OutgoingRouteCapacityReached(self.to_string())),
         }
@@ -0,0 +1,190 @@
+use crate::KaspadMessagePayloadType;
+use parking_lot::Mutex;
+use std::{
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+/// Traffic counters of a single message type. Incoming counters only cover messages delivered to a flow,
+/// messages dropped on a full route are accounted in [`PeerStats::dropped_messages`]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MessageTypeStats {
+    pub messages_in: u64,
+    pub bytes_in: u64,
+    pub messages_out: u64,
+    pub bytes_out: u64,
+}
+
+/// Snapshot of the traffic and misbehavior statistics collected for a peer
+#[derive(Debug, Default, Clone)]
+pub struct PeerStats {
+    /// Traffic counters by message type, for message types exchanged at least once
+    pub by_message_type: Vec<(KaspadMessagePayloadType, MessageTypeStats)>,
+    /// Number of invalid blocks received from this peer
+    pub invalid_blocks: u64,
+    /// Number of invalid transactions received from this peer
+    pub invalid_transactions: u64,
+    /// Number of incoming messages dropped because the route of their flow was full
+    pub dropped_messages: u64,
+    /// Moving average of the time this peer takes to deliver a requested relay block
+    pub relay_latency: Option<Duration>,
+    /// Number of messages waiting in the outgoing queue of this peer
+    pub outgoing_queue_len: usize,
+    pub outgoing_queue_capacity: usize,
+}
+
+impl PeerStats {
+    pub fn messages_in(&self) -> u64 {
+        self.by_message_type.iter().map(|(_, stats)| stats.messages_in).sum()
+    }
+
+    pub fn messages_out(&self) -> u64 {
+        self.by_message_type.iter().map(|(_, stats)| stats.messages_out).sum()
+    }
+
+    /// Fraction of the outgoing queue currently in use, in the range `[0, 1]`
+    pub fn outgoing_queue_fullness(&self) -> f64 {
+        if self.outgoing_queue_capacity == 0 { 0.0 } else { self.outgoing_queue_len as f64 / self.outgoing_queue_capacity as f64 }
+    }
+}
+
+/// Node-wide totals of the counters of all peers, including disconnected ones
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PeerStatsTotals {
+    pub messages_in: u64,
+    pub messages_out: u64,
+    pub invalid_blocks: u64,
+    pub invalid_transactions: u64,
+    pub dropped_messages: u64,
+}
+
+impl PeerStatsTotals {
+    pub fn add(&mut self, stats: &PeerStats) {
+        self.messages_in += stats.messages_in();
+        self.messages_out += stats.messages_out();
+        self.invalid_blocks += stats.invalid_blocks;
+        self.invalid_transactions += stats.invalid_transactions;
+        self.dropped_messages += stats.dropped_messages;
+    }
+}
+
+/// Weight of a new sample in the relay latency moving average, expressed as a power of two divisor
+const RELAY_LATENCY_SMOOTHING_SHIFT: u32 = 3;
+
+/// Counters maintained by a router for the lifetime of its connection
+#[derive(Debug, Default)]
+pub(crate) struct PeerStatsCounters {
+    traffic: Mutex<HashMap<KaspadMessagePayloadType, MessageTypeStats>>,
+    invalid_blocks: AtomicU64,
+    invalid_transactions: AtomicU64,
+    dropped_messages: AtomicU64,
+    /// Relay latency moving average in microseconds, zero until the first sample
+    relay_latency_micros: AtomicU64,
+}
+
+impl PeerStatsCounters {
+    pub(crate) fn record_incoming(&self, msg_type: KaspadMessagePayloadType, bytes: usize) {
+        let mut traffic = self.traffic.lock();
+        let entry = traffic.entry(msg_type).or_default();
+        entry.messages_in += 1;
+        entry.bytes_in += bytes as u64;
+    }
+
+    pub(crate) fn record_outgoing(&self, msg_type: KaspadMessagePayloadType, bytes: usize) {
+        let mut traffic = self.traffic.lock();
+        let entry = traffic.entry(msg_type).or_default();
+        entry.messages_out += 1;
+        entry.bytes_out += bytes as u64;
+    }
+
+    pub(crate) fn record_dropped_message(&self) {
+        self.dropped_messages.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_invalid_block(&self) {
+        self.invalid_blocks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_invalid_transaction(&self) {
+        self.invalid_transactions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_relay_latency(&self, latency: Duration) {
+        let sample = (latency.as_micros() as u64).max(1);
+        let _ = self.relay_latency_micros.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |average| {
+            Some(match average {
+                0 => sample,
+                average => average - (average >> RELAY_LATENCY_SMOOTHING_SHIFT) + (sample >> RELAY_LATENCY_SMOOTHING_SHIFT),
+            })
+        });
+    }
+
+    pub(crate) fn snapshot(&self, outgoing_queue_len: usize, outgoing_queue_capacity: usize) -> PeerStats {
+        let mut by_message_type: Vec<_> = self.traffic.lock().iter().map(|(msg_type, stats)| (*msg_type, *stats)).collect();
+        by_message_type.sort_by_key(|(msg_type, _)| *msg_type as u8);
+        let relay_latency = match self.relay_latency_micros.load(Ordering::Relaxed) {
+            0 => None,
+            micros => Some(Duration::from_micros(micros)),
+        };
+        PeerStats {
+            by_message_type,
+            invalid_blocks: self.invalid_blocks.load(Ordering::Relaxed),
+            invalid_transactions: self.invalid_transactions.load(Ordering::Relaxed),
+            dropped_messages: self.dropped_messages.load(Ordering::Relaxed),
+            relay_latency,
+            outgoing_queue_len,
+            outgoing_queue_capacity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peer_stats_counters() {
+        let counters = PeerStatsCounters::default();
+        counters.record_incoming(KaspadMessagePayloadType::Block, 100);
+        counters.record_incoming(KaspadMessagePayloadType::Block, 50);
+        counters.record_outgoing(KaspadMessagePayloadType::RequestRelayBlocks, 40);
+        counters.record_outgoing(KaspadMessagePayloadType::Addresses, 10);
+        counters.record_invalid_block();
+        counters.record_dropped_message();
+
+        let stats = counters.snapshot(16, 64);
+        assert_eq!(stats.messages_in(), 2);
+        assert_eq!(stats.messages_out(), 2);
+        assert_eq!(stats.invalid_blocks, 1);
+        assert_eq!(stats.invalid_transactions, 0);
+        assert_eq!(stats.dropped_messages, 1);
+        assert_eq!(stats.outgoing_queue_fullness(), 0.25);
+        assert!(stats.relay_latency.is_none());
+
+        // Entries are ordered by message type
+        let types = stats.by_message_type.iter().map(|(msg_type, _)| *msg_type).collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![KaspadMessagePayloadType::Addresses, KaspadMessagePayloadType::Block, KaspadMessagePayloadType::RequestRelayBlocks]
+        );
+        let (_, block) = stats.by_message_type[1];
+        assert_eq!((block.messages_in, block.bytes_in, block.messages_out, block.bytes_out), (2, 150, 0, 0));
+
+        let mut totals = PeerStatsTotals::default();
+        totals.add(&stats);
+        totals.add(&stats);
+        assert_eq!((totals.messages_in, totals.invalid_blocks, totals.dropped_messages), (4, 2, 2));
+    }
+
+    #[test]
+    fn test_relay_latency_moving_average() {
+        let counters = PeerStatsCounters::default();
+        counters.record_relay_latency(Duration::from_millis(80));
+        assert_eq!(counters.snapshot(0, 0).relay_latency, Some(Duration::from_millis(80)));
+
+        // A single slow sample only moves the average by an eighth of the difference
+        counters.record_relay_latency(Duration::from_millis(160));
+        assert_eq!(counters.snapshot(0, 0).relay_latency, Some(Duration::from_millis(90)));
+    }
+}
@@ -48,6 +48,9 @@ pub enum Notification {
 
     #[display(fmt = "NewBlockTemplate notification")]
     NewBlockTemplate(NewBlockTemplateNotification),
+
+    #[display(fmt = "SyncProgressChanged notification: {:?}", "_0.progress.as_ref().map(|p| p.phase)")]
+    SyncProgressChanged(SyncProgressChangedNotification),
 }
 }
 
@@ -64,6 +67,7 @@ impl Notification {
             Notification::VirtualDaaScoreChanged(v) => to_value(&v),
             Notification::SinkBlueScoreChanged(v) => to_value(&v),
             Notification::VirtualChainChanged(v) => to_value(&v),
+            Notification::SyncProgressChanged(v) => to_value(&v),
         }
     }
 }
@@ -158,6 +162,10 @@ impl Serializer for Notification {
                 store!(u16, &8, writer)?;
                 serialize!(NewBlockTemplateNotification, notification, writer)?;
             }
+            Notification::SyncProgressChanged(notification) => {
+                store!(u16, &9, writer)?;
+                serialize!(SyncProgressChangedNotification, notification, writer)?;
+            }
         }
         Ok(())
     }
@@ -203,6 +211,10 @@ impl Deserializer for Notification {
                 let notification = deserialize!(NewBlockTemplateNotification, reader)?;
                 Ok(Notification::NewBlockTemplate(notification))
             }
+            9 => {
+                let notification = deserialize!(SyncProgressChangedNotification, reader)?;
+                Ok(Notification::SyncProgressChanged(notification))
+            }
             _ => Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid variant")),
         }
     }
@@ -40,6 +40,7 @@ pub enum RpcApiOps {
     NotifyVirtualDaaScoreChanged = 16,
     NotifyVirtualChainChanged = 17,
     NotifySinkBlueScoreChanged = 18,
+    NotifySyncProgressChanged = 19,
 
     // Notification ops required by wRPC
 
@@ -54,6 +55,7 @@ pub enum RpcApiOps {
     VirtualDaaScoreChangedNotification = 66,
     PruningPointUtxoSetOverrideNotification = 67,
     NewBlockTemplateNotification = 68,
+    SyncProgressChangedNotification = 69,
 
     // RPC methods
     /// Ping the node to check if connection is alive
@@ -144,6 +146,18 @@ pub enum RpcApiOps {
     GetBlockRewardInfo = 152,
     /// Get Seq-Commit Lane Proof
     GetSeqCommitLaneProof = 153,
+    /// Get transactions accepted by a chain block or a DAA score range
+    GetAcceptedTransactions = 154,
+    /// Mine blocks in-process (simnet/devnet only)
+    GenerateBlocks = 155,
+    /// Get a page of the credits and debits of an address
+    GetAddressHistory = 156,
+    /// Get the balances of addresses at a past DAA score
+    GetBalanceAt = 157,
+    /// Get the phase and progress of the initial block download
+    GetSyncProgress = 158,
+    /// Build a block template dry-run and get the trace of its transaction selection
+    GetBlockTemplateDiagnostics = 159,
 }
 
 impl RpcApiOps {
@@ -159,6 +173,7 @@ impl RpcApiOps {
                 | RpcApiOps::NotifyFinalityConflictResolved
                 | RpcApiOps::NotifySinkBlueScoreChanged
                 | RpcApiOps::NotifyVirtualDaaScoreChanged
+                | RpcApiOps::NotifySyncProgressChanged
                 | RpcApiOps::Subscribe
                 | RpcApiOps::Unsubscribe
         )
@@ -185,6 +200,7 @@ impl From<EventType> for RpcApiOps {
             EventType::VirtualDaaScoreChanged => RpcApiOps::VirtualDaaScoreChangedNotification,
             EventType::PruningPointUtxoSetOverride => RpcApiOps::PruningPointUtxoSetOverrideNotification,
             EventType::NewBlockTemplate => RpcApiOps::NewBlockTemplateNotification,
+            EventType::SyncProgressChanged => RpcApiOps::SyncProgressChangedNotification,
         }
     }
 }
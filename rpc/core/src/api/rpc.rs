@@ -15,6 +15,18 @@ use std::sync::Arc;
 
 pub const MAX_SAFE_WINDOW_SIZE: u32 = 10_000;
 
+/// Number of transactions returned by [`RpcApi::get_accepted_transactions_call`] when the request sets no limit
+pub const DEFAULT_ACCEPTED_TRANSACTIONS_LIMIT: u32 = 1_000;
+/// Upper bound on the number of transactions returned by a single [`RpcApi::get_accepted_transactions_call`]
+pub const MAX_ACCEPTED_TRANSACTIONS_LIMIT: u32 = 10_000;
+/// Upper bound on the number of entries returned by a single [`RpcApi::get_address_history_call`]
+pub const MAX_ADDRESS_HISTORY_LIMIT: u32 = 1_000;
+/// Upper bound on the number of addresses of a single [`RpcApi::get_balance_at_call`]
+pub const MAX_BALANCE_AT_ADDRESSES: usize = 1_000;
+/// Upper bound on the number of priority, and of excluded, transactions customizing a single [`RpcApi::get_block_template_call`]
+/// or [`RpcApi::get_block_template_diagnostics_call`]
+pub const MAX_TEMPLATE_CUSTOMIZATION_TRANSACTIONS: usize = 1_000;
+
 /// Client RPC Api
 ///
 /// The [`RpcApi`] trait defines RPC calls taking a request message as unique parameter.
@@ -102,6 +114,16 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetSyncStatusRequest,
     ) -> RpcResult<GetSyncStatusResponse>;
 
+    /// Requests the sync status of the node along with the phase and progress of the running IBD, if any.
+    async fn get_sync_progress(&self) -> RpcResult<GetSyncProgressResponse> {
+        self.get_sync_progress_call(None, GetSyncProgressRequest {}).await
+    }
+    async fn get_sync_progress_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetSyncProgressRequest,
+    ) -> RpcResult<GetSyncProgressResponse>;
+
     // ---
 
     /// Requests the network the node is currently running against.
@@ -138,6 +160,21 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetBlockTemplateRequest,
     ) -> RpcResult<GetBlockTemplateResponse>;
 
+    /// Builds a block template dry-run and returns the trace of its transaction selection: the candidates considered
+    /// along with the reason of every rejection, and the fees, mass and lane usage of the selected transactions.
+    async fn get_block_template_diagnostics(
+        &self,
+        pay_address: RpcAddress,
+        extra_data: RpcExtraData,
+    ) -> RpcResult<GetBlockTemplateDiagnosticsResponse> {
+        self.get_block_template_diagnostics_call(None, GetBlockTemplateDiagnosticsRequest::new(pay_address, extra_data)).await
+    }
+    async fn get_block_template_diagnostics_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetBlockTemplateDiagnosticsRequest,
+    ) -> RpcResult<GetBlockTemplateDiagnosticsResponse>;
+
     /// Requests the list of known kaspad addresses in the current network (mainnet, testnet, etc.)
     async fn get_peer_addresses(&self) -> RpcResult<GetPeerAddressesResponse> {
         self.get_peer_addresses_call(None, GetPeerAddressesRequest {}).await
@@ -477,7 +514,14 @@ pub trait RpcApi: Sync + Send + AnySync {
     ) -> RpcResult<GetFeeEstimateResponse>;
 
     async fn get_fee_estimate_experimental(&self, verbose: bool) -> RpcResult<GetFeeEstimateExperimentalResponse> {
-        self.get_fee_estimate_experimental_call(None, GetFeeEstimateExperimentalRequest { verbose }).await
+        self.get_fee_estimate_experimental_call(None, GetFeeEstimateExperimentalRequest { verbose, targets: vec![] }).await
+    }
+    /// Estimates the feerate required for inclusion within each target time at the target confidence
+    async fn get_fee_estimate_for_targets(&self, targets: Vec<RpcFeerateTarget>) -> RpcResult<Vec<RpcFeerateTargetEstimate>> {
+        Ok(self
+            .get_fee_estimate_experimental_call(None, GetFeeEstimateExperimentalRequest { verbose: false, targets })
+            .await?
+            .target_estimates)
     }
     async fn get_fee_estimate_experimental_call(
         &self,
@@ -522,6 +566,79 @@ pub trait RpcApi: Sync + Send + AnySync {
         request: GetVirtualChainFromBlockV2Request,
     ) -> RpcResult<GetVirtualChainFromBlockV2Response>;
 
+    /// Returns the transactions accepted by the chain block `accepting_block_hash`, including their fee, masses and
+    /// spent UTXO entries. At most `limit` transactions are returned, after skipping the first `skip` ones.
+    async fn get_accepted_transactions_by_block(
+        &self,
+        accepting_block_hash: RpcHash,
+        skip: u64,
+        limit: u32,
+    ) -> RpcResult<GetAcceptedTransactionsResponse> {
+        self.get_accepted_transactions_call(
+            None,
+            GetAcceptedTransactionsRequest::by_accepting_block(accepting_block_hash, skip, limit),
+        )
+        .await
+    }
+    /// Returns the transactions accepted by the chain blocks whose DAA scores fall in `[start_daa_score, end_daa_score]`.
+    async fn get_accepted_transactions_by_daa_score_range(
+        &self,
+        start_daa_score: u64,
+        end_daa_score: u64,
+        skip: u64,
+        limit: u32,
+    ) -> RpcResult<GetAcceptedTransactionsResponse> {
+        self.get_accepted_transactions_call(
+            None,
+            GetAcceptedTransactionsRequest::by_daa_score_range(start_daa_score, end_daa_score, skip, limit),
+        )
+        .await
+    }
+    async fn get_accepted_transactions_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetAcceptedTransactionsRequest,
+    ) -> RpcResult<GetAcceptedTransactionsResponse>;
+
+    /// Builds, solves and submits `count` blocks paying to `pay_address`, returning their hashes.
+    /// Only available on simnet and devnet, or when proof of work is skipped.
+    async fn generate_blocks(&self, count: u32, pay_address: RpcAddress) -> RpcResult<Vec<RpcHash>> {
+        Ok(self.generate_blocks_call(None, GenerateBlocksRequest::new(count, pay_address)).await?.block_hashes)
+    }
+    async fn generate_blocks_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GenerateBlocksRequest,
+    ) -> RpcResult<GenerateBlocksResponse>;
+
+    /// Requests a page of the credits and debits of `address`, starting at `start_daa_score` or at `cursor` when set.
+    /// Requires the node to run with the address history index.
+    async fn get_address_history(
+        &self,
+        address: RpcAddress,
+        start_daa_score: u64,
+        cursor: Option<String>,
+        limit: u32,
+    ) -> RpcResult<GetAddressHistoryResponse> {
+        self.get_address_history_call(None, GetAddressHistoryRequest::new(address, start_daa_score, cursor, limit)).await
+    }
+    async fn get_address_history_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetAddressHistoryRequest,
+    ) -> RpcResult<GetAddressHistoryResponse>;
+
+    /// Requests the balances of `addresses` at `daa_score`.
+    /// Requires the node to run with the address history index.
+    async fn get_balance_at(&self, addresses: Vec<RpcAddress>, daa_score: u64) -> RpcResult<Vec<RpcBalancesByAddressesEntry>> {
+        Ok(self.get_balance_at_call(None, GetBalanceAtRequest::new(addresses, daa_score)).await?.entries)
+    }
+    async fn get_balance_at_call(
+        &self,
+        connection: Option<&DynRpcConnection>,
+        request: GetBalanceAtRequest,
+    ) -> RpcResult<GetBalanceAtResponse>;
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -2,8 +2,9 @@
 
 use crate::{
     BlockAddedNotification, FinalityConflictNotification, FinalityConflictResolvedNotification, NewBlockTemplateNotification,
-    Notification, PruningPointUtxoSetOverrideNotification, RpcAcceptedTransactionIds, SinkBlueScoreChangedNotification,
-    UtxosChangedNotification, VirtualChainChangedNotification, VirtualDaaScoreChangedNotification, convert::utxo::utxo_set_into_rpc,
+    Notification, PruningPointUtxoSetOverrideNotification, RpcAcceptedTransactionIds, RpcSyncPhase, RpcSyncProgress,
+    SinkBlueScoreChangedNotification, SyncProgressChangedNotification, UtxosChangedNotification, VirtualChainChangedNotification,
+    VirtualDaaScoreChangedNotification, convert::utxo::utxo_set_into_rpc,
 };
 use kaspa_consensus_notify::notification as consensus_notify;
 use kaspa_index_core::notification as index_notify;
@@ -31,6 +32,7 @@ impl From<&consensus_notify::Notification> for Notification {
             consensus_notify::Notification::VirtualDaaScoreChanged(msg) => Notification::VirtualDaaScoreChanged(msg.into()),
             consensus_notify::Notification::PruningPointUtxoSetOverride(msg) => Notification::PruningPointUtxoSetOverride(msg.into()),
             consensus_notify::Notification::NewBlockTemplate(msg) => Notification::NewBlockTemplate(msg.into()),
+            consensus_notify::Notification::SyncProgressChanged(msg) => Notification::SyncProgressChanged(msg.into()),
         }
     }
 }
@@ -112,6 +114,41 @@ impl From<&consensus_notify::NewBlockTemplateNotification> for NewBlockTemplateN
     }
 }
 
+impl From<consensus_notify::SyncPhase> for RpcSyncPhase {
+    fn from(item: consensus_notify::SyncPhase) -> Self {
+        match item {
+            consensus_notify::SyncPhase::PruningProof => RpcSyncPhase::PruningProof,
+            consensus_notify::SyncPhase::Headers => RpcSyncPhase::Headers,
+            consensus_notify::SyncPhase::UtxoSet => RpcSyncPhase::UtxoSet,
+            consensus_notify::SyncPhase::SmtState => RpcSyncPhase::SmtState,
+            consensus_notify::SyncPhase::Bodies => RpcSyncPhase::Bodies,
+            consensus_notify::SyncPhase::VirtualResolution => RpcSyncPhase::VirtualResolution,
+        }
+    }
+}
+
+impl From<&consensus_notify::SyncProgress> for RpcSyncProgress {
+    fn from(item: &consensus_notify::SyncProgress) -> Self {
+        Self {
+            phase: item.phase.into(),
+            syncer_id: item.syncer_id,
+            syncer_address: item.syncer_address,
+            processed: item.processed,
+            total: item.total,
+            low_daa_score: item.low_daa_score,
+            high_daa_score: item.high_daa_score,
+            current_daa_score: item.current_daa_score,
+            eta_seconds: item.eta_seconds,
+        }
+    }
+}
+
+impl From<&consensus_notify::SyncProgressChangedNotification> for SyncProgressChangedNotification {
+    fn from(item: &consensus_notify::SyncProgressChangedNotification) -> Self {
+        Self { progress: item.progress.as_ref().map(|progress| progress.into()) }
+    }
+}
+
 // ----------------------------------------------------------------------------
 // index to rpc_core
 // ----------------------------------------------------------------------------
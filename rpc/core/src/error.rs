@@ -66,6 +66,18 @@ pub enum RpcError {
     #[error("Method unavailable. Run the node with the --utxoindex argument.")]
     NoUtxoIndex,
 
+    #[error("Method unavailable. Run the node with the --utxoindex and --addresshistoryindex arguments.")]
+    NoAddressHistoryIndex,
+
+    #[error("Invalid address history cursor")]
+    InvalidAddressHistoryCursor,
+
+    #[error("Requested {0} addresses, more than max {1} allowed.")]
+    AddressesExceedingMaximum(usize, usize),
+
+    #[error("Requested {0} {1} transactions, more than max {2} allowed.")]
+    TemplateTransactionsExceedingMaximum(usize, String, usize),
+
     #[error("Method unavailable. No connection manager is currently available.")]
     NoConnectionManager,
 
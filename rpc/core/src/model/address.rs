@@ -1,4 +1,4 @@
-use crate::{RpcTransactionOutpoint, RpcUtxoEntry};
+use crate::{RpcTransactionId, RpcTransactionOutpoint, RpcUtxoEntry};
 use serde::{Deserialize, Serialize};
 use workflow_serializer::prelude::*;
 
@@ -58,3 +58,69 @@ impl Deserializer for RpcBalancesByAddressesEntry {
         Ok(Self { address, balance })
     }
 }
+
+/// Direction of an [`RpcAddressHistoryEntry`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[repr(i32)]
+pub enum RpcAddressHistoryEntryKind {
+    /// An output paying to the address was added to the UTXO set
+    Credit = 0,
+    /// An output paying to the address was removed from the UTXO set
+    Debit = 1,
+}
+
+impl From<RpcAddressHistoryEntryKind> for i32 {
+    fn from(value: RpcAddressHistoryEntryKind) -> Self {
+        value as i32
+    }
+}
+
+impl From<i32> for RpcAddressHistoryEntryKind {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Debit,
+            _ => Self::Credit,
+        }
+    }
+}
+
+/// Represents a credit or debit of an address returned by the `GetAddressHistory` RPC.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAddressHistoryEntry {
+    /// The credited or debited outpoint
+    pub outpoint: RpcTransactionOutpoint,
+    /// The transaction creating the credited output, or spending the debited one
+    pub transaction_id: RpcTransactionId,
+    pub kind: RpcAddressHistoryEntryKind,
+    pub amount: u64,
+    /// DAA score of the chain block accepting the creating or spending transaction
+    pub daa_score: u64,
+    pub is_coinbase: bool,
+}
+
+impl Serializer for RpcAddressHistoryEntry {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?; // version
+        serialize!(RpcTransactionOutpoint, &self.outpoint, writer)?;
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(i32, &i32::from(self.kind), writer)?;
+        store!(u64, &self.amount, writer)?;
+        store!(u64, &self.daa_score, writer)?;
+        store!(bool, &self.is_coinbase, writer)
+    }
+}
+
+impl Deserializer for RpcAddressHistoryEntry {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version: u8 = load!(u8, reader)?;
+        let outpoint = deserialize!(RpcTransactionOutpoint, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let kind = RpcAddressHistoryEntryKind::from(load!(i32, reader)?);
+        let amount = load!(u64, reader)?;
+        let daa_score = load!(u64, reader)?;
+        let is_coinbase = load!(bool, reader)?;
+        Ok(Self { outpoint, transaction_id, kind, amount, daa_score, is_coinbase })
+    }
+}
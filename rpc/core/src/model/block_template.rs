@@ -0,0 +1,201 @@
+use crate::{RpcSubnetworkId, RpcTransactionId};
+use serde::{Deserialize, Serialize};
+use workflow_serializer::prelude::*;
+
+/// Outcome of a transaction considered by the selection of a block template.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[repr(i32)]
+pub enum RpcTemplateSelectionOutcome {
+    /// Included in the block template
+    Selected = 0,
+    /// The block already uses the maximum number of lanes
+    LanesPerBlockLimit = 1,
+    /// Including the transaction would exceed the gas limit of its lane
+    GasPerLaneLimit = 2,
+    /// The transaction did not fit into the remaining block mass
+    BlockMassLimit = 3,
+    /// Excluded by the requester
+    Excluded = 4,
+    /// Found invalid by consensus, see [`RpcTemplateCandidate::validation_error`]
+    Invalid = 5,
+}
+
+impl From<RpcTemplateSelectionOutcome> for i32 {
+    fn from(value: RpcTemplateSelectionOutcome) -> Self {
+        value as i32
+    }
+}
+
+impl From<i32> for RpcTemplateSelectionOutcome {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::LanesPerBlockLimit,
+            2 => Self::GasPerLaneLimit,
+            3 => Self::BlockMassLimit,
+            4 => Self::Excluded,
+            5 => Self::Invalid,
+            _ => Self::Selected,
+        }
+    }
+}
+
+/// Block mass dimension, see [`RpcBlockMasses`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[repr(i32)]
+pub enum RpcMassDimension {
+    Compute = 0,
+    Transient = 1,
+    Storage = 2,
+}
+
+impl From<RpcMassDimension> for i32 {
+    fn from(value: RpcMassDimension) -> Self {
+        value as i32
+    }
+}
+
+impl From<i32> for RpcMassDimension {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Transient,
+            2 => Self::Storage,
+            _ => Self::Compute,
+        }
+    }
+}
+
+/// Frontier selector strategy used by the node for the size of its mempool.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[repr(i32)]
+pub enum RpcFrontierSelectorKind {
+    /// All ready transactions fit into a block
+    TakeAll = 0,
+    /// Ready transactions are sampled in advance, the mempool being much larger than a block
+    Sequence = 1,
+    /// Ready transactions are sampled during the selection
+    MutatingTree = 2,
+}
+
+impl From<RpcFrontierSelectorKind> for i32 {
+    fn from(value: RpcFrontierSelectorKind) -> Self {
+        value as i32
+    }
+}
+
+impl From<i32> for RpcFrontierSelectorKind {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Sequence,
+            2 => Self::MutatingTree,
+            _ => Self::TakeAll,
+        }
+    }
+}
+
+/// Masses per block mass dimension.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBlockMasses {
+    pub compute: u64,
+    pub transient: u64,
+    pub storage: u64,
+}
+
+impl Serializer for RpcBlockMasses {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?; // version
+        store!(u64, &self.compute, writer)?;
+        store!(u64, &self.transient, writer)?;
+        store!(u64, &self.storage, writer)
+    }
+}
+
+impl Deserializer for RpcBlockMasses {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version: u8 = load!(u8, reader)?;
+        let compute = load!(u64, reader)?;
+        let transient = load!(u64, reader)?;
+        let storage = load!(u64, reader)?;
+        Ok(Self { compute, transient, storage })
+    }
+}
+
+/// A mempool transaction considered by the selection of a block template.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTemplateCandidate {
+    pub transaction_id: RpcTransactionId,
+    pub outcome: RpcTemplateSelectionOutcome,
+    /// Consensus validation error of an invalid transaction
+    pub validation_error: Option<String>,
+    pub fee: u64,
+    /// Mass used by the selection, i.e. the largest mass over all dimensions once scaled to the compute mass limit
+    pub mass: u64,
+    pub masses: RpcBlockMasses,
+    /// Dimension the selection mass derives from
+    pub binding_dimension: RpcMassDimension,
+    pub subnetwork_id: RpcSubnetworkId,
+    pub gas: u64,
+}
+
+impl Serializer for RpcTemplateCandidate {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?; // version
+        store!(RpcTransactionId, &self.transaction_id, writer)?;
+        store!(i32, &i32::from(self.outcome), writer)?;
+        store!(Option<String>, &self.validation_error, writer)?;
+        store!(u64, &self.fee, writer)?;
+        store!(u64, &self.mass, writer)?;
+        serialize!(RpcBlockMasses, &self.masses, writer)?;
+        store!(i32, &i32::from(self.binding_dimension), writer)?;
+        store!(RpcSubnetworkId, &self.subnetwork_id, writer)?;
+        store!(u64, &self.gas, writer)
+    }
+}
+
+impl Deserializer for RpcTemplateCandidate {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version: u8 = load!(u8, reader)?;
+        let transaction_id = load!(RpcTransactionId, reader)?;
+        let outcome = RpcTemplateSelectionOutcome::from(load!(i32, reader)?);
+        let validation_error = load!(Option<String>, reader)?;
+        let fee = load!(u64, reader)?;
+        let mass = load!(u64, reader)?;
+        let masses = deserialize!(RpcBlockMasses, reader)?;
+        let binding_dimension = RpcMassDimension::from(load!(i32, reader)?);
+        let subnetwork_id = load!(RpcSubnetworkId, reader)?;
+        let gas = load!(u64, reader)?;
+        Ok(Self { transaction_id, outcome, validation_error, fee, mass, masses, binding_dimension, subnetwork_id, gas })
+    }
+}
+
+/// Usage of a lane by the transactions of a block template.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTemplateLaneUsage {
+    pub subnetwork_id: RpcSubnetworkId,
+    pub transaction_count: u64,
+    pub gas: u64,
+}
+
+impl Serializer for RpcTemplateLaneUsage {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?; // version
+        store!(RpcSubnetworkId, &self.subnetwork_id, writer)?;
+        store!(u64, &self.transaction_count, writer)?;
+        store!(u64, &self.gas, writer)
+    }
+}
+
+impl Deserializer for RpcTemplateLaneUsage {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version: u8 = load!(u8, reader)?;
+        let subnetwork_id = load!(RpcSubnetworkId, reader)?;
+        let transaction_count = load!(u64, reader)?;
+        let gas = load!(u64, reader)?;
+        Ok(Self { subnetwork_id, transaction_count, gas })
+    }
+}
@@ -107,3 +107,31 @@ impl Deserializer for RpcFeeEstimateVerboseExperimentalData {
         })
     }
 }
+
+/// A target inclusion time along with the required probability of meeting it
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeerateTarget {
+    pub target_seconds: u64,
+
+    /// Fraction of past transactions paying the estimated feerate which must have met the target, in `(0, 1]` (e.g. 0.95)
+    pub confidence: f64,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcFeerateTargetEstimate {
+    pub target_seconds: u64,
+    pub confidence: f64,
+
+    /// The fee/mass ratio estimated to be required for meeting the target
+    pub feerate: f64,
+
+    /// Whether the feerate is backed by observed mempool waiting times. When `false`, not enough
+    /// transactions were observed and the feerate is derived from the current mempool state alone,
+    /// regardless of the requested confidence.
+    pub from_history: bool,
+
+    /// Number of observed transactions backing a history based estimate
+    pub sample_count: u64,
+}
@@ -123,18 +123,42 @@ pub struct GetBlockTemplateRequest {
     pub pay_address: RpcAddress,
     // TODO: replace with hex serialization
     pub extra_data: RpcExtraData,
+    /// Mempool transactions to always include (along with their mempool ancestors) if valid, in order of preference
+    #[serde(default)]
+    pub priority_transaction_ids: Vec<RpcTransactionId>,
+    /// Mempool transactions which must not be included in the template
+    #[serde(default)]
+    pub excluded_transaction_ids: Vec<RpcTransactionId>,
+    /// Block mass reserved for the priority transactions, 0 allowing them to fill the whole block
+    #[serde(default)]
+    pub reserved_mass: u64,
 }
 impl GetBlockTemplateRequest {
     pub fn new(pay_address: RpcAddress, extra_data: RpcExtraData) -> Self {
-        Self { pay_address, extra_data }
+        Self { pay_address, extra_data, priority_transaction_ids: vec![], excluded_transaction_ids: vec![], reserved_mass: 0 }
+    }
+
+    pub fn with_customization(
+        mut self,
+        priority_transaction_ids: Vec<RpcTransactionId>,
+        excluded_transaction_ids: Vec<RpcTransactionId>,
+        reserved_mass: u64,
+    ) -> Self {
+        self.priority_transaction_ids = priority_transaction_ids;
+        self.excluded_transaction_ids = excluded_transaction_ids;
+        self.reserved_mass = reserved_mass;
+        self
     }
 }
 
 impl Serializer for GetBlockTemplateRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(RpcAddress, &self.pay_address, writer)?;
         store!(RpcExtraData, &self.extra_data, writer)?;
+        store!(Vec<RpcTransactionId>, &self.priority_transaction_ids, writer)?;
+        store!(Vec<RpcTransactionId>, &self.excluded_transaction_ids, writer)?;
+        store!(u64, &self.reserved_mass, writer)?;
 
         Ok(())
     }
@@ -142,11 +166,16 @@ impl Serializer for GetBlockTemplateRequest {
 
 impl Deserializer for GetBlockTemplateRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let pay_address = load!(RpcAddress, reader)?;
         let extra_data = load!(RpcExtraData, reader)?;
+        let (priority_transaction_ids, excluded_transaction_ids, reserved_mass) = if version > 1 {
+            (load!(Vec<RpcTransactionId>, reader)?, load!(Vec<RpcTransactionId>, reader)?, load!(u64, reader)?)
+        } else {
+            (vec![], vec![], 0)
+        };
 
-        Ok(Self { pay_address, extra_data })
+        Ok(Self { pay_address, extra_data, priority_transaction_ids, excluded_transaction_ids, reserved_mass })
     }
 }
 
@@ -603,16 +632,25 @@ impl GetConnectedPeerInfoResponse {
 
 impl Serializer for GetConnectedPeerInfoResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(Vec<RpcPeerInfo>, &self.peer_info, writer)?;
+        // Peer statistics are appended separately to keep the borsh layout of `RpcPeerInfo` readable by older clients
+        for info in self.peer_info.iter() {
+            serialize!(Option<RpcPeerStats>, &info.stats, writer)?;
+        }
         Ok(())
     }
 }
 
 impl Deserializer for GetConnectedPeerInfoResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
-        let peer_info = load!(Vec<RpcPeerInfo>, reader)?;
+        let version = load!(u16, reader)?;
+        let mut peer_info = load!(Vec<RpcPeerInfo>, reader)?;
+        if version > 1 {
+            for info in peer_info.iter_mut() {
+                info.stats = deserialize!(Option<RpcPeerStats>, reader)?;
+            }
+        }
         Ok(Self { peer_info })
     }
 }
@@ -2059,11 +2097,31 @@ pub struct ConnectionMetrics {
     pub json_handshake_failures: u64,
 
     pub active_peers: u32,
+
+    /// P2P messages exchanged with all peers since startup
+    #[serde(default)]
+    pub p2p_messages_rx: u64,
+    #[serde(default)]
+    pub p2p_messages_tx: u64,
+    /// Invalid blocks and transactions received from all peers since startup
+    #[serde(default)]
+    pub p2p_invalid_blocks: u64,
+    #[serde(default)]
+    pub p2p_invalid_transactions: u64,
+    /// Incoming P2P messages dropped since startup because their flow could not keep up
+    #[serde(default)]
+    pub p2p_dropped_messages: u64,
+    /// Average relay block latency of the active peers
+    #[serde(default)]
+    pub p2p_relay_latency_millis: u64,
+    /// Highest outgoing queue fullness among the active peers, in percent
+    #[serde(default)]
+    pub p2p_max_outgoing_queue_fullness: f32,
 }
 
 impl Serializer for ConnectionMetrics {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(u32, &self.borsh_live_connections, writer)?;
         store!(u64, &self.borsh_connection_attempts, writer)?;
         store!(u64, &self.borsh_handshake_failures, writer)?;
@@ -2071,6 +2129,13 @@ impl Serializer for ConnectionMetrics {
         store!(u64, &self.json_connection_attempts, writer)?;
         store!(u64, &self.json_handshake_failures, writer)?;
         store!(u32, &self.active_peers, writer)?;
+        store!(u64, &self.p2p_messages_rx, writer)?;
+        store!(u64, &self.p2p_messages_tx, writer)?;
+        store!(u64, &self.p2p_invalid_blocks, writer)?;
+        store!(u64, &self.p2p_invalid_transactions, writer)?;
+        store!(u64, &self.p2p_dropped_messages, writer)?;
+        store!(u64, &self.p2p_relay_latency_millis, writer)?;
+        store!(f32, &self.p2p_max_outgoing_queue_fullness, writer)?;
 
         Ok(())
     }
@@ -2078,7 +2143,7 @@ impl Serializer for ConnectionMetrics {
 
 impl Deserializer for ConnectionMetrics {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let borsh_live_connections = load!(u32, reader)?;
         let borsh_connection_attempts = load!(u64, reader)?;
         let borsh_handshake_failures = load!(u64, reader)?;
@@ -2086,8 +2151,7 @@ impl Deserializer for ConnectionMetrics {
         let json_connection_attempts = load!(u64, reader)?;
         let json_handshake_failures = load!(u64, reader)?;
         let active_peers = load!(u32, reader)?;
-
-        Ok(Self {
+        let mut metrics = Self {
             borsh_live_connections,
             borsh_connection_attempts,
             borsh_handshake_failures,
@@ -2095,7 +2159,19 @@ impl Deserializer for ConnectionMetrics {
             json_connection_attempts,
             json_handshake_failures,
             active_peers,
-        })
+            ..Default::default()
+        };
+        if version > 1 {
+            metrics.p2p_messages_rx = load!(u64, reader)?;
+            metrics.p2p_messages_tx = load!(u64, reader)?;
+            metrics.p2p_invalid_blocks = load!(u64, reader)?;
+            metrics.p2p_invalid_transactions = load!(u64, reader)?;
+            metrics.p2p_dropped_messages = load!(u64, reader)?;
+            metrics.p2p_relay_latency_millis = load!(u64, reader)?;
+            metrics.p2p_max_outgoing_queue_fullness = load!(f32, reader)?;
+        }
+
+        Ok(metrics)
     }
 }
 
@@ -2473,6 +2549,57 @@ impl Deserializer for GetSyncStatusResponse {
     }
 }
 
+/// Requests the progress of the initial block download (IBD), if running.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSyncProgressRequest {}
+
+impl Serializer for GetSyncProgressRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetSyncProgressRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSyncProgressResponse {
+    pub is_synced: bool,
+    /// Progress of the running IBD phase, `None` if no IBD is running
+    pub progress: Option<RpcSyncProgress>,
+}
+
+impl GetSyncProgressResponse {
+    pub fn new(is_synced: bool, progress: Option<RpcSyncProgress>) -> Self {
+        Self { is_synced, progress }
+    }
+}
+
+impl Serializer for GetSyncProgressResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(bool, &self.is_synced, writer)?;
+        serialize!(Option<RpcSyncProgress>, &self.progress, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for GetSyncProgressResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let is_synced = load!(bool, reader)?;
+        let progress = deserialize!(Option<RpcSyncProgress>, reader)?;
+        Ok(Self { is_synced, progress })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct GetDaaScoreTimestampEstimateRequest {
@@ -2576,21 +2703,27 @@ impl Deserializer for GetFeeEstimateResponse {
 #[serde(rename_all = "camelCase")]
 pub struct GetFeeEstimateExperimentalRequest {
     pub verbose: bool,
+
+    /// Inclusion time targets to estimate the required feerate for, see [`GetFeeEstimateExperimentalResponse::target_estimates`]
+    #[serde(default)]
+    pub targets: Vec<RpcFeerateTarget>,
 }
 
 impl Serializer for GetFeeEstimateExperimentalRequest {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         store!(bool, &self.verbose, writer)?;
+        store!(Vec<RpcFeerateTarget>, &self.targets, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetFeeEstimateExperimentalRequest {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let verbose = load!(bool, reader)?;
-        Ok(Self { verbose })
+        let targets = if version > 1 { load!(Vec<RpcFeerateTarget>, reader)? } else { vec![] };
+        Ok(Self { verbose, targets })
     }
 }
 
@@ -2602,23 +2735,30 @@ pub struct GetFeeEstimateExperimentalResponse {
 
     /// Experimental verbose data
     pub verbose: Option<RpcFeeEstimateVerboseExperimentalData>,
+
+    /// Feerates required for meeting each of the requested targets, in request order. Estimates are based
+    /// on the waiting times observed for accepted mempool transactions, with a fallback to the mempool model
+    #[serde(default)]
+    pub target_estimates: Vec<RpcFeerateTargetEstimate>,
 }
 
 impl Serializer for GetFeeEstimateExperimentalResponse {
     fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
-        store!(u16, &1, writer)?;
+        store!(u16, &2, writer)?;
         serialize!(RpcFeeEstimate, &self.estimate, writer)?;
         serialize!(Option<RpcFeeEstimateVerboseExperimentalData>, &self.verbose, writer)?;
+        store!(Vec<RpcFeerateTargetEstimate>, &self.target_estimates, writer)?;
         Ok(())
     }
 }
 
 impl Deserializer for GetFeeEstimateExperimentalResponse {
     fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
-        let _version = load!(u16, reader)?;
+        let version = load!(u16, reader)?;
         let estimate = deserialize!(RpcFeeEstimate, reader)?;
         let verbose = deserialize!(Option<RpcFeeEstimateVerboseExperimentalData>, reader)?;
-        Ok(Self { estimate, verbose })
+        let target_estimates = if version > 1 { load!(Vec<RpcFeerateTargetEstimate>, reader)? } else { vec![] };
+        Ok(Self { estimate, verbose, target_estimates })
     }
 }
 
@@ -2923,6 +3063,449 @@ impl Deserializer for GetVirtualChainFromBlockV2Response {
     }
 }
 
+/// Requests the transactions accepted by a selected chain block, or by all chain blocks within a DAA score range.
+///
+/// Results are paginated by transaction count. When a response is truncated, the next page is requested by sending
+/// `next_daa_score` as `start_daa_score` along with `next_skip` as `skip` (or, when querying a single
+/// `accepting_block_hash`, by resending it with `skip` set to `next_skip`).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAcceptedTransactionsRequest {
+    /// Chain block to query. When set, the DAA score range is ignored.
+    pub accepting_block_hash: Option<RpcHash>,
+    /// Inclusive lower bound of the accepting chain block DAA scores
+    pub start_daa_score: u64,
+    /// Inclusive upper bound of the accepting chain block DAA scores
+    pub end_daa_score: u64,
+    /// Number of accepted transactions to skip, counted from the first matching chain block
+    pub skip: u64,
+    /// Maximum number of transactions to return; `0` selects the server default
+    pub limit: u32,
+    /// Sets full verbosity by default
+    pub data_verbosity_level: Option<RpcDataVerbosityLevel>,
+}
+
+impl GetAcceptedTransactionsRequest {
+    pub fn new(
+        accepting_block_hash: Option<RpcHash>,
+        start_daa_score: u64,
+        end_daa_score: u64,
+        skip: u64,
+        limit: u32,
+        data_verbosity_level: Option<RpcDataVerbosityLevel>,
+    ) -> Self {
+        Self { accepting_block_hash, start_daa_score, end_daa_score, skip, limit, data_verbosity_level }
+    }
+
+    pub fn by_accepting_block(accepting_block_hash: RpcHash, skip: u64, limit: u32) -> Self {
+        Self::new(Some(accepting_block_hash), 0, 0, skip, limit, None)
+    }
+
+    pub fn by_daa_score_range(start_daa_score: u64, end_daa_score: u64, skip: u64, limit: u32) -> Self {
+        Self::new(None, start_daa_score, end_daa_score, skip, limit, None)
+    }
+}
+
+impl Serializer for GetAcceptedTransactionsRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Option<RpcHash>, &self.accepting_block_hash, writer)?;
+        store!(u64, &self.start_daa_score, writer)?;
+        store!(u64, &self.end_daa_score, writer)?;
+        store!(u64, &self.skip, writer)?;
+        store!(u32, &self.limit, writer)?;
+        serialize!(Option<RpcDataVerbosityLevel>, &self.data_verbosity_level, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetAcceptedTransactionsRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let accepting_block_hash = load!(Option<RpcHash>, reader)?;
+        let start_daa_score = load!(u64, reader)?;
+        let end_daa_score = load!(u64, reader)?;
+        let skip = load!(u64, reader)?;
+        let limit = load!(u32, reader)?;
+        let data_verbosity_level = deserialize!(Option<RpcDataVerbosityLevel>, reader)?;
+
+        Ok(Self { accepting_block_hash, start_daa_score, end_daa_score, skip, limit, data_verbosity_level })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAcceptedTransactionsResponse {
+    /// Matching chain blocks in chain order. Chain blocks accepting no transactions within the page are omitted.
+    pub accepting_blocks: Vec<RpcAcceptingBlockTransactions>,
+    /// DAA score of the chain block to resume from, if the page was truncated
+    pub next_daa_score: Option<u64>,
+    /// Number of accepted transactions of the `next_daa_score` chain block already returned
+    pub next_skip: u64,
+}
+
+impl Serializer for GetAcceptedTransactionsResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcAcceptingBlockTransactions>, &self.accepting_blocks, writer)?;
+        store!(Option<u64>, &self.next_daa_score, writer)?;
+        store!(u64, &self.next_skip, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetAcceptedTransactionsResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let accepting_blocks = deserialize!(Vec<RpcAcceptingBlockTransactions>, reader)?;
+        let next_daa_score = load!(Option<u64>, reader)?;
+        let next_skip = load!(u64, reader)?;
+
+        Ok(Self { accepting_blocks, next_daa_score, next_skip })
+    }
+}
+
+/// Requests the node to build, solve and submit `count` blocks on its own, paying the coinbase
+/// rewards to `pay_address`. Only available on simnet and devnet, or when proof of work is skipped.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateBlocksRequest {
+    pub count: u32,
+    pub pay_address: RpcAddress,
+}
+
+impl GenerateBlocksRequest {
+    pub fn new(count: u32, pay_address: RpcAddress) -> Self {
+        Self { count, pay_address }
+    }
+}
+
+impl Serializer for GenerateBlocksRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(u32, &self.count, writer)?;
+        store!(RpcAddress, &self.pay_address, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GenerateBlocksRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let count = load!(u32, reader)?;
+        let pay_address = load!(RpcAddress, reader)?;
+
+        Ok(Self { count, pay_address })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GenerateBlocksResponse {
+    /// Hashes of the generated blocks in the order they were submitted
+    pub block_hashes: Vec<RpcHash>,
+}
+
+impl Serializer for GenerateBlocksResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcHash>, &self.block_hashes, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GenerateBlocksResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let block_hashes = load!(Vec<RpcHash>, reader)?;
+
+        Ok(Self { block_hashes })
+    }
+}
+
+/// Requests a page of the credits and debits of an address, ordered by DAA score.
+///
+/// The first page starts at `start_daa_score`. The following ones are fetched by passing the `next_cursor`
+/// of the previous response as `cursor`, in which case `start_daa_score` is ignored.
+/// A `limit` of zero requests the largest page the node serves.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressHistoryRequest {
+    pub address: RpcAddress,
+    pub start_daa_score: u64,
+    pub cursor: Option<String>,
+    pub limit: u32,
+}
+
+impl GetAddressHistoryRequest {
+    pub fn new(address: RpcAddress, start_daa_score: u64, cursor: Option<String>, limit: u32) -> Self {
+        Self { address, start_daa_score, cursor, limit }
+    }
+}
+
+impl Serializer for GetAddressHistoryRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcAddress, &self.address, writer)?;
+        store!(u64, &self.start_daa_score, writer)?;
+        store!(Option<String>, &self.cursor, writer)?;
+        store!(u32, &self.limit, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetAddressHistoryRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let address = load!(RpcAddress, reader)?;
+        let start_daa_score = load!(u64, reader)?;
+        let cursor = load!(Option<String>, reader)?;
+        let limit = load!(u32, reader)?;
+
+        Ok(Self { address, start_daa_score, cursor, limit })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetAddressHistoryResponse {
+    pub entries: Vec<RpcAddressHistoryEntry>,
+    /// Opaque position of the next page, `None` once the history is exhausted
+    pub next_cursor: Option<String>,
+}
+
+impl GetAddressHistoryResponse {
+    pub fn new(entries: Vec<RpcAddressHistoryEntry>, next_cursor: Option<String>) -> Self {
+        Self { entries, next_cursor }
+    }
+}
+
+impl Serializer for GetAddressHistoryResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcAddressHistoryEntry>, &self.entries, writer)?;
+        store!(Option<String>, &self.next_cursor, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetAddressHistoryResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let entries = deserialize!(Vec<RpcAddressHistoryEntry>, reader)?;
+        let next_cursor = load!(Option<String>, reader)?;
+
+        Ok(Self { entries, next_cursor })
+    }
+}
+
+/// Requests the balances of addresses as they were at a past DAA score, computed from the address history.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBalanceAtRequest {
+    /// At most [`MAX_BALANCE_AT_ADDRESSES`](crate::api::rpc::MAX_BALANCE_AT_ADDRESSES) addresses
+    pub addresses: Vec<RpcAddress>,
+    pub daa_score: u64,
+}
+
+impl GetBalanceAtRequest {
+    pub fn new(addresses: Vec<RpcAddress>, daa_score: u64) -> Self {
+        Self { addresses, daa_score }
+    }
+}
+
+impl Serializer for GetBalanceAtRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Vec<RpcAddress>, &self.addresses, writer)?;
+        store!(u64, &self.daa_score, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetBalanceAtRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let addresses = load!(Vec<RpcAddress>, reader)?;
+        let daa_score = load!(u64, reader)?;
+
+        Ok(Self { addresses, daa_score })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBalanceAtResponse {
+    pub entries: Vec<RpcBalancesByAddressesEntry>,
+}
+
+impl GetBalanceAtResponse {
+    pub fn new(entries: Vec<RpcBalancesByAddressesEntry>) -> Self {
+        Self { entries }
+    }
+}
+
+impl Serializer for GetBalanceAtResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Vec<RpcBalancesByAddressesEntry>, &self.entries, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetBalanceAtResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let entries = deserialize!(Vec<RpcBalancesByAddressesEntry>, reader)?;
+
+        Ok(Self { entries })
+    }
+}
+
+/// Requests a block template dry-run returning the trace of its transaction selection, in order to find out
+/// why mempool transactions were left out. The request mirrors [`GetBlockTemplateRequest`], but the built
+/// template is neither cached nor returned, and transactions found invalid are kept in the mempool.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBlockTemplateDiagnosticsRequest {
+    pub pay_address: RpcAddress,
+    pub extra_data: RpcExtraData,
+    #[serde(default)]
+    pub priority_transaction_ids: Vec<RpcTransactionId>,
+    #[serde(default)]
+    pub excluded_transaction_ids: Vec<RpcTransactionId>,
+    #[serde(default)]
+    pub reserved_mass: u64,
+}
+
+impl GetBlockTemplateDiagnosticsRequest {
+    pub fn new(pay_address: RpcAddress, extra_data: RpcExtraData) -> Self {
+        Self { pay_address, extra_data, priority_transaction_ids: vec![], excluded_transaction_ids: vec![], reserved_mass: 0 }
+    }
+
+    pub fn with_customization(
+        mut self,
+        priority_transaction_ids: Vec<RpcTransactionId>,
+        excluded_transaction_ids: Vec<RpcTransactionId>,
+        reserved_mass: u64,
+    ) -> Self {
+        self.priority_transaction_ids = priority_transaction_ids;
+        self.excluded_transaction_ids = excluded_transaction_ids;
+        self.reserved_mass = reserved_mass;
+        self
+    }
+}
+
+impl Serializer for GetBlockTemplateDiagnosticsRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcAddress, &self.pay_address, writer)?;
+        store!(RpcExtraData, &self.extra_data, writer)?;
+        store!(Vec<RpcTransactionId>, &self.priority_transaction_ids, writer)?;
+        store!(Vec<RpcTransactionId>, &self.excluded_transaction_ids, writer)?;
+        store!(u64, &self.reserved_mass, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetBlockTemplateDiagnosticsRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let pay_address = load!(RpcAddress, reader)?;
+        let extra_data = load!(RpcExtraData, reader)?;
+        let priority_transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+        let excluded_transaction_ids = load!(Vec<RpcTransactionId>, reader)?;
+        let reserved_mass = load!(u64, reader)?;
+
+        Ok(Self { pay_address, extra_data, priority_transaction_ids, excluded_transaction_ids, reserved_mass })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBlockTemplateDiagnosticsResponse {
+    pub selector_kind: RpcFrontierSelectorKind,
+    /// Number of mempool transactions ready to be included in a block
+    pub ready_transaction_count: u64,
+    /// Number of non-coinbase transactions in the template
+    pub selected_transaction_count: u64,
+    pub total_fees: u64,
+    /// Masses of the selected transactions per dimension along with the block mass limits
+    pub mass_used: RpcBlockMasses,
+    pub mass_limits: RpcBlockMasses,
+    /// Selection masses of the selected transactions along with the block limit they were selected against
+    pub normalized_mass_used: u64,
+    pub normalized_mass_limit: u64,
+    pub lanes: Vec<RpcTemplateLaneUsage>,
+    pub lanes_per_block_limit: u64,
+    pub gas_per_lane_limit: u64,
+    /// Transactions considered by the selection, in the order they were first considered.
+    /// Sampling selectors may leave part of the ready transactions unconsidered.
+    pub candidates: Vec<RpcTemplateCandidate>,
+}
+
+impl Serializer for GetBlockTemplateDiagnosticsResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(i32, &i32::from(self.selector_kind), writer)?;
+        store!(u64, &self.ready_transaction_count, writer)?;
+        store!(u64, &self.selected_transaction_count, writer)?;
+        store!(u64, &self.total_fees, writer)?;
+        serialize!(RpcBlockMasses, &self.mass_used, writer)?;
+        serialize!(RpcBlockMasses, &self.mass_limits, writer)?;
+        store!(u64, &self.normalized_mass_used, writer)?;
+        store!(u64, &self.normalized_mass_limit, writer)?;
+        serialize!(Vec<RpcTemplateLaneUsage>, &self.lanes, writer)?;
+        store!(u64, &self.lanes_per_block_limit, writer)?;
+        store!(u64, &self.gas_per_lane_limit, writer)?;
+        serialize!(Vec<RpcTemplateCandidate>, &self.candidates, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for GetBlockTemplateDiagnosticsResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let selector_kind = RpcFrontierSelectorKind::from(load!(i32, reader)?);
+        let ready_transaction_count = load!(u64, reader)?;
+        let selected_transaction_count = load!(u64, reader)?;
+        let total_fees = load!(u64, reader)?;
+        let mass_used = deserialize!(RpcBlockMasses, reader)?;
+        let mass_limits = deserialize!(RpcBlockMasses, reader)?;
+        let normalized_mass_used = load!(u64, reader)?;
+        let normalized_mass_limit = load!(u64, reader)?;
+        let lanes = deserialize!(Vec<RpcTemplateLaneUsage>, reader)?;
+        let lanes_per_block_limit = load!(u64, reader)?;
+        let gas_per_lane_limit = load!(u64, reader)?;
+        let candidates = deserialize!(Vec<RpcTemplateCandidate>, reader)?;
+
+        Ok(Self {
+            selector_kind,
+            ready_transaction_count,
+            selected_transaction_count,
+            total_fees,
+            mass_used,
+            mass_limits,
+            normalized_mass_used,
+            normalized_mass_limit,
+            lanes,
+            lanes_per_block_limit,
+            gas_per_lane_limit,
+            candidates,
+        })
+    }
+}
+
 // ----------------------------------------------------------------------------
 // Subscriptions & notifications
 // ----------------------------------------------------------------------------
@@ -3655,6 +4238,84 @@ impl Deserializer for NewBlockTemplateNotification {
     }
 }
 
+// ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+// SyncProgressChangedNotification
+
+/// NotifySyncProgressChangedRequest registers this connection for syncProgressChanged notifications.
+///
+/// See: SyncProgressChangedNotification
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifySyncProgressChangedRequest {
+    pub command: Command,
+}
+
+impl NotifySyncProgressChangedRequest {
+    pub fn new(command: Command) -> Self {
+        Self { command }
+    }
+}
+
+impl Serializer for NotifySyncProgressChangedRequest {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(Command, &self.command, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifySyncProgressChangedRequest {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let command = load!(Command, reader)?;
+        Ok(Self { command })
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifySyncProgressChangedResponse {}
+
+impl Serializer for NotifySyncProgressChangedResponse {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for NotifySyncProgressChangedResponse {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        Ok(Self {})
+    }
+}
+
+/// SyncProgressChangedNotification is sent whenever an IBD phase starts, periodically
+/// while it progresses, and once the IBD ends, in which case `progress` is `None`.
+///
+/// See: NotifySyncProgressChangedRequest
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SyncProgressChangedNotification {
+    pub progress: Option<RpcSyncProgress>,
+}
+
+impl Serializer for SyncProgressChangedNotification {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(Option<RpcSyncProgress>, &self.progress, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for SyncProgressChangedNotification {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u16, reader)?;
+        let progress = deserialize!(Option<RpcSyncProgress>, reader)?;
+        Ok(Self { progress })
+    }
+}
+
 ///
 ///  wRPC response for RpcApiOps::Subscribe request
 ///
@@ -3,6 +3,7 @@
 
 pub mod address;
 pub mod block;
+pub mod block_template;
 pub mod blue_work;
 pub mod feerate_estimate;
 pub mod hash;
@@ -15,12 +16,14 @@ pub mod optional;
 pub mod peer;
 pub mod script_class;
 pub mod subnets;
+pub mod sync;
 mod tests;
 pub mod tx;
 pub mod verbosity;
 
 pub use address::*;
 pub use block::*;
+pub use block_template::*;
 pub use blue_work::*;
 pub use feerate_estimate::*;
 pub use hash::*;
@@ -32,5 +35,6 @@ pub use network::*;
 pub use optional::*;
 pub use peer::*;
 pub use subnets::*;
+pub use sync::*;
 pub use tx::*;
 pub use verbosity::*;
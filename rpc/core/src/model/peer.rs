@@ -1,6 +1,7 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use kaspa_utils::networking::{ContextualNetAddress, IpAddress, NetAddress, PeerId};
 use serde::{Deserialize, Serialize};
+use workflow_serializer::prelude::*;
 
 pub type RpcNodeId = PeerId;
 pub type RpcIpAddress = IpAddress;
@@ -20,4 +21,96 @@ pub struct RpcPeerInfo {
     pub advertised_protocol_version: u32,
     pub time_connected: u64, // NOTE: i64 in gRPC protowire
     pub is_ibd_peer: bool,
+
+    /// Traffic and misbehavior statistics, unavailable when talking to older nodes
+    #[serde(default)]
+    #[borsh(skip)]
+    pub stats: Option<RpcPeerStats>,
+}
+
+/// Traffic counters of a single P2P message type exchanged with a peer.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPeerMessageStats {
+    pub message_type: String,
+    pub messages_in: u64,
+    pub bytes_in: u64,
+    pub messages_out: u64,
+    pub bytes_out: u64,
+}
+
+impl Serializer for RpcPeerMessageStats {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        store!(String, &self.message_type, writer)?;
+        store!(u64, &self.messages_in, writer)?;
+        store!(u64, &self.bytes_in, writer)?;
+        store!(u64, &self.messages_out, writer)?;
+        store!(u64, &self.bytes_out, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcPeerMessageStats {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let message_type = load!(String, reader)?;
+        let messages_in = load!(u64, reader)?;
+        let bytes_in = load!(u64, reader)?;
+        let messages_out = load!(u64, reader)?;
+        let bytes_out = load!(u64, reader)?;
+        Ok(Self { message_type, messages_in, bytes_in, messages_out, bytes_out })
+    }
+}
+
+/// Traffic and misbehavior statistics of a connected peer.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPeerStats {
+    /// Traffic counters by message type, for message types exchanged at least once
+    pub messages: Vec<RpcPeerMessageStats>,
+    pub invalid_blocks: u64,
+    pub invalid_transactions: u64,
+    /// Incoming messages dropped because the flow handling them could not keep up
+    pub dropped_messages: u64,
+    /// Moving average of the time the peer takes to deliver a requested relay block
+    pub relay_latency_millis: Option<u64>,
+    pub outgoing_queue_len: u32,
+    pub outgoing_queue_capacity: u32,
+}
+
+impl Serializer for RpcPeerStats {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?;
+        serialize!(Vec<RpcPeerMessageStats>, &self.messages, writer)?;
+        store!(u64, &self.invalid_blocks, writer)?;
+        store!(u64, &self.invalid_transactions, writer)?;
+        store!(u64, &self.dropped_messages, writer)?;
+        store!(Option<u64>, &self.relay_latency_millis, writer)?;
+        store!(u32, &self.outgoing_queue_len, writer)?;
+        store!(u32, &self.outgoing_queue_capacity, writer)?;
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcPeerStats {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version = load!(u8, reader)?;
+        let messages = deserialize!(Vec<RpcPeerMessageStats>, reader)?;
+        let invalid_blocks = load!(u64, reader)?;
+        let invalid_transactions = load!(u64, reader)?;
+        let dropped_messages = load!(u64, reader)?;
+        let relay_latency_millis = load!(Option<u64>, reader)?;
+        let outgoing_queue_len = load!(u32, reader)?;
+        let outgoing_queue_capacity = load!(u32, reader)?;
+        Ok(Self {
+            messages,
+            invalid_blocks,
+            invalid_transactions,
+            dropped_messages,
+            relay_latency_millis,
+            outgoing_queue_len,
+            outgoing_queue_capacity,
+        })
+    }
 }
@@ -0,0 +1,95 @@
+use crate::{RpcNodeId, RpcPeerAddress};
+use serde::{Deserialize, Serialize};
+use workflow_serializer::prelude::*;
+
+/// Phase of an initial block download (IBD).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[repr(i32)]
+pub enum RpcSyncPhase {
+    /// Downloading and validating the pruning point proof
+    PruningProof = 0,
+    /// Downloading block headers
+    Headers = 1,
+    /// Downloading the pruning point UTXO set
+    UtxoSet = 2,
+    /// Downloading the pruning point SMT state
+    SmtState = 3,
+    /// Downloading block bodies
+    Bodies = 4,
+    /// Resolving the virtual state after all block data was received
+    VirtualResolution = 5,
+}
+
+impl From<RpcSyncPhase> for i32 {
+    fn from(value: RpcSyncPhase) -> Self {
+        value as i32
+    }
+}
+
+impl From<i32> for RpcSyncPhase {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::Headers,
+            2 => Self::UtxoSet,
+            3 => Self::SmtState,
+            4 => Self::Bodies,
+            5 => Self::VirtualResolution,
+            _ => Self::PruningProof,
+        }
+    }
+}
+
+/// Progress of a running IBD phase, as returned by the `GetSyncProgress` RPC
+/// and carried by `SyncProgressChanged` notifications.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSyncProgress {
+    pub phase: RpcSyncPhase,
+    /// Id of the peer the node is syncing from
+    pub syncer_id: RpcNodeId,
+    pub syncer_address: RpcPeerAddress,
+    /// Number of items (headers, UTXOs, SMT lanes, blocks) processed so far in this phase
+    pub processed: u64,
+    /// Total number of items of this phase, if known in advance
+    pub total: Option<u64>,
+    /// DAA score range covered by this phase, zero for phases not processing blocks
+    pub low_daa_score: u64,
+    pub high_daa_score: u64,
+    /// DAA score of the last processed block
+    pub current_daa_score: u64,
+    /// Estimated remaining time of this phase in seconds. Unavailable for phases with neither a known total
+    /// nor a DAA score range (pruning proof, SMT state, UTXO set and virtual resolution)
+    pub eta_seconds: Option<u64>,
+}
+
+impl Serializer for RpcSyncProgress {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u8, &1, writer)?; // version
+        store!(i32, &i32::from(self.phase), writer)?;
+        store!(RpcNodeId, &self.syncer_id, writer)?;
+        store!(RpcPeerAddress, &self.syncer_address, writer)?;
+        store!(u64, &self.processed, writer)?;
+        store!(Option<u64>, &self.total, writer)?;
+        store!(u64, &self.low_daa_score, writer)?;
+        store!(u64, &self.high_daa_score, writer)?;
+        store!(u64, &self.current_daa_score, writer)?;
+        store!(Option<u64>, &self.eta_seconds, writer)
+    }
+}
+
+impl Deserializer for RpcSyncProgress {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _version: u8 = load!(u8, reader)?;
+        let phase = RpcSyncPhase::from(load!(i32, reader)?);
+        let syncer_id = load!(RpcNodeId, reader)?;
+        let syncer_address = load!(RpcPeerAddress, reader)?;
+        let processed = load!(u64, reader)?;
+        let total = load!(Option<u64>, reader)?;
+        let low_daa_score = load!(u64, reader)?;
+        let high_daa_score = load!(u64, reader)?;
+        let current_daa_score = load!(u64, reader)?;
+        let eta_seconds = load!(Option<u64>, reader)?;
+        Ok(Self { phase, syncer_id, syncer_address, processed, total, low_daa_score, high_daa_score, current_daa_score, eta_seconds })
+    }
+}
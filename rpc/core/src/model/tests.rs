@@ -494,6 +494,33 @@ mod mockery {
                 advertised_protocol_version: mock(),
                 time_connected: mock(),
                 is_ibd_peer: mock(),
+                stats: mock(),
+            }
+        }
+    }
+
+    impl Mock for RpcPeerMessageStats {
+        fn mock() -> Self {
+            RpcPeerMessageStats {
+                message_type: "Block".to_string(),
+                messages_in: mock(),
+                bytes_in: mock(),
+                messages_out: mock(),
+                bytes_out: mock(),
+            }
+        }
+    }
+
+    impl Mock for RpcPeerStats {
+        fn mock() -> Self {
+            RpcPeerStats {
+                messages: mock(),
+                invalid_blocks: mock(),
+                invalid_transactions: mock(),
+                dropped_messages: mock(),
+                relay_latency_millis: mock(),
+                outgoing_queue_len: mock(),
+                outgoing_queue_capacity: mock(),
             }
         }
     }
@@ -598,6 +625,13 @@ mod mockery {
                 json_connection_attempts: mock(),
                 json_handshake_failures: mock(),
                 active_peers: mock(),
+                p2p_messages_rx: mock(),
+                p2p_messages_tx: mock(),
+                p2p_invalid_blocks: mock(),
+                p2p_invalid_transactions: mock(),
+                p2p_dropped_messages: mock(),
+                p2p_relay_latency_millis: mock(),
+                p2p_max_outgoing_queue_fullness: mock(),
             }
         }
     }
@@ -667,7 +701,13 @@ mod mockery {
 
     impl Mock for GetBlockTemplateRequest {
         fn mock() -> Self {
-            GetBlockTemplateRequest { pay_address: mock(), extra_data: vec![4, 2] }
+            GetBlockTemplateRequest {
+                pay_address: mock(),
+                extra_data: vec![4, 2],
+                priority_transaction_ids: mock(),
+                excluded_transaction_ids: mock(),
+                reserved_mass: mock(),
+            }
         }
     }
 
@@ -681,6 +721,69 @@ mod mockery {
 
     test!(GetBlockTemplateResponse);
 
+    impl Mock for RpcTemplateCandidate {
+        fn mock() -> Self {
+            RpcTemplateCandidate {
+                transaction_id: mock(),
+                outcome: RpcTemplateSelectionOutcome::Invalid,
+                validation_error: Some("transaction is missing outpoints".to_string()),
+                fee: mock(),
+                mass: mock(),
+                masses: mock(),
+                binding_dimension: RpcMassDimension::Storage,
+                subnetwork_id: mock(),
+                gas: mock(),
+            }
+        }
+    }
+
+    impl Mock for RpcBlockMasses {
+        fn mock() -> Self {
+            RpcBlockMasses { compute: mock(), transient: mock(), storage: mock() }
+        }
+    }
+
+    impl Mock for RpcTemplateLaneUsage {
+        fn mock() -> Self {
+            RpcTemplateLaneUsage { subnetwork_id: mock(), transaction_count: mock(), gas: mock() }
+        }
+    }
+
+    impl Mock for GetBlockTemplateDiagnosticsRequest {
+        fn mock() -> Self {
+            GetBlockTemplateDiagnosticsRequest {
+                pay_address: mock(),
+                extra_data: vec![4, 2],
+                priority_transaction_ids: mock(),
+                excluded_transaction_ids: mock(),
+                reserved_mass: mock(),
+            }
+        }
+    }
+
+    test!(GetBlockTemplateDiagnosticsRequest);
+
+    impl Mock for GetBlockTemplateDiagnosticsResponse {
+        fn mock() -> Self {
+            GetBlockTemplateDiagnosticsResponse {
+                selector_kind: RpcFrontierSelectorKind::MutatingTree,
+                ready_transaction_count: mock(),
+                selected_transaction_count: mock(),
+                total_fees: mock(),
+                mass_used: mock(),
+                mass_limits: mock(),
+                normalized_mass_used: mock(),
+                normalized_mass_limit: mock(),
+                lanes: mock(),
+                lanes_per_block_limit: mock(),
+                gas_per_lane_limit: mock(),
+                candidates: mock(),
+            }
+        }
+    }
+
+    test!(GetBlockTemplateDiagnosticsResponse);
+
     impl Mock for GetBlockRequest {
         fn mock() -> Self {
             GetBlockRequest { hash: mock(), include_transactions: true }
@@ -1279,6 +1382,38 @@ mod mockery {
 
     test!(GetSyncStatusResponse);
 
+    impl Mock for RpcSyncProgress {
+        fn mock() -> Self {
+            RpcSyncProgress {
+                phase: RpcSyncPhase::Bodies,
+                syncer_id: mock(),
+                syncer_address: mock(),
+                processed: mock(),
+                total: Some(mock()),
+                low_daa_score: mock(),
+                high_daa_score: mock(),
+                current_daa_score: mock(),
+                eta_seconds: Some(mock()),
+            }
+        }
+    }
+
+    impl Mock for GetSyncProgressRequest {
+        fn mock() -> Self {
+            GetSyncProgressRequest {}
+        }
+    }
+
+    test!(GetSyncProgressRequest);
+
+    impl Mock for GetSyncProgressResponse {
+        fn mock() -> Self {
+            GetSyncProgressResponse { is_synced: false, progress: Some(mock()) }
+        }
+    }
+
+    test!(GetSyncProgressResponse);
+
     impl Mock for GetDaaScoreTimestampEstimateRequest {
         fn mock() -> Self {
             GetDaaScoreTimestampEstimateRequest { daa_scores: mock() }
@@ -1349,6 +1484,109 @@ mod mockery {
 
     test!(GetVirtualChainFromBlockV2Response);
 
+    impl Mock for GetAcceptedTransactionsRequest {
+        fn mock() -> Self {
+            GetAcceptedTransactionsRequest {
+                accepting_block_hash: mock(),
+                start_daa_score: mock(),
+                end_daa_score: mock(),
+                skip: mock(),
+                limit: mock(),
+                data_verbosity_level: None,
+            }
+        }
+    }
+
+    test!(GetAcceptedTransactionsRequest);
+
+    impl Mock for RpcAcceptedTransaction {
+        fn mock() -> Self {
+            RpcAcceptedTransaction {
+                transaction: mock(),
+                merged_block_hash: mock(),
+                fee: mock(),
+                compute_mass: mock(),
+                transient_mass: mock(),
+                storage_mass: mock(),
+            }
+        }
+    }
+
+    impl Mock for RpcAcceptingBlockTransactions {
+        fn mock() -> Self {
+            RpcAcceptingBlockTransactions { accepting_block_hash: mock(), accepting_daa_score: mock(), accepted_transactions: mock() }
+        }
+    }
+
+    impl Mock for GetAcceptedTransactionsResponse {
+        fn mock() -> Self {
+            GetAcceptedTransactionsResponse { accepting_blocks: mock(), next_daa_score: mock(), next_skip: mock() }
+        }
+    }
+
+    test!(GetAcceptedTransactionsResponse);
+
+    impl Mock for GenerateBlocksRequest {
+        fn mock() -> Self {
+            GenerateBlocksRequest { count: mock(), pay_address: mock() }
+        }
+    }
+
+    test!(GenerateBlocksRequest);
+
+    impl Mock for GenerateBlocksResponse {
+        fn mock() -> Self {
+            GenerateBlocksResponse { block_hashes: mock() }
+        }
+    }
+
+    test!(GenerateBlocksResponse);
+
+    impl Mock for GetAddressHistoryRequest {
+        fn mock() -> Self {
+            GetAddressHistoryRequest { address: mock(), start_daa_score: mock(), cursor: Some("00ff".to_string()), limit: mock() }
+        }
+    }
+
+    test!(GetAddressHistoryRequest);
+
+    impl Mock for RpcAddressHistoryEntry {
+        fn mock() -> Self {
+            RpcAddressHistoryEntry {
+                outpoint: mock(),
+                transaction_id: mock(),
+                kind: RpcAddressHistoryEntryKind::Debit,
+                amount: mock(),
+                daa_score: mock(),
+                is_coinbase: mock(),
+            }
+        }
+    }
+
+    impl Mock for GetAddressHistoryResponse {
+        fn mock() -> Self {
+            GetAddressHistoryResponse { entries: mock(), next_cursor: Some("00ff".to_string()) }
+        }
+    }
+
+    test!(GetAddressHistoryResponse);
+
+    impl Mock for GetBalanceAtRequest {
+        fn mock() -> Self {
+            GetBalanceAtRequest { addresses: mock(), daa_score: mock() }
+        }
+    }
+
+    test!(GetBalanceAtRequest);
+
+    impl Mock for GetBalanceAtResponse {
+        fn mock() -> Self {
+            GetBalanceAtResponse { entries: mock() }
+        }
+    }
+
+    test!(GetBalanceAtResponse);
+
     impl Mock for NotifyBlockAddedRequest {
         fn mock() -> Self {
             NotifyBlockAddedRequest { command: Command::Start }
@@ -1569,6 +1807,30 @@ mod mockery {
 
     test!(NewBlockTemplateNotification);
 
+    impl Mock for NotifySyncProgressChangedRequest {
+        fn mock() -> Self {
+            NotifySyncProgressChangedRequest { command: Command::Start }
+        }
+    }
+
+    test!(NotifySyncProgressChangedRequest);
+
+    impl Mock for NotifySyncProgressChangedResponse {
+        fn mock() -> Self {
+            NotifySyncProgressChangedResponse {}
+        }
+    }
+
+    test!(NotifySyncProgressChangedResponse);
+
+    impl Mock for SyncProgressChangedNotification {
+        fn mock() -> Self {
+            SyncProgressChangedNotification { progress: Some(mock()) }
+        }
+    }
+
+    test!(SyncProgressChangedNotification);
+
     impl Mock for SubscribeResponse {
         fn mock() -> Self {
             SubscribeResponse::new(mock())
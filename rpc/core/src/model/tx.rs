@@ -681,3 +681,78 @@ impl Deserializer for RpcChainBlockAcceptedTransactions {
         Ok(Self { chain_block_header, accepted_transactions })
     }
 }
+
+/// Represents a transaction accepted by a chain block, along with the data resolved at acceptance
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAcceptedTransaction {
+    /// Transaction fields as selected by the requested verbosity
+    pub transaction: RpcOptionalTransaction,
+    /// The merged block which included the transaction
+    pub merged_block_hash: RpcHash,
+    /// Sum of the spent UTXO amounts minus the sum of the output values; `None` for coinbase transactions
+    pub fee: Option<u64>,
+    pub compute_mass: u64,
+    pub transient_mass: u64,
+    pub storage_mass: u64,
+}
+
+impl Serializer for RpcAcceptedTransaction {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        serialize!(RpcOptionalTransaction, &self.transaction, writer)?;
+        store!(RpcHash, &self.merged_block_hash, writer)?;
+        store!(Option<u64>, &self.fee, writer)?;
+        store!(u64, &self.compute_mass, writer)?;
+        store!(u64, &self.transient_mass, writer)?;
+        store!(u64, &self.storage_mass, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcAcceptedTransaction {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _struct_version = load!(u16, reader)?;
+        let transaction = deserialize!(RpcOptionalTransaction, reader)?;
+        let merged_block_hash = load!(RpcHash, reader)?;
+        let fee = load!(Option<u64>, reader)?;
+        let compute_mass = load!(u64, reader)?;
+        let transient_mass = load!(u64, reader)?;
+        let storage_mass = load!(u64, reader)?;
+
+        Ok(Self { transaction, merged_block_hash, fee, compute_mass, transient_mass, storage_mass })
+    }
+}
+
+/// Represents the transactions accepted by a single chain block
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAcceptingBlockTransactions {
+    pub accepting_block_hash: RpcHash,
+    pub accepting_daa_score: u64,
+    /// Accepted transactions in acceptance order, possibly a slice of them when the page boundary falls within this block
+    pub accepted_transactions: Vec<RpcAcceptedTransaction>,
+}
+
+impl Serializer for RpcAcceptingBlockTransactions {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        store!(u16, &1, writer)?;
+        store!(RpcHash, &self.accepting_block_hash, writer)?;
+        store!(u64, &self.accepting_daa_score, writer)?;
+        serialize!(Vec<RpcAcceptedTransaction>, &self.accepted_transactions, writer)?;
+
+        Ok(())
+    }
+}
+
+impl Deserializer for RpcAcceptingBlockTransactions {
+    fn deserialize<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {
+        let _struct_version = load!(u16, reader)?;
+        let accepting_block_hash = load!(RpcHash, reader)?;
+        let accepting_daa_score = load!(u64, reader)?;
+        let accepted_transactions = deserialize!(Vec<RpcAcceptedTransaction>, reader)?;
+
+        Ok(Self { accepting_block_hash, accepting_daa_score, accepted_transactions })
+    }
+}
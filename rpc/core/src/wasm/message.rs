@@ -541,6 +541,52 @@ try_from! ( args: GetSyncStatusResponse, IGetSyncStatusResponse, {
     Ok(to_value(&args)?.into())
 });
 
+// ---
+
+declare! {
+    IGetSyncProgressRequest,
+    r#"
+    /**
+     * @category Node RPC
+     */
+    export interface IGetSyncProgressRequest { }
+    "#,
+}
+
+try_from! ( args: IGetSyncProgressRequest, GetSyncProgressRequest, {
+    Ok(from_value(args.into())?)
+});
+
+declare! {
+    IGetSyncProgressResponse,
+    r#"
+    /**
+     * Progress of the running initial block download (IBD), undefined when
+     * no IBD is running.
+     *
+     * @category Node RPC
+     */
+    export interface IGetSyncProgressResponse {
+        isSynced : boolean;
+        progress? : {
+            phase : "pruning-proof" | "headers" | "utxo-set" | "smt-state" | "bodies" | "virtual-resolution";
+            syncerId : string;
+            syncerAddress : string;
+            processed : bigint;
+            total? : bigint;
+            lowDaaScore : bigint;
+            highDaaScore : bigint;
+            currentDaaScore : bigint;
+            etaSeconds? : bigint;
+        };
+    }
+    "#,
+}
+
+try_from! ( args: GetSyncProgressResponse, IGetSyncProgressResponse, {
+    Ok(to_value(&args)?.into())
+});
+
 /*
     Interfaces for methods with arguments
 */
@@ -846,6 +892,18 @@ declare! {
          * `extraData` can contain a user-supplied plain text or a byte array represented by `Uint8array`.
          */
         extraData? : string | Uint8Array;
+        /**
+         * Mempool transactions to always include (along with their mempool ancestors) if valid, in order of preference.
+         */
+        priorityTransactionIds? : HexString[];
+        /**
+         * Mempool transactions which must not be included in the template.
+         */
+        excludedTransactionIds? : HexString[];
+        /**
+         * Block mass reserved for the priority transactions, 0 (default) allowing them to fill the whole block.
+         */
+        reservedMass? : bigint;
     }
     "#,
 }
@@ -861,9 +919,15 @@ try_from! ( args: IGetBlockTemplateRequest, GetBlockTemplateRequest, {
     } else {
         Default::default()
     };
+    let priority_transaction_ids = args.try_get_value("priorityTransactionIds")?.map(from_value).transpose()?.unwrap_or_default();
+    let excluded_transaction_ids = args.try_get_value("excludedTransactionIds")?.map(from_value).transpose()?.unwrap_or_default();
+    let reserved_mass = args.get_u64("reservedMass").ok().unwrap_or_default();
     Ok(GetBlockTemplateRequest {
         pay_address,
         extra_data,
+        priority_transaction_ids,
+        excluded_transaction_ids,
+        reserved_mass,
     })
 });
 
@@ -1822,7 +1886,22 @@ declare! {
      *
      * @category Node RPC
      */
-    export interface IGetFeeEstimateExperimentalRequest { }
+    export interface IGetFeeEstimateExperimentalRequest {
+        verbose : boolean;
+        /**
+         * Inclusion time targets to estimate the required feerate for,
+         * e.g. `{ targetSeconds : 60, confidence : 0.95 }`
+         */
+        targets? : IFeerateTarget[];
+    }
+
+    /**
+     * @category Node RPC
+     */
+    export interface IFeerateTarget {
+        targetSeconds : bigint;
+        confidence : number;
+    }
     "#,
 }
 
@@ -1841,6 +1920,22 @@ declare! {
     export interface IGetFeeEstimateExperimentalResponse {
         estimate : IFeeEstimate;
         verbose? : IFeeEstimateVerboseExperimentalData
+        targetEstimates : IFeerateTargetEstimate[];
+    }
+
+    /**
+     * Feerate required for meeting a requested inclusion time target.
+     * When `fromHistory` is false, not enough transactions were observed and the
+     * feerate is derived from the current mempool state alone.
+     *
+     * @category Node RPC
+     */
+    export interface IFeerateTargetEstimate {
+        targetSeconds : bigint;
+        confidence : number;
+        feerate : number;
+        fromHistory : boolean;
+        sampleCount : bigint;
     }
     "#,
 }
@@ -1854,6 +1949,7 @@ try_from!( args: GetFeeEstimateExperimentalResponse, IGetFeeEstimateExperimental
         let verbose = IFeeEstimateVerboseExperimentalData::try_from(verbose)?;
         response.set("verbose", &verbose)?;
     }
+    response.set("targetEstimates", &to_value(&args.target_estimates)?)?;
 
     Ok(response)
 });
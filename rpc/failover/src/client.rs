@@ -0,0 +1,575 @@
+use crate::node::{FailoverNode, FailoverOptions, NodeHealth, select_node};
+use async_trait::async_trait;
+use futures::future::join_all;
+use kaspa_core::{debug, info, trace, warn};
+use kaspa_notify::{
+    collector::CollectorFrom,
+    error::{Error as NotifyError, Result as NotifyResult},
+    events::EVENT_TYPE_ARRAY,
+    listener::{ListenerId, ListenerLifespan},
+    notifier::Notifier,
+    scope::Scope,
+    subscriber::{Subscriber, SubscriptionManager},
+    subscription::{MutationPolicies, UtxosChangedMutationPolicy, context::SubscriptionContext},
+};
+use kaspa_rpc_core::{
+    Notification,
+    api::{ctl::RpcCtl, rpc::RpcApi},
+    error::{RpcError, RpcResult},
+    model::message::*,
+    notify::{collector::RpcCoreConverter, connection::ChannelConnection},
+};
+use kaspa_utils::{channel::Channel, triggers::SingleTrigger};
+use std::{
+    fmt::{self, Debug},
+    sync::{Arc, Mutex},
+};
+use tokio::task::JoinHandle;
+
+const FAILOVER_CLIENT: &str = "failover-client";
+
+pub type FailoverClientCollector = CollectorFrom<RpcCoreConverter>;
+pub type FailoverClientNotifier = Notifier<Notification, ChannelConnection>;
+
+/// RPC client spreading over several nodes of which it routes every call to
+/// the most up-to-date synced one.
+///
+/// Nodes are health checked periodically with `GetServerInfo`. When the
+/// active node changes, the compounded notification subscriptions of all
+/// listeners are moved to the new node, so listeners keep receiving
+/// notifications without taking any action. Notifications emitted while
+/// switching may however be missed, which is signaled through [`Self::ctl`]
+/// by a close immediately followed by an open.
+///
+/// Usable wherever an [`RpcApi`] is expected, e.g. as the RPC of a wallet
+/// (`kaspa_wallet_core::rpc::Rpc` converts from the client and its [`RpcCtl`]).
+#[derive(Clone)]
+pub struct FailoverRpcClient {
+    inner: Arc<Inner>,
+    notifier: Arc<FailoverClientNotifier>,
+}
+
+struct Inner {
+    nodes: Vec<FailoverNode>,
+    options: FailoverOptions,
+    active: Mutex<Option<usize>>,
+    /// Serializes node switches with the forwarding of subscriptions to the active node
+    switching: tokio::sync::Mutex<()>,
+    notification_channel: Channel<Notification>,
+    ctl: RpcCtl,
+    monitor: Mutex<Option<JoinHandle<()>>>,
+    shutdown: SingleTrigger,
+}
+
+impl Debug for Inner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nodes = self.nodes.iter().map(|node| node.name()).collect::<Vec<_>>();
+        f.debug_struct("Inner").field("nodes", &nodes).field("active", &self.active.lock().unwrap()).finish()
+    }
+}
+
+impl FailoverRpcClient {
+    /// The order of `nodes` sets their priority among equally synced ones
+    pub fn new(nodes: Vec<FailoverNode>, options: FailoverOptions, subscription_context: Option<SubscriptionContext>) -> Self {
+        let inner = Arc::new(Inner {
+            nodes,
+            options,
+            active: Mutex::new(None),
+            switching: Default::default(),
+            notification_channel: Channel::unbounded(),
+            ctl: RpcCtl::new(),
+            monitor: Default::default(),
+            shutdown: SingleTrigger::new(),
+        });
+
+        let enabled_events = EVENT_TYPE_ARRAY[..].into();
+        let converter = Arc::new(RpcCoreConverter::new());
+        let collector = Arc::new(FailoverClientCollector::new(FAILOVER_CLIENT, inner.notification_channel.receiver(), converter));
+        let subscriber = Arc::new(Subscriber::new(FAILOVER_CLIENT, enabled_events, inner.clone(), 0));
+        let policies = MutationPolicies::new(UtxosChangedMutationPolicy::AddressSet);
+        let notifier = Arc::new(Notifier::new(
+            FAILOVER_CLIENT,
+            enabled_events,
+            vec![collector],
+            vec![subscriber],
+            subscription_context.unwrap_or_default(),
+            3,
+            policies,
+        ));
+
+        Self { inner, notifier }
+    }
+
+    /// Runs a first health check so that calls can be routed right away,
+    /// then keeps monitoring the nodes in the background.
+    pub async fn start(&self) {
+        self.notifier.clone().start();
+        self.check_health().await;
+
+        let this = self.clone();
+        let shutdown = self.inner.shutdown.listener.clone();
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(this.inner.options.health_check_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    _ = shutdown.clone() => break,
+                    _ = interval.tick() => this.check_health().await,
+                }
+            }
+            trace!("Failover client: health monitor terminated");
+        });
+        *self.inner.monitor.lock().unwrap() = Some(task);
+    }
+
+    pub async fn stop(&self) -> RpcResult<()> {
+        self.inner.shutdown.trigger.trigger();
+        let monitor = self.inner.monitor.lock().unwrap().take();
+        if let Some(monitor) = monitor {
+            monitor.await.map_err(|err| RpcError::General(err.to_string()))?;
+        }
+        self.activate(None).await;
+        self.inner.notification_channel.close();
+        self.notifier.join().await?;
+        Ok(())
+    }
+
+    /// Checks all nodes concurrently and switches to a better node if needed
+    pub async fn check_health(&self) {
+        let timeout = self.inner.options.health_check_timeout;
+        let health = join_all(self.inner.nodes.iter().map(|node| node.check_health(timeout))).await;
+        let current = *self.inner.active.lock().unwrap();
+        let selected = select_node(&health, current, &self.inner.options);
+        if selected != current {
+            self.activate(selected).await;
+        }
+    }
+
+    async fn activate(&self, selected: Option<usize>) {
+        let previous = {
+            let _guard = self.inner.switching.lock().await;
+            let previous = std::mem::replace(&mut *self.inner.active.lock().unwrap(), selected);
+            if previous == selected {
+                return;
+            }
+            if let Some(previous) = previous {
+                self.inner.nodes[previous].detach().await;
+            }
+            if let Some(selected) = selected {
+                self.inner.nodes[selected].attach(self.inner.notification_channel.sender());
+            }
+            previous
+        };
+
+        let name = |index: Option<usize>| index.map(|index| self.inner.nodes[index].name().to_string());
+        match selected {
+            Some(_) => info!("Failover client: routing RPC calls to node {}", name(selected).unwrap()),
+            None if previous.is_some() => warn!("Failover client: no RPC node available"),
+            None => {}
+        }
+
+        // Replays the subscriptions of all listeners through `start_notify` below, now targeting the new node
+        if selected.is_some()
+            && let Err(err) = self.notifier.try_renew_subscriptions()
+        {
+            warn!("Failover client: unable to renew notification subscriptions: {err}");
+        }
+
+        if previous.is_some() {
+            let _ = self.inner.ctl.signal_close().await;
+        }
+        self.inner.ctl.set_descriptor(name(selected));
+        if selected.is_some() {
+            let _ = self.inner.ctl.signal_open().await;
+        }
+    }
+
+    /// Connection state signaling; a node switch is reported as a disconnection followed by a connection
+    pub fn ctl(&self) -> &RpcCtl {
+        &self.inner.ctl
+    }
+
+    pub fn notifier(&self) -> Arc<FailoverClientNotifier> {
+        self.notifier.clone()
+    }
+
+    pub fn nodes(&self) -> &[FailoverNode] {
+        &self.inner.nodes
+    }
+
+    pub fn active_node(&self) -> Option<&FailoverNode> {
+        self.inner.active.lock().unwrap().map(|index| &self.inner.nodes[index])
+    }
+
+    pub fn nodes_health(&self) -> Vec<(String, NodeHealth)> {
+        self.inner.nodes.iter().map(|node| (node.name().to_string(), node.health())).collect()
+    }
+}
+
+impl Inner {
+    fn active_client(&self) -> RpcResult<Arc<dyn RpcApi>> {
+        let active = *self.active.lock().unwrap();
+        active.and_then(|index| self.nodes[index].client()).ok_or_else(|| RpcError::General("no RPC node available".to_string()))
+    }
+}
+
+#[async_trait]
+impl SubscriptionManager for Inner {
+    async fn start_notify(&self, _: ListenerId, scope: Scope) -> NotifyResult<()> {
+        let _guard = self.switching.lock().await;
+        // Without an active node, the subscription gets renewed once a node is selected
+        let Some(node) = self.active.lock().unwrap().map(|index| &self.nodes[index]) else {
+            return Ok(());
+        };
+        let (Some(id), Some(client)) = (node.listener_id(), node.client()) else {
+            return Ok(());
+        };
+        debug!("Failover client: start_notify {:?} on node {}", scope, node.name());
+        client.start_notify(id, scope).await.map_err(|err| NotifyError::General(err.to_string()))
+    }
+
+    async fn stop_notify(&self, _: ListenerId, scope: Scope) -> NotifyResult<()> {
+        let _guard = self.switching.lock().await;
+        let Some(node) = self.active.lock().unwrap().map(|index| &self.nodes[index]) else {
+            return Ok(());
+        };
+        let (Some(id), Some(client)) = (node.listener_id(), node.client()) else {
+            return Ok(());
+        };
+        debug!("Failover client: stop_notify {:?} on node {}", scope, node.name());
+        client.stop_notify(id, scope).await.map_err(|err| NotifyError::General(err.to_string()))
+    }
+}
+
+#[async_trait]
+impl RpcApi for FailoverRpcClient {
+    route!(ping_call, Ping);
+    route!(get_sync_status_call, GetSyncStatus);
+    route!(get_server_info_call, GetServerInfo);
+    route!(get_metrics_call, GetMetrics);
+    route!(get_connections_call, GetConnections);
+    route!(get_system_info_call, GetSystemInfo);
+    route!(submit_block_call, SubmitBlock);
+    route!(get_block_template_call, GetBlockTemplate);
+    route!(get_block_call, GetBlock);
+    route!(get_info_call, GetInfo);
+    route!(get_current_network_call, GetCurrentNetwork);
+    route!(get_peer_addresses_call, GetPeerAddresses);
+    route!(get_sink_call, GetSink);
+    route!(get_mempool_entry_call, GetMempoolEntry);
+    route!(get_mempool_entries_call, GetMempoolEntries);
+    route!(get_connected_peer_info_call, GetConnectedPeerInfo);
+    route!(add_peer_call, AddPeer);
+    route!(submit_transaction_call, SubmitTransaction);
+    route!(submit_transaction_replacement_call, SubmitTransactionReplacement);
+    route!(get_subnetwork_call, GetSubnetwork);
+    route!(get_virtual_chain_from_block_call, GetVirtualChainFromBlock);
+    route!(get_blocks_call, GetBlocks);
+    route!(get_block_count_call, GetBlockCount);
+    route!(get_block_dag_info_call, GetBlockDagInfo);
+    route!(resolve_finality_conflict_call, ResolveFinalityConflict);
+    route!(shutdown_call, Shutdown);
+    route!(get_headers_call, GetHeaders);
+    route!(get_utxos_by_addresses_call, GetUtxosByAddresses);
+    route!(get_balance_by_address_call, GetBalanceByAddress);
+    route!(get_balances_by_addresses_call, GetBalancesByAddresses);
+    route!(get_sink_blue_score_call, GetSinkBlueScore);
+    route!(ban_call, Ban);
+    route!(unban_call, Unban);
+    route!(estimate_network_hashes_per_second_call, EstimateNetworkHashesPerSecond);
+    route!(get_mempool_entries_by_addresses_call, GetMempoolEntriesByAddresses);
+    route!(get_coin_supply_call, GetCoinSupply);
+    route!(get_daa_score_timestamp_estimate_call, GetDaaScoreTimestampEstimate);
+    route!(get_fee_estimate_call, GetFeeEstimate);
+    route!(get_fee_estimate_experimental_call, GetFeeEstimateExperimental);
+    route!(get_current_block_color_call, GetCurrentBlockColor);
+    route!(get_block_reward_info_call, GetBlockRewardInfo);
+    route!(get_utxo_return_address_call, GetUtxoReturnAddress);
+    route!(get_virtual_chain_from_block_v2_call, GetVirtualChainFromBlockV2);
+    route!(get_seq_commit_lane_proof_call, GetSeqCommitLaneProof);
+    route!(get_accepted_transactions_call, GetAcceptedTransactions);
+    route!(generate_blocks_call, GenerateBlocks);
+    route!(get_address_history_call, GetAddressHistory);
+    route!(get_balance_at_call, GetBalanceAt);
+    route!(get_sync_progress_call, GetSyncProgress);
+    route!(get_block_template_diagnostics_call, GetBlockTemplateDiagnostics);
+
+    // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    // Notification API
+
+    fn register_new_listener(&self, connection: ChannelConnection) -> ListenerId {
+        self.notifier.register_new_listener(connection, ListenerLifespan::Dynamic)
+    }
+
+    async fn unregister_listener(&self, id: ListenerId) -> RpcResult<()> {
+        self.notifier.unregister_listener(id)?;
+        Ok(())
+    }
+
+    async fn start_notify(&self, id: ListenerId, scope: Scope) -> RpcResult<()> {
+        self.notifier.try_start_notify(id, scope)?;
+        Ok(())
+    }
+
+    async fn stop_notify(&self, id: ListenerId, scope: Scope) -> RpcResult<()> {
+        self.notifier.try_stop_notify(id, scope)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::NodeConnector;
+    use futures::FutureExt;
+    use kaspa_notify::{
+        connection::{ChannelType, Connection},
+        scope::NewBlockTemplateScope,
+    };
+    use kaspa_rpc_core::{RpcNetworkId, RpcNetworkType, api::connection::DynRpcConnection};
+    use std::{
+        collections::HashMap,
+        sync::atomic::{AtomicBool, AtomicU64, Ordering},
+        time::Duration,
+    };
+
+    // Expanded after `async_trait`, hence the desugared signature
+    macro_rules! not_implemented {
+        ($fn:ident, $name:tt) => {
+            paste::paste! {
+                #[allow(clippy::type_complexity)]
+                fn $fn<'life0, 'life1, 'async_trait>(
+                    &'life0 self,
+                    _: Option<&'life1 DynRpcConnection>,
+                    _: [<$name Request>],
+                ) -> ::core::pin::Pin<Box<dyn ::core::future::Future<Output = RpcResult<[<$name Response>]>> + Send + 'async_trait>>
+                where
+                    'life0: 'async_trait,
+                    'life1: 'async_trait,
+                    Self: 'async_trait,
+                {
+                    Box::pin(async move { Err(RpcError::NotImplemented) })
+                }
+            }
+        };
+    }
+
+    /// Node reporting a configurable health and recording the subscriptions of its listeners
+    #[derive(Default)]
+    struct NodeMock {
+        is_down: AtomicBool,
+        virtual_daa_score: AtomicU64,
+        next_listener_id: AtomicU64,
+        listeners: Mutex<HashMap<ListenerId, ChannelConnection>>,
+        subscriptions: Mutex<Vec<(ListenerId, Scope)>>,
+    }
+
+    impl NodeMock {
+        fn new(virtual_daa_score: u64) -> Arc<Self> {
+            Arc::new(Self { virtual_daa_score: AtomicU64::new(virtual_daa_score), ..Default::default() })
+        }
+
+        fn subscribed_listener(&self, scope: &Scope) -> Option<ListenerId> {
+            self.subscriptions.lock().unwrap().iter().find(|(_, subscribed)| subscribed == scope).map(|(id, _)| *id)
+        }
+
+        async fn notify(&self, id: ListenerId, notification: Notification) {
+            let connection = self.listeners.lock().unwrap().get(&id).cloned().unwrap();
+            connection.send(notification).await.unwrap();
+        }
+    }
+
+    #[async_trait]
+    impl RpcApi for NodeMock {
+        async fn get_server_info_call(
+            &self,
+            _: Option<&DynRpcConnection>,
+            _: GetServerInfoRequest,
+        ) -> RpcResult<GetServerInfoResponse> {
+            if self.is_down.load(Ordering::SeqCst) {
+                return Err(RpcError::General("node is down".to_string()));
+            }
+            Ok(GetServerInfoResponse {
+                rpc_api_version: 1,
+                rpc_api_revision: 0,
+                server_version: "mock".to_string(),
+                network_id: RpcNetworkId::new(RpcNetworkType::Mainnet),
+                has_utxo_index: false,
+                is_synced: true,
+                virtual_daa_score: self.virtual_daa_score.load(Ordering::SeqCst),
+            })
+        }
+
+        not_implemented!(ping_call, Ping);
+        not_implemented!(get_sync_status_call, GetSyncStatus);
+        not_implemented!(get_metrics_call, GetMetrics);
+        not_implemented!(get_connections_call, GetConnections);
+        not_implemented!(get_system_info_call, GetSystemInfo);
+        not_implemented!(submit_block_call, SubmitBlock);
+        not_implemented!(get_block_template_call, GetBlockTemplate);
+        not_implemented!(get_block_call, GetBlock);
+        not_implemented!(get_info_call, GetInfo);
+        not_implemented!(get_current_network_call, GetCurrentNetwork);
+        not_implemented!(get_peer_addresses_call, GetPeerAddresses);
+        not_implemented!(get_sink_call, GetSink);
+        not_implemented!(get_mempool_entry_call, GetMempoolEntry);
+        not_implemented!(get_mempool_entries_call, GetMempoolEntries);
+        not_implemented!(get_connected_peer_info_call, GetConnectedPeerInfo);
+        not_implemented!(add_peer_call, AddPeer);
+        not_implemented!(submit_transaction_call, SubmitTransaction);
+        not_implemented!(submit_transaction_replacement_call, SubmitTransactionReplacement);
+        not_implemented!(get_subnetwork_call, GetSubnetwork);
+        not_implemented!(get_virtual_chain_from_block_call, GetVirtualChainFromBlock);
+        not_implemented!(get_blocks_call, GetBlocks);
+        not_implemented!(get_block_count_call, GetBlockCount);
+        not_implemented!(get_block_dag_info_call, GetBlockDagInfo);
+        not_implemented!(resolve_finality_conflict_call, ResolveFinalityConflict);
+        not_implemented!(shutdown_call, Shutdown);
+        not_implemented!(get_headers_call, GetHeaders);
+        not_implemented!(get_utxos_by_addresses_call, GetUtxosByAddresses);
+        not_implemented!(get_balance_by_address_call, GetBalanceByAddress);
+        not_implemented!(get_balances_by_addresses_call, GetBalancesByAddresses);
+        not_implemented!(get_sink_blue_score_call, GetSinkBlueScore);
+        not_implemented!(ban_call, Ban);
+        not_implemented!(unban_call, Unban);
+        not_implemented!(estimate_network_hashes_per_second_call, EstimateNetworkHashesPerSecond);
+        not_implemented!(get_mempool_entries_by_addresses_call, GetMempoolEntriesByAddresses);
+        not_implemented!(get_coin_supply_call, GetCoinSupply);
+        not_implemented!(get_daa_score_timestamp_estimate_call, GetDaaScoreTimestampEstimate);
+        not_implemented!(get_fee_estimate_call, GetFeeEstimate);
+        not_implemented!(get_fee_estimate_experimental_call, GetFeeEstimateExperimental);
+        not_implemented!(get_current_block_color_call, GetCurrentBlockColor);
+        not_implemented!(get_block_reward_info_call, GetBlockRewardInfo);
+        not_implemented!(get_utxo_return_address_call, GetUtxoReturnAddress);
+        not_implemented!(get_virtual_chain_from_block_v2_call, GetVirtualChainFromBlockV2);
+        not_implemented!(get_seq_commit_lane_proof_call, GetSeqCommitLaneProof);
+        not_implemented!(get_accepted_transactions_call, GetAcceptedTransactions);
+        not_implemented!(generate_blocks_call, GenerateBlocks);
+        not_implemented!(get_address_history_call, GetAddressHistory);
+        not_implemented!(get_balance_at_call, GetBalanceAt);
+        not_implemented!(get_sync_progress_call, GetSyncProgress);
+        not_implemented!(get_block_template_diagnostics_call, GetBlockTemplateDiagnostics);
+
+        fn register_new_listener(&self, connection: ChannelConnection) -> ListenerId {
+            let id = self.next_listener_id.fetch_add(1, Ordering::SeqCst);
+            self.listeners.lock().unwrap().insert(id, connection);
+            id
+        }
+
+        async fn unregister_listener(&self, id: ListenerId) -> RpcResult<()> {
+            self.listeners.lock().unwrap().remove(&id);
+            self.subscriptions.lock().unwrap().retain(|(listener_id, _)| *listener_id != id);
+            Ok(())
+        }
+
+        async fn start_notify(&self, id: ListenerId, scope: Scope) -> RpcResult<()> {
+            self.subscriptions.lock().unwrap().push((id, scope));
+            Ok(())
+        }
+
+        async fn stop_notify(&self, id: ListenerId, scope: Scope) -> RpcResult<()> {
+            self.subscriptions.lock().unwrap().retain(|subscription| *subscription != (id, scope.clone()));
+            Ok(())
+        }
+    }
+
+    /// Waits for the subscription to reach the node, subscriptions being forwarded asynchronously by the notifier
+    async fn wait_for_subscription(node: &NodeMock, scope: &Scope) -> ListenerId {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(id) = node.subscribed_listener(scope) {
+                    return id;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("the subscription should be forwarded to the active node")
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_follow_node_switch() {
+        let primary = NodeMock::new(1_000);
+        let backup = NodeMock::new(995);
+        let options =
+            FailoverOptions { health_check_interval: Duration::from_secs(3600), max_daa_score_lag: 10, ..Default::default() };
+        let client = FailoverRpcClient::new(
+            vec![FailoverNode::new("primary", primary.clone()), FailoverNode::new("backup", backup.clone())],
+            options,
+            None,
+        );
+        client.start().await;
+        assert_eq!(client.active_node().map(|node| node.name()), Some("primary"));
+
+        let (sender, receiver) = async_channel::unbounded();
+        let listener_id = client.register_new_listener(ChannelConnection::new("test", sender, ChannelType::Closable));
+        let scope: Scope = NewBlockTemplateScope {}.into();
+        client.start_notify(listener_id, scope.clone()).await.unwrap();
+        let primary_listener = wait_for_subscription(&primary, &scope).await;
+        assert!(backup.subscribed_listener(&scope).is_none(), "only the active node gets subscribed");
+
+        primary.notify(primary_listener, Notification::NewBlockTemplate(NewBlockTemplateNotification {})).await;
+        let notification = tokio::time::timeout(Duration::from_secs(5), receiver.recv()).await.unwrap().unwrap();
+        assert!(matches!(notification, Notification::NewBlockTemplate(_)));
+
+        // The primary goes down: calls and subscriptions move to the backup without the listener doing anything
+        primary.is_down.store(true, Ordering::SeqCst);
+        client.check_health().await;
+        assert_eq!(client.active_node().map(|node| node.name()), Some("backup"));
+        assert!(primary.listeners.lock().unwrap().is_empty(), "the listener on the previous node is dropped");
+
+        let backup_listener = wait_for_subscription(&backup, &scope).await;
+        backup.notify(backup_listener, Notification::NewBlockTemplate(NewBlockTemplateNotification {})).await;
+        let notification = tokio::time::timeout(Duration::from_secs(5), receiver.recv()).await.unwrap().unwrap();
+        assert!(matches!(notification, Notification::NewBlockTemplate(_)));
+
+        // Switching back once the primary recovers and outpaces the backup
+        primary.is_down.store(false, Ordering::SeqCst);
+        primary.virtual_daa_score.store(1_100, Ordering::SeqCst);
+        client.check_health().await;
+        assert_eq!(client.active_node().map(|node| node.name()), Some("primary"));
+        wait_for_subscription(&primary, &scope).await;
+
+        client.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_node_unreachable_at_startup_joins_later() {
+        let late = NodeMock::new(1_100);
+        let is_up = Arc::new(AtomicBool::new(false));
+        let connector: NodeConnector = {
+            let (late, is_up) = (late.clone(), is_up.clone());
+            Arc::new(move || {
+                let (late, is_up) = (late.clone(), is_up.clone());
+                async move {
+                    if is_up.load(Ordering::SeqCst) {
+                        Ok(late as Arc<dyn RpcApi>)
+                    } else {
+                        Err(RpcError::General("connection refused".to_string()))
+                    }
+                }
+                .boxed()
+            })
+        };
+        let options =
+            FailoverOptions { health_check_interval: Duration::from_secs(3600), max_daa_score_lag: 10, ..Default::default() };
+        let client = FailoverRpcClient::new(
+            vec![FailoverNode::with_connector("late", connector), FailoverNode::new("backup", NodeMock::new(1_000))],
+            options,
+            None,
+        );
+        client.start().await;
+        assert_eq!(client.active_node().map(|node| node.name()), Some("backup"));
+        assert!(client.nodes()[0].client().is_none());
+        assert!(!client.nodes()[0].health().is_reachable);
+
+        // The node gets connected by the next health check once it is up, and is then selected
+        is_up.store(true, Ordering::SeqCst);
+        client.check_health().await;
+        assert!(client.nodes()[0].client().is_some());
+        assert_eq!(client.active_node().map(|node| node.name()), Some("late"));
+
+        client.stop().await.unwrap();
+    }
+}
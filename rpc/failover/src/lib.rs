@@ -0,0 +1,12 @@
+//!
+//! RPC client routing calls to the healthiest of several nodes, see [`FailoverRpcClient`].
+//!
+
+#[macro_use]
+mod route;
+
+pub mod client;
+pub mod node;
+
+pub use client::FailoverRpcClient;
+pub use node::{FailoverNode, FailoverOptions, NodeConnector, NodeHealth};
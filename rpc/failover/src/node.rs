@@ -0,0 +1,252 @@
+use futures::future::BoxFuture;
+use kaspa_notify::{connection::ChannelType, listener::ListenerId};
+use kaspa_rpc_core::{
+    Notification, RpcNetworkId,
+    api::rpc::{DynRpcService, RpcApi},
+    error::RpcResult,
+    notify::connection::ChannelConnection,
+};
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+const FAILOVER_NODE: &str = "failover-node";
+
+/// Snapshot of the state of a node as observed by the last health check
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NodeHealth {
+    pub is_reachable: bool,
+    pub is_synced: bool,
+    pub has_utxo_index: bool,
+    pub network_id: Option<RpcNetworkId>,
+    pub virtual_daa_score: u64,
+    /// Round trip time of the health check request
+    pub latency: Option<Duration>,
+    pub last_error: Option<String>,
+}
+
+impl NodeHealth {
+    fn unreachable(error: String) -> Self {
+        Self { last_error: Some(error), ..Default::default() }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FailoverOptions {
+    pub health_check_interval: Duration,
+    pub health_check_timeout: Duration,
+    /// DAA score distance the active node may fall behind the most up-to-date
+    /// node before calls get rerouted, preventing back and forth switching
+    /// between nodes that are equally synced
+    pub max_daa_score_lag: u64,
+    /// Nodes of any other network are never selected
+    pub network_id: Option<RpcNetworkId>,
+    pub require_utxo_index: bool,
+    /// Route to the most up-to-date reachable node when none is synced
+    pub allow_unsynced: bool,
+}
+
+impl Default for FailoverOptions {
+    fn default() -> Self {
+        Self {
+            health_check_interval: Duration::from_secs(5),
+            health_check_timeout: Duration::from_secs(3),
+            max_daa_score_lag: 100,
+            network_id: None,
+            require_utxo_index: false,
+            allow_unsynced: true,
+        }
+    }
+}
+
+/// Opens the connection to a node, see [`FailoverNode::with_connector`]
+pub type NodeConnector = Arc<dyn Fn() -> BoxFuture<'static, RpcResult<DynRpcService>> + Send + Sync>;
+
+/// A node the failover client can route calls to
+pub struct FailoverNode {
+    name: String,
+    client: Mutex<Option<DynRpcService>>,
+    connector: Option<NodeConnector>,
+    health: Mutex<NodeHealth>,
+    /// Listener registered on the node while it is the active one
+    listener_id: Mutex<Option<ListenerId>>,
+}
+
+impl FailoverNode {
+    /// `client` must be connected and, when notifications are used, operate in multi-listeners mode
+    pub fn new(name: impl Into<String>, client: DynRpcService) -> Self {
+        Self {
+            name: name.into(),
+            client: Mutex::new(Some(client)),
+            connector: None,
+            health: Default::default(),
+            listener_id: Default::default(),
+        }
+    }
+
+    /// Node connected by `connector` on the first health check that reaches it, so a
+    /// node unreachable at startup joins the failover as soon as it comes up.
+    /// Once connected, the client is expected to reconnect by itself.
+    pub fn with_connector(name: impl Into<String>, connector: NodeConnector) -> Self {
+        Self {
+            name: name.into(),
+            client: Default::default(),
+            connector: Some(connector),
+            health: Default::default(),
+            listener_id: Default::default(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The node client, `None` until a node built [with a connector](Self::with_connector) was first reached
+    pub fn client(&self) -> Option<DynRpcService> {
+        self.client.lock().unwrap().clone()
+    }
+
+    pub fn health(&self) -> NodeHealth {
+        self.health.lock().unwrap().clone()
+    }
+
+    pub(crate) async fn check_health(&self, timeout: Duration) -> NodeHealth {
+        let client = match self.client() {
+            Some(client) => Ok(client),
+            None => self.connect(timeout).await,
+        };
+        let client = match client {
+            Ok(client) => client,
+            Err(err) => {
+                let health = NodeHealth::unreachable(err);
+                *self.health.lock().unwrap() = health.clone();
+                return health;
+            }
+        };
+
+        let start = Instant::now();
+        let health = match tokio::time::timeout(timeout, client.get_server_info()).await {
+            Ok(Ok(info)) => NodeHealth {
+                is_reachable: true,
+                is_synced: info.is_synced,
+                has_utxo_index: info.has_utxo_index,
+                network_id: Some(info.network_id),
+                virtual_daa_score: info.virtual_daa_score,
+                latency: Some(start.elapsed()),
+                last_error: None,
+            },
+            Ok(Err(err)) => NodeHealth::unreachable(err.to_string()),
+            Err(_) => NodeHealth::unreachable(format!("no response within {timeout:?}")),
+        };
+        *self.health.lock().unwrap() = health.clone();
+        health
+    }
+
+    async fn connect(&self, timeout: Duration) -> Result<DynRpcService, String> {
+        let connector = self.connector.as_ref().expect("a node without a client has a connector");
+        match tokio::time::timeout(timeout, connector()).await {
+            Ok(Ok(client)) => {
+                *self.client.lock().unwrap() = Some(client.clone());
+                Ok(client)
+            }
+            Ok(Err(err)) => Err(err.to_string()),
+            Err(_) => Err(format!("no connection within {timeout:?}")),
+        }
+    }
+
+    /// Forwards all notifications subscribed on the node to `sender`
+    pub(crate) fn attach(&self, sender: async_channel::Sender<Notification>) {
+        // Only reached nodes get selected, so the client exists
+        let Some(client) = self.client() else {
+            return;
+        };
+        let connection = ChannelConnection::new(FAILOVER_NODE, sender, ChannelType::Persistent);
+        let id = client.register_new_listener(connection);
+        *self.listener_id.lock().unwrap() = Some(id);
+    }
+
+    pub(crate) async fn detach(&self) {
+        let id = self.listener_id.lock().unwrap().take();
+        if let (Some(id), Some(client)) = (id, self.client()) {
+            // The node is most likely unreachable already, so errors are expected here
+            let _ = client.unregister_listener(id).await;
+        }
+    }
+
+    pub(crate) fn listener_id(&self) -> Option<ListenerId> {
+        *self.listener_id.lock().unwrap()
+    }
+}
+
+/// Picks the node calls should be routed to.
+///
+/// Synced nodes are preferred, then the highest virtual DAA score, then the
+/// lowest index so the order of the nodes acts as a priority. The current node
+/// is kept as long as it is eligible and within `max_daa_score_lag` of the best one.
+pub(crate) fn select_node(health: &[NodeHealth], current: Option<usize>, options: &FailoverOptions) -> Option<usize> {
+    let eligible = |h: &NodeHealth| {
+        h.is_reachable
+            && (options.network_id.is_none() || h.network_id == options.network_id)
+            && (!options.require_utxo_index || h.has_utxo_index)
+    };
+    let any_synced = health.iter().any(|h| eligible(h) && h.is_synced);
+    let candidate = |h: &NodeHealth| eligible(h) && (h.is_synced || (!any_synced && options.allow_unsynced));
+
+    let mut best: Option<usize> = None;
+    for (index, h) in health.iter().enumerate() {
+        if candidate(h) && best.is_none_or(|best| h.virtual_daa_score > health[best].virtual_daa_score) {
+            best = Some(index);
+        }
+    }
+
+    match (current, best) {
+        (Some(current), Some(best))
+            if candidate(&health[current])
+                && health[best].virtual_daa_score.saturating_sub(health[current].virtual_daa_score) <= options.max_daa_score_lag =>
+        {
+            Some(current)
+        }
+        _ => best,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(is_synced: bool, virtual_daa_score: u64) -> NodeHealth {
+        NodeHealth { is_reachable: true, is_synced, virtual_daa_score, ..Default::default() }
+    }
+
+    #[test]
+    fn test_select_node() {
+        let options = FailoverOptions { max_daa_score_lag: 10, ..Default::default() };
+
+        // The most up-to-date synced node wins, ties resolve to the first one
+        let health = vec![node(true, 100), node(false, 500), node(true, 120), node(true, 120)];
+        assert_eq!(select_node(&health, None, &options), Some(2));
+
+        // The current node is kept while within the allowed lag
+        let health = vec![node(true, 112), node(true, 120)];
+        assert_eq!(select_node(&health, Some(0), &options), Some(0));
+        let health = vec![node(true, 109), node(true, 120)];
+        assert_eq!(select_node(&health, Some(0), &options), Some(1));
+
+        // A current node which lost sync is dropped even if close to the best
+        let health = vec![node(false, 120), node(true, 119)];
+        assert_eq!(select_node(&health, Some(0), &options), Some(1));
+
+        // Unsynced nodes only serve when no synced node is left and it is allowed
+        let health = vec![node(false, 90), node(false, 95), NodeHealth::unreachable("down".to_string())];
+        assert_eq!(select_node(&health, None, &options), Some(1));
+        assert_eq!(select_node(&health, None, &FailoverOptions { allow_unsynced: false, ..options.clone() }), None);
+
+        // Unreachable and mismatching nodes are never selected
+        let mut with_index = node(true, 80);
+        with_index.has_utxo_index = true;
+        let health = vec![NodeHealth::unreachable("down".to_string()), node(true, 100), with_index];
+        assert_eq!(select_node(&health, None, &FailoverOptions { require_utxo_index: true, ..options.clone() }), Some(2));
+        assert_eq!(select_node(&[], None, &options), None);
+    }
+}
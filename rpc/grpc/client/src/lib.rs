@@ -280,6 +280,12 @@ impl RpcApi for GrpcClient {
     route!(get_utxo_return_address_call, GetUtxoReturnAddress);
     route!(get_virtual_chain_from_block_v2_call, GetVirtualChainFromBlockV2);
     route!(get_seq_commit_lane_proof_call, GetSeqCommitLaneProof);
+    route!(get_accepted_transactions_call, GetAcceptedTransactions);
+    route!(generate_blocks_call, GenerateBlocks);
+    route!(get_address_history_call, GetAddressHistory);
+    route!(get_balance_at_call, GetBalanceAt);
+    route!(get_sync_progress_call, GetSyncProgress);
+    route!(get_block_template_diagnostics_call, GetBlockTemplateDiagnostics);
 
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
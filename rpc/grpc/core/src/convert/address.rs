@@ -1,6 +1,7 @@
 use crate::protowire;
 use crate::{from, try_from};
-use kaspa_rpc_core::RpcError;
+use kaspa_rpc_core::{RpcError, RpcHash};
+use std::str::FromStr;
 
 // ----------------------------------------------------------------------------
 // rpc_core to protowire
@@ -10,6 +11,17 @@ from!(item: &kaspa_rpc_core::RpcBalancesByAddressesEntry, protowire::RpcBalances
     Self { address: (&item.address).into(), balance: item.balance.unwrap_or_default(), error: None }
 });
 
+from!(item: &kaspa_rpc_core::RpcAddressHistoryEntry, protowire::RpcAddressHistoryEntry, {
+    Self {
+        outpoint: Some((&item.outpoint).into()),
+        transaction_id: item.transaction_id.to_string(),
+        kind: item.kind.into(),
+        amount: item.amount,
+        daa_score: item.daa_score,
+        is_coinbase: item.is_coinbase,
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -18,3 +30,18 @@ try_from!(item: &protowire::RpcBalancesByAddressesEntry, kaspa_rpc_core::RpcBala
     let balance = if item.error.is_some() { None } else { Some(item.balance) };
     Self { address: item.address.as_str().try_into()?, balance }
 });
+
+try_from!(item: &protowire::RpcAddressHistoryEntry, kaspa_rpc_core::RpcAddressHistoryEntry, {
+    Self {
+        outpoint: item
+            .outpoint
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("RpcAddressHistoryEntry".to_string(), "outpoint".to_string()))?
+            .try_into()?,
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        kind: item.kind.into(),
+        amount: item.amount,
+        daa_score: item.daa_score,
+        is_coinbase: item.is_coinbase,
+    }
+});
@@ -0,0 +1,61 @@
+use std::str::FromStr;
+
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::{RpcError, RpcHash, RpcSubnetworkId};
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &kaspa_rpc_core::RpcBlockMasses, protowire::RpcBlockMasses, {
+    Self { compute: item.compute, transient: item.transient, storage: item.storage }
+});
+
+from!(item: &kaspa_rpc_core::RpcTemplateCandidate, protowire::RpcTemplateCandidate, {
+    Self {
+        transaction_id: item.transaction_id.to_string(),
+        outcome: item.outcome.into(),
+        validation_error: item.validation_error.clone(),
+        fee: item.fee,
+        mass: item.mass,
+        masses: Some((&item.masses).into()),
+        binding_dimension: item.binding_dimension.into(),
+        subnetwork_id: item.subnetwork_id.to_string(),
+        gas: item.gas,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcTemplateLaneUsage, protowire::RpcTemplateLaneUsage, {
+    Self { subnetwork_id: item.subnetwork_id.to_string(), transaction_count: item.transaction_count, gas: item.gas }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+from!(item: &protowire::RpcBlockMasses, kaspa_rpc_core::RpcBlockMasses, {
+    Self { compute: item.compute, transient: item.transient, storage: item.storage }
+});
+
+try_from!(item: &protowire::RpcTemplateCandidate, kaspa_rpc_core::RpcTemplateCandidate, {
+    Self {
+        transaction_id: RpcHash::from_str(&item.transaction_id)?,
+        outcome: item.outcome.into(),
+        validation_error: item.validation_error.clone(),
+        fee: item.fee,
+        mass: item.mass,
+        masses: item
+            .masses
+            .as_ref()
+            .ok_or_else(|| RpcError::MissingRpcFieldError("RpcTemplateCandidate".to_string(), "masses".to_string()))?
+            .into(),
+        binding_dimension: item.binding_dimension.into(),
+        subnetwork_id: RpcSubnetworkId::from_str(&item.subnetwork_id)?,
+        gas: item.gas,
+    }
+});
+
+try_from!(item: &protowire::RpcTemplateLaneUsage, kaspa_rpc_core::RpcTemplateLaneUsage, {
+    Self { subnetwork_id: RpcSubnetworkId::from_str(&item.subnetwork_id)?, transaction_count: item.transaction_count, gas: item.gas }
+});
@@ -32,6 +32,23 @@ from!(item: &kaspa_rpc_core::RpcFeeEstimateVerboseExperimentalData, protowire::R
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcFeerateTarget, protowire::RpcFeerateTarget, {
+    Self {
+        target_seconds: item.target_seconds,
+        confidence: item.confidence,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcFeerateTargetEstimate, protowire::RpcFeerateTargetEstimate, {
+    Self {
+        target_seconds: item.target_seconds,
+        confidence: item.confidence,
+        feerate: item.feerate,
+        from_history: item.from_history,
+        sample_count: item.sample_count,
+    }
+});
+
 // ----------------------------------------------------------------------------
 // protowire to rpc_core
 // ----------------------------------------------------------------------------
@@ -64,3 +81,20 @@ try_from!(item: &protowire::RpcFeeEstimateVerboseExperimentalData, kaspa_rpc_cor
         next_block_template_feerate_max: item.next_block_template_feerate_max,
     }
 });
+
+try_from!(item: &protowire::RpcFeerateTarget, kaspa_rpc_core::RpcFeerateTarget, {
+    Self {
+        target_seconds: item.target_seconds,
+        confidence: item.confidence,
+    }
+});
+
+try_from!(item: &protowire::RpcFeerateTargetEstimate, kaspa_rpc_core::RpcFeerateTargetEstimate, {
+    Self {
+        target_seconds: item.target_seconds,
+        confidence: item.confidence,
+        feerate: item.feerate,
+        from_history: item.from_history,
+        sample_count: item.sample_count,
+    }
+});
@@ -67,6 +67,12 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(GetVirtualChainFromBlockV2);
     impl_into_kaspad_request!(GetBlockRewardInfo);
     impl_into_kaspad_request!(GetSeqCommitLaneProof);
+    impl_into_kaspad_request!(GetAcceptedTransactions);
+    impl_into_kaspad_request!(GenerateBlocks);
+    impl_into_kaspad_request!(GetAddressHistory);
+    impl_into_kaspad_request!(GetBalanceAt);
+    impl_into_kaspad_request!(GetSyncProgress);
+    impl_into_kaspad_request!(GetBlockTemplateDiagnostics);
 
     impl_into_kaspad_request!(NotifyBlockAdded);
     impl_into_kaspad_request!(NotifyNewBlockTemplate);
@@ -75,6 +81,7 @@ pub mod kaspad_request_convert {
     impl_into_kaspad_request!(NotifyFinalityConflict);
     impl_into_kaspad_request!(NotifyVirtualDaaScoreChanged);
     impl_into_kaspad_request!(NotifyVirtualChainChanged);
+    impl_into_kaspad_request!(NotifySyncProgressChanged);
     impl_into_kaspad_request!(NotifySinkBlueScoreChanged);
 
     macro_rules! impl_into_kaspad_request {
@@ -208,6 +215,12 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_response!(GetVirtualChainFromBlockV2);
     impl_into_kaspad_response!(GetBlockRewardInfo);
     impl_into_kaspad_response!(GetSeqCommitLaneProof);
+    impl_into_kaspad_response!(GetAcceptedTransactions);
+    impl_into_kaspad_response!(GenerateBlocks);
+    impl_into_kaspad_response!(GetAddressHistory);
+    impl_into_kaspad_response!(GetBalanceAt);
+    impl_into_kaspad_response!(GetSyncProgress);
+    impl_into_kaspad_response!(GetBlockTemplateDiagnostics);
 
     impl_into_kaspad_notify_response!(NotifyBlockAdded);
     impl_into_kaspad_notify_response!(NotifyNewBlockTemplate);
@@ -216,6 +229,7 @@ pub mod kaspad_response_convert {
     impl_into_kaspad_notify_response!(NotifyFinalityConflict);
     impl_into_kaspad_notify_response!(NotifyVirtualDaaScoreChanged);
     impl_into_kaspad_notify_response!(NotifyVirtualChainChanged);
+    impl_into_kaspad_notify_response!(NotifySyncProgressChanged);
     impl_into_kaspad_notify_response!(NotifySinkBlueScoreChanged);
 
     impl_into_kaspad_notify_response!(NotifyUtxosChanged, StopNotifyingUtxosChanged);
@@ -157,6 +157,9 @@ from!(item: &kaspa_rpc_core::GetBlockTemplateRequest, protowire::GetBlockTemplat
     Self {
         pay_address: (&item.pay_address).into(),
         extra_data: String::from_utf8(item.extra_data.clone()).expect("extra data has to be valid UTF-8"),
+        priority_transaction_ids: item.priority_transaction_ids.iter().map(|x| x.to_string()).collect(),
+        excluded_transaction_ids: item.excluded_transaction_ids.iter().map(|x| x.to_string()).collect(),
+        reserved_mass: item.reserved_mass,
     }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetBlockTemplateResponse>, protowire::GetBlockTemplateResponseMessage, {
@@ -409,13 +412,15 @@ from!(item: RpcResult<&kaspa_rpc_core::GetFeeEstimateResponse>, protowire::GetFe
 });
 from!(item: &kaspa_rpc_core::GetFeeEstimateExperimentalRequest, protowire::GetFeeEstimateExperimentalRequestMessage, {
     Self {
-        verbose: item.verbose
+        verbose: item.verbose,
+        targets: item.targets.iter().map(|x| x.into()).collect(),
     }
 });
 from!(item: RpcResult<&kaspa_rpc_core::GetFeeEstimateExperimentalResponse>, protowire::GetFeeEstimateExperimentalResponseMessage, {
     Self {
         estimate: Some((&item.estimate).into()),
         verbose: item.verbose.as_ref().map(|x| x.into()),
+        target_estimates: item.target_estimates.iter().map(|x| x.into()).collect(),
         error: None
     }
 });
@@ -549,6 +554,88 @@ from!(item: RpcResult<&kaspa_rpc_core::GetVirtualChainFromBlockV2Response>, prot
     }
 });
 
+from!(item: &kaspa_rpc_core::GetAcceptedTransactionsRequest, protowire::GetAcceptedTransactionsRequestMessage, {
+    Self {
+        accepting_block_hash: item.accepting_block_hash.map(|x| x.to_string()),
+        start_daa_score: item.start_daa_score,
+        end_daa_score: item.end_daa_score,
+        skip: item.skip,
+        limit: item.limit,
+        data_verbosity_level: item.data_verbosity_level.map(|v| v as i32),
+    }
+});
+
+from!(item: RpcResult<&kaspa_rpc_core::GetAcceptedTransactionsResponse>, protowire::GetAcceptedTransactionsResponseMessage, {
+    Self {
+        accepting_blocks: item.accepting_blocks.iter().map(|x| x.into()).collect(),
+        next_daa_score: item.next_daa_score,
+        next_skip: item.next_skip,
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::GenerateBlocksRequest, protowire::GenerateBlocksRequestMessage, {
+    Self { count: item.count, pay_address: (&item.pay_address).into() }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GenerateBlocksResponse>, protowire::GenerateBlocksResponseMessage, {
+    Self { block_hashes: item.block_hashes.iter().map(|x| x.to_string()).collect(), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetAddressHistoryRequest, protowire::GetAddressHistoryRequestMessage, {
+    Self {
+        address: (&item.address).into(),
+        start_daa_score: item.start_daa_score,
+        cursor: item.cursor.clone().unwrap_or_default(),
+        limit: item.limit,
+    }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetAddressHistoryResponse>, protowire::GetAddressHistoryResponseMessage, {
+    Self {
+        entries: item.entries.iter().map(|x| x.into()).collect(),
+        next_cursor: item.next_cursor.clone().unwrap_or_default(),
+        error: None,
+    }
+});
+
+from!(item: &kaspa_rpc_core::GetBalanceAtRequest, protowire::GetBalanceAtRequestMessage, {
+    Self { addresses: item.addresses.iter().map(|x| x.into()).collect(), daa_score: item.daa_score }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetBalanceAtResponse>, protowire::GetBalanceAtResponseMessage, {
+    Self { entries: item.entries.iter().map(|x| x.into()).collect(), error: None }
+});
+
+from!(&kaspa_rpc_core::GetSyncProgressRequest, protowire::GetSyncProgressRequestMessage);
+from!(item: RpcResult<&kaspa_rpc_core::GetSyncProgressResponse>, protowire::GetSyncProgressResponseMessage, {
+    Self { is_synced: item.is_synced, progress: item.progress.as_ref().map(|x| x.into()), error: None }
+});
+
+from!(item: &kaspa_rpc_core::GetBlockTemplateDiagnosticsRequest, protowire::GetBlockTemplateDiagnosticsRequestMessage, {
+    Self {
+        pay_address: (&item.pay_address).into(),
+        extra_data: String::from_utf8(item.extra_data.clone()).expect("extra data has to be valid UTF-8"),
+        priority_transaction_ids: item.priority_transaction_ids.iter().map(|x| x.to_string()).collect(),
+        excluded_transaction_ids: item.excluded_transaction_ids.iter().map(|x| x.to_string()).collect(),
+        reserved_mass: item.reserved_mass,
+    }
+});
+from!(item: RpcResult<&kaspa_rpc_core::GetBlockTemplateDiagnosticsResponse>, protowire::GetBlockTemplateDiagnosticsResponseMessage, {
+    Self {
+        selector_kind: item.selector_kind.into(),
+        ready_transaction_count: item.ready_transaction_count,
+        selected_transaction_count: item.selected_transaction_count,
+        total_fees: item.total_fees,
+        mass_used: Some((&item.mass_used).into()),
+        mass_limits: Some((&item.mass_limits).into()),
+        normalized_mass_used: item.normalized_mass_used,
+        normalized_mass_limit: item.normalized_mass_limit,
+        lanes: item.lanes.iter().map(|x| x.into()).collect(),
+        lanes_per_block_limit: item.lanes_per_block_limit,
+        gas_per_lane_limit: item.gas_per_lane_limit,
+        candidates: item.candidates.iter().map(|x| x.into()).collect(),
+        error: None,
+    }
+});
+
 from!(item: &kaspa_rpc_core::NotifyUtxosChangedRequest, protowire::NotifyUtxosChangedRequestMessage, {
     Self { addresses: item.addresses.iter().map(|x| x.into()).collect(), command: item.command.into() }
 });
@@ -581,6 +668,11 @@ from!(item: &kaspa_rpc_core::NotifyVirtualDaaScoreChangedRequest, protowire::Not
 });
 from!(RpcResult<&kaspa_rpc_core::NotifyVirtualDaaScoreChangedResponse>, protowire::NotifyVirtualDaaScoreChangedResponseMessage);
 
+from!(item: &kaspa_rpc_core::NotifySyncProgressChangedRequest, protowire::NotifySyncProgressChangedRequestMessage, {
+    Self { command: item.command.into() }
+});
+from!(RpcResult<&kaspa_rpc_core::NotifySyncProgressChangedResponse>, protowire::NotifySyncProgressChangedResponseMessage);
+
 from!(item: &kaspa_rpc_core::NotifyVirtualChainChangedRequest, protowire::NotifyVirtualChainChangedRequestMessage, {
     Self { include_accepted_transaction_ids: item.include_accepted_transaction_ids, command: item.command.into() }
 });
@@ -655,7 +747,13 @@ impl TryFrom<&protowire::SubmitBlockResponseMessage> for kaspa_rpc_core::SubmitB
 }
 
 try_from!(item: &protowire::GetBlockTemplateRequestMessage, kaspa_rpc_core::GetBlockTemplateRequest, {
-    Self { pay_address: item.pay_address.clone().try_into()?, extra_data: RpcExtraData::from_iter(item.extra_data.bytes()) }
+    Self {
+        pay_address: item.pay_address.clone().try_into()?,
+        extra_data: RpcExtraData::from_iter(item.extra_data.bytes()),
+        priority_transaction_ids: item.priority_transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        excluded_transaction_ids: item.excluded_transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        reserved_mass: item.reserved_mass,
+    }
 });
 try_from!(item: &protowire::GetBlockTemplateResponseMessage, RpcResult<kaspa_rpc_core::GetBlockTemplateResponse>, {
     Self {
@@ -832,6 +930,93 @@ try_from!(item: &protowire::GetVirtualChainFromBlockV2ResponseMessage, RpcResult
     }
 });
 
+try_from!(item: &protowire::GetAcceptedTransactionsRequestMessage, kaspa_rpc_core::GetAcceptedTransactionsRequest, {
+    Self {
+        accepting_block_hash: item.accepting_block_hash.as_deref().map(RpcHash::from_str).transpose()?,
+        start_daa_score: item.start_daa_score,
+        end_daa_score: item.end_daa_score,
+        skip: item.skip,
+        limit: item.limit,
+        data_verbosity_level: item.data_verbosity_level.map(RpcDataVerbosityLevel::try_from).transpose()?,
+    }
+});
+try_from!(item: &protowire::GetAcceptedTransactionsResponseMessage, RpcResult<kaspa_rpc_core::GetAcceptedTransactionsResponse>, {
+    Self {
+        accepting_blocks: item.accepting_blocks.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        next_daa_score: item.next_daa_score,
+        next_skip: item.next_skip,
+    }
+});
+
+try_from!(item: &protowire::GenerateBlocksRequestMessage, kaspa_rpc_core::GenerateBlocksRequest, {
+    Self { count: item.count, pay_address: item.pay_address.clone().try_into()? }
+});
+try_from!(item: &protowire::GenerateBlocksResponseMessage, RpcResult<kaspa_rpc_core::GenerateBlocksResponse>, {
+    Self { block_hashes: item.block_hashes.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(item: &protowire::GetAddressHistoryRequestMessage, kaspa_rpc_core::GetAddressHistoryRequest, {
+    Self {
+        address: item.address.as_str().try_into()?,
+        start_daa_score: item.start_daa_score,
+        cursor: if item.cursor.is_empty() { None } else { Some(item.cursor.clone()) },
+        limit: item.limit,
+    }
+});
+try_from!(item: &protowire::GetAddressHistoryResponseMessage, RpcResult<kaspa_rpc_core::GetAddressHistoryResponse>, {
+    Self {
+        entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        next_cursor: if item.next_cursor.is_empty() { None } else { Some(item.next_cursor.clone()) },
+    }
+});
+
+try_from!(item: &protowire::GetBalanceAtRequestMessage, kaspa_rpc_core::GetBalanceAtRequest, {
+    Self {
+        addresses: item.addresses.iter().map(|x| x.as_str().try_into()).collect::<Result<Vec<_>, _>>()?,
+        daa_score: item.daa_score,
+    }
+});
+try_from!(item: &protowire::GetBalanceAtResponseMessage, RpcResult<kaspa_rpc_core::GetBalanceAtResponse>, {
+    Self { entries: item.entries.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()? }
+});
+
+try_from!(&protowire::GetSyncProgressRequestMessage, kaspa_rpc_core::GetSyncProgressRequest);
+try_from!(item: &protowire::GetSyncProgressResponseMessage, RpcResult<kaspa_rpc_core::GetSyncProgressResponse>, {
+    Self { is_synced: item.is_synced, progress: item.progress.as_ref().map(|x| x.try_into()).transpose()? }
+});
+
+try_from!(item: &protowire::GetBlockTemplateDiagnosticsRequestMessage, kaspa_rpc_core::GetBlockTemplateDiagnosticsRequest, {
+    Self {
+        pay_address: item.pay_address.clone().try_into()?,
+        extra_data: RpcExtraData::from_iter(item.extra_data.bytes()),
+        priority_transaction_ids: item.priority_transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        excluded_transaction_ids: item.excluded_transaction_ids.iter().map(|x| RpcHash::from_str(x)).collect::<Result<Vec<_>, _>>()?,
+        reserved_mass: item.reserved_mass,
+    }
+});
+try_from!(item: &protowire::GetBlockTemplateDiagnosticsResponseMessage, RpcResult<kaspa_rpc_core::GetBlockTemplateDiagnosticsResponse>, {
+    let masses = |masses: &Option<protowire::RpcBlockMasses>, field: &str| {
+        masses
+            .as_ref()
+            .map(kaspa_rpc_core::RpcBlockMasses::from)
+            .ok_or_else(|| RpcError::MissingRpcFieldError("GetBlockTemplateDiagnosticsResponseMessage".to_string(), field.to_string()))
+    };
+    Self {
+        selector_kind: item.selector_kind.into(),
+        ready_transaction_count: item.ready_transaction_count,
+        selected_transaction_count: item.selected_transaction_count,
+        total_fees: item.total_fees,
+        mass_used: masses(&item.mass_used, "massUsed")?,
+        mass_limits: masses(&item.mass_limits, "massLimits")?,
+        normalized_mass_used: item.normalized_mass_used,
+        normalized_mass_limit: item.normalized_mass_limit,
+        lanes: item.lanes.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        lanes_per_block_limit: item.lanes_per_block_limit,
+        gas_per_lane_limit: item.gas_per_lane_limit,
+        candidates: item.candidates.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+    }
+});
+
 try_from!(item: &protowire::GetBlocksRequestMessage, kaspa_rpc_core::GetBlocksRequest, {
     Self {
         low_hash: if item.low_hash.is_empty() { None } else { Some(RpcHash::from_str(&item.low_hash)?) },
@@ -965,7 +1150,8 @@ try_from!(item: &protowire::GetFeeEstimateResponseMessage, RpcResult<kaspa_rpc_c
 });
 try_from!(item: &protowire::GetFeeEstimateExperimentalRequestMessage, kaspa_rpc_core::GetFeeEstimateExperimentalRequest, {
     Self {
-        verbose: item.verbose
+        verbose: item.verbose,
+        targets: item.targets.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
     }
 });
 try_from!(item: &protowire::GetFeeEstimateExperimentalResponseMessage, RpcResult<kaspa_rpc_core::GetFeeEstimateExperimentalResponse>, {
@@ -974,7 +1160,8 @@ try_from!(item: &protowire::GetFeeEstimateExperimentalResponseMessage, RpcResult
             .as_ref()
             .ok_or_else(|| RpcError::MissingRpcFieldError("GetFeeEstimateExperimentalResponseMessage".to_string(), "estimate".to_string()))?
             .try_into()?,
-        verbose: item.verbose.as_ref().map(|x| x.try_into()).transpose()?
+        verbose: item.verbose.as_ref().map(|x| x.try_into()).transpose()?,
+        target_estimates: item.target_estimates.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
     }
 });
 
@@ -1130,6 +1317,11 @@ try_from!(item: &protowire::NotifyVirtualDaaScoreChangedRequestMessage, kaspa_rp
 });
 try_from!(&protowire::NotifyVirtualDaaScoreChangedResponseMessage, RpcResult<kaspa_rpc_core::NotifyVirtualDaaScoreChangedResponse>);
 
+try_from!(item: &protowire::NotifySyncProgressChangedRequestMessage, kaspa_rpc_core::NotifySyncProgressChangedRequest, {
+    Self { command: item.command.into() }
+});
+try_from!(&protowire::NotifySyncProgressChangedResponseMessage, RpcResult<kaspa_rpc_core::NotifySyncProgressChangedResponse>);
+
 try_from!(item: &protowire::NotifyVirtualChainChangedRequestMessage, kaspa_rpc_core::NotifyVirtualChainChangedRequest, {
     Self { include_accepted_transaction_ids: item.include_accepted_transaction_ids, command: item.command.into() }
 });
@@ -37,6 +37,13 @@ from!(item: &kaspa_rpc_core::ConnectionMetrics, protowire::ConnectionMetrics, {
         json_connection_attempts: item.json_connection_attempts,
         json_handshake_failures: item.json_handshake_failures,
         active_peers: item.active_peers,
+        p2p_messages_rx: item.p2p_messages_rx,
+        p2p_messages_tx: item.p2p_messages_tx,
+        p2p_invalid_blocks: item.p2p_invalid_blocks,
+        p2p_invalid_transactions: item.p2p_invalid_transactions,
+        p2p_dropped_messages: item.p2p_dropped_messages,
+        p2p_relay_latency_millis: item.p2p_relay_latency_millis,
+        p2p_max_outgoing_queue_fullness: item.p2p_max_outgoing_queue_fullness,
     }
 });
 
@@ -111,6 +118,13 @@ try_from!(item: &protowire::ConnectionMetrics, kaspa_rpc_core::ConnectionMetrics
         json_connection_attempts: item.json_connection_attempts,
         json_handshake_failures: item.json_handshake_failures,
         active_peers: item.active_peers,
+        p2p_messages_rx: item.p2p_messages_rx,
+        p2p_messages_tx: item.p2p_messages_tx,
+        p2p_invalid_blocks: item.p2p_invalid_blocks,
+        p2p_invalid_transactions: item.p2p_invalid_transactions,
+        p2p_dropped_messages: item.p2p_dropped_messages,
+        p2p_relay_latency_millis: item.p2p_relay_latency_millis,
+        p2p_max_outgoing_queue_fullness: item.p2p_max_outgoing_queue_fullness,
     }
 });
 
@@ -1,6 +1,7 @@
 pub mod acceptance_data;
 pub mod address;
 pub mod block;
+pub mod block_template;
 pub mod error;
 pub mod feerate_estimate;
 pub mod header;
@@ -11,4 +12,5 @@ pub mod metrics;
 pub mod notification;
 pub mod optional;
 pub mod peer;
+pub mod sync;
 pub mod tx;
@@ -6,8 +6,8 @@ use crate::protowire::{
     NotifyPruningPointUtxoSetOverrideResponseMessage, NotifyUtxosChangedRequestMessage, NotifyUtxosChangedResponseMessage,
     PruningPointUtxoSetOverrideNotificationMessage, SinkBlueScoreChangedNotificationMessage,
     StopNotifyingPruningPointUtxoSetOverrideRequestMessage, StopNotifyingPruningPointUtxoSetOverrideResponseMessage,
-    StopNotifyingUtxosChangedRequestMessage, StopNotifyingUtxosChangedResponseMessage, UtxosChangedNotificationMessage,
-    VirtualChainChangedNotificationMessage, VirtualDaaScoreChangedNotificationMessage,
+    StopNotifyingUtxosChangedRequestMessage, StopNotifyingUtxosChangedResponseMessage, SyncProgressChangedNotificationMessage,
+    UtxosChangedNotificationMessage, VirtualChainChangedNotificationMessage, VirtualDaaScoreChangedNotificationMessage,
 };
 use crate::{from, try_from};
 use kaspa_notify::subscription::Command;
@@ -34,6 +34,7 @@ from!(item: &kaspa_rpc_core::Notification, Payload, {
         Notification::PruningPointUtxoSetOverride(notification) => {
             Payload::PruningPointUtxoSetOverrideNotification(notification.into())
         },
+        Notification::SyncProgressChanged(notification) => Payload::SyncProgressChangedNotification(notification.into()),
     }
 });
 
@@ -74,6 +75,10 @@ from!(item: &kaspa_rpc_core::VirtualDaaScoreChangedNotification, VirtualDaaScore
 
 from!(&kaspa_rpc_core::PruningPointUtxoSetOverrideNotification, PruningPointUtxoSetOverrideNotificationMessage);
 
+from!(item: &kaspa_rpc_core::SyncProgressChangedNotification, SyncProgressChangedNotificationMessage, {
+    Self { progress: item.progress.as_ref().map(|x| x.into()) }
+});
+
 from!(item: Command, RpcNotifyCommand, {
     match item {
         Command::Start => RpcNotifyCommand::NotifyStart,
@@ -117,6 +122,7 @@ try_from!(item: &Payload, kaspa_rpc_core::Notification, {
         Payload::PruningPointUtxoSetOverrideNotification(notification) => {
             Notification::PruningPointUtxoSetOverride(notification.try_into()?)
         }
+        Payload::SyncProgressChangedNotification(notification) => Notification::SyncProgressChanged(notification.try_into()?),
         _ => Err(RpcError::UnsupportedFeature)?,
     }
 });
@@ -171,6 +177,10 @@ try_from!(item: &VirtualDaaScoreChangedNotificationMessage, kaspa_rpc_core::Virt
 
 try_from!(&PruningPointUtxoSetOverrideNotificationMessage, kaspa_rpc_core::PruningPointUtxoSetOverrideNotification);
 
+try_from!(item: &SyncProgressChangedNotificationMessage, kaspa_rpc_core::SyncProgressChangedNotification, {
+    Self { progress: item.progress.as_ref().map(|x| x.try_into()).transpose()? }
+});
+
 from!(item: RpcNotifyCommand, Command, {
     match item {
         RpcNotifyCommand::NotifyStart => Command::Start,
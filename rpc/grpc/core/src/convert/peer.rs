@@ -19,6 +19,29 @@ from!(item: &kaspa_rpc_core::RpcPeerInfo, protowire::GetConnectedPeerInfoMessage
         advertised_protocol_version: item.advertised_protocol_version,
         time_connected: item.time_connected as i64,
         is_ibd_peer: item.is_ibd_peer,
+        stats: item.stats.as_ref().map(|x| x.into()),
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcPeerMessageStats, protowire::RpcPeerMessageStats, {
+    Self {
+        message_type: item.message_type.clone(),
+        messages_in: item.messages_in,
+        bytes_in: item.bytes_in,
+        messages_out: item.messages_out,
+        bytes_out: item.bytes_out,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcPeerStats, protowire::RpcPeerStats, {
+    Self {
+        messages: item.messages.iter().map(|x| x.into()).collect(),
+        invalid_blocks: item.invalid_blocks,
+        invalid_transactions: item.invalid_transactions,
+        dropped_messages: item.dropped_messages,
+        relay_latency_millis: item.relay_latency_millis,
+        outgoing_queue_len: item.outgoing_queue_len,
+        outgoing_queue_capacity: item.outgoing_queue_capacity,
     }
 });
 
@@ -40,6 +63,29 @@ try_from!(item: &protowire::GetConnectedPeerInfoMessage, kaspa_rpc_core::RpcPeer
         advertised_protocol_version: item.advertised_protocol_version,
         time_connected: item.time_connected as u64,
         is_ibd_peer: item.is_ibd_peer,
+        stats: item.stats.as_ref().map(|x| x.try_into()).transpose()?,
+    }
+});
+
+try_from!(item: &protowire::RpcPeerMessageStats, kaspa_rpc_core::RpcPeerMessageStats, {
+    Self {
+        message_type: item.message_type.clone(),
+        messages_in: item.messages_in,
+        bytes_in: item.bytes_in,
+        messages_out: item.messages_out,
+        bytes_out: item.bytes_out,
+    }
+});
+
+try_from!(item: &protowire::RpcPeerStats, kaspa_rpc_core::RpcPeerStats, {
+    Self {
+        messages: item.messages.iter().map(|x| x.try_into()).collect::<Result<Vec<_>, _>>()?,
+        invalid_blocks: item.invalid_blocks,
+        invalid_transactions: item.invalid_transactions,
+        dropped_messages: item.dropped_messages,
+        relay_latency_millis: item.relay_latency_millis,
+        outgoing_queue_len: item.outgoing_queue_len,
+        outgoing_queue_capacity: item.outgoing_queue_capacity,
     }
 });
 
@@ -0,0 +1,41 @@
+use std::str::FromStr;
+
+use crate::protowire;
+use crate::{from, try_from};
+use kaspa_rpc_core::{RpcError, RpcNodeId, RpcPeerAddress};
+
+// ----------------------------------------------------------------------------
+// rpc_core to protowire
+// ----------------------------------------------------------------------------
+
+from!(item: &kaspa_rpc_core::RpcSyncProgress, protowire::RpcSyncProgress, {
+    Self {
+        phase: item.phase.into(),
+        syncer_id: item.syncer_id.to_string(),
+        syncer_address: item.syncer_address.to_string(),
+        processed: item.processed,
+        total: item.total,
+        low_daa_score: item.low_daa_score,
+        high_daa_score: item.high_daa_score,
+        current_daa_score: item.current_daa_score,
+        eta_seconds: item.eta_seconds,
+    }
+});
+
+// ----------------------------------------------------------------------------
+// protowire to rpc_core
+// ----------------------------------------------------------------------------
+
+try_from!(item: &protowire::RpcSyncProgress, kaspa_rpc_core::RpcSyncProgress, {
+    Self {
+        phase: item.phase.into(),
+        syncer_id: RpcNodeId::from_str(&item.syncer_id)?,
+        syncer_address: RpcPeerAddress::from_str(&item.syncer_address)?,
+        processed: item.processed,
+        total: item.total,
+        low_daa_score: item.low_daa_score,
+        high_daa_score: item.high_daa_score,
+        current_daa_score: item.current_daa_score,
+        eta_seconds: item.eta_seconds,
+    }
+});
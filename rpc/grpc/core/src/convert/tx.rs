@@ -134,6 +134,25 @@ from!(item: &kaspa_rpc_core::RpcChainBlockAcceptedTransactions, protowire::RpcCh
     }
 });
 
+from!(item: &kaspa_rpc_core::RpcAcceptedTransaction, protowire::RpcAcceptedTransaction, {
+    Self {
+        transaction: Some(protowire::RpcOptionalTransaction::from(&item.transaction)),
+        merged_block_hash: item.merged_block_hash.to_string(),
+        fee: item.fee,
+        compute_mass: item.compute_mass,
+        transient_mass: item.transient_mass,
+        storage_mass: item.storage_mass,
+    }
+});
+
+from!(item: &kaspa_rpc_core::RpcAcceptingBlockTransactions, protowire::RpcAcceptingBlockTransactions, {
+    Self {
+        accepting_block_hash: item.accepting_block_hash.to_string(),
+        accepting_daa_score: item.accepting_daa_score,
+        accepted_transactions: item.accepted_transactions.iter().map(protowire::RpcAcceptedTransaction::from).collect(),
+    }
+});
+
 from!(item: &kaspa_rpc_core::RpcScriptPublicKey, protowire::RpcScriptPublicKey, {
     Self { version: item.version().into(), script_public_key: item.script().to_rpc_hex() }
 });
@@ -433,6 +452,30 @@ try_from!(item: &protowire::RpcChainBlockAcceptedTransactions, kaspa_rpc_core::R
     }
 });
 
+try_from!(item: &protowire::RpcAcceptedTransaction, kaspa_rpc_core::RpcAcceptedTransaction, {
+    Self {
+        transaction: item
+            .transaction
+            .as_ref()
+            .map(kaspa_rpc_core::RpcOptionalTransaction::try_from)
+            .transpose()?
+            .ok_or_else(|| RpcError::MissingRpcFieldError("RpcAcceptedTransaction".to_string(), "transaction".to_string()))?,
+        merged_block_hash: RpcHash::from_str(&item.merged_block_hash)?,
+        fee: item.fee,
+        compute_mass: item.compute_mass,
+        transient_mass: item.transient_mass,
+        storage_mass: item.storage_mass,
+    }
+});
+
+try_from!(item: &protowire::RpcAcceptingBlockTransactions, kaspa_rpc_core::RpcAcceptingBlockTransactions, {
+    Self {
+        accepting_block_hash: RpcHash::from_str(&item.accepting_block_hash)?,
+        accepting_daa_score: item.accepting_daa_score,
+        accepted_transactions: item.accepted_transactions.iter().map(kaspa_rpc_core::RpcAcceptedTransaction::try_from).collect::<Result<_, _>>()?,
+    }
+});
+
 try_from!(item: &protowire::RpcUtxosByAddressesEntry, kaspa_rpc_core::RpcUtxosByAddressesEntry, {
     let address = if item.address.is_empty() { None } else { Some(item.address.as_str().try_into()?) };
     Self {
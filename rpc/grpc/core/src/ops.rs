@@ -91,6 +91,12 @@ pub enum KaspadPayloadOps {
     GetVirtualChainFromBlockV2,
     GetBlockRewardInfo,
     GetSeqCommitLaneProof,
+    GetAcceptedTransactions,
+    GenerateBlocks,
+    GetAddressHistory,
+    GetBalanceAt,
+    GetSyncProgress,
+    GetBlockTemplateDiagnostics,
 
     // Subscription commands for starting/stopping notifications
     NotifyBlockAdded,
@@ -101,6 +107,7 @@ pub enum KaspadPayloadOps {
     NotifyPruningPointUtxoSetOverride,
     NotifyVirtualDaaScoreChanged,
     NotifyVirtualChainChanged,
+    NotifySyncProgressChanged,
 
     // Legacy stop subscription commands
     StopNotifyingUtxosChanged,
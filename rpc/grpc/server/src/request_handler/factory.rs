@@ -85,6 +85,12 @@ impl Factory {
                 GetUtxoReturnAddress,
                 GetVirtualChainFromBlockV2,
                 GetSeqCommitLaneProof,
+                GetAcceptedTransactions,
+                GenerateBlocks,
+                GetAddressHistory,
+                GetBalanceAt,
+                GetSyncProgress,
+                GetBlockTemplateDiagnostics,
                 NotifyBlockAdded,
                 NotifyNewBlockTemplate,
                 NotifyFinalityConflict,
@@ -93,6 +99,7 @@ impl Factory {
                 NotifyPruningPointUtxoSetOverride,
                 NotifyVirtualDaaScoreChanged,
                 NotifyVirtualChainChanged,
+                NotifySyncProgressChanged,
                 StopNotifyingUtxosChanged,
                 StopNotifyingPruningPointUtxoSetOverride,
             ]
@@ -214,6 +214,54 @@ impl RpcApi for RpcCoreMock {
         Err(RpcError::NotImplemented)
     }
 
+    async fn get_accepted_transactions_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetAcceptedTransactionsRequest,
+    ) -> RpcResult<GetAcceptedTransactionsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn generate_blocks_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GenerateBlocksRequest,
+    ) -> RpcResult<GenerateBlocksResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_address_history_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetAddressHistoryRequest,
+    ) -> RpcResult<GetAddressHistoryResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_balance_at_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetBalanceAtRequest,
+    ) -> RpcResult<GetBalanceAtResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_sync_progress_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetSyncProgressRequest,
+    ) -> RpcResult<GetSyncProgressResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
+    async fn get_block_template_diagnostics_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetBlockTemplateDiagnosticsRequest,
+    ) -> RpcResult<GetBlockTemplateDiagnosticsResponse> {
+        Err(RpcError::NotImplemented)
+    }
+
     async fn get_subnetwork_call(
         &self,
         _connection: Option<&DynRpcConnection>,
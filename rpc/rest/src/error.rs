@@ -0,0 +1,67 @@
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use kaspa_rpc_core::RpcError;
+use serde::Serialize;
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("{0}")]
+    Other(String),
+
+    #[error("{0}")]
+    BadRequest(String),
+
+    #[error(transparent)]
+    Rpc(#[from] RpcError),
+
+    #[error(transparent)]
+    GrpcClient(#[from] kaspa_grpc_client::error::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+impl From<String> for Error {
+    fn from(s: String) -> Self {
+        Error::Other(s)
+    }
+}
+
+impl Error {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Error::BadRequest(_) => StatusCode::BAD_REQUEST,
+            Error::Rpc(err) => match err {
+                RpcError::RejectedTransaction(..)
+                | RpcError::AddressError(_)
+                | RpcError::HexParsingError(_)
+                | RpcError::MissingRpcFieldError(..)
+                | RpcError::SubnetParsingError(_)
+                | RpcError::InvalidAddressHistoryCursor
+                | RpcError::AddressesExceedingMaximum(..) => StatusCode::BAD_REQUEST,
+                RpcError::TransactionNotFound(_) => StatusCode::NOT_FOUND,
+                RpcError::NoUtxoIndex | RpcError::NoAddressHistoryIndex | RpcError::UnsupportedFeature | RpcError::NotImplemented => {
+                    StatusCode::NOT_IMPLEMENTED
+                }
+                RpcError::UnavailableInSafeMode | RpcError::ConsensusInTransitionalIbdState => StatusCode::SERVICE_UNAVAILABLE,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            },
+            Error::Other(_) | Error::GrpcClient(_) | Error::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// JSON body returned with every non-2xx response
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: String,
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        (self.status_code(), Json(ErrorResponse { error: self.to_string() })).into_response()
+    }
+}
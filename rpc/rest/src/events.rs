@@ -0,0 +1,156 @@
+//! Server-sent events relay of node notifications.
+//!
+//! Every `GET /v1/events` stream registers its own listener on the RPC service and subscribes it to the
+//! requested event types, so streams are fully independent of each other. The listener is unregistered
+//! as soon as the client goes away and the stream is dropped. A client falling more than
+//! [`EVENTS_QUEUE_CAPACITY`] events behind is dropped and its stream ends, so it has to reconnect.
+
+use std::{convert::Infallible, str::FromStr};
+
+use axum::{
+    extract::{Query, State, rejection::QueryRejection},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use futures::{Stream, stream};
+use kaspa_core::warn;
+use kaspa_notify::{
+    events::EventType,
+    listener::ListenerId,
+    notification::Notification as _,
+    scope::{
+        BlockAddedScope, FinalityConflictResolvedScope, FinalityConflictScope, NewBlockTemplateScope,
+        PruningPointUtxoSetOverrideScope, Scope, SinkBlueScoreChangedScope, SyncProgressChangedScope, UtxosChangedScope,
+        VirtualChainChangedScope, VirtualDaaScoreChangedScope,
+    },
+};
+use kaspa_rpc_core::{
+    Notification, RpcAddress,
+    api::rpc::DynRpcService,
+    notify::connection::{ChannelConnection, ChannelType},
+};
+use serde::Deserialize;
+
+use crate::{
+    error::Error,
+    result::Result,
+    routes::{Context, extracted, parse_address},
+};
+
+/// Maximum number of notifications queued for a single event stream
+pub const EVENTS_QUEUE_CAPACITY: usize = 1024;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EventsQuery {
+    /// Comma-separated kebab-case event types, e.g. `block-added,virtual-daa-score-changed`
+    pub events: String,
+    /// Comma-separated addresses tracked by `utxos-changed`; required when that event is requested
+    pub addresses: Option<String>,
+    /// Whether `virtual-chain-changed` notifications carry accepted transaction ids
+    pub include_accepted_transaction_ids: bool,
+}
+
+impl EventsQuery {
+    pub fn scopes(&self) -> Result<Vec<Scope>> {
+        let addresses = self
+            .addresses
+            .as_deref()
+            .map(|list| split_list(list).map(parse_address).collect::<Result<Vec<RpcAddress>>>())
+            .transpose()?;
+
+        let mut event_types = Vec::new();
+        for name in split_list(&self.events) {
+            let event_type = EventType::from_str(name).map_err(|_| Error::BadRequest(format!("unknown event type `{name}`")))?;
+            if !event_types.contains(&event_type) {
+                event_types.push(event_type);
+            }
+        }
+        if event_types.is_empty() {
+            return Err(Error::BadRequest("at least one event type is required in `events`".to_string()));
+        }
+
+        event_types
+            .into_iter()
+            .map(|event_type| {
+                Ok(match event_type {
+                    EventType::BlockAdded => Scope::BlockAdded(BlockAddedScope {}),
+                    EventType::VirtualChainChanged => {
+                        Scope::VirtualChainChanged(VirtualChainChangedScope::new(self.include_accepted_transaction_ids))
+                    }
+                    EventType::FinalityConflict => Scope::FinalityConflict(FinalityConflictScope {}),
+                    EventType::FinalityConflictResolved => Scope::FinalityConflictResolved(FinalityConflictResolvedScope {}),
+                    EventType::UtxosChanged => match addresses.clone() {
+                        // An empty address list subscribes to the whole UTXO set, which is not something to expose over HTTP
+                        Some(addresses) if !addresses.is_empty() => Scope::UtxosChanged(UtxosChangedScope::new(addresses)),
+                        _ => return Err(Error::BadRequest("`utxos-changed` requires a non-empty `addresses` list".to_string())),
+                    },
+                    EventType::SinkBlueScoreChanged => Scope::SinkBlueScoreChanged(SinkBlueScoreChangedScope {}),
+                    EventType::VirtualDaaScoreChanged => Scope::VirtualDaaScoreChanged(VirtualDaaScoreChangedScope {}),
+                    EventType::PruningPointUtxoSetOverride => Scope::PruningPointUtxoSetOverride(PruningPointUtxoSetOverrideScope {}),
+                    EventType::NewBlockTemplate => Scope::NewBlockTemplate(NewBlockTemplateScope {}),
+                    EventType::SyncProgressChanged => Scope::SyncProgressChanged(SyncProgressChangedScope {}),
+                })
+            })
+            .collect()
+    }
+}
+
+fn split_list(list: &str) -> impl Iterator<Item = &str> {
+    list.split(',').map(str::trim).filter(|item| !item.is_empty())
+}
+
+/// Unregisters the stream listener once the client disconnects
+struct Subscription {
+    rpc: DynRpcService,
+    listener_id: ListenerId,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let rpc = self.rpc.clone();
+        let listener_id = self.listener_id;
+        tokio::spawn(async move {
+            if let Err(err) = rpc.unregister_listener(listener_id).await {
+                warn!("REST event stream: failed to unregister listener {listener_id}: {err}");
+            }
+        });
+    }
+}
+
+pub(crate) async fn subscribe(
+    State(context): Context,
+    query: std::result::Result<Query<EventsQuery>, QueryRejection>,
+) -> Result<Sse<impl Stream<Item = std::result::Result<Event, Infallible>>>> {
+    let Query(query) = extracted(query)?;
+    let scopes = query.scopes()?;
+
+    let rpc = context.rpc.clone();
+    let (sender, receiver) = async_channel::bounded(EVENTS_QUEUE_CAPACITY);
+    let connection = ChannelConnection::new("rest-events", sender, ChannelType::Closable).with_close_on_full();
+    let listener_id = rpc.register_new_listener(connection);
+    let subscription = Subscription { rpc: rpc.clone(), listener_id };
+    for scope in scopes {
+        // On failure `subscription` is dropped here, releasing the listener
+        rpc.start_notify(listener_id, scope).await?;
+    }
+
+    let events = stream::unfold((receiver, subscription), |(receiver, subscription)| async move {
+        let notification = receiver.recv().await.ok()?;
+        Some((Ok(to_event(&notification)), (receiver, subscription)))
+    });
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+/// Names the event after its notification variant (e.g. `BlockAdded`) and carries the variant payload as data
+pub fn to_event(notification: &Notification) -> Event {
+    let event = Event::default().event(notification.event_type().to_string());
+    let payload = match serde_json::to_value(notification) {
+        Ok(serde_json::Value::Object(mut tagged)) => match tagged.remove(&notification.event_type().to_string()) {
+            Some(payload) => payload,
+            None => serde_json::Value::Object(tagged),
+        },
+        Ok(other) => other,
+        Err(err) => return event.comment(format!("failed to serialize notification: {err}")),
+    };
+    event.data(payload.to_string())
+}
@@ -0,0 +1,13 @@
+//! HTTP/JSON REST gateway for the Kaspa RPC API.
+//!
+//! Maps a read-mostly subset of [`RpcApi`](kaspa_rpc_core::api::rpc::RpcApi) onto resource URLs, serves an
+//! OpenAPI 3 description of them at `/v1/openapi.json` and relays node notifications as server-sent events.
+//! The gateway is transport agnostic: it wraps any [`DynRpcService`](kaspa_rpc_core::api::rpc::DynRpcService),
+//! be it a gRPC/wRPC client or an in-process RPC service.
+
+pub mod error;
+pub mod events;
+pub mod openapi;
+pub mod result;
+pub mod routes;
+pub mod server;
@@ -0,0 +1,91 @@
+use std::{process::ExitCode, sync::Arc};
+
+use clap::Parser;
+use kaspa_consensus_core::network::NetworkType;
+use kaspa_core::{error, info};
+use kaspa_grpc_client::GrpcClient;
+use kaspa_rest_server::{
+    result::Result,
+    server::{Options, RestServer},
+};
+use kaspa_rpc_core::notify::mode::NotificationMode;
+
+#[derive(Parser, Debug)]
+#[command(name = "kaspa-rest-server", version, about = "REST/OpenAPI gateway for a Kaspa node")]
+struct Args {
+    /// gRPC endpoint of the node, e.g. `grpc://127.0.0.1:16110`; defaults to the local node of the selected network
+    #[arg(long = "rpcserver", short = 's')]
+    rpc_server: Option<String>,
+
+    /// `interface:port` the HTTP server listens on
+    #[arg(long, default_value = "127.0.0.1:8110")]
+    listen: String,
+
+    /// Origin allowed to issue cross-origin requests (repeatable, `*` for any)
+    #[arg(long = "cors-origin")]
+    cors_origins: Vec<String>,
+
+    /// Use the test network
+    #[arg(long, conflicts_with_all = ["simnet", "devnet"])]
+    testnet: bool,
+
+    /// Use the simulation network
+    #[arg(long, conflicts_with = "devnet")]
+    simnet: bool,
+
+    /// Use the development network
+    #[arg(long)]
+    devnet: bool,
+}
+
+impl Args {
+    fn network_type(&self) -> NetworkType {
+        match (self.testnet, self.simnet, self.devnet) {
+            (true, _, _) => NetworkType::Testnet,
+            (_, true, _) => NetworkType::Simnet,
+            (_, _, true) => NetworkType::Devnet,
+            _ => NetworkType::Mainnet,
+        }
+    }
+
+    fn rpc_url(&self) -> String {
+        match &self.rpc_server {
+            Some(url) if url.starts_with("grpc://") => url.clone(),
+            Some(address) => format!("grpc://{address}"),
+            None => format!("grpc://127.0.0.1:{}", self.network_type().default_rpc_port()),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    kaspa_core::log::init_logger(None, "");
+    let args = Args::parse();
+    match run(args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            error!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(args: Args) -> Result<()> {
+    let url = args.rpc_url();
+    info!("Connecting to {url}");
+    let client = GrpcClient::connect_with_args(
+        NotificationMode::MultiListeners,
+        url,
+        None,
+        true,
+        None,
+        false,
+        Some(500_000),
+        Default::default(),
+    )
+    .await?;
+    client.start(None).await;
+
+    let options = Options { listen_address: args.listen, cors_origins: args.cors_origins };
+    RestServer::new(Arc::new(client), options).listen().await
+}
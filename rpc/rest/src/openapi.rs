@@ -0,0 +1,712 @@
+//! OpenAPI 3.0 description of the REST gateway.
+//!
+//! Component schemas describe the JSON (human-readable serde) form of the `kaspa_rpc_core::model` types,
+//! which is not always their derived shape: hashes, scripts and blue work serialize as hex strings and
+//! `RpcTransaction` has a hand-written serializer. Each schema is bound to its model type through
+//! [`ApiSchema`] and the tests below check serialized sample values against it, so a model change that
+//! alters the wire format fails the build here rather than silently invalidating the published spec.
+
+use std::collections::BTreeMap;
+
+use kaspa_rpc_core::{
+    GetBalanceByAddressResponse, GetBalancesByAddressesRequest, GetBalancesByAddressesResponse, GetBlockDagInfoResponse,
+    GetBlockResponse, GetFeeEstimateResponse, GetMempoolEntryResponse, GetServerInfoResponse, GetUtxosByAddressesRequest,
+    GetUtxosByAddressesResponse, RpcBalancesByAddressesEntry, RpcBlock, RpcBlockVerboseData, RpcCovenantBinding, RpcFeeEstimate,
+    RpcFeerateBucket, RpcHeader, RpcMempoolEntry, RpcTransaction, RpcTransactionInput, RpcTransactionInputVerboseData,
+    RpcTransactionOutpoint, RpcTransactionOutput, RpcTransactionOutputVerboseData, RpcTransactionVerboseData, RpcUtxoEntry,
+    RpcUtxosByAddressesEntry, SubmitTransactionRequest, SubmitTransactionResponse,
+};
+use serde_json::{Value, json};
+
+use crate::error::ErrorResponse;
+
+/// A type published under `components/schemas`
+pub trait ApiSchema {
+    /// Component name, by convention the Rust type name
+    const NAME: &'static str;
+
+    /// The schema of the JSON form of the type; referenced types are registered through `components`
+    fn schema(components: &mut Components) -> Value;
+}
+
+/// Registry of the component schemas reachable from the documented operations
+#[derive(Debug, Default)]
+pub struct Components {
+    schemas: BTreeMap<&'static str, Value>,
+}
+
+impl Components {
+    /// Registers `T` along with every type it references and returns a `$ref` to it
+    pub fn reference<T: ApiSchema>(&mut self) -> Value {
+        if !self.schemas.contains_key(T::NAME) {
+            // Reserve the slot first so that self-referencing schemas terminate
+            self.schemas.insert(T::NAME, Value::Null);
+            let schema = T::schema(self);
+            self.schemas.insert(T::NAME, schema);
+        }
+        json!({ "$ref": format!("#/components/schemas/{}", T::NAME) })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.schemas.get(name)
+    }
+
+    fn into_value(self) -> Value {
+        Value::Object(self.schemas.into_iter().map(|(name, schema)| (name.to_string(), schema)).collect())
+    }
+}
+
+/// An object whose properties are all emitted by the serializer
+fn object(properties: &[(&str, Value)]) -> Value {
+    object_with_optional(properties, &[])
+}
+
+/// An object where the `optional` properties may be omitted on input (they are still always emitted on output)
+fn object_with_optional(properties: &[(&str, Value)], optional: &[&str]) -> Value {
+    let required: Vec<&str> = properties.iter().map(|(name, _)| *name).filter(|name| !optional.contains(name)).collect();
+    let properties: serde_json::Map<String, Value> =
+        properties.iter().map(|(name, schema)| (name.to_string(), schema.clone())).collect();
+    json!({ "type": "object", "properties": properties, "required": required, "additionalProperties": false })
+}
+
+fn nullable(schema: Value) -> Value {
+    match schema {
+        // Siblings of `$ref` are ignored by OpenAPI 3.0, so the reference is wrapped
+        Value::Object(map) if map.contains_key("$ref") => json!({ "allOf": [Value::Object(map)], "nullable": true }),
+        Value::Object(mut map) => {
+            map.insert("nullable".to_string(), Value::Bool(true));
+            Value::Object(map)
+        }
+        other => other,
+    }
+}
+
+fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+fn uint(format: &str) -> Value {
+    json!({ "type": "integer", "format": format, "minimum": 0 })
+}
+
+fn double() -> Value {
+    json!({ "type": "number", "format": "double" })
+}
+
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
+
+fn string(description: &str) -> Value {
+    json!({ "type": "string", "description": description })
+}
+
+fn hex(description: &str) -> Value {
+    json!({ "type": "string", "format": "hex", "pattern": "^([0-9a-fA-F]{2})*$", "description": description })
+}
+
+fn hash() -> Value {
+    json!({ "type": "string", "format": "hex", "pattern": "^[0-9a-fA-F]{64}$", "description": "32-byte hash" })
+}
+
+fn address() -> Value {
+    string("Kaspa address including its network prefix, e.g. `kaspa:qz...`")
+}
+
+fn network_id() -> Value {
+    string("Network id, e.g. `mainnet` or `testnet-10`")
+}
+
+fn script_public_key() -> Value {
+    hex("Script version (2 bytes, big endian) followed by the script bytes")
+}
+
+macro_rules! impl_api_schema {
+    ($($ty:ident => |$components:ident| $schema:expr;)*) => {
+        $(
+            impl ApiSchema for $ty {
+                const NAME: &'static str = stringify!($ty);
+
+                #[allow(unused_variables)]
+                fn schema($components: &mut Components) -> Value {
+                    $schema
+                }
+            }
+        )*
+    };
+}
+
+impl_api_schema! {
+    RpcHeader => |c| object(&[
+        ("hash", hash()),
+        ("version", uint("uint16")),
+        ("parentsByLevel", array(array(hash()))),
+        ("hashMerkleRoot", hash()),
+        ("acceptedIdMerkleRoot", hash()),
+        ("utxoCommitment", hash()),
+        ("timestamp", uint("uint64")),
+        ("bits", uint("uint32")),
+        ("nonce", uint("uint64")),
+        ("daaScore", uint("uint64")),
+        ("blueWork", hex("Accumulated blue work, big endian")),
+        ("blueScore", uint("uint64")),
+        ("pruningPoint", hash()),
+    ]);
+    RpcBlockVerboseData => |c| object(&[
+        ("hash", hash()),
+        ("difficulty", double()),
+        ("selectedParentHash", hash()),
+        ("transactionIds", array(hash())),
+        ("isHeaderOnly", boolean()),
+        ("blueScore", uint("uint64")),
+        ("childrenHashes", array(hash())),
+        ("mergeSetBluesHashes", array(hash())),
+        ("mergeSetRedsHashes", array(hash())),
+        ("isChainBlock", boolean()),
+    ]);
+    RpcBlock => |c| object(&[
+        ("header", c.reference::<RpcHeader>()),
+        ("transactions", array(c.reference::<RpcTransaction>())),
+        ("verboseData", nullable(c.reference::<RpcBlockVerboseData>())),
+    ]);
+    RpcTransactionOutpoint => |c| object(&[("transactionId", hash()), ("index", uint("uint32"))]);
+    RpcUtxoEntry => |c| object(&[
+        ("amount", uint("uint64")),
+        ("scriptPublicKey", script_public_key()),
+        ("blockDaaScore", uint("uint64")),
+        ("isCoinbase", boolean()),
+        ("covenantId", nullable(hash())),
+    ]);
+    RpcTransactionInputVerboseData => |c| object(&[]);
+    RpcTransactionInput => |c| object_with_optional(&[
+        ("previousOutpoint", c.reference::<RpcTransactionOutpoint>()),
+        ("signatureScript", hex("Signature script")),
+        ("sequence", uint("uint64")),
+        ("sigOpCount", uint("uint8")),
+        ("computeBudget", uint("uint16")),
+        ("verboseData", nullable(c.reference::<RpcTransactionInputVerboseData>())),
+    ], &["computeBudget"]);
+    RpcCovenantBinding => |c| object(&[("authorizingInput", uint("uint16")), ("covenantId", hash())]);
+    RpcTransactionOutputVerboseData => |c| object(&[
+        ("scriptPublicKeyType", json!({ "type": "string", "enum": ["NonStandard", "PubKey", "PubKeyECDSA", "ScriptHash"] })),
+        ("scriptPublicKeyAddress", address()),
+    ]);
+    RpcTransactionOutput => |c| object(&[
+        ("value", uint("uint64")),
+        ("scriptPublicKey", script_public_key()),
+        ("verboseData", nullable(c.reference::<RpcTransactionOutputVerboseData>())),
+        ("covenant", nullable(c.reference::<RpcCovenantBinding>())),
+    ]);
+    RpcTransactionVerboseData => |c| object(&[
+        ("transactionId", hash()),
+        ("hash", hash()),
+        ("computeMass", uint("uint64")),
+        ("blockHash", hash()),
+        ("blockTime", uint("uint64")),
+    ]);
+    RpcTransaction => |c| object_with_optional(&[
+        ("version", uint("uint16")),
+        ("inputs", array(c.reference::<RpcTransactionInput>())),
+        ("outputs", array(c.reference::<RpcTransactionOutput>())),
+        ("lockTime", uint("uint64")),
+        ("subnetworkId", hex("20-byte subnetwork id")),
+        ("gas", uint("uint64")),
+        ("payload", hex("Transaction payload")),
+        ("storageMass", uint("uint64")),
+        ("mass", json!({ "type": "integer", "format": "uint64", "minimum": 0, "deprecated": true, "description": "Alias of `storageMass`" })),
+        ("verboseData", nullable(c.reference::<RpcTransactionVerboseData>())),
+    ], &["storageMass", "mass"]);
+    RpcUtxosByAddressesEntry => |c| object(&[
+        ("address", nullable(address())),
+        ("outpoint", c.reference::<RpcTransactionOutpoint>()),
+        ("utxoEntry", c.reference::<RpcUtxoEntry>()),
+    ]);
+    RpcBalancesByAddressesEntry => |c| object(&[("address", address()), ("balance", nullable(uint("uint64")))]);
+    RpcFeerateBucket => |c| object(&[("feerate", double()), ("estimatedSeconds", double())]);
+    RpcFeeEstimate => |c| object(&[
+        ("priorityBucket", c.reference::<RpcFeerateBucket>()),
+        ("normalBuckets", array(c.reference::<RpcFeerateBucket>())),
+        ("lowBuckets", array(c.reference::<RpcFeerateBucket>())),
+    ]);
+    // Not renamed to camelCase in the model
+    RpcMempoolEntry => |c| object(&[
+        ("fee", uint("uint64")),
+        ("transaction", c.reference::<RpcTransaction>()),
+        ("is_orphan", boolean()),
+    ]);
+    GetServerInfoResponse => |c| object(&[
+        ("rpcApiVersion", uint("uint16")),
+        ("rpcApiRevision", uint("uint16")),
+        ("serverVersion", string("Node version")),
+        ("networkId", network_id()),
+        ("hasUtxoIndex", boolean()),
+        ("isSynced", boolean()),
+        ("virtualDaaScore", uint("uint64")),
+    ]);
+    GetBlockDagInfoResponse => |c| object(&[
+        ("network", network_id()),
+        ("blockCount", uint("uint64")),
+        ("headerCount", uint("uint64")),
+        ("tipHashes", array(hash())),
+        ("difficulty", double()),
+        ("pastMedianTime", uint("uint64")),
+        ("virtualParentHashes", array(hash())),
+        ("pruningPointHash", hash()),
+        ("virtualDaaScore", uint("uint64")),
+        ("sink", hash()),
+    ]);
+    GetBlockResponse => |c| object(&[("block", c.reference::<RpcBlock>())]);
+    GetBalanceByAddressResponse => |c| object(&[("balance", uint("uint64"))]);
+    GetBalancesByAddressesRequest => |c| object(&[("addresses", array(address()))]);
+    GetBalancesByAddressesResponse => |c| object(&[("entries", array(c.reference::<RpcBalancesByAddressesEntry>()))]);
+    GetUtxosByAddressesRequest => |c| object(&[("addresses", array(address()))]);
+    GetUtxosByAddressesResponse => |c| object(&[("entries", array(c.reference::<RpcUtxosByAddressesEntry>()))]);
+    GetFeeEstimateResponse => |c| object(&[("estimate", c.reference::<RpcFeeEstimate>())]);
+    GetMempoolEntryResponse => |c| object(&[("mempoolEntry", c.reference::<RpcMempoolEntry>())]);
+    SubmitTransactionRequest => |c| object(&[("transaction", c.reference::<RpcTransaction>()), ("allowOrphan", boolean())]);
+    SubmitTransactionResponse => |c| object(&[("transactionId", hash())]);
+    ErrorResponse => |c| object(&[("error", string("Human readable error message"))]);
+}
+
+fn path_parameter(name: &str, description: &str, schema: Value) -> Value {
+    json!({ "name": name, "in": "path", "required": true, "description": description, "schema": schema })
+}
+
+fn query_parameter(name: &str, description: &str, schema: Value) -> Value {
+    json!({ "name": name, "in": "query", "required": false, "description": description, "schema": schema })
+}
+
+struct Operation<'a> {
+    id: &'a str,
+    summary: &'a str,
+    parameters: Vec<Value>,
+    request: Option<Value>,
+    response: Value,
+}
+
+impl Operation<'_> {
+    fn into_value(self, error: &Value) -> Value {
+        let mut operation = json!({
+            "operationId": self.id,
+            "summary": self.summary,
+            "parameters": self.parameters,
+            "responses": {
+                "200": { "description": "Success", "content": { "application/json": { "schema": self.response } } },
+                "default": { "description": "Error", "content": { "application/json": { "schema": error } } },
+            },
+        });
+        if let Some(request) = self.request {
+            operation["requestBody"] = json!({ "required": true, "content": { "application/json": { "schema": request } } });
+        }
+        operation
+    }
+}
+
+/// Renders the OpenAPI document describing every route of [`crate::routes::router`]
+pub fn document() -> Value {
+    let mut c = Components::default();
+    let error = c.reference::<ErrorResponse>();
+    let address_parameter = || path_parameter("address", "Kaspa address including its network prefix", address());
+
+    let operations = [
+        (
+            "/v1/info",
+            "get",
+            Operation {
+                id: "getInfo",
+                summary: "Node version, network and sync state",
+                parameters: vec![],
+                request: None,
+                response: c.reference::<GetServerInfoResponse>(),
+            },
+        ),
+        (
+            "/v1/blockdag",
+            "get",
+            Operation {
+                id: "getBlockDagInfo",
+                summary: "Current state of the block DAG",
+                parameters: vec![],
+                request: None,
+                response: c.reference::<GetBlockDagInfoResponse>(),
+            },
+        ),
+        (
+            "/v1/blocks/{hash}",
+            "get",
+            Operation {
+                id: "getBlock",
+                summary: "A block by hash",
+                parameters: vec![
+                    path_parameter("hash", "Block hash", hash()),
+                    query_parameter("includeTransactions", "Include the block transactions", boolean()),
+                ],
+                request: None,
+                response: c.reference::<GetBlockResponse>(),
+            },
+        ),
+        (
+            "/v1/addresses/{address}/balance",
+            "get",
+            Operation {
+                id: "getBalanceByAddress",
+                summary: "Balance of an address (requires the UTXO index)",
+                parameters: vec![address_parameter()],
+                request: None,
+                response: c.reference::<GetBalanceByAddressResponse>(),
+            },
+        ),
+        (
+            "/v1/addresses/{address}/utxos",
+            "get",
+            Operation {
+                id: "getUtxosByAddress",
+                summary: "UTXOs of an address (requires the UTXO index)",
+                parameters: vec![address_parameter()],
+                request: None,
+                response: c.reference::<GetUtxosByAddressesResponse>(),
+            },
+        ),
+        (
+            "/v1/addresses/balances",
+            "post",
+            Operation {
+                id: "getBalancesByAddresses",
+                summary: "Balances of several addresses (requires the UTXO index)",
+                parameters: vec![],
+                request: Some(c.reference::<GetBalancesByAddressesRequest>()),
+                response: c.reference::<GetBalancesByAddressesResponse>(),
+            },
+        ),
+        (
+            "/v1/addresses/utxos",
+            "post",
+            Operation {
+                id: "getUtxosByAddresses",
+                summary: "UTXOs of several addresses (requires the UTXO index)",
+                parameters: vec![],
+                request: Some(c.reference::<GetUtxosByAddressesRequest>()),
+                response: c.reference::<GetUtxosByAddressesResponse>(),
+            },
+        ),
+        (
+            "/v1/fee-estimate",
+            "get",
+            Operation {
+                id: "getFeeEstimate",
+                summary: "Feerate buckets for priority, normal and low inclusion times",
+                parameters: vec![],
+                request: None,
+                response: c.reference::<GetFeeEstimateResponse>(),
+            },
+        ),
+        (
+            "/v1/mempool/{transactionId}",
+            "get",
+            Operation {
+                id: "getMempoolEntry",
+                summary: "A mempool transaction by id",
+                parameters: vec![
+                    path_parameter("transactionId", "Transaction id", hash()),
+                    query_parameter("includeOrphanPool", "Also look up the orphan pool", boolean()),
+                    query_parameter("filterTransactionPool", "Skip the transaction pool", boolean()),
+                ],
+                request: None,
+                response: c.reference::<GetMempoolEntryResponse>(),
+            },
+        ),
+        (
+            "/v1/transactions",
+            "post",
+            Operation {
+                id: "submitTransaction",
+                summary: "Submit a signed transaction to the mempool",
+                parameters: vec![],
+                request: Some(c.reference::<SubmitTransactionRequest>()),
+                response: c.reference::<SubmitTransactionResponse>(),
+            },
+        ),
+    ];
+
+    let mut paths = serde_json::Map::new();
+    for (path, method, operation) in operations {
+        let item = paths.entry(path).or_insert_with(|| json!({}));
+        item[method] = operation.into_value(&error);
+    }
+    paths.insert("/v1/events".to_string(), json!({ "get": events_operation(&error) }));
+    paths.insert(
+        "/v1/openapi.json".to_string(),
+        json!({ "get": {
+            "operationId": "getOpenApi",
+            "summary": "This document",
+            "responses": { "200": { "description": "OpenAPI document", "content": { "application/json": { "schema": { "type": "object" } } } } },
+        } }),
+    );
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "Kaspa REST API",
+            "description": "HTTP/JSON gateway for the Kaspa node RPC API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": { "schemas": c.into_value() },
+    })
+}
+
+fn events_operation(error: &Value) -> Value {
+    json!({
+        "operationId": "subscribeEvents",
+        "summary": "Stream node notifications as server-sent events",
+        "description": "Each SSE event is named after the notification (e.g. `BlockAdded`) and carries its JSON payload as data.",
+        "parameters": [
+            query_parameter(
+                "events",
+                "Comma-separated event types",
+                json!({ "type": "string", "example": "block-added,virtual-daa-score-changed" }),
+            ),
+            query_parameter(
+                "addresses",
+                "Comma-separated addresses, required by `utxos-changed`",
+                json!({ "type": "string" }),
+            ),
+            query_parameter(
+                "includeAcceptedTransactionIds",
+                "Include accepted transaction ids in `virtual-chain-changed`",
+                boolean(),
+            ),
+        ],
+        "responses": {
+            "200": { "description": "Event stream", "content": { "text/event-stream": { "schema": { "type": "string" } } } },
+            "default": { "description": "Error", "content": { "application/json": { "schema": error } } },
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_addresses::{Address, Prefix, Version};
+    use kaspa_consensus_core::{
+        BlueWorkType,
+        network::{NetworkId, NetworkType},
+        subnets::SUBNETWORK_ID_NATIVE,
+        tx::ScriptPublicKey,
+    };
+    use kaspa_hashes::Hash;
+    use kaspa_rpc_core::RpcScriptClass;
+    use serde::Serialize;
+
+    /// Minimal structural validator covering the schema subset emitted by this module
+    fn validate(components: &Components, schema: &Value, value: &Value, path: &str) {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = reference.trim_start_matches("#/components/schemas/");
+            let schema = components.get(name).unwrap_or_else(|| panic!("{path}: unregistered component {name}"));
+            return validate(components, schema, value, path);
+        }
+        if value.is_null() {
+            assert_eq!(schema.get("nullable"), Some(&Value::Bool(true)), "{path}: null is not allowed");
+            return;
+        }
+        if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+            return all_of.iter().for_each(|schema| validate(components, schema, value, path));
+        }
+        match schema["type"].as_str().unwrap_or_else(|| panic!("{path}: schema without type")) {
+            "object" => {
+                let object = value.as_object().unwrap_or_else(|| panic!("{path}: expected an object, got {value}"));
+                let properties = schema["properties"].as_object().unwrap();
+                for (key, value) in object {
+                    let schema = properties.get(key).unwrap_or_else(|| panic!("{path}: undocumented property `{key}`"));
+                    validate(components, schema, value, &format!("{path}.{key}"));
+                }
+                for required in schema["required"].as_array().unwrap() {
+                    assert!(object.contains_key(required.as_str().unwrap()), "{path}: missing required property {required}");
+                }
+            }
+            "array" => {
+                for (i, item) in value.as_array().unwrap_or_else(|| panic!("{path}: expected an array")).iter().enumerate() {
+                    validate(components, &schema["items"], item, &format!("{path}[{i}]"));
+                }
+            }
+            "string" => {
+                let string = value.as_str().unwrap_or_else(|| panic!("{path}: expected a string, got {value}"));
+                if let Some(variants) = schema.get("enum").and_then(Value::as_array) {
+                    assert!(variants.iter().any(|variant| variant == string), "{path}: `{string}` is not a listed variant");
+                }
+                if schema.get("format") == Some(&json!("hex")) {
+                    assert!(string.len() % 2 == 0 && string.chars().all(|c| c.is_ascii_hexdigit()), "{path}: `{string}` is not hex");
+                }
+            }
+            "integer" => assert!(value.is_u64(), "{path}: expected an unsigned integer, got {value}"),
+            "number" => assert!(value.is_number(), "{path}: expected a number, got {value}"),
+            "boolean" => assert!(value.is_boolean(), "{path}: expected a boolean, got {value}"),
+            other => panic!("{path}: unsupported schema type {other}"),
+        }
+    }
+
+    fn check<T: ApiSchema + Serialize>(value: &T) {
+        let mut components = Components::default();
+        let schema = components.reference::<T>();
+        validate(&components, &schema, &serde_json::to_value(value).unwrap(), T::NAME);
+    }
+
+    fn address() -> Address {
+        Address::new(Prefix::Mainnet, Version::PubKey, &[7u8; 32])
+    }
+
+    fn transaction(verbose: bool) -> RpcTransaction {
+        let script_public_key = ScriptPublicKey::from_vec(0, vec![0x20; 34]);
+        RpcTransaction {
+            version: 0,
+            inputs: vec![RpcTransactionInput {
+                previous_outpoint: RpcTransactionOutpoint { transaction_id: Hash::from_bytes([1; 32]), index: 2 },
+                signature_script: vec![0xab; 66],
+                sequence: 0,
+                sig_op_count: 1,
+                compute_budget: 0,
+                verbose_data: verbose.then_some(RpcTransactionInputVerboseData {}),
+            }],
+            outputs: vec![RpcTransactionOutput {
+                value: 100_000_000,
+                script_public_key,
+                verbose_data: verbose.then(|| RpcTransactionOutputVerboseData {
+                    script_public_key_type: RpcScriptClass::PubKey,
+                    script_public_key_address: address(),
+                }),
+                covenant: verbose.then(|| RpcCovenantBinding::new(0, Hash::from_bytes([3; 32]))),
+            }],
+            lock_time: 0,
+            subnetwork_id: SUBNETWORK_ID_NATIVE,
+            gas: 0,
+            payload: vec![0xcd; 4],
+            storage_mass: 1000,
+            verbose_data: verbose.then(|| RpcTransactionVerboseData {
+                transaction_id: Hash::from_bytes([4; 32]),
+                hash: Hash::from_bytes([5; 32]),
+                compute_mass: 2000,
+                block_hash: Hash::from_bytes([6; 32]),
+                block_time: 1_700_000_000_000,
+            }),
+        }
+    }
+
+    fn block(verbose: bool) -> RpcBlock {
+        RpcBlock {
+            header: RpcHeader {
+                hash: Hash::from_bytes([8; 32]),
+                version: 1,
+                parents_by_level: vec![vec![Hash::from_bytes([9; 32])]],
+                hash_merkle_root: Hash::from_bytes([10; 32]),
+                accepted_id_merkle_root: Hash::from_bytes([11; 32]),
+                utxo_commitment: Hash::from_bytes([12; 32]),
+                timestamp: 1_700_000_000_000,
+                bits: 0x1e7fffff,
+                nonce: 42,
+                daa_score: 1000,
+                blue_work: BlueWorkType::from_u64(123_456),
+                blue_score: 900,
+                pruning_point: Hash::from_bytes([13; 32]),
+            },
+            transactions: vec![transaction(verbose)],
+            verbose_data: verbose.then(|| RpcBlockVerboseData {
+                hash: Hash::from_bytes([8; 32]),
+                difficulty: 1.5,
+                selected_parent_hash: Hash::from_bytes([9; 32]),
+                transaction_ids: vec![Hash::from_bytes([4; 32])],
+                is_header_only: false,
+                blue_score: 900,
+                children_hashes: vec![],
+                merge_set_blues_hashes: vec![Hash::from_bytes([9; 32])],
+                merge_set_reds_hashes: vec![],
+                is_chain_block: true,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_schemas_match_serialized_models() {
+        for verbose in [false, true] {
+            check(&GetBlockResponse { block: block(verbose) });
+            check(&GetMempoolEntryResponse::new(RpcMempoolEntry::new(5000, transaction(verbose), false)));
+            check(&SubmitTransactionRequest::new(transaction(verbose), false));
+        }
+
+        let outpoint = RpcTransactionOutpoint { transaction_id: Hash::from_bytes([1; 32]), index: 0 };
+        let utxo_entry = |covenant_id| RpcUtxoEntry {
+            amount: 5,
+            script_public_key: ScriptPublicKey::from_vec(0, vec![0x20; 34]),
+            block_daa_score: 10,
+            is_coinbase: false,
+            covenant_id,
+        };
+        check(&GetUtxosByAddressesResponse::new(vec![
+            RpcUtxosByAddressesEntry { address: Some(address()), outpoint, utxo_entry: utxo_entry(None) },
+            RpcUtxosByAddressesEntry { address: None, outpoint, utxo_entry: utxo_entry(Some(Hash::from_bytes([2; 32]))) },
+        ]));
+        check(&GetBalancesByAddressesResponse::new(vec![
+            RpcBalancesByAddressesEntry { address: address(), balance: Some(5) },
+            RpcBalancesByAddressesEntry { address: address(), balance: None },
+        ]));
+        check(&GetBalancesByAddressesRequest::new(vec![address()]));
+        check(&GetUtxosByAddressesRequest::new(vec![address()]));
+        check(&GetBalanceByAddressResponse::new(5));
+        check(&SubmitTransactionResponse::new(Hash::from_bytes([4; 32])));
+
+        let bucket = RpcFeerateBucket { feerate: 1.0, estimated_seconds: 0.5 };
+        check(&GetFeeEstimateResponse {
+            estimate: RpcFeeEstimate { priority_bucket: bucket, normal_buckets: vec![bucket], low_buckets: vec![bucket] },
+        });
+        check(&GetServerInfoResponse {
+            rpc_api_version: 1,
+            rpc_api_revision: 0,
+            server_version: "1.0.0".to_string(),
+            network_id: NetworkId::with_suffix(NetworkType::Testnet, 10),
+            has_utxo_index: true,
+            is_synced: true,
+            virtual_daa_score: 1000,
+        });
+        check(&GetBlockDagInfoResponse::new(
+            NetworkId::new(NetworkType::Mainnet),
+            10,
+            10,
+            vec![Hash::from_bytes([1; 32])],
+            1.0,
+            1_700_000_000_000,
+            vec![Hash::from_bytes([1; 32])],
+            Hash::from_bytes([2; 32]),
+            1000,
+            Hash::from_bytes([1; 32]),
+        ));
+        check(&ErrorResponse { error: "boom".to_string() });
+    }
+
+    #[test]
+    fn test_document_references_resolve() {
+        fn collect_refs<'a>(value: &'a Value, refs: &mut Vec<&'a str>) {
+            match value {
+                Value::Object(map) => {
+                    if let Some(reference) = map.get("$ref").and_then(Value::as_str) {
+                        refs.push(reference);
+                    }
+                    map.values().for_each(|value| collect_refs(value, refs));
+                }
+                Value::Array(items) => items.iter().for_each(|value| collect_refs(value, refs)),
+                _ => {}
+            }
+        }
+
+        let document = document();
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+        let mut refs = Vec::new();
+        collect_refs(&document, &mut refs);
+        assert!(!refs.is_empty());
+        for reference in refs {
+            let name = reference.strip_prefix("#/components/schemas/").unwrap();
+            assert!(schemas.get(name).is_some_and(Value::is_object), "dangling reference {reference}");
+        }
+        for path in ["/v1/blocks/{hash}", "/v1/transactions", "/v1/events", "/v1/addresses/{address}/utxos"] {
+            assert!(document["paths"].get(path).is_some(), "{path} is not documented");
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::{str::FromStr, sync::Arc};
+
+use axum::{
+    Json, Router,
+    extract::{
+        Path, Query, State,
+        rejection::{JsonRejection, QueryRejection},
+    },
+    routing::{get, post},
+};
+use kaspa_rpc_core::{
+    GetBalanceByAddressRequest, GetBalanceByAddressResponse, GetBalancesByAddressesRequest, GetBalancesByAddressesResponse,
+    GetBlockDagInfoRequest, GetBlockDagInfoResponse, GetBlockRequest, GetBlockResponse, GetFeeEstimateRequest, GetFeeEstimateResponse,
+    GetMempoolEntryRequest, GetMempoolEntryResponse, GetServerInfoRequest, GetServerInfoResponse, GetUtxosByAddressesRequest,
+    GetUtxosByAddressesResponse, RpcAddress, RpcHash, SubmitTransactionRequest, SubmitTransactionResponse,
+};
+use serde::Deserialize;
+
+use crate::{error::Error, events, result::Result, server::ServerContext};
+
+pub(crate) type Context = State<Arc<ServerContext>>;
+
+pub fn router(context: Arc<ServerContext>) -> Router {
+    Router::new()
+        .route("/v1/openapi.json", get(get_openapi))
+        .route("/v1/info", get(get_info))
+        .route("/v1/blockdag", get(get_block_dag_info))
+        .route("/v1/blocks/{hash}", get(get_block))
+        .route("/v1/addresses/{address}/balance", get(get_balance_by_address))
+        .route("/v1/addresses/{address}/utxos", get(get_utxos_by_address))
+        .route("/v1/addresses/balances", post(get_balances_by_addresses))
+        .route("/v1/addresses/utxos", post(get_utxos_by_addresses))
+        .route("/v1/fee-estimate", get(get_fee_estimate))
+        .route("/v1/mempool/{transaction_id}", get(get_mempool_entry))
+        .route("/v1/transactions", post(submit_transaction))
+        .route("/v1/events", get(events::subscribe))
+        .with_state(context)
+}
+
+/// Unwraps an extractor result, reporting rejections as JSON [`Error::BadRequest`] bodies like every other error
+pub(crate) fn extracted<T>(extracted: std::result::Result<T, impl std::fmt::Display>) -> Result<T> {
+    extracted.map_err(|rejection| Error::BadRequest(rejection.to_string()))
+}
+
+pub(crate) fn parse_hash(kind: &str, value: &str) -> Result<RpcHash> {
+    RpcHash::from_str(value).map_err(|_| Error::BadRequest(format!("invalid {kind} `{value}`")))
+}
+
+pub(crate) fn parse_address(value: &str) -> Result<RpcAddress> {
+    RpcAddress::try_from(value).map_err(|err| Error::BadRequest(format!("invalid address `{value}`: {err}")))
+}
+
+async fn get_openapi(State(context): Context) -> Json<serde_json::Value> {
+    Json(context.openapi.clone())
+}
+
+async fn get_info(State(context): Context) -> Result<Json<GetServerInfoResponse>> {
+    Ok(Json(context.rpc.get_server_info_call(None, GetServerInfoRequest {}).await?))
+}
+
+async fn get_block_dag_info(State(context): Context) -> Result<Json<GetBlockDagInfoResponse>> {
+    Ok(Json(context.rpc.get_block_dag_info_call(None, GetBlockDagInfoRequest {}).await?))
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BlockQuery {
+    pub include_transactions: bool,
+}
+
+async fn get_block(
+    State(context): Context,
+    Path(hash): Path<String>,
+    query: std::result::Result<Query<BlockQuery>, QueryRejection>,
+) -> Result<Json<GetBlockResponse>> {
+    let hash = parse_hash("block hash", &hash)?;
+    let Query(query) = extracted(query)?;
+    Ok(Json(context.rpc.get_block_call(None, GetBlockRequest::new(hash, query.include_transactions)).await?))
+}
+
+async fn get_balance_by_address(State(context): Context, Path(address): Path<String>) -> Result<Json<GetBalanceByAddressResponse>> {
+    let address = parse_address(&address)?;
+    Ok(Json(context.rpc.get_balance_by_address_call(None, GetBalanceByAddressRequest::new(address)).await?))
+}
+
+async fn get_utxos_by_address(State(context): Context, Path(address): Path<String>) -> Result<Json<GetUtxosByAddressesResponse>> {
+    let address = parse_address(&address)?;
+    Ok(Json(context.rpc.get_utxos_by_addresses_call(None, GetUtxosByAddressesRequest::new(vec![address])).await?))
+}
+
+async fn get_balances_by_addresses(
+    State(context): Context,
+    request: std::result::Result<Json<GetBalancesByAddressesRequest>, JsonRejection>,
+) -> Result<Json<GetBalancesByAddressesResponse>> {
+    let Json(request) = extracted(request)?;
+    Ok(Json(context.rpc.get_balances_by_addresses_call(None, request).await?))
+}
+
+async fn get_utxos_by_addresses(
+    State(context): Context,
+    request: std::result::Result<Json<GetUtxosByAddressesRequest>, JsonRejection>,
+) -> Result<Json<GetUtxosByAddressesResponse>> {
+    let Json(request) = extracted(request)?;
+    Ok(Json(context.rpc.get_utxos_by_addresses_call(None, request).await?))
+}
+
+async fn get_fee_estimate(State(context): Context) -> Result<Json<GetFeeEstimateResponse>> {
+    Ok(Json(context.rpc.get_fee_estimate_call(None, GetFeeEstimateRequest {}).await?))
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MempoolEntryQuery {
+    pub include_orphan_pool: bool,
+    pub filter_transaction_pool: bool,
+}
+
+async fn get_mempool_entry(
+    State(context): Context,
+    Path(transaction_id): Path<String>,
+    query: std::result::Result<Query<MempoolEntryQuery>, QueryRejection>,
+) -> Result<Json<GetMempoolEntryResponse>> {
+    let transaction_id = parse_hash("transaction id", &transaction_id)?;
+    let Query(query) = extracted(query)?;
+    let request = GetMempoolEntryRequest::new(transaction_id, query.include_orphan_pool, query.filter_transaction_pool);
+    Ok(Json(context.rpc.get_mempool_entry_call(None, request).await?))
+}
+
+async fn submit_transaction(
+    State(context): Context,
+    request: std::result::Result<Json<SubmitTransactionRequest>, JsonRejection>,
+) -> Result<Json<SubmitTransactionResponse>> {
+    let Json(request) = extracted(request)?;
+    Ok(Json(context.rpc.submit_transaction_call(None, request).await?))
+}
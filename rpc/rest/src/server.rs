@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    http::{HeaderValue, Method, header},
+};
+use kaspa_core::info;
+use kaspa_rpc_core::api::rpc::DynRpcService;
+use tokio::net::TcpListener;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::{error::Error, openapi, result::Result, routes};
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// `interface:port` to bind the HTTP listener to
+    pub listen_address: String,
+    /// Origins allowed to issue cross-origin requests; `*` allows any origin, empty disables CORS
+    pub cors_origins: Vec<String>,
+}
+
+/// State shared by all request handlers
+pub struct ServerContext {
+    pub rpc: DynRpcService,
+    /// The OpenAPI document is static, so it is rendered once at startup
+    pub openapi: serde_json::Value,
+}
+
+pub struct RestServer {
+    context: Arc<ServerContext>,
+    options: Options,
+}
+
+impl RestServer {
+    pub fn new(rpc: DynRpcService, options: Options) -> Self {
+        Self { context: Arc::new(ServerContext { rpc, openapi: openapi::document() }), options }
+    }
+
+    pub fn router(&self) -> Result<Router> {
+        let router = routes::router(self.context.clone());
+        Ok(match self.cors_layer()? {
+            Some(cors) => router.layer(cors),
+            None => router,
+        })
+    }
+
+    fn cors_layer(&self) -> Result<Option<CorsLayer>> {
+        let origins = &self.options.cors_origins;
+        if origins.is_empty() {
+            return Ok(None);
+        }
+        let allow_origin = if origins.iter().any(|origin| origin == "*") {
+            AllowOrigin::any()
+        } else {
+            let origins = origins
+                .iter()
+                .map(|origin| HeaderValue::from_str(origin).map_err(|_| Error::Other(format!("invalid CORS origin `{origin}`"))))
+                .collect::<Result<Vec<_>>>()?;
+            AllowOrigin::list(origins)
+        };
+        Ok(Some(
+            CorsLayer::new()
+                .allow_origin(allow_origin)
+                .allow_methods([Method::GET, Method::POST])
+                .allow_headers([header::CONTENT_TYPE]),
+        ))
+    }
+
+    /// Binds the listener and serves requests until the listener fails
+    pub async fn listen(&self) -> Result<()> {
+        let router = self.router()?;
+        let listener = TcpListener::bind(&self.options.listen_address).await?;
+        info!("Kaspa REST server is listening on http://{}", listener.local_addr()?);
+        axum::serve(listener, router).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use kaspa_consensus_core::mass::BlockMassLimits;
+use kaspa_mining::{
+    BlockTemplateDiagnostics, CandidateTransactionDiagnostics, DimensionMasses, FrontierSelectorKind, LaneUsageDiagnostics,
+    MassDimension, SelectionOutcome,
+};
+use kaspa_rpc_core::{
+    GetBlockTemplateDiagnosticsResponse, RpcBlockMasses, RpcFrontierSelectorKind, RpcMassDimension, RpcTemplateCandidate,
+    RpcTemplateLaneUsage, RpcTemplateSelectionOutcome,
+};
+
+fn masses_into_rpc(masses: DimensionMasses) -> RpcBlockMasses {
+    RpcBlockMasses { compute: masses.compute, transient: masses.transient, storage: masses.storage }
+}
+
+fn limits_into_rpc(limits: BlockMassLimits) -> RpcBlockMasses {
+    RpcBlockMasses { compute: limits.compute, transient: limits.transient, storage: limits.storage }
+}
+
+fn selector_kind_into_rpc(kind: FrontierSelectorKind) -> RpcFrontierSelectorKind {
+    match kind {
+        FrontierSelectorKind::TakeAll => RpcFrontierSelectorKind::TakeAll,
+        FrontierSelectorKind::Sequence => RpcFrontierSelectorKind::Sequence,
+        FrontierSelectorKind::MutatingTree => RpcFrontierSelectorKind::MutatingTree,
+    }
+}
+
+fn dimension_into_rpc(dimension: MassDimension) -> RpcMassDimension {
+    match dimension {
+        MassDimension::Compute => RpcMassDimension::Compute,
+        MassDimension::Transient => RpcMassDimension::Transient,
+        MassDimension::Storage => RpcMassDimension::Storage,
+    }
+}
+
+/// Splits the validation error off the outcome, since RPC outcomes are plain enums
+fn outcome_into_rpc(outcome: SelectionOutcome) -> (RpcTemplateSelectionOutcome, Option<String>) {
+    match outcome {
+        SelectionOutcome::Selected => (RpcTemplateSelectionOutcome::Selected, None),
+        SelectionOutcome::LanesPerBlockLimit => (RpcTemplateSelectionOutcome::LanesPerBlockLimit, None),
+        SelectionOutcome::GasPerLaneLimit => (RpcTemplateSelectionOutcome::GasPerLaneLimit, None),
+        SelectionOutcome::BlockMassLimit => (RpcTemplateSelectionOutcome::BlockMassLimit, None),
+        SelectionOutcome::Excluded => (RpcTemplateSelectionOutcome::Excluded, None),
+        SelectionOutcome::Invalid(error) => (RpcTemplateSelectionOutcome::Invalid, Some(error)),
+    }
+}
+
+pub trait TemplateCandidateConverter {
+    fn into_rpc(self) -> RpcTemplateCandidate;
+}
+
+impl TemplateCandidateConverter for CandidateTransactionDiagnostics {
+    fn into_rpc(self) -> RpcTemplateCandidate {
+        let (outcome, validation_error) = outcome_into_rpc(self.outcome);
+        RpcTemplateCandidate {
+            transaction_id: self.transaction_id,
+            outcome,
+            validation_error,
+            fee: self.fee,
+            mass: self.mass,
+            masses: masses_into_rpc(self.masses),
+            binding_dimension: dimension_into_rpc(self.binding_dimension),
+            subnetwork_id: self.subnetwork_id,
+            gas: self.gas,
+        }
+    }
+}
+
+pub trait TemplateLaneUsageConverter {
+    fn into_rpc(self) -> RpcTemplateLaneUsage;
+}
+
+impl TemplateLaneUsageConverter for LaneUsageDiagnostics {
+    fn into_rpc(self) -> RpcTemplateLaneUsage {
+        RpcTemplateLaneUsage { subnetwork_id: self.subnetwork_id, transaction_count: self.transaction_count as u64, gas: self.gas }
+    }
+}
+
+pub trait BlockTemplateDiagnosticsConverter {
+    fn into_rpc(self) -> GetBlockTemplateDiagnosticsResponse;
+}
+
+impl BlockTemplateDiagnosticsConverter for BlockTemplateDiagnostics {
+    fn into_rpc(self) -> GetBlockTemplateDiagnosticsResponse {
+        GetBlockTemplateDiagnosticsResponse {
+            selector_kind: selector_kind_into_rpc(self.selector_kind),
+            ready_transaction_count: self.ready_transaction_count as u64,
+            selected_transaction_count: self.selected_transaction_count as u64,
+            total_fees: self.total_fees,
+            mass_used: masses_into_rpc(self.mass_used),
+            mass_limits: limits_into_rpc(self.mass_limits),
+            normalized_mass_used: self.normalized_mass_used,
+            normalized_mass_limit: self.normalized_mass_limit,
+            lanes: self.lanes.into_iter().map(TemplateLaneUsageConverter::into_rpc).collect(),
+            lanes_per_block_limit: self.lane_limits.lanes_per_block as u64,
+            gas_per_lane_limit: self.lane_limits.gas_per_lane,
+            candidates: self.candidates.into_iter().map(TemplateCandidateConverter::into_rpc).collect(),
+        }
+    }
+}
@@ -21,13 +21,13 @@ use kaspa_math::Uint256;
 use kaspa_mining::model::{TransactionIdSet, owner_txs::OwnerTransactions};
 use kaspa_notify::converter::Converter;
 use kaspa_rpc_core::{
-    BlockAddedNotification, Notification, RpcAcceptanceDataVerbosity, RpcAcceptedTransactionIds, RpcBlock, RpcBlockVerboseData,
-    RpcChainBlockAcceptedTransactions, RpcError, RpcHash, RpcHeaderVerbosity, RpcMempoolEntry, RpcMempoolEntryByAddress,
-    RpcMergesetBlockAcceptanceDataVerbosity, RpcOptionalHeader, RpcOptionalTransaction, RpcOptionalTransactionInput,
-    RpcOptionalTransactionInputVerboseData, RpcOptionalTransactionOutput, RpcOptionalTransactionOutputVerboseData,
-    RpcOptionalTransactionVerboseData, RpcOptionalUtxoEntry, RpcOptionalUtxoEntryVerboseData, RpcResult, RpcTransaction,
-    RpcTransactionInput, RpcTransactionInputVerboseDataVerbosity, RpcTransactionInputVerbosity, RpcTransactionOutput,
-    RpcTransactionOutputVerboseData, RpcTransactionOutputVerboseDataVerbosity, RpcTransactionOutputVerbosity,
+    BlockAddedNotification, Notification, RpcAcceptanceDataVerbosity, RpcAcceptedTransaction, RpcAcceptedTransactionIds, RpcBlock,
+    RpcBlockVerboseData, RpcChainBlockAcceptedTransactions, RpcError, RpcHash, RpcHeaderVerbosity, RpcMempoolEntry,
+    RpcMempoolEntryByAddress, RpcMergesetBlockAcceptanceDataVerbosity, RpcOptionalHeader, RpcOptionalTransaction,
+    RpcOptionalTransactionInput, RpcOptionalTransactionInputVerboseData, RpcOptionalTransactionOutput,
+    RpcOptionalTransactionOutputVerboseData, RpcOptionalTransactionVerboseData, RpcOptionalUtxoEntry, RpcOptionalUtxoEntryVerboseData,
+    RpcResult, RpcTransaction, RpcTransactionInput, RpcTransactionInputVerboseDataVerbosity, RpcTransactionInputVerbosity,
+    RpcTransactionOutput, RpcTransactionOutputVerboseData, RpcTransactionOutputVerboseDataVerbosity, RpcTransactionOutputVerbosity,
     RpcTransactionVerboseData, RpcTransactionVerboseDataVerbosity, RpcTransactionVerbosity, RpcUtxoEntryVerboseDataVerbosity,
     RpcUtxoEntryVerbosity,
 };
@@ -605,6 +605,53 @@ impl ConsensusConverter {
         })
     }
 
+    /// Converts the accepted transactions listed in `merged_block_data` along with their fee and masses.
+    /// The spent UTXO entries are always resolved since the fee depends on them.
+    pub async fn get_accepted_transactions_with_fees(
+        &self,
+        consensus: &ConsensusProxy,
+        accepting_block: Hash,
+        merged_block_data: MergesetBlockAcceptanceData,
+        verbosity: &RpcTransactionVerbosity,
+    ) -> RpcResult<Vec<RpcAcceptedTransaction>> {
+        let merged_block_hash = merged_block_data.block_hash;
+        let merged_block_timestamp = consensus.async_get_header(merged_block_hash).await?.timestamp;
+
+        let TransactionQueryResult::SignableTransaction(txs) = consensus
+            .async_get_transactions_by_block_acceptance_data(
+                accepting_block,
+                merged_block_data,
+                None,
+                TransactionType::SignableTransaction,
+            )
+            .await?
+        else {
+            return Err(RpcError::General("expected populated transactions".to_string()));
+        };
+
+        let mut converted = Vec::with_capacity(txs.len());
+        for tx in txs.iter() {
+            let masses = consensus
+                .calculate_transaction_non_contextual_masses(&tx.tx)
+                .map_err(|err: TxRuleError| RpcError::ConsensusError(ConsensusError::GeneralOwned(err.to_string())))?;
+            let fee = accepted_transaction_fee(tx)?;
+            let transaction = self
+                .convert_signable_transaction_with_verbosity(consensus, tx, Some(merged_block_hash), merged_block_timestamp, verbosity)
+                .await?;
+
+            converted.push(RpcAcceptedTransaction {
+                transaction,
+                merged_block_hash,
+                fee,
+                compute_mass: masses.compute_mass,
+                transient_mass: masses.transient_mass,
+                storage_mass: tx.tx.storage_mass(),
+            });
+        }
+
+        Ok(converted)
+    }
+
     async fn get_mergeset_accepted_transactions_with_verbosity(
         &self,
         consensus: &ConsensusProxy,
@@ -692,6 +739,26 @@ impl ConsensusConverter {
     }
 }
 
+/// Returns the fee paid by an accepted transaction, or `None` for coinbase transactions and when some spent entry is unknown
+fn accepted_transaction_fee(tx: &SignableTransaction) -> RpcResult<Option<u64>> {
+    if tx.tx.is_coinbase() {
+        return Ok(None);
+    }
+    let Some(input_amount) = tx.entries.iter().map(|entry| entry.as_ref().map(|entry| entry.amount)).sum::<Option<u64>>() else {
+        return Ok(None);
+    };
+    let output_amount = tx.tx.outputs.iter().map(|output| output.value).sum::<u64>();
+    match input_amount.checked_sub(output_amount) {
+        Some(fee) => Ok(Some(fee)),
+        None => Err(RpcError::General(format!(
+            "accepted transaction {} spends {} sompi but outputs {} sompi",
+            tx.tx.id(),
+            input_amount,
+            output_amount
+        ))),
+    }
+}
+
 #[async_trait]
 impl Converter for ConsensusConverter {
     type Incoming = ConsensusNotification;
@@ -715,3 +782,100 @@ impl Debug for ConsensusConverter {
         f.debug_struct("ConsensusConverter").field("consensus_manager", &"").field("config", &self.config).finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        api::ConsensusApi,
+        config::params::SIMNET_PARAMS,
+        errors::tx::TxResult,
+        mass::NonContextualMasses,
+        subnets::{SUBNETWORK_ID_COINBASE, SUBNETWORK_ID_NATIVE},
+        tx::{ScriptPublicKey, TransactionOutpoint},
+    };
+    use kaspa_consensusmanager::ConsensusCtl;
+    use kaspa_rpc_core::RpcDataVerbosityLevel;
+    use std::thread::JoinHandle;
+
+    struct MassConsensus;
+
+    impl ConsensusApi for MassConsensus {
+        fn calculate_transaction_non_contextual_masses(&self, _transaction: &Transaction) -> TxResult<NonContextualMasses> {
+            Ok(NonContextualMasses::new(2000, 0))
+        }
+    }
+
+    impl ConsensusCtl for MassConsensus {
+        fn start(&self) -> Vec<JoinHandle<()>> {
+            vec![]
+        }
+
+        fn stop(&self) {}
+
+        fn make_active(&self) {}
+    }
+
+    fn signable_transaction(input_amounts: &[Option<u64>], output_amounts: &[u64]) -> SignableTransaction {
+        // Pay-to-pubkey script so that addresses can be extracted
+        let script_public_key = ScriptPublicKey::from_vec(0, [vec![0x20], vec![1; 32], vec![0xac]].concat());
+        let inputs = (0..input_amounts.len())
+            .map(|i| TransactionInput::new(TransactionOutpoint::new(Hash::from_u64_word(i as u64 + 1), 0), vec![], 0, 1))
+            .collect();
+        let outputs = output_amounts.iter().map(|&value| TransactionOutput::new(value, script_public_key.clone())).collect();
+        let tx = Transaction::new(0, inputs, outputs, 0, SUBNETWORK_ID_NATIVE, 0, vec![]);
+        let entries = input_amounts
+            .iter()
+            .map(|amount| amount.map(|amount| UtxoEntry::new(amount, script_public_key.clone(), 10, false, None)))
+            .collect();
+        MutableTransaction { tx, entries, calculated_fee: None, calculated_non_contextual_masses: None }
+    }
+
+    #[test]
+    fn test_accepted_transaction_fee() {
+        assert_eq!(accepted_transaction_fee(&signable_transaction(&[Some(700), Some(400)], &[1000])).unwrap(), Some(100));
+        assert_eq!(accepted_transaction_fee(&signable_transaction(&[Some(1000)], &[1000])).unwrap(), Some(0));
+        // Unknown spent entries leave the fee unknown
+        assert_eq!(accepted_transaction_fee(&signable_transaction(&[Some(1000), None], &[500])).unwrap(), None);
+        // Outputs exceeding inputs betray inconsistent data rather than a zero fee
+        assert!(accepted_transaction_fee(&signable_transaction(&[Some(1000)], &[1001])).is_err());
+
+        let coinbase = MutableTransaction::new(Transaction::new(0, vec![], vec![], 0, SUBNETWORK_ID_COINBASE, 0, vec![]));
+        assert_eq!(accepted_transaction_fee(&coinbase).unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_accepted_transaction_verbosity() {
+        let consensus_manager = Arc::new(ConsensusManager::from_consensus(Arc::new(MassConsensus)));
+        let converter = ConsensusConverter::new(consensus_manager.clone(), Arc::new(Config::new(SIMNET_PARAMS)));
+        let session = consensus_manager.consensus().session().await;
+        let tx = signable_transaction(&[Some(700), Some(400)], &[1000]);
+        let convert = |level| {
+            let verbosity = RpcTransactionVerbosity::from(level);
+            let (converter, session, tx) = (&converter, &session, &tx);
+            async move {
+                converter.convert_signable_transaction_with_verbosity(session, tx, Some(Hash::from_u64_word(9)), 0, &verbosity).await
+            }
+        };
+
+        let converted = convert(RpcDataVerbosityLevel::None).await.unwrap();
+        assert!(converted.version.is_none() && converted.lock_time.is_none());
+        assert!(converted.inputs.iter().all(|input| input.signature_script.is_none() && input.previous_outpoint.is_none()));
+        assert!(converted.outputs[0].value.is_none());
+        assert!(converted.verbose_data.unwrap().transaction_id.is_none());
+
+        let converted = convert(RpcDataVerbosityLevel::Low).await.unwrap();
+        assert!(converted.version.is_none() && converted.lock_time.is_none());
+        assert!(converted.inputs.iter().all(|input| input.signature_script.is_some() && input.previous_outpoint.is_none()));
+        assert_eq!(converted.outputs[0].value, Some(1000));
+        let verbose_data = converted.verbose_data.unwrap();
+        assert_eq!(verbose_data.transaction_id, Some(tx.tx.id()));
+        assert_eq!(verbose_data.compute_mass, None);
+
+        let converted = convert(RpcDataVerbosityLevel::Full).await.unwrap();
+        assert_eq!((converted.version, converted.lock_time), (Some(0), Some(0)));
+        let utxo_entry = converted.inputs[1].verbose_data.as_ref().unwrap().utxo_entry.as_ref().unwrap();
+        assert_eq!(utxo_entry.amount, Some(400));
+        assert_eq!(converted.verbose_data.unwrap().compute_mass, Some(2000));
+    }
+}
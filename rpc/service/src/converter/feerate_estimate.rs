@@ -1,6 +1,6 @@
-use kaspa_mining::feerate::{FeeEstimateVerbose, FeerateBucket, FeerateEstimations};
+use kaspa_mining::feerate::{FeeEstimateVerbose, FeerateBucket, FeerateEstimations, FeerateTargetEstimation};
 use kaspa_rpc_core::{
-    RpcFeeEstimate, RpcFeeEstimateVerboseExperimentalData as RpcFeeEstimateVerbose, RpcFeerateBucket,
+    RpcFeeEstimate, RpcFeeEstimateVerboseExperimentalData as RpcFeeEstimateVerbose, RpcFeerateBucket, RpcFeerateTargetEstimate,
     message::GetFeeEstimateExperimentalResponse as RpcFeeEstimateVerboseResponse,
 };
 
@@ -44,6 +44,23 @@ impl FeeEstimateVerboseConverter for FeeEstimateVerbose {
                 next_block_template_feerate_median: self.next_block_template_feerate_median,
                 next_block_template_feerate_max: self.next_block_template_feerate_max,
             }),
+            target_estimates: vec![],
+        }
+    }
+}
+
+pub trait FeerateTargetEstimationConverter {
+    fn into_rpc(self) -> RpcFeerateTargetEstimate;
+}
+
+impl FeerateTargetEstimationConverter for FeerateTargetEstimation {
+    fn into_rpc(self) -> RpcFeerateTargetEstimate {
+        RpcFeerateTargetEstimate {
+            target_seconds: self.target.seconds,
+            confidence: self.target.confidence,
+            feerate: self.feerate,
+            from_history: self.from_history,
+            sample_count: self.sample_count,
         }
     }
 }
@@ -1,8 +1,8 @@
 use std::sync::Arc;
 
 use kaspa_p2p_flows::flow_context::FlowContext;
-use kaspa_p2p_lib::{Peer, PeerKey};
-use kaspa_rpc_core::RpcPeerInfo;
+use kaspa_p2p_lib::{Peer, PeerKey, PeerStats};
+use kaspa_rpc_core::{RpcPeerInfo, RpcPeerMessageStats, RpcPeerStats};
 
 pub struct ProtocolConverter {
     flow_context: Arc<FlowContext>,
@@ -25,6 +25,29 @@ impl ProtocolConverter {
             user_agent: properties.user_agent.clone(),
             advertised_protocol_version: properties.advertised_protocol_version,
             time_connected: peer.time_connected(),
+            stats: Some(Self::get_peer_stats(peer.stats())),
+        }
+    }
+
+    fn get_peer_stats(stats: &PeerStats) -> RpcPeerStats {
+        RpcPeerStats {
+            messages: stats
+                .by_message_type
+                .iter()
+                .map(|(msg_type, counters)| RpcPeerMessageStats {
+                    message_type: format!("{msg_type:?}"),
+                    messages_in: counters.messages_in,
+                    bytes_in: counters.bytes_in,
+                    messages_out: counters.messages_out,
+                    bytes_out: counters.bytes_out,
+                })
+                .collect(),
+            invalid_blocks: stats.invalid_blocks,
+            invalid_transactions: stats.invalid_transactions,
+            dropped_messages: stats.dropped_messages,
+            relay_latency_millis: stats.relay_latency.map(|latency| latency.as_millis() as u64),
+            outgoing_queue_len: stats.outgoing_queue_len as u32,
+            outgoing_queue_capacity: stats.outgoing_queue_capacity as u32,
         }
     }
 
@@ -1,7 +1,8 @@
 //! Core server implementation for ClientAPI
 
 use super::collector::{CollectorFromConsensus, CollectorFromIndex};
-use crate::converter::feerate_estimate::{FeeEstimateConverter, FeeEstimateVerboseConverter};
+use crate::converter::block_template::BlockTemplateDiagnosticsConverter;
+use crate::converter::feerate_estimate::{FeeEstimateConverter, FeeEstimateVerboseConverter, FeerateTargetEstimationConverter};
 use crate::converter::{consensus::ConsensusConverter, index::IndexConverter, protocol::ProtocolConverter};
 use async_trait::async_trait;
 use kaspa_consensus_core::api::counters::ProcessingCounters;
@@ -10,10 +11,12 @@ use kaspa_consensus_core::errors::block::RuleError;
 use kaspa_consensus_core::tx::{TransactionQueryResult, TransactionType};
 use kaspa_consensus_core::utxo::utxo_inquirer::UtxoInquirerError;
 use kaspa_consensus_core::{
+    acceptance_data::MergesetBlockAcceptanceData,
     block::Block,
     coinbase::MinerData,
     config::Config,
     constants::MAX_SOMPI,
+    header::Header,
     network::NetworkType,
     tx::{COINBASE_TRANSACTION_INDEX, Transaction},
 };
@@ -37,9 +40,9 @@ use kaspa_index_core::{
     connection::IndexChannelConnection, indexed_utxos::UtxoSetByScriptPublicKey, notification::Notification as IndexNotification,
     notifier::IndexNotifier,
 };
-use kaspa_mining::feerate::FeeEstimateVerbose;
+use kaspa_mining::feerate::{FeeEstimateVerbose, FeerateTarget};
 use kaspa_mining::model::tx_query::TransactionQuery;
-use kaspa_mining::{manager::MiningManagerProxy, mempool::tx::Orphan};
+use kaspa_mining::{BlockTemplateCustomization, manager::MiningManagerProxy, mempool::tx::Orphan};
 use kaspa_notify::listener::ListenerLifespan;
 use kaspa_notify::subscription::context::SubscriptionContext;
 use kaspa_notify::subscription::{MutationPolicies, UtxosChangedMutationPolicy};
@@ -61,7 +64,10 @@ use kaspa_rpc_core::{
     api::{
         connection::DynRpcConnection,
         ops::{RPC_API_REVISION, RPC_API_VERSION},
-        rpc::{MAX_SAFE_WINDOW_SIZE, RpcApi},
+        rpc::{
+            DEFAULT_ACCEPTED_TRANSACTIONS_LIMIT, MAX_ACCEPTED_TRANSACTIONS_LIMIT, MAX_ADDRESS_HISTORY_LIMIT, MAX_BALANCE_AT_ADDRESSES,
+            MAX_SAFE_WINDOW_SIZE, MAX_TEMPLATE_CUSTOMIZATION_TRANSACTIONS, RpcApi,
+        },
     },
     model::*,
     notify::connection::ChannelConnection,
@@ -69,13 +75,18 @@ use kaspa_rpc_core::{
 use kaspa_system_info::SystemInfo;
 use kaspa_txscript::{extract_script_pub_key_address, pay_to_address_script};
 use kaspa_utils::expiring_cache::ExpiringCache;
+use kaspa_utils::hex::{FromHex, ToHex};
 use kaspa_utils::{channel::Channel, triggers::SingleTrigger};
 use kaspa_utils_tower::counters::TowerConnectionCounters;
-use kaspa_utxoindex::api::UtxoIndexProxy;
+use kaspa_utxoindex::{
+    api::UtxoIndexProxy,
+    model::{AddressHistoryCursor, AddressHistoryEntryKind},
+};
 use std::time::Duration;
 use std::{
     collections::HashMap,
     iter::once,
+    ops::Range,
     sync::{Arc, atomic::Ordering},
     vec,
 };
@@ -126,6 +137,15 @@ pub struct RpcCoreService {
 
 const RPC_CORE: &str = "rpc-core";
 
+/// Maximum number of chain blocks scanned by a single `GetAcceptedTransactions` request
+const MAX_ACCEPTED_TRANSACTIONS_CHAIN_BLOCKS: usize = 1_000;
+
+/// Maximum number of inclusion time targets a single `GetFeeEstimateExperimental` request may ask for
+const MAX_FEERATE_TARGETS: usize = 32;
+
+/// Upper bound on the number of blocks a single `GenerateBlocks` call may produce
+const MAX_GENERATED_BLOCKS: u32 = 1_000;
+
 impl RpcCoreService {
     pub const IDENT: &'static str = "rpc-core-service";
 
@@ -285,6 +305,24 @@ impl RpcCoreService {
             (false, false) => Ok(TransactionQuery::TransactionsOnly),
         }
     }
+
+    /// Bounds the transaction lists of a block template request, each id costing a mempool lookup while building the template
+    fn template_customization(
+        priority_transaction_ids: Vec<RpcTransactionId>,
+        excluded_transaction_ids: Vec<RpcTransactionId>,
+        reserved_mass: u64,
+    ) -> RpcResult<BlockTemplateCustomization> {
+        for (ids, kind) in [(&priority_transaction_ids, "priority"), (&excluded_transaction_ids, "excluded")] {
+            if ids.len() > MAX_TEMPLATE_CUSTOMIZATION_TRANSACTIONS {
+                return Err(RpcError::TemplateTransactionsExceedingMaximum(
+                    ids.len(),
+                    kind.to_string(),
+                    MAX_TEMPLATE_CUSTOMIZATION_TRANSACTIONS,
+                ));
+            }
+        }
+        Ok(BlockTemplateCustomization::new(priority_transaction_ids, excluded_transaction_ids, reserved_mass))
+    }
 }
 
 #[async_trait]
@@ -382,7 +420,9 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         let script_public_key = kaspa_txscript::pay_to_address_script(&request.pay_address);
         let extra_data = version().as_bytes().iter().chain(once(&(b'/'))).chain(&request.extra_data).cloned().collect::<Vec<_>>();
         let miner_data: MinerData = MinerData::new(script_public_key, extra_data);
-        let block_template = self.mining_manager.clone().get_block_template(&session, miner_data).await?;
+        let customization =
+            Self::template_customization(request.priority_transaction_ids, request.excluded_transaction_ids, request.reserved_mass)?;
+        let block_template = self.mining_manager.clone().get_customized_block_template(&session, miner_data, customization).await?;
 
         // Check coinbase tx payload length
         if block_template.block.transactions[COINBASE_TRANSACTION_INDEX].payload.len() > self.config.max_coinbase_payload_len {
@@ -398,6 +438,34 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         })
     }
 
+    async fn get_block_template_diagnostics_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetBlockTemplateDiagnosticsRequest,
+    ) -> RpcResult<GetBlockTemplateDiagnosticsResponse> {
+        trace!("incoming GetBlockTemplateDiagnostics request");
+
+        // The dry-run builds a template as get_block_template does, so the same restrictions apply
+        if *self.config.net == NetworkType::Mainnet && !self.config.enable_mainnet_mining {
+            return Err(RpcError::General("Mining on mainnet is not supported for initial Rust versions".to_owned()));
+        }
+        if request.pay_address.prefix != self.config.prefix() {
+            return Err(kaspa_addresses::AddressError::InvalidPrefix(request.pay_address.prefix.to_string()))?;
+        }
+
+        let session = self.consensus_manager.consensus().unguarded_session();
+        if session.async_is_consensus_in_transitional_ibd_state().await {
+            return Err(RpcError::ConsensusInTransitionalIbdState);
+        }
+        let script_public_key = kaspa_txscript::pay_to_address_script(&request.pay_address);
+        let extra_data = version().as_bytes().iter().chain(once(&(b'/'))).chain(&request.extra_data).cloned().collect::<Vec<_>>();
+        let miner_data: MinerData = MinerData::new(script_public_key, extra_data);
+        let customization =
+            Self::template_customization(request.priority_transaction_ids, request.excluded_transaction_ids, request.reserved_mass)?;
+        let diagnostics = self.mining_manager.clone().get_block_template_diagnostics(&session, miner_data, customization).await?;
+        Ok(diagnostics.into_rpc())
+    }
+
     async fn get_current_block_color_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -669,7 +737,7 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         };
         self.flow_context.submit_rpc_transaction(&session, transaction, orphan).await.map_err(|err| {
             let err = RpcError::RejectedTransaction(transaction_id, err.to_string());
-            debug!("{err}");
+            debug!(tx_id:% = transaction_id; "{err}");
             err
         })?;
         Ok(SubmitTransactionResponse::new(transaction_id))
@@ -949,23 +1017,50 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         connection: Option<&DynRpcConnection>,
         request: GetFeeEstimateExperimentalRequest,
     ) -> RpcResult<GetFeeEstimateExperimentalResponse> {
-        if request.verbose {
+        if request.targets.len() > MAX_FEERATE_TARGETS {
+            return Err(RpcError::General(format!("Cannot estimate more than {MAX_FEERATE_TARGETS} feerate targets per request")));
+        }
+        let targets = request
+            .targets
+            .iter()
+            .map(|target| {
+                if target.target_seconds == 0 || !(target.confidence > 0.0 && target.confidence <= 1.0) {
+                    return Err(RpcError::General(format!(
+                        "Invalid feerate target of {} seconds at confidence {}: expected a positive time and a confidence in (0, 1]",
+                        target.target_seconds, target.confidence
+                    )));
+                }
+                Ok(FeerateTarget { seconds: target.target_seconds, confidence: target.confidence })
+            })
+            .collect::<RpcResult<Vec<_>>>()?;
+
+        let mut response = if request.verbose {
             let mining_manager = self.mining_manager.clone();
             let consensus_manager = self.consensus_manager.clone();
             let prefix = self.config.prefix();
 
-            let response = self
-                .fee_estimate_verbose_cache
+            self.fee_estimate_verbose_cache
                 .get(async move {
                     let session = consensus_manager.consensus().unguarded_session();
                     mining_manager.get_realtime_feerate_estimations_verbose(&session, prefix).await.map(FeeEstimateVerbose::into_rpc)
                 })
-                .await?;
-            Ok(response)
+                .await?
         } else {
             let estimate = self.get_fee_estimate_call(connection, GetFeeEstimateRequest {}).await?.estimate;
-            Ok(GetFeeEstimateExperimentalResponse { estimate, verbose: None })
+            GetFeeEstimateExperimentalResponse { estimate, verbose: None, target_estimates: vec![] }
+        };
+
+        if !targets.is_empty() {
+            response.target_estimates = self
+                .mining_manager
+                .clone()
+                .get_feerate_estimations_for_targets(targets)
+                .await
+                .into_iter()
+                .map(FeerateTargetEstimationConverter::into_rpc)
+                .collect();
         }
+        Ok(response)
     }
 
     async fn get_utxo_return_address_call(
@@ -1219,15 +1314,35 @@ NOTE: This error usually indicates an RPC conversion error between the node and
             disk_io_write_per_sec: disk_io_write_per_sec as f32,
         });
 
-        let connection_metrics = req.connection_metrics.then(|| ConnectionMetrics {
-            borsh_live_connections: self.wrpc_borsh_counters.active_connections.load(Ordering::Relaxed) as u32,
-            borsh_connection_attempts: self.wrpc_borsh_counters.total_connections.load(Ordering::Relaxed) as u64,
-            borsh_handshake_failures: self.wrpc_borsh_counters.handshake_failures.load(Ordering::Relaxed) as u64,
-            json_live_connections: self.wrpc_json_counters.active_connections.load(Ordering::Relaxed) as u32,
-            json_connection_attempts: self.wrpc_json_counters.total_connections.load(Ordering::Relaxed) as u64,
-            json_handshake_failures: self.wrpc_json_counters.handshake_failures.load(Ordering::Relaxed) as u64,
-
-            active_peers: self.flow_context.hub().active_peers_len() as u32,
+        let connection_metrics = req.connection_metrics.then(|| {
+            let hub = self.flow_context.hub();
+            let totals = hub.peer_stats_totals();
+            let peer_stats = hub.active_routers().iter().map(|router| router.stats()).collect::<Vec<_>>();
+            let relay_latencies = peer_stats.iter().filter_map(|stats| stats.relay_latency).collect::<Vec<_>>();
+            let p2p_relay_latency_millis = match relay_latencies.len() {
+                0 => 0,
+                n => (relay_latencies.iter().sum::<Duration>() / n as u32).as_millis() as u64,
+            };
+            let p2p_max_outgoing_queue_fullness = peer_stats.iter().map(|stats| stats.outgoing_queue_fullness()).fold(0.0, f64::max);
+
+            ConnectionMetrics {
+                borsh_live_connections: self.wrpc_borsh_counters.active_connections.load(Ordering::Relaxed) as u32,
+                borsh_connection_attempts: self.wrpc_borsh_counters.total_connections.load(Ordering::Relaxed) as u64,
+                borsh_handshake_failures: self.wrpc_borsh_counters.handshake_failures.load(Ordering::Relaxed) as u64,
+                json_live_connections: self.wrpc_json_counters.active_connections.load(Ordering::Relaxed) as u32,
+                json_connection_attempts: self.wrpc_json_counters.total_connections.load(Ordering::Relaxed) as u64,
+                json_handshake_failures: self.wrpc_json_counters.handshake_failures.load(Ordering::Relaxed) as u64,
+
+                active_peers: hub.active_peers_len() as u32,
+
+                p2p_messages_rx: totals.messages_in,
+                p2p_messages_tx: totals.messages_out,
+                p2p_invalid_blocks: totals.invalid_blocks,
+                p2p_invalid_transactions: totals.invalid_transactions,
+                p2p_dropped_messages: totals.dropped_messages,
+                p2p_relay_latency_millis,
+                p2p_max_outgoing_queue_fullness: (p2p_max_outgoing_queue_fullness * 100.0) as f32,
+            }
         });
 
         let bandwidth_metrics = req.bandwidth_metrics.then(|| BandwidthMetrics {
@@ -1342,6 +1457,20 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         Ok(GetSyncStatusResponse { is_synced })
     }
 
+    async fn get_sync_progress_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        _request: GetSyncProgressRequest,
+    ) -> RpcResult<GetSyncProgressResponse> {
+        let session = self.consensus_manager.consensus().unguarded_session();
+
+        let sink_daa_score_timestamp = session.async_get_sink_daa_score_timestamp().await;
+        let is_synced = self.mining_rule_engine.is_sink_recent_and_connected(sink_daa_score_timestamp)
+            && !session.async_is_consensus_in_transitional_ibd_state().await;
+        let progress = self.flow_context.sync_progress().progress().as_ref().map(RpcSyncProgress::from);
+        Ok(GetSyncProgressResponse::new(is_synced, progress))
+    }
+
     async fn get_virtual_chain_from_block_v2_call(
         &self,
         _connection: Option<&DynRpcConnection>,
@@ -1395,6 +1524,231 @@ NOTE: This error usually indicates an RPC conversion error between the node and
         })
     }
 
+    async fn get_accepted_transactions_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetAcceptedTransactionsRequest,
+    ) -> RpcResult<GetAcceptedTransactionsResponse> {
+        let session = self.consensus_manager.consensus().session().await;
+        // Accepted transactions are read from block bodies which might be missing while in a transitional IBD state
+        if session.async_is_consensus_in_transitional_ibd_state().await {
+            return Err(RpcError::ConsensusInTransitionalIbdState);
+        }
+
+        let verbosity = RpcTransactionVerbosity::from(request.data_verbosity_level.unwrap_or(RpcDataVerbosityLevel::Full));
+        let mut remaining = match request.limit {
+            0 => DEFAULT_ACCEPTED_TRANSACTIONS_LIMIT,
+            limit => limit.min(MAX_ACCEPTED_TRANSACTIONS_LIMIT),
+        } as usize;
+
+        let chain_blocks = match request.accepting_block_hash {
+            Some(hash) => {
+                if !session.async_is_chain_block(hash).await? {
+                    return Err(RpcError::General(format!("block {hash} is not a selected chain block")));
+                }
+                vec![hash]
+            }
+            None => {
+                if request.start_daa_score > request.end_daa_score {
+                    return Err(RpcError::General(format!(
+                        "start DAA score {} is greater than end DAA score {}",
+                        request.start_daa_score, request.end_daa_score
+                    )));
+                }
+                // Fetch one extra chain block so that a range exceeding the scan cap yields where to resume
+                session
+                    .async_get_chain_block_hashes_by_daa_score_range(
+                        request.start_daa_score,
+                        request.end_daa_score,
+                        MAX_ACCEPTED_TRANSACTIONS_CHAIN_BLOCKS + 1,
+                    )
+                    .await?
+            }
+        };
+
+        let mut skip = request.skip;
+        let mut accepting_blocks = Vec::new();
+        let mut next = None;
+        for (i, &accepting_block_hash) in chain_blocks.iter().enumerate() {
+            let accepting_daa_score = session.async_get_header(accepting_block_hash).await?.daa_score;
+            if remaining == 0 || i == MAX_ACCEPTED_TRANSACTIONS_CHAIN_BLOCKS {
+                next = Some((accepting_daa_score, 0));
+                break;
+            }
+
+            let acceptance_data = session.async_get_blocks_acceptance_data(vec![accepting_block_hash], None).await?.remove(0);
+            let accepted_count = acceptance_data.iter().map(|mbad| mbad.accepted_transactions.len() as u64).sum::<u64>();
+            if skip >= accepted_count {
+                skip -= accepted_count;
+                continue;
+            }
+
+            let page = accepted_transactions_page(
+                acceptance_data.iter().map(|mbad| mbad.accepted_transactions.len()),
+                skip as usize,
+                remaining,
+            );
+            let mut returned = skip;
+            skip = 0;
+            let mut accepted_transactions = Vec::new();
+            for (index, range) in page {
+                let mbad = &acceptance_data[index];
+                remaining -= range.len();
+                returned += range.len() as u64;
+                let merged_block_data = MergesetBlockAcceptanceData {
+                    block_hash: mbad.block_hash,
+                    accepted_transactions: mbad.accepted_transactions[range].to_vec(),
+                };
+                accepted_transactions.extend(
+                    self.consensus_converter
+                        .get_accepted_transactions_with_fees(&session, accepting_block_hash, merged_block_data, &verbosity)
+                        .await?,
+                );
+            }
+
+            accepting_blocks.push(RpcAcceptingBlockTransactions { accepting_block_hash, accepting_daa_score, accepted_transactions });
+            if returned < accepted_count {
+                next = Some((accepting_daa_score, returned));
+                break;
+            }
+        }
+
+        Ok(GetAcceptedTransactionsResponse {
+            accepting_blocks,
+            next_daa_score: next.map(|(daa_score, _)| daa_score),
+            next_skip: next.map_or(0, |(_, skip)| skip),
+        })
+    }
+
+    async fn generate_blocks_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GenerateBlocksRequest,
+    ) -> RpcResult<GenerateBlocksResponse> {
+        if !matches!(*self.config.net, NetworkType::Simnet | NetworkType::Devnet) && !self.config.skip_proof_of_work {
+            return Err(RpcError::General(
+                "Block generation is only available on simnet and devnet or when proof of work is skipped".to_owned(),
+            ));
+        }
+        if request.count > MAX_GENERATED_BLOCKS {
+            return Err(RpcError::General(format!("Cannot generate more than {MAX_GENERATED_BLOCKS} blocks per request")));
+        }
+        if request.pay_address.prefix != self.config.prefix() {
+            return Err(kaspa_addresses::AddressError::InvalidPrefix(request.pay_address.prefix.to_string()))?;
+        }
+
+        let session = self.consensus_manager.consensus().unguarded_session();
+        if session.async_is_consensus_in_transitional_ibd_state().await {
+            return Err(RpcError::ConsensusInTransitionalIbdState);
+        }
+        let script_public_key = pay_to_address_script(&request.pay_address);
+        let miner_data = MinerData::new(script_public_key, version().as_bytes().to_vec());
+        let mut block_hashes = Vec::with_capacity(request.count as usize);
+        for _ in 0..request.count {
+            // Submitting waits for the virtual state to include the block, so the next template builds on top of it
+            let mut block = self.mining_manager.clone().get_block_template(&session, miner_data.clone()).await?.block;
+            if !self.config.skip_proof_of_work {
+                block.header = tokio::task::spawn_blocking(move || solve_header(block.header))
+                    .await
+                    .map_err(|err| RpcError::General(err.to_string()))?;
+            }
+            let block = block.to_immutable();
+            let hash = block.hash();
+            self.flow_context
+                .submit_rpc_block(&session, block)
+                .await
+                .map_err(|err| RpcError::General(format!("Generated block {hash} was rejected: {err}")))?;
+            block_hashes.push(hash);
+        }
+
+        Ok(GenerateBlocksResponse { block_hashes })
+    }
+
+    async fn get_address_history_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetAddressHistoryRequest,
+    ) -> RpcResult<GetAddressHistoryResponse> {
+        if !self.config.utxoindex || !self.config.address_history_index {
+            return Err(RpcError::NoAddressHistoryIndex);
+        }
+        let session = self.consensus_manager.consensus().unguarded_session();
+
+        // do not retrieve the address history while in unstable ibd state.
+        if session.async_is_consensus_in_transitional_ibd_state().await {
+            return Err(RpcError::ConsensusInTransitionalIbdState);
+        }
+        let from = match request.cursor.as_deref() {
+            Some(cursor) => Vec::<u8>::from_hex(cursor)
+                .ok()
+                .and_then(|bytes| AddressHistoryCursor::from_bytes(&bytes))
+                .ok_or(RpcError::InvalidAddressHistoryCursor)?,
+            None => AddressHistoryCursor::from_daa_score(request.start_daa_score),
+        };
+        let limit = match request.limit {
+            0 => MAX_ADDRESS_HISTORY_LIMIT,
+            limit => limit.min(MAX_ADDRESS_HISTORY_LIMIT),
+        };
+        let page = self
+            .utxoindex
+            .clone()
+            .unwrap()
+            .get_address_history(pay_to_address_script(&request.address), from, limit as usize)
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?;
+        let entries = page
+            .entries
+            .into_iter()
+            .map(|entry| RpcAddressHistoryEntry {
+                outpoint: entry.outpoint.into(),
+                transaction_id: entry.transaction_id,
+                kind: match entry.kind {
+                    AddressHistoryEntryKind::Credit => RpcAddressHistoryEntryKind::Credit,
+                    AddressHistoryEntryKind::Debit => RpcAddressHistoryEntryKind::Debit,
+                },
+                amount: entry.amount,
+                daa_score: entry.daa_score,
+                is_coinbase: entry.is_coinbase,
+            })
+            .collect();
+        Ok(GetAddressHistoryResponse::new(entries, page.next.map(|cursor| cursor.to_bytes().as_slice().to_hex())))
+    }
+
+    async fn get_balance_at_call(
+        &self,
+        _connection: Option<&DynRpcConnection>,
+        request: GetBalanceAtRequest,
+    ) -> RpcResult<GetBalanceAtResponse> {
+        if !self.config.utxoindex || !self.config.address_history_index {
+            return Err(RpcError::NoAddressHistoryIndex);
+        }
+        let session = self.consensus_manager.consensus().unguarded_session();
+
+        // do not retrieve balances while in unstable ibd state.
+        if session.async_is_consensus_in_transitional_ibd_state().await {
+            return Err(RpcError::ConsensusInTransitionalIbdState);
+        }
+        if request.addresses.len() > MAX_BALANCE_AT_ADDRESSES {
+            return Err(RpcError::AddressesExceedingMaximum(request.addresses.len(), MAX_BALANCE_AT_ADDRESSES));
+        }
+        let balances = self
+            .utxoindex
+            .clone()
+            .unwrap()
+            .get_balances_at(request.addresses.iter().map(pay_to_address_script).collect(), request.daa_score)
+            .await
+            .map_err(|err| RpcError::General(err.to_string()))?;
+        let entries = request
+            .addresses
+            .iter()
+            .map(|address| {
+                let balance = balances.get(&pay_to_address_script(address)).copied();
+                RpcBalancesByAddressesEntry { address: address.to_owned(), balance }
+            })
+            .collect();
+        Ok(GetBalanceAtResponse::new(entries))
+    }
+
     // ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     // Notification API
 
@@ -1478,3 +1832,60 @@ impl AsyncService for RpcCoreService {
         })
     }
 }
+
+/// Selects the page of the transactions accepted by a chain block which starts `skip` transactions in and holds at
+/// most `limit` of them. Accepted transactions are given as counts per merged block, and the page as ranges within
+/// the accepted transactions of each merged block it covers.
+fn accepted_transactions_page(
+    merged_counts: impl IntoIterator<Item = usize>,
+    mut skip: usize,
+    mut limit: usize,
+) -> Vec<(usize, Range<usize>)> {
+    let mut page = Vec::new();
+    for (index, count) in merged_counts.into_iter().enumerate() {
+        if limit == 0 {
+            break;
+        }
+        if skip >= count {
+            skip -= count;
+            continue;
+        }
+        let end = count.min(skip + limit);
+        page.push((index, skip..end));
+        limit -= end - skip;
+        skip = 0;
+    }
+    page
+}
+
+/// Searches the nonce satisfying the target of `header`
+fn solve_header(mut header: Header) -> Header {
+    let state = kaspa_pow::State::new(&header);
+    header.nonce = (0..=u64::MAX).find(|&nonce| state.check_pow(nonce).0).expect("no nonce satisfies the target");
+    header.finalize();
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accepted_transactions_page() {
+        let counts = [3, 0, 5, 2];
+        // A page within the first merged block
+        assert_eq!(accepted_transactions_page(counts, 0, 2), vec![(0, 0..2)]);
+        // A page spanning merged blocks skips the empty ones
+        assert_eq!(accepted_transactions_page(counts, 2, 4), vec![(0, 2..3), (2, 0..3)]);
+        // Skipping exactly up to a merged block boundary
+        assert_eq!(accepted_transactions_page(counts, 3, 5), vec![(2, 0..5)]);
+        // A limit exceeding the remaining transactions returns them all
+        assert_eq!(accepted_transactions_page(counts, 7, 100), vec![(2, 4..5), (3, 0..2)]);
+        assert_eq!(accepted_transactions_page(counts, 0, 100), vec![(0, 0..3), (2, 0..5), (3, 0..2)]);
+        // Skipping all or more transactions, or a zero limit, yield an empty page
+        assert!(accepted_transactions_page(counts, 10, 1).is_empty());
+        assert!(accepted_transactions_page(counts, 11, 1).is_empty());
+        assert!(accepted_transactions_page(counts, 0, 0).is_empty());
+        assert!(accepted_transactions_page([], 0, 1).is_empty());
+    }
+}
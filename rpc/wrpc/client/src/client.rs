@@ -76,6 +76,7 @@ impl Inner {
             RpcApiOps::VirtualDaaScoreChangedNotification,
             RpcApiOps::PruningPointUtxoSetOverrideNotification,
             RpcApiOps::NewBlockTemplateNotification,
+            RpcApiOps::SyncProgressChangedNotification,
         ]
         .into_iter()
         .for_each(|notification_op| {
@@ -665,6 +666,12 @@ impl RpcApi for KaspaRpcClient {
             SubmitTransactionReplacement,
             Unban,
             GetSeqCommitLaneProof,
+            GetAcceptedTransactions,
+            GenerateBlocks,
+            GetAddressHistory,
+            GetBalanceAt,
+            GetSyncProgress,
+            GetBlockTemplateDiagnostics,
         ]
     );
 
@@ -72,6 +72,12 @@ impl Router {
                 GetVirtualChainFromBlock,
                 GetVirtualChainFromBlockV2,
                 GetSeqCommitLaneProof,
+                GetAcceptedTransactions,
+                GenerateBlocks,
+                GetAddressHistory,
+                GetBalanceAt,
+                GetSyncProgress,
+                GetBlockTemplateDiagnostics,
                 ResolveFinalityConflict,
                 Shutdown,
                 SubmitBlock,
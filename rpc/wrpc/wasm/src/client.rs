@@ -908,6 +908,10 @@ build_wrpc_wasm_bindgen_subscriptions!([
     /// New block template notification event is produced when a new block
     /// template is generated for mining in the Kaspa BlockDAG.
     NewBlockTemplate,
+    /// Manage subscription for a sync progress changed notification event.
+    /// Sync progress changed notification event is produced periodically while
+    /// the node runs an initial block download (IBD).
+    SyncProgressChanged,
 ]);
 
 // Build RPC method invocation functions. This macro
@@ -977,6 +981,10 @@ build_wrpc_wasm_bindgen_interface!(
         /// Obtains basic information about the synchronization status of the Kaspa node.
         /// Returned information: Syncing status.
         GetSyncStatus,
+        /// Obtains the progress of the initial block download (IBD) run by the Kaspa node.
+        /// Returned information: Syncing status, IBD phase, syncer peer, processed items,
+        /// DAA score range and estimated remaining time.
+        GetSyncProgress,
         /// Feerate estimates
         GetFeeEstimate,
         /// Retrieves the current network configuration.
@@ -25,6 +25,7 @@ export enum RpcEventType {
     VirtualDaaScoreChanged = "virtual-daa-score-changed",
     PruningPointUtxoSetOverride = "pruning-point-utxo-set-override",
     NewBlockTemplate = "new-block-template",
+    SyncProgressChanged = "sync-progress-changed",
 }
 
 /**
@@ -40,7 +41,8 @@ export type RpcEventData = IBlockAdded
     | ISinkBlueScoreChanged 
     | IVirtualDaaScoreChanged 
     | IPruningPointUtxoSetOverride 
-    | INewBlockTemplate;
+    | INewBlockTemplate 
+    | ISyncProgressChanged;
 
 /**
  * RPC notification event data map.
@@ -59,6 +61,7 @@ export type RpcEventMap = {
     "virtual-daa-score-changed" : IVirtualDaaScoreChanged,
     "pruning-point-utxo-set-override" : IPruningPointUtxoSetOverride,
     "new-block-template" : INewBlockTemplate,
+    "sync-progress-changed" : ISyncProgressChanged,
 }
 
 /**
@@ -84,6 +87,7 @@ export type RpcEvent = {
  * {@link RpcClient.subscribeSinkBlueScoreChanged},
  * {@link RpcClient.subscribePruningPointUtxoSetOverride},
  * {@link RpcClient.subscribeNewBlockTemplate},
+ * {@link RpcClient.subscribeSyncProgressChanged},
  * 
  * @category Node RPC
  */
@@ -241,3 +245,19 @@ declare! {
     }
     "#,
 }
+
+declare! {
+    ISyncProgressChanged,
+    r#"
+    /**
+     * Sync progress changed notification event is produced periodically while
+     * the node runs an initial block download (IBD) and whenever the IBD enters
+     * a new phase. An undefined `progress` signals the end of the IBD.
+     * 
+     * @category Node RPC
+     */
+    export interface ISyncProgressChanged {
+        [key: string]: any;
+    }
+    "#,
+}
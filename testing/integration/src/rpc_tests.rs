@@ -17,10 +17,14 @@ use kaspa_notify::{
     connection::{ChannelConnection, ChannelType},
     scope::{
         BlockAddedScope, FinalityConflictScope, NewBlockTemplateScope, PruningPointUtxoSetOverrideScope, Scope,
-        SinkBlueScoreChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
+        SinkBlueScoreChangedScope, SyncProgressChangedScope, UtxosChangedScope, VirtualChainChangedScope, VirtualDaaScoreChangedScope,
     },
 };
-use kaspa_rpc_core::{Notification, api::rpc::RpcApi, model::*};
+use kaspa_rpc_core::{
+    Notification,
+    api::rpc::{MAX_TEMPLATE_CUSTOMIZATION_TRANSACTIONS, RpcApi},
+    model::*,
+};
 use kaspa_utils::{fd_budget, networking::ContextualNetAddress};
 use kaspad_lib::args::Args;
 use tokio::task::JoinHandle;
@@ -54,6 +58,7 @@ async fn sanity_test() {
         enable_unsynced_mining: true,
         block_template_cache_lifetime: Some(0),
         utxoindex: true,
+        address_history_index: true,
         unsafe_rpc: true,
         ..Default::default()
     };
@@ -112,10 +117,7 @@ async fn sanity_test() {
                     let GetBlockTemplateResponse { block, is_synced } = rpc_client
                         .get_block_template_call(
                             None,
-                            GetBlockTemplateRequest {
-                                pay_address: Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]),
-                                extra_data: Vec::new(),
-                            },
+                            GetBlockTemplateRequest::new(Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]), Vec::new()),
                         )
                         .await
                         .unwrap();
@@ -200,13 +202,62 @@ async fn sanity_test() {
             }
 
             KaspadPayloadOps::GetBlockTemplate => {
-                tst!(op, "see SubmitBlock")
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Transactions missing from the mempool are ignored by a customized template
+                    let pay_address = Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]);
+                    let request = GetBlockTemplateRequest::new(pay_address.clone(), Vec::new()).with_customization(
+                        vec![Hash::from_u64_word(1)],
+                        vec![Hash::from_u64_word(2)],
+                        1_000,
+                    );
+                    let response = rpc_client.get_block_template_call(None, request).await.unwrap();
+                    assert!(!response.block.transactions.is_empty(), "the template should at least hold the coinbase");
+
+                    // Oversized priority or excluded lists are rejected
+                    let too_many = vec![Hash::from_u64_word(1); MAX_TEMPLATE_CUSTOMIZATION_TRANSACTIONS + 1];
+                    let request =
+                        GetBlockTemplateRequest::new(pay_address.clone(), Vec::new()).with_customization(too_many.clone(), vec![], 0);
+                    assert!(rpc_client.get_block_template_call(None, request).await.is_err());
+                    let request =
+                        GetBlockTemplateRequest::new(pay_address.clone(), Vec::new()).with_customization(vec![], too_many, 0);
+                    assert!(rpc_client.get_block_template_call(None, request).await.is_err());
+                })
             }
 
             KaspadPayloadOps::GetCurrentBlockColor => {
                 tst!(op, "see SubmitBlock")
             }
 
+            KaspadPayloadOps::GetBlockTemplateDiagnostics => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let pay_address = Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]);
+                    let response = rpc_client.get_block_template_diagnostics(pay_address.clone(), Vec::new()).await.unwrap();
+                    // Every considered candidate is reported and only selected ones account for the used mass
+                    assert!(response.candidates.len() as u64 <= response.ready_transaction_count);
+                    assert_eq!(
+                        response.candidates.iter().filter(|c| c.outcome == RpcTemplateSelectionOutcome::Selected).count() as u64,
+                        response.selected_transaction_count
+                    );
+                    assert!(response.normalized_mass_used <= response.normalized_mass_limit);
+                    assert!(response.lanes.len() as u64 <= response.lanes_per_block_limit);
+
+                    // A mainnet address must be rejected by a simnet node
+                    let mainnet_address = Address::new(Prefix::Mainnet, Version::PubKey, &[0u8; 32]);
+                    assert!(rpc_client.get_block_template_diagnostics(mainnet_address, Vec::new()).await.is_err());
+
+                    // And so must oversized priority or excluded lists
+                    let too_many = vec![Hash::from_u64_word(1); MAX_TEMPLATE_CUSTOMIZATION_TRANSACTIONS + 1];
+                    let request = GetBlockTemplateDiagnosticsRequest::new(pay_address.clone(), Vec::new()).with_customization(
+                        vec![],
+                        too_many,
+                        0,
+                    );
+                    assert!(rpc_client.get_block_template_diagnostics_call(None, request).await.is_err());
+                })
+            }
+
             KaspadPayloadOps::GetBlockRewardInfo => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -477,6 +528,39 @@ async fn sanity_test() {
                 })
             }
 
+            KaspadPayloadOps::GetAddressHistory => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let address = Address::new(Prefix::Simnet, Version::PubKey, &[1u8; 32]);
+                    let response = rpc_client.get_address_history(address.clone(), 0, None, 10).await.unwrap();
+                    assert!(response.entries.is_empty());
+                    assert!(response.next_cursor.is_none());
+
+                    let result = rpc_client.get_address_history(address, 0, Some("00ff".to_string()), 10).await;
+                    assert!(result.is_err());
+                })
+            }
+
+            KaspadPayloadOps::GetBalanceAt => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let addresses = vec![Address::new(Prefix::Simnet, Version::PubKey, &[1u8; 32])];
+                    let entries = rpc_client.get_balance_at(addresses.clone(), u64::MAX).await.unwrap();
+                    assert_eq!(entries.len(), 1);
+                    assert_eq!(entries[0].address, addresses[0]);
+                    assert_eq!(entries[0].balance, Some(0));
+                })
+            }
+
+            KaspadPayloadOps::GetSyncProgress => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    let response = rpc_client.get_sync_progress_call(None, GetSyncProgressRequest {}).await.unwrap();
+                    // The node has no peers so no IBD can be running
+                    assert!(response.progress.is_none());
+                })
+            }
+
             KaspadPayloadOps::GetSinkBlueScore => {
                 let rpc_client = client.clone();
                 tst!(op, {
@@ -687,6 +771,15 @@ async fn sanity_test() {
                     }
                     assert!(response.verbose.is_some());
                     info!("{:?}", response.verbose);
+
+                    let targets = vec![RpcFeerateTarget { target_seconds: 60, confidence: 0.95 }];
+                    let estimates = rpc_client.get_fee_estimate_for_targets(targets).await.unwrap();
+                    assert_eq!(estimates.len(), 1);
+                    assert_eq!(estimates[0].target_seconds, 60);
+                    assert!(estimates[0].feerate > 0.0);
+
+                    let invalid = vec![RpcFeerateTarget { target_seconds: 60, confidence: 1.5 }];
+                    assert!(rpc_client.get_fee_estimate_for_targets(invalid).await.is_err());
                 })
             }
 
@@ -740,6 +833,32 @@ async fn sanity_test() {
                 })
             }
 
+            KaspadPayloadOps::GetAcceptedTransactions => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // A range beyond the sink accepts nothing and has no continuation
+                    let response = rpc_client.get_accepted_transactions_by_daa_score_range(u64::MAX, u64::MAX, 0, 0).await.unwrap();
+                    assert!(response.accepting_blocks.is_empty());
+                    assert_eq!(response.next_daa_score, None);
+
+                    // An inverted range or an unknown accepting block must yield an error
+                    assert!(rpc_client.get_accepted_transactions_by_daa_score_range(2, 1, 0, 0).await.is_err());
+                    assert!(rpc_client.get_accepted_transactions_by_block(0.into(), 0, 0).await.is_err());
+                })
+            }
+
+            KaspadPayloadOps::GenerateBlocks => {
+                let rpc_client = client.clone();
+                tst!(op, {
+                    // Generating blocks would interfere with the block counts asserted by the SubmitBlock arm,
+                    // so only rejections are covered here (see `generate_blocks_test`)
+                    let mainnet_address = Address::new(Prefix::Mainnet, Version::PubKey, &[0u8; 32]);
+                    assert!(rpc_client.generate_blocks(1, mainnet_address).await.is_err());
+                    let simnet_address = Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]);
+                    assert!(rpc_client.generate_blocks(u32::MAX, simnet_address).await.is_err());
+                })
+            }
+
             KaspadPayloadOps::NotifyBlockAdded => {
                 let rpc_client = client.clone();
                 let id = listener_id;
@@ -801,6 +920,13 @@ async fn sanity_test() {
                         .unwrap();
                 })
             }
+            KaspadPayloadOps::NotifySyncProgressChanged => {
+                let rpc_client = client.clone();
+                let id = listener_id;
+                tst!(op, {
+                    rpc_client.start_notify(id, SyncProgressChangedScope {}.into()).await.unwrap();
+                })
+            }
             KaspadPayloadOps::StopNotifyingUtxosChanged => {
                 let rpc_client = client.clone();
                 let id = listener_id;
@@ -861,7 +987,7 @@ async fn seq_commit_lane_proof_test() {
     let GetBlockTemplateResponse { block, .. } = grpc
         .get_block_template_call(
             None,
-            GetBlockTemplateRequest { pay_address: Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]), extra_data: Vec::new() },
+            GetBlockTemplateRequest::new(Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]), Vec::new()),
         )
         .await
         .unwrap();
@@ -933,7 +1059,7 @@ async fn seq_commit_lane_proof_test() {
     let GetBlockTemplateResponse { block: block2, .. } = grpc
         .get_block_template_call(
             None,
-            GetBlockTemplateRequest { pay_address: Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]), extra_data: Vec::new() },
+            GetBlockTemplateRequest::new(Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]), Vec::new()),
         )
         .await
         .unwrap();
@@ -997,3 +1123,43 @@ fn verify_lane_proof_locally(header: &Header, lane_key: Hash, response: &GetSeqC
     )
     .expect("verify_smt_metadata");
 }
+
+#[tokio::test]
+async fn generate_blocks_test() {
+    kaspa_core::log::try_init_logger("info");
+    kaspa_core::panic::configure_panic();
+
+    let args = Args { simnet: true, disable_upnp: true, block_template_cache_lifetime: Some(0), ..Default::default() };
+
+    let fd_total_budget = fd_budget::test_limit();
+    let mut daemon = Daemon::new_random_with_args(args, fd_total_budget);
+    let client = daemon.start().await;
+
+    let pay_address = Address::new(Prefix::Simnet, Version::PubKey, &[0u8; 32]);
+    let first = client.generate_blocks(3, pay_address.clone()).await.unwrap();
+    let second = client.generate_blocks(2, pay_address).await.unwrap();
+    assert_eq!(first.len(), 3);
+    assert_eq!(second.len(), 2);
+
+    // Each generated block is built on top of the previous one, which makes the last one the sink
+    let response = client.get_block_count_call(None, GetBlockCountRequest {}).await.unwrap();
+    assert_eq!(response.block_count, 5);
+    let response = client.get_sink_call(None, GetSinkRequest {}).await.unwrap();
+    assert_eq!(response.sink, *second.last().unwrap());
+    let response = client
+        .get_virtual_chain_from_block_call(
+            None,
+            GetVirtualChainFromBlockRequest {
+                start_hash: SIMNET_GENESIS.hash,
+                include_accepted_transaction_ids: false,
+                min_confirmation_count: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.added_chain_block_hashes, first.into_iter().chain(second).collect::<Vec<_>>());
+
+    client.disconnect().await.unwrap();
+    drop(client);
+    daemon.shutdown();
+}
@@ -24,8 +24,8 @@ use crate::storage::account::AccountSettings;
 use crate::storage::{PrvKeyData, PrvKeyDataId};
 use crate::tx::PaymentOutput;
 use crate::tx::{Fees, Generator, GeneratorSettings, GeneratorSummary, PaymentDestination, PendingTransaction, Signer};
-use crate::utxo::UtxoContextBinding;
 use crate::utxo::balance::{AtomicBalance, BalanceStrings};
+use crate::utxo::{UtxoContextBinding, UtxoEntryId};
 use kaspa_bip32::{ChildNumber, ExtendedPrivateKey, PrivateKey};
 use kaspa_consensus_client::UtxoEntry;
 use kaspa_consensus_client::UtxoEntryReference;
@@ -336,12 +336,14 @@ pub trait Account: AnySync + Send + Sync + 'static {
 
     /// Send funds to a [`PaymentDestination`] comprised of one or multiple [`PaymentOutputs`](crate::tx::PaymentOutputs)
     /// or [`PaymentDestination::Change`] variant that will forward funds to the change address.
+    /// If `utxos` is supplied, only these UTXOs are used as transaction inputs.
     async fn send(
         self: Arc<Self>,
         destination: PaymentDestination,
         fee_rate: Option<f64>,
         priority_fee_sompi: Fees,
         payload: Option<Vec<u8>>,
+        utxos: Option<Vec<UtxoEntryId>>,
         wallet_secret: Secret,
         payment_secret: Option<Secret>,
         abortable: &Abortable,
@@ -352,6 +354,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
 
         let settings =
             GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), destination, fee_rate, priority_fee_sompi, payload)?;
+        let settings = self.apply_utxo_selection(settings, utxos)?;
 
         let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
 
@@ -506,6 +509,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
         transfer_amount_sompi: u64,
         fee_rate: Option<f64>,
         priority_fee_sompi: Fees,
+        utxos: Option<Vec<UtxoEntryId>>,
         wallet_secret: Secret,
         payment_secret: Option<Secret>,
         abortable: &Abortable,
@@ -533,6 +537,7 @@ pub trait Account: AnySync + Send + Sync + 'static {
             final_transaction_payload,
         )?
         .utxo_context_transfer(destination_account.utxo_context());
+        let settings = self.apply_utxo_selection(settings, utxos)?;
 
         let generator = Generator::try_new(settings, Some(signer), Some(abortable))?;
 
@@ -557,9 +562,12 @@ pub trait Account: AnySync + Send + Sync + 'static {
         fee_rate: Option<f64>,
         priority_fee_sompi: Fees,
         payload: Option<Vec<u8>>,
+        utxos: Option<Vec<UtxoEntryId>>,
         abortable: &Abortable,
     ) -> Result<GeneratorSummary> {
-        let settings = GeneratorSettings::try_new_with_account(self.as_dyn_arc(), destination, fee_rate, priority_fee_sompi, payload)?;
+        let settings =
+            GeneratorSettings::try_new_with_account(self.clone().as_dyn_arc(), destination, fee_rate, priority_fee_sompi, payload)?;
+        let settings = self.apply_utxo_selection(settings, utxos)?;
 
         let generator = Generator::try_new(settings, None, Some(abortable))?;
 
@@ -571,6 +579,14 @@ pub trait Account: AnySync + Send + Sync + 'static {
         Ok(generator.summary())
     }
 
+    /// Restrict the inputs of the generated transactions to the supplied UTXOs (coin control).
+    fn apply_utxo_selection(&self, settings: GeneratorSettings, utxos: Option<Vec<UtxoEntryId>>) -> Result<GeneratorSettings> {
+        match utxos {
+            Some(utxos) => Ok(settings.with_utxo_selection(self.utxo_context().select_mature_utxos(&utxos)?)),
+            None => Ok(settings),
+        }
+    }
+
     fn as_derivation_capable(self: Arc<Self>) -> Result<Arc<dyn DerivationCapableAccount>> {
         Err(Error::AccountAddressDerivationCaps)
     }
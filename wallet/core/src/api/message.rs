@@ -7,6 +7,7 @@
 
 use crate::imports::*;
 use crate::tx::{Fees, GeneratorSummary, PaymentDestination};
+use crate::utxo::UtxoEntryId;
 use kaspa_addresses::Address;
 use kaspa_consensus_client::{TransactionOutpoint, UtxoEntry};
 use kaspa_hashes::Hash;
@@ -504,6 +505,9 @@ pub struct AccountsSendRequest {
     pub fee_rate: Option<f64>,
     pub priority_fee_sompi: Fees,
     pub payload: Option<Vec<u8>>,
+    /// Optional explicit set of UTXOs to use as transaction inputs (coin control)
+    #[serde(default)]
+    pub utxos: Option<Vec<TransactionOutpointWrapper>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -625,6 +629,18 @@ impl From<TransactionOutpointWrapper> for TransactionOutpoint {
     }
 }
 
+impl From<TransactionOutpointWrapper> for UtxoEntryId {
+    fn from(outpoint: TransactionOutpointWrapper) -> Self {
+        Self { transaction_id: outpoint.transaction_id, index: outpoint.index }
+    }
+}
+
+impl From<UtxoEntryId> for TransactionOutpointWrapper {
+    fn from(id: UtxoEntryId) -> Self {
+        Self { transaction_id: id.transaction_id, index: id.index }
+    }
+}
+
 impl From<UtxoEntryWrapper> for UtxoEntry {
     fn from(entry: UtxoEntryWrapper) -> Self {
         Self {
@@ -653,6 +669,54 @@ impl From<UtxoEntry> for UtxoEntryWrapper {
     }
 }
 
+/// Lock UTXOs so that they are excluded from automatic input selection.
+/// Locked UTXOs can still be spent by supplying them explicitly in
+/// [`AccountsSendRequest::utxos`] or [`AccountsTransferRequest::utxos`].
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsLockUtxosRequest {
+    pub account_id: AccountId,
+    pub outpoints: Vec<TransactionOutpointWrapper>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsLockUtxosResponse {
+    /// All UTXOs currently locked in the account
+    pub locked: Vec<TransactionOutpointWrapper>,
+}
+
+/// Unlock the supplied UTXOs or all locked UTXOs if `outpoints` is `None`.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsUnlockUtxosRequest {
+    pub account_id: AccountId,
+    pub outpoints: Option<Vec<TransactionOutpointWrapper>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsUnlockUtxosResponse {
+    /// UTXOs that remain locked in the account
+    pub locked: Vec<TransactionOutpointWrapper>,
+}
+
+/// Set or clear (if `policy` is `None`) the background UTXO consolidation
+/// policy of an account. The wallet keeps the supplied secrets in memory
+/// while the policy is active.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsConsolidationPolicySetRequest {
+    pub account_id: AccountId,
+    pub wallet_secret: Secret,
+    pub payment_secret: Option<Secret>,
+    pub policy: Option<ConsolidationPolicy>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountsConsolidationPolicySetResponse {}
+
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AccountsTransferRequest {
@@ -664,6 +728,9 @@ pub struct AccountsTransferRequest {
     pub fee_rate: Option<f64>,
     pub priority_fee_sompi: Option<Fees>,
     // pub priority_fee_sompi: Fees,
+    /// Optional explicit set of UTXOs to use as transaction inputs (coin control)
+    #[serde(default)]
+    pub utxos: Option<Vec<TransactionOutpointWrapper>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -683,6 +750,9 @@ pub struct AccountsEstimateRequest {
     pub fee_rate: Option<f64>,
     pub priority_fee_sompi: Fees,
     pub payload: Option<Vec<u8>>,
+    /// Optional explicit set of UTXOs to use as transaction inputs (coin control)
+    #[serde(default)]
+    pub utxos: Option<Vec<TransactionOutpointWrapper>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -698,6 +768,16 @@ pub struct FeeRateEstimateBucket {
     seconds: f64,
 }
 
+impl FeeRateEstimateBucket {
+    pub fn feerate(&self) -> f64 {
+        self.feerate
+    }
+
+    pub fn seconds(&self) -> f64 {
+        self.seconds
+    }
+}
+
 impl From<RpcFeerateBucket> for FeeRateEstimateBucket {
     fn from(bucket: RpcFeerateBucket) -> Self {
         Self { feerate: bucket.feerate, seconds: bucket.estimated_seconds }
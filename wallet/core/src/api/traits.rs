@@ -450,6 +450,59 @@ pub trait WalletApi: Send + Sync + AnySync {
     /// Get UTXOs for an account.
     async fn accounts_get_utxos_call(self: Arc<Self>, request: AccountsGetUtxosRequest) -> Result<AccountsGetUtxosResponse>;
 
+    /// Wrapper around [`accounts_lock_utxos_call()`](Self::accounts_lock_utxos_call)
+    async fn accounts_lock_utxos(
+        self: Arc<Self>,
+        account_id: AccountId,
+        outpoints: Vec<TransactionOutpointWrapper>,
+    ) -> Result<Vec<TransactionOutpointWrapper>> {
+        Ok(self.accounts_lock_utxos_call(AccountsLockUtxosRequest { account_id, outpoints }).await?.locked)
+    }
+
+    /// Lock account UTXOs, excluding them from automatic input selection
+    /// so that concurrent sends do not compete for the same inputs.
+    /// Returns the list of all UTXOs locked in the account.
+    async fn accounts_lock_utxos_call(self: Arc<Self>, request: AccountsLockUtxosRequest) -> Result<AccountsLockUtxosResponse>;
+
+    /// Wrapper around [`accounts_unlock_utxos_call()`](Self::accounts_unlock_utxos_call)
+    async fn accounts_unlock_utxos(
+        self: Arc<Self>,
+        account_id: AccountId,
+        outpoints: Option<Vec<TransactionOutpointWrapper>>,
+    ) -> Result<Vec<TransactionOutpointWrapper>> {
+        Ok(self.accounts_unlock_utxos_call(AccountsUnlockUtxosRequest { account_id, outpoints }).await?.locked)
+    }
+
+    /// Unlock account UTXOs (all UTXOs if `outpoints` is `None`).
+    /// Returns the list of UTXOs that remain locked.
+    async fn accounts_unlock_utxos_call(self: Arc<Self>, request: AccountsUnlockUtxosRequest) -> Result<AccountsUnlockUtxosResponse>;
+
+    /// Wrapper around [`accounts_consolidation_policy_set_call()`](Self::accounts_consolidation_policy_set_call)
+    async fn accounts_consolidation_policy_set(
+        self: Arc<Self>,
+        account_id: AccountId,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+        policy: Option<ConsolidationPolicy>,
+    ) -> Result<()> {
+        self.accounts_consolidation_policy_set_call(AccountsConsolidationPolicySetRequest {
+            account_id,
+            wallet_secret,
+            payment_secret,
+            policy,
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Set or clear the background UTXO consolidation policy of an account.
+    /// The policy is evaluated on each fee rate update, requiring the fee rate
+    /// poller to be enabled via [`fee_rate_poller_enable()`](Self::fee_rate_poller_enable).
+    async fn accounts_consolidation_policy_set_call(
+        self: Arc<Self>,
+        request: AccountsConsolidationPolicySetRequest,
+    ) -> Result<AccountsConsolidationPolicySetResponse>;
+
     /// Transfer funds to another account. Returns an [`AccountsTransferResponse`]
     /// struct that contains a [`GeneratorSummary`] as well `transaction_ids`
     /// containing a list of submitted transaction ids. Unlike funds sent to an
@@ -103,6 +103,9 @@ impl WalletApi for WalletClient {
         AccountsPskbBroadcast,
         AccountsPskbSend,
         AccountsGetUtxos,
+        AccountsLockUtxos,
+        AccountsUnlockUtxos,
+        AccountsConsolidationPolicySet,
         AccountsTransfer,
         AccountsEstimate,
         TransactionsDataGet,
@@ -189,6 +192,9 @@ impl WalletServer {
         AccountsPskbBroadcast,
         AccountsPskbSend,
         AccountsGetUtxos,
+        AccountsLockUtxos,
+        AccountsUnlockUtxos,
+        AccountsConsolidationPolicySet,
         AccountsTransfer,
         AccountsEstimate,
         TransactionsDataGet,
@@ -374,6 +374,12 @@ pub enum Error {
 
     #[error("Failed to merge bundles")]
     CommitRevealBundleMergeError,
+
+    #[error("UTXO {0} not found in the account")]
+    UtxoNotFound(String),
+
+    #[error("UTXO {0} is not mature or is already being spent")]
+    UtxoNotSpendable(String),
 }
 
 impl From<Aborted> for Error {
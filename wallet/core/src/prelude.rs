@@ -18,8 +18,8 @@ pub use crate::utils::{
     try_kaspa_str_to_sompi_i64,
 };
 pub use crate::utxo::balance::{Balance, BalanceStrings};
-pub use crate::wallet::Wallet;
 pub use crate::wallet::args::*;
+pub use crate::wallet::{ConsolidationPolicy, Wallet};
 pub use async_lock::{Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
 pub use kaspa_addresses::{Address, Prefix as AddressPrefix};
 pub use kaspa_bip32::{Language, Mnemonic, WordCount};
@@ -7,6 +7,8 @@ use std::sync::Arc;
 pub use kaspa_rpc_core::api::ctl::RpcCtl;
 pub use kaspa_rpc_core::api::rpc::RpcApi;
 pub use kaspa_rpc_core::notify::mode::NotificationMode;
+#[cfg(not(target_arch = "wasm32"))]
+pub use kaspa_rpc_failover::{FailoverNode, FailoverOptions, FailoverRpcClient};
 pub use kaspa_wrpc_client::Resolver;
 pub use kaspa_wrpc_client::WrpcEncoding;
 pub use kaspa_wrpc_client::client::{ConnectOptions, ConnectStrategy};
@@ -38,3 +40,13 @@ impl Rpc {
         &self.rpc_ctl
     }
 }
+
+/// Routes the wallet RPC through a [`FailoverRpcClient`], which must be started.
+/// A switch of node is signaled to the wallet as a disconnection followed by a connection.
+#[cfg(not(target_arch = "wasm32"))]
+impl From<FailoverRpcClient> for Rpc {
+    fn from(client: FailoverRpcClient) -> Self {
+        let rpc_ctl = client.ctl().clone();
+        Rpc::new(Arc::new(client), rpc_ctl)
+    }
+}
@@ -167,4 +167,11 @@ impl GeneratorSettings {
         self.destination_utxo_context = Some(destination_utxo_context.clone());
         self
     }
+
+    /// Restrict transaction inputs to the supplied UTXO entries (coin control).
+    pub fn with_utxo_selection(mut self, utxo_entries: Vec<UtxoEntryReference>) -> Self {
+        self.utxo_iterator = Box::new(UtxoIterator::with_entries(utxo_entries));
+        self.priority_utxo_entries = None;
+        self
+    }
 }
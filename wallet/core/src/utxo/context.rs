@@ -93,6 +93,8 @@ pub struct Context {
     /// Confirmation occurs when the transaction UTXOs are
     /// removed from the context by the UTXO change notification.
     pub(crate) outgoing: AHashMap<TransactionId, OutgoingTransaction>,
+    /// Mature UTXOs reserved by the user, skipped by automatic input selection
+    pub(crate) locked: AHashSet<UtxoEntryId>,
     /// Total balance of all UTXOs in this context (mature, pending)
     balance: Option<Balance>,
     /// Addresses monitored by this UTXO context
@@ -107,6 +109,7 @@ impl Default for Context {
             stasis: AHashMap::default(),
             map: AHashMap::default(),
             outgoing: AHashMap::default(),
+            locked: AHashSet::default(),
             balance: None,
             addresses: Arc::new(DashSet::new()),
         }
@@ -124,6 +127,7 @@ impl Context {
         self.stasis.clear();
         self.pending.clear();
         self.outgoing.clear();
+        self.locked.clear();
         self.addresses.clear();
         self.balance = None;
     }
@@ -207,6 +211,59 @@ impl UtxoContext {
         self.context().pending.len()
     }
 
+    /// Number of mature UTXOs available for automatic input selection
+    pub fn unlocked_mature_utxo_size(&self) -> usize {
+        let context = self.context();
+        context.mature.iter().filter(|entry| !context.locked.contains(entry.id_as_ref())).count()
+    }
+
+    /// Lock UTXOs so that they are not consumed by transactions whose inputs are selected automatically.
+    /// Locks are runtime-only and are released when the UTXO is spent or the context is cleared.
+    pub fn lock_utxos(&self, ids: &[UtxoEntryId]) -> Result<()> {
+        let mut context = self.context();
+        if let Some(id) = ids.iter().find(|id| !context.map.contains_key(*id)) {
+            return Err(Error::UtxoNotFound(id.to_string()));
+        }
+        context.locked.extend(ids.iter().cloned());
+        Ok(())
+    }
+
+    /// Unlock the supplied UTXOs or all locked UTXOs if `ids` is `None`.
+    pub fn unlock_utxos(&self, ids: Option<&[UtxoEntryId]>) {
+        let mut context = self.context();
+        match ids {
+            Some(ids) => ids.iter().for_each(|id| {
+                context.locked.remove(id);
+            }),
+            None => context.locked.clear(),
+        }
+    }
+
+    pub fn locked_utxos(&self) -> Vec<UtxoEntryId> {
+        self.context().locked.iter().cloned().collect()
+    }
+
+    /// Get the mature UTXO entries matching `ids` for use as explicitly selected transaction inputs.
+    /// Locked UTXOs can be selected this way, allowing the holder of a lock to spend them.
+    pub fn select_mature_utxos(&self, ids: &[UtxoEntryId]) -> Result<Vec<UtxoEntryReference>> {
+        let context = self.context();
+        let requested = ids.iter().collect::<AHashSet<_>>();
+        let mature = context
+            .mature
+            .iter()
+            .filter(|entry| requested.contains(entry.id_as_ref()))
+            .map(|entry| (entry.id_as_ref(), entry))
+            .collect::<AHashMap<_, _>>();
+        ids.iter()
+            .map(|id| {
+                if !context.map.contains_key(id) {
+                    return Err(Error::UtxoNotFound(id.to_string()));
+                }
+                mature.get(id).map(|&entry| entry.clone()).ok_or_else(|| Error::UtxoNotSpendable(id.to_string()))
+            })
+            .collect()
+    }
+
     pub fn balance(&self) -> Option<Balance> {
         self.context().balance.clone()
     }
@@ -252,6 +309,7 @@ impl UtxoContext {
             let mut context = self.context();
             let pending_utxo_entries = pending_tx.utxo_entries();
             context.mature.retain(|entry| !pending_utxo_entries.contains_key(&entry.id()));
+            context.locked.retain(|id| !pending_utxo_entries.contains_key(id));
 
             let outgoing_transaction = OutgoingTransaction::new(current_daa_score, self.clone(), pending_tx.clone());
             self.processor().register_outgoing_transaction(outgoing_transaction.clone());
@@ -359,6 +417,7 @@ impl UtxoContext {
 
         for utxo in utxos.into_iter() {
             let id = utxo.id();
+            context.locked.remove(&id);
             // remove from local map
             if context.map.remove(&id).is_some() {
                 if let Some(pending) = context.pending.remove(&id) {
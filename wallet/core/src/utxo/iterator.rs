@@ -11,8 +11,16 @@ pub struct UtxoIterator {
 }
 
 impl UtxoIterator {
+    /// Iterates over the mature UTXOs of the context, skipping the locked ones
     pub fn new(utxo_context: &UtxoContext) -> Self {
-        Self { entries: utxo_context.context().mature.clone(), cursor: 0 }
+        let context = utxo_context.context();
+        let entries = context.mature.iter().filter(|entry| !context.locked.contains(entry.id_as_ref())).cloned().collect();
+        Self { entries, cursor: 0 }
+    }
+
+    /// Iterates over an explicit selection of UTXOs
+    pub fn with_entries(entries: Vec<UtxoEntryReference>) -> Self {
+        Self { entries, cursor: 0 }
     }
 }
 
@@ -33,3 +33,41 @@ fn test_utxo_generator_empty_utxo_noop() -> Result<()> {
     // assert!(tx.is_none());
     Ok(())
 }
+
+#[tokio::test]
+async fn test_utxo_locks_and_selection() -> Result<()> {
+    let network_id = NetworkId::with_suffix(NetworkType::Testnet, 10);
+    let rpc_api_mock = Arc::new(RpcCoreMock::new());
+    let processor = UtxoProcessor::new(Some(rpc_api_mock.clone().into()), Some(network_id), None, None);
+    let context = UtxoContext::new(&processor, UtxoContextBinding::default());
+
+    let entries = [1.0, 2.0, 3.0].into_iter().map(kaspa_to_sompi).map(UtxoEntryReference::simulated).collect::<Vec<_>>();
+    for entry in entries.iter() {
+        context.insert(entry.clone(), 0, true).await?;
+    }
+
+    let locked = entries[1].id();
+    context.lock_utxos(std::slice::from_ref(&locked))?;
+    assert_eq!(context.locked_utxos(), vec![locked.clone()]);
+    assert_eq!(context.unlocked_mature_utxo_size(), 2);
+
+    // automatic selection skips locked entries
+    let selected = UtxoIterator::new(&context).map(|entry| entry.id()).collect::<Vec<_>>();
+    assert_eq!(selected, vec![entries[0].id(), entries[2].id()]);
+
+    // explicit selection may include locked entries
+    let selected = context.select_mature_utxos(std::slice::from_ref(&locked))?;
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected[0].id(), locked);
+
+    // unknown entries are rejected
+    let unknown = UtxoEntryReference::simulated(kaspa_to_sompi(1.0)).id();
+    assert!(matches!(context.lock_utxos(std::slice::from_ref(&unknown)), Err(Error::UtxoNotFound(_))));
+    assert!(matches!(context.select_mature_utxos(std::slice::from_ref(&unknown)), Err(Error::UtxoNotFound(_))));
+
+    context.unlock_utxos(None);
+    assert!(context.locked_utxos().is_empty());
+    assert_eq!(UtxoIterator::new(&context).count(), 3);
+
+    Ok(())
+}
@@ -9,6 +9,7 @@ use crate::result::Result;
 use crate::storage::Binding;
 use crate::storage::interface::TransactionRangeResult;
 use crate::tx::Fees;
+use crate::utxo::UtxoEntryId;
 use kaspa_rpc_core::RpcFeeEstimate;
 use kaspa_wallet_pskt::bundle::Bundle;
 use workflow_core::channel::Receiver;
@@ -388,16 +389,26 @@ impl WalletApi for super::Wallet {
     }
 
     async fn accounts_send_call(self: Arc<Self>, request: AccountsSendRequest) -> Result<AccountsSendResponse> {
-        let AccountsSendRequest { account_id, wallet_secret, payment_secret, destination, fee_rate, priority_fee_sompi, payload } =
-            request;
+        let AccountsSendRequest {
+            account_id,
+            wallet_secret,
+            payment_secret,
+            destination,
+            fee_rate,
+            priority_fee_sompi,
+            payload,
+            utxos,
+        } = request;
 
         let guard = self.guard();
         let guard = guard.lock().await;
         let account = self.get_account_by_id(&account_id, &guard).await?.ok_or(Error::AccountNotFound(account_id))?;
 
+        let utxos = utxos.map(|utxos| utxos.into_iter().map(UtxoEntryId::from).collect());
         let abortable = Abortable::new();
-        let (generator_summary, transaction_ids) =
-            account.send(destination, fee_rate, priority_fee_sompi, payload, wallet_secret, payment_secret, &abortable, None).await?;
+        let (generator_summary, transaction_ids) = account
+            .send(destination, fee_rate, priority_fee_sompi, payload, utxos, wallet_secret, payment_secret, &abortable, None)
+            .await?;
 
         Ok(AccountsSendResponse { generator_summary, transaction_ids })
     }
@@ -437,6 +448,50 @@ impl WalletApi for super::Wallet {
         Ok(AccountsGetUtxosResponse { utxos: utxos.into_iter().map(|entry| entry.into()).collect::<Vec<UtxoEntryWrapper>>() })
     }
 
+    async fn accounts_lock_utxos_call(self: Arc<Self>, request: AccountsLockUtxosRequest) -> Result<AccountsLockUtxosResponse> {
+        let AccountsLockUtxosRequest { account_id, outpoints } = request;
+        let guard = self.guard();
+        let guard = guard.lock().await;
+        let account = self.get_account_by_id(&account_id, &guard).await?.ok_or(Error::AccountNotFound(account_id))?;
+
+        let utxo_context = account.utxo_context();
+        utxo_context.lock_utxos(&outpoints.into_iter().map(UtxoEntryId::from).collect::<Vec<_>>())?;
+        let locked = utxo_context.locked_utxos().into_iter().map(TransactionOutpointWrapper::from).collect();
+        Ok(AccountsLockUtxosResponse { locked })
+    }
+
+    async fn accounts_unlock_utxos_call(self: Arc<Self>, request: AccountsUnlockUtxosRequest) -> Result<AccountsUnlockUtxosResponse> {
+        let AccountsUnlockUtxosRequest { account_id, outpoints } = request;
+        let guard = self.guard();
+        let guard = guard.lock().await;
+        let account = self.get_account_by_id(&account_id, &guard).await?.ok_or(Error::AccountNotFound(account_id))?;
+
+        let utxo_context = account.utxo_context();
+        let outpoints = outpoints.map(|outpoints| outpoints.into_iter().map(UtxoEntryId::from).collect::<Vec<_>>());
+        utxo_context.unlock_utxos(outpoints.as_deref());
+        let locked = utxo_context.locked_utxos().into_iter().map(TransactionOutpointWrapper::from).collect();
+        Ok(AccountsUnlockUtxosResponse { locked })
+    }
+
+    async fn accounts_consolidation_policy_set_call(
+        self: Arc<Self>,
+        request: AccountsConsolidationPolicySetRequest,
+    ) -> Result<AccountsConsolidationPolicySetResponse> {
+        let AccountsConsolidationPolicySetRequest { account_id, wallet_secret, payment_secret, policy } = request;
+        let guard = self.guard();
+        let guard = guard.lock().await;
+        let account = self.get_account_by_id(&account_id, &guard).await?.ok_or(Error::AccountNotFound(account_id))?;
+
+        if let Some(policy) = policy {
+            // validate secrets before retaining them
+            account.prv_key_data(wallet_secret.clone()).await?;
+            self.set_consolidation_policy(account_id, policy, wallet_secret, payment_secret);
+        } else {
+            self.clear_consolidation_policy(&account_id);
+        }
+        Ok(AccountsConsolidationPolicySetResponse {})
+    }
+
     async fn accounts_pskb_send_call(self: Arc<Self>, request: AccountsPskbSendRequest) -> Result<AccountsPskbSendResponse> {
         let AccountsPskbSendRequest { account_id, pskb, wallet_secret, payment_secret, sign_for_address } = request;
         let pskb = Bundle::deserialize(&pskb)?;
@@ -458,6 +513,7 @@ impl WalletApi for super::Wallet {
             fee_rate,
             priority_fee_sompi,
             transfer_amount_sompi,
+            utxos,
         } = request;
 
         let guard = self.guard();
@@ -473,6 +529,7 @@ impl WalletApi for super::Wallet {
                 transfer_amount_sompi,
                 fee_rate,
                 priority_fee_sompi.unwrap_or(Fees::SenderPays(0)),
+                utxos.map(|utxos| utxos.into_iter().map(UtxoEntryId::from).collect()),
                 wallet_secret,
                 payment_secret,
                 &abortable,
@@ -587,7 +644,7 @@ impl WalletApi for super::Wallet {
     }
 
     async fn accounts_estimate_call(self: Arc<Self>, request: AccountsEstimateRequest) -> Result<AccountsEstimateResponse> {
-        let AccountsEstimateRequest { account_id, destination, fee_rate, priority_fee_sompi, payload } = request;
+        let AccountsEstimateRequest { account_id, destination, fee_rate, priority_fee_sompi, payload, utxos } = request;
 
         let guard = self.guard();
         let guard = guard.lock().await;
@@ -606,7 +663,8 @@ impl WalletApi for super::Wallet {
 
         let abortable = Abortable::new();
         self.inner.estimation_abortables.lock().unwrap().insert(account_id, abortable.clone());
-        let result = account.estimate(destination, fee_rate, priority_fee_sompi, payload, &abortable).await;
+        let utxos = utxos.map(|utxos| utxos.into_iter().map(UtxoEntryId::from).collect());
+        let result = account.estimate(destination, fee_rate, priority_fee_sompi, payload, utxos, &abortable).await;
         self.inner.estimation_abortables.lock().unwrap().remove(&account_id);
 
         Ok(AccountsEstimateResponse { generator_summary: result? })
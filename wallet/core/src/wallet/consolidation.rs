@@ -0,0 +1,63 @@
+//!
+//! Background UTXO consolidation policies.
+//!
+//! A [`ConsolidationPolicy`] assigned to an account makes the wallet
+//! compound the account UTXOs (by sweeping them into the change address)
+//! when the number of spendable UTXOs exceeds a threshold and the network
+//! fee rate is low enough. The policy is evaluated on each [`Events::FeeRate`]
+//! notification, so the fee rate poller must be enabled
+//! (see [`WalletApi::fee_rate_poller_enable`](crate::api::WalletApi::fee_rate_poller_enable)).
+//!
+
+use crate::imports::*;
+
+/// Account UTXO consolidation policy.
+#[derive(Clone, Debug, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsolidationPolicy {
+    /// Consolidate when the account has more than this number of spendable UTXOs
+    pub min_utxo_count: u64,
+    /// Consolidate only when the network fee rate (in sompi per gram) does not exceed this value
+    pub max_fee_rate: f64,
+}
+
+impl ConsolidationPolicy {
+    pub fn new(min_utxo_count: u64, max_fee_rate: f64) -> Self {
+        Self { min_utxo_count, max_fee_rate }
+    }
+
+    pub fn should_consolidate(&self, utxo_count: usize, fee_rate: f64) -> bool {
+        utxo_count as u64 > self.min_utxo_count && fee_rate <= self.max_fee_rate
+    }
+}
+
+/// Active consolidation policy of an account together with the
+/// secrets required to sign the consolidation transactions.
+#[derive(Clone)]
+pub(crate) struct ConsolidationContext {
+    pub policy: ConsolidationPolicy,
+    pub wallet_secret: Secret,
+    pub payment_secret: Option<Secret>,
+    /// Set while a consolidation sweep is in progress
+    pub running: Arc<AtomicBool>,
+}
+
+impl ConsolidationContext {
+    pub fn new(policy: ConsolidationPolicy, wallet_secret: Secret, payment_secret: Option<Secret>) -> Self {
+        Self { policy, wallet_secret, payment_secret, running: Arc::new(AtomicBool::new(false)) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_consolidation_policy() {
+        let policy = ConsolidationPolicy::new(100, 1.0);
+        assert!(!policy.should_consolidate(100, 1.0));
+        assert!(policy.should_consolidate(101, 1.0));
+        assert!(policy.should_consolidate(101, 0.5));
+        assert!(!policy.should_consolidate(101, 1.5));
+    }
+}
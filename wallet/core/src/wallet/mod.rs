@@ -11,8 +11,10 @@
 
 pub mod api;
 pub mod args;
+pub mod consolidation;
 pub mod maps;
 pub use args::*;
+pub use consolidation::ConsolidationPolicy;
 
 use crate::account::ScanNotifier;
 use crate::api::traits::WalletApi;
@@ -24,6 +26,7 @@ use crate::settings::{SettingsStore, WalletSettings};
 use crate::storage::interface::{OpenArgs, StorageDescriptor};
 use crate::storage::local::Storage;
 use crate::storage::local::interface::LocalStore;
+use crate::wallet::consolidation::ConsolidationContext;
 use crate::wallet::keydata::PrvKeyDataVariantKind;
 use crate::wallet::maps::ActiveAccountMap;
 use kaspa_bip32::{ExtendedKey, Language, Mnemonic, Prefix as KeyPrefix, WordCount};
@@ -103,6 +106,7 @@ struct Inner {
     multiplexer: Multiplexer<Box<Events>>,
     wallet_bus: Channel<WalletBusMessage>,
     estimation_abortables: Mutex<HashMap<AccountId, Abortable>>,
+    consolidation_policies: Mutex<HashMap<AccountId, ConsolidationContext>>,
     retained_contexts: Mutex<HashMap<String, Arc<Vec<u8>>>>,
     // Mutex used to protect concurrent access to accounts at the wallet api level
     guard: Arc<AsyncMutex<()>>,
@@ -170,6 +174,7 @@ impl Wallet {
                 utxo_processor: utxo_processor.clone(),
                 wallet_bus,
                 estimation_abortables: Mutex::new(HashMap::new()),
+                consolidation_policies: Mutex::new(HashMap::new()),
                 retained_contexts: Mutex::new(HashMap::new()),
                 guard: Arc::new(AsyncMutex::new(())),
                 account_guard: Arc::new(AsyncMutex::new(())),
@@ -288,6 +293,7 @@ impl Wallet {
 
     pub async fn close(self: &Arc<Wallet>) -> Result<()> {
         if self.is_open() {
+            self.inner.consolidation_policies.lock().unwrap().clear();
             self.reset(true).await?;
             self.store().close().await?;
             self.notify(Events::WalletClose).await?;
@@ -1101,9 +1107,68 @@ impl Wallet {
             self.store().as_transaction_record_store()?.store(&[record]).await?;
         }
 
+        if let Events::FeeRate { normal, .. } = &*event {
+            self.handle_consolidation(normal.feerate());
+        }
+
         Ok(())
     }
 
+    /// Set the UTXO consolidation policy of an account. The secrets are retained
+    /// in memory until the policy is cleared or the wallet is closed.
+    pub fn set_consolidation_policy(
+        &self,
+        account_id: AccountId,
+        policy: ConsolidationPolicy,
+        wallet_secret: Secret,
+        payment_secret: Option<Secret>,
+    ) {
+        let context = ConsolidationContext::new(policy, wallet_secret, payment_secret);
+        self.inner.consolidation_policies.lock().unwrap().insert(account_id, context);
+    }
+
+    pub fn clear_consolidation_policy(&self, account_id: &AccountId) {
+        self.inner.consolidation_policies.lock().unwrap().remove(account_id);
+    }
+
+    pub fn consolidation_policy(&self, account_id: &AccountId) -> Option<ConsolidationPolicy> {
+        self.inner.consolidation_policies.lock().unwrap().get(account_id).map(|context| context.policy.clone())
+    }
+
+    /// Evaluate account consolidation policies against the current network fee rate
+    /// and sweep the accounts whose policy conditions are met.
+    fn handle_consolidation(self: &Arc<Self>, fee_rate: f64) {
+        if !self.is_synced() {
+            return;
+        }
+
+        let policies =
+            self.inner.consolidation_policies.lock().unwrap().iter().map(|(id, context)| (*id, context.clone())).collect::<Vec<_>>();
+
+        for (account_id, context) in policies {
+            let Some(account) = self.active_accounts().get(&account_id) else {
+                continue;
+            };
+
+            let utxo_count = account.utxo_context().unlocked_mature_utxo_size();
+            if !context.policy.should_consolidate(utxo_count, fee_rate) || context.running.swap(true, Ordering::SeqCst) {
+                continue;
+            }
+
+            let this = self.clone();
+            spawn(async move {
+                let ConsolidationContext { wallet_secret, payment_secret, running, .. } = context;
+                let abortable = Abortable::new();
+                if let Err(err) = account.sweep(wallet_secret, payment_secret, Some(fee_rate), &abortable, None).await {
+                    this.notify(Events::Error { message: format!("UTXO consolidation failed for account {account_id}: {err}") })
+                        .await
+                        .ok();
+                }
+                running.store(false, Ordering::SeqCst);
+            });
+        }
+    }
+
     async fn start_task(self: &Arc<Self>) -> Result<()> {
         let this = self.clone();
         let task_ctl_receiver = self.inner.task_ctl.request.receiver.clone();
@@ -1416,6 +1416,11 @@ declare! {
          * If not supplied, the destination will be the change address resulting in a UTXO compound transaction.
          */
         destination? : IPaymentOutput[];
+        /**
+         * Optional list of UTXOs to use as transaction inputs (coin control).
+         * If not supplied, inputs are selected automatically from unlocked UTXOs.
+         */
+        utxos? : ITransactionOutpoint[];
     }
     "#,
 }
@@ -1432,7 +1437,9 @@ try_from! ( args: IAccountsSendRequest, AccountsSendRequest, {
     let destination: PaymentDestination =
         if outputs.is_undefined() { PaymentDestination::Change } else { PaymentOutputs::try_owned_from(outputs)?.into() };
 
-    Ok(AccountsSendRequest { account_id, wallet_secret, payment_secret, fee_rate, priority_fee_sompi, destination, payload })
+    let utxos = args.try_get_value("utxos")?.map(from_value::<Vec<TransactionOutpointWrapper>>).transpose()?;
+
+    Ok(AccountsSendRequest { account_id, wallet_secret, payment_secret, fee_rate, priority_fee_sompi, destination, payload, utxos })
 });
 
 declare! {
@@ -1696,6 +1703,139 @@ try_from! ( args: AccountsGetUtxosResponse, IAccountsGetUtxosResponse, {
 
 // ---
 
+declare! {
+    IAccountsLockUtxosRequest,
+    r#"
+    /**
+     * Lock UTXOs, excluding them from automatic input selection.
+     * Locked UTXOs can still be spent by supplying them explicitly
+     * in the `utxos` field of send, transfer and estimate requests.
+     *
+     * @category Wallet API
+     */
+    export interface IAccountsLockUtxosRequest {
+        accountId : HexString;
+        outpoints : ITransactionOutpoint[];
+    }
+    "#,
+}
+
+try_from! ( args: IAccountsLockUtxosRequest, AccountsLockUtxosRequest, {
+    Ok(from_value::<AccountsLockUtxosRequest>(args.into())?)
+});
+
+declare! {
+    IAccountsLockUtxosResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsLockUtxosResponse {
+        locked : ITransactionOutpoint[];
+    }
+    "#,
+}
+
+try_from! ( args: AccountsLockUtxosResponse, IAccountsLockUtxosResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IAccountsUnlockUtxosRequest,
+    r#"
+    /**
+     * Unlock the supplied UTXOs or all locked UTXOs if `outpoints` is not supplied.
+     *
+     * @category Wallet API
+     */
+    export interface IAccountsUnlockUtxosRequest {
+        accountId : HexString;
+        outpoints? : ITransactionOutpoint[];
+    }
+    "#,
+}
+
+try_from! ( args: IAccountsUnlockUtxosRequest, AccountsUnlockUtxosRequest, {
+    Ok(from_value::<AccountsUnlockUtxosRequest>(args.into())?)
+});
+
+declare! {
+    IAccountsUnlockUtxosResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsUnlockUtxosResponse {
+        locked : ITransactionOutpoint[];
+    }
+    "#,
+}
+
+try_from! ( args: AccountsUnlockUtxosResponse, IAccountsUnlockUtxosResponse, {
+    Ok(to_value(&args)?.into())
+});
+
+// ---
+
+declare! {
+    IAccountsConsolidationPolicySetRequest,
+    r#"
+    /**
+     * Set or clear (if `policy` is not supplied) the background UTXO consolidation policy.
+     * The policy is evaluated on fee rate updates and requires the fee rate poller to be enabled.
+     *
+     * @category Wallet API
+     */
+    export interface IAccountsConsolidationPolicySetRequest {
+        accountId : HexString;
+        walletSecret : string;
+        paymentSecret? : string;
+        policy? : {
+            /**
+             * Consolidate when the account has more than this number of spendable UTXOs.
+             */
+            minUtxoCount : number;
+            /**
+             * Consolidate only when the network fee rate (sompi per gram) does not exceed this value.
+             */
+            maxFeeRate : number;
+        };
+    }
+    "#,
+}
+
+try_from! ( args: IAccountsConsolidationPolicySetRequest, AccountsConsolidationPolicySetRequest, {
+    let account_id = args.get_account_id("accountId")?;
+    let wallet_secret = args.get_secret("walletSecret")?;
+    let payment_secret = args.try_get_secret("paymentSecret")?;
+    let policy = args.try_get_value("policy")?.map(from_value::<ConsolidationPolicy>).transpose()?;
+    Ok(AccountsConsolidationPolicySetRequest { account_id, wallet_secret, payment_secret, policy })
+});
+
+declare! {
+    IAccountsConsolidationPolicySetResponse,
+    r#"
+    /**
+     * 
+     *  
+     * @category Wallet API
+     */
+    export interface IAccountsConsolidationPolicySetResponse { }
+    "#,
+}
+
+try_from! ( _args: AccountsConsolidationPolicySetResponse, IAccountsConsolidationPolicySetResponse, {
+    Ok(IAccountsConsolidationPolicySetResponse::default())
+});
+
+// ---
+
 declare! {
     IAccountsTransferRequest,
     r#"
@@ -1712,6 +1852,7 @@ declare! {
         feeRate? : number;
         priorityFeeSompi? : IFees | bigint;
         transferAmountSompi : bigint;
+        utxos? : ITransactionOutpoint[];
     }
     "#,
 }
@@ -1724,6 +1865,7 @@ try_from! ( args: IAccountsTransferRequest, AccountsTransferRequest, {
     let fee_rate = args.get_f64("feeRate").ok();
     let priority_fee_sompi = args.try_get::<IFees>("priorityFeeSompi")?.map(Fees::try_from).transpose()?;
     let transfer_amount_sompi = args.get_u64("transferAmountSompi")?;
+    let utxos = args.try_get_value("utxos")?.map(from_value::<Vec<TransactionOutpointWrapper>>).transpose()?;
 
     Ok(AccountsTransferRequest {
         source_account_id,
@@ -1733,6 +1875,7 @@ try_from! ( args: IAccountsTransferRequest, AccountsTransferRequest, {
         fee_rate,
         priority_fee_sompi,
         transfer_amount_sompi,
+        utxos,
     })
 });
 
@@ -1774,6 +1917,7 @@ declare! {
         feeRate? : number;
         priorityFeeSompi : IFees | bigint;
         payload? : Uint8Array | string;
+        utxos? : ITransactionOutpoint[];
     }
     "#,
 }
@@ -1788,7 +1932,9 @@ try_from! ( args: IAccountsEstimateRequest, AccountsEstimateRequest, {
     let destination: PaymentDestination =
         if outputs.is_undefined() { PaymentDestination::Change } else { PaymentOutputs::try_owned_from(outputs)?.into() };
 
-    Ok(AccountsEstimateRequest { account_id, fee_rate, priority_fee_sompi, destination, payload })
+    let utxos = args.try_get_value("utxos")?.map(from_value::<Vec<TransactionOutpointWrapper>>).transpose()?;
+
+    Ok(AccountsEstimateRequest { account_id, fee_rate, priority_fee_sompi, destination, payload, utxos })
 });
 
 declare! {
@@ -50,6 +50,9 @@ declare_wasm_handlers!([
     AccountsPskbBroadcast,
     AccountsPskbSend,
     AccountsGetUtxos,
+    AccountsLockUtxos,
+    AccountsUnlockUtxos,
+    AccountsConsolidationPolicySet,
     AccountsTransfer,
     AccountsEstimate,
     TransactionsDataGet,
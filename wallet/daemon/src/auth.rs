@@ -0,0 +1,48 @@
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use subtle::ConstantTimeEq;
+
+/// Bearer token authentication applied to both the HTTP and the websocket endpoints
+#[derive(Debug, Clone)]
+pub struct Auth {
+    token: Option<String>,
+}
+
+impl Auth {
+    pub fn new(token: String) -> Self {
+        Self { token: Some(token) }
+    }
+
+    /// Accepts every request; only meant for daemons bound to a loopback interface
+    pub fn disabled() -> Self {
+        Self { token: None }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.token.is_some()
+    }
+
+    pub fn verify(&self, headers: &HeaderMap) -> bool {
+        let Some(expected) = &self.token else {
+            return true;
+        };
+        headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|provided| bool::from(provided.trim().as_bytes().ct_eq(expected.as_bytes())))
+    }
+}
+
+pub async fn authenticate(State(auth): State<Arc<Auth>>, request: Request, next: Next) -> Response {
+    if auth.verify(request.headers()) {
+        next.run(request).await
+    } else {
+        (StatusCode::UNAUTHORIZED, [(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"))]).into_response()
+    }
+}
@@ -0,0 +1,29 @@
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("{0}")]
+    Custom(String),
+
+    #[error(transparent)]
+    Wallet(#[from] kaspa_wallet_core::error::Error),
+
+    #[error(transparent)]
+    NetworkId(#[from] kaspa_consensus_core::network::NetworkIdError),
+
+    #[error(transparent)]
+    SerdeJson(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+impl From<String> for Error {
+    fn from(s: String) -> Self {
+        Error::Custom(s)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(s: &str) -> Self {
+        Error::Custom(s.to_string())
+    }
+}
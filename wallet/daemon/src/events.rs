@@ -0,0 +1,111 @@
+use crate::jsonrpc::JSONRPC_VERSION;
+use async_trait::async_trait;
+use kaspa_core::warn;
+use kaspa_wallet_core::{api::transport::EventHandler, events::Events};
+use serde::Deserialize;
+use serde_json::json;
+use std::{collections::HashSet, sync::Arc, sync::Mutex};
+use tokio::sync::broadcast;
+
+/// Name of the JSON-RPC notification method carrying wallet events
+pub const EVENT_NOTIFICATION_METHOD: &str = "event";
+
+/// A wallet event serialized once into its JSON-RPC notification form
+#[derive(Debug)]
+pub struct EventNotification {
+    /// The `type` tag of the event, e.g. `balance` or `maturity`
+    pub kind: String,
+    pub message: String,
+}
+
+/// Fans the wallet event stream out to all connected websocket sessions
+pub struct EventBroadcaster {
+    sender: broadcast::Sender<Arc<EventNotification>>,
+}
+
+impl EventBroadcaster {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<EventNotification>> {
+        self.sender.subscribe()
+    }
+}
+
+#[async_trait]
+impl EventHandler for EventBroadcaster {
+    async fn handle_event(&self, event: &Events) {
+        // Serializing is wasted work while no session is listening
+        if self.sender.receiver_count() == 0 {
+            return;
+        }
+        let params = match serde_json::to_value(event) {
+            Ok(params) => params,
+            Err(err) => {
+                warn!("Unable to serialize wallet event: {err}");
+                return;
+            }
+        };
+        let kind = params.get("type").and_then(|kind| kind.as_str()).unwrap_or_default().to_string();
+        let message = json!({ "jsonrpc": JSONRPC_VERSION, "method": EVENT_NOTIFICATION_METHOD, "params": params }).to_string();
+        // An error only means that all receivers went away in the meantime
+        let _ = self.sender.send(Arc::new(EventNotification { kind, message }));
+    }
+}
+
+/// Parameters of the websocket `subscribe` and `unsubscribe` methods.
+/// Omitting `events` applies the call to every event kind.
+#[derive(Debug, Default, Deserialize)]
+pub struct SubscriptionParams {
+    pub events: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default)]
+struct Filter {
+    all: bool,
+    // Event kinds excluded from `all`, or the only kinds included otherwise
+    kinds: HashSet<String>,
+}
+
+/// Event subscription state of a single websocket session
+#[derive(Debug, Default)]
+pub struct Subscription {
+    filter: Mutex<Filter>,
+}
+
+impl Subscription {
+    pub fn subscribe(&self, kinds: Option<Vec<String>>) {
+        let mut filter = self.filter.lock().unwrap();
+        match kinds {
+            None => {
+                filter.all = true;
+                filter.kinds.clear();
+            }
+            Some(kinds) if filter.all => kinds.iter().for_each(|kind| {
+                filter.kinds.remove(kind);
+            }),
+            Some(kinds) => filter.kinds.extend(kinds),
+        }
+    }
+
+    pub fn unsubscribe(&self, kinds: Option<Vec<String>>) {
+        let mut filter = self.filter.lock().unwrap();
+        match kinds {
+            None => {
+                filter.all = false;
+                filter.kinds.clear();
+            }
+            Some(kinds) if filter.all => filter.kinds.extend(kinds),
+            Some(kinds) => kinds.iter().for_each(|kind| {
+                filter.kinds.remove(kind);
+            }),
+        }
+    }
+
+    pub fn accepts(&self, kind: &str) -> bool {
+        let filter = self.filter.lock().unwrap();
+        filter.all != filter.kinds.contains(kind)
+    }
+}
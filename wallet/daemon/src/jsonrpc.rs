@@ -0,0 +1,198 @@
+use crate::events::{Subscription, SubscriptionParams};
+use kaspa_wallet_core::{api::transport::WalletServer, error::Error as WalletError};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+
+pub const JSONRPC_VERSION: &str = "2.0";
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+/// Error raised by the wallet while processing an otherwise valid call
+pub const WALLET_ERROR: i64 = -32000;
+
+pub const SUBSCRIBE_METHOD: &str = "subscribe";
+pub const UNSUBSCRIBE_METHOD: &str = "unsubscribe";
+
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub jsonrpc: String,
+    pub method: String,
+    #[serde(default)]
+    pub params: Option<Value>,
+    /// `None` when the member is absent, which makes the request a notification
+    #[serde(default, deserialize_with = "present")]
+    pub id: Option<Value>,
+}
+
+fn present<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ErrorObject {
+    pub code: i64,
+    pub message: String,
+}
+
+impl ErrorObject {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+}
+
+impl From<WalletError> for ErrorObject {
+    fn from(err: WalletError) -> Self {
+        match err {
+            WalletError::NotImplemented => ErrorObject::new(METHOD_NOT_FOUND, "Method not found"),
+            // Responses are produced by the wallet itself, so a serde failure points at the params
+            WalletError::SerdeJson(err) => ErrorObject::new(INVALID_PARAMS, format!("Invalid params: {err}")),
+            err => ErrorObject::new(WALLET_ERROR, err.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Response {
+    pub jsonrpc: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorObject>,
+    pub id: Value,
+}
+
+impl Response {
+    pub fn new(id: Value, result: std::result::Result<Value, ErrorObject>) -> Self {
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self { jsonrpc: JSONRPC_VERSION.to_string(), result, error, id }
+    }
+}
+
+/// Routes JSON-RPC calls to the [`WalletServer`] serde transport
+pub struct Dispatcher {
+    wallet_server: Arc<WalletServer>,
+}
+
+impl Dispatcher {
+    pub fn new(wallet_server: Arc<WalletServer>) -> Self {
+        Self { wallet_server }
+    }
+
+    /// Processes a single request or a batch and returns the serialized reply,
+    /// or `None` when the message consisted of notifications only. The
+    /// subscription methods are only available with a websocket `session`.
+    pub async fn handle(&self, message: &str, session: Option<&Subscription>) -> Option<String> {
+        let reply = match serde_json::from_str::<Value>(message) {
+            Ok(Value::Array(batch)) if !batch.is_empty() => {
+                let mut responses = Vec::with_capacity(batch.len());
+                for request in batch {
+                    responses.extend(self.handle_request(request, session).await);
+                }
+                (!responses.is_empty()).then(|| serde_json::to_string(&responses))
+            }
+            Ok(Value::Array(_)) => {
+                Some(serde_json::to_string(&Response::new(Value::Null, Err(ErrorObject::new(INVALID_REQUEST, "Empty batch")))))
+            }
+            Ok(request) => self.handle_request(request, session).await.map(|response| serde_json::to_string(&response)),
+            Err(err) => Some(serde_json::to_string(&Response::new(Value::Null, Err(ErrorObject::new(PARSE_ERROR, err.to_string()))))),
+        };
+        reply.map(|reply| reply.expect("JSON-RPC responses are always serializable"))
+    }
+
+    async fn handle_request(&self, request: Value, session: Option<&Subscription>) -> Option<Response> {
+        let request = match serde_json::from_value::<Request>(request) {
+            Ok(request) if request.jsonrpc == JSONRPC_VERSION => request,
+            Ok(request) => {
+                let error = ErrorObject::new(INVALID_REQUEST, format!("Unsupported JSON-RPC version `{}`", request.jsonrpc));
+                return Some(Response::new(request.id.unwrap_or_default(), Err(error)));
+            }
+            Err(err) => return Some(Response::new(Value::Null, Err(ErrorObject::new(INVALID_REQUEST, err.to_string())))),
+        };
+
+        let result = match (request.method.as_str(), session) {
+            (SUBSCRIBE_METHOD, Some(session)) => parse_subscription(request.params).map(|params| {
+                session.subscribe(params.events);
+                Value::Bool(true)
+            }),
+            (UNSUBSCRIBE_METHOD, Some(session)) => parse_subscription(request.params).map(|params| {
+                session.unsubscribe(params.events);
+                Value::Bool(true)
+            }),
+            (method, _) => self.call(method, request.params).await,
+        };
+
+        request.id.map(|id| Response::new(id, result))
+    }
+
+    /// Invokes a wallet API method by its kebab-case name
+    pub async fn call(&self, method: &str, params: Option<Value>) -> std::result::Result<Value, ErrorObject> {
+        // Requests without fields (e.g. `get-status`) may omit params entirely
+        let params = match params {
+            None | Some(Value::Null) => "{}".to_string(),
+            Some(params) => params.to_string(),
+        };
+        let response = self.wallet_server.call_with_serde(method, &params).await?;
+        serde_json::from_str(&response).map_err(|err| ErrorObject::new(INTERNAL_ERROR, err.to_string()))
+    }
+}
+
+fn parse_subscription(params: Option<Value>) -> std::result::Result<SubscriptionParams, ErrorObject> {
+    match params {
+        None | Some(Value::Null) => Ok(SubscriptionParams::default()),
+        Some(params) => {
+            serde_json::from_value(params).map_err(|err| ErrorObject::new(INVALID_PARAMS, format!("Invalid params: {err}")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_request_id_presence() {
+        let call: Request = serde_json::from_value(json!({ "jsonrpc": "2.0", "method": "ping", "id": null })).unwrap();
+        assert_eq!(call.id, Some(Value::Null));
+        let notification: Request = serde_json::from_value(json!({ "jsonrpc": "2.0", "method": "ping" })).unwrap();
+        assert_eq!(notification.id, None);
+        assert_eq!(notification.params, None);
+    }
+
+    #[test]
+    fn test_response_shape() {
+        let ok = serde_json::to_value(Response::new(json!(1), Ok(json!({ "message": "pong" })))).unwrap();
+        assert_eq!(ok, json!({ "jsonrpc": "2.0", "result": { "message": "pong" }, "id": 1 }));
+
+        let err = serde_json::to_value(Response::new(json!("a"), Err(WalletError::NotImplemented.into()))).unwrap();
+        assert_eq!(err, json!({ "jsonrpc": "2.0", "error": { "code": METHOD_NOT_FOUND, "message": "Method not found" }, "id": "a" }));
+    }
+
+    #[test]
+    fn test_subscription_filter() {
+        let session = Subscription::default();
+        assert!(!session.accepts("balance"));
+
+        session.subscribe(Some(vec!["balance".to_string()]));
+        assert!(session.accepts("balance"));
+        assert!(!session.accepts("maturity"));
+
+        session.subscribe(None);
+        session.unsubscribe(Some(vec!["sync-state".to_string()]));
+        assert!(session.accepts("maturity"));
+        assert!(!session.accepts("sync-state"));
+
+        session.subscribe(Some(vec!["sync-state".to_string()]));
+        assert!(session.accepts("sync-state"));
+
+        session.unsubscribe(None);
+        assert!(!session.accepts("balance"));
+    }
+}
@@ -0,0 +1,16 @@
+//!
+//! Headless wallet daemon exposing the [`WalletApi`](kaspa_wallet_core::api::WalletApi)
+//! over authenticated JSON-RPC 2.0 (HTTP `POST /` and websocket `GET /ws`).
+//!
+//! Every wallet API method is available under its kebab-case name (e.g. `wallet-open`,
+//! `accounts-send`) with the corresponding request struct as `params`. Websocket clients
+//! can additionally `subscribe` to wallet [`Events`](kaspa_wallet_core::events::Events),
+//! which are pushed as `event` notifications.
+//!
+
+pub mod auth;
+pub mod error;
+pub mod events;
+pub mod jsonrpc;
+pub mod result;
+pub mod server;
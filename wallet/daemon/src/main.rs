@@ -0,0 +1,135 @@
+use std::{net::SocketAddr, path::PathBuf, process::ExitCode, sync::Arc};
+
+use clap::Parser;
+use kaspa_consensus_core::network::NetworkId;
+use kaspa_core::{error, info, warn};
+use kaspa_wallet_core::{api::transport::WalletServer, prelude::*};
+use kaspa_walletd::{
+    auth::Auth,
+    events::EventBroadcaster,
+    jsonrpc::Dispatcher,
+    result::Result,
+    server::{Options, WalletDaemonServer},
+};
+use kaspa_wrpc_client::Resolver;
+
+const AUTH_TOKEN_ENV: &str = "KASPA_WALLETD_AUTH_TOKEN";
+const WALLET_SECRET_ENV: &str = "KASPA_WALLETD_WALLET_SECRET";
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+#[derive(Parser, Debug)]
+#[command(name = "kaspa-walletd", version, about = "Headless Kaspa wallet daemon serving the wallet API over JSON-RPC")]
+struct Args {
+    /// Network to operate on, e.g. `mainnet` or `testnet-10`
+    #[arg(long, default_value = "mainnet")]
+    network: NetworkId,
+
+    /// wRPC (Borsh) endpoint of the node; defaults to the local node of the selected network
+    #[arg(long = "rpcserver", short = 's', conflicts_with = "resolver")]
+    rpc_server: Option<String>,
+
+    /// Connect to a public node obtained from the resolver instead of a local node
+    #[arg(long)]
+    resolver: bool,
+
+    /// `interface:port` the JSON-RPC server listens on
+    #[arg(long, default_value = "127.0.0.1:8120")]
+    listen: SocketAddr,
+
+    /// File containing the bearer token clients must present (`KASPA_WALLETD_AUTH_TOKEN` is used otherwise)
+    #[arg(long)]
+    auth_token_file: Option<PathBuf>,
+
+    /// Serve without authentication; only allowed on a loopback interface
+    #[arg(long, conflicts_with = "auth_token_file")]
+    disable_auth: bool,
+
+    /// Wallet file to open at startup; otherwise clients are expected to call `wallet-open`
+    #[arg(long)]
+    wallet: Option<String>,
+
+    /// File containing the wallet secret (`KASPA_WALLETD_WALLET_SECRET` is used otherwise)
+    #[arg(long, requires = "wallet")]
+    wallet_secret_file: Option<PathBuf>,
+}
+
+impl Args {
+    fn auth(&self) -> Result<Auth> {
+        if self.disable_auth {
+            if !self.listen.ip().is_loopback() {
+                return Err("authentication can only be disabled when listening on a loopback interface".into());
+            }
+            return Ok(Auth::disabled());
+        }
+        let token = match &self.auth_token_file {
+            Some(path) => std::fs::read_to_string(path)?,
+            None => std::env::var(AUTH_TOKEN_ENV)
+                .map_err(|_| format!("no auth token provided, use --auth-token-file or set {AUTH_TOKEN_ENV}"))?,
+        };
+        let token = token.trim();
+        if token.is_empty() {
+            return Err("the auth token must not be empty".into());
+        }
+        Ok(Auth::new(token.to_string()))
+    }
+
+    fn wallet_secret(&self) -> Result<Secret> {
+        let secret = match &self.wallet_secret_file {
+            Some(path) => std::fs::read_to_string(path)?,
+            None => std::env::var(WALLET_SECRET_ENV)
+                .map_err(|_| format!("no wallet secret provided, use --wallet-secret-file or set {WALLET_SECRET_ENV}"))?,
+        };
+        Ok(Secret::new(secret.trim_end_matches(['\r', '\n']).as_bytes().to_vec()))
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    kaspa_core::log::init_logger(None, "");
+    let args = Args::parse();
+    match run(args).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            error!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(args: Args) -> Result<()> {
+    let auth = args.auth()?;
+    let resolver = args.resolver.then(Resolver::default);
+    let wallet = Arc::new(Wallet::try_new(Wallet::local_store()?, resolver, Some(args.network))?);
+    wallet.start().await?;
+
+    let events = Arc::new(EventBroadcaster::new(EVENT_CHANNEL_CAPACITY));
+    let wallet_server = Arc::new(WalletServer::new(wallet.clone(), events.clone()));
+    wallet_server.start();
+
+    if let Some(filename) = &args.wallet {
+        let wallet_secret = args.wallet_secret()?;
+        let request =
+            WalletOpenRequest { wallet_secret, filename: Some(filename.clone()), account_descriptors: false, legacy_accounts: None };
+        wallet.clone().wallet_open_call(request).await?;
+        wallet.clone().accounts_activate_call(AccountsActivateRequest { account_ids: None }).await?;
+        info!("Opened wallet `{filename}`");
+    }
+
+    // Without a resolver the wallet falls back to the node of the selected network on localhost
+    let url = args.rpc_server.clone().or_else(|| (!args.resolver).then(|| "127.0.0.1".to_string()));
+    let request =
+        ConnectRequest { url, network_id: args.network, retry_on_error: true, block_async_connect: false, require_sync: false };
+    wallet.clone().connect_call(request).await?;
+
+    let options = Options { listen_address: args.listen.to_string(), auth };
+    let server = WalletDaemonServer::new(Arc::new(Dispatcher::new(wallet_server.clone())), events, options);
+    let shutdown = async {
+        tokio::signal::ctrl_c().await.unwrap_or_else(|err| warn!("Unable to listen for the shutdown signal: {err}"));
+    };
+    let result = server.listen(shutdown).await;
+
+    info!("Shutting down");
+    wallet_server.stop_task().await?;
+    wallet.stop().await?;
+    result
+}
@@ -0,0 +1,116 @@
+use crate::{
+    auth::{Auth, authenticate},
+    events::{EventBroadcaster, Subscription},
+    jsonrpc::Dispatcher,
+    result::Result,
+};
+use axum::{
+    Router,
+    extract::{
+        State,
+        ws::{Message, WebSocket, WebSocketUpgrade},
+    },
+    http::{StatusCode, header},
+    middleware,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use kaspa_core::{debug, info, warn};
+use std::{future::Future, sync::Arc};
+use tokio::sync::{broadcast::error::RecvError, mpsc};
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub listen_address: String,
+    pub auth: Auth,
+}
+
+#[derive(Clone)]
+struct ServerContext {
+    dispatcher: Arc<Dispatcher>,
+    events: Arc<EventBroadcaster>,
+}
+
+pub struct WalletDaemonServer {
+    context: ServerContext,
+    options: Options,
+}
+
+impl WalletDaemonServer {
+    pub fn new(dispatcher: Arc<Dispatcher>, events: Arc<EventBroadcaster>, options: Options) -> Self {
+        Self { context: ServerContext { dispatcher, events }, options }
+    }
+
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/", post(rpc_handler))
+            .route("/ws", get(ws_handler))
+            .with_state(self.context.clone())
+            .layer(middleware::from_fn_with_state(Arc::new(self.options.auth.clone()), authenticate))
+    }
+
+    /// Serves requests until `shutdown` resolves
+    pub async fn listen(self, shutdown: impl Future<Output = ()> + Send + 'static) -> Result<()> {
+        let listener = tokio::net::TcpListener::bind(&self.options.listen_address).await?;
+        info!("Wallet JSON-RPC server listening on http://{} (websocket at /ws)", listener.local_addr()?);
+        if !self.options.auth.is_enabled() {
+            warn!("Authentication is disabled, any local process can control the wallet");
+        }
+        axum::serve(listener, self.router()).with_graceful_shutdown(shutdown).await?;
+        Ok(())
+    }
+}
+
+async fn rpc_handler(State(context): State<ServerContext>, body: String) -> Response {
+    match context.dispatcher.handle(&body, None).await {
+        Some(reply) => ([(header::CONTENT_TYPE, "application/json")], reply).into_response(),
+        None => StatusCode::NO_CONTENT.into_response(),
+    }
+}
+
+async fn ws_handler(State(context): State<ServerContext>, upgrade: WebSocketUpgrade) -> Response {
+    upgrade.on_upgrade(move |socket| ws_session(context, socket))
+}
+
+async fn ws_session(context: ServerContext, mut socket: WebSocket) {
+    let session = Arc::new(Subscription::default());
+    let mut events = context.events.subscribe();
+    // Calls are processed concurrently so that long running ones
+    // (e.g. `accounts-send`) do not hold back event delivery
+    let (replies, mut pending) = mpsc::unbounded_channel::<String>();
+
+    loop {
+        let outgoing = tokio::select! {
+            message = socket.recv() => match message {
+                Some(Ok(Message::Text(text))) => {
+                    let (dispatcher, session, replies) = (context.dispatcher.clone(), session.clone(), replies.clone());
+                    tokio::spawn(async move {
+                        if let Some(reply) = dispatcher.handle(text.as_str(), Some(&session)).await {
+                            let _ = replies.send(reply);
+                        }
+                    });
+                    continue;
+                }
+                Some(Ok(Message::Close(_))) | None => break,
+                Some(Ok(_)) => continue,
+                Some(Err(err)) => {
+                    debug!("Websocket session error: {err}");
+                    break;
+                }
+            },
+            Some(reply) = pending.recv() => reply,
+            event = events.recv() => match event {
+                Ok(event) if session.accepts(&event.kind) => event.message.clone(),
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("Websocket session lagging behind, {skipped} wallet events dropped");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            },
+        };
+        if socket.send(Message::Text(outgoing.into())).await.is_err() {
+            break;
+        }
+    }
+}
@@ -66,6 +66,9 @@ pub enum Error {
 
     #[error("Invalid UTF-8 sequence")]
     Utf8(#[from] std::str::Utf8Error),
+
+    #[error("MuSig2 -> {0}")]
+    MuSig2(#[from] crate::musig2::MuSig2Error),
 }
 
 impl Error {
@@ -10,6 +10,7 @@ pub mod derivation_path;
 pub mod error;
 mod imports;
 pub mod keypair;
+pub mod musig2;
 pub mod prelude;
 pub mod privatekey;
 pub mod privkeygen;
@@ -0,0 +1,718 @@
+//!
+//! MuSig2 ([BIP-327](https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki))
+//! Schnorr key aggregation and multi-party signing.
+//!
+//! A set of co-signers aggregates their public keys into a single key
+//! ([`KeyAggContext`]) that is indistinguishable from a regular Schnorr key
+//! and can receive funds on a regular P2PK address. Spending requires
+//! two communication rounds between the co-signers:
+//!
+//! 1. each co-signer generates a nonce pair ([`SecNonce::generate`]) and
+//!    shares the public part ([`PubNonce`]);
+//! 2. once all public nonces are known, each co-signer produces a partial
+//!    signature ([`sign_partial`]) using the aggregated nonce ([`AggNonce`]).
+//!
+//! The partial signatures are then combined ([`aggregate_partial_signatures`])
+//! into a single Schnorr signature valid for the aggregated key.
+//!
+//! A [`SecNonce`] must never be reused: signing two different messages with
+//! the same secret nonce reveals the secret key.
+//!
+
+use kaspa_addresses::{Address, Prefix, Version};
+use rand::RngCore;
+use secp256k1::{
+    Message, Parity, PublicKey, SECP256K1, Scalar, SecretKey, XOnlyPublicKey, constants::CURVE_ORDER, schnorr::Signature,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MuSig2Error {
+    #[error("no public keys to aggregate")]
+    NoPublicKeys,
+
+    #[error("no public nonces to aggregate")]
+    NoPublicNonces,
+
+    #[error("public key {0} is not a participant of the aggregated key")]
+    UnknownParticipant(PublicKey),
+
+    #[error("secret nonce was generated for a different key")]
+    NonceKeyMismatch,
+
+    #[error("invalid partial signature of {0}")]
+    InvalidPartialSignature(PublicKey),
+
+    #[error("aggregated signature is invalid")]
+    InvalidSignature,
+
+    #[error(transparent)]
+    Secp256k1(#[from] secp256k1::Error),
+}
+
+pub type MuSig2Result<T> = std::result::Result<T, MuSig2Error>;
+
+fn tagged_hash(tag: &str, parts: &[&[u8]]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    parts.iter().for_each(|part| hasher.update(part));
+    hasher.finalize().into()
+}
+
+/// Interprets a 32-byte hash as a scalar modulo the curve order
+fn scalar_mod_n(mut bytes: [u8; 32]) -> Scalar {
+    if let Ok(scalar) = Scalar::from_be_bytes(bytes) {
+        return scalar;
+    }
+    // the hash is below 2n, so a single subtraction reduces it
+    let mut borrow = 0u8;
+    for i in (0..32).rev() {
+        let (value, underflow1) = bytes[i].overflowing_sub(CURVE_ORDER[i]);
+        let (value, underflow2) = value.overflowing_sub(borrow);
+        bytes[i] = value;
+        borrow = (underflow1 || underflow2) as u8;
+    }
+    Scalar::from_be_bytes(bytes).expect("reduced below the curve order")
+}
+
+/// Aggregation of the co-signer public keys into a single Schnorr public key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyAggContext {
+    public_keys: Vec<PublicKey>,
+    coefficients: Vec<Scalar>,
+    aggregated_public_key: PublicKey,
+}
+
+impl KeyAggContext {
+    /// Aggregates the public keys in the given order. All co-signers
+    /// must use the same order to obtain the same aggregated key.
+    pub fn new(public_keys: Vec<PublicKey>) -> MuSig2Result<Self> {
+        if public_keys.is_empty() {
+            return Err(MuSig2Error::NoPublicKeys);
+        }
+
+        let serialized = public_keys.iter().map(|public_key| public_key.serialize()).collect::<Vec<_>>();
+        let list_hash = tagged_hash("KeyAgg list", &serialized.iter().map(|key| key.as_slice()).collect::<Vec<_>>());
+        let second_key = serialized.iter().find(|key| **key != serialized[0]);
+        let coefficients = serialized
+            .iter()
+            .map(|key| {
+                if Some(key) == second_key { Scalar::ONE } else { scalar_mod_n(tagged_hash("KeyAgg coefficient", &[&list_hash, key])) }
+            })
+            .collect::<Vec<_>>();
+
+        let points = public_keys
+            .iter()
+            .zip(coefficients.iter())
+            .map(|(public_key, coefficient)| public_key.mul_tweak(SECP256K1, coefficient))
+            .collect::<Result<Vec<_>, _>>()?;
+        let aggregated_public_key = PublicKey::combine_keys(&points.iter().collect::<Vec<_>>())?;
+
+        Ok(Self { public_keys, coefficients, aggregated_public_key })
+    }
+
+    /// Aggregates the public keys sorted in lexicographic order of their
+    /// serialization, making the aggregated key independent of the key order.
+    pub fn new_sorted(mut public_keys: Vec<PublicKey>) -> MuSig2Result<Self> {
+        public_keys.sort_by_key(|public_key| public_key.serialize());
+        Self::new(public_keys)
+    }
+
+    pub fn public_keys(&self) -> &[PublicKey] {
+        &self.public_keys
+    }
+
+    pub fn aggregated_public_key(&self) -> PublicKey {
+        self.aggregated_public_key
+    }
+
+    pub fn x_only_public_key(&self) -> XOnlyPublicKey {
+        self.aggregated_public_key.x_only_public_key().0
+    }
+
+    /// Returns the P2PK address of the aggregated public key
+    pub fn to_address(&self, prefix: Prefix) -> Address {
+        Address::new(prefix, Version::PubKey, &self.x_only_public_key().serialize())
+    }
+
+    fn coefficient(&self, public_key: &PublicKey) -> Option<Scalar> {
+        self.public_keys.iter().position(|key| key == public_key).map(|index| self.coefficients[index])
+    }
+
+    fn has_odd_y(&self) -> bool {
+        self.aggregated_public_key.x_only_public_key().1 == Parity::Odd
+    }
+}
+
+/// Secret nonce pair of a co-signer. It is consumed by [`sign_partial`]
+/// and must be kept private and never reused.
+pub struct SecNonce {
+    k1: SecretKey,
+    k2: SecretKey,
+    public_key: PublicKey,
+}
+
+impl SecNonce {
+    /// Generates a fresh nonce pair for `secret_key` following the BIP-327 `NonceGen`
+    /// algorithm. Binding the nonce to the message to be signed (when already known)
+    /// adds defense in depth against a weak random number generator.
+    pub fn generate(secret_key: &SecretKey, key_agg: &KeyAggContext, message: Option<&[u8; 32]>) -> (SecNonce, PubNonce) {
+        let public_key = secret_key.public_key(SECP256K1);
+        let aggregated_key = key_agg.x_only_public_key();
+        loop {
+            let mut rand = [0u8; 32];
+            rand::thread_rng().fill_bytes(&mut rand);
+            // a zero scalar has a negligible probability, retry with fresh randomness
+            if let Some(nonce) =
+                Self::generate_internal(&rand, Some(secret_key), &public_key, Some(&aggregated_key), message.map(|m| &m[..]), None)
+            {
+                return nonce;
+            }
+        }
+    }
+
+    /// `NonceGen` with the random bytes `rand` as an explicit input. Returns `None` if a nonce is zero.
+    fn generate_internal(
+        rand: &[u8; 32],
+        secret_key: Option<&SecretKey>,
+        public_key: &PublicKey,
+        aggregated_key: Option<&XOnlyPublicKey>,
+        message: Option<&[u8]>,
+        extra_in: Option<&[u8]>,
+    ) -> Option<(SecNonce, PubNonce)> {
+        let mut rand = *rand;
+        if let Some(secret_key) = secret_key {
+            let aux = tagged_hash("MuSig/aux", &[&rand]);
+            rand.iter_mut().zip(secret_key.secret_bytes().iter().zip(aux)).for_each(|(byte, (key, mask))| *byte = key ^ mask);
+        }
+        let aggregated_key = aggregated_key.map(|key| key.serialize().to_vec()).unwrap_or_default();
+        let message_prefixed = match message {
+            Some(message) => [&[1u8][..], &(message.len() as u64).to_be_bytes(), message].concat(),
+            None => vec![0u8],
+        };
+        let extra_in = extra_in.unwrap_or_default();
+
+        let k = |index: u8| {
+            let hash = tagged_hash(
+                "MuSig/nonce",
+                &[
+                    &rand,
+                    &[33],
+                    &public_key.serialize(),
+                    &[aggregated_key.len() as u8],
+                    &aggregated_key,
+                    &message_prefixed,
+                    &(extra_in.len() as u32).to_be_bytes(),
+                    extra_in,
+                    &[index],
+                ],
+            );
+            SecretKey::from_slice(&scalar_mod_n(hash).to_be_bytes()).ok()
+        };
+
+        let (k1, k2) = (k(0)?, k(1)?);
+        let pub_nonce = PubNonce { r1: k1.public_key(SECP256K1), r2: k2.public_key(SECP256K1) };
+        Some((SecNonce { k1, k2, public_key: *public_key }, pub_nonce))
+    }
+}
+
+impl Drop for SecNonce {
+    fn drop(&mut self) {
+        self.k1.non_secure_erase();
+        self.k2.non_secure_erase();
+    }
+}
+
+/// Adds two points, `None` standing for the point at infinity
+fn add_points(a: Option<PublicKey>, b: Option<PublicKey>) -> Option<PublicKey> {
+    match (a, b) {
+        (Some(a), Some(b)) => a.combine(&b).ok(),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+/// Public nonce pair shared by a co-signer during the first signing round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PubNonce {
+    pub r1: PublicKey,
+    pub r2: PublicKey,
+}
+
+impl PubNonce {
+    pub fn from_slice(bytes: &[u8]) -> MuSig2Result<Self> {
+        if bytes.len() != 66 {
+            return Err(MuSig2Error::Secp256k1(secp256k1::Error::InvalidPublicKey));
+        }
+        Ok(Self { r1: PublicKey::from_slice(&bytes[..33])?, r2: PublicKey::from_slice(&bytes[33..])? })
+    }
+
+    pub fn serialize(&self) -> [u8; 66] {
+        let mut bytes = [0u8; 66];
+        bytes[..33].copy_from_slice(&self.r1.serialize());
+        bytes[33..].copy_from_slice(&self.r2.serialize());
+        bytes
+    }
+}
+
+/// Sum of the public nonces of all co-signers. Each half is `None` when the sum
+/// is the point at infinity, which BIP-327 encodes as 33 zero bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AggNonce {
+    pub r1: Option<PublicKey>,
+    pub r2: Option<PublicKey>,
+}
+
+impl AggNonce {
+    pub fn aggregate<'a>(pub_nonces: impl IntoIterator<Item = &'a PubNonce>) -> MuSig2Result<Self> {
+        let mut pub_nonces = pub_nonces.into_iter().peekable();
+        if pub_nonces.peek().is_none() {
+            return Err(MuSig2Error::NoPublicNonces);
+        }
+        Ok(pub_nonces.fold(Self { r1: None, r2: None }, |sum, nonce| Self {
+            r1: add_points(sum.r1, Some(nonce.r1)),
+            r2: add_points(sum.r2, Some(nonce.r2)),
+        }))
+    }
+
+    pub fn from_slice(bytes: &[u8]) -> MuSig2Result<Self> {
+        if bytes.len() != 66 {
+            return Err(MuSig2Error::Secp256k1(secp256k1::Error::InvalidPublicKey));
+        }
+        let point = |bytes: &[u8]| if bytes == [0u8; 33] { Ok(None) } else { PublicKey::from_slice(bytes).map(Some) };
+        Ok(Self { r1: point(&bytes[..33])?, r2: point(&bytes[33..])? })
+    }
+
+    pub fn serialize(&self) -> [u8; 66] {
+        let mut bytes = [0u8; 66];
+        if let Some(r1) = self.r1 {
+            bytes[..33].copy_from_slice(&r1.serialize());
+        }
+        if let Some(r2) = self.r2 {
+            bytes[33..].copy_from_slice(&r2.serialize());
+        }
+        bytes
+    }
+}
+
+/// Partial signature produced by a co-signer during the second signing round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartialSignature(#[serde(with = "kaspa_utils::serde_bytes_fixed")] [u8; 32]);
+
+impl PartialSignature {
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    fn scalar(&self) -> Option<SecretKey> {
+        SecretKey::from_slice(&self.0).ok()
+    }
+}
+
+/// Signing session values shared by all co-signers
+struct Session {
+    /// Nonce coefficient
+    b: Scalar,
+    /// Final nonce point
+    r: PublicKey,
+    /// Schnorr challenge
+    e: Scalar,
+}
+
+impl Session {
+    fn new(key_agg: &KeyAggContext, agg_nonce: &AggNonce, message: &[u8]) -> MuSig2Result<Self> {
+        let aggregated_key = key_agg.x_only_public_key().serialize();
+        let b = scalar_mod_n(tagged_hash("MuSig/noncecoef", &[&agg_nonce.serialize(), &aggregated_key, message]));
+        // a final nonce at infinity is replaced by the generator, as specified by BIP-327
+        let r = add_points(agg_nonce.r1, agg_nonce.r2.and_then(|r2| r2.mul_tweak(SECP256K1, &b).ok())).unwrap_or_else(|| {
+            SecretKey::from_slice(&Scalar::ONE.to_be_bytes()).expect("one is a valid secret key").public_key(SECP256K1)
+        });
+        let e = scalar_mod_n(tagged_hash("BIP0340/challenge", &[&r.x_only_public_key().0.serialize(), &aggregated_key, message]));
+        Ok(Self { b, r, e })
+    }
+
+    fn has_odd_r(&self) -> bool {
+        self.r.x_only_public_key().1 == Parity::Odd
+    }
+}
+
+/// Produces the partial signature of `secret_key` over `message`, consuming the secret nonce.
+pub fn sign_partial(
+    key_agg: &KeyAggContext,
+    secret_key: &SecretKey,
+    sec_nonce: SecNonce,
+    agg_nonce: &AggNonce,
+    message: &[u8],
+) -> MuSig2Result<PartialSignature> {
+    let public_key = secret_key.public_key(SECP256K1);
+    if sec_nonce.public_key != public_key {
+        return Err(MuSig2Error::NonceKeyMismatch);
+    }
+    let a = key_agg.coefficient(&public_key).ok_or(MuSig2Error::UnknownParticipant(public_key))?;
+    let session = Session::new(key_agg, agg_nonce, message)?;
+
+    let (k1, k2) = if session.has_odd_r() { (sec_nonce.k1.negate(), sec_nonce.k2.negate()) } else { (sec_nonce.k1, sec_nonce.k2) };
+    let d = if key_agg.has_odd_y() { secret_key.negate() } else { *secret_key };
+
+    // s = k1 + b * k2 + e * a * d
+    let s =
+        k1.add_tweak(&Scalar::from(k2.mul_tweak(&session.b)?))?.add_tweak(&Scalar::from(d.mul_tweak(&a)?.mul_tweak(&session.e)?))?;
+    Ok(PartialSignature(s.secret_bytes()))
+}
+
+/// Verifies the partial signature of a co-signer against its public nonce.
+pub fn verify_partial(
+    key_agg: &KeyAggContext,
+    partial_signature: &PartialSignature,
+    pub_nonce: &PubNonce,
+    public_key: &PublicKey,
+    agg_nonce: &AggNonce,
+    message: &[u8],
+) -> MuSig2Result<()> {
+    let invalid = || MuSig2Error::InvalidPartialSignature(*public_key);
+    let a = key_agg.coefficient(public_key).ok_or(MuSig2Error::UnknownParticipant(*public_key))?;
+    let session = Session::new(key_agg, agg_nonce, message)?;
+    let s = partial_signature.scalar().ok_or_else(invalid)?;
+
+    // s * G == R1 + b * R2 + e * a * P (with R and P negated according to the parity of the final nonce and the aggregated key)
+    let mut r = pub_nonce.r1.combine(&pub_nonce.r2.mul_tweak(SECP256K1, &session.b)?).map_err(|_| invalid())?;
+    if session.has_odd_r() {
+        r = r.negate(SECP256K1);
+    }
+    let mut p = public_key.mul_tweak(SECP256K1, &a)?.mul_tweak(SECP256K1, &session.e)?;
+    if key_agg.has_odd_y() {
+        p = p.negate(SECP256K1);
+    }
+    let expected = r.combine(&p).map_err(|_| invalid())?;
+    (s.public_key(SECP256K1) == expected).then_some(()).ok_or_else(invalid)
+}
+
+/// Combines the partial signatures of all co-signers into a Schnorr signature
+/// of `message` valid for the aggregated public key.
+pub fn aggregate_partial_signatures<'a>(
+    key_agg: &KeyAggContext,
+    agg_nonce: &AggNonce,
+    partial_signatures: impl IntoIterator<Item = &'a PartialSignature>,
+    message: &[u8; 32],
+) -> MuSig2Result<Signature> {
+    let session = Session::new(key_agg, agg_nonce, message)?;
+    let s = partial_signatures.into_iter().try_fold(None::<SecretKey>, |sum, partial| {
+        let scalar = partial.scalar().ok_or(MuSig2Error::InvalidSignature)?;
+        match sum {
+            Some(sum) => sum.add_tweak(&Scalar::from(scalar)).map(Some).map_err(|_| MuSig2Error::InvalidSignature),
+            None => Ok(Some(scalar)),
+        }
+    })?;
+    let s = s.ok_or(MuSig2Error::InvalidSignature)?;
+
+    let mut bytes = [0u8; 64];
+    bytes[..32].copy_from_slice(&session.r.x_only_public_key().0.serialize());
+    bytes[32..].copy_from_slice(&s.secret_bytes());
+    let signature = Signature::from_slice(&bytes)?;
+    SECP256K1
+        .verify_schnorr(&signature, &Message::from_digest(*message), &key_agg.x_only_public_key())
+        .map_err(|_| MuSig2Error::InvalidSignature)?;
+    Ok(signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kaspa_utils::hex::FromHex;
+    use secp256k1::Keypair;
+
+    #[test]
+    fn test_musig2_signing() {
+        let keypairs = (0..3).map(|_| Keypair::new(SECP256K1, &mut rand::thread_rng())).collect::<Vec<_>>();
+        let public_keys = keypairs.iter().map(|kp| kp.public_key()).collect::<Vec<_>>();
+        let key_agg = KeyAggContext::new_sorted(public_keys.clone()).unwrap();
+        assert_eq!(key_agg, KeyAggContext::new_sorted(public_keys.iter().rev().copied().collect()).unwrap());
+        assert_eq!(key_agg.to_address(Prefix::Testnet).version, Version::PubKey);
+
+        let message = [42u8; 32];
+        let (sec_nonces, pub_nonces): (Vec<_>, Vec<_>) =
+            keypairs.iter().map(|kp| SecNonce::generate(&kp.secret_key(), &key_agg, Some(&message))).unzip();
+        let agg_nonce = AggNonce::aggregate(&pub_nonces).unwrap();
+
+        let partial_signatures = keypairs
+            .iter()
+            .zip(sec_nonces)
+            .map(|(kp, sec_nonce)| sign_partial(&key_agg, &kp.secret_key(), sec_nonce, &agg_nonce, &message).unwrap())
+            .collect::<Vec<_>>();
+        for ((kp, pub_nonce), partial) in keypairs.iter().zip(pub_nonces.iter()).zip(partial_signatures.iter()) {
+            verify_partial(&key_agg, partial, pub_nonce, &kp.public_key(), &agg_nonce, &message).unwrap();
+        }
+        assert_eq!(
+            verify_partial(&key_agg, &partial_signatures[0], &pub_nonces[1], &public_keys[1], &agg_nonce, &message),
+            Err(MuSig2Error::InvalidPartialSignature(public_keys[1]))
+        );
+
+        let signature = aggregate_partial_signatures(&key_agg, &agg_nonce, &partial_signatures, &message).unwrap();
+        SECP256K1.verify_schnorr(&signature, &Message::from_digest(message), &key_agg.x_only_public_key()).unwrap();
+
+        // a missing co-signer invalidates the aggregated signature
+        assert_eq!(
+            aggregate_partial_signatures(&key_agg, &agg_nonce, &partial_signatures[..2], &message),
+            Err(MuSig2Error::InvalidSignature)
+        );
+    }
+
+    fn public_keys(keys: &[&str]) -> Vec<PublicKey> {
+        keys.iter().map(|key| PublicKey::from_slice(&Vec::<u8>::from_hex(key).unwrap()).unwrap()).collect()
+    }
+
+    // Test vectors of BIP-327: https://github.com/bitcoin/bips/tree/master/bip-0327/vectors
+
+    #[test]
+    fn test_bip327_key_agg_vectors() {
+        let keys = public_keys(&[
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66",
+        ]);
+        for (indices, expected) in [
+            (vec![0, 1, 2], "90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C"),
+            (vec![2, 1, 0], "6204DE8B083426DC6EAF9502D27024D53FC826BF7D2012148A0575435DF54B2B"),
+            (vec![0, 0, 0], "B436E3BAD62B8CD409969A224731C193D051162D8C5AE8B109306127DA3AA935"),
+            (vec![0, 0, 1, 1], "69BC22BFA5D106306E48A20679DE1D7389386124D07571D0D872686028C26A3E"),
+        ] {
+            let key_agg = KeyAggContext::new(indices.into_iter().map(|index| keys[index]).collect()).unwrap();
+            assert_eq!(key_agg.x_only_public_key().serialize(), <[u8; 32]>::from_hex(expected).unwrap());
+        }
+
+        // invalid public keys are rejected before aggregation
+        for invalid in [
+            "020000000000000000000000000000000000000000000000000000000000000005",
+            "02FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30",
+            "04F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        ] {
+            assert!(PublicKey::from_slice(&Vec::<u8>::from_hex(invalid).unwrap()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_bip327_nonce_gen_vectors() {
+        let rand = [0x0f; 32];
+        let secret_key = SecretKey::from_slice(&[2; 32]).unwrap();
+        let aggregated_key = XOnlyPublicKey::from_slice(&[7; 32]).unwrap();
+        let keys = public_keys(&[
+            "024D4B6CD1361032CA9BD2AEB9D900AA4D45D9EAD80AC9423374C451A7254D0766",
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        ]);
+        assert_eq!(secret_key.public_key(SECP256K1), keys[0]);
+        for (secret_key, public_key, aggregated_key, message, extra_in, expected_sec_nonce, expected_pub_nonce) in [
+            (
+                Some(&secret_key),
+                keys[0],
+                Some(&aggregated_key),
+                Some(vec![1; 32]),
+                Some(vec![8; 32]),
+                "B114E502BEAA4E301DD08A50264172C84E41650E6CB726B410C0694D59EFFB6495B5CAF28D045B973D63E3C99A44B807BDE375FD6CB39E46DC4A511708D0E9D2",
+                "02F7BE7089E8376EB355272368766B17E88E7DB72047D05E56AA881EA52B3B35DF02C29C8046FDD0DED4C7E55869137200FBDBFE2EB654267B6D7013602CAED3115A",
+            ),
+            (
+                Some(&secret_key),
+                keys[0],
+                Some(&aggregated_key),
+                Some(vec![]),
+                Some(vec![8; 32]),
+                "E862B068500320088138468D47E0E6F147E01B6024244AE45EAC40ACE5929B9F0789E051170B9E705D0B9EB49049A323BBBBB206D8E05C19F46C6228742AA7A9",
+                "023034FA5E2679F01EE66E12225882A7A48CC66719B1B9D3B6C4DBD743EFEDA2C503F3FD6F01EB3A8E9CB315D73F1F3D287CAFBB44AB321153C6287F407600205109",
+            ),
+            (
+                Some(&secret_key),
+                keys[0],
+                Some(&aggregated_key),
+                Some(vec![0x26; 38]),
+                Some(vec![8; 32]),
+                "3221975ACBDEA6820EABF02A02B7F27D3A8EF68EE42787B88CBEFD9AA06AF3632EE85B1A61D8EF31126D4663A00DD96E9D1D4959E72D70FE5EBB6E7696EBA66F",
+                "02E5BBC21C69270F59BD634FCBFA281BE9D76601295345112C58954625BF23793A021307511C79F95D38ACACFF1B4DA98228B77E65AA216AD075E9673286EFB4EAF3",
+            ),
+            (
+                None,
+                keys[1],
+                None,
+                None,
+                None,
+                "89BDD787D0284E5E4D5FC572E49E316BAB7E21E3B1830DE37DFE80156FA41A6D0B17AE8D024C53679699A6FD7944D9C4A366B514BAF43088E0708B1023DD2897",
+                "02C96E7CB1E8AA5DAC64D872947914198F607D90ECDE5200DE52978AD5DED63C000299EC5117C2D29EDEE8A2092587C3909BE694D5CFF0667D6C02EA4059F7CD9786",
+            ),
+        ] {
+            let (sec_nonce, pub_nonce) =
+                SecNonce::generate_internal(&rand, secret_key, &public_key, aggregated_key, message.as_deref(), extra_in.as_deref())
+                    .unwrap();
+            assert_eq!(
+                [sec_nonce.k1.secret_bytes(), sec_nonce.k2.secret_bytes()].concat(),
+                Vec::<u8>::from_hex(expected_sec_nonce).unwrap()
+            );
+            assert_eq!(sec_nonce.public_key, public_key);
+            assert_eq!(pub_nonce.serialize(), <[u8; 66]>::from_hex(expected_pub_nonce).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_bip327_nonce_agg_vectors() {
+        let pub_nonces = [
+            "020151C80F435648DF67A22B749CD798CE54E0321D034B92B709B567D60A42E66603BA47FBC1834437B3212E89A84D8425E7BF12E0245D98262268EBDCB385D50641",
+            "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A60248C264CDD57D3C24D79990B0F865674EB62A0F9018277A95011B41BFC193B833",
+            "020151C80F435648DF67A22B749CD798CE54E0321D034B92B709B567D60A42E6660279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            "03FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A60379BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+        ]
+        .map(|nonce| PubNonce::from_slice(&Vec::<u8>::from_hex(nonce).unwrap()).unwrap());
+
+        let agg_nonce = AggNonce::aggregate(&pub_nonces[0..2]).unwrap();
+        assert_eq!(
+            agg_nonce.serialize(),
+            <[u8; 66]>::from_hex("035FE1873B4F2967F52FEA4A06AD5A8ECCBE9D0FD73068012C894E2E87CCB5804B024725377345BDE0E9C33AF3C43C0A29A9249F2F2956FA8CFEB55C8573D0262DC8").unwrap()
+        );
+
+        // the sum of the second points is the point at infinity, serialized as 33 zero bytes
+        let agg_nonce = AggNonce::aggregate(&pub_nonces[2..4]).unwrap();
+        assert_eq!(agg_nonce.r2, None);
+        assert_eq!(
+            agg_nonce.serialize(),
+            <[u8; 66]>::from_hex("035FE1873B4F2967F52FEA4A06AD5A8ECCBE9D0FD73068012C894E2E87CCB5804B000000000000000000000000000000000000000000000000000000000000000000").unwrap()
+        );
+        assert_eq!(AggNonce::from_slice(&agg_nonce.serialize()).unwrap(), agg_nonce);
+
+        let invalid = "04FF406FFD8ADB9CD29877E4985014F66A59F6CD01C0E88CAA8E5F3166B1F676A60248C264CDD57D3C24D79990B0F865674EB62A0F9018277A95011B41BFC193B833";
+        assert!(PubNonce::from_slice(&Vec::<u8>::from_hex(invalid).unwrap()).is_err());
+        assert_eq!(AggNonce::aggregate(&[]), Err(MuSig2Error::NoPublicNonces));
+    }
+
+    #[test]
+    fn test_bip327_sign_verify_vectors() {
+        let secret_key =
+            SecretKey::from_slice(&<[u8; 32]>::from_hex("7FB9E0E687ADA1EEBF7ECFE2F21E73EBDB51A7D450948DFE8D76D7F2D1007671").unwrap())
+                .unwrap();
+        let keys = public_keys(&[
+            "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA661",
+        ]);
+        assert_eq!(secret_key.public_key(SECP256K1), keys[0]);
+        let sec_nonce = || SecNonce {
+            k1: SecretKey::from_slice(
+                &<[u8; 32]>::from_hex("508B81A611F100A6B2B6B29656590898AF488BCF2E1F55CF22E5CFB84421FE61").unwrap(),
+            )
+            .unwrap(),
+            k2: SecretKey::from_slice(
+                &<[u8; 32]>::from_hex("FA27FD49B1D50085B481285E1CA205D55C82CC1B31FF5CD54A489829355901F7").unwrap(),
+            )
+            .unwrap(),
+            public_key: keys[0],
+        };
+        let pub_nonces = [
+            "0337C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+            "0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F817980279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            "032DE2662628C90B03F5E720284EB52FF7D71F4284F627B68A853D78C78E1FFE9303E4C5524E83FFE1493B9077CF1CA6BEB2090C93D930321071AD40B2F44E599046",
+            "0237C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0387BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480",
+        ]
+        .map(|nonce| PubNonce::from_slice(&Vec::<u8>::from_hex(nonce).unwrap()).unwrap());
+        let agg_nonces = [
+            "028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9",
+            "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+        ]
+        .map(|nonce| AggNonce::from_slice(&Vec::<u8>::from_hex(nonce).unwrap()).unwrap());
+        assert_eq!(AggNonce::aggregate(&pub_nonces[0..3]).unwrap(), agg_nonces[0]);
+        assert_eq!(AggNonce::aggregate([&pub_nonces[0], &pub_nonces[3]]).unwrap(), agg_nonces[1]);
+        let messages =
+            [Vec::<u8>::from_hex("F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF").unwrap(), vec![], vec![0x26; 38]];
+
+        for (key_indices, nonce_indices, agg_nonce_index, message_index, signer_index, expected) in [
+            (vec![0, 1, 2], vec![0, 1, 2], 0, 0, 0, "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB"),
+            (vec![1, 0, 2], vec![1, 0, 2], 0, 0, 1, "9FF2F7AAA856150CC8819254218D3ADEEB0535269051897724F9DB3789513A52"),
+            (vec![1, 2, 0], vec![1, 2, 0], 0, 0, 2, "FA23C359F6FAC4E7796BB93BC9F0532A95468C539BA20FF86D7C76ED92227900"),
+            // both halves of the aggregated nonce are the point at infinity
+            (vec![0, 1], vec![0, 3], 1, 0, 0, "AE386064B26105404798F75DE2EB9AF5EDA5387B064B83D049CB7C5E08879531"),
+            // empty message
+            (vec![0, 1, 2], vec![0, 1, 2], 0, 1, 0, "D7D63FFD644CCDA4E62BC2BC0B1D02DD32A1DC3030E155195810231D1037D82D"),
+            // 38-byte message
+            (vec![0, 1, 2], vec![0, 1, 2], 0, 2, 0, "E184351828DA5094A97C79CABDAAA0BFB87608C32E8829A4DF5340A6F243B78C"),
+        ] {
+            let key_agg = KeyAggContext::new(key_indices.iter().map(|&index| keys[index]).collect()).unwrap();
+            let (agg_nonce, message) = (&agg_nonces[agg_nonce_index], &messages[message_index]);
+            let partial = sign_partial(&key_agg, &secret_key, sec_nonce(), agg_nonce, message).unwrap();
+            assert_eq!(partial.to_bytes(), <[u8; 32]>::from_hex(expected).unwrap());
+            let pub_nonce = &pub_nonces[nonce_indices[signer_index]];
+            verify_partial(&key_agg, &partial, pub_nonce, &keys[key_indices[signer_index]], agg_nonce, message).unwrap();
+        }
+
+        let key_agg = KeyAggContext::new(keys.clone()).unwrap();
+        let partial = PartialSignature::from_bytes(
+            <[u8; 32]>::from_hex("012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB").unwrap(),
+        );
+        let negated = PartialSignature::from_bytes(partial.scalar().unwrap().negate().secret_bytes());
+        let exceeding = PartialSignature::from_bytes(CURVE_ORDER);
+        for (partial, signer_index) in [(negated, 0), (partial, 1), (exceeding, 0)] {
+            assert_eq!(
+                verify_partial(&key_agg, &partial, &pub_nonces[signer_index], &keys[signer_index], &agg_nonces[0], &messages[0]),
+                Err(MuSig2Error::InvalidPartialSignature(keys[signer_index]))
+            );
+        }
+
+        // the secret nonce must belong to the signing key
+        let other_key = SecretKey::from_slice(&[2; 32]).unwrap();
+        assert!(matches!(
+            sign_partial(&key_agg, &other_key, sec_nonce(), &agg_nonces[0], &messages[0]),
+            Err(MuSig2Error::NonceKeyMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_bip327_sig_agg_vectors() {
+        let keys = public_keys(&[
+            "03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9",
+            "02D2DC6F5DF7C56ACF38C7FA0AE7A759AE30E19B37359DFDE015872324C7EF6E05",
+            "03C7FB101D97FF930ACD0C6760852EF64E69083DE0B06AC6335724754BB4B0522C",
+        ]);
+        let partial_signatures = [
+            "B15D2CD3C3D22B04DAE438CE653F6B4ECF042F42CFDED7C41B64AAF9B4AF53FB",
+            "6193D6AC61B354E9105BBDC8937A3454A6D705B6D57322A5A472A02CE99FCB64",
+            "9A87D3B79EC67228CB97878B76049B15DBD05B8158D17B5B9114D3C226887505",
+            "66F82EA90923689B855D36C6B7E032FB9970301481B99E01CDB4D6AC7C347A15",
+        ]
+        .map(|partial| PartialSignature::from_bytes(<[u8; 32]>::from_hex(partial).unwrap()));
+        let message = <[u8; 32]>::from_hex("599C67EA410D005B9DA90817CF03ED3B1C868E4DA4EDF00A5880B0082C237869").unwrap();
+
+        for (agg_nonce, key_indices, partial_indices, expected) in [
+            (
+                "0341432722C5CD0268D829C702CF0D1CBCE57033EED201FD335191385227C3210C03D377F2D258B64AADC0E16F26462323D701D286046A2EA93365656AFD9875982B",
+                [0, 1],
+                [0, 1],
+                "041DA22223CE65C92C9A0D6C2CAC828AAF1EEE56304FEC371DDF91EBB2B9EF0912F1038025857FEDEB3FF696F8B99FA4BB2C5812F6095A2E0004EC99CE18DE1E",
+            ),
+            (
+                "0224AFD36C902084058B51B5D36676BBA4DC97C775873768E58822F87FE437D792028CB15929099EEE2F5DAE404CD39357591BA32E9AF4E162B8D3E7CB5EFE31CB20",
+                [0, 2],
+                [2, 3],
+                "1069B67EC3D2F3C7C08291ACCB17A9C9B8F2819A52EB5DF8726E17E7D6B52E9F01800260A7E9DAC450F4BE522DE4CE12BA91AEAF2B4279219EF74BE1D286ADD9",
+            ),
+        ] {
+            let key_agg = KeyAggContext::new(key_indices.iter().map(|&index| keys[index]).collect()).unwrap();
+            let agg_nonce = AggNonce::from_slice(&Vec::<u8>::from_hex(agg_nonce).unwrap()).unwrap();
+            let partials = partial_indices.iter().map(|&index| &partial_signatures[index]);
+            let signature = aggregate_partial_signatures(&key_agg, &agg_nonce, partials, &message).unwrap();
+            assert_eq!(signature.serialize(), <[u8; 64]>::from_hex(expected).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_scalar_mod_n() {
+        // (2^256 - 1) - n
+        let mut expected = [0u8; 32];
+        expected[15..]
+            .copy_from_slice(&[0x01, 0x45, 0x51, 0x23, 0x19, 0x50, 0xb7, 0x5f, 0xc4, 0x40, 0x2d, 0xa1, 0x73, 0x2f, 0xc9, 0xbe, 0xbe]);
+        assert_eq!(scalar_mod_n([0xff; 32]).to_be_bytes(), expected);
+        assert_eq!(scalar_mod_n(CURVE_ORDER), Scalar::ZERO);
+        assert_eq!(scalar_mod_n([7; 32]).to_be_bytes(), [7; 32]);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::binary;
 use crate::error::Error;
 use crate::prelude::*;
 use crate::pskt::{Inner as PSKTInner, PSKT};
@@ -67,14 +68,35 @@ impl Bundle {
         Ok(format!("PSKB{}", hex::encode(serde_json::to_string(self)?)))
     }
 
+    /// Deserializes a bundle from either the `PSKB`-prefixed hex JSON form or the base64 binary form
     pub fn deserialize(hex_data: &str) -> Result<Self, Error> {
         if let Some(hex_data) = hex_data.strip_prefix("PSKB") {
             Ok(serde_json::from_slice(hex::decode(hex_data)?.as_slice())?)
+        } else if hex_data.trim_start().starts_with(binary::PSKB_BASE64_PREFIX) {
+            Self::from_base64(hex_data)
         } else {
             Err(Error::PskbPrefixError)
         }
     }
 
+    /// Serializes the bundle in the compact binary format
+    pub fn to_bytes(&self) -> Vec<u8> {
+        binary::encode_bundle(self.0.iter())
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        Ok(Bundle(binary::decode_bundle(data)?))
+    }
+
+    /// Serializes the bundle in the compact binary format, encoded as base64
+    pub fn to_base64(&self) -> String {
+        binary::to_base64(&self.to_bytes())
+    }
+
+    pub fn from_base64(data: &str) -> Result<Self, Error> {
+        Self::from_bytes(&binary::from_base64(data)?)
+    }
+
     pub fn display_format<F>(&self, network_id: NetworkId, sompi_formatter: F) -> String
     where
         F: Fn(u64, &NetworkType) -> String,
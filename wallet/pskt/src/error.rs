@@ -42,10 +42,26 @@ pub enum Error {
     PskbPrefixError,
     #[error("PSKT serialization requires 'PSKT' prefix")]
     PsktPrefixError,
+    #[error("Binary decode error: {0}")]
+    BinaryDecode(#[from] crate::binary::DecodeError),
+    #[error("Base64 decode error: {0}")]
+    Base64Decode(#[from] base64::DecodeError),
     #[error("Cannot set payload on PSKT version {0}, payload requires version 1 or higher")]
     PayloadRequiresVersion1(crate::pskt::Version),
     #[error("Outputs not allowed to contain covenant due to pskt or tx versions mismatch")]
     Covenant,
+    #[error("Redeem script does not match the {0} template")]
+    TemplateMismatch(&'static str),
+    #[error("Missing signature for input {0}")]
+    MissingSignature(usize),
+    #[error(transparent)]
+    Htlc(#[from] kaspa_txscript::HtlcCreateError),
+    #[error(transparent)]
+    Vault(#[from] kaspa_txscript::VaultCreateError),
+    #[error(transparent)]
+    MuSig2(#[from] kaspa_wallet_keys::musig2::MuSig2Error),
+    #[error("Missing MuSig2 public nonces for input {0}")]
+    MissingMuSig2Nonces(usize),
 }
 #[derive(thiserror::Error, Debug)]
 pub enum ConstructorError {
@@ -7,6 +7,7 @@ use kaspa_consensus_core::{
     hashing::sighash_type::{SIG_HASH_ALL, SigHashType},
     tx::{TransactionId, TransactionOutpoint, UtxoEntry},
 };
+use kaspa_wallet_keys::musig2::{PartialSignature, PubNonce};
 use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, marker::PhantomData, ops::Add};
 
@@ -47,6 +48,17 @@ pub struct Input {
     /// The finalized, fully-constructed scriptSig with signatures and any other
     /// scripts necessary for this input to pass validation.
     pub final_script_sig: Option<Vec<u8>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(setter)]
+    /// Public keys of the MuSig2 co-signers, in key aggregation order, whose
+    /// aggregated key is the public key of the spent output.
+    pub musig2_participants: Vec<secp256k1::PublicKey>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    /// A map from MuSig2 co-signer public keys to their public nonces.
+    pub musig2_pub_nonces: BTreeMap<secp256k1::PublicKey, PubNonce>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    /// A map from MuSig2 co-signer public keys to their partial signatures.
+    pub musig2_partial_sigs: BTreeMap<secp256k1::PublicKey, PartialSignature>,
     #[serde(skip_serializing, default)]
     pub(crate) hidden: PhantomData<()>, // prevents manual filling of fields
     #[builder(setter)]
@@ -71,6 +83,9 @@ impl Default for Input {
             sig_op_count: Default::default(),
             bip32_derivations: Default::default(),
             final_script_sig: Default::default(),
+            musig2_participants: Default::default(),
+            musig2_pub_nonces: Default::default(),
+            musig2_partial_sigs: Default::default(),
             hidden: Default::default(),
             proprietaries: Default::default(),
             unknowns: Default::default(),
@@ -129,6 +144,15 @@ impl Add for Input {
         };
 
         self.bip32_derivations = combine_if_no_conflicts(self.bip32_derivations, rhs.bip32_derivations)?;
+        self.musig2_participants = match (self.musig2_participants, rhs.musig2_participants) {
+            (left, right) if right.is_empty() || left == right => left,
+            (left, right) if left.is_empty() => right,
+            (left, right) => return Err(CombineError::NotCompatibleMuSig2Participants { this: left, that: right }),
+        };
+        self.musig2_pub_nonces = combine_if_no_conflicts(self.musig2_pub_nonces, rhs.musig2_pub_nonces)
+            .map_err(CombineError::NotCompatibleMuSig2PubNonces)?;
+        self.musig2_partial_sigs = combine_if_no_conflicts(self.musig2_partial_sigs, rhs.musig2_partial_sigs)
+            .map_err(CombineError::NotCompatibleMuSig2PartialSigs)?;
         self.proprietaries =
             combine_if_no_conflicts(self.proprietaries, rhs.proprietaries).map_err(CombineError::NotCompatibleProprietary)?;
         self.unknowns = combine_if_no_conflicts(self.unknowns, rhs.unknowns).map_err(CombineError::NotCompatibleUnknownField)?;
@@ -161,6 +185,12 @@ pub enum CombineError {
 
     #[error("Two different derivations for the same key")]
     NotCompatibleBip32Derivations(#[from] CombineMapErr<secp256k1::PublicKey, Option<KeySource>>),
+    #[error("Two different MuSig2 participant lists detected")]
+    NotCompatibleMuSig2Participants { this: Vec<secp256k1::PublicKey>, that: Vec<secp256k1::PublicKey> },
+    #[error("Two different MuSig2 public nonces for the same key")]
+    NotCompatibleMuSig2PubNonces(CombineMapErr<secp256k1::PublicKey, PubNonce>),
+    #[error("Two different MuSig2 partial signatures for the same key")]
+    NotCompatibleMuSig2PartialSigs(CombineMapErr<secp256k1::PublicKey, PartialSignature>),
     #[error("Two different unknown field values")]
     NotCompatibleUnknownField(CombineMapErr<String, serde_value::Value>),
     #[error("Two different proprietary values")]
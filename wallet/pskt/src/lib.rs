@@ -5,6 +5,7 @@
 //! multiple `PSKT` instances allowing for exchange of Kaspa sweep transactions.
 //!
 
+pub mod binary;
 pub mod bundle;
 pub mod error;
 pub mod global;
@@ -12,6 +13,7 @@ pub mod input;
 pub mod output;
 pub mod pskt;
 pub mod role;
+pub mod template;
 pub mod wasm;
 
 mod convert;
@@ -3,8 +3,12 @@
 //!
 
 use kaspa_bip32::{DerivationPath, KeyFingerprint, secp256k1};
-use kaspa_consensus_core::{Hash, hashing::sighash::SigHashReusedValuesUnsync};
+use kaspa_consensus_core::{
+    Hash,
+    hashing::sighash::{SigHashReusedValuesUnsync, calc_schnorr_signature_hash},
+};
 use kaspa_txscript::EngineCtx;
+use kaspa_wallet_keys::musig2::{self, AggNonce, KeyAggContext, SecNonce};
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::{collections::BTreeMap, fmt::Display, fmt::Formatter, future::Future, marker::PhantomData, ops::Deref};
@@ -165,6 +169,17 @@ impl<R> PSKT<R> {
         SignableTransaction::with_entries(tx, entries)
     }
 
+    /// Schnorr signature hashes of the inputs, which are the messages signed by MuSig2 co-signers
+    fn musig2_messages(&self) -> Vec<[u8; 32]> {
+        let tx = self.unsigned_tx();
+        let reused_values = SigHashReusedValuesUnsync::new();
+        self.inputs
+            .iter()
+            .enumerate()
+            .map(|(idx, input)| calc_schnorr_signature_hash(&tx.as_verifiable(), idx, input.sighash_type, &reused_values).as_bytes())
+            .collect()
+    }
+
     fn calculate_id_internal(&self) -> TransactionId {
         self.unsigned_tx().tx.id()
     }
@@ -184,6 +199,24 @@ impl<R> PSKT<R> {
             Err(Error::PsktPrefixError)
         }
     }
+
+    /// Serializes the PSKT in the compact binary format
+    pub fn to_bytes(&self) -> Vec<u8> {
+        crate::binary::encode_pskt(self)
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        Ok(crate::binary::decode_pskt(data)?.into())
+    }
+
+    /// Serializes the PSKT in the compact binary format, encoded as base64
+    pub fn to_base64(&self) -> String {
+        crate::binary::to_base64(&self.to_bytes())
+    }
+
+    pub fn from_base64(data: &str) -> Result<Self, Error> {
+        Self::from_bytes(&crate::binary::from_base64(data)?)
+    }
 }
 
 impl Default for PSKT<Creator> {
@@ -336,6 +369,48 @@ impl PSKT<Signer> {
         Ok(self)
     }
 
+    /// First MuSig2 signing round: generates the nonces of `secret_key` for every input it co-signs
+    /// and adds the public nonces to the PSKT. The returned secret nonces (indexed by input) must be
+    /// kept private by the co-signer and passed to [`pass_musig2_partial_sigs`](Self::pass_musig2_partial_sigs)
+    /// once the public nonces of all co-signers have been combined.
+    pub fn pass_musig2_nonces(mut self, secret_key: &secp256k1::SecretKey) -> Result<(Self, Vec<Option<SecNonce>>), Error> {
+        let public_key = secret_key.public_key(secp256k1::SECP256K1);
+        let messages = self.musig2_messages();
+        let mut sec_nonces = Vec::with_capacity(messages.len());
+        for (input, message) in self.inner_pskt.inputs.iter_mut().zip(messages) {
+            if !input.musig2_participants.contains(&public_key) {
+                sec_nonces.push(None);
+                continue;
+            }
+            let key_agg = KeyAggContext::new(input.musig2_participants.clone())?;
+            let (sec_nonce, pub_nonce) = SecNonce::generate(secret_key, &key_agg, Some(&message));
+            input.musig2_pub_nonces.insert(public_key, pub_nonce);
+            sec_nonces.push(Some(sec_nonce));
+        }
+        Ok((self, sec_nonces))
+    }
+
+    /// Second MuSig2 signing round: adds the partial signatures of `secret_key`
+    /// for the inputs it generated nonces for.
+    pub fn pass_musig2_partial_sigs(
+        mut self,
+        secret_key: &secp256k1::SecretKey,
+        sec_nonces: Vec<Option<SecNonce>>,
+    ) -> Result<Self, Error> {
+        let public_key = secret_key.public_key(secp256k1::SECP256K1);
+        let messages = self.musig2_messages();
+        for (index, ((input, message), sec_nonce)) in self.inner_pskt.inputs.iter_mut().zip(messages).zip(sec_nonces).enumerate() {
+            let Some(sec_nonce) = sec_nonce else {
+                continue;
+            };
+            let key_agg = KeyAggContext::new(input.musig2_participants.clone())?;
+            let agg_nonce = musig2_agg_nonce(index, input)?;
+            let partial_sig = musig2::sign_partial(&key_agg, secret_key, sec_nonce, &agg_nonce, &message)?;
+            input.musig2_partial_sigs.insert(public_key, partial_sig);
+        }
+        Ok(self)
+    }
+
     pub fn calculate_id(&self) -> TransactionId {
         self.calculate_id_internal()
     }
@@ -406,6 +481,29 @@ impl<R> std::ops::Add<PSKT<R>> for PSKT<Combiner> {
 }
 
 impl PSKT<Combiner> {
+    /// Aggregates the MuSig2 partial signatures of every input signed by all of its co-signers
+    /// into a Schnorr signature of the aggregated key, stored in the input partial signatures.
+    /// Inputs still missing partial signatures are left untouched.
+    pub fn aggregate_musig2_signatures(mut self) -> Result<Self, Error> {
+        let messages = self.musig2_messages();
+        for (index, (input, message)) in self.inner_pskt.inputs.iter_mut().zip(messages).enumerate() {
+            let participants = &input.musig2_participants;
+            if participants.is_empty() || !participants.iter().all(|key| input.musig2_partial_sigs.contains_key(key)) {
+                continue;
+            }
+            let key_agg = KeyAggContext::new(participants.clone())?;
+            let agg_nonce = musig2_agg_nonce(index, input)?;
+            for key in participants.iter() {
+                let pub_nonce = input.musig2_pub_nonces.get(key).ok_or(Error::MissingMuSig2Nonces(index))?;
+                musig2::verify_partial(&key_agg, &input.musig2_partial_sigs[key], pub_nonce, key, &agg_nonce, &message)?;
+            }
+            let partial_sigs = participants.iter().map(|key| &input.musig2_partial_sigs[key]);
+            let signature = musig2::aggregate_partial_signatures(&key_agg, &agg_nonce, partial_sigs, &message)?;
+            input.partial_sigs.insert(key_agg.aggregated_public_key(), Signature::Schnorr(signature));
+        }
+        Ok(self)
+    }
+
     pub fn signer(self) -> PSKT<Signer> {
         PSKT { inner_pskt: self.inner_pskt, role: Default::default() }
     }
@@ -499,6 +597,17 @@ impl PSKT<Extractor> {
     }
 }
 
+/// Aggregates the public nonces of all MuSig2 co-signers of an input
+fn musig2_agg_nonce(index: usize, input: &Input) -> Result<AggNonce, Error> {
+    let pub_nonces = input
+        .musig2_participants
+        .iter()
+        .map(|key| input.musig2_pub_nonces.get(key))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(Error::MissingMuSig2Nonces(index))?;
+    Ok(AggNonce::aggregate(pub_nonces)?)
+}
+
 /// Error combining pskt.
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
 pub enum CombineError {
@@ -536,6 +645,67 @@ pub struct TxNotFinalized {}
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use kaspa_consensus_core::{
+        config::params::TESTNET_PARAMS,
+        tx::{ScriptPublicKey, TransactionOutpoint, UtxoEntry},
+    };
+    use kaspa_txscript::pay_to_address_script;
+    use secp256k1::{SecretKey, rand::thread_rng};
+
+    #[test]
+    fn test_musig2_signing() {
+        let secret_keys: Vec<_> = (0..3).map(|_| SecretKey::new(&mut thread_rng())).collect();
+        let public_keys = secret_keys.iter().map(|sk| sk.public_key(secp256k1::SECP256K1)).collect();
+        let key_agg = KeyAggContext::new(public_keys).unwrap();
+        let address = key_agg.to_address(kaspa_addresses::Prefix::Testnet);
+
+        let utxo_entry = UtxoEntry::new(100_000_000, pay_to_address_script(&address), 0, false, None);
+        let input = InputBuilder::default()
+            .utxo_entry(utxo_entry)
+            .previous_outpoint(TransactionOutpoint::new(TransactionId::from_bytes([1; 32]), 0))
+            .sig_op_count(1)
+            .musig2_participants(key_agg.public_keys().to_vec())
+            .build()
+            .unwrap();
+        let output =
+            OutputBuilder::default().amount(99_990_000).script_public_key(ScriptPublicKey::new(0, vec![].into())).build().unwrap();
+        let pskt = PSKT::<Creator>::default().constructor().input(input).output(output).unwrap().signer();
+
+        // first round: every co-signer adds its public nonces to its copy of the pskt
+        let (signed, sec_nonces): (Vec<_>, Vec<_>) = secret_keys.iter().map(|sk| pskt.clone().pass_musig2_nonces(sk).unwrap()).unzip();
+        assert!(sec_nonces.iter().all(|sec_nonces| sec_nonces[0].is_some()));
+        let (_, sec_nonce) = pskt.clone().pass_musig2_nonces(&SecretKey::new(&mut thread_rng())).unwrap();
+        assert!(sec_nonce[0].is_none());
+        // a co-signer cannot sign before the nonces of all co-signers are known
+        let (partial, sec_nonce) = pskt.clone().pass_musig2_nonces(&secret_keys[0]).unwrap();
+        assert!(matches!(partial.pass_musig2_partial_sigs(&secret_keys[0], sec_nonce), Err(Error::MissingMuSig2Nonces(0))));
+        let with_nonces = signed.into_iter().try_fold(pskt.combiner(), |combined, pskt| combined + pskt).unwrap().signer();
+
+        // second round: partial signatures are combined and aggregated into a single signature
+        let signed = secret_keys
+            .iter()
+            .zip(sec_nonces)
+            .map(|(sk, sec_nonces)| with_nonces.clone().pass_musig2_partial_sigs(sk, sec_nonces).unwrap())
+            .collect::<Vec<_>>();
+        let incomplete = (with_nonces.clone().combiner() + signed[0].clone()).unwrap().aggregate_musig2_signatures().unwrap();
+        assert!(incomplete.inputs[0].partial_sigs.is_empty());
+        let combined = signed.into_iter().try_fold(with_nonces.combiner(), |combined, pskt| combined + pskt).unwrap();
+        let aggregated = combined.aggregate_musig2_signatures().unwrap();
+        let signature = aggregated.inputs[0].partial_sigs[&key_agg.aggregated_public_key()];
+
+        let finalized = aggregated
+            .finalizer()
+            .finalize_sync(|inner: &Inner| -> Result<Vec<Vec<u8>>, String> {
+                let input = &inner.inputs[0];
+                let mut signature_script = vec![65u8];
+                signature_script.extend(signature.into_bytes());
+                signature_script.push(input.sighash_type.to_u8());
+                Ok(vec![signature_script])
+            })
+            .unwrap();
+        finalized.extractor().unwrap().extract_tx(&TESTNET_PARAMS).unwrap();
+    }
 
     // #[test]
     // fn it_works() {
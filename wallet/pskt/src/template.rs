@@ -0,0 +1,285 @@
+//!
+//! Construction and finalization of PSKTs spending the standard script templates
+//! of [`kaspa_txscript`]: hash-time-locked contracts ([`HtlcScript`]) and
+//! timelocked vaults ([`VaultScript`]).
+//!
+//! The construction helpers produce a single-input [`PSKT<Constructor>`] with the
+//! lock time and sequence required by the chosen spending path, so that signers
+//! commit to them. Once signed, the matching `finalize_*` helper assembles the
+//! signature scripts from the partial signatures of each input.
+//!
+
+use crate::error::Error;
+use crate::input::Input;
+use crate::pskt::{FinalizeError, Inner, InputBuilder, OutputBuilder, PSKT};
+use crate::role::{Constructor, Creator, Finalizer};
+use kaspa_consensus_core::tx::{ScriptPublicKey, TransactionOutpoint, UtxoEntry};
+use kaspa_txscript::{
+    HtlcScript, VaultScript, htlc_claim_signature_script, htlc_refund_signature_script, vault_recover_signature_script,
+    vault_withdraw_signature_script,
+};
+
+/// Builds a PSKT claiming an HTLC output to `destination`. The claim path is spendable
+/// at any time by the HTLC receiver, see [`finalize_htlc_claim`].
+pub fn htlc_claim(
+    utxo_entry: UtxoEntry,
+    outpoint: TransactionOutpoint,
+    redeem_script: Vec<u8>,
+    destination: ScriptPublicKey,
+    fee: u64,
+) -> Result<PSKT<Constructor>, Error> {
+    let htlc = HtlcScript::from_redeem_script(&redeem_script).ok_or(Error::TemplateMismatch("htlc"))?;
+    let input = template_input(utxo_entry.clone(), outpoint, redeem_script, htlc.sig_op_count())?;
+    template_pskt(input, &utxo_entry, destination, fee)
+}
+
+/// Builds a PSKT refunding an HTLC output to `destination`. The transaction lock time
+/// is set to the HTLC lock time, so the PSKT is only valid once that lock time is reached.
+pub fn htlc_refund(
+    utxo_entry: UtxoEntry,
+    outpoint: TransactionOutpoint,
+    redeem_script: Vec<u8>,
+    destination: ScriptPublicKey,
+    fee: u64,
+) -> Result<PSKT<Constructor>, Error> {
+    let htlc = HtlcScript::from_redeem_script(&redeem_script).ok_or(Error::TemplateMismatch("htlc"))?;
+    let mut input = template_input(utxo_entry.clone(), outpoint, redeem_script, htlc.sig_op_count())?;
+    input.min_time = Some(htlc.lock_time);
+    // OpCheckLockTimeVerify rejects finalized inputs
+    input.sequence = Some(0);
+    template_pskt(input, &utxo_entry, destination, fee)
+}
+
+/// Builds a PSKT withdrawing a vault output to `destination` with the owner key.
+/// The input sequence is set to the vault delay, so the PSKT is only valid once
+/// the output has aged by that delay.
+pub fn vault_withdraw(
+    utxo_entry: UtxoEntry,
+    outpoint: TransactionOutpoint,
+    redeem_script: Vec<u8>,
+    destination: ScriptPublicKey,
+    fee: u64,
+) -> Result<PSKT<Constructor>, Error> {
+    let vault = VaultScript::from_redeem_script(&redeem_script).ok_or(Error::TemplateMismatch("vault"))?;
+    let mut input = template_input(utxo_entry.clone(), outpoint, redeem_script, vault.sig_op_count())?;
+    input.sequence = Some(vault.delay);
+    template_pskt(input, &utxo_entry, destination, fee)
+}
+
+/// Builds a PSKT moving a vault output to `destination` with the recovery keys.
+pub fn vault_recover(
+    utxo_entry: UtxoEntry,
+    outpoint: TransactionOutpoint,
+    redeem_script: Vec<u8>,
+    destination: ScriptPublicKey,
+    fee: u64,
+) -> Result<PSKT<Constructor>, Error> {
+    let vault = VaultScript::from_redeem_script(&redeem_script).ok_or(Error::TemplateMismatch("vault"))?;
+    let input = template_input(utxo_entry.clone(), outpoint, redeem_script, vault.sig_op_count())?;
+    template_pskt(input, &utxo_entry, destination, fee)
+}
+
+fn template_input(
+    utxo_entry: UtxoEntry,
+    outpoint: TransactionOutpoint,
+    redeem_script: Vec<u8>,
+    sig_op_count: u8,
+) -> Result<Input, Error> {
+    Ok(InputBuilder::default()
+        .utxo_entry(utxo_entry)
+        .previous_outpoint(outpoint)
+        .sig_op_count(sig_op_count)
+        .redeem_script(redeem_script)
+        .build()?)
+}
+
+fn template_pskt(input: Input, utxo_entry: &UtxoEntry, destination: ScriptPublicKey, fee: u64) -> Result<PSKT<Constructor>, Error> {
+    if utxo_entry.amount <= fee {
+        return Err(Error::ExcessUnlockFeeError);
+    }
+    let output = OutputBuilder::default().amount(utxo_entry.amount - fee).script_public_key(destination).build()?;
+    PSKT::<Creator>::default().constructor().input(input).output(output)
+}
+
+/// Finalizes the HTLC claim of every input with the receiver signature and the secret preimage.
+pub fn finalize_htlc_claim(pskt: PSKT<Finalizer>, preimage: &[u8]) -> Result<PSKT<Finalizer>, FinalizeError<Error>> {
+    pskt.finalize_sync(|inner: &Inner| {
+        inner
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(index, input)| -> Result<Vec<u8>, Error> {
+                let (htlc, redeem_script) = htlc_input(input)?;
+                let signature = input_signature(index, input, &htlc.receiver)?;
+                Ok(htlc_claim_signature_script(redeem_script, &signature, preimage)?)
+            })
+            .collect()
+    })
+}
+
+/// Finalizes the HTLC refund of every input with the refund key signature.
+pub fn finalize_htlc_refund(pskt: PSKT<Finalizer>) -> Result<PSKT<Finalizer>, FinalizeError<Error>> {
+    pskt.finalize_sync(|inner: &Inner| {
+        inner
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(index, input)| -> Result<Vec<u8>, Error> {
+                let (htlc, redeem_script) = htlc_input(input)?;
+                let signature = input_signature(index, input, &htlc.refund)?;
+                Ok(htlc_refund_signature_script(redeem_script, &signature)?)
+            })
+            .collect()
+    })
+}
+
+/// Finalizes the vault withdrawal of every input with the owner signature.
+pub fn finalize_vault_withdraw(pskt: PSKT<Finalizer>) -> Result<PSKT<Finalizer>, FinalizeError<Error>> {
+    pskt.finalize_sync(|inner: &Inner| {
+        inner
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(index, input)| -> Result<Vec<u8>, Error> {
+                let (vault, redeem_script) = vault_input(input)?;
+                let signature = input_signature(index, input, &vault.owner)?;
+                Ok(vault_withdraw_signature_script(redeem_script, &signature)?)
+            })
+            .collect()
+    })
+}
+
+/// Finalizes the vault recovery of every input, using the signatures of the
+/// first `required` recovery keys (in redeem script order) that signed the input.
+pub fn finalize_vault_recover(pskt: PSKT<Finalizer>) -> Result<PSKT<Finalizer>, FinalizeError<Error>> {
+    pskt.finalize_sync(|inner: &Inner| {
+        inner
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(index, input)| -> Result<Vec<u8>, Error> {
+                let (vault, redeem_script) = vault_input(input)?;
+                let signatures = vault
+                    .recovery_keys
+                    .iter()
+                    .filter_map(|key| input_signature(index, input, key).ok())
+                    .take(vault.required)
+                    .collect::<Vec<_>>();
+                if signatures.len() < vault.required {
+                    return Err(Error::MissingSignature(index));
+                }
+                Ok(vault_recover_signature_script(redeem_script, &signatures)?)
+            })
+            .collect()
+    })
+}
+
+fn htlc_input(input: &Input) -> Result<(HtlcScript, Vec<u8>), Error> {
+    let redeem_script = input.redeem_script.clone().ok_or(Error::MissingRedeemScript)?;
+    let htlc = HtlcScript::from_redeem_script(&redeem_script).ok_or(Error::TemplateMismatch("htlc"))?;
+    Ok((htlc, redeem_script))
+}
+
+fn vault_input(input: &Input) -> Result<(VaultScript, Vec<u8>), Error> {
+    let redeem_script = input.redeem_script.clone().ok_or(Error::MissingRedeemScript)?;
+    let vault = VaultScript::from_redeem_script(&redeem_script).ok_or(Error::TemplateMismatch("vault"))?;
+    Ok((vault, redeem_script))
+}
+
+/// Returns the partial signature of the given x-only public key followed by the input sighash type
+fn input_signature(index: usize, input: &Input, key: &[u8; 32]) -> Result<Vec<u8>, Error> {
+    input
+        .partial_sigs
+        .iter()
+        .find(|(pub_key, _)| &pub_key.x_only_public_key().0.serialize() == key)
+        .map(|(_, signature)| signature.into_bytes().into_iter().chain([input.sighash_type.to_u8()]).collect())
+        .ok_or(Error::MissingSignature(index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use kaspa_consensus_core::{
+        config::params::TESTNET_PARAMS,
+        hashing::sighash::{SigHashReusedValuesUnsync, calc_schnorr_signature_hash},
+        tx::TransactionId,
+    };
+    use kaspa_txscript::{HTLC_PREIMAGE_SIZE, pay_to_script_hash_script};
+    use secp256k1::{Keypair, rand::thread_rng};
+    use sha2::{Digest, Sha256};
+
+    fn sign(pskt: PSKT<Signer>, kp: &Keypair) -> PSKT<Signer> {
+        let reused_values = SigHashReusedValuesUnsync::new();
+        pskt.pass_signature_sync(|tx, sighash| -> Result<Vec<SignInputOk>, String> {
+            (0..tx.tx.inputs.len())
+                .map(|idx| {
+                    let hash = calc_schnorr_signature_hash(&tx.as_verifiable(), idx, sighash[idx], &reused_values);
+                    let msg = secp256k1::Message::from_digest_slice(hash.as_bytes().as_slice()).unwrap();
+                    Ok(SignInputOk { signature: Signature::Schnorr(kp.sign_schnorr(msg)), pub_key: kp.public_key(), key_source: None })
+                })
+                .collect()
+        })
+        .unwrap()
+    }
+
+    fn entry(redeem_script: &[u8]) -> (UtxoEntry, TransactionOutpoint) {
+        let entry = UtxoEntry::new(100_000_000, pay_to_script_hash_script(redeem_script), 0, false, None);
+        (entry, TransactionOutpoint::new(TransactionId::from_bytes([1; 32]), 0))
+    }
+
+    #[test]
+    fn test_htlc_spends() {
+        let [receiver, refund] = std::array::from_fn(|_| Keypair::new(secp256k1::SECP256K1, &mut thread_rng()));
+        let preimage = [3u8; HTLC_PREIMAGE_SIZE];
+        let htlc = HtlcScript::new(
+            Sha256::digest(preimage).into(),
+            receiver.x_only_public_key().0.serialize(),
+            refund.x_only_public_key().0.serialize(),
+            500,
+        );
+        let redeem_script = htlc.redeem_script().unwrap();
+        let (utxo_entry, outpoint) = entry(&redeem_script);
+        let destination = ScriptPublicKey::new(0, vec![].into());
+
+        let claim = htlc_claim(utxo_entry.clone(), outpoint, redeem_script.clone(), destination.clone(), 1000).unwrap();
+        let finalized = finalize_htlc_claim(sign(claim.signer(), &receiver).finalizer(), &preimage).unwrap();
+        let tx = finalized.extractor().unwrap().extract_tx(&TESTNET_PARAMS).unwrap().tx;
+        assert_eq!(tx.lock_time, 0);
+
+        let refund_pskt = htlc_refund(utxo_entry.clone(), outpoint, redeem_script.clone(), destination.clone(), 1000).unwrap();
+        // the receiver signature does not unlock the refund path
+        assert!(matches!(
+            finalize_htlc_refund(sign(refund_pskt.clone().signer(), &receiver).finalizer()),
+            Err(FinalizeError::FinalaziCb(Error::MissingSignature(0)))
+        ));
+        let finalized = finalize_htlc_refund(sign(refund_pskt.signer(), &refund).finalizer()).unwrap();
+        let tx = finalized.extractor().unwrap().extract_tx(&TESTNET_PARAMS).unwrap().tx;
+        assert_eq!(tx.lock_time, 500);
+        assert_eq!(tx.inputs[0].sequence, 0);
+
+        assert!(matches!(
+            vault_recover(utxo_entry, outpoint, redeem_script, destination, 1000),
+            Err(Error::TemplateMismatch("vault"))
+        ));
+    }
+
+    #[test]
+    fn test_vault_spends() {
+        let [owner, recovery1, recovery2] = std::array::from_fn(|_| Keypair::new(secp256k1::SECP256K1, &mut thread_rng()));
+        let recovery_keys = [recovery1, recovery2].iter().map(|kp| kp.x_only_public_key().0.serialize()).collect();
+        let vault = VaultScript::new(owner.x_only_public_key().0.serialize(), 1000, recovery_keys, 1);
+        let redeem_script = vault.redeem_script().unwrap();
+        let (utxo_entry, outpoint) = entry(&redeem_script);
+        let destination = ScriptPublicKey::new(0, vec![].into());
+
+        let withdraw = vault_withdraw(utxo_entry.clone(), outpoint, redeem_script.clone(), destination.clone(), 1000).unwrap();
+        let finalized = finalize_vault_withdraw(sign(withdraw.signer(), &owner).finalizer()).unwrap();
+        let tx = finalized.extractor().unwrap().extract_tx(&TESTNET_PARAMS).unwrap().tx;
+        assert_eq!(tx.inputs[0].sequence, 1000);
+
+        let recover = vault_recover(utxo_entry, outpoint, redeem_script, destination, 1000).unwrap();
+        let finalized = finalize_vault_recover(sign(recover.signer(), &recovery2).finalizer()).unwrap();
+        finalized.extractor().unwrap().extract_tx(&TESTNET_PARAMS).unwrap();
+    }
+}
@@ -33,6 +33,17 @@ impl PSKB {
         self.0.serialize().map_err(Error::from)
     }
 
+    #[wasm_bindgen(js_name = "toBase64")]
+    pub fn to_base64(&self) -> String {
+        self.0.to_base64()
+    }
+
+    #[wasm_bindgen(js_name = "fromBase64")]
+    pub fn from_base64(data: &str) -> Result<PSKB> {
+        let bundle = Inner::from_base64(data).map_err(Error::from)?;
+        Ok(PSKB(bundle))
+    }
+
     #[wasm_bindgen(js_name = "displayFormat")]
     pub fn display_format(&self, network_id: &NetworkIdT) -> Result<String> {
         let network_id = NetworkId::try_cast_from(network_id).map_err(|err| Error::Custom(err.to_string()))?.into_owned();
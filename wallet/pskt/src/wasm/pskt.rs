@@ -76,8 +76,10 @@ impl<T> TryFrom<Payload> for Native<T> {
 
     fn try_from(value: Payload) -> Result<Self> {
         let Payload { data } = value;
-        if data.starts_with("PSKT") {
-            unimplemented!("PSKT binary serialization")
+        if data.starts_with(crate::binary::PSKT_BASE64_PREFIX) {
+            Ok(Native::from_base64(&data)?)
+        } else if data.starts_with("PSKT") {
+            Ok(Native::from_hex(&data)?)
         } else {
             Ok(serde_json::from_str(&data).map_err(|err| format!("Invalid JSON: {err}"))?)
         }